@@ -132,10 +132,11 @@ fn test_marlin() {
 
 #[test]
 fn test_bulletproofs() {
-    use zkp_toolkit::bulletproofs::create_random_proof;
+    use zkp_toolkit::bulletproofs::{prove, setup};
 
     let num = 10;
     let rng = &mut test_rng(); // Only in test code.
+    let gens = setup::<E, _>(num as usize, rng);
 
     println!("Bulletproofs proving...");
 
@@ -146,11 +147,9 @@ fn test_bulletproofs() {
         num: num,
     };
 
-    let (gens, r1cs, proof) = create_random_proof::<E, _, _>(c, rng).unwrap();
-    // let gens_bytes = postcard::to_allocvec(&gens).unwrap();
-    // let r1cs_bytes = postcard::to_allocvec(&r1cs).unwrap();
-    // let proof_bytes = postcard::to_allocvec(&proof).unwrap();
-    let proof_bytes = postcard::to_allocvec(&(gens, r1cs, proof)).unwrap();
+    let (_shape, proof) = prove(&gens, c, rng).unwrap();
+    let gens_bytes = postcard::to_allocvec(&gens).unwrap();
+    let proof_bytes = postcard::to_allocvec(&proof).unwrap();
     let public_bytes = postcard::to_allocvec(&vec![Fr::from(10u32)]).unwrap();
 
     println!("Bulletproofs verifying...");
@@ -158,7 +157,7 @@ fn test_bulletproofs() {
     println!("Bulletproofs verifying on CKB...");
 
     proving_test(
-        Default::default(),
+        gens_bytes.into(),
         proof_bytes.into(),
         public_bytes.into(),
         "mini_bulletproofs_verifier",
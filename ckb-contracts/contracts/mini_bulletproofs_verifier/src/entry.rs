@@ -7,7 +7,7 @@ use crate::error::Error;
 
 use zkp_toolkit::{
     bn_256::{Bn_256 as E, Fr},
-    bulletproofs::{verify_proof, Generators, Proof, R1csCircuit},
+    bulletproofs::{synthesize_shape, verify, Generators, Proof},
     math::PrimeField,
     r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError},
 };
@@ -47,8 +47,8 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for Mini<F> {
 }
 
 pub fn main() -> Result<(), Error> {
-    // load verify key.
-    let _vk_data = match load_cell_data(0, Source::Output) {
+    // load the generators.
+    let gens_data = match load_cell_data(0, Source::Output) {
         Ok(data) => data,
         Err(err) => return Err(err.into()),
     };
@@ -65,19 +65,20 @@ pub fn main() -> Result<(), Error> {
         Err(err) => return Err(err.into()),
     };
 
-    let (gens, r1cs, proof): (Generators<E>, R1csCircuit<E>, Proof<E>) =
-        postcard::from_bytes(&proof_data).map_err(|_e| Error::Encoding)?;
+    let gens: Generators<E> = postcard::from_bytes(&gens_data).map_err(|_e| Error::Encoding)?;
+    let proof: Proof<E> = postcard::from_bytes(&proof_data).map_err(|_e| Error::Encoding)?;
     let publics: Vec<Fr> = postcard::from_bytes(&public_data).map_err(|_e| Error::Encoding)?;
 
     // Demo circuit
-    let _c = Mini::<Fr> {
+    let c = Mini::<Fr> {
         x: None,
         y: None,
         z: None,
         num: 10,
     };
+    let shape = synthesize_shape::<E, _>(c).map_err(|_e| Error::Verify)?;
 
-    match verify_proof(&gens, &proof, &r1cs, &publics) {
+    match verify(&gens, &shape, &proof, &publics) {
         Ok(true) => Ok(()),
         _ => Err(Error::Verify),
     }
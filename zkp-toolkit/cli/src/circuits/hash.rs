@@ -1,48 +1,119 @@
-use zkp_toolkit::gadgets::mimc::{hash, mimc};
-use zkp_toolkit::math::PrimeField;
-use zkp_toolkit::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use std::marker::PhantomData;
 
-use super::{CliCircuit, Publics};
+use zkp_toolkit::gadgets::abstract_hash::{AbstractHash, AbstractHashFrOutput};
+use zkp_toolkit::gadgets::mimc::AbstractHashMimc;
+use zkp_toolkit::gadgets::native;
+use zkp_toolkit::gadgets::poseidon::AbstractHashPoseidon;
+use zkp_toolkit::gadgets::rescue::AbstractHashRescue;
+use zkp_toolkit::gadgets::uint8::{bytes_to_bits_le, UInt8};
+use zkp_toolkit::math::{FromBytes, PrimeField};
+use zkp_toolkit::r1cs::{ConstraintSynthesizer, ConstraintSystem, PublicInputs, SynthesisError};
 
-pub struct Hash<F: PrimeField> {
-    image: Option<Vec<u8>>,
-    _f: std::marker::PhantomData<F>,
+use super::{pack_bits_le, CliCircuit, Publics};
+
+/// The preimage is zero-padded to the 32 bytes of one block, the last byte
+/// stays zero so the block reads as a field element below the modulus.
+const PREIMAGE_BYTES: usize = 31;
+
+/// A hash into a field element, its gadget and its native counterpart.
+pub trait FieldHash<F: PrimeField> {
+    type Gadget: AbstractHash<F, Output = AbstractHashFrOutput<F>>;
+
+    fn hash(elements: &[F]) -> F;
+}
+
+pub struct Mimc;
+pub struct Poseidon;
+pub struct Rescue;
+
+impl<F: PrimeField> FieldHash<F> for Mimc {
+    type Gadget = AbstractHashMimc<F>;
+
+    fn hash(elements: &[F]) -> F {
+        native::mimc_hash(elements)
+    }
+}
+
+impl<F: PrimeField> FieldHash<F> for Poseidon {
+    type Gadget = AbstractHashPoseidon<F>;
+
+    fn hash(elements: &[F]) -> F {
+        native::poseidon_hash(elements)
+    }
 }
 
-impl<F: PrimeField> ConstraintSynthesizer<F> for Hash<F> {
+impl<F: PrimeField> FieldHash<F> for Rescue {
+    type Gadget = AbstractHashRescue<F>;
+
+    fn hash(elements: &[F]) -> F {
+        native::rescue_hash(elements)
+    }
+}
+
+/// The field element of the zero-padded `preimage`, as the circuit packs it.
+fn block<F: PrimeField>(preimage: &[u8]) -> F {
+    let mut block = preimage.to_vec();
+    block.push(0);
+    F::from_repr(F::BigInt::read(&block[..]).unwrap())
+}
+
+/// The preimage of a hash of `H`, MiMC by default.
+pub struct Hash<F: PrimeField, H = Mimc> {
+    preimage: Option<Vec<u8>>,
+    _f: PhantomData<(F, H)>,
+}
+
+impl<F: PrimeField, H: FieldHash<F>> ConstraintSynthesizer<F> for Hash<F, H> {
     fn generate_constraints<CS: ConstraintSystem<F>>(
         self,
         cs: &mut CS,
     ) -> Result<(), SynthesisError> {
-        let value = if let Some(bytes) = self.image {
-            mimc(cs.ns(|| "mimc-gadget"), Some(&bytes))?
-        } else {
-            mimc(cs.ns(|| "mimc-gadget"), None)?
+        let preimage = match self.preimage {
+            Some(bytes) => bytes.into_iter().map(Some).collect(),
+            None => vec![None; PREIMAGE_BYTES],
         };
+        let bytes = UInt8::alloc_vec(cs.ns(|| "preimage"), &preimage)?;
+
+        // the hash of the block, whose last byte is zero and so does not
+        // take part in the packing.
+        let block = pack_bits_le(cs.ns(|| "block"), &bytes_to_bits_le(&bytes))?;
 
-        let _ = cs.alloc_input(
-            || "image",
-            || value.ok_or(SynthesisError::AssignmentMissing),
-        )?;
+        let image = H::Gadget::hash_enforce(cs.ns(|| "hash"), &[&block.into()])?;
+        image.to_allocated_fr().inputize(cs.ns(|| "image"))?;
 
         Ok(())
     }
 }
 
-impl<F: PrimeField> CliCircuit<F> for Hash<F> {
+impl<F: PrimeField, H: FieldHash<F>> PublicInputs<F> for Hash<F, H> {
+    fn public_inputs(&self) -> Vec<F> {
+        self.preimage
+            .iter()
+            .map(|preimage| H::hash(&[block(preimage)]))
+            .collect()
+    }
+}
+
+impl<F: PrimeField, H: FieldHash<F>> CliCircuit<F> for Hash<F, H> {
     fn power_off() -> Self {
         Hash {
-            image: None,
+            preimage: None,
             _f: Default::default(),
         }
     }
 
     fn power_on(args: &[String]) -> (Self, Publics<F>) {
-        let image_bytes = args[0].as_bytes();
-        let hash_image = hash(image_bytes);
+        let mut preimage = args[0].as_bytes().to_vec();
+        assert!(
+            preimage.len() <= PREIMAGE_BYTES,
+            "the image string is at most 31 bytes"
+        );
+        preimage.resize(PREIMAGE_BYTES, 0);
+
+        let hash_image = H::hash(&[block(&preimage)]);
         (
             Hash {
-                image: Some(image_bytes.to_vec()),
+                preimage: Some(preimage),
                 _f: Default::default(),
             },
             Publics::Hash(hash_image),
@@ -53,3 +124,83 @@ impl<F: PrimeField> CliCircuit<F> for Hash<F> {
         "[image string]".to_owned()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use zkp_toolkit::bn_256::Fr;
+    use zkp_toolkit::test_cs::TestConstraintSystem;
+
+    use super::*;
+
+    /// The image the circuit inputizes is the native MiMC hash of the
+    /// zero-padded bytes.
+    #[test]
+    fn test_image_of_bytes() {
+        use zkp_toolkit::gadgets::mimc::{hash, MimcParameters};
+
+        for preimage in [&b""[..], b"iamsecret", &[0xffu8; PREIMAGE_BYTES]].iter() {
+            let mut padded = preimage.to_vec();
+            padded.resize(PREIMAGE_BYTES, 0);
+            let c = Hash::<Fr> {
+                preimage: Some(padded.clone()),
+                _f: Default::default(),
+            };
+            padded.push(0);
+            let image = hash::<Fr>(&padded, &MimcParameters::shared());
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            c.generate_constraints(&mut cs).unwrap();
+            assert!(cs.is_satisfied());
+            assert!(cs.verify(&[image]));
+            assert!(!cs.verify(&[image + Fr::from(1u32)]));
+        }
+    }
+
+    #[test]
+    fn test_namespace_report() {
+        let (c, publics) = Hash::<Fr>::power_on(&["iamsecret".to_owned()]);
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        c.generate_constraints(&mut cs).unwrap();
+        assert!(cs.is_satisfied());
+        match publics {
+            Publics::Hash(image) => assert!(cs.verify(&[image])),
+            _ => unreachable!(),
+        }
+
+        let hash = cs.hash();
+        let report = cs.namespace_report(2);
+
+        // the zero and the two constraints of each round of one block take
+        // the most, the 248 bits of the preimage come next.
+        assert_eq!(report[0], ("hash/mimc_hash".to_owned(), 1 + 644, 1 + 644));
+        assert_eq!(
+            report.iter().map(|r| r.1).sum::<usize>(),
+            cs.num_constraints()
+        );
+        assert!(cs.pretty_report(2).contains("hash/mimc_hash"));
+
+        // the report does not renumber the variables.
+        assert_eq!(cs.hash(), hash);
+    }
+
+    #[test]
+    fn test_bulletproofs_proof_size() {
+        use zkp_toolkit::bn_256::Bn_256;
+        use zkp_toolkit::bulletproofs::{
+            prove_with_shape, setup, synthesize_shape, verify_circuit,
+        };
+
+        let rng = &mut zkp_toolkit::math::test_rng();
+        let shape = synthesize_shape::<Bn_256, _>(Hash::<Fr>::power_off()).unwrap();
+        let gens = setup::<Bn_256, _>(shape.generators_size(), rng);
+
+        let (c, publics) = Hash::<Fr>::power_on(&["iamsecret".to_owned()]);
+        let proof = prove_with_shape(&gens, &shape, c, rng).unwrap();
+        assert!(verify_circuit(&gens, Hash::<Fr>::power_off(), &proof, &publics.inputs()).unwrap());
+
+        // the proof has the digest of the constraints, not the constraints.
+        let proof_bytes = postcard::to_allocvec(&proof).unwrap();
+        let r1cs_bytes = postcard::to_allocvec(&shape.r1cs_circuit).unwrap();
+        assert!(proof_bytes.len() * 10 < r1cs_bytes.len());
+    }
+}
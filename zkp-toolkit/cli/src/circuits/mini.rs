@@ -1,5 +1,5 @@
 use zkp_toolkit::math::PrimeField;
-use zkp_toolkit::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::r1cs::{ConstraintSynthesizer, ConstraintSystem, PublicInputs, SynthesisError};
 
 use super::{CliCircuit, Publics};
 
@@ -37,6 +37,12 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for Mini<F> {
     }
 }
 
+impl<F: PrimeField> PublicInputs<F> for Mini<F> {
+    fn public_inputs(&self) -> Vec<F> {
+        self.z.into_iter().collect()
+    }
+}
+
 impl<F: PrimeField> CliCircuit<F> for Mini<F> {
     fn power_off() -> Self {
         Mini {
@@ -76,3 +82,41 @@ impl<F: PrimeField> CliCircuit<F> for Mini<F> {
         "[x] [y] [z]".to_owned()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use zkp_toolkit::bn_256::Fr;
+    use zkp_toolkit::test_cs::{dump, DumpOptions};
+
+    use super::*;
+
+    fn dump_mini(c: Mini<Fr>, values: bool) -> String {
+        let mut out = vec![];
+        let options = DumpOptions {
+            namespace: String::new(),
+            values,
+        };
+        dump(c, &mut out, &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_dump() {
+        let line = "x * (y + 2) = z: (x) * (y + 2) = (z(output))\n";
+        assert_eq!(dump_mini(Mini::power_off(), false), line.repeat(10));
+        assert_eq!(
+            dump_mini(Mini::power_off(), true),
+            "x * (y + 2) = z: (?) * (? + 2) = (?)\n".repeat(10)
+        );
+
+        let args = ["2", "3", "10"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>();
+        let (c, _) = Mini::power_on(&args);
+        assert_eq!(
+            dump_mini(c, true),
+            "x * (y + 2) = z: (2) * (3 + 2) = (10)\n".repeat(10)
+        );
+    }
+}
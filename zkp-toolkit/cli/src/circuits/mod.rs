@@ -1,18 +1,109 @@
-use zkp_toolkit::math::Field;
-use zkp_toolkit::r1cs::ConstraintSynthesizer;
+use zkp_toolkit::gadgets::boolean::Boolean;
+use zkp_toolkit::gadgets::fr::AllocatedFr;
+use zkp_toolkit::math::{Field, PrimeField};
+use zkp_toolkit::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, LinearCombination, PublicInputs, SynthesisError,
+};
 
+/// The public parameters of a proof file, of which the verifier computes
+/// the public inputs.
 pub enum Publics<F: Field> {
     Mini(u64),
     Hash(F),
+    Sha256(Vec<u8>),
+    /// The bits of the range and the coordinates of the commitment.
+    Range(usize, F, F),
 }
 
-pub trait CliCircuit<F: Field>: Sized + ConstraintSynthesizer<F> {
+impl<F: PrimeField> Publics<F> {
+    /// The public inputs of the parameters, as the circuit allocates them.
+    pub fn inputs(&self) -> Vec<F> {
+        match self {
+            Publics::Mini(z) => vec![F::from_repr(F::BigInt::from(*z))],
+            Publics::Hash(image) => vec![*image],
+            Publics::Sha256(image) => sha256::image_publics(image),
+            Publics::Range(_, x, y) => vec![*x, *y],
+        }
+    }
+}
+
+pub trait CliCircuit<F: Field>: Sized + ConstraintSynthesizer<F> + PublicInputs<F> {
     fn power_off() -> Self;
 
     fn power_on(args: &[String]) -> (Self, Publics<F>);
 
+    /// The circuit without witnesses of the shape of this one, for the
+    /// circuits whose arguments change their constraints.
+    fn power_off_like(&self) -> Self {
+        Self::power_off()
+    }
+
     fn options() -> String;
 }
 
+/// Allocates the number of the little-endian `bits`, which must be fewer
+/// than the capacity of the field.
+fn pack_bits_le<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    bits: &[Boolean],
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    assert!(bits.len() < F::size_in_bits());
+
+    let num = AllocatedFr::alloc(cs.ns(|| "num"), || {
+        bits.iter().rev().try_fold(F::zero(), |acc, b| {
+            let bit = b.get_value().ok_or(SynthesisError::AssignmentMissing)?;
+            Ok(if bit {
+                acc.double() + F::one()
+            } else {
+                acc.double()
+            })
+        })
+    })?;
+
+    let mut lc = LinearCombination::zero();
+    let mut coeff = F::one();
+    for bit in bits {
+        lc = lc + &bit.lc(CS::one(), coeff);
+        coeff = coeff.double();
+    }
+    cs.enforce(
+        || "packing",
+        |_| lc,
+        |lc| lc + CS::one(),
+        |lc| lc + num.get_variable(),
+    );
+
+    Ok(num)
+}
+
 pub mod hash;
 pub mod mini;
+pub mod range;
+pub mod sha256;
+
+#[cfg(test)]
+mod test {
+    use zkp_toolkit::bn_256::Fr;
+    use zkp_toolkit::r1cs::assert_public_inputs_match;
+
+    use super::*;
+
+    /// The inputs the verifier computes of the proof file are those the
+    /// circuit allocates, and those of the circuit itself.
+    fn check_publics<C: CliCircuit<Fr>>(args: &[&str]) {
+        let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        let (c, publics) = C::power_on(&args);
+        assert_eq!(publics.inputs(), c.public_inputs());
+        assert_public_inputs_match(c, &publics.inputs());
+    }
+
+    #[test]
+    fn test_publics() {
+        check_publics::<mini::Mini<Fr>>(&["2", "3", "10"]);
+        check_publics::<hash::Hash<Fr>>(&["iamsecret"]);
+        check_publics::<hash::Hash<Fr, hash::Poseidon>>(&["iamsecret"]);
+        check_publics::<hash::Hash<Fr, hash::Rescue>>(&["iamsecret"]);
+        check_publics::<sha256::Sha256<Fr>>(&["iamsecret"]);
+        check_publics::<range::Range<zkp_toolkit::bn_256::Bn_256>>(&["42", "64"]);
+    }
+}
@@ -0,0 +1,97 @@
+use zkp_toolkit::bls12_377::Bls12_377;
+use zkp_toolkit::bls12_381::Bls12_381;
+use zkp_toolkit::bn_256::Bn_256;
+use zkp_toolkit::circuits::committed_range::{commitment, randomness_bits, RangeCircuit};
+use zkp_toolkit::math::{curves::models::TEModelParameters, Curve, PrimeField};
+
+use super::{CliCircuit, Publics};
+
+/// The range of the setup of `range`, the one of a u64.
+pub const RANGE_BITS: usize = 64;
+
+/// The twisted Edwards curve of the commitments of a curve, embedded in its
+/// scalar field.
+pub trait EmbeddedCurve: Curve {
+    type Edwards: TEModelParameters<BaseField = Self::Fr>;
+}
+
+impl EmbeddedCurve for Bn_256 {
+    type Edwards = zkp_toolkit::baby_jubjub::EdwardsParameters;
+}
+
+impl EmbeddedCurve for Bls12_381 {
+    type Edwards = zkp_toolkit::jubjub::JubJubParameters;
+}
+
+impl EmbeddedCurve for Bls12_377 {
+    type Edwards = zkp_toolkit::edwards_bls12::EdwardsParameters;
+}
+
+/// The range proof of a value committed over the embedded curve of `E`.
+pub type Range<E> = RangeCircuit<<E as EmbeddedCurve>::Edwards>;
+
+impl<P> CliCircuit<P::BaseField> for RangeCircuit<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    fn power_off() -> Self {
+        RangeCircuit::blank(RANGE_BITS)
+    }
+
+    fn power_on(args: &[String]) -> (Self, Publics<P::BaseField>) {
+        let value = args[0]
+            .as_str()
+            .parse::<u64>()
+            .expect("Interger parse error");
+        let n_bits = args
+            .get(1)
+            .map(|n| n.as_str().parse::<usize>().expect("Interger parse error"))
+            .unwrap_or(RANGE_BITS);
+        assert!(n_bits > 0 && n_bits <= 64, "the range is of 1 to 64 bits");
+
+        let rng = &mut rand::thread_rng();
+        let randomness: Vec<bool> = (0..randomness_bits::<P>())
+            .map(|_| rand::Rng::gen(rng))
+            .collect();
+        let c = commitment::<P>(value, n_bits, &randomness);
+
+        (
+            RangeCircuit::new(value, randomness, n_bits),
+            Publics::Range(n_bits, c.x, c.y),
+        )
+    }
+
+    fn power_off_like(&self) -> Self {
+        RangeCircuit::blank(self.n_bits())
+    }
+
+    fn options() -> String {
+        "[value] [bits, 64 by default]".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use zkp_toolkit::bn_256::Fr;
+    use zkp_toolkit::r1cs::debug_satisfied;
+
+    use super::*;
+
+    #[test]
+    fn test_range_bits() {
+        let args = ["42".to_owned(), "8".to_owned()];
+        let (c, publics) = Range::<Bn_256>::power_on(&args);
+        assert_eq!(c.power_off_like().n_bits(), 8);
+        assert!(matches!(publics, Publics::Range(8, _, _)));
+        assert_eq!(debug_satisfied(c).unwrap(), None);
+
+        // 300 is out of the range of 8 bits.
+        let args = ["300".to_owned(), "8".to_owned()];
+        let (c, _) = Range::<Bn_256>::power_on(&args);
+        assert_eq!(
+            debug_satisfied::<Fr, _>(c).unwrap().unwrap().path,
+            "packing"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use zkp_toolkit::gadgets::multipack::{self, compute_multipacking, pack_into_inputs};
+use zkp_toolkit::gadgets::sha256::sha256;
+use zkp_toolkit::gadgets::uint8::{bits_to_bytes_be, bytes_to_bits_be, bytes_to_bits_le, UInt8};
+use zkp_toolkit::math::PrimeField;
+use zkp_toolkit::r1cs::{ConstraintSynthesizer, ConstraintSystem, PublicInputs, SynthesisError};
+
+use super::{CliCircuit, Publics};
+
+/// The preimage is zero-padded to 32 bytes.
+const PREIMAGE_BYTES: usize = 32;
+
+pub struct Sha256<F: PrimeField> {
+    preimage: Option<Vec<u8>>,
+    _f: std::marker::PhantomData<F>,
+}
+
+/// The public inputs of a sha256 image, its little-endian bits multipacked.
+pub fn image_publics<F: PrimeField>(image: &[u8]) -> Vec<F> {
+    compute_multipacking(&multipack::bytes_to_bits_le(image))
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for Sha256<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let preimage = match self.preimage {
+            Some(bytes) => bytes.into_iter().map(Some).collect(),
+            None => vec![None; PREIMAGE_BYTES],
+        };
+        let bytes = UInt8::alloc_vec(cs.ns(|| "preimage"), &preimage)?;
+
+        let image = sha256(cs.ns(|| "sha256-gadget"), &bytes_to_bits_be(&bytes))?;
+        let image = bits_to_bytes_be(&image);
+
+        pack_into_inputs(cs.ns(|| "image"), &bytes_to_bits_le(&image))?;
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> PublicInputs<F> for Sha256<F> {
+    fn public_inputs(&self) -> Vec<F> {
+        use sha2::Digest;
+
+        self.preimage
+            .iter()
+            .flat_map(|preimage| image_publics(&sha2::Sha256::digest(preimage)))
+            .collect()
+    }
+}
+
+impl<F: PrimeField> CliCircuit<F> for Sha256<F> {
+    fn power_off() -> Self {
+        Sha256 {
+            preimage: None,
+            _f: Default::default(),
+        }
+    }
+
+    fn power_on(args: &[String]) -> (Self, Publics<F>) {
+        use sha2::Digest;
+
+        let mut preimage = args[0].as_bytes().to_vec();
+        assert!(
+            preimage.len() <= PREIMAGE_BYTES,
+            "the image string is at most 32 bytes"
+        );
+        preimage.resize(PREIMAGE_BYTES, 0);
+
+        let image = sha2::Sha256::digest(&preimage).to_vec();
+        (
+            Sha256 {
+                preimage: Some(preimage),
+                _f: Default::default(),
+            },
+            Publics::Sha256(image),
+        )
+    }
+
+    fn options() -> String {
+        "[image string]".to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use sha2::Digest;
+    use zkp_toolkit::bn_256::Fr;
+    use zkp_toolkit::test_cs::TestConstraintSystem;
+
+    use super::*;
+
+    /// The image the circuit inputizes is the `sha2` digest of the bytes.
+    #[test]
+    fn test_image_of_bytes() {
+        let mut preimages = vec![vec![0u8; PREIMAGE_BYTES], b"iamsecret".to_vec()];
+        preimages[1].resize(PREIMAGE_BYTES, 0);
+
+        for preimage in preimages {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            Sha256::<Fr> {
+                preimage: Some(preimage.clone()),
+                _f: Default::default(),
+            }
+            .generate_constraints(&mut cs)
+            .unwrap();
+            assert!(cs.is_satisfied());
+
+            let digest = sha2::Sha256::digest(&preimage);
+            assert!(cs.verify(&image_publics::<Fr>(&digest)));
+            assert!(!cs.verify(&image_publics::<Fr>(&sha2::Sha256::digest(b"iamsecret"))));
+        }
+
+        // the digest of 32 zero bytes.
+        let digest = sha2::Sha256::digest(&[0u8; 32]);
+        assert_eq!(
+            digest[..],
+            [
+                0x66, 0x68, 0x7a, 0xad, 0xf8, 0x62, 0xbd, 0x77, 0x6c, 0x8f, 0xc1, 0x8b, 0x8e, 0x9f,
+                0x8e, 0x20, 0x08, 0x97, 0x14, 0x85, 0x6e, 0xe2, 0x33, 0xb3, 0x90, 0x2a, 0x59, 0x1d,
+                0x0d, 0x5f, 0x29, 0x25,
+            ][..]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use zkp_toolkit::bls12_377::Bls12_377;
+use zkp_toolkit::bls12_381::Bls12_381;
+use zkp_toolkit::bn_256::Bn_256;
+use zkp_toolkit::math::Curve;
+use zkp_toolkit::r1cs::ConstraintSynthesizer;
+use zkp_toolkit::spartan::{nizk, snark};
+
+/// The public label the deterministic spartan parameters are derived from.
+pub const SPARTAN_LABEL: &'static [u8] = b"ZKP-TOOLKIT-CLI-SPARTAN-V1";
+
+/// Curves whose spartan parameters can be derived from `SPARTAN_LABEL`, so
+/// that no universal setup file has to be shipped.
+pub trait DeterministicSpartan: Curve {
+    fn snark_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+        c: C,
+    ) -> Result<snark::Parameters<Self>, String>;
+
+    fn nizk_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+        c: C,
+    ) -> Result<nizk::Parameters<Self>, String>;
+}
+
+macro_rules! impl_deterministic_spartan {
+    ($curve:ident) => {
+        impl DeterministicSpartan for $curve {
+            fn snark_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+                c: C,
+            ) -> Result<snark::Parameters<Self>, String> {
+                snark::generate_deterministic_parameters(c, SPARTAN_LABEL)
+                    .map_err(|e| format!("{:?}", e))
+            }
+
+            fn nizk_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+                c: C,
+            ) -> Result<nizk::Parameters<Self>, String> {
+                nizk::generate_deterministic_parameters(c, SPARTAN_LABEL)
+                    .map_err(|e| format!("{:?}", e))
+            }
+        }
+    };
+}
+
+impl_deterministic_spartan!(Bn_256);
+impl_deterministic_spartan!(Bls12_381);
+
+impl DeterministicSpartan for Bls12_377 {
+    fn snark_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+        _c: C,
+    ) -> Result<snark::Parameters<Self>, String> {
+        Err("CURVE: bls12_377 has no hash to curve, use spartan_snark.".to_owned())
+    }
+
+    fn nizk_parameters<C: ConstraintSynthesizer<Self::Fr>>(
+        _c: C,
+    ) -> Result<nizk::Parameters<Self>, String> {
+        Err("CURVE: bls12_377 has no hash to curve, use spartan_nizk.".to_owned())
+    }
+}
@@ -0,0 +1,93 @@
+use std::env;
+use zkp_toolkit::math::Curve;
+use zkp_toolkit::r1cs::stats::{analyze, R1csStats};
+
+mod circuits;
+use circuits::CliCircuit;
+
+use circuits::hash::{Hash, Mimc, Poseidon, Rescue};
+use circuits::mini::Mini;
+use circuits::range::Range;
+use circuits::sha256::Sha256;
+
+macro_rules! handle_circuit {
+    ($curve:ident, $circuit:expr) => {
+        match $circuit {
+            "mini" => analyze(Mini::<<$curve as Curve>::Fr>::power_off()),
+            "hash" | "hash-mimc" => analyze(Hash::<<$curve as Curve>::Fr, Mimc>::power_off()),
+            "hash-poseidon" => analyze(Hash::<<$curve as Curve>::Fr, Poseidon>::power_off()),
+            "hash-rescue" => analyze(Hash::<<$curve as Curve>::Fr, Rescue>::power_off()),
+            "sha256" => analyze(Sha256::<<$curve as Curve>::Fr>::power_off()),
+            "range" => analyze(Range::<$curve>::power_off()),
+            _ => return Err(format!("CIRCUIT: {} not implement.", $circuit)),
+        }
+    };
+}
+
+fn print_stats(stats: &R1csStats) {
+    println!("constraints:     {}", stats.num_constraints);
+    println!("inputs:          {} (with one)", stats.num_inputs);
+    println!("aux variables:   {}", stats.num_aux);
+    println!(
+        "entries:         A {}, B {}, C {}",
+        stats.a_non_zeros, stats.b_non_zeros, stats.c_non_zeros
+    );
+    println!("max row density: {}", stats.max_row_density);
+    println!("");
+    println!("groth16 domain:  {}", stats.groth16.domain);
+    println!(
+        "marlin domains:  x {}, h {}, k {}",
+        stats.marlin.domain_x, stats.marlin.domain_h, stats.marlin.domain_k
+    );
+    println!(
+        "spartan sizes:   constraints {}, variables {}, entries {}",
+        stats.spartan.num_constraints, stats.spartan.num_variables, stats.spartan.num_non_zeros
+    );
+}
+
+fn main() -> Result<(), String> {
+    let args: Vec<_> = env::args().collect();
+    if args.len() < 3 {
+        println!("inspect");
+        println!("");
+        println!("Usage: inspect [CURVE] [CIRCUIT]");
+        println!("");
+        println!("CURVE:");
+        println!("    bn_256    -- BN_256 pairing curve.");
+        println!("    bls12_381 -- BLS12_381 pairing curve.");
+        println!("    bls12_377 -- BLS12_377 pairing curve.");
+        println!("");
+        println!("CIRCUIT:");
+        println!("    mini    -- Mini circuit. proof: x * (y + 2) = z.");
+        println!("    hash    -- Hash circuit. proof: mimc hash.");
+        println!("    hash-[mimc|poseidon|rescue] -- Hash circuit. proof: the hash.");
+        println!("    sha256  -- Hash circuit. proof: sha256 hash.");
+        println!("    range   -- Range circuit. proof: 0 <= value < 2^64, of a commitment of the value.");
+        println!("");
+
+        return Err("Params invalid!".to_owned());
+    }
+
+    let (curve, circuit) = (args[1].as_str(), args[2].as_str());
+
+    let stats = match curve {
+        "bn_256" => {
+            use zkp_toolkit::bn_256::Bn_256;
+            handle_circuit!(Bn_256, circuit)
+        }
+        "bls12_381" => {
+            use zkp_toolkit::bls12_381::Bls12_381;
+            handle_circuit!(Bls12_381, circuit)
+        }
+        "bls12_377" => {
+            use zkp_toolkit::bls12_377::Bls12_377;
+            handle_circuit!(Bls12_377, circuit)
+        }
+        _ => return Err(format!("Curve: {} not implement.", curve)),
+    }
+    .map_err(|e| format!("{}", e))?;
+
+    print_stats(&stats);
+
+    Ok(())
+}
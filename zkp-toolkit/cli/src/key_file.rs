@@ -0,0 +1,172 @@
+// each binary writes or reads the setup files, not both.
+#![allow(dead_code)]
+
+use std::path::Path;
+use zkp_toolkit::key_fingerprint::KeyFingerprint;
+
+/// The first bytes of every setup file of `setup` but the groth16 proving
+/// key, which has a header of its own.
+const MAGIC: &'static [u8; 8] = b"ZKPSETUP";
+
+/// The version of the header, bumped when its layout changes.
+const HEADER_VERSION: u8 = 1;
+
+/// The header of a setup file: the version of the header, the scheme and
+/// the curve the file is of, and the fingerprint of the verifying key, of
+/// none when the key is of the circuit and not of the file, as of the marlin
+/// universal setup.
+///
+/// `MAGIC`, then the version, the lengths and the ASCII of the names of the
+/// scheme and of the curve, and `1` and the fingerprint, or `0`.
+#[derive(Debug, PartialEq)]
+pub struct KeyHeader {
+    pub version: u8,
+    pub scheme: String,
+    pub curve: String,
+    pub fingerprint: Option<KeyFingerprint>,
+}
+
+impl KeyHeader {
+    pub fn new(scheme: &str, curve: &str, fingerprint: Option<KeyFingerprint>) -> Self {
+        KeyHeader {
+            version: HEADER_VERSION,
+            scheme: scheme.to_owned(),
+            curve: curve.to_owned(),
+            fingerprint,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(self.version);
+        for name in &[&self.scheme, &self.curve] {
+            bytes.push(name.len() as u8);
+            bytes.extend(name.as_bytes());
+        }
+        match self.fingerprint {
+            Some(fingerprint) => {
+                bytes.push(1);
+                bytes.extend(&fingerprint);
+            }
+            None => bytes.push(0),
+        }
+        bytes
+    }
+
+    /// The header of `bytes`, and the bytes after it.
+    pub fn split(bytes: &[u8]) -> Result<(Self, &[u8]), String> {
+        let invalid = || {
+            "not a setup file, or of a version before the setup headers: run setup again".to_owned()
+        };
+        if !bytes.starts_with(MAGIC) || bytes.len() == MAGIC.len() {
+            return Err(invalid());
+        }
+        let version = bytes[MAGIC.len()];
+        if version != HEADER_VERSION {
+            return Err(format!(
+                "setup file of version {}, not {}: run setup again",
+                version, HEADER_VERSION
+            ));
+        }
+
+        let mut rest = &bytes[MAGIC.len() + 1..];
+        let mut names = vec![];
+        for _ in 0..2 {
+            let len = *rest.first().ok_or_else(invalid)? as usize;
+            let name = rest.get(1..1 + len).ok_or_else(invalid)?;
+            names.push(String::from_utf8(name.to_vec()).map_err(|_| invalid())?);
+            rest = &rest[1 + len..];
+        }
+        let fingerprint = match rest.first() {
+            Some(0) => {
+                rest = &rest[1..];
+                None
+            }
+            Some(1) if rest.len() > 32 => {
+                let mut fingerprint = [0u8; 32];
+                fingerprint.copy_from_slice(&rest[1..33]);
+                rest = &rest[33..];
+                Some(fingerprint)
+            }
+            _ => return Err(invalid()),
+        };
+
+        let curve = names.pop().unwrap();
+        let scheme = names.pop().unwrap();
+        let header = KeyHeader {
+            version,
+            scheme,
+            curve,
+            fingerprint,
+        };
+        Ok((header, rest))
+    }
+
+    /// Fails telling both, when the file is of another scheme or curve.
+    pub fn expect(&self, scheme: &str, curve: &str) -> Result<(), String> {
+        if self.scheme != scheme || self.curve != curve {
+            return Err(format!(
+                "setup file is of {} over {}, not of {} over {}",
+                self.scheme, self.curve, scheme, curve
+            ));
+        }
+        Ok(())
+    }
+
+    /// Fails when the verifying key read of the file is not the one the
+    /// header tells, e.g. of a corrupted file.
+    pub fn check_fingerprint(&self, key: &KeyFingerprint) -> Result<(), String> {
+        match self.fingerprint {
+            Some(fingerprint) if fingerprint != *key => Err(format!(
+                "setup file has the key {}, not the key {} of its header",
+                to_hex(key),
+                to_hex(&fingerprint)
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Writes `body` after the header of `scheme`, `curve` and `fingerprint`.
+pub fn write_setup_file(
+    path: &Path,
+    scheme: &str,
+    curve: &str,
+    fingerprint: Option<KeyFingerprint>,
+    body: &[u8],
+) {
+    let mut bytes = KeyHeader::new(scheme, curve, fingerprint).to_bytes();
+    bytes.extend(body);
+    std::fs::write(path, bytes).unwrap();
+}
+
+/// Reads the header and the body of the setup file `path` of `scheme` and
+/// `curve`, failing clearly when it is of another scheme or curve.
+pub fn read_setup_file(
+    path: &Path,
+    scheme: &str,
+    curve: &str,
+) -> Result<(KeyHeader, Vec<u8>), String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{:?}: {}", path, e))?;
+    let (header, body) = KeyHeader::split(&bytes).map_err(|e| format!("{:?}: {}", path, e))?;
+    header
+        .expect(scheme, curve)
+        .map_err(|e| format!("{:?}: {}", path, e))?;
+    if let Some(fingerprint) = header.fingerprint {
+        println!("Setup file key fingerprint: {}", to_hex(&fingerprint));
+    }
+    let body = body.to_vec();
+    Ok((header, body))
+}
+
+/// The name of the bulletproofs generators file of `size`, of no circuit:
+/// every circuit of the size is proven of it.
+pub fn gens_file_name(curve: &str, size: usize) -> String {
+    format!("bulletproofs-{}-{}.gens", curve, size)
+}
+
+pub fn to_hex(v: &[u8]) -> String {
+    let mut s = String::with_capacity(v.len() * 2);
+    s.extend(v.iter().map(|b| format!("{:02x}", b)));
+    s
+}
@@ -1,12 +1,22 @@
 use std::env;
+use std::fs::File;
+use std::io::BufWriter;
 use std::path::PathBuf;
 use zkp_toolkit::math::Curve;
 
 mod circuits;
 use circuits::CliCircuit;
 
-use circuits::hash::Hash;
+mod deterministic;
+use deterministic::DeterministicSpartan;
+
+mod key_file;
+use key_file::{gens_file_name, to_hex, write_setup_file};
+
+use circuits::hash::{Hash, Mimc, Poseidon, Rescue};
 use circuits::mini::Mini;
+use circuits::range::Range;
+use circuits::sha256::Sha256;
 
 const SETUP_DIR: &'static str = "./setup_files";
 
@@ -17,8 +27,24 @@ macro_rules! handle_circuit {
                 let c = Mini::<<$curve as Curve>::Fr>::power_off();
                 handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
             }
-            "hash" => {
-                let c = Hash::<<$curve as Curve>::Fr>::power_off();
+            "hash" | "hash-mimc" => {
+                let c = Hash::<<$curve as Curve>::Fr, Mimc>::power_off();
+                handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
+            }
+            "hash-poseidon" => {
+                let c = Hash::<<$curve as Curve>::Fr, Poseidon>::power_off();
+                handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
+            }
+            "hash-rescue" => {
+                let c = Hash::<<$curve as Curve>::Fr, Rescue>::power_off();
+                handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
+            }
+            "sha256" => {
+                let c = Sha256::<<$curve as Curve>::Fr>::power_off();
+                handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
+            }
+            "range" => {
+                let c = Range::<$curve>::power_off();
                 handle_scheme!($curve, c, $curve_name, $scheme, $circuit);
             }
             _ => return Err(format!("CIRCUIT: {} not implement.", $circuit)),
@@ -33,23 +59,51 @@ macro_rules! handle_scheme {
             std::fs::create_dir_all(&vk_path).unwrap();
         }
         let rng = &mut rand::thread_rng();
-        let (vk_bytes, pk_bytes) = match $scheme {
+        let (vk_bytes, fingerprint, params) = match $scheme {
             "groth16" => {
-                use zkp_toolkit::groth16::generate_random_parameters;
+                use zkp_toolkit::groth16::{generate_random_parameters, prepare_verifying_key};
                 let params = generate_random_parameters::<$curve, _, _>($c, rng).unwrap();
                 let vk = postcard::to_allocvec(&params.vk).unwrap();
-                let pk = postcard::to_allocvec(&params).unwrap();
-                (vk, pk)
+                let fingerprint = params.vk.key_fingerprint();
+
+                // the prepared verify key, bigger but faster to verify with.
+                let pvk = prepare_verifying_key(&params.vk);
+                let pvk_name = format!("{}-{}-{}.pvk", $scheme, $curve_name, $circuit);
+                let mut pvk_path = vk_path.clone();
+                pvk_path.push(pvk_name.clone());
+                let pvk_bytes = postcard::to_allocvec(&pvk).unwrap();
+                write_setup_file(&pvk_path, $scheme, $curve_name, Some(fingerprint), &pvk_bytes);
+                println!("Prepared Verify Key: {}", pvk_name);
+
+                (vk, fingerprint, params)
+            }
+            "bulletproofs" => {
+                use zkp_toolkit::bulletproofs::{setup, synthesize_shape};
+                // the generators are of a size, not of the circuit.
+                let size = synthesize_shape::<$curve, _>($c).unwrap().generators_size();
+                let gens_name = gens_file_name($curve_name, size);
+                vk_path.push(gens_name.clone());
+                if vk_path.exists() {
+                    println!("Bulletproofs generators: {}, already set up.", gens_name);
+                    return Ok(());
+                }
+                let gens = setup::<$curve, _>(size, rng);
+                let gens_bytes = postcard::to_allocvec(&gens).unwrap();
+                write_setup_file(&vk_path, $scheme, $curve_name, None, &gens_bytes);
+                println!("Bulletproofs generators: {}", gens_name);
+                return Ok(());
             }
             "marlin" => {
-                use zkp_toolkit::marlin::universal_setup;
-                // set max circuit num: 2^16
-                let srs = universal_setup::<$curve, _>(2usize.pow(16), rng).unwrap();
+                use zkp_toolkit::marlin::{max_degree, universal_setup};
+                // just large enough for the circuit.
+                let degree = max_degree::<$curve, _>($c).unwrap();
+                let srs = universal_setup::<$curve, _>(degree, rng).unwrap();
                 let srs_bytes = postcard::to_allocvec(&srs).unwrap();
-                let vk_name = format!("{}-{}.universal_setup", $scheme, $curve_name);
+                let vk_name = format!("{}-{}-{}.universal_setup", $scheme, $curve_name, $circuit);
                 println!("Marlin universal setup: {}", vk_name);
                 vk_path.push(vk_name);
-                std::fs::write(vk_path, srs_bytes).unwrap();
+                // the verify key is of the index of the circuit, not of the file.
+                write_setup_file(&vk_path, $scheme, $curve_name, None, &srs_bytes);
                 return Ok(());
             }
             "spartan_snark" => {
@@ -61,7 +115,9 @@ macro_rules! handle_scheme {
                 //let hash_off = Hash::<<$curve as Curve>::Fr>::power_off();
                 let srs = generate_random_parameters::<$curve, _, _>($c, rng).unwrap();
                 let srs_bytes = postcard::to_allocvec(&srs).unwrap();
-                std::fs::write(vk_path, srs_bytes).unwrap();
+                let fingerprint = srs.keypair().1.key_fingerprint();
+                write_setup_file(&vk_path, $scheme, $curve_name, Some(fingerprint), &srs_bytes);
+                println!("Verify Key fingerprint: {}", to_hex(&fingerprint));
                 return Ok(());
             }
             "spartan_nizk" => {
@@ -73,7 +129,19 @@ macro_rules! handle_scheme {
                 //let hash_off = Hash::<<$curve as Curve>::Fr>::power_off();
                 let srs = generate_random_parameters::<$curve, _, _>($c, rng).unwrap();
                 let srs_bytes = postcard::to_allocvec(&srs).unwrap();
-                std::fs::write(vk_path, srs_bytes).unwrap();
+                let fingerprint = srs.keypair().1.key_fingerprint();
+                write_setup_file(&vk_path, $scheme, $curve_name, Some(fingerprint), &srs_bytes);
+                println!("Verify Key fingerprint: {}", to_hex(&fingerprint));
+                return Ok(());
+            }
+            "spartan_snark_deterministic" => {
+                <$curve as DeterministicSpartan>::snark_parameters($c)?;
+                println!("Spartan snark parameters are derived from a public label, no setup file needed.");
+                return Ok(());
+            }
+            "spartan_nizk_deterministic" => {
+                <$curve as DeterministicSpartan>::nizk_parameters($c)?;
+                println!("Spartan nizk parameters are derived from a public label, no setup file needed.");
                 return Ok(());
             }
             _ => return Err(format!("SCHEME: {} not implement.", $scheme)),
@@ -85,10 +153,13 @@ macro_rules! handle_scheme {
         vk_path.push(vk_name.clone());
         pk_path.push(pk_name.clone());
 
-        std::fs::write(pk_path, pk_bytes).unwrap();
-        std::fs::write(vk_path, vk_bytes).unwrap();
+        // streamed a section at a time, of no copy of the whole key in memory.
+        let pk_file = BufWriter::new(File::create(pk_path).unwrap());
+        params.write_streaming(pk_file).unwrap();
+        write_setup_file(&vk_path, $scheme, $curve_name, Some(fingerprint), &vk_bytes);
 
         println!("Prove Key: {}, Verify Key: {}, ", pk_name, vk_name);
+        println!("Verify Key fingerprint: {}", to_hex(&fingerprint));
     };
 }
 
@@ -101,17 +172,24 @@ fn main() -> Result<(), String> {
         println!("");
         println!("SCHEME:");
         println!("    groth16       -- Groth16 zero-knowledge proof system.");
+        println!("    bulletproofs  -- Bulletproofs zero-knowledge proof system.");
         println!("    marlin        -- Marlin zero-knowledge proof system.");
         println!("    spartan_snark -- Spartan with snark zero-knowledge proof system.");
         println!("    spartan_nizk  -- Spartan with nizk zero-knowledge proof system.");
+        println!("    spartan_snark_deterministic -- Spartan snark, parameters derived from a public label.");
+        println!("    spartan_nizk_deterministic  -- Spartan nizk, parameters derived from a public label.");
         println!("");
         println!("CURVE:");
         println!("    bn_256    -- BN_256 pairing curve.");
         println!("    bls12_381 -- BLS12_381 pairing curve.");
+        println!("    bls12_377 -- BLS12_377 pairing curve.");
         println!("");
         println!("CIRCUIT:");
         println!("    mini    -- Mini circuit. proof: x * (y + 2) = z.");
         println!("    hash    -- Hash circuit. proof: mimc hash.");
+        println!("    hash-[mimc|poseidon|rescue] -- Hash circuit. proof: the hash.");
+        println!("    sha256  -- Hash circuit. proof: sha256 hash.");
+        println!("    range   -- Range circuit. proof: 0 <= value < 2^64, of a commitment of the value.");
         println!("");
         println!("");
 
@@ -121,7 +199,7 @@ fn main() -> Result<(), String> {
     let circuit = if args.len() > 3 {
         args[3].as_str()
     } else {
-        "mini" // it will use in marlin.
+        "mini"
     };
     let (curve, scheme, circuit) = (args[2].as_str(), args[1].as_str(), circuit);
     println!("Start setup...");
@@ -135,6 +213,10 @@ fn main() -> Result<(), String> {
             use zkp_toolkit::bls12_381::Bls12_381;
             handle_circuit!(Bls12_381, curve, scheme, circuit);
         }
+        "bls12_377" => {
+            use zkp_toolkit::bls12_377::Bls12_377;
+            handle_circuit!(Bls12_377, curve, scheme, circuit);
+        }
         _ => return Err(format!("Curve: {} not implement.", curve)),
     }
 
@@ -1,29 +1,62 @@
 use serde_json::json;
 use std::env;
 use std::path::PathBuf;
-use zkp_toolkit::math::Curve;
+use zkp_toolkit::math::{Curve, Field};
+use zkp_toolkit::r1cs::debug_satisfied;
 
 mod circuits;
 use circuits::{CliCircuit, Publics};
 
-use circuits::hash::Hash;
+mod deterministic;
+use deterministic::DeterministicSpartan;
+
+mod key_file;
+use key_file::{gens_file_name, read_setup_file, to_hex};
+
+use circuits::hash::{Hash, Mimc, Poseidon, Rescue};
 use circuits::mini::Mini;
+use circuits::range::{Range, RANGE_BITS};
+use circuits::sha256::Sha256;
 
 const PROOFS_DIR: &'static str = "./proof_files";
 const SETUP_DIR: &'static str = "./setup_files";
 
 macro_rules! handle_circuit {
-    ($curve:ident, $curve_name:expr, $scheme:expr, $circuit:expr, $args:expr) => {
+    ($curve:ident, $curve_name:expr, $scheme:expr, $circuit:expr, $args:expr, $check:expr, $compressed:expr) => {
         match $circuit {
             "mini" => {
                 let (c, publics) = Mini::<<$curve as Curve>::Fr>::power_on($args);
                 let off_c = Mini::<<$curve as Curve>::Fr>::power_off();
-                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit);
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
+            }
+            "hash" | "hash-mimc" => {
+                let (c, publics) = Hash::<<$curve as Curve>::Fr, Mimc>::power_on($args);
+                let off_c = Hash::<<$curve as Curve>::Fr, Mimc>::power_off();
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
+            }
+            "hash-poseidon" => {
+                let (c, publics) = Hash::<<$curve as Curve>::Fr, Poseidon>::power_on($args);
+                let off_c = Hash::<<$curve as Curve>::Fr, Poseidon>::power_off();
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
             }
-            "hash" => {
-                let (c, publics) = Hash::<<$curve as Curve>::Fr>::power_on($args);
-                let off_c = Hash::<<$curve as Curve>::Fr>::power_off();
-                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit);
+            "hash-rescue" => {
+                let (c, publics) = Hash::<<$curve as Curve>::Fr, Rescue>::power_on($args);
+                let off_c = Hash::<<$curve as Curve>::Fr, Rescue>::power_off();
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
+            }
+            "sha256" => {
+                let (c, publics) = Sha256::<<$curve as Curve>::Fr>::power_on($args);
+                let off_c = Sha256::<<$curve as Curve>::Fr>::power_off();
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
+            }
+            "range" => {
+                let (c, publics) = Range::<$curve>::power_on($args);
+                // the keys of a setup are of the range of the setup.
+                if c.n_bits() != RANGE_BITS && has_circuit_key($scheme) {
+                    return Err(format!("SCHEME: {} proves ranges of {} bits only.", $scheme, RANGE_BITS));
+                }
+                let off_c = c.power_off_like();
+                handle_scheme!($curve, c, off_c, publics, $curve_name, $scheme, $circuit, $args, $check, $compressed);
             }
             _ => return Err(format!("CIRCUIT: {} not implement.", $circuit)),
         };
@@ -31,41 +64,47 @@ macro_rules! handle_circuit {
 }
 
 macro_rules! handle_scheme {
-    ($curve:ident, $c:expr, $off_c:expr, $publics:expr, $curve_name:expr, $scheme:expr, $circuit:expr) => {
+    ($curve:ident, $c:expr, $off_c:expr, $publics:expr, $curve_name:expr, $scheme:expr, $circuit:expr, $args:expr, $check:expr, $compressed:expr) => {
+        if $check {
+            check(&$c, $args)?;
+            println!("Circuit check passed.");
+        }
+
         let mut pk_path = PathBuf::from(SETUP_DIR);
         pk_path.push(format!("{}-{}-{}.pk", $scheme, $curve_name, $circuit));
-        let pk = std::fs::read(&pk_path).unwrap_or(vec![]);
         let rng = &mut rand::thread_rng();
+        let fingerprint = fingerprint(&$c);
 
         let proof_bytes = match $scheme {
             "groth16" => {
                 println!("Will use pk file: {:?}", pk_path);
-                use zkp_toolkit::groth16::{create_random_proof, Parameters};
-                let params: Parameters<$curve> = postcard::from_bytes(&pk).unwrap();
-                let proof = create_random_proof(&params, $c, rng).unwrap();
-                postcard::to_allocvec(&proof).unwrap()
+                use zkp_toolkit::groth16::{create_random_proof_from_source, MappedParameters};
+                // the queries are decoded of the mapped file only as the prover takes them.
+                let params = MappedParameters::<$curve>::open(&pk_path).unwrap();
+                let proof = create_random_proof_from_source(&params, $c, rng).unwrap();
+                if $compressed {
+                    proof.to_compressed_bytes().unwrap()
+                } else {
+                    postcard::to_allocvec(&proof).unwrap()
+                }
             }
             "bulletproofs" => {
-                use zkp_toolkit::bulletproofs::create_random_proof;
-                let (gens, r1cs, proof) = create_random_proof::<$curve, _, _>($c, rng).unwrap();
-                let mut gens_bytes = postcard::to_allocvec(&gens).unwrap();
-                let mut r1cs_bytes = postcard::to_allocvec(&r1cs).unwrap();
-                let mut proof_bytes = postcard::to_allocvec(&proof).unwrap();
-                let mut bytes = vec![];
-                bytes.extend(&(gens_bytes.len() as u32).to_le_bytes());
-                bytes.append(&mut gens_bytes);
-                bytes.extend(&(r1cs_bytes.len() as u32).to_le_bytes());
-                bytes.append(&mut r1cs_bytes);
-                bytes.append(&mut proof_bytes);
-
-                bytes
+                use zkp_toolkit::bulletproofs::{prove_with_shape, synthesize_shape, Generators};
+                let shape = synthesize_shape::<$curve, _>($off_c).unwrap();
+                let mut gens_path = PathBuf::from(SETUP_DIR);
+                gens_path.push(gens_file_name($curve_name, shape.generators_size()));
+                println!("Will use generators file: {:?}", gens_path);
+                let (_header, gens_bytes) = read_setup_file(&gens_path, $scheme, $curve_name)?;
+                let gens: Generators<$curve> = postcard::from_bytes(&gens_bytes).unwrap();
+                let proof = prove_with_shape(&gens, &shape, $c, rng).unwrap();
+                postcard::to_allocvec(&proof).unwrap()
             }
             "marlin" => {
                 use zkp_toolkit::marlin::{index, create_random_proof, UniversalParams};
                 let mut srs_path = PathBuf::from(SETUP_DIR);
-                srs_path.push(format!("{}-{}.universal_setup", $scheme, $curve_name));
+                srs_path.push(format!("{}-{}-{}.universal_setup", $scheme, $curve_name, $circuit));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (_header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: UniversalParams<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (ipk, _ivk) = index(&srs, $off_c).unwrap();
                 let proof = create_random_proof(&ipk, $c, rng).unwrap();
@@ -77,7 +116,7 @@ macro_rules! handle_scheme {
                 let mut srs_path = PathBuf::from(SETUP_DIR);
                 srs_path.push(format!("{}-{}-{}.universal_setup", $scheme, $curve_name, $circuit));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (_header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: Parameters<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (pk, _vk) = srs.keypair();
                 let proof = create_random_proof(&pk, $c, rng).unwrap();
@@ -88,12 +127,26 @@ macro_rules! handle_scheme {
                 let mut srs_path = PathBuf::from(SETUP_DIR);
                 srs_path.push(format!("{}-{}-{}.universal_setup", $scheme, $curve_name, $circuit));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (_header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: Parameters<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (pk, _vk) = srs.keypair();
                 let proof = create_random_proof(&pk, $c, rng).unwrap();
                 postcard::to_allocvec(&proof).unwrap()
             }
+            "spartan_snark_deterministic" => {
+                use zkp_toolkit::spartan::snark::create_random_proof;
+                let params = <$curve as DeterministicSpartan>::snark_parameters($off_c)?;
+                let (pk, _vk) = params.keypair();
+                let proof = create_random_proof(&pk, $c, rng).unwrap();
+                postcard::to_allocvec(&proof).unwrap()
+            }
+            "spartan_nizk_deterministic" => {
+                use zkp_toolkit::spartan::nizk::create_random_proof;
+                let params = <$curve as DeterministicSpartan>::nizk_parameters($off_c)?;
+                let (pk, _vk) = params.keypair();
+                let proof = create_random_proof(&pk, $c, rng).unwrap();
+                postcard::to_allocvec(&proof).unwrap()
+            }
             _ => return Err(format!("SCHEME: {} not implement.", $scheme)),
         };
 
@@ -108,7 +161,13 @@ macro_rules! handle_scheme {
             Publics::Mini(z) => vec![format!("{}", z)],
             Publics::Hash(image) => vec![
                 format!("{}", to_hex(&postcard::to_allocvec(&image).unwrap()))
-            ]
+            ],
+            Publics::Sha256(image) => vec![to_hex(&image)],
+            Publics::Range(n_bits, x, y) => vec![
+                format!("{}", n_bits),
+                to_hex(&postcard::to_allocvec(&x).unwrap()),
+                to_hex(&postcard::to_allocvec(&y).unwrap()),
+            ],
         };
 
         let content = json!({
@@ -116,24 +175,49 @@ macro_rules! handle_scheme {
             "scheme": $scheme,
             "curve": $curve_name,
             "params": params,
+            "fingerprint": fingerprint,
             "proof": to_hex(&proof_bytes)
         });
         serde_json::to_writer(&std::fs::File::create(path).unwrap(), &content).unwrap();
     };
 }
 
-fn to_hex(v: &[u8]) -> String {
-    let mut s = String::with_capacity(v.len() * 2);
-    s.extend(v.iter().map(|b| format!("{:02x}", b)));
-    s
+/// Checks the constraints of `_c`'s circuit with the witness of `args`,
+/// reporting the first one it violates.
+fn check<F: Field, C: CliCircuit<F>>(_c: &C, args: &[String]) -> Result<(), String> {
+    let (c, _) = C::power_on(args);
+    match debug_satisfied(c) {
+        Ok(None) => Ok(()),
+        Ok(Some(unsatisfied)) => Err(unsatisfied.to_string()),
+        Err(e) => Err(format!("the circuit failed to synthesize: {}", e)),
+    }
+}
+
+/// The fingerprint of `c`'s circuit, for the verifier to tell it from the
+/// circuit of its key.
+fn fingerprint<F: Field, C: CliCircuit<F>>(c: &C) -> String {
+    to_hex(&zkp_toolkit::r1cs::fingerprint(c.power_off_like()).unwrap())
+}
+
+/// The schemes whose setup files are of the circuit, rather than of its
+/// size or of no setup at all.
+fn has_circuit_key(scheme: &str) -> bool {
+    ["groth16", "marlin", "spartan_snark", "spartan_nizk"].contains(&scheme)
 }
 
 fn main() -> Result<(), String> {
-    let args: Vec<_> = env::args().collect();
+    let mut args: Vec<_> = env::args().collect();
+    let check = args.iter().any(|arg| arg == "--check");
+    let compressed = args.iter().any(|arg| arg == "--compressed");
+    args.retain(|arg| arg != "--check" && arg != "--compressed");
     if args.len() < 2 {
         println!("zkp-prove");
         println!("");
-        println!("Usage: zkp-prove [SCHEME] [CURVE] [CIRCUIT] [ARGUMENTS]");
+        println!("Usage: zkp-prove [--check] [--compressed] [SCHEME] [CURVE] [CIRCUIT] [ARGUMENTS]");
+        println!("");
+        println!("OPTIONS:");
+        println!("    --check       -- check the witness against every constraint before proving.");
+        println!("    --compressed  -- write the groth16 proof as its compressed points, 128 bytes on bn_256.");
         println!("");
         println!("SCHEME:");
         println!("    groth16       -- Groth16 zero-knowledge proof system.");
@@ -141,14 +225,20 @@ fn main() -> Result<(), String> {
         println!("    marlin        -- Marlin zero-knowledge proof system.");
         println!("    spartan_snark -- Spartan with snark zero-knowledge proof system.");
         println!("    spartan_nizk  -- Spartan with nizk zero-knowledge proof system.");
+        println!("    spartan_snark_deterministic -- Spartan snark, parameters derived from a public label.");
+        println!("    spartan_nizk_deterministic  -- Spartan nizk, parameters derived from a public label.");
         println!("");
         println!("CURVE:");
         println!("    bn_256    -- BN_256 pairing curve.");
         println!("    bls12_381 -- BLS12_381 pairing curve.");
+        println!("    bls12_377 -- BLS12_377 pairing curve.");
         println!("");
         println!("CIRCUIT:");
         println!("    mini    -- Mini circuit. proof: x * (y + 2) = z.");
         println!("    hash    -- Hash circuit. proof: mimc hash.");
+        println!("    hash-[mimc|poseidon|rescue] -- Hash circuit. proof: the hash.");
+        println!("    sha256  -- Hash circuit. proof: sha256 hash.");
+        println!("    range   -- Range circuit. proof: 0 <= value < 2^bits, of a commitment of the value.");
         println!("");
         println!("CIRCUIT ARGUMENTS:");
         println!("    [arguments]    -- circuits arguments.");
@@ -159,15 +249,22 @@ fn main() -> Result<(), String> {
     }
 
     let (curve, scheme, circuit) = (args[2].as_str(), args[1].as_str(), args[3].as_str());
+    if compressed && scheme != "groth16" {
+        return Err(format!("SCHEME: {} has no compressed proofs.", scheme));
+    }
 
     match curve {
         "bn_256" => {
             use zkp_toolkit::bn_256::Bn_256;
-            handle_circuit!(Bn_256, curve, scheme, circuit, &args[4..]);
+            handle_circuit!(Bn_256, curve, scheme, circuit, &args[4..], check, compressed);
         }
         "bls12_381" => {
             use zkp_toolkit::bls12_381::Bls12_381;
-            handle_circuit!(Bls12_381, curve, scheme, circuit, &args[4..]);
+            handle_circuit!(Bls12_381, curve, scheme, circuit, &args[4..], check, compressed);
+        }
+        "bls12_377" => {
+            use zkp_toolkit::bls12_377::Bls12_377;
+            handle_circuit!(Bls12_377, curve, scheme, circuit, &args[4..], check, compressed);
         }
         _ => return Err(format!("Curve: {} not implement.", curve)),
     }
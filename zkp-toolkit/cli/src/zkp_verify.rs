@@ -1,22 +1,31 @@
 use std::env;
 use std::path::PathBuf;
 use zkp_toolkit::math::Curve;
+use zkp_toolkit::r1cs::CircuitFingerprint;
 
 mod circuits;
-use circuits::CliCircuit;
+use circuits::{CliCircuit, Publics};
 
-use circuits::hash::Hash;
+mod deterministic;
+use deterministic::DeterministicSpartan;
+
+mod key_file;
+use key_file::{gens_file_name, read_setup_file};
+
+use circuits::hash::{Hash, Mimc, Poseidon, Rescue};
 use circuits::mini::Mini;
+use circuits::range::Range;
+use circuits::sha256::Sha256;
 
 const SETUP_DIR: &'static str = "./setup_files";
 
 macro_rules! handle_circuit {
-    ($curve:ident, $curve_name:expr, $scheme:expr, $circuit:expr, $proof_bytes:expr, $params:expr) => {
+    ($curve:ident, $curve_name:expr, $scheme:expr, $circuit:expr, $proof_bytes:expr, $params:expr, $fingerprint:expr) => {
         match $circuit {
             "mini" => {
                 let num: u64 = $params[0].as_str().unwrap().parse().unwrap();
                 let c = Mini::<<$curve as Curve>::Fr>::power_off();
-                let publics = vec![<$curve as Curve>::Fr::from(num)];
+                let publics = Publics::<<$curve as Curve>::Fr>::Mini(num).inputs();
                 handle_scheme!(
                     $curve,
                     c,
@@ -24,14 +33,62 @@ macro_rules! handle_circuit {
                     $curve_name,
                     $scheme,
                     $circuit,
-                    $proof_bytes
+                    $proof_bytes,
+                    $fingerprint
                 );
             }
-            "hash" => {
+            "hash" | "hash-mimc" => {
                 let fr_bytes = from_hex($params[0].as_str().unwrap()).unwrap();
                 let image: <$curve as Curve>::Fr = postcard::from_bytes(&fr_bytes).unwrap();
-                let c = Hash::<<$curve as Curve>::Fr>::power_off();
-                let publics = vec![image];
+                let c = Hash::<<$curve as Curve>::Fr, Mimc>::power_off();
+                let publics = Publics::Hash(image).inputs();
+                handle_scheme!(
+                    $curve,
+                    c,
+                    &publics,
+                    $curve_name,
+                    $scheme,
+                    $circuit,
+                    $proof_bytes,
+                    $fingerprint
+                );
+            }
+            "hash-poseidon" => {
+                let fr_bytes = from_hex($params[0].as_str().unwrap()).unwrap();
+                let image: <$curve as Curve>::Fr = postcard::from_bytes(&fr_bytes).unwrap();
+                let c = Hash::<<$curve as Curve>::Fr, Poseidon>::power_off();
+                let publics = Publics::Hash(image).inputs();
+                handle_scheme!(
+                    $curve,
+                    c,
+                    &publics,
+                    $curve_name,
+                    $scheme,
+                    $circuit,
+                    $proof_bytes,
+                    $fingerprint
+                );
+            }
+            "hash-rescue" => {
+                let fr_bytes = from_hex($params[0].as_str().unwrap()).unwrap();
+                let image: <$curve as Curve>::Fr = postcard::from_bytes(&fr_bytes).unwrap();
+                let c = Hash::<<$curve as Curve>::Fr, Rescue>::power_off();
+                let publics = Publics::Hash(image).inputs();
+                handle_scheme!(
+                    $curve,
+                    c,
+                    &publics,
+                    $curve_name,
+                    $scheme,
+                    $circuit,
+                    $proof_bytes,
+                    $fingerprint
+                );
+            }
+            "sha256" => {
+                let image = from_hex($params[0].as_str().unwrap()).unwrap();
+                let c = Sha256::<<$curve as Curve>::Fr>::power_off();
+                let publics = Publics::<<$curve as Curve>::Fr>::Sha256(image).inputs();
                 handle_scheme!(
                     $curve,
                     c,
@@ -39,7 +96,27 @@ macro_rules! handle_circuit {
                     $curve_name,
                     $scheme,
                     $circuit,
-                    $proof_bytes
+                    $proof_bytes,
+                    $fingerprint
+                );
+            }
+            "range" => {
+                let n_bits: usize = $params[0].as_str().unwrap().parse().unwrap();
+                let x_bytes = from_hex($params[1].as_str().unwrap()).unwrap();
+                let y_bytes = from_hex($params[2].as_str().unwrap()).unwrap();
+                let x: <$curve as Curve>::Fr = postcard::from_bytes(&x_bytes).unwrap();
+                let y: <$curve as Curve>::Fr = postcard::from_bytes(&y_bytes).unwrap();
+                let c = Range::<$curve>::blank(n_bits);
+                let publics = Publics::Range(n_bits, x, y).inputs();
+                handle_scheme!(
+                    $curve,
+                    c,
+                    &publics,
+                    $curve_name,
+                    $scheme,
+                    $circuit,
+                    $proof_bytes,
+                    $fingerprint
                 );
             }
             _ => return Err(format!("CIRCUIT: {} not implement.", $circuit)),
@@ -48,43 +125,67 @@ macro_rules! handle_circuit {
 }
 
 macro_rules! handle_scheme {
-    ($curve:ident, $c:expr, $publics:expr, $curve_name:expr, $scheme:expr, $circuit:expr, $proof_bytes:expr) => {
+    ($curve:ident, $c:expr, $publics:expr, $curve_name:expr, $scheme:expr, $circuit:expr, $proof_bytes:expr, $fingerprint:expr) => {
         let mut vk_path = PathBuf::from(SETUP_DIR);
         vk_path.push(format!("{}-{}-{}.vk", $scheme, $curve_name, $circuit));
-        let vk_bytes = std::fs::read(&vk_path).unwrap_or(vec![]);
 
         let proof_result = match $scheme {
             "groth16" => {
-                println!("Will use vk file: {:?}", vk_path);
-                use zkp_toolkit::groth16::{prepare_verifying_key, verify_proof, Proof, VerifyKey};
-                let vk: VerifyKey<$curve> = postcard::from_bytes(&vk_bytes).unwrap();
-                let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
-                let pvk = prepare_verifying_key(&vk);
-                verify_proof(&pvk, &proof, &$publics).unwrap()
+                use zkp_toolkit::groth16::{verify_proof, PreparedVerifyingKey, Proof, VerifyKey};
+                // compressed proofs are told apart by their length.
+                let proof: Proof<$curve> =
+                    if $proof_bytes.len() == Proof::<$curve>::compressed_size() {
+                        Proof::from_compressed_bytes(&$proof_bytes).map_err(|e| e.to_string())?
+                    } else {
+                        postcard::from_bytes(&$proof_bytes).unwrap()
+                    };
+                let pvk_path = vk_path.with_extension("pvk");
+                if pvk_path.exists() {
+                    println!("Will use prepared vk file: {:?}", pvk_path);
+                    let (header, pvk_bytes) = read_setup_file(&pvk_path, $scheme, $curve_name)?;
+                    let pvk: PreparedVerifyingKey<$curve> =
+                        postcard::from_bytes(&pvk_bytes).unwrap();
+                    header.check_fingerprint(&pvk.vk.key_fingerprint())?;
+                    check_fingerprint($fingerprint, &pvk.vk.fingerprint)?;
+                    verify_proof(&pvk, &proof, &$publics).unwrap()
+                } else {
+                    println!("Will use vk file: {:?}", vk_path);
+                    let (header, vk_bytes) = read_setup_file(&vk_path, $scheme, $curve_name)?;
+                    let vk: VerifyKey<$curve> = postcard::from_bytes(&vk_bytes).unwrap();
+                    header.check_fingerprint(&vk.key_fingerprint())?;
+                    check_fingerprint($fingerprint, &vk.fingerprint)?;
+                    verify_proof(&vk, &proof, &$publics).unwrap()
+                }
             }
             "bulletproofs" => {
-                use zkp_toolkit::bulletproofs::{verify_proof, Generators, Proof, R1csCircuit};
-                let mut gens_len_bytes = [0u8; 4];
-                gens_len_bytes.copy_from_slice($proof_bytes.drain(0..4).as_slice());
-                let gens_len = u32::from_le_bytes(gens_len_bytes) as usize;
-                let gens: Generators<$curve> =
-                    postcard::from_bytes($proof_bytes.drain(0..gens_len).as_slice()).unwrap();
-                let mut r1cs_len_bytes = [0u8; 4];
-                r1cs_len_bytes.copy_from_slice($proof_bytes.drain(0..4).as_slice());
-                let r1cs_len = u32::from_le_bytes(r1cs_len_bytes) as usize;
-                let r1cs: R1csCircuit<$curve> =
-                    postcard::from_bytes($proof_bytes.drain(0..r1cs_len).as_slice()).unwrap();
+                use zkp_toolkit::bulletproofs::{synthesize_shape, verify, Generators, Proof};
+                // the constraints are of the circuit, the proof has their
+                // digest only.
+                let shape = synthesize_shape::<$curve, _>($c).unwrap();
                 let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
-                verify_proof(&gens, &proof, &r1cs, $publics).unwrap()
+                if proof.digest() != &shape.digest() {
+                    return Err("key/circuit mismatch".to_owned());
+                }
+                let mut gens_path = PathBuf::from(SETUP_DIR);
+                gens_path.push(gens_file_name($curve_name, shape.generators_size()));
+                println!("Will use generators file: {:?}", gens_path);
+                let (_header, gens_bytes) = read_setup_file(&gens_path, $scheme, $curve_name)?;
+                let gens: Generators<$curve> = postcard::from_bytes(&gens_bytes).unwrap();
+                verify(&gens, &shape, &proof, $publics).map_err(|e| e.to_string())?
             }
             "marlin" => {
                 use zkp_toolkit::marlin::{index, verify_proof, Proof, UniversalParams};
                 let mut srs_path = PathBuf::from(SETUP_DIR);
-                srs_path.push(format!("{}-{}.universal_setup", $scheme, $curve_name));
+                srs_path.push(format!(
+                    "{}-{}-{}.universal_setup",
+                    $scheme, $curve_name, $circuit
+                ));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (_header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: UniversalParams<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (_ipk, ivk) = index(&srs, $c).unwrap();
+                println!("Verify key fingerprint: {}", key_file::to_hex(&ivk.key_fingerprint()));
+                check_fingerprint($fingerprint, &ivk.fingerprint)?;
                 let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
                 verify_proof(&ivk, &proof, $publics).unwrap()
             }
@@ -96,9 +197,11 @@ macro_rules! handle_scheme {
                     $scheme, $curve_name, $circuit
                 ));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: Parameters<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (_pk, vk) = srs.keypair();
+                header.check_fingerprint(&vk.key_fingerprint())?;
+                check_fingerprint($fingerprint, vk.fingerprint())?;
                 let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
                 verify_proof(&vk, &proof, $publics).unwrap()
             }
@@ -110,9 +213,27 @@ macro_rules! handle_scheme {
                     $scheme, $curve_name, $circuit
                 ));
                 println!("Will use universal setup file: {:?}", srs_path);
-                let srs_bytes = std::fs::read(&srs_path).unwrap_or(vec![]);
+                let (header, srs_bytes) = read_setup_file(&srs_path, $scheme, $curve_name)?;
                 let srs: Parameters<$curve> = postcard::from_bytes(&srs_bytes).unwrap();
                 let (_pk, vk) = srs.keypair();
+                header.check_fingerprint(&vk.key_fingerprint())?;
+                check_fingerprint($fingerprint, vk.fingerprint())?;
+                let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
+                verify_proof(&vk, &proof, $publics).unwrap()
+            }
+            "spartan_snark_deterministic" => {
+                use zkp_toolkit::spartan::snark::{verify_proof, Proof};
+                let params = <$curve as DeterministicSpartan>::snark_parameters($c)?;
+                let (_pk, vk) = params.keypair();
+                check_fingerprint($fingerprint, vk.fingerprint())?;
+                let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
+                verify_proof(&vk, &proof, $publics).unwrap()
+            }
+            "spartan_nizk_deterministic" => {
+                use zkp_toolkit::spartan::nizk::{verify_proof, Proof};
+                let params = <$curve as DeterministicSpartan>::nizk_parameters($c)?;
+                let (_pk, vk) = params.keypair();
+                check_fingerprint($fingerprint, vk.fingerprint())?;
                 let proof: Proof<$curve> = postcard::from_bytes(&$proof_bytes).unwrap();
                 verify_proof(&vk, &proof, $publics).unwrap()
             }
@@ -123,6 +244,17 @@ macro_rules! handle_scheme {
     };
 }
 
+/// Fails fast, before verifying, when the proof is of another circuit than
+/// the key. Proofs of no fingerprint are verified as they are.
+fn check_fingerprint(proof: Option<&str>, key: &CircuitFingerprint) -> Result<(), String> {
+    match proof {
+        Some(fingerprint) if from_hex(fingerprint) != Ok(key.to_vec()) => {
+            Err("key/circuit mismatch".to_owned())
+        }
+        _ => Ok(()),
+    }
+}
+
 fn from_hex(s: &str) -> Result<Vec<u8>, ()> {
     if s.len() % 2 != 0 {
         return Err(());
@@ -156,18 +288,39 @@ fn main() -> Result<(), String> {
     let scheme = json["scheme"].as_str().unwrap();
     let curve = json["curve"].as_str().unwrap();
     let params = &json["params"];
-    let mut proof = from_hex(json["proof"].as_str().unwrap()).expect("proof invalid");
+    let fingerprint = json["fingerprint"].as_str();
+    let proof = from_hex(json["proof"].as_str().unwrap()).expect("proof invalid");
 
     println!("Start verify...");
 
     match curve {
         "bn_256" => {
             use zkp_toolkit::bn_256::Bn_256;
-            handle_circuit!(Bn_256, curve, scheme, circuit, proof, params);
+            handle_circuit!(Bn_256, curve, scheme, circuit, proof, params, fingerprint);
         }
         "bls12_381" => {
             use zkp_toolkit::bls12_381::Bls12_381;
-            handle_circuit!(Bls12_381, curve, scheme, circuit, proof, params);
+            handle_circuit!(
+                Bls12_381,
+                curve,
+                scheme,
+                circuit,
+                proof,
+                params,
+                fingerprint
+            );
+        }
+        "bls12_377" => {
+            use zkp_toolkit::bls12_377::Bls12_377;
+            handle_circuit!(
+                Bls12_377,
+                curve,
+                scheme,
+                circuit,
+                proof,
+                params,
+                fingerprint
+            );
         }
         _ => return Err(format!("Curve: {} not implement.", curve)),
     }
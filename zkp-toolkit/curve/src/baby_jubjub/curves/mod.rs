@@ -1,6 +1,8 @@
-use crate::baby_jubjub::{Fq, Fr};
+use crate::{
+    baby_jubjub::{Fq, Fr},
+    bn_256::BigInteger,
+};
 use math::{
-    biginteger::BigInteger256,
     curves::{
         models::{ModelParameters, MontgomeryModelParameters, TEModelParameters},
         twisted_edwards_extended::{GroupAffine, GroupProjective},
@@ -26,14 +28,14 @@ pub type BabyJubJubAffine = GroupAffine<EdwardsParameters>;
 pub type BabyJubJubProjective = GroupProjective<EdwardsParameters>;
 
 #[rustfmt::skip]
-const GENERATOR_X: Fq = field_new!(Fq, BigInteger256([
+const GENERATOR_X: Fq = field_new!(Fq, BigInteger([
     0x3db6612c2863cc99,
     0x8a9e4521b36347dc,
     0x310a1a625c16a534,
     0x23ceae2710df4a14,
 ]));
 #[rustfmt::skip]
-const GENERATOR_Y: Fq = field_new!(Fq, BigInteger256([
+const GENERATOR_Y: Fq = field_new!(Fq, BigInteger([
     0xb83342d20d0201aa,
     0x2ffef2f7cdcfeac7,
     0xbfa79a9425a6e625,
@@ -59,7 +61,7 @@ impl ModelParameters for EdwardsParameters {
 impl TEModelParameters for EdwardsParameters {
     /// COEFF_A = 1
     #[rustfmt::skip]
-    const COEFF_A: Fq = field_new!(Fq, BigInteger256([
+    const COEFF_A: Fq = field_new!(Fq, BigInteger([
         0xac96341c4ffffffb,
         0x36fc76959f60cd29,
         0x666ea36f7879462e,
@@ -74,7 +76,7 @@ impl TEModelParameters for EdwardsParameters {
     /// COEFF_D = 168696/168700 mod q
     ///         = 9706598848417545097372247223557719406784115219466060233080913168975159366771
     #[rustfmt::skip]
-    const COEFF_D: Fq = field_new!(Fq, BigInteger256([
+    const COEFF_D: Fq = field_new!(Fq, BigInteger([
         0xe7a66d1d9fb08e74,
         0xd775bbd5e17629dc,
         0x70ccd097286ef1e7,
@@ -87,7 +89,7 @@ impl TEModelParameters for EdwardsParameters {
     /// COFACTOR^(-1) mod r =
     /// 2394026564107420727433200628387514462817212225638746351800188703329891451411
     #[rustfmt::skip]
-    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger256([
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger([
         0xfac308b2e25a3d4b,
         0xa7c55b66e25b59cb,
         0xeccdd46def0f28c5,
@@ -103,7 +105,7 @@ impl TEModelParameters for EdwardsParameters {
 impl MontgomeryModelParameters for EdwardsParameters {
     /// COEFF_A = 168698
     #[rustfmt::skip]
-    const COEFF_A: Fq = field_new!(Fq, BigInteger256([
+    const COEFF_A: Fq = field_new!(Fq, BigInteger([
         9251058552732279275u64,
         16047179255329565110u64,
         14708493084570629864u64,
@@ -111,7 +113,7 @@ impl MontgomeryModelParameters for EdwardsParameters {
     ]));
     /// COEFF_B = 168700
     #[rustfmt::skip]
-    const COEFF_B: Fq = field_new!(Fq, BigInteger256([
+    const COEFF_B: Fq = field_new!(Fq, BigInteger([
         10785223227458347488u64,
         2627865112663806840u64,
         16189334210225400552u64,
@@ -1,7 +1,11 @@
 use math::{
     bytes::{FromBytes, ToBytes},
-    curves::{AffineCurve, ProjectiveCurve},
-    test_rng, Zero,
+    curves::{
+        models::{MontgomeryModelParameters, TEModelParameters},
+        AffineCurve, ProjectiveCurve,
+    },
+    fields::{Field, FpParameters, PrimeField},
+    test_rng, One, UniformRand, Zero,
 };
 use core::str::FromStr;
 use rand::Rng;
@@ -111,3 +115,92 @@ fn test_bytes() {
 fn test_montgomery_conversion() {
     montgomery_conversion_test::<EdwardsParameters>();
 }
+
+#[test]
+fn test_generator_curve_equation() {
+    let g = BabyJubJubAffine::prime_subgroup_generator();
+    let x2 = g.x.square();
+    let y2 = g.y.square();
+
+    // x^2 + y^2 = 1 + d * x^2 * y^2
+    assert_eq!(x2 + &y2, Fq::one() + &(EdwardsParameters::COEFF_D * &x2 * &y2));
+}
+
+#[test]
+fn test_prime_subgroup_order() {
+    let g = BabyJubJubAffine::prime_subgroup_generator();
+    let order = <Fr as PrimeField>::Params::MODULUS;
+
+    assert!(g.mul(order).is_zero());
+    assert!(!g.mul(Fr::one()).is_zero());
+}
+
+#[test]
+fn test_cofactor() {
+    let mut rng = test_rng();
+
+    // Find a point on the full curve which is outside of the prime subgroup.
+    let p = loop {
+        let x = Fq::rand(&mut rng);
+        if let Some(p) = BabyJubJubAffine::get_point_from_x(x, rng.gen()) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                break p;
+            }
+        }
+    };
+    assert!(p.is_on_curve());
+
+    let cleared = p.mul_by_cofactor();
+    assert!(cleared.is_in_correct_subgroup_assuming_on_curve());
+    assert_eq!(cleared, p.into_projective().double().double().double().into_affine());
+
+    let g: BabyJubJubAffine = rng.gen();
+    assert_eq!(g.mul_by_cofactor().mul_by_cofactor_inv(), g);
+}
+
+/// Maps an Edwards point to the birationally equivalent Montgomery curve
+/// B * v^2 = u^3 + A * u^2 + u, with u = (1 + y) / (1 - y) and v = u / x.
+fn to_montgomery(p: &BabyJubJubAffine) -> (Fq, Fq) {
+    let u = (Fq::one() + &p.y) * &(Fq::one() - &p.y).inverse().unwrap();
+    let v = u * &p.x.inverse().unwrap();
+    (u, v)
+}
+
+#[test]
+fn test_edwards_addition_against_montgomery() {
+    let a = <EdwardsParameters as MontgomeryModelParameters>::COEFF_A;
+    let b = <EdwardsParameters as MontgomeryModelParameters>::COEFF_B;
+
+    let mut rng = test_rng();
+    for _ in 0..100 {
+        let p: BabyJubJubAffine = rng.gen();
+        let q: BabyJubJubAffine = rng.gen();
+        let (u1, v1) = to_montgomery(&p);
+        let (u2, v2) = to_montgomery(&q);
+        let (u3, v3) = to_montgomery(&(p + &q));
+
+        for (u, v) in [(u1, v1), (u2, v2), (u3, v3)].iter() {
+            assert_eq!(b * &v.square(), u.square() * u + &(a * &u.square()) + u);
+        }
+
+        // Montgomery chord addition.
+        let lambda = (v2 - &v1) * &(u2 - &u1).inverse().unwrap();
+        let u = b * &lambda.square() - &a - &u1 - &u2;
+        let v = lambda * &(u1 - &u) - &v1;
+        assert_eq!((u, v), (u3, v3));
+    }
+}
+
+#[test]
+fn test_serde() {
+    let mut rng = test_rng();
+    let a: BabyJubJubAffine = rng.gen();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: BabyJubJubAffine = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+
+    let a = a.into_projective();
+    let bytes = postcard::to_allocvec(&a).unwrap();
+    let b: BabyJubJubProjective = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(a, b);
+}
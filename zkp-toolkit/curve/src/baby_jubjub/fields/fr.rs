@@ -1,20 +1,33 @@
+#[cfg(feature = "const_generic_fp")]
+use math::{biginteger::BigInt, fields::Fp};
+#[cfg(not(feature = "const_generic_fp"))]
 use math::{
-    biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    biginteger::BigInteger256,
+    fields::{Fp256, Fp256Parameters},
 };
+use math::fields::FpParameters;
 
+use crate::bn_256::BigInteger;
+
+#[cfg(not(feature = "const_generic_fp"))]
 pub type Fr = Fp256<FrParameters>;
+#[cfg(feature = "const_generic_fp")]
+pub type Fr = Fp<FrParameters, 4>;
 
 pub struct FrParameters;
 
+#[cfg(not(feature = "const_generic_fp"))]
 impl Fp256Parameters for FrParameters {}
 
 impl FpParameters for FrParameters {
-    type BigInt = BigInteger;
+    #[cfg(not(feature = "const_generic_fp"))]
+    type BigInt = BigInteger256;
+    #[cfg(feature = "const_generic_fp")]
+    type BigInt = BigInt<4>;
 
     /// MODULUS = 2736030358979909402780800718157159386076813972158567259200215660948447373041
     #[rustfmt::skip]
-    const MODULUS: BigInteger = BigInteger([
+    const MODULUS: Self::BigInt = BigInteger([
         0x677297dc392126f1,
         0xab3eedb83920ee0a,
         0x370a08b6d0302b0b,
@@ -28,7 +41,7 @@ impl FpParameters for FrParameters {
     const REPR_SHAVE_BITS: u32 = 5;
 
     #[rustfmt::skip]
-    const R: BigInteger = BigInteger([
+    const R: Self::BigInt = BigInteger([
         0x073315dea08f9c76,
         0xe7acffc6a098f24b,
         0xf85a9201d818f015,
@@ -36,7 +49,7 @@ impl FpParameters for FrParameters {
     ]);
 
     #[rustfmt::skip]
-    const R2: BigInteger = BigInteger([
+    const R2: Self::BigInt = BigInteger([
         0x35e44abee7ecb21e,
         0x74646cacf5f84ec4,
         0xe472df203faa158f,
@@ -47,7 +60,7 @@ impl FpParameters for FrParameters {
 
     #[rustfmt::skip]
     /// GENERATOR = 31
-    const GENERATOR: BigInteger = BigInteger([
+    const GENERATOR: Self::BigInt = BigInteger([
         0x3c284f376f3993d1,
         0x08bc9d93705cf8b8,
         0x239d5fcbd9538f3e,
@@ -56,28 +69,28 @@ impl FpParameters for FrParameters {
 
     const TWO_ADICITY: u32 = 4;
 
-    const ROOT_OF_UNITY: BigInteger = BigInteger([
+    const ROOT_OF_UNITY: Self::BigInt = BigInteger([
         0x1721ada8d4d27255,
         0xcda0f5264e0e35bb,
         0x961a936922086fe6,
         0x1ab00857387dd52,
     ]);
 
-    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+    const MODULUS_MINUS_ONE_DIV_TWO: Self::BigInt = BigInteger([
         0x33b94bee1c909378,
         0xd59f76dc1c907705,
         0x9b85045b68181585,
         0x30644e72e131a02,
     ]);
 
-    const T: BigInteger = BigInteger([
+    const T: Self::BigInt = BigInteger([
         0xa677297dc392126f,
         0xbab3eedb83920ee0,
         0x5370a08b6d0302b0,
         0x60c89ce5c26340,
     ]);
 
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+    const T_MINUS_ONE_DIV_TWO: Self::BigInt = BigInteger([
         0x533b94bee1c90937,
         0x5d59f76dc1c90770,
         0x29b85045b6818158,
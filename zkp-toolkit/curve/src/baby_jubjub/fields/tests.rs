@@ -1,6 +1,8 @@
-use crate::baby_jubjub::{Fq, Fr};
+use crate::{
+    baby_jubjub::{Fq, Fr},
+    bn_256::BigInteger,
+};
 use math::{
-    biginteger::BigInteger256 as BigInteger,
     bytes::{FromBytes, ToBytes},
     fields::{Field, LegendreSymbol::*, SquareRootField},
     test_rng, One, Zero,
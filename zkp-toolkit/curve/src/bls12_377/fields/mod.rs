@@ -5,9 +5,19 @@ pub mod fr;
 #[cfg(any(feature = "bls12_377", feature = "edwards_bls12"))]
 pub use self::fr::*;
 
-#[cfg(any(feature = "bls12_377", feature = "sw6", feature = "edwards_sw6"))]
+#[cfg(any(
+    feature = "bls12_377",
+    feature = "sw6",
+    feature = "edwards_sw6",
+    feature = "bw6_761"
+))]
 pub mod fq;
-#[cfg(any(feature = "bls12_377", feature = "sw6", feature = "edwards_sw6"))]
+#[cfg(any(
+    feature = "bls12_377",
+    feature = "sw6",
+    feature = "edwards_sw6",
+    feature = "bw6_761"
+))]
 pub use self::fq::*;
 
 #[cfg(feature = "bls12_377")]
@@ -5,10 +5,10 @@ use crate::{
     bls12_381,
     bls12_381::*,
     curves::{
-        bls12,
+        bls12::{self, Bls12Parameters},
         models::{ModelParameters, SWModelParameters},
     },
-    field_new, Zero,
+    field_new, AffineCurve, Zero,
 };
 
 pub type G1Affine = bls12::G1Affine<bls12_381::Parameters>;
@@ -58,6 +58,13 @@ impl SWModelParameters for Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// Multiplies by `h_eff = 1 - x`, which is enough to land in G1 and
+    /// about half the size of `COFACTOR`.
+    fn clear_cofactor(p: &G1Affine) -> G1Projective {
+        debug_assert!(bls12_381::Parameters::X_IS_NEGATIVE);
+        p.mul(Fr::from(bls12_381::Parameters::X[0] + 1))
+    }
 }
 
 /// G1_GENERATOR_X =
@@ -5,10 +5,10 @@ use crate::{
     bls12_381,
     bls12_381::*,
     curves::{
-        bls12,
+        bls12::{self, Bls12Parameters},
         models::{ModelParameters, SWModelParameters},
     },
-    field_new, Zero,
+    field_new, AffineCurve, Field, ProjectiveCurve, Zero,
 };
 
 pub type G2Affine = bls12::G2Affine<bls12_381::Parameters>;
@@ -62,8 +62,97 @@ impl SWModelParameters for Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// Multiplies by `h_eff` of RFC 9380 with the method of Budroni and
+    /// Pintore, `[x^2 - x - 1] P + [x - 1] psi(P) + psi^2(2 P)`.
+    fn clear_cofactor(p: &G2Affine) -> G2Projective {
+        let p = p.into_projective();
+        let t1 = mul_by_x(&p);
+        let mut t2 = psi(&p);
+        let mut t3 = psi(&psi(&p.double()));
+        t3 -= &t2;
+        t2 += &t1;
+        t3 += &mul_by_x(&t2);
+        t3 -= &t1;
+        t3 -= &p;
+        t3
+    }
+
+    /// A point of E2 is in G2 if and only if `psi(P) = [x] P`, see Scott,
+    /// "A note on group membership tests for G1, G2 and GT on BLS
+    /// pairing-friendly curves".
+    fn is_in_correct_subgroup_assuming_on_curve(p: &G2Affine) -> bool {
+        let p = p.into_projective();
+        psi(&p) == mul_by_x(&p)
+    }
+}
+
+/// Multiplies `p` by the (negative) curve parameter `x`.
+fn mul_by_x(p: &G2Projective) -> G2Projective {
+    let res = p.mul(Fr::from(bls12_381::Parameters::X[0]));
+    if bls12_381::Parameters::X_IS_NEGATIVE {
+        -res
+    } else {
+        res
+    }
 }
 
+/// The untwist-Frobenius-twist endomorphism
+/// `psi(x, y) = (PSI_X * x^p, PSI_Y * y^p)`, applied to Jacobian coordinates.
+fn psi(p: &G2Projective) -> G2Projective {
+    let mut x = p.x;
+    let mut y = p.y;
+    let mut z = p.z;
+    x.frobenius_map(1);
+    y.frobenius_map(1);
+    z.frobenius_map(1);
+    G2Projective::new(x * &PSI_X, y * &PSI_Y, z)
+}
+
+/// PSI_X = 1 / (1 + u)^((p - 1) / 3)
+#[rustfmt::skip]
+const PSI_X: Fq2 = field_new!(
+    Fq2,
+    field_new!(Fq, BigInteger384([
+        0x0,
+        0x0,
+        0x0,
+        0x0,
+        0x0,
+        0x0,
+    ])),
+    field_new!(Fq, BigInteger384([
+        0x890dc9e4867545c3,
+        0x2af322533285a5d5,
+        0x50880866309b7e2c,
+        0xa20d1b8c7e881024,
+        0x14e4f04fe2db9068,
+        0x14e56d3f1564853a,
+    ])),
+);
+
+/// PSI_Y = 1 / (1 + u)^((p - 1) / 2)
+#[rustfmt::skip]
+const PSI_Y: Fq2 = field_new!(
+    Fq2,
+    field_new!(Fq, BigInteger384([
+        0x3e2f585da55c9ad1,
+        0x4294213d86c18183,
+        0x382844c88b623732,
+        0x92ad2afd19103e18,
+        0x1d794e4fac7cf0b9,
+        0xbd592fc7d825ec8,
+    ])),
+    field_new!(Fq, BigInteger384([
+        0x7bcfa7a25aa30fda,
+        0xdc17dec12a927e7c,
+        0x2f088dd86b4ebef1,
+        0xd1ca2087da74d4a7,
+        0x2da2596696cebc1d,
+        0xe2b7eedbbfd87d2,
+    ])),
+);
+
 pub const G2_GENERATOR_X: Fq2 = field_new!(Fq2, G2_GENERATOR_X_C0, G2_GENERATOR_X_C1);
 pub const G2_GENERATOR_Y: Fq2 = field_new!(Fq2, G2_GENERATOR_Y_C0, G2_GENERATOR_Y_C1);
 
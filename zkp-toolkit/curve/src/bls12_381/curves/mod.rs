@@ -6,6 +6,7 @@ use crate::bls12_381::{Fq, Fq12Parameters, Fq2Parameters, Fq6Parameters};
 
 pub mod g1;
 pub mod g2;
+pub mod zcash;
 
 #[cfg(test)]
 mod tests;
@@ -13,6 +14,7 @@ mod tests;
 pub use self::{
     g1::{G1Affine, G1Projective},
     g2::{G2Affine, G2Projective},
+    zcash::ZcashEncoding,
 };
 
 pub type Bls12_381 = Bls12<Parameters>;
@@ -1,15 +1,17 @@
 #![allow(unused_imports)]
 use core::ops::{AddAssign, MulAssign};
 use math::{
+    bytes::ToBytes,
     curves::{models::SWModelParameters, AffineCurve, PairingEngine, ProjectiveCurve},
     fields::{Field, FpParameters, PrimeField, SquareRootField},
-    test_rng, One, Zero,
+    test_rng, One, UniformRand, Zero,
 };
 use rand::Rng;
 
 use crate::{
     bls12_381::{
         g1, g2, Bls12_381, Fq, Fq12, Fq2, Fr, G1Affine, G1Projective, G2Affine, G2Projective,
+        ZcashEncoding,
     },
     tests::{
         curves::{curve_tests, sw_tests},
@@ -89,6 +91,67 @@ fn test_bilinearity() {
     assert_eq!(ans3.pow(Fr::characteristic()), Fq12::one());
 }
 
+#[test]
+fn test_miller_loop_with_affine() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    let q = G2Projective::rand(&mut rng).into_affine();
+    let r = G1Projective::rand(&mut rng).into_affine();
+    let s = G2Projective::rand(&mut rng).into_affine();
+    let s_prepared = s.into();
+    let zero_prepared = G2Affine::zero().into();
+
+    // the same loop as of every pair prepared, of the points at infinity too.
+    for (p, q) in &[(p, q), (G1Affine::zero(), q), (p, G2Affine::zero())] {
+        let prepared = [(r.into(), &s_prepared), ((*p).into(), &zero_prepared)];
+        let ans1 = Bls12_381::miller_loop_with_affine(p, q, &prepared);
+        let ans2 = Bls12_381::miller_loop(&[
+            ((*p).into(), (*q).into()),
+            (r.into(), s.into()),
+            ((*p).into(), G2Affine::zero().into()),
+        ]);
+        assert_eq!(ans1, ans2);
+    }
+    let ans =
+        Bls12_381::final_exponentiation(&Bls12_381::miller_loop_with_affine(&p, &q, &[])).unwrap();
+    assert_eq!(ans, Bls12_381::pairing(p, q));
+}
+
+#[test]
+fn test_multi_pairing() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    let q = G2Projective::rand(&mut rng).into_affine();
+    let r = G1Projective::rand(&mut rng).into_affine();
+    let s = G2Projective::rand(&mut rng).into_affine();
+
+    // e(P, Q) * e(-P, Q) = 1
+    let ans = Bls12_381::multi_pairing(&[p.into(), (-p).into()], &[q.into(), q.into()]).unwrap();
+    assert_eq!(ans, Fq12::one());
+
+    let ans1 = Bls12_381::pairing(p, q) * &Bls12_381::pairing(r, s);
+    let ans2 = Bls12_381::multi_pairing(&[p.into(), r.into()], &[q.into(), s.into()]).unwrap();
+    assert_eq!(ans1, ans2);
+}
+
+#[test]
+fn test_gt_compression() {
+    let mut rng = test_rng();
+    for _ in 0..10 {
+        let a: G1Projective = rng.gen();
+        let b: G2Projective = rng.gen();
+        let gt = Bls12_381::pairing(a, b);
+        assert_eq!(gt.cyclotomic_square(), gt.square());
+
+        let compressed = gt.compress().unwrap();
+        assert_eq!(Fq12::decompress(&compressed), gt);
+        assert_eq!(
+            2 * math::to_bytes![compressed].unwrap().len(),
+            math::to_bytes![gt].unwrap().len()
+        );
+    }
+}
+
 #[test]
 fn test_g1_generator_raw() {
     let mut x = Fq::zero();
@@ -120,3 +183,165 @@ fn test_g1_generator_raw() {
         x.add_assign(&Fq::one());
     }
 }
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_zcash_encoding_generators() {
+    // G1 and G2 generators as serialized uncompressed by the zcash crates.
+    let g1 = from_hex(concat!(
+        "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+        "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1",
+    ));
+    let g2 = from_hex(concat!(
+        "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+        "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+    ));
+
+    assert_eq!(G1Affine::prime_subgroup_generator().to_zcash_bytes(), g1);
+    assert_eq!(G2Affine::prime_subgroup_generator().to_zcash_bytes(), g2);
+    assert_eq!(
+        G1Affine::from_zcash_bytes(&g1, true).unwrap(),
+        G1Affine::prime_subgroup_generator()
+    );
+    assert_eq!(
+        G2Affine::from_zcash_bytes(&g2, true).unwrap(),
+        G2Affine::prime_subgroup_generator()
+    );
+}
+
+#[test]
+fn test_zcash_encoding_round_trip() {
+    let mut rng = test_rng();
+    for _ in 0..10 {
+        let a = rng.gen::<G1Projective>().into_affine();
+        let b = rng.gen::<G2Projective>().into_affine();
+        let a_bytes = a.to_zcash_bytes();
+        let b_bytes = b.to_zcash_bytes();
+        assert_eq!(a_bytes.len(), G1Affine::ZCASH_SIZE);
+        assert_eq!(b_bytes.len(), G2Affine::ZCASH_SIZE);
+        assert_eq!(G1Affine::from_zcash_bytes(&a_bytes, true).unwrap(), a);
+        assert_eq!(G2Affine::from_zcash_bytes(&b_bytes, true).unwrap(), b);
+    }
+
+    let mut infinity = vec![0u8; G1Affine::ZCASH_SIZE];
+    infinity[0] = 0x40;
+    assert_eq!(G1Affine::zero().to_zcash_bytes(), infinity);
+    assert!(G1Affine::from_zcash_bytes(&infinity, true)
+        .unwrap()
+        .is_zero());
+    let mut infinity = vec![0u8; G2Affine::ZCASH_SIZE];
+    infinity[0] = 0x40;
+    assert_eq!(G2Affine::zero().to_zcash_bytes(), infinity);
+    assert!(G2Affine::from_zcash_bytes(&infinity, true)
+        .unwrap()
+        .is_zero());
+}
+
+#[test]
+fn test_zcash_encoding_invalid() {
+    let g1 = G1Affine::prime_subgroup_generator().to_zcash_bytes();
+    assert!(G1Affine::from_zcash_bytes(&g1[1..], true).is_err());
+
+    // The compression and the sort flags are not of uncompressed points.
+    for flag in &[0x80, 0x20] {
+        let mut bytes = g1.clone();
+        bytes[0] |= flag;
+        assert!(G1Affine::from_zcash_bytes(&bytes, false).is_err());
+    }
+
+    // The infinity flag is of all zeros after it.
+    let mut bytes = G1Affine::zero().to_zcash_bytes();
+    bytes[95] = 1;
+    assert!(G1Affine::from_zcash_bytes(&bytes, false).is_err());
+
+    // y + 1 is not on the curve, but is read unchecked.
+    let mut bytes = g1.clone();
+    bytes[95] ^= 1;
+    assert!(G1Affine::from_zcash_bytes(&bytes, true).is_err());
+    assert!(G1Affine::from_zcash_bytes(&bytes, false).is_ok());
+
+    // q encodes zero but is not reduced.
+    let mut bytes = g1.clone();
+    let modulus = from_hex("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+    bytes[48..].copy_from_slice(&modulus);
+    assert!(G1Affine::from_zcash_bytes(&bytes, false).is_err());
+
+    // A point on the twist outside the prime order subgroup.
+    let mut rng = test_rng();
+    let point = loop {
+        if let Some(p) = G2Affine::get_point_from_x(rng.gen(), false) {
+            break p;
+        }
+    };
+    assert!(!point.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G2Affine::from_zcash_bytes(&point.to_zcash_bytes(), true).is_err());
+    assert!(G2Affine::from_zcash_bytes(&point.to_zcash_bytes(), false).is_ok());
+}
+
+#[test]
+fn test_compressed_generators() {
+    // G1 and G2 generators as serialized compressed by the zcash crates.
+    let g1 = from_hex(
+        "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+    );
+    let g2 = from_hex(concat!(
+        "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+    ));
+
+    let mut bytes = vec![];
+    G1Affine::prime_subgroup_generator()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes, g1);
+    let mut bytes = vec![];
+    G2Affine::prime_subgroup_generator()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes, g2);
+
+    // -G1 and -G2 of the sort flag, and the points at infinity.
+    let mut bytes = vec![];
+    (-G1Affine::prime_subgroup_generator())
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes[0], g1[0] | 0x20);
+    assert_eq!(bytes[1..], g1[1..]);
+    let mut bytes = vec![];
+    (-G2Affine::prime_subgroup_generator())
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes[0], g2[0] | 0x20);
+    assert_eq!(bytes[1..], g2[1..]);
+
+    let mut infinity = vec![0u8; 48];
+    infinity[0] = 0xc0;
+    let mut bytes = vec![];
+    G1Affine::zero().serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes, infinity);
+    assert!(G1Affine::deserialize_compressed(&infinity[..])
+        .unwrap()
+        .is_zero());
+    let mut infinity = vec![0u8; 96];
+    infinity[0] = 0xc0;
+    let mut bytes = vec![];
+    G2Affine::zero().serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes, infinity);
+
+    assert_eq!(
+        G1Affine::deserialize_compressed(&g1[..]).unwrap(),
+        G1Affine::prime_subgroup_generator()
+    );
+    assert_eq!(
+        G2Affine::deserialize_compressed(&g2[..]).unwrap(),
+        G2Affine::prime_subgroup_generator()
+    );
+}
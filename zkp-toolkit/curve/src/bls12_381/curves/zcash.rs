@@ -0,0 +1,131 @@
+//! The uncompressed encoding of bls12_381 points of the zcash `pairing` and
+//! `bls12_381` crates, which bellman writes its parameters in.
+//!
+//! Every coordinate is big-endian, as by `to_bytes_be`. A G1 point is
+//! `x || y` and a G2 point is `x.c1 || x.c0 || y.c1 || y.c0`. The three top
+//! bits of the first byte are the flags of the compressed encoding of
+//! `serialize_compressed`: compression, infinity and sort. Uncompressed
+//! points clear the first and the last, and the point at infinity is the
+//! infinity flag followed by zeros.
+
+use math::{
+    bytes::{from_bytes_be, to_bytes_be, ToBytes},
+    curves::{
+        flags::{COMPRESSION_FLAG, INFINITY_FLAG, SORT_FLAG},
+        AffineCurve,
+    },
+    io::Result as IoResult,
+    Zero,
+};
+
+use crate::{
+    bls12_381::{Fq, Fq2, G1Affine, G2Affine},
+    Vec,
+};
+
+/// The size in bytes of an encoded base field element.
+const FQ_SIZE: usize = 48;
+
+/// A point with a zcash-compatible uncompressed byte encoding. The zcash
+/// compressed encoding is the one of `serialize_compressed`.
+pub trait ZcashEncoding: Sized {
+    /// The size in bytes of an encoded point.
+    const ZCASH_SIZE: usize;
+
+    /// Returns the uncompressed encoding of the point.
+    fn to_zcash_bytes(&self) -> Vec<u8>;
+
+    /// Reads a point from its uncompressed encoding. Fails on a wrong length,
+    /// on wrong flags and on coordinates which are not reduced modulo the
+    /// field characteristic, and, if `checked`, on points which are not on
+    /// the curve or not in the prime order subgroup.
+    fn from_zcash_bytes(bytes: &[u8], checked: bool) -> IoResult<Self>;
+}
+
+fn write_coordinate<F: ToBytes>(f: &F, out: &mut Vec<u8>) {
+    out.extend_from_slice(&to_bytes_be(f).expect("writing to a Vec does not fail"));
+}
+
+/// The coordinates of `bytes` of the flags cleared, or none of the point at
+/// infinity.
+fn read_flags(bytes: &[u8], size: usize) -> IoResult<Option<Vec<u8>>> {
+    if bytes.len() != size {
+        return Err(math::error("invalid length of an encoded point"));
+    }
+    if bytes[0] & (COMPRESSION_FLAG | SORT_FLAG) != 0 {
+        return Err(math::error("invalid flags of an uncompressed point"));
+    }
+    if bytes[0] & INFINITY_FLAG != 0 {
+        if bytes[0] != INFINITY_FLAG || bytes[1..].iter().any(|b| *b != 0) {
+            return Err(math::error("invalid encoding of the point at infinity"));
+        }
+        return Ok(None);
+    }
+    Ok(Some(bytes.to_vec()))
+}
+
+fn infinity(size: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; size];
+    bytes[0] = INFINITY_FLAG;
+    bytes
+}
+
+fn check_point<G: AffineCurve>(point: G, checked: bool) -> IoResult<G> {
+    if !checked {
+        Ok(point)
+    } else if !point.is_on_curve() {
+        Err(math::error("point is not on the curve"))
+    } else if !point.is_in_correct_subgroup_assuming_on_curve() {
+        Err(math::error("point is not in the prime order subgroup"))
+    } else {
+        Ok(point)
+    }
+}
+
+impl ZcashEncoding for G1Affine {
+    const ZCASH_SIZE: usize = 2 * FQ_SIZE;
+
+    fn to_zcash_bytes(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return infinity(Self::ZCASH_SIZE);
+        }
+        let mut bytes = Vec::with_capacity(Self::ZCASH_SIZE);
+        write_coordinate(&self.x, &mut bytes);
+        write_coordinate(&self.y, &mut bytes);
+        bytes
+    }
+
+    fn from_zcash_bytes(bytes: &[u8], checked: bool) -> IoResult<Self> {
+        let bytes = match read_flags(bytes, Self::ZCASH_SIZE)? {
+            Some(bytes) => bytes,
+            None => return Ok(Self::zero()),
+        };
+        let x: Fq = from_bytes_be(&bytes[..FQ_SIZE])?;
+        let y: Fq = from_bytes_be(&bytes[FQ_SIZE..])?;
+        check_point(Self::new(x, y, false), checked)
+    }
+}
+
+impl ZcashEncoding for G2Affine {
+    const ZCASH_SIZE: usize = 4 * FQ_SIZE;
+
+    fn to_zcash_bytes(&self) -> Vec<u8> {
+        if self.is_zero() {
+            return infinity(Self::ZCASH_SIZE);
+        }
+        let mut bytes = Vec::with_capacity(Self::ZCASH_SIZE);
+        write_coordinate(&self.x, &mut bytes);
+        write_coordinate(&self.y, &mut bytes);
+        bytes
+    }
+
+    fn from_zcash_bytes(bytes: &[u8], checked: bool) -> IoResult<Self> {
+        let bytes = match read_flags(bytes, Self::ZCASH_SIZE)? {
+            Some(bytes) => bytes,
+            None => return Ok(Self::zero()),
+        };
+        let x: Fq2 = from_bytes_be(&bytes[..2 * FQ_SIZE])?;
+        let y: Fq2 = from_bytes_be(&bytes[2 * FQ_SIZE..])?;
+        check_point(Self::new(x, y, false), checked)
+    }
+}
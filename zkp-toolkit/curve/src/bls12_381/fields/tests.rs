@@ -6,20 +6,25 @@ use core::{
 };
 use math::{
     biginteger::{BigInteger, BigInteger384},
+    bytes::ToBytes,
     fields::{
         Field, Fp12Parameters, Fp2Parameters, Fp6Parameters, FpParameters, PrimeField,
         SquareRootField,
     },
-    One, UniformRand, Zero,
+    to_bytes, One, UniformRand, Zero,
 };
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 
 use crate::{
     bls12_381::{
-        Fq, Fq12, Fq12Parameters, Fq2, Fq2Parameters, Fq6, Fq6Parameters, FqParameters, Fr,
+        g2::G2_GENERATOR_X, Fq, Fq12, Fq12Parameters, Fq2, Fq2Parameters, Fq6, Fq6Parameters,
+        FqParameters, Fr,
     },
-    tests::fields::{field_test, frobenius_test, primefield_test, sqrt_field_test},
+    tests::fields::{
+        field_test, frobenius_test, primefield_test, serialization_test, sqrt_field_test,
+    },
+    Vec,
 };
 
 pub(crate) const ITERATIONS: usize = 5;
@@ -1719,6 +1724,61 @@ fn test_fq_legendre() {
     assert_eq!(QuadraticResidue, Fq::from_repr(e).legendre());
 }
 
+#[test]
+fn test_fq2_sqrt() {
+    use math::{
+        curves::{models::SWModelParameters, AffineCurve, ProjectiveCurve},
+        LegendreSymbol::*,
+    };
+
+    use crate::bls12_381::{g2, G2Affine};
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    assert_eq!(Fq2::zero().sqrt().unwrap(), Fq2::zero());
+
+    for _ in 0..1000 {
+        // Ensure sqrt(a^2) = a or -a, and that the smaller root is returned
+        let a = Fq2::rand(&mut rng);
+        let b = a.square();
+        assert_eq!(b.legendre(), QuadraticResidue);
+        let root = b.sqrt().unwrap();
+        assert!(root == a || root == -a);
+        assert!(root <= -root);
+        assert_eq!((-a).square().sqrt().unwrap(), root);
+
+        // Ensure sqrt(a)^2 = a for random a
+        if let Some(root) = a.sqrt() {
+            assert_eq!(root.square(), a);
+        } else {
+            assert_eq!(a.legendre(), QuadraticNonResidue);
+        }
+
+        // Every element of Fq is a square in Fq2
+        let c = Fq2::new(Fq::rand(&mut rng), Fq::zero());
+        assert_eq!(c.sqrt().unwrap().square(), c);
+    }
+
+    // -1 is not a square in Fq, its roots in Fq2 are u and -u
+    let neg_one = Fq2::new(-Fq::one(), Fq::zero());
+    let root = neg_one.sqrt().unwrap();
+    assert_eq!(root.square(), neg_one);
+    assert!(root.c0.is_zero());
+
+    // The y-coordinate of G2 points must be recoverable from x
+    let generator = G2Affine::prime_subgroup_generator();
+    let points = [
+        generator,
+        generator.into_projective().double().into_affine(),
+        generator.mul(Fr::rand(&mut rng)).into_affine(),
+    ];
+    for point in points.iter() {
+        let rhs = point.x.square() * &point.x + &g2::Parameters::COEFF_B;
+        let y = rhs.sqrt().unwrap();
+        assert!(y == point.y || y == -point.y);
+    }
+}
+
 #[test]
 fn test_fq2_ordering() {
     let mut a = Fq2::new(Fq::zero(), Fq::zero());
@@ -2329,3 +2389,49 @@ fn test_fq12_mul_by_014() {
         assert_eq!(a, b);
     }
 }
+
+/// Version 1 of the `Fq2`/`Fq6`/`Fq12` byte layout. A failure here means the
+/// serialized format changed, which breaks persisted keys and proofs.
+#[test]
+fn test_fq12_bytes_layout_v1() {
+    const FQ_SIZE: usize = 48;
+
+    // the k-th Fq coefficient in tower order is k + 1
+    let fq2 = |k: u64| Fq2::new(Fq::from(2 * k + 1), Fq::from(2 * k + 2));
+    let a = Fq12::new(
+        Fq6::new(fq2(0), fq2(1), fq2(2)),
+        Fq6::new(fq2(3), fq2(4), fq2(5)),
+    );
+    let mut expected = vec![0u8; 12 * FQ_SIZE];
+    for k in 0..12 {
+        expected[k * FQ_SIZE] = k as u8 + 1;
+    }
+    assert_eq!(to_bytes![a].unwrap(), expected);
+    assert_eq!(postcard::to_allocvec(&a).unwrap(), expected);
+    assert_eq!(to_bytes![a.c0].unwrap(), expected[..6 * FQ_SIZE].to_vec());
+
+    // the x-coordinate of the G2 generator
+    let x = from_hex(concat!(
+        "b8bd21c1c85680d4efbb05a82603ac0b77d1e37a640b51b4",
+        "023b40fad47ae4c65110c52d27050826910a8ff0b2a24a02",
+        "7e2b045d057dace5575d941312f14c3349507fdcbb61dab5",
+        "1ab62099d0d06b59654f2788a0d3ac7d609f7152602be013",
+    ));
+    assert_eq!(to_bytes![G2_GENERATOR_X].unwrap(), x);
+    let decoded: Fq2 = postcard::from_bytes(&x).unwrap();
+    assert_eq!(decoded, G2_GENERATOR_X);
+}
+
+#[test]
+fn test_extension_serialization() {
+    serialization_test::<Fq2>();
+    serialization_test::<Fq6>();
+    serialization_test::<Fq12>();
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
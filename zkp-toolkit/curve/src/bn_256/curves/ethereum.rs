@@ -0,0 +1,124 @@
+//! The encoding of bn_256 points used by the Ethereum precompiles for point
+//! addition, scalar multiplication and the pairing check (EIP-196, EIP-197).
+//!
+//! Every coordinate is a 32-byte big-endian integer. A G1 point is `x || y`
+//! and a G2 point is `x.c1 || x.c0 || y.c1 || y.c0`, that is, with the
+//! imaginary part of each Fq2 coordinate first. The point at infinity is
+//! encoded as all zeros.
+
+use math::{
+    bytes::{FromBytes, ToBytes},
+    curves::AffineCurve,
+    io::Result as IoResult,
+    Zero,
+};
+
+use crate::{
+    bn_256::{Fq, Fq2, G1Affine, G2Affine},
+    Vec,
+};
+
+/// The size in bytes of an encoded base field element.
+const FQ_SIZE: usize = 32;
+
+/// A point with an Ethereum-compatible byte encoding.
+pub trait EthereumEncoding: Sized {
+    /// The size in bytes of an encoded point.
+    const ETHEREUM_SIZE: usize;
+
+    /// Returns the encoding of the point.
+    fn to_ethereum_bytes(&self) -> Vec<u8>;
+
+    /// Reads a point from its encoding. Fails on a wrong length, on
+    /// coordinates which are not reduced modulo the field characteristic,
+    /// and on points which are not in the prime order subgroup.
+    fn from_ethereum_bytes(bytes: &[u8]) -> IoResult<Self>;
+}
+
+fn write_fq(fq: &Fq, out: &mut Vec<u8>) {
+    let mut bytes = Vec::with_capacity(FQ_SIZE);
+    fq.write(&mut bytes)
+        .expect("writing to a Vec does not fail");
+    bytes.reverse();
+    out.extend_from_slice(&bytes);
+}
+
+fn read_fq(bytes: &[u8]) -> IoResult<Fq> {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    Fq::read(&bytes[..])
+}
+
+fn read_fq2(bytes: &[u8]) -> IoResult<Fq2> {
+    let c1 = read_fq(&bytes[..FQ_SIZE])?;
+    let c0 = read_fq(&bytes[FQ_SIZE..])?;
+    Ok(Fq2::new(c0, c1))
+}
+
+fn check_point<G: AffineCurve>(point: G, on_curve: bool) -> IoResult<G> {
+    if !on_curve {
+        Err(math::error("point is not on the curve"))
+    } else if !point.is_in_correct_subgroup_assuming_on_curve() {
+        Err(math::error("point is not in the prime order subgroup"))
+    } else {
+        Ok(point)
+    }
+}
+
+impl EthereumEncoding for G1Affine {
+    const ETHEREUM_SIZE: usize = 2 * FQ_SIZE;
+
+    fn to_ethereum_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::ETHEREUM_SIZE);
+        if self.is_zero() {
+            bytes.resize(Self::ETHEREUM_SIZE, 0);
+        } else {
+            write_fq(&self.x, &mut bytes);
+            write_fq(&self.y, &mut bytes);
+        }
+        bytes
+    }
+
+    fn from_ethereum_bytes(bytes: &[u8]) -> IoResult<Self> {
+        if bytes.len() != Self::ETHEREUM_SIZE {
+            return Err(math::error("invalid length of an encoded G1 point"));
+        }
+        let x = read_fq(&bytes[..FQ_SIZE])?;
+        let y = read_fq(&bytes[FQ_SIZE..])?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(Self::zero());
+        }
+        let point = Self::new(x, y, false);
+        check_point(point, point.is_on_curve())
+    }
+}
+
+impl EthereumEncoding for G2Affine {
+    const ETHEREUM_SIZE: usize = 4 * FQ_SIZE;
+
+    fn to_ethereum_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::ETHEREUM_SIZE);
+        if self.is_zero() {
+            bytes.resize(Self::ETHEREUM_SIZE, 0);
+        } else {
+            write_fq(&self.x.c1, &mut bytes);
+            write_fq(&self.x.c0, &mut bytes);
+            write_fq(&self.y.c1, &mut bytes);
+            write_fq(&self.y.c0, &mut bytes);
+        }
+        bytes
+    }
+
+    fn from_ethereum_bytes(bytes: &[u8]) -> IoResult<Self> {
+        if bytes.len() != Self::ETHEREUM_SIZE {
+            return Err(math::error("invalid length of an encoded G2 point"));
+        }
+        let x = read_fq2(&bytes[..2 * FQ_SIZE])?;
+        let y = read_fq2(&bytes[2 * FQ_SIZE..])?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(Self::zero());
+        }
+        let point = Self::new(x, y, false);
+        check_point(point, point.is_on_curve())
+    }
+}
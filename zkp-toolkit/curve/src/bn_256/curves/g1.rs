@@ -1,10 +1,9 @@
 use crate::{
-    biginteger::BigInteger256,
     bn_256,
     bn_256::*,
     curves::{
         bn,
-        models::{ModelParameters, SWModelParameters},
+        models::{glv::GLVParameters, ModelParameters, SWModelParameters},
     },
     field_new, Zero,
 };
@@ -22,12 +21,12 @@ impl ModelParameters for Parameters {
 
 impl SWModelParameters for Parameters {
     /// COEFF_A = 0
-    const COEFF_A: Fq = field_new!(Fq, BigInteger256([0x0, 0x0, 0x0, 0x0]));
+    const COEFF_A: Fq = field_new!(Fq, BigInteger([0x0, 0x0, 0x0, 0x0]));
 
     /// TODO
     const COEFF_B: Fq = field_new!(
         Fq,
-        BigInteger256([
+        BigInteger([
             0x7a17caa950ad28d7,
             0x1f6ac17ae15521b9,
             0x334bea4e696bd284,
@@ -38,14 +37,13 @@ impl SWModelParameters for Parameters {
     /// TODO
     const COFACTOR: &'static [u64] = &[0x1];
 
-    /// TODO
-    /// COFACTOR_INV = COFACTOR^{-1} mod r
+    /// COFACTOR_INV = COFACTOR^{-1} mod r = 1
     #[rustfmt::skip]
-    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger256([
-        0x0,
-        0x0,
-        0x0,
-        0x0,
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger([
+        0xac96341c4ffffffb,
+        0x36fc76959f60cd29,
+        0x666ea36f7879462e,
+        0x0e0a77c19a07df2f,
     ]));
 
     /// AFFINE_GENERATOR_COEFFS = (G1_GENERATOR_X, G1_GENERATOR_Y)
@@ -56,14 +54,68 @@ impl SWModelParameters for Parameters {
     fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
         Self::BaseField::zero()
     }
+
+    /// The endomorphism (x, y) -> (beta * x, y) acts as multiplication by
+    /// lambda, with the short lattice basis
+    /// (a1, b1) = (147946756881789319000765030803803410728, -9931322734385697763),
+    /// (a2, b2) = (9931322734385697763, 147946756881789319010696353538189108491).
+    #[inline(always)]
+    fn glv() -> Option<GLVParameters<Self>> {
+        Some(GLVParameters {
+            beta: GLV_BETA,
+            lambda: GLV_LAMBDA,
+            b1: GLV_B1,
+            b2: GLV_B2,
+            g1: &[0x5398fd0300ff6565, 0x4ccef014a773d2d2, 0x2],
+            g2: &[0xd91d232ec7e0b3d7, 0x2],
+        })
+    }
 }
 
+/// GLV_BETA = 21888242871839275220042445260109153167277707414472061641714758635765020556616,
+/// a cube root of unity in Fq.
+#[rustfmt::skip]
+const GLV_BETA: Fq = field_new!(Fq, BigInteger([
+    0x3350c88e13e80b9c,
+    0x7dce557cdb5e56b9,
+    0x6001b4b8b615564a,
+    0x2682e617020217e0,
+]));
+
+/// GLV_LAMBDA = 21888242871839275217838484774961031246154997185409878258781734729429964517155,
+/// the cube root of unity in Fr matching GLV_BETA.
+#[rustfmt::skip]
+const GLV_LAMBDA: Fr = field_new!(Fr, BigInteger([
+    0x0363f29955fcd653,
+    0x73e7950b5fc1e200,
+    0xc5fce83e576d9d24,
+    0x059c805da1c3a4d4,
+]));
+
+/// GLV_B1 = -9931322734385697763 mod r
+#[rustfmt::skip]
+const GLV_B1: Fr = field_new!(Fr, BigInteger([
+    0x1f7cc8d147e0b3d8,
+    0x71b98e53f86c230d,
+    0xc13246fc769bb9eb,
+    0x1b76169efc0e7649,
+]));
+
+/// GLV_B2 = 147946756881789319010696353538189108491
+#[rustfmt::skip]
+const GLV_B2: Fr = field_new!(Fr, BigInteger([
+    0x5001b1ef4f009a9b,
+    0xbd5e82c846474116,
+    0x5fec7030d27930cf,
+    0x03e59388fe18f17e,
+]));
+
 // Generator of G1
 // x = 1
 // y = 2
 pub const G1_GENERATOR_X: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0xd35d438dc58f0d9d,
         0x0a78eb28f5c70b3d,
         0x666ea36f7879462c,
@@ -73,7 +125,7 @@ pub const G1_GENERATOR_X: Fq = field_new!(
 
 pub const G1_GENERATOR_Y: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0xa6ba871b8b1e1b3a,
         0x14f1d651eb8e167b,
         0xccdd46def0f28c58,
@@ -1,5 +1,4 @@
 use crate::{
-    biginteger::BigInteger256,
     bn_256,
     bn_256::*,
     curves::{
@@ -30,7 +29,7 @@ impl SWModelParameters for Parameters {
         Fq2,
         field_new!(
             Fq,
-            BigInteger256([
+            BigInteger([
                 0x3bf938e377b802a8,
                 0x020b1b273633535d,
                 0x26b7edf049755260,
@@ -39,7 +38,7 @@ impl SWModelParameters for Parameters {
         ),
         field_new!(
             Fq,
-            BigInteger256([
+            BigInteger([
                 0x38e7ecccd1dcff67,
                 0x65f0b37d93ce0d3e,
                 0xd749d0dd22ac00aa,
@@ -56,14 +55,14 @@ impl SWModelParameters for Parameters {
         0x30644e72e131a029,
     ];
 
-    /// TODO
     /// COFACTOR_INV = COFACTOR^{-1} mod r
+    /// = 10944121435919637613327163357776759465618812564592884533313067514031822496649
     #[rustfmt::skip]
-    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger256([
-        0x0,
-        0x0,
-        0x0,
-        0x0,
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger([
+        0x7fff17d53ff2895e,
+        0xd0617390cf7919e5,
+        0xb9af426b22d0eb61,
+        0x270485e31bd72a4d,
     ]));
 
     /// AFFINE_GENERATOR_COEFFS = (G1_GENERATOR_X, G1_GENERATOR_Y)
@@ -88,7 +87,7 @@ pub const G2_GENERATOR_Y: Fq2 = field_new!(Fq2, G2_GENERATOR_Y_C0, G2_GENERATOR_
 //     + 8495653923123431417604973247489272438418190587263600148770280649306958101930
 pub const G2_GENERATOR_X_C0: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0x8e83b5d102bc2026,
         0xdceb1935497b0172,
         0xfbb8264797811adf,
@@ -98,7 +97,7 @@ pub const G2_GENERATOR_X_C0: Fq = field_new!(
 
 pub const G2_GENERATOR_X_C1: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0xafb4737da84c6140,
         0x6043dd5a5802d8c4,
         0x09e950fc52a02f86,
@@ -108,7 +107,7 @@ pub const G2_GENERATOR_X_C1: Fq = field_new!(
 
 pub const G2_GENERATOR_Y_C0: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0x619dfa9d886be9f6,
         0xfe7fd297f59e9b78,
         0xff9e1a62231b7dfe,
@@ -118,7 +117,7 @@ pub const G2_GENERATOR_Y_C0: Fq = field_new!(
 
 pub const G2_GENERATOR_Y_C1: Fq = field_new!(
     Fq,
-    BigInteger256([
+    BigInteger([
         0x64095b56c71856ee,
         0xdc57f922327d3cbb,
         0x55f935be33351076,
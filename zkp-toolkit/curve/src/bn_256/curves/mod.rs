@@ -1,11 +1,11 @@
 use math::{
-    biginteger::BigInteger256,
     curves::bn::{Bn, BnParameters},
     field_new,
 };
 
-use crate::bn_256::{Fq, Fq12Parameters, Fq2, Fq2Parameters, Fq6Parameters};
+use crate::bn_256::{BigInteger, Fq, Fq12Parameters, Fq2, Fq2Parameters, Fq6Parameters};
 
+pub mod ethereum;
 pub mod g1;
 pub mod g2;
 
@@ -13,6 +13,7 @@ pub mod g2;
 mod tests;
 
 pub use self::{
+    ethereum::EthereumEncoding,
     g1::{G1Affine, G1Projective},
     g2::{G2Affine, G2Projective},
 };
@@ -35,7 +36,7 @@ impl BnParameters for Parameters {
         Fq2,
         field_new!(
             Fq,
-            BigInteger256([
+            BigInteger([
                 0xe4bbdd0c2936b629,
                 0xbb30f162e133bacb,
                 0x31a9d1b6f9645366,
@@ -44,7 +45,7 @@ impl BnParameters for Parameters {
         ),
         field_new!(
             Fq,
-            BigInteger256([
+            BigInteger([
                 0xa1d77ce45ffe77c7,
                 0x07affd117826d1db,
                 0x6d16bd27bb7edc6b,
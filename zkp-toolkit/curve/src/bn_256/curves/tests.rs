@@ -1,13 +1,18 @@
 use core::ops::{AddAssign, MulAssign};
 use math::{
-    curves::{models::SWModelParameters, AffineCurve, PairingEngine},
-    fields::{Field, PrimeField, SquareRootField},
-    test_rng, One, Zero,
+    biginteger::BigInteger,
+    curves::{models::SWModelParameters, AffineCurve, PairingEngine, ProjectiveCurve},
+    fields::{Field, FpParameters, PrimeField, SquareRootField},
+    msm::VariableBaseMSM,
+    test_rng, One, UniformRand, Zero,
 };
 use rand::Rng;
 
 use crate::{
-    bn_256::{g1, g2, Bn_256, Fq, Fq12, Fr, G1Affine, G1Projective, G2Affine, G2Projective},
+    bn_256::{
+        g1, g2, Bn_256, EthereumEncoding, Fq, Fq12, Fr, G1Affine, G1Projective, G2Affine,
+        G2Projective,
+    },
     tests::{
         curves::{curve_tests, sw_tests},
         groups::group_test,
@@ -88,6 +93,48 @@ fn test_bilinearity() {
     assert_eq!(ans3.pow(Fr::characteristic()), Fq12::one());
 }
 
+#[test]
+fn test_miller_loop_with_affine() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    let q = G2Projective::rand(&mut rng).into_affine();
+    let r = G1Projective::rand(&mut rng).into_affine();
+    let s = G2Projective::rand(&mut rng).into_affine();
+    let s_prepared = s.into();
+    let zero_prepared = G2Affine::zero().into();
+
+    // the same loop as of every pair prepared, of the points at infinity too.
+    for (p, q) in &[(p, q), (G1Affine::zero(), q), (p, G2Affine::zero())] {
+        let prepared = [(r.into(), &s_prepared), ((*p).into(), &zero_prepared)];
+        let ans1 = Bn_256::miller_loop_with_affine(p, q, &prepared);
+        let ans2 = Bn_256::miller_loop(&[
+            ((*p).into(), (*q).into()),
+            (r.into(), s.into()),
+            ((*p).into(), G2Affine::zero().into()),
+        ]);
+        assert_eq!(ans1, ans2);
+    }
+    let ans = Bn_256::final_exponentiation(&Bn_256::miller_loop_with_affine(&p, &q, &[])).unwrap();
+    assert_eq!(ans, Bn_256::pairing(p, q));
+}
+
+#[test]
+fn test_multi_pairing() {
+    let mut rng = test_rng();
+    let p = G1Projective::rand(&mut rng).into_affine();
+    let q = G2Projective::rand(&mut rng).into_affine();
+    let r = G1Projective::rand(&mut rng).into_affine();
+    let s = G2Projective::rand(&mut rng).into_affine();
+
+    // e(P, Q) * e(-P, Q) = 1
+    let ans = Bn_256::multi_pairing(&[p.into(), (-p).into()], &[q.into(), q.into()]).unwrap();
+    assert_eq!(ans, Fq12::one());
+
+    let ans1 = Bn_256::pairing(p, q) * &Bn_256::pairing(r, s);
+    let ans2 = Bn_256::multi_pairing(&[p.into(), r.into()], &[q.into(), s.into()]).unwrap();
+    assert_eq!(ans1, ans2);
+}
+
 #[test]
 fn test_g1_generator_raw() {
     let mut x = Fq::zero();
@@ -121,3 +168,248 @@ fn test_g1_generator_raw() {
         x.add_assign(&Fq::one());
     }
 }
+
+#[test]
+fn test_g1_glv_endomorphism() {
+    let glv = g1::Parameters::glv().unwrap();
+    assert_eq!(glv.beta.pow(&[3]), Fq::one());
+    assert_ne!(glv.beta, Fq::one());
+    assert_eq!(glv.lambda.pow(&[3]), Fr::one());
+    assert_ne!(glv.lambda, Fr::one());
+
+    let g = G1Affine::prime_subgroup_generator();
+    let phi_g = G1Affine::new(g.x * &glv.beta, g.y, false);
+    assert_eq!(g.mul(glv.lambda).into_affine(), phi_g);
+
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let k: Fr = rng.gen();
+        let ((neg1, k1), (neg2, k2)) = glv.decompose(&k);
+        assert!(k1.num_bits() <= 128);
+        assert!(k2.num_bits() <= 128);
+
+        let signed = |neg, k| {
+            if neg {
+                -Fr::from_repr(k)
+            } else {
+                Fr::from_repr(k)
+            }
+        };
+        assert_eq!(signed(neg1, k1) + &(signed(neg2, k2) * &glv.lambda), k);
+    }
+}
+
+#[test]
+fn test_g1_mul_glv() {
+    let glv = g1::Parameters::glv().unwrap();
+    let mut rng = test_rng();
+    let half = Fr::one().double().inverse().unwrap();
+
+    let mut scalars = vec![
+        Fr::zero(),
+        Fr::one(),
+        -Fr::one(),
+        half,
+        half + &Fr::one(),
+        glv.lambda,
+        -glv.lambda,
+        glv.lambda + &Fr::one(),
+        glv.lambda.square(),
+    ];
+    let mut k = Fr::zero();
+    for _ in 0..100 {
+        k -= &Fr::one();
+        scalars.push(k);
+        scalars.push(-k);
+    }
+    scalars.extend((0..2000).map(|_| rng.gen::<Fr>()));
+
+    let p: G1Projective = rng.gen();
+    let p_affine = p.into_affine();
+    for k in scalars {
+        let expected = p_affine.mul(k.into_repr());
+        assert_eq!(p.mul_glv(&k), expected);
+        assert_eq!(p.mul(k.into_repr()), expected);
+    }
+
+    assert!(G1Projective::zero().mul_glv(&rng.gen()).is_zero());
+    // Scalars which are not reduced fall back to double-and-add.
+    assert!(p.mul(<Fr as PrimeField>::Params::MODULUS).is_zero());
+}
+
+#[test]
+fn test_g1_glv_msm() {
+    const SAMPLES: usize = 1 << 8;
+
+    let mut rng = test_rng();
+    let mut scalars = (0..SAMPLES)
+        .map(|_| rng.gen::<Fr>().into_repr())
+        .collect::<Vec<_>>();
+    scalars[0] = Fr::one().into_repr();
+    scalars[1] = (-Fr::one()).into_repr();
+    scalars[2] = Fr::zero().into_repr();
+    let bases = (0..SAMPLES)
+        .map(|_| rng.gen::<G1Projective>().into_affine())
+        .collect::<Vec<_>>();
+
+    let naive = bases
+        .iter()
+        .zip(&scalars)
+        .fold(G1Projective::zero(), |acc, (b, s)| acc + &b.mul(*s));
+    let glv = VariableBaseMSM::multi_scalar_mul(&bases, &scalars);
+    let plain = VariableBaseMSM::multi_scalar_mul_chunked(&bases, &scalars, SAMPLES);
+
+    assert_eq!(naive.into_affine(), glv.into_affine());
+    assert_eq!(naive.into_affine(), plain.into_affine());
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_ethereum_encoding_generators() {
+    // G1 and G2 generators as given in EIP-196 and EIP-197.
+    let g1 = from_hex(concat!(
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+    ));
+    let g2 = from_hex(concat!(
+        "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+        "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+        "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+        "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+    ));
+
+    assert_eq!(G1Affine::prime_subgroup_generator().to_ethereum_bytes(), g1);
+    assert_eq!(G2Affine::prime_subgroup_generator().to_ethereum_bytes(), g2);
+    assert_eq!(
+        G1Affine::from_ethereum_bytes(&g1).unwrap(),
+        G1Affine::prime_subgroup_generator()
+    );
+    assert_eq!(
+        G2Affine::from_ethereum_bytes(&g2).unwrap(),
+        G2Affine::prime_subgroup_generator()
+    );
+}
+
+#[test]
+fn test_ethereum_encoding_round_trip() {
+    let mut rng = test_rng();
+    for _ in 0..10 {
+        let a = rng.gen::<G1Projective>().into_affine();
+        let b = rng.gen::<G2Projective>().into_affine();
+        let a_bytes = a.to_ethereum_bytes();
+        let b_bytes = b.to_ethereum_bytes();
+        assert_eq!(a_bytes.len(), G1Affine::ETHEREUM_SIZE);
+        assert_eq!(b_bytes.len(), G2Affine::ETHEREUM_SIZE);
+        assert_eq!(G1Affine::from_ethereum_bytes(&a_bytes).unwrap(), a);
+        assert_eq!(G2Affine::from_ethereum_bytes(&b_bytes).unwrap(), b);
+    }
+
+    let zero = vec![0u8; G1Affine::ETHEREUM_SIZE];
+    assert_eq!(G1Affine::zero().to_ethereum_bytes(), zero);
+    assert!(G1Affine::from_ethereum_bytes(&zero).unwrap().is_zero());
+    let zero = vec![0u8; G2Affine::ETHEREUM_SIZE];
+    assert_eq!(G2Affine::zero().to_ethereum_bytes(), zero);
+    assert!(G2Affine::from_ethereum_bytes(&zero).unwrap().is_zero());
+}
+
+#[test]
+fn test_ethereum_encoding_invalid() {
+    let g1 = G1Affine::prime_subgroup_generator().to_ethereum_bytes();
+    assert!(G1Affine::from_ethereum_bytes(&g1[1..]).is_err());
+
+    // (1, 3) is not on the curve.
+    let mut bytes = g1.clone();
+    bytes[63] = 3;
+    assert!(G1Affine::from_ethereum_bytes(&bytes).is_err());
+
+    // y + q encodes the same residue but is not reduced.
+    let mut bytes = g1.clone();
+    let modulus = from_hex("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
+    bytes[32..].copy_from_slice(&modulus);
+    bytes[63] += 2;
+    assert!(G1Affine::from_ethereum_bytes(&bytes).is_err());
+
+    // A point on the twist outside the prime order subgroup.
+    let mut rng = test_rng();
+    let point = loop {
+        if let Some(p) = G2Affine::get_point_from_x(rng.gen(), false) {
+            break p;
+        }
+    };
+    assert!(!point.is_in_correct_subgroup_assuming_on_curve());
+    assert!(G2Affine::from_ethereum_bytes(&point.to_ethereum_bytes()).is_err());
+}
+
+#[test]
+fn test_final_exponentiation() {
+    // (q^12 - 1) / r
+    #[rustfmt::skip]
+    const EXPONENT: [u64; 44] = [
+    0x86964b64ca86f120, 0x40a4efb7e54523a4, 0x837fa97896e84abb, 0x361102b6b9b2b918,
+    0xc0de81def35692da, 0xbe04c7e8a6c3c760, 0xd766f9c9d570bb7f, 0xc230974d83561841,
+    0x5bba1668c3be69a3, 0x7f3811c410526294, 0x29baee7ddadda71c, 0xbf813b8d145da900,
+    0x641bbadf423f9a2c, 0xa80bb4ea44eacc5e, 0xcd65664814fde37c, 0x4a0364b9580291d2,
+    0xee93dfb10826f0dd, 0x6b42db8dc5514724, 0xbb10cf430b0f3785, 0x40494e406f804216,
+    0x55cfe107acf3aafb, 0x2088ec80e0ebae87, 0x846a3ed011a337a0, 0x48a45a4a1e3a5195,
+    0xe5664568dfc50e16, 0xab6a41294c0cc4eb, 0x82d0d602d268c7da, 0x6668449aed3cc48a,
+    0x5062cd0fb2015dfc, 0x7f2940a8b1ddb3d1, 0x77f5b63a2a226448, 0xfef0781361e443ae,
+    0xf977870e88d5c6c8, 0x790364a61f676baa, 0x5887e72eceaddea3, 0x1377e563a09a1b70,
+    0xc54efee1bd8c3b2, 0x3ec3d15ad524d8f7, 0xdaf15466b2383a5d, 0xe1e30a73bb94fec0,
+    0x6a1c71015f3f7be2, 0x842d43bf6369b1ff, 0x20fddadf107d20bc, 0x2f4b6dc970,
+    ];
+
+    let mut rng = test_rng();
+    for _ in 0..5 {
+        let f: Fq12 = rng.gen();
+        assert_eq!(
+            Bn_256::final_exponentiation(&f).unwrap(),
+            f.pow(&EXPONENT[..])
+        );
+    }
+}
+
+#[test]
+fn test_gt_compression() {
+    let mut rng = test_rng();
+    for _ in 0..10 {
+        let a: G1Projective = rng.gen();
+        let b: G2Projective = rng.gen();
+        let gt = Bn_256::pairing(a, b);
+        assert_eq!(Fq12::decompress(&gt.compress().unwrap()), gt);
+    }
+
+    assert!(Fq12::one().compress().unwrap().is_zero());
+    assert!((-Fq12::one()).compress().is_none());
+}
+
+#[test]
+fn test_compressed_generator() {
+    // (1, 2), of the smaller y-coordinate: the compression flag alone.
+    let mut g1 = vec![0u8; 32];
+    g1[0] = 0x80;
+    g1[31] = 1;
+    let mut bytes = vec![];
+    G1Affine::prime_subgroup_generator()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert_eq!(bytes, g1);
+
+    // (1, -2), and the point at infinity of the infinity flag alone.
+    let mut bytes = vec![];
+    (-G1Affine::prime_subgroup_generator())
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    g1[0] = 0xc0;
+    assert_eq!(bytes, g1);
+    let mut infinity = vec![0u8; 32];
+    infinity[0] = 0x40;
+    let mut bytes = vec![];
+    G1Affine::zero().serialize_compressed(&mut bytes).unwrap();
+    assert_eq!(bytes, infinity);
+}
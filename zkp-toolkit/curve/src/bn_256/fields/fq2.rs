@@ -1,5 +1,5 @@
 use crate::bn_256::*;
-use math::{biginteger::BigInteger256 as BigInteger, field_new, fields::*};
+use math::{field_new, fields::*};
 
 pub type Fq2 = Fp2<Fq2Parameters>;
 
@@ -1,5 +1,5 @@
 use crate::bn_256::*;
-use math::{biginteger::BigInteger256 as BigInteger, field_new, fields::*};
+use math::{field_new, fields::*};
 
 pub type Fq6 = Fp6<Fq6Parameters>;
 
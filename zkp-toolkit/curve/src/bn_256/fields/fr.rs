@@ -1,18 +1,31 @@
+#[cfg(feature = "const_generic_fp")]
+use math::{biginteger::BigInt, fields::Fp};
+#[cfg(not(feature = "const_generic_fp"))]
 use math::{
-    biginteger::BigInteger256 as BigInteger,
-    fields::{Fp256, Fp256Parameters, FpParameters},
+    biginteger::BigInteger256,
+    fields::{Fp256, Fp256Parameters},
 };
+use math::fields::FpParameters;
 
+use crate::bn_256::BigInteger;
+
+#[cfg(not(feature = "const_generic_fp"))]
 pub type Fr = Fp256<FrParameters>;
+#[cfg(feature = "const_generic_fp")]
+pub type Fr = Fp<FrParameters, 4>;
 
 pub struct FrParameters;
 
+#[cfg(not(feature = "const_generic_fp"))]
 impl Fp256Parameters for FrParameters {}
 
 impl FpParameters for FrParameters {
-    type BigInt = BigInteger;
+    #[cfg(not(feature = "const_generic_fp"))]
+    type BigInt = BigInteger256;
+    #[cfg(feature = "const_generic_fp")]
+    type BigInt = BigInt<4>;
 
-    const MODULUS: BigInteger = BigInteger([
+    const MODULUS: Self::BigInt = BigInteger([
         0x43e1f593f0000001,
         0x2833e84879b97091,
         0xb85045b68181585d,
@@ -25,14 +38,14 @@ impl FpParameters for FrParameters {
 
     const REPR_SHAVE_BITS: u32 = 2;
 
-    const R: BigInteger = BigInteger([
+    const R: Self::BigInt = BigInteger([
         0xac96341c4ffffffb,
         0x36fc76959f60cd29,
         0x666ea36f7879462e,
         0xe0a77c19a07df2f,
     ]);
 
-    const R2: BigInteger = BigInteger([
+    const R2: Self::BigInt = BigInteger([
         0x1bb8e645ae216da7,
         0x53fe3ab1e35c59e3,
         0x8c49833d53bb8085,
@@ -42,7 +55,7 @@ impl FpParameters for FrParameters {
     // 0x6586864b4c6911b3c2e1f593efffffff
     const INV: u64 = 14042775128853446655;
 
-    const GENERATOR: BigInteger = BigInteger([
+    const GENERATOR: Self::BigInt = BigInteger([
         0x3057819e4fffffdb,
         0x307f6d866832bb01,
         0x5c65ec9f484e3a89,
@@ -51,7 +64,7 @@ impl FpParameters for FrParameters {
 
     const TWO_ADICITY: u32 = 28;
 
-    const ROOT_OF_UNITY: BigInteger = BigInteger([
+    const ROOT_OF_UNITY: Self::BigInt = BigInteger([
         0x9632c7c5b639feb8,
         0x985ce3400d0ff299,
         0xb2dd880001b0ecd8,
@@ -59,7 +72,7 @@ impl FpParameters for FrParameters {
     ]);
 
     // use in (also bellman) - SqrtFiled - legendre
-    const MODULUS_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+    const MODULUS_MINUS_ONE_DIV_TWO: Self::BigInt = BigInteger([
         11669102379873075200u64,
         10671829228508198984u64,
         15863968012492123182u64,
@@ -67,7 +80,7 @@ impl FpParameters for FrParameters {
     ]);
 
     // use in SqrtField - sqrt
-    const T: BigInteger = BigInteger([
+    const T: Self::BigInt = BigInteger([
         11211439779908376895u64,
         1735440370612733063u64,
         1376415503089949544u64,
@@ -75,7 +88,7 @@ impl FpParameters for FrParameters {
     ]);
 
     // use in SqrtField - sqrt
-    const T_MINUS_ONE_DIV_TWO: BigInteger = BigInteger([
+    const T_MINUS_ONE_DIV_TWO: Self::BigInt = BigInteger([
         14829091926808964255u64,
         867720185306366531u64,
         688207751544974772u64,
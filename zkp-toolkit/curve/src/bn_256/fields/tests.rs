@@ -1,13 +1,24 @@
 use math::{
-    fields::{Field, Fp2Parameters, Fp6Parameters},
-    One, UniformRand,
+    biginteger::{BigInt, BigInteger256},
+    bytes::ToBytes,
+    fields::{
+        Field, Fp, Fp256, Fp256Parameters, Fp2Parameters, Fp6Parameters, FpParameters,
+        PrimeField, SquareRootField,
+    },
+    to_bytes, One, UniformRand,
 };
-use rand::SeedableRng;
+use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
 
 use crate::{
-    bn_256::{Fq, Fq12, Fq2, Fq2Parameters, Fq6, Fq6Parameters, Fr},
-    tests::fields::{field_test, frobenius_test, primefield_test, sqrt_field_test},
+    bn_256::{
+        g2::G2_GENERATOR_X, Fq, Fq12, Fq2, Fq2Parameters, Fq6, Fq6Parameters, FqParameters, Fr,
+        FrParameters,
+    },
+    tests::fields::{
+        field_test, frobenius_test, primefield_test, serialization_test, sqrt_field_test,
+    },
+    Vec,
 };
 
 pub(crate) const ITERATIONS: usize = 5;
@@ -90,3 +101,175 @@ fn test_frob_coeffs() {
 
     assert_eq!(Fq6Parameters::FROBENIUS_COEFF_FP6_C1[0], Fq2::one());
 }
+
+/// Mirrors the parameters of a bn_256 field for both the `Fp256` macro type
+/// and the limb-generic `Fp<P, 4>`, so the two can be run side by side
+/// whichever one the `const_generic_fp` feature selects for `Fr` and `Fq`.
+macro_rules! mirror_fp_parameters {
+    ($macro_params:ident, $const_params:ident, $params:ty) => {
+        struct $macro_params;
+
+        impl Fp256Parameters for $macro_params {}
+
+        mirror_fp_parameters!(@impl $macro_params, BigInteger256, $params);
+
+        struct $const_params;
+
+        mirror_fp_parameters!(@impl $const_params, BigInt<4>, $params);
+    };
+    (@impl $name:ident, $repr:ty, $params:ty) => {
+        impl FpParameters for $name {
+            type BigInt = $repr;
+
+            const MODULUS: $repr = <$repr>::new(<$params as FpParameters>::MODULUS.0);
+            const MODULUS_BITS: u32 = <$params as FpParameters>::MODULUS_BITS;
+            const REPR_SHAVE_BITS: u32 = <$params as FpParameters>::REPR_SHAVE_BITS;
+            const R: $repr = <$repr>::new(<$params as FpParameters>::R.0);
+            const R2: $repr = <$repr>::new(<$params as FpParameters>::R2.0);
+            const INV: u64 = <$params as FpParameters>::INV;
+            const GENERATOR: $repr = <$repr>::new(<$params as FpParameters>::GENERATOR.0);
+            const CAPACITY: u32 = <$params as FpParameters>::CAPACITY;
+            const TWO_ADICITY: u32 = <$params as FpParameters>::TWO_ADICITY;
+            const T: $repr = <$repr>::new(<$params as FpParameters>::T.0);
+            const ROOT_OF_UNITY: $repr =
+                <$repr>::new(<$params as FpParameters>::ROOT_OF_UNITY.0);
+            const T_MINUS_ONE_DIV_TWO: $repr =
+                <$repr>::new(<$params as FpParameters>::T_MINUS_ONE_DIV_TWO.0);
+            const MODULUS_MINUS_ONE_DIV_TWO: $repr =
+                <$repr>::new(<$params as FpParameters>::MODULUS_MINUS_ONE_DIV_TWO.0);
+        }
+    };
+}
+
+mirror_fp_parameters!(MacroFrParameters, ConstFrParameters, FrParameters);
+mirror_fp_parameters!(MacroFqParameters, ConstFqParameters, FqParameters);
+
+/// Number of random operations each differential test runs through both
+/// implementations; every `DIFFERENTIAL_EXPENSIVE_STRIDE`-th step also runs
+/// inversion, square roots and serialization.
+const DIFFERENTIAL_ITERATIONS: usize = 1_000_000;
+const DIFFERENTIAL_EXPENSIVE_STRIDE: usize = 1 << 10;
+
+fn differential_test<M, C>(seed: u64)
+where
+    M: PrimeField<BigInt = BigInteger256> + SquareRootField,
+    C: PrimeField<BigInt = BigInt<4>> + SquareRootField,
+{
+    fn assert_same<M, C>(m: &M, c: &C)
+    where
+        M: PrimeField<BigInt = BigInteger256>,
+        C: PrimeField<BigInt = BigInt<4>>,
+    {
+        assert_eq!(m.into_repr().0, c.into_repr().0);
+    }
+
+    let mut rng = XorShiftRng::seed_from_u64(seed);
+    let sample = |rng: &mut XorShiftRng| {
+        let m = M::rand(rng);
+        let c = C::from_repr(BigInt::new(m.into_repr().0));
+        assert_same(&m, &c);
+        (m, c)
+    };
+
+    let (mut acc_m, mut acc_c) = sample(&mut rng);
+    for i in 0..DIFFERENTIAL_ITERATIONS {
+        let (m, c) = sample(&mut rng);
+        match rng.gen_range(0, 6) {
+            0 => {
+                acc_m += &m;
+                acc_c += &c;
+            }
+            1 => {
+                acc_m -= &m;
+                acc_c -= &c;
+            }
+            2 => {
+                acc_m *= &m;
+                acc_c *= &c;
+            }
+            3 => {
+                acc_m.square_in_place();
+                acc_c.square_in_place();
+            }
+            4 => {
+                acc_m.double_in_place();
+                acc_c.double_in_place();
+            }
+            _ => {
+                acc_m = -acc_m;
+                acc_c = -acc_c;
+            }
+        }
+        assert_same(&acc_m, &acc_c);
+        assert_eq!(acc_m.cmp(&m), acc_c.cmp(&c));
+
+        if i % DIFFERENTIAL_EXPENSIVE_STRIDE == 0 {
+            match (acc_m.inverse(), acc_c.inverse()) {
+                (Some(inv_m), Some(inv_c)) => assert_same(&inv_m, &inv_c),
+                (None, None) => {}
+                _ => panic!("inverse disagrees for {}", acc_m),
+            }
+            match (acc_m.sqrt(), acc_c.sqrt()) {
+                (Some(sqrt_m), Some(sqrt_c)) => assert_same(&sqrt_m, &sqrt_c),
+                (None, None) => {}
+                _ => panic!("sqrt disagrees for {}", acc_m),
+            }
+            assert_eq!(to_bytes!(acc_m).unwrap(), to_bytes!(acc_c).unwrap());
+            assert_same(&acc_m, &C::read(&to_bytes!(acc_m).unwrap()[..]).unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_fr_const_generic_differential() {
+    differential_test::<Fp256<MacroFrParameters>, Fp<ConstFrParameters, 4>>(1231275789u64);
+}
+
+#[test]
+fn test_fq_const_generic_differential() {
+    differential_test::<Fp256<MacroFqParameters>, Fp<ConstFqParameters, 4>>(1231275789u64);
+}
+
+/// Version 1 of the `Fq2`/`Fq6`/`Fq12` byte layout. A failure here means the
+/// serialized format changed, which breaks persisted keys and proofs.
+#[test]
+fn test_fq12_bytes_layout_v1() {
+    const FQ_SIZE: usize = 32;
+
+    // the k-th Fq coefficient in tower order is k + 1
+    let fq2 = |k: u64| Fq2::new(Fq::from(2 * k + 1), Fq::from(2 * k + 2));
+    let a = Fq12::new(
+        Fq6::new(fq2(0), fq2(1), fq2(2)),
+        Fq6::new(fq2(3), fq2(4), fq2(5)),
+    );
+    let mut expected = vec![0u8; 12 * FQ_SIZE];
+    for k in 0..12 {
+        expected[k * FQ_SIZE] = k as u8 + 1;
+    }
+    assert_eq!(to_bytes![a].unwrap(), expected);
+    assert_eq!(postcard::to_allocvec(&a).unwrap(), expected);
+    assert_eq!(to_bytes![a.c0].unwrap(), expected[..6 * FQ_SIZE].to_vec());
+
+    // the x-coordinate of the G2 generator
+    let x = from_hex(concat!(
+        "edf692d95cbdde46ddda5ef7d422436779445c5e66006a42761e1f12efde0018",
+        "c212f3aeb785e49712e7a9353349aaf1255dfb31b7bf60723a480d9293938e19",
+    ));
+    assert_eq!(to_bytes![G2_GENERATOR_X].unwrap(), x);
+    let decoded: Fq2 = postcard::from_bytes(&x).unwrap();
+    assert_eq!(decoded, G2_GENERATOR_X);
+}
+
+#[test]
+fn test_extension_serialization() {
+    serialization_test::<Fq2>();
+    serialization_test::<Fq6>();
+    serialization_test::<Fq12>();
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
@@ -5,3 +5,10 @@ mod fields;
 #[cfg(feature = "bn_256")]
 pub use curves::*;
 pub use fields::*;
+
+/// The limb representation backing the bn_256 family of fields. The
+/// `const_generic_fp` feature switches it to the limb-generic `BigInt<4>`.
+#[cfg(not(feature = "const_generic_fp"))]
+pub(crate) use math::biginteger::BigInteger256 as BigInteger;
+#[cfg(feature = "const_generic_fp")]
+pub(crate) use math::biginteger::BigInt as BigInteger;
@@ -0,0 +1,116 @@
+use math::{
+    biginteger::{BigInteger384, BigInteger768 as BigInteger},
+    curves::{
+        bw6,
+        models::{ModelParameters, SWModelParameters},
+    },
+    field_new, Zero,
+};
+
+use crate::bw6_761::{self, Fq, Fr, FQ_ZERO};
+
+pub type G1Affine = bw6::G1Affine<bw6_761::Parameters>;
+pub type G1Projective = bw6::G1Projective<bw6_761::Parameters>;
+pub type G1Prepared = bw6::G1Prepared<bw6_761::Parameters>;
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for Parameters {
+    /// COEFF_A = 0
+    const COEFF_A: Fq = FQ_ZERO;
+
+    /// COEFF_B = -1
+    #[rustfmt::skip]
+    const COEFF_B: Fq = field_new!(Fq, BigInteger([
+        0xf29a000000007ab6,
+        0x8c391832e000739b,
+        0x77738a6b6870f959,
+        0xbe36179047832b03,
+        0x84f3089e56574722,
+        0xc5a3614ac0b1d984,
+        0x5c81153f4906e9fe,
+        0x4d28be3a9f55c815,
+        0xd72c1d6f77d5f5c5,
+        0x73a18e069ac04458,
+        0xf9dfaa846595555f,
+        0x00d0f0a60a5be58c,
+    ]));
+
+    /// COFACTOR =
+    /// 26642435879335816683987677701488073867751118270052650655942102502312977592501693353047140953112195348280268661194876
+    #[rustfmt::skip]
+    const COFACTOR: &'static [u64] = &[
+        0x3de580000000007c,
+        0x832ba4061000003b,
+        0xc61c554757551c0c,
+        0xc856a0853c9db94c,
+        0x2c77d5ac34cb12ef,
+        0xad1972339049ce76,
+    ];
+
+    /// COFACTOR^(-1) mod r =
+    /// 91141326767669940707819291241958318717982251277713150053234367522357946997763584490607453720072232540829942217804
+    #[rustfmt::skip]
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger384([
+        0x06cbc6719306b799,
+        0x35e466ec4fc64c14,
+        0x174a5cff3b221c74,
+        0x512b12e900a45d2e,
+        0x62eba10945bdbdf0,
+        0x00fe1019f053865a,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (G1_GENERATOR_X, G1_GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (G1_GENERATOR_X, G1_GENERATOR_Y);
+
+    #[inline(always)]
+    fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
+        Self::BaseField::zero()
+    }
+}
+
+// The generator is COFACTOR * (x, y), where x = 2 is the smallest positive
+// x-coordinate on the curve and y is the smaller square root of x^3 - 1.
+
+/// G1_GENERATOR_X =
+/// 5121082781502305659180673370797932802982723165543122975335209418297923345190975559367024470794567213188867624617939698818627134048004915564891369096213721202693630090148097888923493708182442885908076346607300083583163982163789167
+#[rustfmt::skip]
+pub const G1_GENERATOR_X: Fq = field_new!(Fq, BigInteger([
+    0x851a1c1166ae2603,
+    0xeea72e25492571be,
+    0xa6f20c75f7feab02,
+    0xeeea8a1b066c47a4,
+    0x6469c9e9402afc4f,
+    0x4cd9755dfe95e5ba,
+    0x3a1be33544ef7b02,
+    0x1e7fe0555536b119,
+    0xfd947c7fee73da05,
+    0xcffde31744f837a0,
+    0xa7b583036935c864,
+    0x009918c73dcc124a,
+]));
+
+/// G1_GENERATOR_Y =
+/// 3936891438836912583780051937508657570812555345221986954460153541952284123191322673907692687918062549220672207508016306900511821136915723707181225276750353973961376292016478674329987421847947883311993206004548631860683957877121757
+#[rustfmt::skip]
+pub const G1_GENERATOR_Y: Fq = field_new!(Fq, BigInteger([
+    0xa3c13889f58ed694,
+    0xee5cb8acda0ea650,
+    0xcea7c1807dfc6e02,
+    0xdc672db2da00df5d,
+    0xcde53070a22b574d,
+    0x4f3dc271f97fe60b,
+    0xd5a2d25bb5f4cc8c,
+    0xd68088f33580a980,
+    0x760b0d826b393143,
+    0xe54149ee3970878e,
+    0xc62f69887a0e0ed1,
+    0x0030ceaa8cbab39d,
+]));
@@ -0,0 +1,116 @@
+use math::{
+    biginteger::{BigInteger384, BigInteger768 as BigInteger},
+    curves::{
+        bw6,
+        models::{ModelParameters, SWModelParameters},
+    },
+    field_new, Zero,
+};
+
+use crate::bw6_761::{self, Fq, Fr, FQ_ZERO};
+
+pub type G2Affine = bw6::G2Affine<bw6_761::Parameters>;
+pub type G2Projective = bw6::G2Projective<bw6_761::Parameters>;
+pub type G2Prepared = bw6::G2Prepared<bw6_761::Parameters>;
+
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Parameters;
+
+impl ModelParameters for Parameters {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+}
+
+impl SWModelParameters for Parameters {
+    /// COEFF_A = 0
+    const COEFF_A: Fq = FQ_ZERO;
+
+    /// COEFF_B = 4
+    #[rustfmt::skip]
+    const COEFF_B: Fq = field_new!(Fq, BigInteger([
+        0x136efffffffe16c9,
+        0x82cf5a6dcffe3319,
+        0x6458c05f1f0e0741,
+        0xd10ae605e52a4eda,
+        0x41ca591c0266e100,
+        0x7d0fd59c3626929f,
+        0x9967dc004d00c112,
+        0x1ccff9c033379af5,
+        0x9ad6ec10a23f63af,
+        0x5cec11251a72c235,
+        0x8d18b1ae789ba83e,
+        0x0024f5d6c91bd3ec,
+    ]));
+
+    /// COFACTOR =
+    /// 26642435879335816683987677701488073867751118270052650655942102502312977592501693353047140953112195348280268661194869
+    #[rustfmt::skip]
+    const COFACTOR: &'static [u64] = &[
+        0x3de5800000000075,
+        0x832ba4061000003b,
+        0xc61c554757551c0c,
+        0xc856a0853c9db94c,
+        0x2c77d5ac34cb12ef,
+        0xad1972339049ce76,
+    ];
+
+    /// COFACTOR^(-1) mod r =
+    /// 214911522365886453591244899095480747723790054550866810551297776298664428889000553861210287833206024638187939842124
+    #[rustfmt::skip]
+    const COFACTOR_INV: Fr = field_new!(Fr, BigInteger384([
+        0xc789f8066b9af9ee,
+        0xca6c6dc67d6d42ce,
+        0x87d7a8a9da8db962,
+        0xaf9ef54b967af20c,
+        0xd2be337d7d38f0a5,
+        0x005ef152cf9ffea4,
+    ]));
+
+    /// AFFINE_GENERATOR_COEFFS = (G2_GENERATOR_X, G2_GENERATOR_Y)
+    const AFFINE_GENERATOR_COEFFS: (Self::BaseField, Self::BaseField) =
+        (G2_GENERATOR_X, G2_GENERATOR_Y);
+
+    #[inline(always)]
+    fn mul_by_a(_: &Self::BaseField) -> Self::BaseField {
+        Self::BaseField::zero()
+    }
+}
+
+// The generator is COFACTOR * (x, y), where x = 1 is the smallest positive
+// x-coordinate on the curve and y is the smaller square root of x^3 + 4.
+
+/// G2_GENERATOR_X =
+/// 4299495046121976197967899669570453663523609133273326781652351086315825538613647196868186765217397067654540962955975840176693419657193486845273767814170238104346390446211356851381676719718477415944688400098442139983341339355564981
+#[rustfmt::skip]
+pub const G2_GENERATOR_X: Fq = field_new!(Fq, BigInteger([
+    0x22aab15d6e6db785,
+    0x2d4ff25bb7779d47,
+    0xd6e307f08fd0c1a9,
+    0xe1f9a0520c1f952d,
+    0xa4fb4fd53ad518d4,
+    0xf19a04a7a47da06d,
+    0xb40146bfc4efa321,
+    0x42eaf4df92bc1fcf,
+    0x941a4672957418ab,
+    0xce598708852a92dc,
+    0xc5676a44843ae7bb,
+    0x002fc541f69a8dbe,
+]));
+
+/// G2_GENERATOR_Y =
+/// 5390068907621955088169558704058566111636841949933812064258391788701969884935470780815806205108266591280868872340793447961301446566465351736303970166703756567269434202182284388197563290670801062112376259336491499915806321277334517
+#[rustfmt::skip]
+pub const G2_GENERATOR_Y: Fq = field_new!(Fq, BigInteger([
+    0x0797e6c45cf5a4db,
+    0xf2af166031457596,
+    0x40a34fe935a0bd36,
+    0xb5b1538e72692756,
+    0x8b2071df96488d9b,
+    0x41914b18661e79e2,
+    0xc5ddedd06903d6f2,
+    0x6dda5abf0c1df1aa,
+    0xd561192fc0f03d13,
+    0xe45ff24a1d016a19,
+    0x14d2af10a474f091,
+    0x00a11703f4f8d9f4,
+]));
@@ -0,0 +1,72 @@
+use math::{
+    biginteger::BigInteger768,
+    curves::{
+        bls12::TwistType,
+        bw6::{BW6Parameters, BW6},
+    },
+};
+
+use crate::bw6_761::{Fq, Fq3Parameters, Fq6Parameters};
+
+pub mod g1;
+pub mod g2;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::{
+    g1::{G1Affine, G1Prepared, G1Projective},
+    g2::{G2Affine, G2Prepared, G2Projective},
+};
+
+#[allow(non_camel_case_types)]
+pub type BW6_761 = BW6<Parameters>;
+
+pub struct Parameters;
+
+impl BW6Parameters for Parameters {
+    /// X = 9586122913090633729, the seed of BLS12-377
+    #[rustfmt::skip]
+    const X: BigInteger768 = BigInteger768([
+        0x8508c00000000001, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+    const X_IS_NEGATIVE: bool = false;
+
+    /// ATE_LOOP_COUNT_1 = X + 1
+    const ATE_LOOP_COUNT_1: &'static [u64] = &[0x8508c00000000002];
+    const ATE_LOOP_COUNT_1_IS_NEGATIVE: bool = false;
+
+    /// ATE_LOOP_COUNT_2 = X^3 - X^2 - X =
+    /// 880904806456922042166256752416502360955572640081583800319
+    #[rustfmt::skip]
+    const ATE_LOOP_COUNT_2: &'static [i8] = &[
+        -1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0,
+        1, 0, 0, -1, 0, 1, 0, -1, 0, 0, 0, 0, -1, 0, 1, 0,
+        0, 0, 1, 0, 0, 0, 1, 0, 0, 1, 0, 1, 0, 0, 0, 1,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -1, 0, -1, 0, 0, 0,
+        0, -1, 0, 0, 1, 0, 0, 0, -1, 0, 0, -1, 0, 1, 0, -1,
+        0, 0, 0, 1, 0, 0, 1, 0, -1, 0, 1, 0, 1, 0, 0, 0,
+        1, 0, -1, 0, -1, 0, 0, 0, 0, 0, 1, 0, 0, 1,
+    ];
+    const ATE_LOOP_COUNT_2_IS_NEGATIVE: bool = false;
+
+    /// R0(X) = -103*X^7 + 70*X^6 + 269*X^5 - 197*X^4 - 314*X^3 - 73*X^2 - 263*X - 220
+    const FINAL_EXPONENT_LAST_CHUNK_R0: &'static [i64] =
+        &[-220, -263, -73, -314, -197, 269, 70, -103];
+    /// R1(X) = 103*X^9 - 276*X^8 + 77*X^7 + 492*X^6 - 445*X^5 - 65*X^4 + 452*X^3
+    ///         - 181*X^2 + 34*X + 229
+    const FINAL_EXPONENT_LAST_CHUNK_R1: &'static [i64] =
+        &[229, 34, -181, 452, -65, -445, 492, 77, -276, 103];
+
+    const TWIST_TYPE: TwistType = TwistType::M;
+    type Fp = Fq;
+    type Fp3Params = Fq3Parameters;
+    type Fp6Params = Fq6Parameters;
+    type G1Parameters = self::g1::Parameters;
+    type G2Parameters = self::g2::Parameters;
+}
@@ -0,0 +1 @@
+pub use crate::bls12_377::{Fq as Fr, FqParameters as FrParameters};
@@ -0,0 +1,64 @@
+use math::{test_rng, Field};
+use rand::Rng;
+
+use crate::bw6_761::*;
+
+use crate::tests::fields::{field_test, frobenius_test, primefield_test, sqrt_field_test};
+
+#[test]
+fn test_fr() {
+    let mut rng = test_rng();
+    let a: Fr = rng.gen();
+    let b: Fr = rng.gen();
+    field_test(a, b);
+    sqrt_field_test(a);
+    primefield_test::<Fr>();
+}
+
+#[test]
+fn test_fq() {
+    let mut rng = test_rng();
+    let a: Fq = rng.gen();
+    let b: Fq = rng.gen();
+    field_test(a, b);
+    sqrt_field_test(a);
+    primefield_test::<Fq>();
+}
+
+#[test]
+fn test_fq3() {
+    let mut rng = test_rng();
+    let a: Fq3 = rng.gen();
+    let b: Fq3 = rng.gen();
+    field_test(a, b);
+    sqrt_field_test(a);
+    frobenius_test::<Fq3, _>(Fq::characteristic(), 13);
+}
+
+#[test]
+fn test_fq6() {
+    let mut rng = test_rng();
+    let a: Fq6 = rng.gen();
+    let b: Fq6 = rng.gen();
+    field_test(a, b);
+    frobenius_test::<Fq6, _>(Fq::characteristic(), 13);
+}
+
+#[test]
+fn test_fq6_sparse_mul() {
+    let mut rng = test_rng();
+    let a: Fq6 = rng.gen();
+    let c0: Fq = rng.gen();
+    let c1: Fq = rng.gen();
+    let c4: Fq = rng.gen();
+
+    let mut sparse = a;
+    sparse.mul_by_014(&c0, &c1, &c4);
+    let full = Fq6::new(Fq3::new(c0, c1, FQ_ZERO), Fq3::new(FQ_ZERO, c4, FQ_ZERO));
+    assert_eq!(sparse, a * &full);
+
+    let mut sparse = a;
+    sparse.mul_by_034(&c0, &c1, &c4);
+    let full = Fq6::new(Fq3::new(c0, FQ_ZERO, FQ_ZERO), Fq3::new(c1, c4, FQ_ZERO));
+    assert_eq!(sparse, a * &full);
+}
@@ -0,0 +1,5 @@
+mod curves;
+mod fields;
+
+pub use curves::*;
+pub use fields::*;
@@ -4,6 +4,7 @@ use curve25519_dalek::scalar::Scalar;
 use curve25519_dalek::traits::Identity;
 use curve25519_dalek::traits::VartimeMultiscalarMul;
 use math::{
+    biginteger::BigInteger256 as BigInteger,
     curves::{AffineCurve, Curve, ProjectiveCurve},
     PrimeField, ToBytes, Zero,
 };
@@ -30,22 +31,23 @@ impl Curve for Curve25519 {
         let ss = &s[0..size];
         let pp = &p[0..size];
 
-        let scalars: Vec<Scalar> = ss
-            .iter()
-            .map(|s| {
-                let mut bytes = [0u8; 32];
-                let mut vec = Vec::new();
-                let _ = s.write(&mut vec);
-                bytes.copy_from_slice(&vec[..]);
-                Scalar::from_bytes_mod_order(bytes)
-            })
-            .collect();
+        let scalars: Vec<Scalar> = ss.iter().map(|s| to_scalar(s.into_repr())).collect();
         let points: Vec<RistrettoPoint> = pp.iter().map(|p| p.0).collect();
         let point = RistrettoPoint::vartime_multiscalar_mul(scalars, points);
         Curve25519Point(point)
     }
 }
 
+/// Converts an integer to a dalek scalar, reducing it modulo the group order.
+fn to_scalar(repr: BigInteger) -> Scalar {
+    let mut bytes = [0u8; 32];
+    repr.write(&mut bytes[..])
+        .expect("a 256-bit integer fits in 32 bytes");
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// An element of the Ristretto group. The group has prime order, so the
+/// cofactor is one and every point is in the prime order subgroup.
 #[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Default, Debug)]
 pub struct Curve25519Point(pub RistrettoPoint);
 
@@ -70,20 +72,20 @@ impl AffineCurve for Curve25519Point {
         &self,
         other: S,
     ) -> Self::Projective {
-        let mut p = self.clone();
-        p *= Fr::from_repr(other.into());
-        p
+        Self(self.0 * to_scalar(other.into()))
     }
 
     fn mul_by_cofactor(&self) -> Self {
-        let mut p = self.clone();
-        p *= Fr::from(8u32);
-        p.into()
+        *self
     }
 
     fn mul_by_cofactor_inv(&self) -> Self {
-        //self *= (Fr::from(8u32).inverse().unwrap())
-        panic!("Curve mul_by_cofactor_inv");
+        *self
+    }
+
+    // dalek checks the points as it decompresses them, on deserializing.
+    fn is_on_curve(&self) -> bool {
+        true
     }
 }
 
@@ -93,21 +95,18 @@ impl ProjectiveCurve for Curve25519Point {
     type Affine = Curve25519Point;
 
     fn prime_subgroup_generator() -> Self {
-        panic!("Curve prime_subgroup_generator");
+        <Self as AffineCurve>::prime_subgroup_generator()
     }
 
-    fn batch_normalization(_v: &mut [Self]) {
-        panic!("Curve batch normalization");
-    }
+    // the affine and projective representations are the same.
+    fn batch_normalization(_v: &mut [Self]) {}
 
-    fn batch_normalization_into_affine(v: &[Self]) -> crate::Vec<Self::Affine> {
-        let mut v = v.to_vec();
-        Self::batch_normalization(&mut v);
+    fn batch_into_affine(v: crate::Vec<Self>) -> crate::Vec<Self::Affine> {
         v
     }
 
     fn is_normalized(&self) -> bool {
-        panic!("Curve is_normalized");
+        true
     }
 
     fn double(&self) -> Self {
@@ -117,7 +116,8 @@ impl ProjectiveCurve for Curve25519Point {
     }
 
     fn double_in_place(&mut self) -> &mut Self {
-        panic!("Curve double_in_place");
+        self.0 += self.0;
+        self
     }
 
     fn into_affine(&self) -> Self::Affine {
@@ -133,9 +133,8 @@ impl ProjectiveCurve for Curve25519Point {
         *self += other;
     }
 
-    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(mut self, other: S) -> Self {
-        self *= Fr::from_repr(other.into());
-        self
+    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(self, other: S) -> Self {
+        Self(self.0 * to_scalar(other.into()))
     }
 }
 
@@ -158,6 +157,10 @@ impl math::FromBytes for Curve25519Point {
     }
 }
 
+impl math::ConstSerializedSize for Curve25519Point {
+    const SERIALIZED_SIZE: usize = 32;
+}
+
 impl Zero for Curve25519Point {
     fn zero() -> Self {
         Self(RistrettoPoint::identity())
@@ -202,11 +205,7 @@ impl core::ops::SubAssign<Curve25519Point> for Curve25519Point {
 
 impl core::ops::MulAssign<<Self as ProjectiveCurve>::ScalarField> for Curve25519Point {
     fn mul_assign(&mut self, rhs: <Self as ProjectiveCurve>::ScalarField) {
-        let mut bytes = [0u8; 32];
-        let mut vec = Vec::new();
-        let _ = rhs.write(&mut vec);
-        bytes.copy_from_slice(&vec[..]);
-        self.0 *= Scalar::from_bytes_mod_order(bytes)
+        self.0 *= to_scalar(rhs.into_repr())
     }
 }
 
@@ -0,0 +1,95 @@
+use math::{
+    curves::{AffineCurve, Curve, ProjectiveCurve},
+    fields::{FpParameters, PrimeField},
+    test_rng, FromBytes, ToBytes, Zero,
+};
+use rand::Rng;
+
+use crate::{
+    curve25519::{Curve25519, Curve25519Point, Fr, FrParameters},
+    tests::{
+        curves::curve_tests,
+        fields::{field_test, primefield_test, sqrt_field_test},
+        groups::group_test,
+    },
+    Vec,
+};
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_fr() {
+    let mut rng = test_rng();
+    let a: Fr = rng.gen();
+    let b: Fr = rng.gen();
+    field_test(a, b);
+    primefield_test::<Fr>();
+    sqrt_field_test(b);
+}
+
+#[test]
+fn test_projective_curve() {
+    curve_tests::<Curve25519Point>();
+}
+
+#[test]
+fn test_projective_group() {
+    let mut rng = test_rng();
+    let a: Curve25519Point = rng.gen();
+    let b: Curve25519Point = rng.gen();
+    group_test(a, b);
+}
+
+#[test]
+fn test_generator() {
+    let generator = <Curve25519Point as AffineCurve>::prime_subgroup_generator();
+    assert_eq!(
+        generator,
+        <Curve25519Point as ProjectiveCurve>::prime_subgroup_generator()
+    );
+
+    // the generator has order r
+    assert!(AffineCurve::mul(&generator, FrParameters::MODULUS).is_zero());
+    assert!(AffineCurve::is_in_correct_subgroup_assuming_on_curve(&generator));
+
+    // the encodings of small multiples of the generator from RFC 9496.
+    let multiples = [
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+    ];
+    let mut point = Curve25519Point::zero();
+    for expected in multiples.iter() {
+        point += &generator;
+        let expected = from_hex(expected);
+        assert_eq!(math::to_bytes!(point).unwrap(), expected);
+        assert_eq!(Curve25519Point::read(&expected[..]).unwrap(), point);
+    }
+    assert_eq!(
+        ProjectiveCurve::mul(generator, Fr::from(3u64).into_repr()),
+        point
+    );
+    assert_eq!(generator.double().double(), point + &generator);
+}
+
+#[test]
+fn test_vartime_multiscalar_mul() {
+    let mut rng = test_rng();
+    let scalars = (0..16).map(|_| rng.gen()).collect::<Vec<Fr>>();
+    let points = (0..16).map(|_| rng.gen()).collect::<Vec<Curve25519Point>>();
+
+    let expected = scalars
+        .iter()
+        .zip(&points)
+        .map(|(s, p)| AffineCurve::mul(p, *s))
+        .sum::<Curve25519Point>();
+    assert_eq!(
+        Curve25519::vartime_multiscalar_mul(&scalars, &points),
+        expected
+    );
+}
@@ -0,0 +1,42 @@
+//! The `BLS12381G1_XMD:SHA-256_SSWU_RO_` and `BLS12381G2_XMD:SHA-256_SSWU_RO_`
+//! suites.
+
+use crate::{
+    bls12_381::{Fq, Fq2},
+    fields::PrimeField,
+    BigInteger, Zero,
+};
+
+use super::{from_be_bytes_mod_order, HashToField};
+
+mod g1;
+mod g2;
+
+impl HashToField for Fq {
+    const L: usize = 64;
+
+    const DEGREE: usize = 1;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        from_be_bytes_mod_order(bytes)
+    }
+
+    fn sgn0(&self) -> bool {
+        self.into_repr().is_odd()
+    }
+}
+
+impl HashToField for Fq2 {
+    const L: usize = 64;
+
+    const DEGREE: usize = 2;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        let (c0, c1) = bytes.split_at(Self::L);
+        Fq2::new(Fq::from_uniform_bytes(c0), Fq::from_uniform_bytes(c1))
+    }
+
+    fn sgn0(&self) -> bool {
+        self.c0.sgn0() || (self.c0.is_zero() && self.c1.sgn0())
+    }
+}
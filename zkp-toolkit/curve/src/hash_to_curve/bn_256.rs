@@ -0,0 +1,110 @@
+//! `BN254G1_XMD:SHA-256_SVDW_RO_`: the Shallue-van de Woestijne map of
+//! RFC 9380, section 6.6.1, onto G1 with `Z = 1`. G1 has cofactor one, so no
+//! cofactor clearing is needed.
+
+use crate::{
+    bn_256::{g1, BigInteger, Fq, G1Affine},
+    curves::{models::SWModelParameters, AffineCurve},
+    field_new,
+    fields::{Field, PrimeField, SquareRootField},
+    BigInteger as _, One, ProjectiveCurve, Zero,
+};
+
+use super::{from_be_bytes_mod_order, hash_to_field, HashToCurve, HashToField};
+
+impl HashToField for Fq {
+    const L: usize = 48;
+
+    const DEGREE: usize = 1;
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        from_be_bytes_mod_order(bytes)
+    }
+
+    fn sgn0(&self) -> bool {
+        self.into_repr().is_odd()
+    }
+}
+
+impl HashToCurve for G1Affine {
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self {
+        let u = hash_to_field::<Fq>(msg, dst, 2);
+        let mut q = map_to_curve(&u[0]).into_projective();
+        q.add_assign_mixed(&map_to_curve(&u[1]));
+        q.into_affine()
+    }
+}
+
+/// Maps a field element to a point of G1.
+pub(crate) fn map_to_curve(u: &Fq) -> G1Affine {
+    let g = |x: &Fq| x.square() * x + &g1::Parameters::COEFF_B;
+
+    let tv1 = u.square() * &SVDW_C1;
+    let tv2 = Fq::one() + &tv1;
+    let tv1 = Fq::one() - &tv1;
+    // inv0: the exceptional case maps to x = c2 + z below
+    let tv3 = (tv1 * &tv2).inverse().unwrap_or_else(Fq::zero);
+    let tv4 = *u * &tv1 * &tv3 * &SVDW_C3;
+
+    let x1 = SVDW_C2 - &tv4;
+    let x2 = SVDW_C2 + &tv4;
+    let x3 = (tv2.square() * &tv3).square() * &SVDW_C4 + &SVDW_Z;
+
+    // at least one of g(x1), g(x2) and g(x3) is a square
+    let (x, mut y) = [x1, x2, x3]
+        .iter()
+        .find_map(|x| g(x).sqrt().map(|y| (*x, y)))
+        .unwrap();
+    if u.sgn0() != y.sgn0() {
+        y = -y;
+    }
+    G1Affine::new(x, y, false)
+}
+
+/// SVDW_Z = 1
+#[rustfmt::skip]
+const SVDW_Z: Fq = field_new!(Fq, BigInteger([
+    0xd35d438dc58f0d9d,
+    0x0a78eb28f5c70b3d,
+    0x666ea36f7879462c,
+    0x0e0a77c19a07df2f,
+]));
+
+/// SVDW_C1 = g(Z) = 4
+#[rustfmt::skip]
+const SVDW_C1: Fq = field_new!(Fq, BigInteger([
+    0x115482203dbf392d,
+    0x926242126eaa626a,
+    0xe16a48076063c052,
+    0x07c5909386eddc93,
+]));
+
+/// SVDW_C2 = -Z / 2 =
+/// 10944121435919637611123202872628637544348155578648911831344518947322613104291
+#[rustfmt::skip]
+const SVDW_C2: Fq = field_new!(Fq, BigInteger([
+    0xb461a4448976f7d5,
+    0xc6843fb439555fa7,
+    0x28f0d12384840918,
+    0x112ceb58a394e07d,
+]));
+
+/// SVDW_C3 = sqrt(-g(Z) * 3 * Z^2), with sgn0(SVDW_C3) = 0,
+/// = 8815841940592487685674414971303048083897117035520822607866
+#[rustfmt::skip]
+const SVDW_C3: Fq = field_new!(Fq, BigInteger([
+    0x7c8487078735ab72,
+    0x51da7e0048bfb8d4,
+    0x945cfd183cbd7bf4,
+    0x0b70b1ec48ae62c6,
+]));
+
+/// SVDW_C4 = -4 * g(Z) / (3 * Z^2) =
+/// 7296080957279758407415468581752425029565437052432607887563012631548408736189
+#[rustfmt::skip]
+const SVDW_C4: Fq = field_new!(Fq, BigInteger([
+    0xa79a2bdca0800831,
+    0x19fd7617e49815a1,
+    0xbb8d0c885550c7b1,
+    0x05c4aeb6ec7e0f48,
+]));
@@ -0,0 +1,176 @@
+//! Hashing arbitrary messages to field elements and to points of the prime
+//! order subgroup of an elliptic curve, following
+//! [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380.html).
+//!
+//! Only the random oracle (`_RO_`) encodings are provided, with
+//! `expand_message_xmd` instantiated with SHA-256. The maps are not constant
+//! time, so they must not be applied to secret inputs.
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    fields::{Field, PrimeField, SquareRootField},
+    Vec,
+};
+
+#[cfg(feature = "bls12_381")]
+mod bls12_381;
+
+#[cfg(feature = "bn_256")]
+mod bn_256;
+
+#[cfg(test)]
+mod tests;
+
+/// The output size of SHA-256 in bytes, `b_in_bytes` in RFC 9380.
+const B_IN_BYTES: usize = 32;
+
+/// The input block size of SHA-256 in bytes, `s_in_bytes` in RFC 9380.
+const S_IN_BYTES: usize = 64;
+
+/// Prefix used to shorten domain separation tags longer than 255 bytes.
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+/// A field which messages can be hashed to.
+pub trait HashToField: SquareRootField {
+    /// The number of bytes sampled per base prime field element, that is
+    /// `L = ceil((ceil(log2(p)) + 128) / 8)`.
+    const L: usize;
+
+    /// The extension degree over the base prime field, `m` in RFC 9380.
+    const DEGREE: usize;
+
+    /// Reduces `DEGREE * L` uniformly random bytes to a field element.
+    fn from_uniform_bytes(bytes: &[u8]) -> Self;
+
+    /// The `sgn0` function of RFC 9380, section 4.1.
+    fn sgn0(&self) -> bool;
+}
+
+/// A group which messages can be hashed to.
+pub trait HashToCurve: Sized {
+    /// Hashes `msg` to a point of the prime order subgroup, using the domain
+    /// separation tag `dst`.
+    fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Self;
+}
+
+/// `expand_message_xmd` with SHA-256, producing `len` pseudorandom bytes.
+///
+/// Panics if `len` exceeds `255 * 32` bytes.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+    let ell = (len + B_IN_BYTES - 1) / B_IN_BYTES;
+    assert!(ell <= 255, "requested too many bytes");
+
+    let long_dst;
+    let dst = if dst.len() > 255 {
+        long_dst = Sha256::new()
+            .chain(OVERSIZE_DST_PREFIX)
+            .chain(dst)
+            .finalize();
+        long_dst.as_slice()
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    let b_0 = Sha256::new()
+        .chain(&[0u8; S_IN_BYTES][..])
+        .chain(msg)
+        .chain(&(len as u16).to_be_bytes())
+        .chain(&[0u8])
+        .chain(dst)
+        .chain(&dst_len)
+        .finalize();
+
+    let mut b_i = Sha256::new()
+        .chain(&b_0)
+        .chain(&[1u8])
+        .chain(dst)
+        .chain(&dst_len)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let mut xored = [0u8; B_IN_BYTES];
+        for (x, (b0, bi)) in xored.iter_mut().zip(b_0.iter().zip(b_i.iter())) {
+            *x = b0 ^ bi;
+        }
+        b_i = Sha256::new()
+            .chain(&xored)
+            .chain(&[i as u8])
+            .chain(dst)
+            .chain(&dst_len)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len);
+    uniform_bytes
+}
+
+/// Hashes `msg` to `count` elements of `F`, using the domain separation tag
+/// `dst`.
+pub fn hash_to_field<F: HashToField>(msg: &[u8], dst: &[u8], count: usize) -> Vec<F> {
+    let len_per_elem = F::DEGREE * F::L;
+    let uniform_bytes = expand_message_xmd(msg, dst, count * len_per_elem);
+    uniform_bytes
+        .chunks(len_per_elem)
+        .map(F::from_uniform_bytes)
+        .collect()
+}
+
+/// Derives `n` independent generators of `G` from `label`, by hashing the
+/// big-endian encoding of each index `0..n` as a `u64` with `label` as the
+/// domain separation tag. Nobody knows the discrete logarithm relations
+/// between the outputs.
+pub fn generators_from_label<G: HashToCurve>(label: &[u8], n: usize) -> Vec<G> {
+    (0..n as u64)
+        .map(|i| G::hash_to_curve(&i.to_be_bytes(), label))
+        .collect()
+}
+
+/// Interprets `bytes` as a big-endian integer and reduces it modulo the
+/// characteristic of `F`.
+pub(crate) fn from_be_bytes_mod_order<F: PrimeField>(bytes: &[u8]) -> F {
+    let base = F::from(256u64);
+    bytes
+        .iter()
+        .fold(F::zero(), |acc, byte| acc * &base + &F::from(*byte))
+}
+
+/// The simplified Shallue-van de Woestijne-Ulas map of RFC 9380, section
+/// 6.6.2, onto `y^2 = x^3 + a * x + b` with `a * b != 0`. `z` is the
+/// non-square constant chosen for the curve.
+pub(crate) fn map_to_curve_sswu<F: HashToField>(u: &F, a: &F, b: &F, z: &F) -> (F, F) {
+    let z_u2 = *z * &u.square();
+    let tv1 = z_u2.square() + &z_u2;
+    let x1 = match tv1.inverse() {
+        Some(tv1_inv) => -*b * &a.inverse().unwrap() * &(F::one() + &tv1_inv),
+        // u is a root of tv1 (or zero), the exceptional case
+        None => *b * &(*z * a).inverse().unwrap(),
+    };
+    let gx1 = (x1.square() + a) * &x1 + b;
+
+    let (x, mut y) = match gx1.sqrt() {
+        Some(y1) => (x1, y1),
+        None => {
+            // g(z * u^2 * x1) = z^3 * u^6 * g(x1) is a square
+            let x2 = z_u2 * &x1;
+            let gx2 = (x2.square() + a) * &x2 + b;
+            (x2, gx2.sqrt().unwrap())
+        }
+    };
+    if u.sgn0() != y.sgn0() {
+        y = -y;
+    }
+    (x, y)
+}
+
+/// Evaluates the polynomial with coefficients `coeffs`, constant term first,
+/// at `x`.
+pub(crate) fn evaluate_poly<F: Field>(coeffs: &[F], x: &F) -> F {
+    coeffs
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, coeff| acc * x + coeff)
+}
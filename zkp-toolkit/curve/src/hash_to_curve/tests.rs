@@ -0,0 +1,394 @@
+use super::*;
+use crate::{format, String};
+
+const Q128_LEN: usize = 128;
+const A512_LEN: usize = 512;
+const Q128: &str = "q128_";
+const A512: &str = "a512_";
+
+/// Expands the `q128_` and `a512_` messages of the RFC 9380 test vectors.
+fn message(msg: &str) -> String {
+    match msg {
+        Q128 => format!("{}{}", msg, "q".repeat(Q128_LEN)),
+        A512 => format!("{}{}", msg, "a".repeat(A512_LEN)),
+        _ => String::from(msg),
+    }
+}
+
+fn decode_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[cfg(any(feature = "bls12_381", feature = "bn_256"))]
+fn field_from_hex<F: PrimeField>(hex: &str) -> F {
+    from_be_bytes_mod_order(&decode_hex(hex))
+}
+
+#[test]
+fn test_expand_message_xmd() {
+    let long_dst = format!(
+        "QUUX-V01-CS02-with-expander-SHA256-128-long-DST-{}",
+        "1".repeat(208)
+    );
+    for (dst, vectors) in &[
+        ("QUUX-V01-CS02-with-expander-SHA256-128", EXPAND_SHORT_DST),
+        (long_dst.as_str(), EXPAND_LONG_DST),
+    ] {
+        for (msg, len, uniform_bytes) in vectors.iter() {
+            let expanded = expand_message_xmd(message(msg).as_bytes(), dst.as_bytes(), *len);
+            assert_eq!(expanded, decode_hex(uniform_bytes));
+        }
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+#[test]
+fn test_bls12_381_g1_vectors() {
+    use crate::bls12_381::{Fq, G1Affine};
+
+    let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+    for (msg, u0, u1, x, y) in BLS12_381_G1_VECTORS {
+        let msg = message(msg);
+        let u = hash_to_field::<Fq>(msg.as_bytes(), dst, 2);
+        assert_eq!(u, vec![field_from_hex::<Fq>(u0), field_from_hex(u1)]);
+
+        let p = G1Affine::hash_to_curve(msg.as_bytes(), dst);
+        assert_eq!(
+            p,
+            G1Affine::new(field_from_hex(x), field_from_hex(y), false)
+        );
+        assert!(p.is_on_curve());
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+fn fq2_from_hex(hex: &str) -> crate::bls12_381::Fq2 {
+    let mut components = hex.split(',');
+    let c0 = field_from_hex(components.next().unwrap());
+    let c1 = field_from_hex(components.next().unwrap());
+    crate::bls12_381::Fq2::new(c0, c1)
+}
+
+#[cfg(feature = "bls12_381")]
+#[test]
+fn test_bls12_381_g2_vectors() {
+    use crate::bls12_381::{Fq2, G2Affine};
+
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    for (msg, u0, u1, x, y) in BLS12_381_G2_VECTORS {
+        let msg = message(msg);
+        let u = hash_to_field::<Fq2>(msg.as_bytes(), dst, 2);
+        assert_eq!(u, vec![fq2_from_hex(u0), fq2_from_hex(u1)]);
+
+        let p = G2Affine::hash_to_curve(msg.as_bytes(), dst);
+        assert_eq!(p, G2Affine::new(fq2_from_hex(x), fq2_from_hex(y), false));
+        assert!(p.is_on_curve());
+        assert!(p.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+#[test]
+fn test_bls12_381_g2_bilinearity() {
+    use crate::bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+    use crate::{AffineCurve, PairingEngine, ProjectiveCurve, UniformRand};
+    use rand::SeedableRng;
+
+    let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(1231275789u64);
+    let dst = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+    let g1 = G1Affine::prime_subgroup_generator();
+    let h1 = G2Affine::hash_to_curve(b"message 1", dst);
+    let h2 = G2Affine::hash_to_curve(b"message 2", dst);
+
+    let a = Fr::rand(&mut rng);
+    let lhs = Bls12_381::pairing(g1.mul(a), h1);
+    let rhs = Bls12_381::pairing(g1, h1.mul(a));
+    assert_eq!(lhs, rhs);
+
+    let mut sum = h1.into_projective();
+    sum.add_assign_mixed(&h2);
+    let lhs = Bls12_381::pairing(g1, sum);
+    let rhs = Bls12_381::pairing(g1, h1) * &Bls12_381::pairing(g1, h2);
+    assert_eq!(lhs, rhs);
+    assert_ne!(Bls12_381::pairing(g1, h1), Bls12_381::pairing(g1, h2));
+}
+
+#[cfg(feature = "bn_256")]
+#[test]
+fn test_bn_256_g1() {
+    use crate::bn_256::G1Affine;
+
+    let dst = b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+    let p = G1Affine::hash_to_curve(b"", dst);
+    let expected = G1Affine::new(
+        field_from_hex("0a976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86"),
+        field_from_hex("02925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5"),
+        false,
+    );
+    assert_eq!(p, expected);
+
+    let p = G1Affine::hash_to_curve(b"abc", dst);
+    let expected = G1Affine::new(
+        field_from_hex("23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1"),
+        field_from_hex("04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d"),
+        false,
+    );
+    assert_eq!(p, expected);
+
+    for i in 0..100u32 {
+        let p = G1Affine::hash_to_curve(&i.to_le_bytes(), dst);
+        assert!(p.is_on_curve());
+    }
+}
+
+#[cfg(feature = "bn_256")]
+#[test]
+fn test_generators_from_label() {
+    use crate::bn_256::G1Affine;
+
+    let generators = generators_from_label::<G1Affine>(b"generators", 16);
+    assert_eq!(generators.len(), 16);
+    for (i, g) in generators.iter().enumerate() {
+        assert!(g.is_on_curve());
+        assert!(generators[i + 1..].iter().all(|h| h != g));
+    }
+    assert_eq!(
+        generators,
+        generators_from_label::<G1Affine>(b"generators", 16)
+    );
+    assert_eq!(
+        generators[..4],
+        generators_from_label::<G1Affine>(b"generators", 4)[..]
+    );
+    assert_ne!(
+        generators[0],
+        generators_from_label::<G1Affine>(b"other", 1)[0]
+    );
+}
+
+/// `expand_message_xmd_SHA256_38.json`: (msg, len_in_bytes, uniform_bytes)
+#[rustfmt::skip]
+const EXPAND_SHORT_DST: &[(&str, usize, &str)] = &[
+    ("", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+    ("abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+    ("abcdef0123456789", 0x20, "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1"),
+    (Q128, 0x20, "b23a1d2b4d97b2ef7785562a7e8bac7eed54ed6e97e29aa51bfe3f12ddad1ff9"),
+    (A512, 0x20, "4623227bcc01293b8c130bf771da8c298dede7383243dc0993d2d94823958c4c"),
+    ("", 0x80, "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbe\
+        e0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18\
+        eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dc\
+        c541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced"),
+    ("abc", 0x80, "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a\
+        647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635\
+        bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00\
+        058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40"),
+    ("abcdef0123456789", 0x80, "ef904a29bffc4cf9ee82832451c946ac3c8f8058ae97d8d629831a74c6572bd9\
+        ebd0df635cd1f208e2038e760c4994984ce73f0d55ea9f22af83ba4734569d4b\
+        c95e18350f740c07eef653cbb9f87910d833751825f0ebefa1abe5420bb52be1\
+        4cf489b37fe1a72f7de2d10be453b2c9d9eb20c7e3f6edc5a60629178d9478df"),
+    (Q128, 0x80, "80be107d0884f0d881bb460322f0443d38bd222db8bd0b0a5312a6fedb49c1bb\
+        d88fd75d8b9a09486c60123dfa1d73c1cc3169761b17476d3c6b7cbbd727acd0\
+        e2c942f4dd96ae3da5de368d26b32286e32de7e5a8cb2949f866a0b80c58116b\
+        29fa7fabb3ea7d520ee603e0c25bcaf0b9a5e92ec6a1fe4e0391d1cdbce8c68a"),
+    (A512, 0x80, "546aff5444b5b79aa6148bd81728704c32decb73a3ba76e9e75885cad9def1d0\
+        6d6792f8a7d12794e90efed817d96920d728896a4510864370c207f99bd4a608\
+        ea121700ef01ed879745ee3e4ceef777eda6d9e5e38b90c86ea6fb0b36504ba4\
+        a45d22e86f6db5dd43d98a294bebb9125d5b794e9d2a81181066eb954966a487"),
+];
+
+/// `expand_message_xmd_SHA256_256.json`: (msg, len_in_bytes, uniform_bytes)
+#[rustfmt::skip]
+const EXPAND_LONG_DST: &[(&str, usize, &str)] = &[
+    ("", 0x20, "e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3"),
+    ("abc", 0x20, "52dbf4f36cf560fca57dedec2ad924ee9c266341d8f3d6afe5171733b16bbb12"),
+    ("abcdef0123456789", 0x20, "35387dcf22618f3728e6c686490f8b431f76550b0b2c61cbc1ce7001536f4521"),
+    (Q128, 0x20, "01b637612bb18e840028be900a833a74414140dde0c4754c198532c3a0ba42bc"),
+    (A512, 0x20, "20cce7033cabc5460743180be6fa8aac5a103f56d481cf369a8accc0c374431b"),
+    ("", 0x80, "14604d85432c68b757e485c8894db3117992fc57e0e136f71ad987f789a0abc2\
+        87c47876978e2388a02af86b1e8d1342e5ce4f7aaa07a87321e691f6fba7e007\
+        2eecc1218aebb89fb14a0662322d5edbd873f0eb35260145cd4e64f748c5dfe6\
+        0567e126604bcab1a3ee2dc0778102ae8a5cfd1429ebc0fa6bf1a53c36f55dfc"),
+    ("abc", 0x80, "1a30a5e36fbdb87077552b9d18b9f0aee16e80181d5b951d0471d55b66684914\
+        aef87dbb3626eaabf5ded8cd0686567e503853e5c84c259ba0efc37f71c839da\
+        2129fe81afdaec7fbdc0ccd4c794727a17c0d20ff0ea55e1389d6982d1241cb8\
+        d165762dbc39fb0cee4474d2cbbd468a835ae5b2f20e4f959f56ab24cd6fe267"),
+    ("abcdef0123456789", 0x80, "d2ecef3635d2397f34a9f86438d772db19ffe9924e28a1caf6f1c8f15603d402\
+        8f40891044e5c7e39ebb9b31339979ff33a4249206f67d4a1e7c765410bcd249\
+        ad78d407e303675918f20f26ce6d7027ed3774512ef5b00d816e51bfcc96c353\
+        9601fa48ef1c07e494bdc37054ba96ecb9dbd666417e3de289d4f424f502a982"),
+    (Q128, 0x80, "ed6e8c036df90111410431431a232d41a32c86e296c05d426e5f44e75b9a50d3\
+        35b2412bc6c91e0a6dc131de09c43110d9180d0a70f0d6289cb4e43b05f7ee5e\
+        9b3f42a1fad0f31bac6a625b3b5c50e3a83316783b649e5ecc9d3b1d9471cb50\
+        24b7ccf40d41d1751a04ca0356548bc6e703fca02ab521b505e8e45600508d32"),
+    (A512, 0x80, "78b53f2413f3c688f07732c10e5ced29a17c6a16f717179ffbe38d92d6c9ec29\
+        6502eb9889af83a1928cd162e845b0d3c5424e83280fed3d10cffb2f8431f14e\
+        7a23f4c68819d40617589e4c41169d0b56e0e3535be1fd71fbb08bb70c5b5ffe\
+        d953d6c14bf7618b35fc1f4c4b30538236b4b08c9fbf90462447a8ada60be495"),
+];
+
+/// `BLS12381G1_XMD:SHA-256_SSWU_RO_`: (msg, u0, u1, P.x, P.y)
+#[cfg(feature = "bls12_381")]
+#[rustfmt::skip]
+const BLS12_381_G1_VECTORS: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "",
+        "0ba14bd907ad64a016293ee7c2d276b8eae71f25a4b941eece7b0d89f17f75cb\
+            3ae5438a614fb61d6835ad59f29c564f",
+        "019b9bd7979f12657976de2884c7cce192b82c177c80e0ec604436a7f538d231\
+            552f0d96d9f7babe5fa3b19b3ff25ac9",
+        "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4\
+            e8cf62d9c09db0fac349612b759e79a1",
+        "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc\
+            68ee29813bb7994998f3eae0c9c6a265",
+    ),
+    (
+        "abc",
+        "0d921c33f2bad966478a03ca35d05719bdf92d347557ea166e5bba579eea9b83\
+            e9afa5c088573c2281410369fbd32951",
+        "003574a00b109ada2f26a37a91f9d1e740dffd8d69ec0c35e1e9f4652c7dba61\
+            123e9dd2e76c655d956e2b3462611139",
+        "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3a\
+            ee664ba5379a7655d3c68900be2f6903",
+        "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af21553331\
+            1f0b8dfaaa154fa6b88176c229f2885d",
+    ),
+    (
+        "abcdef0123456789",
+        "062d1865eb80ebfa73dcfc45db1ad4266b9f3a93219976a3790ab8d52d3e5f1e\
+            62f3b01795e36834b17b70e7b76246d4",
+        "0cdc3e2f271f29c4ff75020857ce6c5d36008c9b48385ea2f2bf6f96f428a3de\
+            b798aa033cd482d1cdc8b30178b08e3a",
+        "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57\
+            a6a27200a784cbc248e84f357ce82d98",
+        "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889\
+            501dc7413753f9599b099ebcbbd2d709",
+    ),
+    (
+        Q128,
+        "010476f6a060453c0b1ad0b628f3e57c23039ee16eea5e71bb87c3b5419b1255\
+            dc0e5883322e563b84a29543823c0e86",
+        "0b1a912064fb0554b180e07af7e787f1f883a0470759c03c1b6509eb8ce980d1\
+            670305ae7b928226bb58fdc0a419f46e",
+        "15f68eaa693b95ccb85215dc65fa81038d69629f70aeee0d0f677cf22285e7bf\
+            58d7cb86eefe8f2e9bc3f8cb84fac488",
+        "1807a1d50c29f430b8cafc4f8638dfeeadf51211e1602a5f184443076715f91b\
+            b90a48ba1e370edce6ae1062f5e6dd38",
+    ),
+    (
+        A512,
+        "0a8ffa7447f6be1c5a2ea4b959c9454b431e29ccc0802bc052413a9c5b4f9aac\
+            67a93431bd480d15be1e057c8a08e8c6",
+        "05d487032f602c90fa7625dbafe0f4a49ef4a6b0b33d7bb349ff4cf5410d297f\
+            d6241876e3e77b651cfc8191e40a68b7",
+        "082aabae8b7dedb0e78aeb619ad3bfd9277a2f77ba7fad20ef6aabdc6c31d19b\
+            a5a6d12283553294c1825c4b3ca2dcfe",
+        "05b84ae5a942248eea39e1d91030458c40153f3b654ab7872d779ad1e942856a\
+            20c438e8d99bc8abfbf74729ce1f7ac8",
+    ),
+];
+
+/// `BLS12381G2_XMD:SHA-256_SSWU_RO_`: (msg, u0, u1, P.x, P.y), with the
+/// `c0` and `c1` components of each element separated by a comma
+#[cfg(feature = "bls12_381")]
+#[rustfmt::skip]
+const BLS12_381_G2_VECTORS: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "",
+        "03dbc2cce174e91ba93cbb08f26b917f98194a2ea08d1cce\
+            75b2b9cc9f21689d80bd79b594a613d0a68eb807dfdc1cf8,\
+            05a2acec64114845711a54199ea339abd125ba38253b70a9\
+            2c876df10598bd1986b739cad67961eb94f7076511b3b39a",
+        "02f99798e8a5acdeed60d7e18e9120521ba1f47ec0909846\
+            62846bc825de191b5b7641148c0dbc237726a334473eee94,\
+            145a81e418d4010cc027a68f14391b30074e89e60ee7a22f\
+            87217b2f6eb0c4b94c9115b436e6fa4607e95a98de30a435",
+        "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d\
+            69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a,\
+            05cb8437535e20ecffaef7752baddf98034139c38452458b\
+            aeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+        "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca\
+            195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92,\
+            12424ac32561493f3fe3c260708a12b7c620e7be00099a97\
+            4e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+    ),
+    (
+        "abc",
+        "15f7c0aa8f6b296ab5ff9c2c7581ade64f4ee6f1bf18f551\
+            79ff44a2cf355fa53dd2a2158c5ecb17d7c52f63e7195771,\
+            01c8067bf4c0ba709aa8b9abc3d1cef589a4758e09ef5373\
+            2d670fd8739a7274e111ba2fcaa71b3d33df2a3a0c8529dd",
+        "187111d5e088b6b9acfdfad078c4dacf72dcd17ca17c82be\
+            35e79f8c372a693f60a033b461d81b025864a0ad051a06e4,\
+            08b852331c96ed983e497ebc6dee9b75e373d923b729194a\
+            f8e72a051ea586f3538a6ebb1e80881a082fa2b24df9f566",
+        "02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe\
+            0e7a210245129dbec7780ccc7954725f4168aff2787776e6,\
+            139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc\
+            374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8",
+        "1787327b68159716a37440985269cf584bcb1e621d3a7202\
+            be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48,\
+            00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e\
+            03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16",
+    ),
+    (
+        "abcdef0123456789",
+        "0313d9325081b415bfd4e5364efaef392ecf69b087496973\
+            b229303e1816d2080971470f7da112c4eb43053130b785e1,\
+            062f84cb21ed89406890c051a0e8b9cf6c575cf6e8e18ecf\
+            63ba86826b0ae02548d83b483b79e48512b82a6c0686df8f",
+        "1739123845406baa7be5c5dc74492051b6d42504de008c63\
+            5f3535bb831d478a341420e67dcc7b46b2e8cba5379cca97,\
+            01897665d9cb5db16a27657760bbea7951f67ad68f8d55f7\
+            113f24ba6ddd82caef240a9bfa627972279974894701d975",
+        "121982811d2491fde9ba7ed31ef9ca474f0e1501297f68c2\
+            98e9f4c0028add35aea8bb83d53c08cfc007c1e005723cd0,\
+            190d119345b94fbd15497bcba94ecf7db2cbfd1e1fe7da03\
+            4d26cbba169fb3968288b3fafb265f9ebd380512a71c3f2c",
+        "05571a0f8d3c08d094576981f4a3b8eda0a8e771fcdcc8ec\
+            ceaf1356a6acf17574518acb506e435b639353c2e14827c8,\
+            0bb5e7572275c567462d91807de765611490205a941a5a6a\
+            f3b1691bfe596c31225d3aabdf15faff860cb4ef17c7c3be",
+    ),
+    (
+        Q128,
+        "025820cefc7d06fd38de7d8e370e0da8a52498be9b53cba9\
+            927b2ef5c6de1e12e12f188bbc7bc923864883c57e49e253,\
+            034147b77ce337a52e5948f66db0bab47a8d038e712123bb\
+            381899b6ab5ad20f02805601e6104c29df18c254b8618c7b",
+        "0930315cae1f9a6017c3f0c8f2314baa130e1cf13f6532bf\
+            f0a8a1790cd70af918088c3db94bda214e896e1543629795,\
+            10c4df2cacf67ea3cb3108b00d4cbd0b3968031ebc8eac4b\
+            1ebcefe84d6b715fde66bef0219951ece29d1facc8a520ef",
+        "19a84dd7248a1066f737cc34502ee5555bd3c19f2ecdb3c7\
+            d9e24dc65d4e25e50d83f0f77105e955d78f4762d33c17da,\
+            0934aba516a52d8ae479939a91998299c76d39cc0c035cd1\
+            8813bec433f587e2d7a4fef038260eef0cef4d02aae3eb91",
+        "14f81cd421617428bc3b9fe25afbb751d934a00493524bc4\
+            e065635b0555084dd54679df1536101b2c979c0152d09192,\
+            09bcccfa036b4847c9950780733633f13619994394c23ff0\
+            b32fa6b795844f4a0673e20282d07bc69641cee04f5e5662",
+    ),
+    (
+        A512,
+        "190b513da3e66fc9a3587b78c76d1d132b1152174d0b83e3\
+            c1114066392579a45824c5fa17649ab89299ddd4bda54935,\
+            12ab625b0fe0ebd1367fe9fac57bb1168891846039b4216b\
+            9d94007b674de2d79126870e88aeef54b2ec717a887dcf39",
+        "0e6a42010cf435fb5bacc156a585e1ea3294cc81d0ceb819\
+            24d95040298380b164f702275892cedd81b62de3aba3f6b5,\
+            117d9a0defc57a33ed208428cb84e54c85a6840e7648480a\
+            e428838989d25d97a0af8e3255be62b25c2a85630d2dddd8",
+        "01a6ba2f9a11fa5598b2d8ace0fbe0a0eacb65deceb476fb\
+            bcb64fd24557c2f4b18ecfc5663e54ae16a84f5ab7f62534,\
+            11fca2ff525572795a801eed17eb12785887c7b63fb77a42\
+            be46ce4a34131d71f7a73e95fee3f812aea3de78b4d01569",
+        "0b6798718c8aed24bc19cb27f866f1c9effcdbf92397ad64\
+            48b5c9db90d2b9da6cbabf48adc1adf59a1a28344e79d57e,\
+            03a47f8e6d1763ba0cad63d6114c0accbef65707825a511b\
+            251a660a9b3994249ae4e63fac38b23da0c398689ee2ab52",
+    ),
+];
@@ -1,12 +1,15 @@
 use crate::jubjub::{Fq, Fr};
 use math::{
-    biginteger::BigInteger256,
+    biginteger::{BigInteger, BigInteger256},
+    bytes::{FromBytes, ToBytes},
     curves::{
         models::{ModelParameters, MontgomeryModelParameters, TEModelParameters},
         twisted_edwards_extended::{GroupAffine, GroupProjective},
         Curve,
     },
     field_new,
+    fields::{Field, FpParameters, PrimeField, SquareRootField},
+    One, Zero,
 };
 
 #[cfg(test)]
@@ -25,6 +28,55 @@ impl Curve for JubJub {
     type Projective = JubJubProjective;
 }
 
+/// Encodes `point` in the 32-byte format used by Zcash Sapling: the
+/// little-endian `y` coordinate, with the sign (lowest bit) of `x` stored
+/// in the most significant bit of the last byte.
+pub fn to_zcash_bytes(point: &JubJubAffine) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    point
+        .y
+        .into_repr()
+        .write(&mut bytes[..])
+        .expect("a base field element fits in 32 bytes");
+    if point.x.into_repr().is_odd() {
+        bytes[31] |= 0x80;
+    }
+    bytes
+}
+
+/// Decodes a point from the 32-byte format used by Zcash Sapling.
+///
+/// Returns `None` if `y` is not canonical or no point with this `y` exists.
+/// The point is on the curve but not guaranteed to be in the prime order
+/// subgroup, see `GroupAffine::is_in_prime_subgroup`.
+pub fn from_zcash_bytes(bytes: &[u8; 32]) -> Option<JubJubAffine> {
+    let sign = bytes[31] >> 7 == 1;
+    let mut y_bytes = *bytes;
+    y_bytes[31] &= 0x7f;
+
+    let y_repr = BigInteger256::read(&y_bytes[..]).ok()?;
+    if y_repr >= <Fq as PrimeField>::Params::MODULUS {
+        return None;
+    }
+    let y = Fq::from_repr(y_repr);
+
+    // x^2 = (1 - y^2) / (a - d * y^2)
+    let y2 = y.square();
+    let numerator = Fq::one() - &y2;
+    let denominator =
+        <JubJubParameters as TEModelParameters>::COEFF_A - &(JubJubParameters::COEFF_D * &y2);
+    let x = (numerator * &denominator.inverse()?).sqrt()?;
+
+    if x.into_repr().is_odd() == sign {
+        Some(JubJubAffine::new(x, y))
+    } else if x.is_zero() {
+        // Zero has no negative, so the sign bit must not be set.
+        None
+    } else {
+        Some(JubJubAffine::new(-x, y))
+    }
+}
+
 #[rustfmt::skip]
 const GENERATOR_X: Fq = field_new!(Fq, BigInteger256([
     14080349899812819339,
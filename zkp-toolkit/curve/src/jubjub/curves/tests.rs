@@ -2,9 +2,11 @@
 
 use core::str::FromStr;
 use math::{
+    biginteger::BigInteger256,
     bytes::{FromBytes, ToBytes},
     curves::{AffineCurve, ProjectiveCurve},
-    test_rng, Zero,
+    fields::PrimeField,
+    test_rng, One, Zero,
 };
 use rand::Rng;
 
@@ -117,3 +119,225 @@ fn test_bytes() {
 fn test_montgomery_conversion() {
     montgomery_conversion_test::<JubJubParameters>();
 }
+
+#[test]
+fn test_prime_subgroup() {
+    let generator = JubJubAffine::prime_subgroup_generator();
+    assert!(generator.is_in_prime_subgroup());
+
+    // (0, -1) is the point of order 2.
+    let p = JubJubAffine::new(Fq::zero(), -Fq::one());
+    assert!(p.is_on_curve());
+    assert!(!p.is_in_prime_subgroup());
+    assert!(p.mul_by_cofactor().is_zero());
+
+    let mut rng = test_rng();
+    let a: JubJubAffine = rng.gen();
+    assert!(a.is_in_prime_subgroup());
+}
+
+#[test]
+fn test_zcash_bytes() {
+    let mut identity = [0u8; 32];
+    identity[0] = 1;
+    assert_eq!(to_zcash_bytes(&JubJubAffine::zero()), identity);
+    assert_eq!(from_zcash_bytes(&identity), Some(JubJubAffine::zero()));
+
+    // (0, -1) is encoded as q - 1.
+    #[rustfmt::skip]
+    let minus_one = [
+        0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+        0xfe, 0x5b, 0xfe, 0xff, 0x02, 0xa4, 0xbd, 0x53,
+        0x05, 0xd8, 0xa1, 0x09, 0x08, 0xd8, 0x39, 0x33,
+        0x48, 0x7d, 0x9d, 0x29, 0x53, 0xa7, 0xed, 0x73,
+    ];
+    let p = JubJubAffine::new(Fq::zero(), -Fq::one());
+    assert_eq!(to_zcash_bytes(&p), minus_one);
+    assert_eq!(from_zcash_bytes(&minus_one), Some(p));
+
+    // x = 0 has no negative, so the sign bit must be clear.
+    let mut bad = identity;
+    bad[31] |= 0x80;
+    assert_eq!(from_zcash_bytes(&bad), None);
+
+    // y = q is not canonical.
+    let mut bad = minus_one;
+    bad[0] = 0x01;
+    assert_eq!(from_zcash_bytes(&bad), None);
+
+    let mut rng = test_rng();
+    for _ in 0..100 {
+        let a: JubJubAffine = rng.gen();
+        let bytes = to_zcash_bytes(&a);
+        assert_eq!(from_zcash_bytes(&bytes), Some(a));
+
+        // Negation only flips the sign bit.
+        let neg_bytes = to_zcash_bytes(&-a);
+        assert_eq!(bytes[..31], neg_bytes[..31]);
+        assert_eq!(bytes[31] ^ 0x80, neg_bytes[31]);
+    }
+}
+
+#[test]
+fn test_zcash_bytes_vectors() {
+    // The generator of the full group of the zkcrypto `jubjub` crate of
+    // Zcash, of y = 11.
+    let x = Fq::from_repr(BigInteger256([
+        0xe4b3_d35d_f1a7_adfe,
+        0xcaf5_5d1b_29bf_81af,
+        0x8b0f_03dd_d60a_8187,
+        0x62ed_cbb8_bf37_87c8,
+    ]));
+    let full_generator = JubJubAffine::new(x, Fq::from(11u64));
+    assert!(full_generator.is_on_curve());
+
+    // The encodings of its cofactor multiple and the next 15 multiples of
+    // that, from `test_serialization_consistency` of the crate.
+    #[rustfmt::skip]
+    let vectors: [[u8; 32]; 16] = [
+        [
+            0xcb, 0x55, 0x0c, 0xd5, 0x38, 0xea, 0x0c, 0xc1,
+            0x13, 0x84, 0x80, 0x40, 0x8e, 0x6e, 0xaa, 0xb9,
+            0xb3, 0x6c, 0x61, 0x3f, 0x0d, 0xd3, 0xf7, 0x78,
+            0x4f, 0xdb, 0x6e, 0xea, 0x83, 0x7b, 0x13, 0xd7,
+        ],
+        [
+            0x71, 0x9a, 0xf0, 0xe6, 0xe0, 0xc6, 0xd0, 0xaa,
+            0x68, 0x0f, 0x3b, 0x7e, 0x97, 0xde, 0xe9, 0xc3,
+            0xcb, 0xc3, 0xa7, 0x81, 0x59, 0x79, 0xf0, 0x8e,
+            0x33, 0xa6, 0x40, 0xfa, 0xb8, 0xca, 0x9a, 0xb1,
+        ],
+        [
+            0xc5, 0x29, 0x5d, 0xd1, 0xcb, 0x37, 0xa4, 0xae,
+            0x58, 0x00, 0x5a, 0xc7, 0x01, 0x9c, 0x95, 0x8d,
+            0xf0, 0x1d, 0x0e, 0x52, 0x56, 0xe1, 0x7e, 0x81,
+            0xba, 0x9d, 0x94, 0xa2, 0xdb, 0x33, 0x9c, 0xc7,
+        ],
+        [
+            0xb6, 0x75, 0xfa, 0xf1, 0x51, 0xc4, 0xc7, 0xe3,
+            0x97, 0x4a, 0xf3, 0x11, 0xdd, 0x61, 0xc8, 0x8b,
+            0xc0, 0x53, 0xe7, 0x23, 0xd6, 0x0e, 0x5f, 0x45,
+            0x82, 0xc9, 0x04, 0x74, 0xb1, 0x13, 0xb3, 0x00,
+        ],
+        [
+            0x76, 0x29, 0x1d, 0xc8, 0x3c, 0xbd, 0x77, 0xfc,
+            0x4e, 0x28, 0xe6, 0x12, 0xd0, 0xdd, 0x26, 0xd6,
+            0xb0, 0xfa, 0x04, 0x0a, 0x4d, 0x65, 0x1a, 0xd8,
+            0xc1, 0xc6, 0xe2, 0x54, 0x19, 0xb1, 0xe6, 0xb9,
+        ],
+        [
+            0xe2, 0xbd, 0xe3, 0xd0, 0x70, 0x75, 0x88, 0x62,
+            0x48, 0x26, 0xd3, 0xa7, 0xfe, 0x52, 0xae, 0x71,
+            0x70, 0xa6, 0x8a, 0xab, 0xa6, 0x71, 0x34, 0xfb,
+            0x81, 0xc5, 0x8a, 0x2d, 0xc3, 0x07, 0x3d, 0x8c,
+        ],
+        [
+            0x26, 0xc6, 0x9c, 0xc4, 0x92, 0xe1, 0x37, 0xa3,
+            0x8a, 0xb2, 0x9d, 0x80, 0x73, 0x87, 0xcc, 0xd7,
+            0x00, 0x21, 0xab, 0x14, 0x3c, 0x20, 0x8e, 0xd1,
+            0x21, 0xe9, 0x7d, 0x92, 0xcf, 0x0c, 0x10, 0x18,
+        ],
+        [
+            0x11, 0xbb, 0xe7, 0x53, 0xa5, 0x24, 0xe8, 0xb8,
+            0x8c, 0xcd, 0xc3, 0xfc, 0xa6, 0x55, 0x3b, 0x56,
+            0x03, 0xe2, 0xd3, 0x43, 0xb3, 0x1d, 0xee, 0xb5,
+            0x66, 0x8e, 0x3a, 0x3f, 0x39, 0x59, 0xae, 0x8a,
+        ],
+        [
+            0xd2, 0x9f, 0x50, 0x10, 0xb5, 0x27, 0xdd, 0xcc,
+            0xe0, 0x90, 0x91, 0x4f, 0x36, 0xe7, 0x08, 0x8c,
+            0x8e, 0xd8, 0x5d, 0xbe, 0xb7, 0x74, 0xae, 0x3f,
+            0x21, 0xf2, 0xb1, 0x76, 0x94, 0x28, 0xf1, 0xcb,
+        ],
+        [
+            0x00, 0x8f, 0x6b, 0x66, 0x95, 0xbb, 0x1b, 0x7c,
+            0x12, 0x0a, 0x62, 0x1c, 0x71, 0x7b, 0x79, 0xb9,
+            0x1d, 0x98, 0x0e, 0x82, 0x95, 0x1c, 0x57, 0x23,
+            0x87, 0x87, 0x99, 0x36, 0x70, 0x35, 0x36, 0x44,
+        ],
+        [
+            0xb2, 0x83, 0x55, 0xa0, 0xd6, 0x33, 0xd0, 0x9d,
+            0xc4, 0x98, 0xf7, 0x5d, 0xca, 0x38, 0x51, 0xef,
+            0x9b, 0x7a, 0x3b, 0xbc, 0xed, 0xfd, 0x0b, 0xa9,
+            0xd0, 0xec, 0x0c, 0x04, 0xa3, 0xd3, 0x58, 0x61,
+        ],
+        [
+            0xf6, 0xc2, 0xe7, 0xc3, 0x9f, 0x65, 0xb4, 0x85,
+            0x50, 0x15, 0xb9, 0xdc, 0xc3, 0x73, 0x90, 0x0c,
+            0x5a, 0x96, 0x2c, 0x75, 0x08, 0x9c, 0xa8, 0xf8,
+            0xce, 0x29, 0x3c, 0x52, 0x43, 0x4b, 0x39, 0x43,
+        ],
+        [
+            0xd4, 0xcd, 0xab, 0x99, 0x71, 0x10, 0xc2, 0xf1,
+            0xe0, 0x2b, 0xb1, 0x6e, 0xbe, 0xf8, 0x16, 0xc9,
+            0xd0, 0xa6, 0x02, 0x53, 0x86, 0x82, 0x55, 0x81,
+            0xa6, 0x88, 0xb9, 0xbf, 0xa3, 0x26, 0x36, 0x0a,
+        ],
+        [
+            0x08, 0x3c, 0xbe, 0x27, 0x99, 0xde, 0x77, 0x17,
+            0x8e, 0xed, 0x0c, 0x6e, 0x92, 0x09, 0x13, 0xdb,
+            0x8f, 0x40, 0xa1, 0x63, 0xc7, 0x4d, 0x27, 0x94,
+            0x46, 0xd5, 0xf6, 0xe3, 0x96, 0xb2, 0xed, 0xb2,
+        ],
+        [
+            0x0b, 0x72, 0xd9, 0xa0, 0x65, 0x25, 0x64, 0xdc,
+            0x38, 0x72, 0x2a, 0x1f, 0x8a, 0x21, 0x54, 0x9d,
+            0xd6, 0xa7, 0x49, 0xe9, 0x73, 0x51, 0x7c, 0x86,
+            0x0f, 0x1f, 0xb5, 0x3c, 0xb8, 0x82, 0xaf, 0x9f,
+        ],
+        [
+            0x8d, 0xee, 0xeb, 0xca, 0xf1, 0x20, 0xd2, 0x0a,
+            0x7f, 0xe6, 0x36, 0x1f, 0x92, 0x50, 0xf7, 0x09,
+            0x6b, 0x7c, 0x00, 0x1a, 0xcb, 0x10, 0xed, 0x22,
+            0xd6, 0x93, 0x85, 0x0f, 0x1d, 0xec, 0x25, 0x58,
+        ],
+    ];
+
+    let generator = full_generator.mul_by_cofactor();
+    let mut p = generator;
+    for bytes in vectors.iter() {
+        assert_eq!(&to_zcash_bytes(&p), bytes);
+        assert_eq!(from_zcash_bytes(bytes), Some(p));
+        p = p + generator;
+    }
+
+    // The Sapling spend authorization and binding signature generators of
+    // librustzcash, in the prime order subgroup.
+    #[rustfmt::skip]
+    let spending_key_generator = [
+        0x30, 0xb5, 0xf2, 0xaa, 0xad, 0x32, 0x56, 0x30,
+        0xbc, 0xdd, 0xdb, 0xce, 0x4d, 0x67, 0x65, 0x6d,
+        0x05, 0xfd, 0x1c, 0xc2, 0xd0, 0x37, 0xbb, 0x53,
+        0x75, 0xb6, 0xe9, 0x6d, 0x9e, 0x01, 0xa1, 0xd7,
+    ];
+    #[rustfmt::skip]
+    let value_commitment_randomness_generator = [
+        0x8b, 0x6a, 0x0b, 0x38, 0xb9, 0xfa, 0xae, 0x3c,
+        0x3b, 0x80, 0x3b, 0x47, 0xb0, 0xf1, 0x46, 0xad,
+        0x50, 0xab, 0x22, 0x1e, 0x6e, 0x2a, 0xfb, 0xe6,
+        0xdb, 0xde, 0x45, 0xcb, 0xa9, 0xd3, 0x81, 0xed,
+    ];
+    for bytes in [spending_key_generator, value_commitment_randomness_generator].iter() {
+        let p = from_zcash_bytes(bytes).unwrap();
+        assert!(p.is_in_prime_subgroup());
+        assert_eq!(&to_zcash_bytes(&p), bytes);
+    }
+
+    // The non-canonical encodings of ZIP 216: (0, 1) and (0, -1) of the
+    // sign bit set, which read once it is cleared.
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    #[rustfmt::skip]
+    let minus_one = [
+        0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff,
+        0xfe, 0x5b, 0xfe, 0xff, 0x02, 0xa4, 0xbd, 0x53,
+        0x05, 0xd8, 0xa1, 0x09, 0x08, 0xd8, 0x39, 0x33,
+        0x48, 0x7d, 0x9d, 0x29, 0x53, 0xa7, 0xed, 0x73,
+    ];
+    for encoding in [one, minus_one].iter() {
+        let mut non_canonical = *encoding;
+        non_canonical[31] |= 0x80;
+        assert_eq!(from_zcash_bytes(&non_canonical), None);
+        assert!(from_zcash_bytes(encoding).is_some());
+    }
+}
@@ -46,7 +46,12 @@ pub use bls12_377::Bls12_377;
 ///////////////////////////////////////////////////////////////////////////////
 #[cfg(all(
     not(feature = "bls12_377"),
-    any(feature = "edwards_bls12", feature = "sw6", feature = "edwards_sw6")
+    any(
+        feature = "edwards_bls12",
+        feature = "sw6",
+        feature = "edwards_sw6",
+        feature = "bw6_761"
+    )
 ))]
 pub(crate) mod bls12_377;
 
@@ -110,6 +115,13 @@ pub(crate) mod sw6;
 pub mod edwards_sw6;
 ///////////////////////////////////////////////////////////////////////////////
 
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(feature = "bw6_761")]
+pub mod bw6_761;
+#[cfg(feature = "bw6_761")]
+pub use bw6_761::BW6_761;
+///////////////////////////////////////////////////////////////////////////////
+
 ///////////////////////////////////////////////////////////////////////////////
 #[cfg(feature = "bn_256")]
 pub mod bn_256;
@@ -138,5 +150,17 @@ pub mod curve25519;
 pub use curve25519::Curve25519;
 ///////////////////////////////////////////////////////////////////////////////
 
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+#[cfg(feature = "secp256k1")]
+pub use secp256k1::Secp256k1;
+///////////////////////////////////////////////////////////////////////////////
+
+///////////////////////////////////////////////////////////////////////////////
+#[cfg(feature = "hash_to_curve")]
+pub mod hash_to_curve;
+///////////////////////////////////////////////////////////////////////////////
+
 #[cfg(test)]
 pub(crate) mod tests;
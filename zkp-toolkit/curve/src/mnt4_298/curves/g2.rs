@@ -58,11 +58,11 @@ impl SWModelParameters for Parameters {
     /// 475922286169261325753349249653048451545124879932565935237842521413255878328503110407553025
     #[rustfmt::skip]
     const COFACTOR: &'static [u64] = &[
-        13170844244115976942,
-        6062972374298450277,
-        7810648509476950452,
-        16507697214350673085,
-        723130988757,
+        15480692783052488705,
+        9802782456999489873,
+        14622846468721090623,
+        11702080941310629006,
+        4110145082483,
     ];
 
     /// COFACTOR^(-1) mod r =
@@ -65,18 +65,18 @@ impl SWModelParameters for Parameters {
     /// 41898490967918953402344214791240637128170709919953949071783502921025352812571106773058893763790338921418070971888049094905534395567574915333486969589229856772141392370549616644545554517640527237829320384324374366385444967219201
     #[rustfmt::skip]
     const COFACTOR: &'static [u64] = &[
-        8121037487654637401,
-        10852008117014267471,
-        8173141446812032609,
-        1981366235846946267,
-        10083249093827677683,
-        7936793942629294791,
-        273611532446643446,
-        5724916205372494142,
-        9758748372896588778,
-        14576012668941958316,
-        7826002690588963973,
-        184348979057415,
+        16436257212445032449,
+        8690275530472745198,
+        17315389657026393162,
+        1645397558963170979,
+        3544984605440726586,
+        12665092767997125024,
+        11083680675069097885,
+        575819899841080717,
+        6825179918269667443,
+        13256793349531086829,
+        1162650133526138285,
+        497830423872529,
     ];
 
     /// COFACTOR^(-1) mod r =
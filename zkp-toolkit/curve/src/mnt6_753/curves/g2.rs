@@ -97,18 +97,30 @@ impl SWModelParameters for Parameters {
     /// 1755483545388786116744270475466687259186947712032004459714210070280389500116987496124098574823389466285978151140155508638765729019174599527183600372094760023144398285325863550664578643924584541949466179502227232245309952839189635010671372908411609248348904807785904229403747495114436660255866932060472369629692502198423138429922875792635236729929780298333055698257230963645509826963717287902205842627121011526048163097042046361575549171961352924692480000
     #[rustfmt::skip]
     const COFACTOR: &'static [u64] = &[
-        14257870366738354724,
-        16332099126112812691,
-        16180867228539301329,
-        7250559771010835766,
-        6350961449885540165,
-        527544447342807112,
-        3597423024414210037,
-        392998627085518731,
-        6224057631692187718,
-        14873194180781466152,
-        8717918574387205629,
-        42386371095684,
+        17839255819456086016,
+        500623104730997740,
+        2110252009236161768,
+        1500878543414750896,
+        12839751506594314239,
+        8978537329634833065,
+        13830010955957826199,
+        7626514311663165506,
+        14876243211944528805,
+        2316601947950921451,
+        2601177562497904269,
+        18300670698693155036,
+        17321427554953155530,
+        12586270719596716948,
+        807965545138267130,
+        13086323046094411844,
+        16597411233431396880,
+        5578519820383338987,
+        16478065054289650824,
+        12110148809888520863,
+        5901144846689643164,
+        3407195776166256068,
+        14663852814447346059,
+        13435169368,
     ];
 
     /// COFACTOR^(-1) mod r =
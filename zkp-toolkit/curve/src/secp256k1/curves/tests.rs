@@ -0,0 +1,112 @@
+use core::str::FromStr;
+use math::{
+    biginteger::BigInteger,
+    curves::{models::SWModelParameters, AffineCurve, ProjectiveCurve},
+    fields::{Field, FpParameters, PrimeField},
+    test_rng, One, Zero,
+};
+use rand::Rng;
+
+use crate::{
+    secp256k1::{Fq, Fr, FrParameters, Secp256k1Affine, Secp256k1Parameters, Secp256k1Projective},
+    tests::{
+        curves::{curve_tests, sw_tests},
+        groups::group_test,
+    },
+};
+
+#[test]
+fn test_projective_curve() {
+    curve_tests::<Secp256k1Projective>();
+
+    sw_tests::<Secp256k1Parameters>();
+}
+
+#[test]
+fn test_projective_group() {
+    let mut rng = test_rng();
+    let a: Secp256k1Projective = rng.gen();
+    let b: Secp256k1Projective = rng.gen();
+    group_test(a, b);
+}
+
+#[test]
+fn test_generator() {
+    let generator = Secp256k1Affine::prime_subgroup_generator();
+    assert!(generator.is_on_curve());
+    assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+
+    // the generator has order r
+    assert!(generator.mul(FrParameters::MODULUS).is_zero());
+    assert_eq!(generator.mul(-Fr::one()).into_affine(), -generator);
+    assert!(!generator.mul(Fr::one()).is_zero());
+}
+
+#[test]
+fn test_mul_vectors() {
+    // k * G for the scalars 2, 3 and 0xAA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522
+    let vectors = [
+        (
+            "2",
+            "89565891926547004231252920425935692360644145829622209833684329913297188986597",
+            "12158399299693830322967808612713398636155367887041628176798871954788371653930",
+        ),
+        (
+            "3",
+            "112711660439710606056748659173929673102114977341539408544630613555209775888121",
+            "25583027980570883691656905877401976406448868254816295069919888960541586679410",
+        ),
+        (
+            "77059549740374936337596179780007572461065571555507600191520924336939429631266",
+            "23960696573610029253367988531088137163395307586261939660421638862381187549638",
+            "5176714262835066281222529495396963740342889891785920566957581938958806065714",
+        ),
+    ];
+
+    let generator = Secp256k1Projective::prime_subgroup_generator();
+    for (k, x, y) in vectors.iter() {
+        let k = Fr::from_str(k).unwrap();
+        let expected =
+            Secp256k1Affine::new(Fq::from_str(x).unwrap(), Fq::from_str(y).unwrap(), false);
+        assert!(expected.is_on_curve());
+        assert_eq!(generator.mul(k).into_affine(), expected);
+        assert_eq!(generator.mul_glv(&k).into_affine(), expected);
+    }
+}
+
+#[test]
+fn test_glv_endomorphism() {
+    let glv = Secp256k1Parameters::glv().unwrap();
+    assert_eq!(glv.beta.pow(&[3]), Fq::one());
+    assert_ne!(glv.beta, Fq::one());
+    assert_eq!(glv.lambda.pow(&[3]), Fr::one());
+    assert_ne!(glv.lambda, Fr::one());
+
+    let g = Secp256k1Affine::prime_subgroup_generator();
+    let phi_g = Secp256k1Affine::new(g.x * &glv.beta, g.y, false);
+    assert_eq!(g.mul(glv.lambda).into_affine(), phi_g);
+
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let k: Fr = rng.gen();
+        let ((neg1, k1), (neg2, k2)) = glv.decompose(&k);
+        assert!(k1.num_bits() <= 129);
+        assert!(k2.num_bits() <= 129);
+
+        let signed = |neg, k| {
+            if neg {
+                -Fr::from_repr(k)
+            } else {
+                Fr::from_repr(k)
+            }
+        };
+        assert_eq!(signed(neg1, k1) + &(signed(neg2, k2) * &glv.lambda), k);
+    }
+
+    let p: Secp256k1Projective = rng.gen();
+    let p_affine = p.into_affine();
+    for _ in 0..100 {
+        let k: Fr = rng.gen();
+        assert_eq!(p.mul_glv(&k), p_affine.mul(k.into_repr()));
+    }
+}
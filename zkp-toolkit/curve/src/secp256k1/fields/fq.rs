@@ -0,0 +1,21 @@
+use math::{
+    biginteger::BigInteger256,
+    derive_field_params,
+    fields::{Fp256, Fp256Parameters, FpParameters},
+};
+
+pub type Fq = Fp256<FqParameters>;
+
+pub struct FqParameters;
+
+impl Fp256Parameters for FqParameters {}
+
+impl FpParameters for FqParameters {
+    type BigInt = BigInteger256;
+
+    // MODULUS = 2^256 - 2^32 - 977
+    derive_field_params!(
+        "115792089237316195423570985008687907853269984665640564039457584007908834671663",
+        generator = 3
+    );
+}
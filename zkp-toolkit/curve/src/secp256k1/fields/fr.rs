@@ -0,0 +1,21 @@
+use math::{
+    biginteger::BigInteger256,
+    derive_field_params,
+    fields::{Fp256, Fp256Parameters, FpParameters},
+};
+
+pub type Fr = Fp256<FrParameters>;
+
+pub struct FrParameters;
+
+impl Fp256Parameters for FrParameters {}
+
+impl FpParameters for FrParameters {
+    type BigInt = BigInteger256;
+
+    // MODULUS = the order of the secp256k1 group
+    derive_field_params!(
+        "115792089237316195423570985008687907852837564279074904382605163141518161494337",
+        generator = 7
+    );
+}
@@ -0,0 +1,66 @@
+use math::{
+    fields::{Field, FpParameters, PrimeField},
+    test_rng, One, Zero,
+};
+use rand::Rng;
+
+use crate::{
+    secp256k1::{Fq, FqParameters, Fr},
+    tests::fields::{field_test, primefield_test, sqrt_field_test},
+};
+
+#[test]
+fn test_fr() {
+    let mut rng = test_rng();
+    let a: Fr = rng.gen();
+    let b: Fr = rng.gen();
+    field_test(a, b);
+    primefield_test::<Fr>();
+    sqrt_field_test(b);
+}
+
+#[test]
+fn test_fq() {
+    let mut rng = test_rng();
+    let a: Fq = rng.gen();
+    let b: Fq = rng.gen();
+    field_test(a, b);
+    primefield_test::<Fq>();
+    sqrt_field_test(a);
+}
+
+#[test]
+fn test_full_width_modulus() {
+    // Both moduli use all 256 bits, so sums, doubles and Montgomery products
+    // can overflow the limbs before being reduced.
+    assert_eq!(FqParameters::REPR_SHAVE_BITS, 0);
+
+    let minus_one = -Fq::one();
+    let minus_two = minus_one - &Fq::one();
+    assert_eq!(minus_one + &minus_one, minus_two);
+    assert_eq!(minus_one.double(), minus_two);
+    assert_eq!(minus_one * &minus_one, Fq::one());
+    assert_eq!(minus_one.square(), Fq::one());
+    assert_eq!(minus_one + &Fq::one(), Fq::zero());
+
+    let minus_one = -Fr::one();
+    assert_eq!(
+        (minus_one + &minus_one).into_repr(),
+        (-Fr::from(2u64)).into_repr()
+    );
+    assert_eq!(minus_one.square(), Fr::one());
+
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let a: Fq = rng.gen();
+        let b: Fq = rng.gen();
+        assert_eq!(a + &b - &b, a);
+        assert_eq!(a.double(), a + &a);
+        assert_eq!(
+            (a + &b).square(),
+            a.square() + &(a * &b).double() + &b.square()
+        );
+        assert_eq!(a * &a.inverse().unwrap(), Fq::one());
+        assert_eq!(Fq::from_repr(a.into_repr()), a);
+    }
+}
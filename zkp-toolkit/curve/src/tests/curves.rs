@@ -1,9 +1,11 @@
 #![allow(unused)]
 use math::{
-    curves::{AffineCurve, ProjectiveCurve, flags::SWFlags},
-    ToBytes,
-    Field, MontgomeryModelParameters, One, PrimeField,
-    SWModelParameters, TEModelParameters, UniformRand, Vec, Zero,
+    curves::{
+        flags::{Flags, SWFlags, COMPRESSION_FLAG},
+        AffineCurve, ProjectiveCurve,
+    },
+    Field, FpParameters, MontgomeryModelParameters, One, PrimeField, SWModelParameters,
+    TEModelParameters, ToBytes, UniformRand, Vec, Zero,
 };
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
@@ -194,8 +196,11 @@ fn random_transformation_test<G: ProjectiveCurve>() {
             .map(|_| G::rand(&mut rng))
             .collect::<Vec<_>>();
 
+        // Random points are not normalized, unless the affine and projective
+        // representations are the same.
+        let normalized = G::prime_subgroup_generator().double().is_normalized();
         for i in &v {
-            assert!(!i.is_normalized());
+            assert_eq!(i.is_normalized(), normalized);
         }
 
         use rand::distributions::{Distribution, Uniform};
@@ -221,6 +226,67 @@ fn random_transformation_test<G: ProjectiveCurve>() {
 
         assert_eq!(v, expected_v);
     }
+
+    // Batch conversion to affine, with identities at both ends
+    let mut v = (0..ITERATIONS)
+        .map(|_| G::rand(&mut rng))
+        .collect::<Vec<_>>();
+    v[0] = G::zero();
+    v[ITERATIONS - 1] = G::zero();
+    let expected_v = v.iter().map(|v| v.into_affine()).collect::<Vec<_>>();
+    assert_eq!(G::batch_into_affine(v), expected_v);
+    assert_eq!(
+        G::batch_into_affine(vec![G::zero(); 3]),
+        vec![G::Affine::zero(); 3]
+    );
+    assert!(G::batch_into_affine(vec![]).is_empty());
+}
+
+fn batch_affine_addition_test<G: ProjectiveCurve>() {
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    let a = G::rand(&mut rng).into_affine();
+    let b = G::rand(&mut rng).into_affine();
+    let a2 = a.into_projective().double().into_affine();
+    let zero = G::Affine::zero();
+
+    // Doublings, negations and infinities mixed with generic additions.
+    let mut pairs = vec![
+        (a, b),
+        (a, a),
+        (a, -a),
+        (zero, a),
+        (a, zero),
+        (zero, zero),
+        (a2, a),
+        (a, a2),
+        (-a, -a),
+        (b, b),
+    ];
+    for _ in 0..ITERATIONS {
+        pairs.push((
+            G::rand(&mut rng).into_affine(),
+            G::rand(&mut rng).into_affine(),
+        ));
+    }
+
+    let expected = pairs
+        .iter()
+        .map(|(a, b)| {
+            let mut sum = a.into_projective();
+            sum.add_assign_mixed(b);
+            sum.into_affine()
+        })
+        .collect::<Vec<_>>();
+
+    let (mut bases, other): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
+    G::Affine::batch_add_in_place_affine(&mut bases, &other);
+    assert_eq!(bases, expected);
+
+    // Only special cases, so no inversion is needed at all.
+    let mut bases = vec![zero, a, a];
+    G::Affine::batch_add_in_place_affine(&mut bases, &[a, zero, -a]);
+    assert_eq!(bases, vec![a, a, zero]);
 }
 
 pub fn curve_tests<G: ProjectiveCurve>() {
@@ -276,15 +342,248 @@ pub fn curve_tests<G: ProjectiveCurve>() {
         assert_eq!(b, c);
     }
 
+    // Serialized size of affine points
+    {
+        use math::ConstSerializedSize;
+        for a in &[G::Affine::zero(), G::rand(&mut rng).into_affine()] {
+            let mut bytes = vec![];
+            a.write(&mut bytes).unwrap();
+            assert_eq!(bytes.len(), G::Affine::SERIALIZED_SIZE);
+            let (b, rest) = G::Affine::read_exact_from(&bytes).unwrap();
+            assert_eq!(*a, b);
+            assert!(rest.is_empty());
+        }
+    }
+
     random_addition_test::<G>();
     random_multiplication_test::<G>();
     random_doubling_test::<G>();
     random_negation_test::<G>();
     random_transformation_test::<G>();
+    batch_affine_addition_test::<G>();
 }
 
 pub fn sw_tests<P: SWModelParameters>() {
     //sw_from_random_bytes::<P>();
+    sw_compressed_serialization_test::<P>();
+    sw_projective_serde_test::<P>();
+    sw_display_test::<P>();
+    sw_cofactor_test::<P>();
+}
+
+fn sw_cofactor_test<P: SWModelParameters>() {
+    use math::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let order = <P::ScalarField as PrimeField>::Params::MODULUS;
+
+    for _ in 0..ITERATIONS {
+        // a random point on the curve, usually outside of the prime order
+        // subgroup
+        let p = loop {
+            let x = P::BaseField::rand(&mut rng);
+            if let Some(p) = GroupAffine::<P>::get_point_from_x(x, false) {
+                break p;
+            }
+        };
+        assert_eq!(
+            AffineCurve::is_in_correct_subgroup_assuming_on_curve(&p),
+            p.mul(order).is_zero()
+        );
+
+        for cleared in [p.clear_cofactor(), p.mul_by_cofactor()].iter() {
+            assert!(cleared.is_on_curve());
+            assert!(AffineCurve::is_in_correct_subgroup_assuming_on_curve(
+                cleared
+            ));
+            assert!(cleared.mul(order).is_zero());
+        }
+        assert_eq!(
+            p.into_projective().clear_cofactor(),
+            p.clear_cofactor().into_projective()
+        );
+        assert_eq!(
+            p.into_projective().mul_by_cofactor(),
+            p.mul_by_cofactor().into_projective()
+        );
+
+        let q = GroupProjective::<P>::rand(&mut rng);
+        assert!(ProjectiveCurve::is_in_correct_subgroup_assuming_on_curve(
+            &q
+        ));
+        assert_eq!(q.mul_by_cofactor().mul_by_cofactor_inv(), q);
+    }
+}
+
+fn sw_compressed_serialization_test<P: SWModelParameters>() {
+    use math::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    let size = GroupAffine::<P>::compressed_size();
+
+    let generator = GroupAffine::<P>::prime_subgroup_generator();
+    let mut points = vec![GroupAffine::<P>::zero(), generator, -generator];
+    for _ in 0..ITERATIONS {
+        let p = GroupProjective::<P>::rand(&mut rng).into_affine();
+        points.push(p);
+        points.push(-p);
+    }
+    for p in points {
+        let mut bytes = vec![];
+        p.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), size);
+        assert_eq!(
+            GroupAffine::<P>::deserialize_compressed(&bytes[..]).unwrap(),
+            p
+        );
+    }
+
+    // the flags of the point at infinity together with a non-zero
+    // x-coordinate
+    let mut bytes = vec![];
+    GroupAffine::<P>::zero()
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    bytes[size - 1] = 1;
+    assert!(GroupAffine::<P>::deserialize_compressed(&bytes[..]).is_err());
+
+    // the compression flag cleared
+    let mut bytes = vec![];
+    generator.serialize_compressed(&mut bytes).unwrap();
+    bytes[0] &= !COMPRESSION_FLAG;
+    assert!(GroupAffine::<P>::deserialize_compressed(&bytes[..]).is_err());
+
+    // an x-coordinate which is not on the curve
+    let mut x = P::BaseField::zero();
+    while GroupAffine::<P>::get_point_from_x(x, false).is_some() {
+        x += &P::BaseField::one();
+    }
+    let mut bytes = vec![];
+    GroupAffine::<P>::new(x, P::BaseField::one(), false)
+        .serialize_compressed(&mut bytes)
+        .unwrap();
+    assert!(GroupAffine::<P>::deserialize_compressed(&bytes[..]).is_err());
+
+    // too few bytes
+    assert!(GroupAffine::<P>::deserialize_compressed(&bytes[..size - 1]).is_err());
+
+    #[cfg(feature = "compressed")]
+    {
+        let bytes = postcard::to_allocvec(&generator).unwrap();
+        assert_eq!(bytes.len(), size);
+        let decoded: GroupAffine<P> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, generator);
+    }
+}
+
+fn sw_projective_serde_test<P: SWModelParameters>() {
+    use math::short_weierstrass_jacobian::{GroupAffine, GroupProjective};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(bound = "")]
+    struct MixedKey<P: SWModelParameters> {
+        affine: GroupAffine<P>,
+        #[serde(with = "math::curves::serde_affine")]
+        projective: GroupProjective<P>,
+    }
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..ITERATIONS {
+        let a = GroupProjective::<P>::rand(&mut rng);
+        let a_affine = a.into_affine();
+
+        // the same point with a non-trivial z coordinate
+        let z = P::BaseField::rand(&mut rng);
+        let z2 = z.square();
+        let b = GroupProjective::<P>::new(a.x * &z2, a.y * &(z2 * &z), a.z * &z);
+        assert_eq!(a, b);
+
+        for p in &[a, b, GroupProjective::<P>::zero()] {
+            let affine = p.into_affine();
+
+            let bytes = postcard::to_allocvec(p).unwrap();
+            assert_eq!(bytes, postcard::to_allocvec(&affine).unwrap());
+            let decoded: GroupProjective<P> = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(&decoded, p);
+            let decoded: GroupAffine<P> = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded, affine);
+
+            let json = serde_json::to_string(p).unwrap();
+            assert_eq!(json, serde_json::to_string(&affine).unwrap());
+            #[cfg(feature = "compressed")]
+            assert_eq!(json, format!("\"{:x}\"", affine));
+            let decoded: GroupProjective<P> = serde_json::from_str(&json).unwrap();
+            assert_eq!(&decoded, p);
+        }
+
+        let key = MixedKey {
+            affine: a_affine,
+            projective: b,
+        };
+        let bytes = postcard::to_allocvec(&key).unwrap();
+        let decoded: MixedKey<P> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.affine, a_affine);
+        assert_eq!(decoded.projective, a);
+
+        let json = serde_json::to_string(&key).unwrap();
+        let decoded: MixedKey<P> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.affine, a_affine);
+        assert_eq!(decoded.projective, a);
+    }
+}
+
+fn sw_display_test<P: SWModelParameters>() {
+    use math::{
+        short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+        FromBytes,
+    };
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn parse_coordinate<F: FromBytes>(hex: &str) -> F {
+        let mut bytes = from_hex(hex.strip_prefix("0x").unwrap());
+        bytes.reverse();
+        F::read(&bytes[..]).unwrap()
+    }
+
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+
+    for _ in 0..ITERATIONS {
+        let a = GroupProjective::<P>::rand(&mut rng).into_affine();
+
+        let printed = format!("{}", a);
+        let (x, y) = printed
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(", "))
+            .unwrap();
+        let b = GroupAffine::<P>::new(parse_coordinate(x), parse_coordinate(y), false);
+        assert_eq!(a, b);
+
+        let hex = format!("{:x}", a);
+        let mut bytes = vec![];
+        a.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(from_hex(&hex), bytes);
+        assert_eq!(format!("{:#x}", a), format!("0x{}", hex));
+        assert_eq!(
+            GroupAffine::<P>::deserialize_compressed(&from_hex(&hex)[..]).unwrap(),
+            a
+        );
+    }
+
+    let zero = GroupAffine::<P>::zero();
+    assert_eq!(format!("{}", zero), "inf");
+    assert_eq!(
+        GroupAffine::<P>::deserialize_compressed(&from_hex(&format!("{:x}", zero))[..]).unwrap(),
+        zero
+    );
 }
 
 pub(crate) fn montgomery_conversion_test<P>()
@@ -1,5 +1,6 @@
 #![allow(unused)]
 use crate::{
+    bytes::ToBytes,
     fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
     io::Cursor,
     curves::flags::{Flags, SWFlags},
@@ -304,13 +305,72 @@ pub fn field_test<F: Field>(a: F, b: F) {
     );
     // (a - b)^2 = (-(b - a))^2
     assert_eq!((a - &b).square(), (-(b - &a)).square());
+
+    // Serialized size
+    {
+        use math::{ConstSerializedSize, ToBytes};
+        let mut bytes = vec![];
+        a.write(&mut bytes).unwrap();
+        b.write(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 2 * F::SERIALIZED_SIZE);
+        let (a2, rest) = F::read_exact_from(&bytes).unwrap();
+        let (b2, rest) = F::read_exact_from(rest).unwrap();
+        assert_eq!((a2, b2), (a, b));
+        assert!(rest.is_empty());
+    }
     random_field_tests::<F>();
 }
 
-pub fn primefield_test<F: PrimeField>() {
+pub fn primefield_test<F>()
+where
+    F: PrimeField + serde::Serialize + for<'a> serde::Deserialize<'a>,
+{
     from_str_test::<F>();
     let one = F::one();
     assert_eq!(F::from_repr(one.into_repr()), one);
+    primefield_serde_test::<F>();
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Checks that `F` round trips in postcard, and that JSON writes it as the
+/// hex of its `ToBytes` encoding.
+fn primefield_serde_test<F>()
+where
+    F: PrimeField + serde::Serialize + for<'a> serde::Deserialize<'a>,
+{
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a = F::rand(&mut rng);
+        let bytes = postcard::to_allocvec(&a).unwrap();
+        assert_eq!(postcard::from_bytes::<F>(&bytes).unwrap(), a);
+
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, format!("\"{}\"", to_hex(&math::to_bytes![a].unwrap())));
+        assert_eq!(serde_json::from_str::<F>(&json).unwrap(), a);
+    }
+
+    // hex of another length, not hex, or not reduced is rejected
+    let size = F::SERIALIZED_SIZE;
+    assert!(serde_json::from_str::<F>(&format!("\"{}\"", "00".repeat(size - 1))).is_err());
+    assert!(serde_json::from_str::<F>(&format!("\"{}\"", "zz".repeat(size))).is_err());
+    assert!(serde_json::from_str::<F>(&format!("\"{}\"", "ff".repeat(size))).is_err());
+
+    // postcard limbs of the modulus, or above it, are rejected
+    use crate::{biginteger::BigInteger, fields::FpParameters};
+    let limbs = |limbs: &[u64]| -> Vec<u8> {
+        limbs
+            .iter()
+            .flat_map(|limb| postcard::to_allocvec(limb).unwrap())
+            .collect()
+    };
+    let mut modulus = F::Params::MODULUS;
+    assert!(postcard::from_bytes::<F>(&limbs(modulus.as_ref())).is_err());
+    assert!(postcard::from_bytes::<F>(&limbs(&vec![u64::MAX; modulus.as_ref().len()])).is_err());
+    modulus.sub_noborrow(&F::BigInt::from(1));
+    assert!(postcard::from_bytes::<F>(&limbs(modulus.as_ref())).is_ok());
 }
 
 pub fn sqrt_field_test<F: SquareRootField>(elem: F) {
@@ -323,6 +383,37 @@ pub fn sqrt_field_test<F: SquareRootField>(elem: F) {
     random_sqrt_tests::<F>();
 }
 
+/// Checks that the serde encoding of `F` is its `ToBytes` layout, and that
+/// both round trip.
+pub fn serialization_test<F>()
+where
+    F: Field + serde::Serialize + for<'a> serde::Deserialize<'a>,
+{
+    let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
+    for _ in 0..ITERATIONS {
+        let a = F::rand(&mut rng);
+        let bytes = math::to_bytes![a].unwrap();
+        assert_eq!(bytes.len(), F::SERIALIZED_SIZE);
+        assert_eq!(F::read(&bytes[..]).unwrap(), a);
+
+        assert_eq!(postcard::to_allocvec(&a).unwrap(), bytes);
+        assert_eq!(postcard::from_bytes::<F>(&bytes).unwrap(), a);
+
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, format!("\"{}\"", to_hex(&bytes)));
+        assert_eq!(serde_json::from_str::<F>(&json).unwrap(), a);
+        // the tuple of bytes JSON was written as before
+        let tuple = serde_json::to_string(&bytes).unwrap();
+        assert_eq!(serde_json::from_str::<F>(&tuple).unwrap(), a);
+    }
+
+    // coefficients which are not reduced are rejected
+    let bytes = crate::vec![0xffu8; F::SERIALIZED_SIZE];
+    assert!(F::read(&bytes[..]).is_err());
+    assert!(postcard::from_bytes::<F>(&bytes).is_err());
+    assert!(postcard::from_bytes::<F>(&bytes[..F::SERIALIZED_SIZE - 1]).is_err());
+}
+
 pub fn frobenius_test<F: Field, C: AsRef<[u64]>>(characteristic: C, maxpower: usize) {
     let mut rng = XorShiftRng::seed_from_u64(1231275789u64);
 
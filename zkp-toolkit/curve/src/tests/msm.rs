@@ -1,8 +1,11 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
 #![cfg(feature = "bls12_381")]
-use crate::bls12_381::{Fr, G1Projective};
-use math::{msm::VariableBaseMSM, AffineCurve, PrimeField, ProjectiveCurve, UniformRand, Zero};
+use crate::bls12_381::{Fr, G1Affine, G1Projective, G2Projective};
+use math::{
+    msm::{VariableBaseMSM, Wnaf},
+    AffineCurve, Field, One, PrimeField, ProjectiveCurve, UniformRand, Zero,
+};
 use rand::SeedableRng;
 use rand_xorshift::XorShiftRng;
 
@@ -55,3 +58,118 @@ fn test_with_bls12_unequal_numbers() {
 
     assert_eq!(naive.into_affine(), fast.into_affine());
 }
+
+#[test]
+fn test_chunked_is_deterministic() {
+    const SAMPLES: usize = 1 << 10;
+
+    let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+    let v = (0..SAMPLES)
+        .map(|_| Fr::rand(&mut rng).into_repr())
+        .collect::<Vec<_>>();
+    let g = (0..SAMPLES)
+        .map(|_| G1Projective::rand(&mut rng).into_affine())
+        .collect::<Vec<_>>();
+
+    let fast = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice()).into_affine();
+
+    for chunk_size in &[100, 300, SAMPLES - 1, SAMPLES, SAMPLES + 1] {
+        let chunked =
+            VariableBaseMSM::multi_scalar_mul_chunked(g.as_slice(), v.as_slice(), *chunk_size);
+        assert_eq!(fast, chunked.into_affine());
+
+        // Unequal numbers of bases and scalars are truncated to the shorter one.
+        let chunked =
+            VariableBaseMSM::multi_scalar_mul_chunked(g.as_slice(), &v[..SAMPLES - 1], *chunk_size);
+        let fast = VariableBaseMSM::multi_scalar_mul(g.as_slice(), &v[..SAMPLES - 1]);
+        assert_eq!(fast.into_affine(), chunked.into_affine());
+    }
+}
+
+#[test]
+fn test_batch_affine_buckets() {
+    const SAMPLES: usize = 1 << 11;
+
+    let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+    // Few distinct bases and scalars, so that the buckets get equal points,
+    // negations and points cancelling out to infinity.
+    let a = G1Projective::rand(&mut rng).into_affine();
+    let b = G1Projective::rand(&mut rng).into_affine();
+    let candidates = [a, -a, b, -b, a, G1Affine::zero()];
+    let s = Fr::rand(&mut rng);
+    let scalar_candidates = [s, s, -s, Fr::one(), Fr::zero(), s.double()];
+
+    let g = (0..SAMPLES)
+        .map(|i| candidates[i % candidates.len()])
+        .collect::<Vec<_>>();
+    let v = (0..SAMPLES)
+        .map(|i| scalar_candidates[(i / candidates.len()) % scalar_candidates.len()].into_repr())
+        .collect::<Vec<_>>();
+
+    let mut expected = G1Projective::zero();
+    for (base, scalar) in g.iter().zip(&v) {
+        expected += &base.mul(*scalar);
+    }
+    let fast = VariableBaseMSM::multi_scalar_mul(g.as_slice(), v.as_slice());
+
+    assert_eq!(expected.into_affine(), fast.into_affine());
+}
+
+#[test]
+fn test_wnaf_base() {
+    const SAMPLES: usize = 1 << 8;
+
+    let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+    let g = G1Projective::rand(&mut rng);
+    let mut v = (0..SAMPLES).map(|_| Fr::rand(&mut rng)).collect::<Vec<_>>();
+    v.extend_from_slice(&[Fr::zero(), Fr::one(), -Fr::one()]);
+
+    let mut wnaf = Wnaf::new();
+    let mut base = wnaf.base(g, v.len());
+    let mut shared = base.shared();
+    for s in v.iter() {
+        let expected = g.mul(*s);
+        assert_eq!(base.scalar(s), expected);
+        assert_eq!(shared.scalar(s), expected);
+    }
+
+    // the context can be reused with another base and window size
+    let g = G1Projective::rand(&mut rng);
+    let mut base = wnaf.base(g, 1);
+    for s in v.iter() {
+        assert_eq!(base.scalar(s), g.mul(*s));
+    }
+
+    let h = G2Projective::rand(&mut rng);
+    let mut wnaf = Wnaf::new();
+    let mut base = wnaf.base(h, v.len());
+    for s in v.iter() {
+        assert_eq!(base.scalar(s), h.mul(*s));
+    }
+}
+
+#[test]
+fn test_wnaf_scalar() {
+    const SAMPLES: usize = 1 << 8;
+
+    let mut rng = XorShiftRng::seed_from_u64(234872845u64);
+
+    let g = (0..SAMPLES)
+        .map(|_| G1Projective::rand(&mut rng))
+        .collect::<Vec<_>>();
+
+    let mut wnaf = Wnaf::new();
+    for s in [Fr::rand(&mut rng), Fr::zero(), Fr::one(), -Fr::one()].iter() {
+        let mut scalar = wnaf.scalar(s);
+        let mut shared = scalar.shared();
+        for base in g.iter() {
+            let expected = base.mul(*s);
+            assert_eq!(scalar.base(*base), expected);
+            assert_eq!(shared.base(*base), expected);
+        }
+        assert!(scalar.base(G1Projective::zero()).is_zero());
+    }
+}
@@ -7,7 +7,7 @@ use zkp_toolkit::gadgets::mimc::AbstractHashMimc;
 use zkp_toolkit::gadgets::mimc::AbstractHashMimcOutput;
 
 use curve::bn_256::{Bn_256, Fr};
-use zkp_toolkit::gadgets::mimc::hash;
+use zkp_toolkit::gadgets::mimc::{hash, MimcParameters};
 
 use math::ToBytes;
 use rand::prelude::*;
@@ -26,7 +26,7 @@ impl Merge for MergeMimc {
         let mut bytes = vec![];
         let _ = left.write(&mut bytes);
         let _ = right.write(&mut bytes);
-        hash(&bytes)
+        hash(&bytes, &MimcParameters::default())
     }
 }
 
@@ -1,13 +1,11 @@
 use math::One;
 use math::Zero;
-use sha2::Digest;
-use sha2::Sha256;
-use zkp_toolkit::gadgets::merkletree::cbmt::Merge;
 use zkp_toolkit::gadgets::merkletree::cbmt::MerkleProof;
 use zkp_toolkit::gadgets::merkletree::cbmt::CBMT;
 use zkp_toolkit::gadgets::merkletree::cbmt_constraints::MerkleProofGadget;
 use zkp_toolkit::gadgets::sha256::AbstractHashSha256;
 use zkp_toolkit::gadgets::sha256::AbstractHashSha256Output;
+use zkp_toolkit::gadgets::sha256::MergeSha256;
 use zkp_toolkit::groth16::verify_proof;
 
 use curve::bn_256::{Bn_256, Fr};
@@ -19,20 +17,6 @@ use scheme::groth16::{
 use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
 use std::time::Instant;
 
-struct MergeSha256;
-
-impl Merge for MergeSha256 {
-    type Item = Vec<u8>;
-
-    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
-        let mut input = Vec::new();
-        input.extend(&mut left.iter().map(|i| *i));
-        input.extend(&mut right.iter().map(|i| *i));
-        let mut h = Sha256::new();
-        h.update(&input);
-        h.finalize().to_vec()
-    }
-}
 type CBMTSHA256 = CBMT<Vec<u8>, MergeSha256>;
 
 struct MerkleTreeCircuit {
@@ -46,14 +30,10 @@ impl ConstraintSynthesizer<Fr> for MerkleTreeCircuit {
         self,
         cs: &mut CS,
     ) -> Result<(), SynthesisError> {
-        let var_root = AbstractHashSha256Output::alloc_input(
-            cs.ns(|| "tree_root"),
-            self.root.unwrap().clone(),
-        )
-        .unwrap();
+        let var_root =
+            AbstractHashSha256Output::alloc_input(cs.ns(|| "tree_root"), self.root).unwrap();
         let var_leaf =
-            AbstractHashSha256Output::alloc(cs.ns(|| format!("leaf",)), self.leaf.unwrap())
-                .unwrap();
+            AbstractHashSha256Output::alloc(cs.ns(|| format!("leaf",)), self.leaf).unwrap();
         let proof_val = self.proof.ok_or(SynthesisError::AssignmentMissing).unwrap();
         let lemmas = proof_val
             .lemmas()
@@ -62,7 +42,7 @@ impl ConstraintSynthesizer<Fr> for MerkleTreeCircuit {
             .map(|(j, v)| {
                 AbstractHashSha256Output::alloc(
                     cs.ns(|| format!("proof_lemmas_{}", j)),
-                    (*v).clone(),
+                    Some((*v).clone()),
                 )
                 .unwrap()
             })
@@ -8,43 +8,41 @@ use scheme::clinkv2::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisEr
 /// This is our demo circuit for proving knowledge of the
 /// preimage of a Poseidon hash invocation.
 use std::time::Instant;
-use zkp_toolkit::gadgets::poseidon::PoseidonConstant;
+use zkp_toolkit::gadgets::poseidon::PoseidonParameters;
 
 // Hash Poseidon utilizes Sponge Construction
 // r, bitrate; c, capacity; M, state value, equal to r + c;
 const _R: usize = 2;
 const _C: usize = 1;
-const RF: usize = 8;
-const RP: usize = 83;
 
+// the width of the default parameters.
 const M: usize = _R + _C;
-const N: usize = RF + RP; // round number
 
 const ALPH: [u64; 1] = [5];
 
 pub struct PoseidonDemo<'a, F: PrimeField> {
     pub xl: Option<F>,
     pub xr: Option<F>,
-    pub constants: &'a PoseidonConstant<F>,
+    pub constants: &'a PoseidonParameters<F>,
 }
 
-pub fn hades_permutation<F>(state: &mut [F], rc: &PoseidonConstant<F>)
+pub fn hades_permutation<F>(state: &mut [F], rc: &PoseidonParameters<F>)
 where
     F: PrimeField,
 {
-    for i in 0..N {
+    for i in 0..rc.rounds() {
         for j in 0..M {
             state[j].add_assign(&rc.ark[i][j]);
         }
 
-        if i < (RF / 2) || i > (RF / 2) {
+        if rc.is_full_round(i) {
             // full round
             for j in 0..M {
                 state[j] = state[j].pow(ALPH);
             }
         } else {
             // partial round
-            state[M - 1] = state[M - 1].pow(ALPH);
+            state[0] = state[0].pow(ALPH);
         }
 
         // MixLayer
@@ -62,7 +60,7 @@ where
     }
 }
 
-pub fn poseidon_hash<F: PrimeField>(xl: F, xr: F, constants: &PoseidonConstant<F>) -> F {
+pub fn poseidon_hash<F: PrimeField>(xl: F, xr: F, constants: &PoseidonParameters<F>) -> F {
     let mut state = [xl, xr, F::one()];
     hades_permutation(&mut state, &constants);
     // c == 1
@@ -104,7 +102,7 @@ impl<'a, F: PrimeField> ConstraintSynthesizer<F> for PoseidonDemo<'a, F> {
         let mut state_value = [xl_value, xr_value, three_value];
         let mut state = [xl, xr, three];
 
-        for i in 0..N {
+        for i in 0..self.constants.rounds() {
             let cs = &mut cs.ns(|| format!("round {}", i));
 
             for j in 0..M {
@@ -132,7 +130,7 @@ impl<'a, F: PrimeField> ConstraintSynthesizer<F> for PoseidonDemo<'a, F> {
                 state[j] = tmp;
             }
 
-            if i < (RF / 2) || i > (RF / 2) {
+            if self.constants.is_full_round(i) {
                 // full round
                 for j in 0..M {
                     let tuple = pow_with_constraint(&state_value[j], &state[j], ALPH, cs, index)?;
@@ -141,10 +139,9 @@ impl<'a, F: PrimeField> ConstraintSynthesizer<F> for PoseidonDemo<'a, F> {
                 }
             } else {
                 // partial round
-                let tuple =
-                    pow_with_constraint(&state_value[M - 1], &state[M - 1], ALPH, cs, index)?;
-                state_value[M - 1] = tuple.0;
-                state[M - 1] = tuple.1;
+                let tuple = pow_with_constraint(&state_value[0], &state[0], ALPH, cs, index)?;
+                state_value[0] = tuple.0;
+                state[0] = tuple.1;
             }
 
             // Mix Layer
@@ -296,7 +293,7 @@ fn main() {
     let mut rng = &mut test_rng();
     const SAMPLES: usize = 8;
     use curve::bn_256::{Bn_256, Fr};
-    let constants = PoseidonConstant::<Fr>::new_fp255();
+    let constants = PoseidonParameters::<Fr>::default();
 
     // Create parameters for our circuit
     println!("poseidon_clinkv2 setup:");
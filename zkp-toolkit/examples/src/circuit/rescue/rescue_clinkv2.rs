@@ -1,6 +1,6 @@
 use math::One;
 use math::{test_rng, BitIterator, PrimeField};
-use zkp_toolkit::gadgets::rescue::RescueConstant;
+use zkp_toolkit::gadgets::rescue::RescueParameters;
 
 use curve::bn_256::{Bn_256, Fr};
 use rand::Rng;
@@ -28,7 +28,7 @@ const INVALPH: [u64; 4] = [
     0x26b6a528b427b354,
 ]; // 0x26b6a528b427b35493736af8679aad17535cb9d394945a0dcfe7f7a98ccccccd
 
-pub fn block_cipher<F>(state: &mut [F], rc: &RescueConstant<F>)
+pub fn block_cipher<F>(state: &mut [F], rc: &RescueParameters<F>)
 where
     F: PrimeField,
 {
@@ -60,7 +60,7 @@ where
     }
 }
 
-pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueConstant<F>) -> F {
+pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueParameters<F>) -> F {
     let mut state = [xl, xr, F::one()];
     block_cipher(&mut state, &constants);
 
@@ -74,7 +74,7 @@ pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueConstant<F>) -
 pub struct RescueDemo<'a, F: PrimeField> {
     pub xl: Option<F>,
     pub xr: Option<F>,
-    pub constants: &'a RescueConstant<F>,
+    pub constants: &'a RescueParameters<F>,
 }
 
 /// Our demo circuit implements this `Circuit` trait which
@@ -317,7 +317,7 @@ fn main() {
     println!("Creating parameters...");
     let mut rng = &mut test_rng();
     const SAMPLES: usize = 8;
-    let constants = RescueConstant::<Fr>::new_fp255();
+    let constants = RescueParameters::<Fr>::bn_256();
 
     // Create parameters for our circuit
     println!("rescue_clinkv2 setup:");
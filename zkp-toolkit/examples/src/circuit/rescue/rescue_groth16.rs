@@ -1,6 +1,6 @@
 use math::{test_rng, BitIterator, PrimeField};
 use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
-use zkp_toolkit::gadgets::rescue::RescueConstant;
+use zkp_toolkit::gadgets::rescue::RescueParameters;
 
 // Hash Rescue utilizes Sponge Construction
 // r, bitrate; c, capacity; M, state value, equal to r + c;
@@ -20,7 +20,7 @@ const INVALPH: [u64; 4] = [
     0x26b6a528b427b354,
 ]; // 0x26b6a528b427b35493736af8679aad17535cb9d394945a0dcfe7f7a98ccccccd
 
-pub fn block_cipher<F>(state: &mut [F], rc: &RescueConstant<F>)
+pub fn block_cipher<F>(state: &mut [F], rc: &RescueParameters<F>)
 where
     F: PrimeField,
 {
@@ -52,7 +52,7 @@ where
     }
 }
 
-pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueConstant<F>) -> F {
+pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueParameters<F>) -> F {
     let mut state = [xl, xr, F::zero()];
     block_cipher(&mut state, &constants);
 
@@ -66,7 +66,7 @@ pub fn rescue_hash<F: PrimeField>(xl: F, xr: F, constants: &RescueConstant<F>) -
 pub struct RescueDemo<'a, F: PrimeField> {
     pub xl: Option<F>,
     pub xr: Option<F>,
-    pub constants: &'a RescueConstant<F>,
+    pub constants: &'a RescueParameters<F>,
 }
 
 /// Our demo circuit implements this `Circuit` trait which
@@ -287,7 +287,7 @@ fn main() {
 
     let rng = &mut test_rng();
     use curve::bn_256::{Bn_256, Fr};
-    let constants = RescueConstant::<Fr>::new_fp255();
+    let constants = RescueParameters::<Fr>::bn_256();
 
     println!("Creating parameters...");
 
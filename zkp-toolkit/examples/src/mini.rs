@@ -102,6 +102,7 @@ fn main() {
     let pvk2 = prepare_verifying_key(&vk2);
     let proof = create_random_proof(&params2, circuit, &mut rng).unwrap();
     let proof_bytes = postcard::to_allocvec(&proof).unwrap();
+    println!("GROTH16 PROOF SIZE: {} bytes", proof_bytes.len());
     let proof2: Proof<Bn_256> = postcard::from_bytes(&proof_bytes).unwrap();
     assert!(verify_proof(&pvk2, &proof2, &[Fr::from(10u32)]).unwrap());
 
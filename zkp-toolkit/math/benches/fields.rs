@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use curve::bls12_381::Fr;
+use math::fields::pow_many;
+use math::{test_rng, Field, FpParameters, PrimeField, UniformRand};
+
+const NUM_BASES: usize = 1 << 16;
+
+fn bench_pow_many(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let bases = (0..NUM_BASES).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let exp = <Fr as PrimeField>::Params::MODULUS_MINUS_ONE_DIV_TWO;
+
+    let mut group = c.benchmark_group("pow_many");
+    group.sample_size(10);
+    group.bench_function("per_element", |b| {
+        b.iter(|| bases.iter().map(|base| base.pow(exp)).collect::<Vec<_>>())
+    });
+    group.bench_function("batch", |b| b.iter(|| pow_many(&bases, exp.as_ref())));
+    group.finish();
+}
+
+criterion_group!(benches, bench_pow_many);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curve::bn_256::{Fr, G1Projective};
+use math::msm::{FixedBaseMSM, VariableBaseMSM, Wnaf};
+use math::{test_rng, FpParameters, PrimeField, ProjectiveCurve, UniformRand};
+
+const LOG_SIZES: [usize; 3] = [14, 16, 18];
+
+fn bench_variable_base_msm(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let max_size = 1 << LOG_SIZES[LOG_SIZES.len() - 1];
+    let bases = (0..max_size)
+        .map(|_| G1Projective::rand(rng))
+        .collect::<Vec<_>>();
+    let bases = G1Projective::batch_normalization_into_affine(&bases);
+    let scalars = (0..max_size)
+        .map(|_| Fr::rand(rng).into_repr())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("variable_base_msm");
+    group.sample_size(10);
+    for log_size in LOG_SIZES.iter() {
+        let size = 1 << log_size;
+        group.bench_with_input(BenchmarkId::new("single_chunk", size), &size, |b, &size| {
+            b.iter(|| {
+                VariableBaseMSM::multi_scalar_mul_chunked(&bases[..size], &scalars[..size], size)
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("default", size), &size, |b, &size| {
+            b.iter(|| VariableBaseMSM::multi_scalar_mul(&bases[..size], &scalars[..size]))
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixed_base_mul(c: &mut Criterion) {
+    const SIZE: usize = 1 << 12;
+
+    let rng = &mut test_rng();
+    let base = G1Projective::rand(rng);
+    let scalars = (0..SIZE).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    let scalar_bits = <Fr as PrimeField>::Params::MODULUS_BITS as usize;
+
+    let mut group = c.benchmark_group("fixed_base_mul");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("direct", SIZE), |b| {
+        b.iter(|| scalars.iter().map(|s| base.mul(*s)).collect::<Vec<_>>())
+    });
+    group.bench_function(BenchmarkId::new("wnaf", SIZE), |b| {
+        b.iter(|| {
+            let mut wnaf = Wnaf::new();
+            let mut wnaf = wnaf.base(base, SIZE);
+            scalars.iter().map(|s| wnaf.scalar(s)).collect::<Vec<_>>()
+        })
+    });
+    group.bench_function(BenchmarkId::new("window_table", SIZE), |b| {
+        b.iter(|| {
+            let window = FixedBaseMSM::get_mul_window_size(SIZE);
+            let table = FixedBaseMSM::get_window_table(scalar_bits, window, base);
+            FixedBaseMSM::multi_scalar_mul(scalar_bits, window, &table, &scalars)
+        })
+    });
+    group.finish();
+}
+
+fn bench_batch_into_affine(c: &mut Criterion) {
+    const SIZE: usize = 1 << 16;
+
+    let rng = &mut test_rng();
+    let points = (0..SIZE)
+        .map(|_| G1Projective::rand(rng))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("batch_into_affine");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("into_affine", SIZE), |b| {
+        b.iter(|| points.iter().map(|p| p.into_affine()).collect::<Vec<_>>())
+    });
+    group.bench_function(BenchmarkId::new("batch_into_affine", SIZE), |b| {
+        b.iter(|| G1Projective::batch_into_affine(points.clone()))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_variable_base_msm,
+    bench_fixed_base_mul,
+    bench_batch_into_affine
+);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use curve::bn_256::{Bn_256, Fq12, G1Projective, G2Projective};
+use math::{test_rng, One, PairingEngine, ProjectiveCurve, UniformRand};
+
+// A product of three pairings, the shape of the Groth16 verification equation.
+fn bench_groth16_pairings(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let ps = (0..3)
+        .map(|_| G1Projective::rand(rng).into_affine())
+        .collect::<Vec<_>>();
+    let qs = (0..3)
+        .map(|_| G2Projective::rand(rng).into_affine())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("groth16_pairings");
+    group.sample_size(10);
+    group.bench_function("separate", |b| {
+        b.iter(|| {
+            ps.iter()
+                .zip(&qs)
+                .map(|(p, q)| Bn_256::pairing(*p, *q))
+                .fold(Fq12::one(), |acc, e| acc * &e)
+        })
+    });
+    group.bench_function("multi_pairing", |b| {
+        b.iter(|| {
+            let ps = ps.iter().map(|p| (*p).into()).collect::<Vec<_>>();
+            let qs = qs.iter().map(|q| (*q).into()).collect::<Vec<_>>();
+            Bn_256::multi_pairing(&ps, &qs).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_groth16_pairings);
+criterion_main!(benches);
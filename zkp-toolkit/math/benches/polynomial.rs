@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curve::bls12_381::Fr;
+use math::fft::DensePolynomial;
+use math::{test_rng, UniformRand};
+
+const DEGREE: usize = 1 << 16;
+const NUM_POINTS: usize = 1 << 10;
+
+fn bench_batch_evaluate(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let poly = DensePolynomial::<Fr>::rand(DEGREE, rng);
+    let points = (0..NUM_POINTS).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("batch_evaluate");
+    group.sample_size(10);
+    group.bench_with_input(
+        BenchmarkId::new("per_point", NUM_POINTS),
+        &points,
+        |b, points| b.iter(|| points.iter().map(|p| poly.evaluate(*p)).collect::<Vec<_>>()),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("subproduct_tree", NUM_POINTS),
+        &points,
+        |b, points| b.iter(|| poly.batch_evaluate(points)),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch_evaluate);
+criterion_main!(benches);
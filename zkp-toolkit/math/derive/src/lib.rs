@@ -0,0 +1,239 @@
+//! Procedural macros for the `math` crate.
+
+extern crate proc_macro;
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitInt, LitStr, Token,
+};
+
+/// Bases used for the Miller-Rabin primality test of the modulus.
+const MILLER_RABIN_BASES: [u64; 20] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71,
+];
+
+/// Bound of the trial division used to find small prime factors of `MODULUS - 1`.
+const TRIAL_DIVISION_BOUND: u64 = 1 << 16;
+
+/// The limb counts for which `math::biginteger` provides a `BigInteger` type.
+const SUPPORTED_LIMBS: [usize; 5] = [4, 5, 6, 12, 13];
+
+struct FieldParamsInput {
+    modulus: LitStr,
+    generator: LitInt,
+}
+
+impl Parse for FieldParamsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let modulus = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key: Ident = input.parse()?;
+        if key != "generator" {
+            return Err(syn::Error::new(
+                key.span(),
+                "expected `generator = <integer>`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let generator = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(FieldParamsInput { modulus, generator })
+    }
+}
+
+/// Derives all constants of `math::fields::FpParameters` from the decimal
+/// modulus and a multiplicative generator.
+///
+/// The macro expands to the associated constants, so it is used inside the
+/// `FpParameters` implementation, next to the `BigInt` type:
+///
+/// ```ignore
+/// impl FpParameters for FrParameters {
+///     type BigInt = BigInteger256;
+///
+///     derive_field_params!(
+///         "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+///         generator = 7
+///     );
+/// }
+/// ```
+///
+/// The modulus must pass a Miller-Rabin test, and the generator must be a
+/// quadratic non-residue with no order dividing `(MODULUS - 1) / q` for the
+/// prime factors `q` of `MODULUS - 1` found by trial division, otherwise the
+/// build fails.
+#[proc_macro]
+pub fn derive_field_params(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as FieldParamsInput);
+    match field_params(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn field_params(input: &FieldParamsInput) -> syn::Result<TokenStream2> {
+    let modulus =
+        input.modulus.value().parse::<BigUint>().map_err(|_| {
+            syn::Error::new(input.modulus.span(), "modulus must be a decimal integer")
+        })?;
+    let generator = input.generator.base10_parse::<u64>()?;
+
+    if modulus <= BigUint::from(2u64) || !is_probable_prime(&modulus) {
+        return Err(syn::Error::new(
+            input.modulus.span(),
+            "modulus is not an odd prime",
+        ));
+    }
+
+    let modulus_bits = modulus.bits() as usize;
+    let limbs = modulus_bits.div_ceil(64);
+    if !SUPPORTED_LIMBS.contains(&limbs) {
+        return Err(syn::Error::new(
+            input.modulus.span(),
+            format!("no BigInteger type with {} limbs", limbs),
+        ));
+    }
+
+    let one = BigUint::one();
+    let modulus_minus_one = &modulus - &one;
+    let generator = BigUint::from(generator);
+    if generator.is_zero() || generator >= modulus {
+        return Err(syn::Error::new(
+            input.generator.span(),
+            "generator must be a non-zero element of the field",
+        ));
+    }
+    if let Some(factor) = generator_order_factor(&generator, &modulus) {
+        return Err(syn::Error::new(
+            input.generator.span(),
+            format!(
+                "{} is not a multiplicative generator: its order divides (MODULUS - 1) / {}",
+                generator, factor
+            ),
+        ));
+    }
+
+    let mut two_adicity = 0u32;
+    let mut t = modulus_minus_one.clone();
+    while t.is_even() {
+        t >>= 1;
+        two_adicity += 1;
+    }
+
+    let r = (&one << (64 * limbs)) % &modulus;
+    let r2 = (&r * &r) % &modulus;
+    let word = &one << 64;
+    let inv: BigUint = (&word - inverse_mod_word(&(&modulus % &word))) % &word;
+    let inv = inv.to_u64_digits().first().cloned().unwrap_or(0);
+    let root_of_unity = generator.modpow(&t, &modulus);
+
+    let bigint = format_ident!("BigInteger{}", 64 * limbs);
+    let to_bigint = |value: &BigUint| {
+        let mut digits = value.to_u64_digits();
+        digits.resize(limbs, 0);
+        quote! { math::biginteger::#bigint([#(#digits),*]) }
+    };
+    let to_mont = |value: &BigUint| to_bigint(&((value * &r) % &modulus));
+
+    let modulus_bits = modulus_bits as u32;
+    let capacity = modulus_bits - 1;
+    let repr_shave_bits = (64 * limbs) as u32 - modulus_bits;
+    let modulus_tokens = to_bigint(&modulus);
+    let r_tokens = to_bigint(&r);
+    let r2_tokens = to_bigint(&r2);
+    let generator_tokens = to_mont(&generator);
+    let root_of_unity_tokens = to_mont(&root_of_unity);
+    let modulus_minus_one_div_two = to_bigint(&(&modulus_minus_one >> 1));
+    let t_minus_one_div_two = to_bigint(&((&t - &one) >> 1));
+    let t_tokens = to_bigint(&t);
+
+    Ok(quote! {
+        const MODULUS: Self::BigInt = #modulus_tokens;
+        const MODULUS_BITS: u32 = #modulus_bits;
+        const CAPACITY: u32 = #capacity;
+        const REPR_SHAVE_BITS: u32 = #repr_shave_bits;
+        const R: Self::BigInt = #r_tokens;
+        const R2: Self::BigInt = #r2_tokens;
+        const INV: u64 = #inv;
+        const GENERATOR: Self::BigInt = #generator_tokens;
+        const TWO_ADICITY: u32 = #two_adicity;
+        const ROOT_OF_UNITY: Self::BigInt = #root_of_unity_tokens;
+        const MODULUS_MINUS_ONE_DIV_TWO: Self::BigInt = #modulus_minus_one_div_two;
+        const T: Self::BigInt = #t_tokens;
+        const T_MINUS_ONE_DIV_TWO: Self::BigInt = #t_minus_one_div_two;
+    })
+}
+
+/// Miller-Rabin test of `n` against the fixed `MILLER_RABIN_BASES`.
+fn is_probable_prime(n: &BigUint) -> bool {
+    let one = BigUint::one();
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut s = 0;
+    while d.is_even() {
+        d >>= 1;
+        s += 1;
+    }
+
+    'bases: for base in MILLER_RABIN_BASES.iter() {
+        let base = BigUint::from(*base);
+        if &base % n == BigUint::zero() {
+            continue;
+        }
+        let mut x = base.modpow(&d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..s {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Returns a prime factor `q` of `modulus - 1` such that
+/// `generator^((modulus - 1) / q) = 1`, among 2, the factors found by trial
+/// division and the remaining cofactor if it is prime.
+fn generator_order_factor(generator: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    let one = BigUint::one();
+    let modulus_minus_one = modulus - &one;
+
+    let mut factors = vec![];
+    let mut cofactor = modulus_minus_one.clone();
+    let mut q = 2u64;
+    while q < TRIAL_DIVISION_BOUND {
+        let big_q = BigUint::from(q);
+        if (&cofactor % &big_q).is_zero() {
+            factors.push(big_q.clone());
+            while (&cofactor % &big_q).is_zero() {
+                cofactor /= &big_q;
+            }
+        }
+        q += if q == 2 { 1 } else { 2 };
+    }
+    if cofactor > one && is_probable_prime(&cofactor) {
+        factors.push(cofactor);
+    }
+
+    factors
+        .into_iter()
+        .find(|q| generator.modpow(&(&modulus_minus_one / q), modulus) == one)
+}
+
+/// Computes `a^-1 mod 2^64` for odd `a`, using that the multiplicative group
+/// of integers modulo 2^64 has order 2^63.
+fn inverse_mod_word(a: &BigUint) -> BigUint {
+    let word = BigUint::one() << 64;
+    let exponent = (BigUint::one() << 63) - BigUint::one();
+    a.modpow(&exponent, &word)
+}
@@ -0,0 +1,350 @@
+use core::fmt::{Formatter, Result as FmtResult};
+use serde::{
+    de::{Error as DeError, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserializer, Serializer,
+};
+
+use super::*;
+
+/// A `BigInteger` backed by `N` 64-bit limbs, least-significant limb first.
+///
+/// This is the representation used by the limb-generic `Fp<P, N>` field.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct BigInt<const N: usize>(pub [u64; N]);
+
+impl<const N: usize> BigInt<N> {
+    pub const fn new(value: [u64; N]) -> Self {
+        BigInt(value)
+    }
+}
+
+impl<const N: usize> Default for BigInt<N> {
+    #[inline]
+    fn default() -> Self {
+        BigInt([0u64; N])
+    }
+}
+
+impl<const N: usize> BigInteger for BigInt<N> {
+    const NUM_LIMBS: usize = N;
+
+    #[inline]
+    fn add_nocarry(&mut self, other: &Self) -> bool {
+        let mut carry = 0;
+
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a = arithmetic::adc(*a, *b, &mut carry);
+        }
+
+        carry != 0
+    }
+
+    #[inline]
+    fn sub_noborrow(&mut self, other: &Self) -> bool {
+        let mut borrow = 0;
+
+        for (a, b) in self.0.iter_mut().zip(other.0.iter()) {
+            *a = arithmetic::sbb(*a, *b, &mut borrow);
+        }
+
+        borrow != 0
+    }
+
+    #[inline]
+    fn mul2(&mut self) {
+        let mut last = 0;
+        for i in &mut self.0 {
+            let tmp = *i >> 63;
+            *i <<= 1;
+            *i |= last;
+            last = tmp;
+        }
+    }
+
+    #[inline]
+    fn muln(&mut self, mut n: u32) {
+        if n as usize >= 64 * N {
+            *self = Self::from(0);
+            return;
+        }
+
+        while n >= 64 {
+            let mut t = 0;
+            for i in &mut self.0 {
+                core::mem::swap(&mut t, i);
+            }
+            n -= 64;
+        }
+
+        if n > 0 {
+            let mut t = 0;
+            for i in &mut self.0 {
+                let t2 = *i >> (64 - n);
+                *i <<= n;
+                *i |= t;
+                t = t2;
+            }
+        }
+    }
+
+    #[inline]
+    fn div2(&mut self) {
+        let mut t = 0;
+        for i in self.0.iter_mut().rev() {
+            let t2 = *i << 63;
+            *i >>= 1;
+            *i |= t;
+            t = t2;
+        }
+    }
+
+    #[inline]
+    fn divn(&mut self, mut n: u32) {
+        if n as usize >= 64 * N {
+            *self = Self::from(0);
+            return;
+        }
+
+        while n >= 64 {
+            let mut t = 0;
+            for i in self.0.iter_mut().rev() {
+                core::mem::swap(&mut t, i);
+            }
+            n -= 64;
+        }
+
+        if n > 0 {
+            let mut t = 0;
+            for i in self.0.iter_mut().rev() {
+                let t2 = *i << (64 - n);
+                *i >>= n;
+                *i |= t;
+                t = t2;
+            }
+        }
+    }
+
+    #[inline]
+    fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    #[inline]
+    fn is_even(&self) -> bool {
+        !self.is_odd()
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&e| e == 0)
+    }
+
+    #[inline]
+    fn num_bits(&self) -> u32 {
+        let mut ret = N as u32 * 64;
+        for i in self.0.iter().rev() {
+            let leading = i.leading_zeros();
+            ret -= leading;
+            if leading != 64 {
+                break;
+            }
+        }
+
+        ret
+    }
+
+    #[inline]
+    fn get_bit(&self, i: usize) -> bool {
+        if i >= 64 * N {
+            false
+        } else {
+            let limb = i / 64;
+            let bit = i - (64 * limb);
+            (self.0[limb] & (1 << bit)) != 0
+        }
+    }
+
+    #[inline]
+    fn from_bits(bits: &[bool]) -> Self {
+        let mut res = Self::default();
+        let mut acc: u64 = 0;
+
+        let mut bits = bits.to_vec();
+        bits.reverse();
+        for (i, bits64) in bits.chunks(64).enumerate() {
+            for bit in bits64.iter().rev() {
+                acc <<= 1;
+                acc += *bit as u64;
+            }
+            res.0[i] = acc;
+            acc = 0;
+        }
+        res
+    }
+
+    #[inline]
+    fn to_bits(&self) -> Vec<bool> {
+        let mut res = Vec::with_capacity(N * 64);
+        for b in BitIterator::new(self.0) {
+            res.push(b);
+        }
+        res
+    }
+
+    #[inline]
+    fn find_wnaf(&self) -> Vec<i64> {
+        let mut res = vec![];
+
+        let mut e = *self;
+        while !e.is_zero() {
+            let z: i64;
+            if e.is_odd() {
+                z = 2 - (e.0[0] % 4) as i64;
+                if z >= 0 {
+                    e.sub_noborrow(&Self::from(z as u64));
+                } else {
+                    e.add_nocarry(&Self::from((-z) as u64));
+                }
+            } else {
+                z = 0;
+            }
+            res.push(z);
+            e.div2();
+        }
+
+        res
+    }
+
+    #[inline]
+    fn change_pos(&mut self, i: usize, v: u64) {
+        self.0[i] = v;
+    }
+}
+
+impl<const N: usize> ToBytes for BigInt<N> {
+    #[inline]
+    fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        for limb in self.0.iter() {
+            limb.write(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> FromBytes for BigInt<N> {
+    #[inline]
+    fn read<R: Read>(mut reader: R) -> IoResult<Self> {
+        let mut res = Self::default();
+        for limb in res.0.iter_mut() {
+            *limb = u64::read(&mut reader)?;
+        }
+        Ok(res)
+    }
+}
+
+impl<const N: usize> ConstSerializedSize for BigInt<N> {
+    const SERIALIZED_SIZE: usize = N * 8;
+}
+
+impl<const N: usize> Display for BigInt<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for i in self.0.iter().rev() {
+            write!(f, "{:016X}", *i)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Ord for BigInt<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        for (a, b) in self.0.iter().rev().zip(other.0.iter().rev()) {
+            if a < b {
+                return core::cmp::Ordering::Less;
+            } else if a > b {
+                return core::cmp::Ordering::Greater;
+            }
+        }
+
+        core::cmp::Ordering::Equal
+    }
+}
+
+impl<const N: usize> PartialOrd for BigInt<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Distribution<BigInt<N>> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BigInt<N> {
+        let mut res = BigInt::default();
+        for limb in res.0.iter_mut() {
+            *limb = rng.gen();
+        }
+        res
+    }
+}
+
+impl<const N: usize> AsMut<[u64]> for BigInt<N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u64] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> AsRef<[u64]> for BigInt<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u64] {
+        &self.0
+    }
+}
+
+impl<const N: usize> From<u64> for BigInt<N> {
+    #[inline]
+    fn from(val: u64) -> BigInt<N> {
+        let mut repr = Self::default();
+        repr.0[0] = val;
+        repr
+    }
+}
+
+// serde only implements arrays up to a fixed length, so the limbs are
+// (de)serialized as a tuple by hand, matching the layout of `[u64; N]`.
+impl<const N: usize> serde::Serialize for BigInt<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for limb in self.0.iter() {
+            tuple.serialize_element(limb)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for BigInt<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LimbsVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for LimbsVisitor<N> {
+            type Value = BigInt<N>;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+                write!(f, "an array of {} u64 limbs", N)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<BigInt<N>, A::Error> {
+                let mut res = BigInt::default();
+                for (i, limb) in res.0.iter_mut().enumerate() {
+                    *limb = seq
+                        .next_element()?
+                        .ok_or_else(|| DeError::invalid_length(i, &self))?;
+                }
+                Ok(res)
+            }
+        }
+
+        deserializer.deserialize_tuple(N, LimbsVisitor::<N>)
+    }
+}
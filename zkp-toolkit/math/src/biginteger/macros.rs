@@ -219,6 +219,14 @@ macro_rules! bigint_impl {
             }
         }
 
+        impl ConstSerializedSize for $name {
+            const SERIALIZED_SIZE: usize = <[u64; $num_limbs]>::SERIALIZED_SIZE;
+        }
+
+        const _: () = assert!(
+            <$name as ConstSerializedSize>::SERIALIZED_SIZE == <$name as BigInteger>::NUM_LIMBS * 8
+        );
+
         impl Display for $name {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 for i in self.0.iter().rev() {
@@ -1,5 +1,5 @@
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::BitIterator,
     io::{Read, Result as IoResult, Write},
     UniformRand, Vec,
@@ -13,6 +13,9 @@ use rand::{
 #[macro_use]
 mod macros;
 
+mod bigint;
+pub use self::bigint::BigInt;
+
 bigint_impl!(BigInteger64, 1);
 bigint_impl!(BigInteger128, 2);
 bigint_impl!(BigInteger256, 4);
@@ -29,6 +32,7 @@ mod tests;
 pub trait BigInteger:
     ToBytes
     + FromBytes
+    + ConstSerializedSize
     + Copy
     + Clone
     + Debug
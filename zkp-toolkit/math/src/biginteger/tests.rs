@@ -104,3 +104,10 @@ fn test_biginteger832() {
     use crate::biginteger::BigInteger832 as B;
     test_biginteger(B::new([0u64; 13]));
 }
+
+#[test]
+fn test_bigint_const_generic() {
+    use crate::biginteger::BigInt;
+    test_biginteger(BigInt::new([0u64; 4]));
+    test_biginteger(BigInt::new([0u64; 13]));
+}
@@ -16,8 +16,45 @@ pub trait FromBytes: Sized {
     fn read<R: Read>(reader: R) -> IoResult<Self>;
 }
 
+/// Types whose `ToBytes` encoding always has the same length.
+pub trait ConstSerializedSize {
+    /// The number of bytes written by `ToBytes::write` and consumed by
+    /// `FromBytes::read`.
+    const SERIALIZED_SIZE: usize;
+
+    /// Reads `Self` from the first `Self::SERIALIZED_SIZE` bytes of `bytes`,
+    /// and returns it together with the remaining bytes.
+    #[inline]
+    fn read_exact_from(bytes: &[u8]) -> IoResult<(Self, &[u8])>
+    where
+        Self: FromBytes,
+    {
+        if bytes.len() < Self::SERIALIZED_SIZE {
+            return Err(error("ConstSerializedSize::read_exact_from: not enough bytes"));
+        }
+        let (head, tail) = bytes.split_at(Self::SERIALIZED_SIZE);
+        Ok((Self::read(head)?, tail))
+    }
+}
+
 macro_rules! array_bytes {
     ($N:expr) => {
+        impl ConstSerializedSize for [u8; $N] {
+            const SERIALIZED_SIZE: usize = $N;
+        }
+
+        impl ConstSerializedSize for [u16; $N] {
+            const SERIALIZED_SIZE: usize = $N * 2;
+        }
+
+        impl ConstSerializedSize for [u32; $N] {
+            const SERIALIZED_SIZE: usize = $N * 4;
+        }
+
+        impl ConstSerializedSize for [u64; $N] {
+            const SERIALIZED_SIZE: usize = $N * 8;
+        }
+
         impl ToBytes for [u8; $N] {
             #[inline]
             fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -179,6 +216,10 @@ impl FromBytes for u8 {
     }
 }
 
+impl ConstSerializedSize for u8 {
+    const SERIALIZED_SIZE: usize = 1;
+}
+
 impl ToBytes for u16 {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -195,6 +236,10 @@ impl FromBytes for u16 {
     }
 }
 
+impl ConstSerializedSize for u16 {
+    const SERIALIZED_SIZE: usize = 2;
+}
+
 impl ToBytes for u32 {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -211,6 +256,10 @@ impl FromBytes for u32 {
     }
 }
 
+impl ConstSerializedSize for u32 {
+    const SERIALIZED_SIZE: usize = 4;
+}
+
 impl ToBytes for u64 {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -227,6 +276,10 @@ impl FromBytes for u64 {
     }
 }
 
+impl ConstSerializedSize for u64 {
+    const SERIALIZED_SIZE: usize = 8;
+}
+
 impl ToBytes for u128 {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -243,6 +296,10 @@ impl FromBytes for u128 {
     }
 }
 
+impl ConstSerializedSize for u128 {
+    const SERIALIZED_SIZE: usize = 16;
+}
+
 impl ToBytes for () {
     #[inline]
     fn write<W: Write>(&self, _writer: W) -> IoResult<()> {
@@ -257,6 +314,10 @@ impl FromBytes for () {
     }
 }
 
+impl ConstSerializedSize for () {
+    const SERIALIZED_SIZE: usize = 0;
+}
+
 impl ToBytes for bool {
     #[inline]
     fn write<W: Write>(&self, writer: W) -> IoResult<()> {
@@ -276,6 +337,10 @@ impl FromBytes for bool {
     }
 }
 
+impl ConstSerializedSize for bool {
+    const SERIALIZED_SIZE: usize = 1;
+}
+
 impl<T: ToBytes> ToBytes for Vec<T> {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -303,9 +368,213 @@ impl<'a, T: 'a + ToBytes> ToBytes for &'a T {
     }
 }
 
+/// The `ToBytes` encoding of `value` reversed: big-endian, and for the
+/// elements of an extension field, of the highest coefficient first.
+pub fn to_bytes_be<T: ToBytes>(value: &T) -> IoResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    value.write(&mut bytes)?;
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Reads a value written by `to_bytes_be`.
+pub fn from_bytes_be<T: FromBytes>(bytes: &[u8]) -> IoResult<T> {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+    T::read(&bytes[..])
+}
+
+/// The lowercase hex of bytes, the encoding of them in human-readable serde
+/// formats.
+struct Hex<'a>(&'a [u8]);
+
+impl core::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes `bytes` with serde as a fixed-length tuple of bytes, which
+/// compact formats such as postcard write without a length prefix, or as
+/// the string of their hex in human-readable formats such as JSON.
+pub fn serialize_byte_tuple<S: serde::Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeTuple;
+
+    if serializer.is_human_readable() {
+        return serializer.collect_str(&Hex(bytes));
+    }
+
+    let mut tuple = serializer.serialize_tuple(bytes.len())?;
+    for byte in bytes {
+        tuple.serialize_element(byte)?;
+    }
+    tuple.end()
+}
+
+/// Deserializes a tuple of `len` bytes written by `serialize_byte_tuple`.
+/// Human-readable formats take the hex string, or the tuple of bytes they
+/// were written as before.
+pub fn deserialize_byte_tuple<'de, D: serde::Deserializer<'de>>(
+    len: usize,
+    deserializer: D,
+) -> Result<Vec<u8>, D::Error> {
+    use core::fmt::{Formatter, Result as FmtResult};
+    use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+
+    struct ByteTupleVisitor(usize);
+
+    impl<'de> Visitor<'de> for ByteTupleVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+            write!(f, "a tuple of {} bytes or their hex", self.0)
+        }
+
+        fn visit_str<E: Error>(self, hex: &str) -> Result<Self::Value, E> {
+            if !hex.is_ascii() || hex.len() != 2 * self.0 {
+                return Err(E::invalid_value(Unexpected::Str(hex), &self));
+            }
+            (0..self.0)
+                .map(|i| {
+                    u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                        .map_err(|_| E::invalid_value(Unexpected::Str(hex), &self))
+                })
+                .collect()
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::with_capacity(self.0);
+            for i in 0..self.0 {
+                let byte = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(ByteTupleVisitor(len))
+    } else {
+        deserializer.deserialize_tuple(len, ByteTupleVisitor(len))
+    }
+}
+
+/// Serializes `value` with serde as the tuple of its `ToBytes` encoding, for
+/// types whose serde layout is their byte layout.
+pub fn serialize_as_bytes<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ToBytes + ConstSerializedSize,
+    S: serde::Serializer,
+{
+    use serde::ser::Error;
+
+    let mut bytes = Vec::with_capacity(T::SERIALIZED_SIZE);
+    value
+        .write(&mut bytes)
+        .map_err(|_| S::Error::custom("failed to encode value"))?;
+    serialize_byte_tuple(&bytes, serializer)
+}
+
+/// Deserializes a value written by `serialize_as_bytes`, failing whenever
+/// `FromBytes::read` does.
+pub fn deserialize_from_bytes<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromBytes + ConstSerializedSize,
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+
+    let bytes = deserialize_byte_tuple(T::SERIALIZED_SIZE, deserializer)?;
+    T::read(&bytes[..]).map_err(|_| D::Error::custom("invalid encoding"))
+}
+
+/// Serializes the prime field element `value` as the hex of its `ToBytes`
+/// encoding in human-readable formats, and in the others as the tuple
+/// struct `name` of its `limbs` and a `PhantomData`, the layout the prime
+/// fields had derived.
+pub(crate) fn serialize_prime_field<T, L, S>(
+    value: &T,
+    name: &'static str,
+    limbs: &L,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: ToBytes + ConstSerializedSize,
+    L: serde::Serialize,
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeTupleStruct;
+
+    if serializer.is_human_readable() {
+        return serialize_as_bytes(value, serializer);
+    }
+
+    let mut state = serializer.serialize_tuple_struct(name, 2)?;
+    state.serialize_field(limbs)?;
+    state.serialize_field(&core::marker::PhantomData::<()>)?;
+    state.end()
+}
+
+/// Deserializes a prime field element written by `serialize_prime_field`,
+/// of its limbs by `from_limbs` in formats which are not human-readable,
+/// which gives `None` for limbs not reduced modulo the modulus.
+pub(crate) fn deserialize_prime_field<'de, T, L, D>(
+    name: &'static str,
+    from_limbs: fn(L) -> Option<T>,
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    T: FromBytes + ConstSerializedSize,
+    L: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    use core::{
+        fmt::{Formatter, Result as FmtResult},
+        marker::PhantomData,
+    };
+    use serde::de::{Error, SeqAccess, Visitor};
+
+    struct LimbsVisitor<T, L> {
+        name: &'static str,
+        from_limbs: fn(L) -> Option<T>,
+    }
+
+    impl<'de, T, L: serde::Deserialize<'de>> Visitor<'de> for LimbsVisitor<T, L> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+            write!(f, "tuple struct {}", self.name)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+            let limbs: L = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let _: PhantomData<()> = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            (self.from_limbs)(limbs).ok_or_else(|| A::Error::custom("unreduced field element"))
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        return deserialize_from_bytes(deserializer);
+    }
+
+    deserializer.deserialize_tuple_struct(name, 2, LimbsVisitor { name, from_limbs })
+}
+
 #[cfg(test)]
 mod test {
-    use super::ToBytes;
+    use super::{ConstSerializedSize, ToBytes};
     use crate::Vec;
     #[test]
     fn test_macro_empty() {
@@ -329,4 +598,20 @@ mod test {
         actual_bytes.extend_from_slice(&array3);
         assert_eq!(bytes, actual_bytes);
     }
+
+    #[test]
+    fn test_read_exact_from() {
+        let bytes = to_bytes![7u32, [1u8; 3], true].unwrap();
+        assert_eq!(
+            bytes.len(),
+            u32::SERIALIZED_SIZE + <[u8; 3]>::SERIALIZED_SIZE + bool::SERIALIZED_SIZE
+        );
+
+        let (a, rest) = u32::read_exact_from(&bytes).unwrap();
+        let (b, rest) = <[u8; 3]>::read_exact_from(rest).unwrap();
+        let (c, rest) = bool::read_exact_from(rest).unwrap();
+        assert_eq!((a, b, c), (7, [1u8; 3], true));
+        assert!(rest.is_empty());
+        assert!(u64::read_exact_from(&bytes[..7]).is_err());
+    }
 }
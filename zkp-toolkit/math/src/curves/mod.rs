@@ -1,8 +1,8 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
-    fields::{Field, PrimeField, SquareRootField},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
+    fields::{Field, FpParameters, PrimeField, SquareRootField},
     groups::Group,
     UniformRand, Vec,
 };
@@ -77,7 +77,8 @@ pub trait PairingEngine: Sized + 'static + Copy + Debug + Sync + Send {
     #[must_use]
     fn final_exponentiation(_: &Self::Fqk) -> Option<Self::Fqk>;
 
-    /// Computes a product of pairings.
+    /// Computes a product of pairings, sharing the Miller loop between all
+    /// the pairs.
     #[must_use]
     fn product_of_pairings<'a, I>(i: I) -> Self::Fqk
     where
@@ -86,6 +87,22 @@ pub trait PairingEngine: Sized + 'static + Copy + Debug + Sync + Send {
         Self::final_exponentiation(&Self::miller_loop(i)).unwrap()
     }
 
+    /// Computes the product of the pairings of `ps[i]` and `qs[i]` with a
+    /// single Miller loop and a single final exponentiation, `None` if the
+    /// final exponentiation fails.
+    ///
+    /// Panics if `ps` and `qs` have different lengths.
+    #[must_use]
+    fn multi_pairing(ps: &[Self::G1Prepared], qs: &[Self::G2Prepared]) -> Option<Self::Fqk> {
+        assert_eq!(ps.len(), qs.len(), "mismatched number of pairing inputs");
+        let pairs = ps
+            .iter()
+            .cloned()
+            .zip(qs.iter().cloned())
+            .collect::<Vec<_>>();
+        Self::final_exponentiation(&Self::miller_loop(&pairs))
+    }
+
     /// Performs multiple pairing operations
     #[must_use]
     fn pairing<G1, G2>(p: G1, q: G2) -> Self::Fqk
@@ -98,6 +115,22 @@ pub trait PairingEngine: Sized + 'static + Copy + Debug + Sync + Send {
         Self::product_of_pairings(core::iter::once(&(g1_prep, g2_prep)))
     }
 
+    /// Performs a miller loop of `(p, q)` and of the `prepared` pairs, the
+    /// line functions of `q` evaluated along the loop rather than prepared,
+    /// e.g. of a point of G2 of a proof, paired once. The engines of the
+    /// bls12 and bn curves allocate nothing, the others prepare `q`.
+    #[must_use]
+    fn miller_loop_with_affine(
+        p: &Self::G1Affine,
+        q: &Self::G2Affine,
+        prepared: &[(Self::G1Prepared, &Self::G2Prepared)],
+    ) -> Self::Fqk {
+        let mut pairs = Vec::with_capacity(prepared.len() + 1);
+        pairs.push((Self::G1Prepared::from(*p), Self::G2Prepared::from(*q)));
+        pairs.extend(prepared.iter().map(|(p, q)| (p.clone(), (*q).clone())));
+        Self::miller_loop(&pairs)
+    }
+
     fn vartime_multiscalar_mul_g2(
         scalars: &[Self::Fr],
         points: &[Self::G2Affine],
@@ -162,7 +195,13 @@ pub trait ProjectiveCurve:
     /// Normalizes a slice of projective elements and outputs a vector
     /// containing the affine equivalents.
     fn batch_normalization_into_affine(v: &[Self]) -> Vec<Self::Affine> {
-        let mut v = v.to_vec();
+        Self::batch_into_affine(v.to_vec())
+    }
+
+    /// Converts a vector of projective elements to affine with a single
+    /// field inversion shared by all of them (Montgomery's trick), instead
+    /// of one inversion per `into_affine()`.
+    fn batch_into_affine(mut v: Vec<Self>) -> Vec<Self::Affine> {
         Self::batch_normalization(&mut v);
         v.into_iter().map(|v| v.into()).collect()
     }
@@ -199,6 +238,33 @@ pub trait ProjectiveCurve:
     /// This is usually faster than adding `other` in projective form.
     fn add_assign_mixed(&mut self, other: &Self::Affine);
 
+    /// Multiply this element by the cofactor.
+    #[must_use]
+    fn mul_by_cofactor(&self) -> Self {
+        self.into_affine().mul_by_cofactor().into()
+    }
+
+    /// Multiply this element by the inverse of the cofactor in
+    /// `Self::ScalarField`.
+    #[must_use]
+    fn mul_by_cofactor_inv(&self) -> Self {
+        self.into_affine().mul_by_cofactor_inv().into()
+    }
+
+    /// Maps this element into the prime order subgroup, see
+    /// `AffineCurve::clear_cofactor`.
+    #[must_use]
+    fn clear_cofactor(&self) -> Self {
+        self.into_affine().clear_cofactor().into()
+    }
+
+    /// Checks that this element, assumed to be on the curve, is in the prime
+    /// order subgroup.
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
+        self.into_affine()
+            .is_in_correct_subgroup_assuming_on_curve()
+    }
+
     /// Performs scalar multiplication of this element.
     fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(mut self, other: S) -> Self {
         let mut res = Self::zero();
@@ -230,6 +296,7 @@ pub trait AffineCurve:
     + Sized
     + ToBytes
     + FromBytes
+    + ConstSerializedSize
     + Copy
     + Clone
     + Default
@@ -278,8 +345,60 @@ pub trait AffineCurve:
     /// `Self::ScalarField`.
     #[must_use]
     fn mul_by_cofactor_inv(&self) -> Self;
+
+    /// Maps this element into the prime order subgroup. Defaults to
+    /// `mul_by_cofactor`, curves with a cheaper map use that instead.
+    #[must_use]
+    fn clear_cofactor(&self) -> Self {
+        self.mul_by_cofactor()
+    }
+
+    /// Checks that this element satisfies the curve equation. Deserializing
+    /// does not check it, so elements of untrusted bytes are checked with it
+    /// and `is_in_correct_subgroup_assuming_on_curve`.
+    fn is_on_curve(&self) -> bool;
+
+    /// Checks that this element, assumed to be on the curve, is in the prime
+    /// order subgroup.
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
+        self.mul(<Self::ScalarField as PrimeField>::Params::MODULUS)
+            .is_zero()
+    }
+
+    /// Sets `bases[i] = bases[i] + other[i]` for every `i`.
+    ///
+    /// The default implementation adds in projective coordinates and
+    /// normalizes the sums in a batch. Curves can override it to share a
+    /// single field inversion among all the affine additions.
+    fn batch_add_in_place_affine(bases: &mut [Self], other: &[Self]) {
+        let sums = bases
+            .iter()
+            .zip(other)
+            .map(|(a, b)| {
+                let mut sum = a.into_projective();
+                sum.add_assign_mixed(b);
+                sum
+            })
+            .collect::<Vec<_>>();
+        let sums = Self::Projective::batch_normalization_into_affine(&sums);
+        bases.iter_mut().zip(sums).for_each(|(a, sum)| *a = sum);
+    }
+
+    /// Splits `self * scalar` into `p1 * k1 + p2 * k2`, where `k1` and `k2`
+    /// are about half the bit size of `scalar`, if the curve has an
+    /// efficiently computable endomorphism. Returns `None` otherwise.
+    #[inline]
+    fn glv_split(
+        &self,
+        _scalar: &<Self::ScalarField as PrimeField>::BigInt,
+    ) -> Option<[(Self, <Self::ScalarField as PrimeField>::BigInt); 2]> {
+        None
+    }
 }
 
+/// A curve used as a plain group, for the schemes and gadgets which do not
+/// need a pairing. Every `PairingEngine` is a `Curve` through its G1, while
+/// curves without a pairing, such as secp256k1, implement it directly.
 pub trait Curve: 'static + Clone {
     /// The base field that hosts.
     type Fq: PrimeField + SquareRootField;
@@ -343,3 +462,25 @@ pub fn prepare_g2<E: PairingEngine>(g: impl Into<E::G2Affine>) -> E::G2Prepared
     let g: E::G2Affine = g.into();
     E::G2Prepared::from(g)
 }
+
+/// Serde helpers which write a projective point in its affine form, for use as
+/// `#[serde(with = "math::curves::serde_affine")]` on a field of any
+/// `ProjectiveCurve` type. As with the serde impls of the short Weierstrass
+/// projective points, the `z` coordinate is not preserved.
+pub mod serde_affine {
+    use super::{AffineCurve, ProjectiveCurve};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<G: ProjectiveCurve, S: Serializer>(
+        point: &G,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        point.into_affine().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, G: ProjectiveCurve, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<G, D::Error> {
+        G::Affine::deserialize(deserializer).map(|p| p.into_projective())
+    }
+}
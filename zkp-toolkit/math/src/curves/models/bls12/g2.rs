@@ -37,10 +37,10 @@ pub struct G2Prepared<P: Bls12Parameters> {
     Debug(bound = "P: Bls12Parameters")
 )]
 #[derive(Serialize, Deserialize)]
-struct G2HomProjective<P: Bls12Parameters> {
-    x: Fp2<P::Fp2Params>,
-    y: Fp2<P::Fp2Params>,
-    z: Fp2<P::Fp2Params>,
+pub(super) struct G2HomProjective<P: Bls12Parameters> {
+    pub(super) x: Fp2<P::Fp2Params>,
+    pub(super) y: Fp2<P::Fp2Params>,
+    pub(super) z: Fp2<P::Fp2Params>,
 }
 
 impl<P: Bls12Parameters> Default for G2Prepared<P> {
@@ -117,7 +117,7 @@ impl<P: Bls12Parameters> G2Prepared<P> {
     }
 }
 
-fn doubling_step<B: Bls12Parameters>(
+pub(super) fn doubling_step<B: Bls12Parameters>(
     r: &mut G2HomProjective<B>,
     two_inv: &B::Fp,
 ) -> (Fp2<B::Fp2Params>, Fp2<B::Fp2Params>, Fp2<B::Fp2Params>) {
@@ -146,7 +146,7 @@ fn doubling_step<B: Bls12Parameters>(
     }
 }
 
-fn addition_step<B: Bls12Parameters>(
+pub(super) fn addition_step<B: Bls12Parameters>(
     r: &mut G2HomProjective<B>,
     q: &G2Affine<B>,
 ) -> (Fp2<B::Fp2Params>, Fp2<B::Fp2Params>, Fp2<B::Fp2Params>) {
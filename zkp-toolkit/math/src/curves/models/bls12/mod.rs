@@ -1,5 +1,6 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
+use self::g2::G2HomProjective;
 use crate::{
     curves::{
         models::{ModelParameters, SWModelParameters},
@@ -12,7 +13,7 @@ use crate::{
         BitIterator, Field, Fp2, PrimeField, SquareRootField,
     },
 };
-use num_traits::One;
+use num_traits::{One, Zero};
 
 use core::marker::PhantomData;
 
@@ -73,6 +74,19 @@ impl<P: Bls12Parameters> Bls12<P> {
         }
     }
 
+    // Evaluate the `j`-th line function of each of the prepared pairs.
+    fn ell_prepared(
+        f: &mut Fp12<P::Fp12Params>,
+        prepared: &[(G1Prepared<P>, &G2Prepared<P>)],
+        j: usize,
+    ) {
+        for (p, q) in prepared {
+            if !p.is_zero() && !q.is_zero() {
+                Self::ell(f, &q.ell_coeffs[j], &p.0);
+            }
+        }
+    }
+
     fn exp_by_x(mut f: Fp12<P::Fp12Params>) -> Fp12<P::Fp12Params> {
         f = f.cyclotomic_exp(P::X);
         if P::X_IS_NEGATIVE {
@@ -128,6 +142,48 @@ impl<P: Bls12Parameters> PairingEngine for Bls12<P> {
         f
     }
 
+    fn miller_loop_with_affine(
+        p: &Self::G1Affine,
+        q: &Self::G2Affine,
+        prepared: &[(Self::G1Prepared, &Self::G2Prepared)],
+    ) -> Self::Fqk {
+        // the steps of `G2Prepared::from`, of no coefficients kept.
+        let two_inv = P::Fp::one().double().inverse().unwrap();
+        let affine = !p.is_zero() && !q.is_zero();
+        let mut r = G2HomProjective {
+            x: q.x,
+            y: q.y,
+            z: Fp2::one(),
+        };
+
+        let mut f = Self::Fqk::one();
+        let mut j = 0;
+
+        for i in BitIterator::new(P::X).skip(1) {
+            f.square_in_place();
+
+            if affine {
+                Self::ell(&mut f, &g2::doubling_step::<P>(&mut r, &two_inv), p);
+            }
+            Self::ell_prepared(&mut f, prepared, j);
+            j += 1;
+
+            if i {
+                if affine {
+                    Self::ell(&mut f, &g2::addition_step::<P>(&mut r, q), p);
+                }
+                Self::ell_prepared(&mut f, prepared, j);
+                j += 1;
+            }
+        }
+
+        if P::X_IS_NEGATIVE {
+            f.conjugate();
+        }
+
+        f
+    }
+
     fn final_exponentiation(f: &Self::Fqk) -> Option<Self::Fqk> {
         // Computing the final exponentation following
         // https://eprint.iacr.org/2016/130.pdf.
@@ -38,10 +38,10 @@ pub struct G2Prepared<P: BnParameters> {
     Debug(bound = "P: BnParameters")
 )]
 #[derive(Serialize, Deserialize)]
-struct G2HomProjective<P: BnParameters> {
-    x: Fp2<P::Fp2Params>,
-    y: Fp2<P::Fp2Params>,
-    z: Fp2<P::Fp2Params>,
+pub(super) struct G2HomProjective<P: BnParameters> {
+    pub(super) x: Fp2<P::Fp2Params>,
+    pub(super) y: Fp2<P::Fp2Params>,
+    pub(super) z: Fp2<P::Fp2Params>,
 }
 
 impl<P: BnParameters> Default for G2Prepared<P> {
@@ -137,7 +137,7 @@ impl<P: BnParameters> G2Prepared<P> {
     }
 }
 
-fn doubling_step<P: BnParameters>(
+pub(super) fn doubling_step<P: BnParameters>(
     r: &mut G2HomProjective<P>,
 ) -> (Fp2<P::Fp2Params>, Fp2<P::Fp2Params>, Fp2<P::Fp2Params>) {
     // Adaptation of Algorithm 26, https://eprint.iacr.org/2010/354.pdf
@@ -215,7 +215,7 @@ fn doubling_step<P: BnParameters>(
     (tmp0, tmp3, tmp6)
 }
 
-fn addition_step<P: BnParameters>(
+pub(super) fn addition_step<P: BnParameters>(
     r: &mut G2HomProjective<P>,
     q: &G2Affine<P>,
 ) -> (Fp2<P::Fp2Params>, Fp2<P::Fp2Params>, Fp2<P::Fp2Params>) {
@@ -1,6 +1,10 @@
-use core::{marker::PhantomData, ops::MulAssign};
-use num_traits::One;
+use core::{
+    marker::PhantomData,
+    ops::{MulAssign, Neg},
+};
+use num_traits::{One, Zero};
 
+use self::g2::G2HomProjective;
 use crate::{
     curves::{ModelParameters, PairingEngine, SWModelParameters},
     fields::{
@@ -54,8 +58,21 @@ impl<P: BnParameters> Bn<P> {
         f.mul_by_034(&c0, &c1, &coeffs.2);
     }
 
+    // Evaluate the `j`-th line function of each of the prepared pairs.
+    fn ell_prepared(
+        f: &mut Fp12<P::Fp12Params>,
+        prepared: &[(G1Prepared<P>, &G2Prepared<P>)],
+        j: usize,
+    ) {
+        for (p, q) in prepared {
+            if !p.is_zero() && !q.is_zero() {
+                Self::ell(f, &q.ell_coeffs[j], &p.0);
+            }
+        }
+    }
+
     fn exp_by_x(f: &mut Fp12<P::Fp12Params>) {
-        *f = f.pow(&P::U)
+        *f = f.cyclotomic_exp(P::U)
     }
 }
 
@@ -124,6 +141,69 @@ impl<P: BnParameters> PairingEngine for Bn<P> {
         f
     }
 
+    fn miller_loop_with_affine(
+        p: &Self::G1Affine,
+        q: &Self::G2Affine,
+        prepared: &[(Self::G1Prepared, &Self::G2Prepared)],
+    ) -> Self::Fqk {
+        // the steps of `G2Prepared::from`, of no coefficients kept.
+        let affine = !p.is_zero() && !q.is_zero();
+        let mut r: G2HomProjective<P> = G2HomProjective {
+            x: q.x,
+            y: q.y,
+            z: Fp2::one(),
+        };
+        let negq = -*q;
+
+        let mut f = Self::Fqk::one();
+        let mut j = 0;
+
+        for i in (1..P::SIX_U_PLUS_2_NAF.len()).rev() {
+            if i != P::SIX_U_PLUS_2_NAF.len() - 1 {
+                f.square_in_place();
+            }
+            if affine {
+                Self::ell(&mut f, &g2::doubling_step(&mut r), p);
+            }
+            Self::ell_prepared(&mut f, prepared, j);
+            j += 1;
+
+            let addend = match P::SIX_U_PLUS_2_NAF[i - 1] {
+                1 => q,
+                -1 => &negq,
+                _ => continue,
+            };
+            if affine {
+                Self::ell(&mut f, &g2::addition_step(&mut r, addend), p);
+            }
+            Self::ell_prepared(&mut f, prepared, j);
+            j += 1;
+        }
+
+        // two additional steps: for q1 and minus q2
+
+        let mut q1 = *q;
+        q1.x.c1 = q1.x.c1.neg();
+        q1.x.mul_assign(&P::Fp6Params::FROBENIUS_COEFF_FP6_C1[1]);
+        q1.y.c1 = q1.y.c1.neg();
+        q1.y.mul_assign(&P::CUBIC_NONRESIDUE_TO_Q_MINUS_1_OVER_2);
+
+        let mut minusq2 = *q;
+        minusq2
+            .x
+            .mul_assign(&P::Fp6Params::FROBENIUS_COEFF_FP6_C1[2]);
+
+        for addend in &[q1, minusq2] {
+            if affine {
+                Self::ell(&mut f, &g2::addition_step(&mut r, addend), p);
+            }
+            Self::ell_prepared(&mut f, prepared, j);
+            j += 1;
+        }
+
+        f
+    }
+
     fn final_exponentiation(f: &Self::Fqk) -> Option<Self::Fqk> {
         let mut f1 = *f;
         f1.conjugate();
@@ -136,6 +216,7 @@ impl<P: BnParameters> PairingEngine for Bn<P> {
                 r.frobenius_map(2);
                 r.mul_assign(&f2);
 
+                // r is in the cyclotomic subgroup from here on.
                 let mut fp = r;
                 fp.frobenius_map(1);
 
@@ -185,7 +266,7 @@ impl<P: BnParameters> PairingEngine for Bn<P> {
                 y6.mul_assign(&fu3p);
                 y6.conjugate();
 
-                y6.square_in_place();
+                y6 = y6.cyclotomic_square();
                 y6.mul_assign(&y4);
                 y6.mul_assign(&y5);
 
@@ -195,16 +276,16 @@ impl<P: BnParameters> PairingEngine for Bn<P> {
 
                 y6.mul_assign(&y2);
 
-                t1.square_in_place();
+                t1 = t1.cyclotomic_square();
                 t1.mul_assign(&y6);
-                t1.square_in_place();
+                t1 = t1.cyclotomic_square();
 
                 let mut t0 = t1;
                 t0.mul_assign(&y1);
 
                 t1.mul_assign(&y0);
 
-                t0.square_in_place();
+                t0 = t0.cyclotomic_square();
                 t0.mul_assign(&t1);
 
                 Some(t0)
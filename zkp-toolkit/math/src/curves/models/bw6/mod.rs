@@ -0,0 +1,223 @@
+use crate::{
+    curves::{
+        bls12::TwistType,
+        models::{ModelParameters, SWModelParameters},
+        PairingEngine,
+    },
+    fields::{
+        fp3::Fp3Parameters,
+        fp6_2over3::{Fp6, Fp6Parameters},
+        BitIterator, Field, PrimeField, SquareRootField,
+    },
+};
+use num_traits::One;
+
+use core::marker::PhantomData;
+
+/// Parameters of a BW6 curve, the outer curve of a BLS12 curve with seed `X`
+/// whose scalar field is the base field of the BLS12 curve. G1 and G2 both
+/// live over the base field, G2 on a sextic twist.
+pub trait BW6Parameters: 'static {
+    const X: <Self::Fp as PrimeField>::BigInt;
+    const X_IS_NEGATIVE: bool;
+    /// The loop count of the first Miller loop, `X + 1`.
+    const ATE_LOOP_COUNT_1: &'static [u64];
+    const ATE_LOOP_COUNT_1_IS_NEGATIVE: bool;
+    /// The loop count of the second Miller loop, `X^3 - X^2 - X`, in
+    /// non-adjacent form with the least significant digit first.
+    const ATE_LOOP_COUNT_2: &'static [i8];
+    const ATE_LOOP_COUNT_2_IS_NEGATIVE: bool;
+    /// Coefficients of `R0` and `R1`, constant term first, where the hard
+    /// part of the final exponentiation is raised to
+    /// `R0(X) + q * R1(X)`, a multiple of `(q^2 - q + 1) / r` coprime to `r`.
+    const FINAL_EXPONENT_LAST_CHUNK_R0: &'static [i64];
+    const FINAL_EXPONENT_LAST_CHUNK_R1: &'static [i64];
+    const TWIST_TYPE: TwistType;
+    type Fp: PrimeField + SquareRootField + Into<<Self::Fp as PrimeField>::BigInt>;
+    type Fp3Params: Fp3Parameters<Fp = Self::Fp>;
+    type Fp6Params: Fp6Parameters<Fp3Params = Self::Fp3Params>;
+    type G1Parameters: SWModelParameters<BaseField = Self::Fp>;
+    type G2Parameters: SWModelParameters<
+        BaseField = Self::Fp,
+        ScalarField = <Self::G1Parameters as ModelParameters>::ScalarField,
+    >;
+}
+
+pub mod g1;
+pub mod g2;
+
+pub use self::{
+    g1::{G1Affine, G1Prepared, G1Projective},
+    g2::{G2Affine, G2Prepared, G2Projective},
+};
+
+#[derive(Derivative, Serialize, Deserialize)]
+#[derivative(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct BW6<P: BW6Parameters>(PhantomData<fn() -> P>);
+
+impl<P: BW6Parameters> BW6<P> {
+    // Evaluate the line function at point p.
+    fn ell(f: &mut Fp6<P::Fp6Params>, coeffs: &(P::Fp, P::Fp, P::Fp), p: &G1Affine<P>) {
+        let mut c0 = coeffs.0;
+        let mut c1 = coeffs.1;
+        let mut c2 = coeffs.2;
+
+        match P::TWIST_TYPE {
+            TwistType::M => {
+                c2 *= &p.y;
+                c1 *= &p.x;
+                f.mul_by_014(&c0, &c1, &c2);
+            }
+            TwistType::D => {
+                c0 *= &p.y;
+                c1 *= &p.x;
+                f.mul_by_034(&c0, &c1, &c2);
+            }
+        }
+    }
+
+    fn exp_by_x(f: &Fp6<P::Fp6Params>) -> Fp6<P::Fp6Params> {
+        let f = f.cyclotomic_exp(&P::X);
+        if P::X_IS_NEGATIVE {
+            f.unitary_inverse()
+        } else {
+            f
+        }
+    }
+
+    // Raises an element of the cyclotomic subgroup to a small signed power.
+    fn exp_by_small(f: &Fp6<P::Fp6Params>, e: i64) -> Fp6<P::Fp6Params> {
+        let abs = <P::Fp as PrimeField>::BigInt::from(e.unsigned_abs());
+        let f = f.cyclotomic_exp(&abs);
+        if e < 0 {
+            f.unitary_inverse()
+        } else {
+            f
+        }
+    }
+
+    fn final_exponentiation_first_chunk(
+        elt: &Fp6<P::Fp6Params>,
+        elt_inv: &Fp6<P::Fp6Params>,
+    ) -> Fp6<P::Fp6Params> {
+        // (q^3-1)*(q+1)
+
+        // elt_q3 = elt^(q^3), which is the conjugate
+        let elt_q3 = elt.unitary_inverse();
+        // elt_q3_over_elt = elt^(q^3-1)
+        let elt_q3_over_elt = elt_q3 * elt_inv;
+        // alpha = elt^((q^3-1) * q)
+        let mut alpha = elt_q3_over_elt;
+        alpha.frobenius_map(1);
+        // beta = elt^((q^3-1)*(q+1)
+        alpha * &elt_q3_over_elt
+    }
+
+    fn final_exponentiation_last_chunk(elt: &Fp6<P::Fp6Params>) -> Fp6<P::Fp6Params> {
+        // Following https://eprint.iacr.org/2020/351.pdf, raises to
+        // R0(X) + q * R1(X) by Horner's rule in X, where each step multiplies
+        // in elt^R0_i * (elt^q)^R1_i.
+        let mut elt_q = *elt;
+        elt_q.frobenius_map(1);
+
+        let r0 = P::FINAL_EXPONENT_LAST_CHUNK_R0;
+        let r1 = P::FINAL_EXPONENT_LAST_CHUNK_R1;
+        let degree = core::cmp::max(r0.len(), r1.len());
+        let coeff = |i: usize| {
+            let e0 = r0.get(i).cloned().unwrap_or(0);
+            let e1 = r1.get(i).cloned().unwrap_or(0);
+            Self::exp_by_small(elt, e0) * &Self::exp_by_small(&elt_q, e1)
+        };
+
+        let mut result = Fp6::one();
+        for i in (0..degree).rev() {
+            result = Self::exp_by_x(&result) * &coeff(i);
+        }
+        result
+    }
+}
+
+impl<P: BW6Parameters> PairingEngine for BW6<P> {
+    type Fr = <P::G1Parameters as ModelParameters>::ScalarField;
+    type G1Projective = G1Projective<P>;
+    type G1Affine = G1Affine<P>;
+    type G1Prepared = G1Prepared<P>;
+    type G2Projective = G2Projective<P>;
+    type G2Affine = G2Affine<P>;
+    type G2Prepared = G2Prepared<P>;
+    type Fq = P::Fp;
+    type Fqe = P::Fp;
+    type Fqk = Fp6<P::Fp6Params>;
+
+    fn miller_loop<'a, I>(i: I) -> Self::Fqk
+    where
+        I: IntoIterator<Item = &'a (Self::G1Prepared, Self::G2Prepared)>,
+    {
+        // Algorithm 5 of https://eprint.iacr.org/2020/351.pdf, the product
+        // of f_{X+1,Q}(P) and f_{X^3-X^2-X,Q}(P)^q.
+        let mut pairs_1 = vec![];
+        let mut pairs_2 = vec![];
+        for (p, q) in i {
+            if !p.is_zero() && !q.is_zero() {
+                pairs_1.push((p, q.ell_coeffs_1.iter()));
+                pairs_2.push((p, q.ell_coeffs_2.iter()));
+            }
+        }
+
+        let mut f_1 = Self::Fqk::one();
+
+        for i in BitIterator::new(P::ATE_LOOP_COUNT_1)
+            .skip_while(|b| !b)
+            .skip(1)
+        {
+            f_1.square_in_place();
+
+            for (p, ref mut coeffs) in &mut pairs_1 {
+                Self::ell(&mut f_1, coeffs.next().unwrap(), &p.0);
+            }
+
+            if i {
+                for &mut (p, ref mut coeffs) in &mut pairs_1 {
+                    Self::ell(&mut f_1, coeffs.next().unwrap(), &p.0);
+                }
+            }
+        }
+
+        if P::ATE_LOOP_COUNT_1_IS_NEGATIVE {
+            f_1 = f_1.unitary_inverse();
+        }
+
+        let mut f_2 = Self::Fqk::one();
+
+        for i in (1..P::ATE_LOOP_COUNT_2.len()).rev() {
+            if i != P::ATE_LOOP_COUNT_2.len() - 1 {
+                f_2.square_in_place();
+            }
+
+            for (p, ref mut coeffs) in &mut pairs_2 {
+                Self::ell(&mut f_2, coeffs.next().unwrap(), &p.0);
+            }
+
+            if P::ATE_LOOP_COUNT_2[i - 1] != 0 {
+                for &mut (p, ref mut coeffs) in &mut pairs_2 {
+                    Self::ell(&mut f_2, coeffs.next().unwrap(), &p.0);
+                }
+            }
+        }
+
+        if P::ATE_LOOP_COUNT_2_IS_NEGATIVE {
+            f_2 = f_2.unitary_inverse();
+        }
+
+        f_2.frobenius_map(1);
+
+        f_1 * &f_2
+    }
+
+    fn final_exponentiation(f: &Self::Fqk) -> Option<Self::Fqk> {
+        f.inverse().map(|f_inv| {
+            let f = Self::final_exponentiation_first_chunk(f, &f_inv);
+            Self::final_exponentiation_last_chunk(&f)
+        })
+    }
+}
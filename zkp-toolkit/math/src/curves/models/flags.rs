@@ -187,3 +187,12 @@ impl Flags for EdwardsFlags {
         1
     }
 }
+
+/// The flag of the first byte of a compressed point, in the big-endian
+/// layout of ZCash and of the IETF draft of pairing-friendly curves.
+pub const COMPRESSION_FLAG: u8 = 1 << 7;
+/// The flag of the first byte of the encoding of the point at infinity.
+pub const INFINITY_FLAG: u8 = 1 << 6;
+/// The flag of the first byte of a compressed point of the
+/// lexicographically largest of its two y-coordinates.
+pub const SORT_FLAG: u8 = 1 << 5;
@@ -0,0 +1,85 @@
+use crate::{curves::models::ModelParameters, fields::PrimeField};
+
+/// Parameters of the GLV endomorphism `phi(x, y) = (beta * x, y)` of a short
+/// Weierstrass curve with `a = 0`, which acts on the prime order subgroup as
+/// multiplication by `lambda`.
+///
+/// A scalar `k` is decomposed as `k = k1 + k2 * lambda (mod r)` with the help
+/// of a short basis `(a1, b1), (a2, b2)` of the lattice
+/// `{(x, y) : x + y * lambda = 0 (mod r)}`, so that `|k1|` and `|k2|` are about
+/// half the bit size of `r`. Only the `b` coordinates are needed, together with
+/// the precomputed roundings `g1 = round(2^m * b2 / r)` and
+/// `g2 = round(-2^m * b1 / r)`, where `m` is the bit size of the scalar field
+/// representation. The basis signs are chosen such that `g1` and `g2` are
+/// non-negative.
+pub struct GLVParameters<P: ModelParameters> {
+    /// A non-trivial cube root of unity in the base field.
+    pub beta: P::BaseField,
+    /// The eigenvalue of `phi` on the prime order subgroup, a non-trivial
+    /// cube root of unity in the scalar field.
+    pub lambda: P::ScalarField,
+    /// `b1 mod r`.
+    pub b1: P::ScalarField,
+    /// `b2 mod r`.
+    pub b2: P::ScalarField,
+    /// `round(2^m * b2 / r)` as `u64` limbs, least significant limb first.
+    pub g1: &'static [u64],
+    /// `round(-2^m * b1 / r)` as `u64` limbs, least significant limb first.
+    pub g2: &'static [u64],
+}
+
+impl<P: ModelParameters> GLVParameters<P> {
+    /// Decomposes `k` as `k1 + k2 * lambda`. Both halves are returned as
+    /// their absolute value together with a flag which is set when the half
+    /// is negative.
+    pub fn decompose(
+        &self,
+        k: &P::ScalarField,
+    ) -> (
+        (bool, <P::ScalarField as PrimeField>::BigInt),
+        (bool, <P::ScalarField as PrimeField>::BigInt),
+    ) {
+        let k_repr = k.into_repr();
+        let c1 = Self::mul_shift(&k_repr, self.g1);
+        let c2 = Self::mul_shift(&k_repr, self.g2);
+
+        // k2 = -c1 * b1 - c2 * b2, k1 = k - k2 * lambda
+        let k2 = -(c1 * &self.b1 + &(c2 * &self.b2));
+        let k1 = *k - &(k2 * &self.lambda);
+
+        (Self::signed(k1), Self::signed(k2))
+    }
+
+    /// Returns `floor(k * g / 2^m)` as a scalar field element.
+    fn mul_shift(k: &<P::ScalarField as PrimeField>::BigInt, g: &[u64]) -> P::ScalarField {
+        let k = k.as_ref();
+        let mut product = vec![0u64; k.len() + g.len()];
+        for (i, k_i) in k.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, g_j) in g.iter().enumerate() {
+                let t = (*k_i as u128) * (*g_j as u128) + (product[i + j] as u128) + carry;
+                product[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            product[i + g.len()] = carry as u64;
+        }
+
+        let mut repr = <P::ScalarField as PrimeField>::BigInt::default();
+        let high = &product[k.len()..];
+        let len = core::cmp::min(high.len(), repr.as_ref().len());
+        debug_assert!(high[len..].iter().all(|limb| *limb == 0));
+        repr.as_mut()[..len].copy_from_slice(&high[..len]);
+        P::ScalarField::from_repr(repr)
+    }
+
+    /// Interprets elements above `(r - 1) / 2` as negative.
+    fn signed(k: P::ScalarField) -> (bool, <P::ScalarField as PrimeField>::BigInt) {
+        let k_repr = k.into_repr();
+        let neg_repr = (-k).into_repr();
+        if neg_repr < k_repr {
+            (true, neg_repr)
+        } else {
+            (false, k_repr)
+        }
+    }
+}
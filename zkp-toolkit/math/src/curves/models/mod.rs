@@ -1,7 +1,15 @@
-use crate::fields::{Field, PrimeField, SquareRootField};
+use crate::fields::{BitIterator, Field, PrimeField, SquareRootField};
+use num_traits::Zero;
+
+use self::{
+    glv::GLVParameters,
+    short_weierstrass_jacobian::{GroupAffine, GroupProjective},
+};
 
 pub mod bls12;
 pub mod bn;
+pub mod bw6;
+pub mod glv;
 pub mod mnt4;
 pub mod mnt6;
 pub mod short_weierstrass_jacobian;
@@ -34,6 +42,38 @@ pub trait SWModelParameters: ModelParameters {
         copy += &Self::COEFF_B;
         copy
     }
+
+    /// Returns the parameters of the GLV endomorphism if the curve has one,
+    /// in which case scalar multiplication and MSM split every scalar into
+    /// two halves of about half the bit size.
+    #[inline(always)]
+    fn glv() -> Option<GLVParameters<Self>>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Maps a point on the curve into the prime order subgroup. Multiplies by
+    /// `COFACTOR` unless the curve overrides it with a cheaper map, which may
+    /// differ from it by a factor coprime to the group order.
+    fn clear_cofactor(item: &GroupAffine<Self>) -> GroupProjective<Self>
+    where
+        Self: Sized,
+    {
+        item.scale_by_cofactor()
+    }
+
+    /// Checks that a point on the curve is in the prime order subgroup.
+    /// Multiplies by the group order unless the curve overrides it with a
+    /// cheaper check.
+    fn is_in_correct_subgroup_assuming_on_curve(item: &GroupAffine<Self>) -> bool
+    where
+        Self: Sized,
+    {
+        item.mul_bits(BitIterator::new(Self::ScalarField::characteristic()))
+            .is_zero()
+    }
 }
 
 pub trait TEModelParameters: ModelParameters {
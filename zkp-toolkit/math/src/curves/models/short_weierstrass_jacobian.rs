@@ -1,10 +1,10 @@
 use crate::{
-    curves::models::SWModelParameters as Parameters,
+    curves::models::{glv::GLVParameters, SWModelParameters as Parameters},
     io::{Read, Result as IoResult, Write},
-    UniformRand, Vec,
+    vec, UniformRand, Vec,
 };
 use core::{
-    fmt::{Display, Formatter, Result as FmtResult},
+    fmt::{Display, Error as FmtError, Formatter, LowerHex, Result as FmtResult},
     marker::PhantomData,
     ops::{Add, AddAssign, MulAssign, Neg, Sub, SubAssign},
 };
@@ -15,12 +15,12 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{from_bytes_be, to_bytes_be, ConstSerializedSize, FromBytes, ToBytes},
     curves::{
-        flags::{Flags, SWFlags},
+        flags::{Flags, SWFlags, COMPRESSION_FLAG, INFINITY_FLAG, SORT_FLAG},
         AffineCurve, ProjectiveCurve,
     },
-    fields::{BitIterator, Field, PrimeField, SquareRootField},
+    fields::{batch_inversion, BitIterator, Field, FpParameters, PrimeField, SquareRootField},
 };
 
 #[cfg(feature = "parallel")]
@@ -35,7 +35,7 @@ use rayon::prelude::*;
     Debug(bound = "P: Parameters"),
     Hash(bound = "P: Parameters")
 )]
-#[derive(Serialize, Deserialize)]
+#[cfg_attr(not(feature = "compressed"), derive(Serialize, Deserialize))]
 pub struct GroupAffine<P: Parameters> {
     pub x: P::BaseField,
     pub y: P::BaseField,
@@ -44,16 +44,48 @@ pub struct GroupAffine<P: Parameters> {
     _params: PhantomData<P>,
 }
 
+/// Prints `(0x<x>, 0x<y>)`, or `inf` for the point at infinity. Each
+/// coordinate is the hex of its `ToBytes` encoding in reverse, that is, the
+/// big-endian integer for a prime field, with the highest coefficient first
+/// for an extension field.
 impl<P: Parameters> Display for GroupAffine<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         if self.infinity {
-            write!(f, "GroupAffine(Infinity)")
+            write!(f, "inf")
         } else {
-            write!(f, "GroupAffine(x={}, y={})", self.x, self.y)
+            write!(f, "(0x")?;
+            write_reversed_hex(f, &self.x)?;
+            write!(f, ", 0x")?;
+            write_reversed_hex(f, &self.y)?;
+            write!(f, ")")
         }
     }
 }
 
+/// Prints the hex of the compressed encoding, as written by
+/// `serialize_compressed`.
+impl<P: Parameters> LowerHex for GroupAffine<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let bytes = self.to_compressed_bytes().map_err(|_| FmtError)?;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        for byte in &bytes {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_reversed_hex<F: ToBytes>(f: &mut Formatter<'_>, value: &F) -> FmtResult {
+    let mut bytes = Vec::new();
+    value.write(&mut bytes).map_err(|_| FmtError)?;
+    for byte in bytes.iter().rev() {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
 impl<P: Parameters> GroupAffine<P> {
     pub fn new(x: P::BaseField, y: P::BaseField, infinity: bool) -> Self {
         Self {
@@ -110,8 +142,140 @@ impl<P: Parameters> GroupAffine<P> {
     }
 
     pub fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
-        self.mul_bits(BitIterator::new(P::ScalarField::characteristic()))
-            .is_zero()
+        P::is_in_correct_subgroup_assuming_on_curve(self)
+    }
+
+    /// Writes the compressed encoding of the point: the x-coordinate
+    /// big-endian, as by `to_bytes_be`, with flags in the top bits of the
+    /// first byte. Where the modulus of the base field leaves three of them
+    /// free, as for bls12_381, these are the `COMPRESSION_FLAG`, the
+    /// `INFINITY_FLAG` and the `SORT_FLAG` of the layout of ZCash and of the
+    /// IETF draft. Where it leaves two, as for bn_256, the sort flag takes
+    /// the place of the infinity flag, which is then set alone, as in gnark.
+    /// Where it leaves fewer, as for secp256k1, the three flags are written
+    /// in an extra leading byte. The point at infinity is encoded as `x = 0`.
+    pub fn serialize_compressed<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        writer.write_all(&self.to_compressed_bytes()?)
+    }
+
+    /// Reads a point written by `serialize_compressed`, recovering y from the
+    /// curve equation. Fails on non-canonical encodings and on x-coordinates
+    /// which are not on the curve. The point is not checked to be in the
+    /// prime order subgroup.
+    pub fn deserialize_compressed<R: Read>(mut reader: R) -> IoResult<Self> {
+        let mut bytes = vec![0u8; Self::compressed_size()];
+        reader.read_exact(&mut bytes)?;
+        Self::from_compressed_bytes(bytes)
+    }
+
+    /// The size in bytes of the compressed encoding.
+    pub fn compressed_size() -> usize {
+        if Self::spare_bits() >= 2 {
+            P::BaseField::SERIALIZED_SIZE
+        } else {
+            P::BaseField::SERIALIZED_SIZE + 1
+        }
+    }
+
+    /// The number of top bits of an encoded base field element which are
+    /// always zero.
+    fn spare_bits() -> u32 {
+        P::BaseField::characteristic()
+            .last()
+            .map_or(0, |limb| limb.leading_zeros())
+    }
+
+    /// The flags of the compressed encoding of the point at infinity, or of a
+    /// point of the largest y-coordinate or not.
+    fn compressed_flags(infinity: bool, largest: bool) -> u8 {
+        if Self::spare_bits() == 2 {
+            match (infinity, largest) {
+                (true, _) => INFINITY_FLAG,
+                (false, false) => COMPRESSION_FLAG,
+                (false, true) => COMPRESSION_FLAG | INFINITY_FLAG,
+            }
+        } else {
+            let mut flags = COMPRESSION_FLAG;
+            if infinity {
+                flags |= INFINITY_FLAG;
+            }
+            if largest {
+                flags |= SORT_FLAG;
+            }
+            flags
+        }
+    }
+
+    /// The bits of the first byte of a compressed point which hold flags.
+    fn compressed_flags_mask() -> u8 {
+        if Self::spare_bits() == 2 {
+            COMPRESSION_FLAG | INFINITY_FLAG
+        } else {
+            COMPRESSION_FLAG | INFINITY_FLAG | SORT_FLAG
+        }
+    }
+
+    fn to_compressed_bytes(&self) -> IoResult<Vec<u8>> {
+        let (x, flags) = if self.infinity {
+            (P::BaseField::zero(), Self::compressed_flags(true, false))
+        } else {
+            (self.x, Self::compressed_flags(false, self.y > -self.y))
+        };
+        let mut bytes = to_bytes_be(&x)?;
+        if Self::spare_bits() < 2 {
+            bytes.insert(0, 0);
+        }
+        debug_assert_eq!(bytes[0] & Self::compressed_flags_mask(), 0);
+        bytes[0] |= flags;
+        Ok(bytes)
+    }
+
+    fn from_compressed_bytes(mut bytes: Vec<u8>) -> IoResult<Self> {
+        let mask = Self::compressed_flags_mask();
+        let raw_flags = bytes[0] & mask;
+        bytes[0] &= !mask;
+        if Self::spare_bits() < 2 && bytes.remove(0) != 0 {
+            return Err(crate::error("invalid flags byte of a compressed point"));
+        }
+        let x: P::BaseField = from_bytes_be(&bytes)?;
+        if raw_flags == Self::compressed_flags(true, false) {
+            if x.is_zero() {
+                Ok(Self::zero())
+            } else {
+                Err(crate::error("invalid encoding of the point at infinity"))
+            }
+        } else if raw_flags == Self::compressed_flags(false, false)
+            || raw_flags == Self::compressed_flags(false, true)
+        {
+            let largest = raw_flags == Self::compressed_flags(false, true);
+            Self::get_point_from_x(x, largest)
+                .ok_or_else(|| crate::error("x-coordinate is not on the curve"))
+        } else {
+            Err(crate::error("invalid flags of a compressed point"))
+        }
+    }
+}
+
+#[cfg(feature = "compressed")]
+impl<P: Parameters> serde::Serialize for GroupAffine<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::Error;
+
+        let bytes = self
+            .to_compressed_bytes()
+            .map_err(|_| S::Error::custom("failed to encode point"))?;
+        crate::bytes::serialize_byte_tuple(&bytes, serializer)
+    }
+}
+
+#[cfg(feature = "compressed")]
+impl<'de, P: Parameters> serde::Deserialize<'de> for GroupAffine<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let bytes = crate::bytes::deserialize_byte_tuple(Self::compressed_size(), deserializer)?;
+        GroupAffine::from_compressed_bytes(bytes)
+            .map_err(|_| D::Error::custom("invalid compressed point"))
     }
 }
 
@@ -186,6 +350,82 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn clear_cofactor(&self) -> Self {
+        P::clear_cofactor(self).into()
+    }
+
+    fn is_on_curve(&self) -> bool {
+        GroupAffine::is_on_curve(self)
+    }
+
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
+        P::is_in_correct_subgroup_assuming_on_curve(self)
+    }
+
+    fn batch_add_in_place_affine(bases: &mut [Self], other: &[Self]) {
+        // Every addition needs a single inversion of its slope's denominator,
+        // these are shared with Montgomery's trick. A zero denominator marks
+        // the additions which need no inversion.
+        let mut denominators = bases
+            .iter()
+            .zip(other)
+            .map(|(a, b)| {
+                if a.is_zero() || b.is_zero() {
+                    P::BaseField::zero()
+                } else if a.x == b.x {
+                    if a.y == b.y {
+                        // Doubling, the denominator is zero iff a = -a.
+                        a.y.double()
+                    } else {
+                        // a = -b
+                        P::BaseField::zero()
+                    }
+                } else {
+                    b.x - &a.x
+                }
+            })
+            .collect::<Vec<_>>();
+        batch_inversion(&mut denominators);
+
+        for ((a, b), inv) in bases.iter_mut().zip(other).zip(denominators) {
+            if a.is_zero() {
+                *a = *b;
+            } else if b.is_zero() {
+                continue;
+            } else if inv.is_zero() {
+                *a = Self::zero();
+            } else {
+                let lambda = if a.x == b.x {
+                    // (3 * x^2 + a) / (2 * y)
+                    let x2 = a.x.square();
+                    (x2.double() + &x2 + &P::COEFF_A) * &inv
+                } else {
+                    // (y2 - y1) / (x2 - x1)
+                    (b.y - &a.y) * &inv
+                };
+                let x = lambda.square() - &a.x - &b.x;
+                let y = lambda * &(a.x - &x) - &a.y;
+                *a = Self::new(x, y, false);
+            }
+        }
+    }
+
+    fn glv_split(
+        &self,
+        scalar: &<P::ScalarField as PrimeField>::BigInt,
+    ) -> Option<[(Self, <P::ScalarField as PrimeField>::BigInt); 2]> {
+        let glv = P::glv()?;
+        if *scalar >= <P::ScalarField as PrimeField>::Params::MODULUS {
+            return None;
+        }
+
+        let ((neg1, k1), (neg2, k2)) = glv.decompose(&P::ScalarField::from_repr(*scalar));
+        let p1 = if neg1 { -*self } else { *self };
+        let p2 = Self::new(self.x * &glv.beta, self.y, self.infinity);
+        let p2 = if neg2 { -p2 } else { p2 };
+        Some([(p1, k1), (p2, k2)])
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -220,6 +460,10 @@ impl<P: Parameters> FromBytes for GroupAffine<P> {
     }
 }
 
+impl<P: Parameters> ConstSerializedSize for GroupAffine<P> {
+    const SERIALIZED_SIZE: usize = 2 * P::BaseField::SERIALIZED_SIZE + bool::SERIALIZED_SIZE;
+}
+
 impl<P: Parameters> Default for GroupAffine<P> {
     #[inline]
     fn default() -> Self {
@@ -235,7 +479,6 @@ impl<P: Parameters> Default for GroupAffine<P> {
     Debug(bound = "P: Parameters"),
     Hash(bound = "P: Parameters")
 )]
-#[derive(Serialize, Deserialize)]
 pub struct GroupProjective<P: Parameters> {
     pub x: P::BaseField,
     pub y: P::BaseField,
@@ -243,6 +486,21 @@ pub struct GroupProjective<P: Parameters> {
     _params: PhantomData<P>,
 }
 
+/// A projective point is serialized as its affine form, so the encoding is
+/// lossy w.r.t. the `z` coordinate: a deserialized point is equal to the
+/// original as a group element, but comes back normalized.
+impl<P: Parameters> serde::Serialize for GroupProjective<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&GroupAffine::from(*self), serializer)
+    }
+}
+
+impl<'de, P: Parameters> serde::Deserialize<'de> for GroupProjective<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <GroupAffine<P> as serde::Deserialize>::deserialize(deserializer).map(Self::from)
+    }
+}
+
 impl<P: Parameters> Display for GroupProjective<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", GroupAffine::from(*self))
@@ -318,6 +576,42 @@ impl<P: Parameters> GroupProjective<P> {
             _params: PhantomData,
         }
     }
+
+    /// Performs scalar multiplication with the GLV method: `scalar` is split
+    /// into two halves of about half the bit size, which are multiplied in a
+    /// single double-and-add pass with `self` and the image of `self` under
+    /// the endomorphism.
+    ///
+    /// Panics if the curve has no GLV endomorphism, see `Parameters::glv`.
+    pub fn mul_glv(&self, scalar: &P::ScalarField) -> Self {
+        let glv = P::glv().expect("the curve has no GLV endomorphism");
+        self.mul_glv_with(&glv, scalar)
+    }
+
+    fn mul_glv_with(&self, glv: &GLVParameters<P>, scalar: &P::ScalarField) -> Self {
+        let ((neg1, k1), (neg2, k2)) = glv.decompose(scalar);
+
+        // phi(X, Y, Z) = (beta * X, Y, Z), as x = X / Z^2.
+        let p1 = if neg1 { -*self } else { *self };
+        let p2 = Self::new(self.x * &glv.beta, self.y, self.z);
+        let p2 = if neg2 { -p2 } else { p2 };
+        let p1_p2 = p1 + &p2;
+
+        let mut res = Self::zero();
+        for (b1, b2) in BitIterator::new(k1)
+            .zip(BitIterator::new(k2))
+            .skip_while(|(b1, b2)| !b1 && !b2)
+        {
+            res.double_in_place();
+            match (b1, b2) {
+                (true, true) => res += &p1_p2,
+                (true, false) => res += &p1,
+                (false, true) => res += &p2,
+                (false, false) => {}
+            }
+        }
+        res
+    }
 }
 
 impl<P: Parameters> Zero for GroupProjective<P> {
@@ -548,6 +842,24 @@ impl<P: Parameters> ProjectiveCurve for GroupProjective<P> {
             self.z -= &hh;
         }
     }
+
+    fn mul<S: Into<<Self::ScalarField as PrimeField>::BigInt>>(self, other: S) -> Self {
+        let scalar = other.into();
+        if let Some(glv) = P::glv() {
+            if scalar < <P::ScalarField as PrimeField>::Params::MODULUS {
+                return self.mul_glv_with(&glv, &P::ScalarField::from_repr(scalar));
+            }
+        }
+
+        let mut res = Self::zero();
+        for i in BitIterator::new(scalar).skip_while(|b| !b) {
+            res.double_in_place();
+            if i {
+                res += &self;
+            }
+        }
+        res
+    }
 }
 
 impl<P: Parameters> Neg for GroupProjective<P> {
@@ -15,7 +15,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     curves::{
         flags::{Flags, SWFlags},
         AffineCurve, ProjectiveCurve,
@@ -184,6 +184,14 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn is_on_curve(&self) -> bool {
+        GroupAffine::is_on_curve(self)
+    }
+
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
+        GroupAffine::is_in_correct_subgroup_assuming_on_curve(self)
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -217,6 +225,10 @@ impl<P: Parameters> FromBytes for GroupAffine<P> {
     }
 }
 
+impl<P: Parameters> ConstSerializedSize for GroupAffine<P> {
+    const SERIALIZED_SIZE: usize = 2 * P::BaseField::SERIALIZED_SIZE + bool::SERIALIZED_SIZE;
+}
+
 impl<P: Parameters> Default for GroupAffine<P> {
     #[inline]
     fn default() -> Self {
@@ -232,7 +244,6 @@ impl<P: Parameters> Default for GroupAffine<P> {
     Debug(bound = "P: Parameters"),
     Hash(bound = "P: Parameters")
 )]
-#[derive(Serialize, Deserialize)]
 pub struct GroupProjective<P: Parameters> {
     pub x: P::BaseField,
     pub y: P::BaseField,
@@ -240,6 +251,21 @@ pub struct GroupProjective<P: Parameters> {
     _params: PhantomData<P>,
 }
 
+/// A projective point is serialized as its affine form, so the encoding is
+/// lossy w.r.t. the `z` coordinate: a deserialized point is equal to the
+/// original as a group element, but comes back normalized.
+impl<P: Parameters> serde::Serialize for GroupProjective<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&GroupAffine::from(*self), serializer)
+    }
+}
+
+impl<'de, P: Parameters> serde::Deserialize<'de> for GroupProjective<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <GroupAffine<P> as serde::Deserialize>::deserialize(deserializer).map(Self::from)
+    }
+}
+
 impl<P: Parameters> Display for GroupProjective<P> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", GroupAffine::from(*self))
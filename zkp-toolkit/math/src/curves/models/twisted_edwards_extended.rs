@@ -14,7 +14,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     curves::{
         flags::{EdwardsFlags, Flags},
         models::{
@@ -115,6 +115,12 @@ impl<P: Parameters> GroupAffine<P> {
         self.mul_bits(BitIterator::new(P::ScalarField::characteristic()))
             .is_zero()
     }
+
+    /// Checks that the current point is on the elliptic curve and in the
+    /// prime order subgroup.
+    pub fn is_in_prime_subgroup(&self) -> bool {
+        self.is_on_curve() && self.is_in_correct_subgroup_assuming_on_curve()
+    }
 }
 
 impl<P: Parameters> Zero for GroupAffine<P> {
@@ -158,6 +164,14 @@ impl<P: Parameters> AffineCurve for GroupAffine<P> {
     fn mul_by_cofactor_inv(&self) -> Self {
         self.mul(P::COFACTOR_INV).into()
     }
+
+    fn is_on_curve(&self) -> bool {
+        GroupAffine::is_on_curve(self)
+    }
+
+    fn is_in_correct_subgroup_assuming_on_curve(&self) -> bool {
+        GroupAffine::is_in_correct_subgroup_assuming_on_curve(self)
+    }
 }
 
 impl<P: Parameters> Neg for GroupAffine<P> {
@@ -234,6 +248,10 @@ impl<P: Parameters> FromBytes for GroupAffine<P> {
     }
 }
 
+impl<P: Parameters> ConstSerializedSize for GroupAffine<P> {
+    const SERIALIZED_SIZE: usize = 2 * P::BaseField::SERIALIZED_SIZE;
+}
+
 impl<P: Parameters> Default for GroupAffine<P> {
     #[inline]
     fn default() -> Self {
@@ -4,7 +4,10 @@
 
 use crate::fft::{DensePolynomial, EvaluationDomain};
 use crate::{PrimeField, Vec};
+use core::fmt;
 use core::ops::{Add, AddAssign, Div, DivAssign, Index, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 /// Stores a polynomial in evaluation form.
 #[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
@@ -16,6 +19,19 @@ pub struct Evaluations<F: PrimeField> {
     domain: EvaluationDomain<F>,
 }
 
+/// The error of the pointwise arithmetic of evaluations over different
+/// domains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DomainMismatch;
+
+impl fmt::Display for DomainMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("domains are unequal")
+    }
+}
+
+impl crate::Error for DomainMismatch {}
+
 impl<F: PrimeField> crate::ToBytes for Evaluations<F> {
     #[inline]
     fn write<W: crate::io::Write>(&self, mut w: W) -> crate::io::Result<()> {
@@ -30,6 +46,21 @@ impl<F: PrimeField> Evaluations<F> {
         Self { evals, domain }
     }
 
+    /// Evaluate `poly` over `domain`. The degree of `poly` must be smaller
+    /// than the size of `domain`.
+    pub fn from_poly(poly: &DensePolynomial<F>, domain: EvaluationDomain<F>) -> Self {
+        assert!(
+            poly.coeffs.len() <= domain.size(),
+            "polynomial degree exceeds the domain size"
+        );
+        poly.evaluate_over_domain_by_ref(domain)
+    }
+
+    /// The domain these evaluations are taken over.
+    pub fn domain(&self) -> EvaluationDomain<F> {
+        self.domain
+    }
+
     /// Interpolate a polynomial from a list of evaluations
     pub fn interpolate_by_ref(&self) -> DensePolynomial<F> {
         DensePolynomial::from_coefficients_vec(self.domain.ifft(&self.evals))
@@ -41,6 +72,52 @@ impl<F: PrimeField> Evaluations<F> {
         domain.ifft_in_place(&mut evals);
         DensePolynomial::from_coefficients_vec(evals)
     }
+
+    /// `self * other`, or an error if they are over different domains, where
+    /// the `Mul` operator panics.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, DomainMismatch> {
+        let mut result = self.clone();
+        result.zip_with(other, |a, b| *a *= b)?;
+        Ok(result)
+    }
+
+    /// `self + other`, or an error if they are over different domains, where
+    /// the `Add` operator panics.
+    pub fn checked_add(&self, other: &Self) -> Result<Self, DomainMismatch> {
+        let mut result = self.clone();
+        result.zip_with(other, |a, b| *a += b)?;
+        Ok(result)
+    }
+
+    /// `self - other`, or an error if they are over different domains, where
+    /// the `Sub` operator panics.
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, DomainMismatch> {
+        let mut result = self.clone();
+        result.zip_with(other, |a, b| *a -= b)?;
+        Ok(result)
+    }
+
+    /// `self / other`, or an error if they are over different domains, where
+    /// the `Div` operator panics.
+    pub fn checked_div(&self, other: &Self) -> Result<Self, DomainMismatch> {
+        let mut result = self.clone();
+        result.zip_with(other, |a, b| *a /= b)?;
+        Ok(result)
+    }
+
+    /// Applies `op` to the pairs of evaluations at the same points.
+    fn zip_with<O>(&mut self, other: &Self, op: O) -> Result<(), DomainMismatch>
+    where
+        O: Fn(&mut F, &F) + Send + Sync,
+    {
+        if self.domain != other.domain {
+            return Err(DomainMismatch);
+        }
+        cfg_iter_mut!(self.evals)
+            .zip(&other.evals)
+            .for_each(|(a, b)| op(a, b));
+        Ok(())
+    }
 }
 
 impl<F: PrimeField> Index<usize> for Evaluations<F> {
@@ -62,14 +139,21 @@ impl<'a, 'b, F: PrimeField> Mul<&'a Evaluations<F>> for &'b Evaluations<F> {
     }
 }
 
+impl<'a, F: PrimeField> Mul<&'a Evaluations<F>> for Evaluations<F> {
+    type Output = Evaluations<F>;
+
+    #[inline]
+    fn mul(mut self, other: &'a Evaluations<F>) -> Evaluations<F> {
+        self *= other;
+        self
+    }
+}
+
 impl<'a, F: PrimeField> MulAssign<&'a Evaluations<F>> for Evaluations<F> {
     #[inline]
     fn mul_assign(&mut self, other: &'a Evaluations<F>) {
-        assert_eq!(self.domain, other.domain, "domains are unequal");
-        self.evals
-            .iter_mut()
-            .zip(&other.evals)
-            .for_each(|(a, b)| *a *= b);
+        self.zip_with(other, |a, b| *a *= b)
+            .unwrap_or_else(|e| panic!("{}", e));
     }
 }
 
@@ -84,14 +168,21 @@ impl<'a, 'b, F: PrimeField> Add<&'a Evaluations<F>> for &'b Evaluations<F> {
     }
 }
 
+impl<'a, F: PrimeField> Add<&'a Evaluations<F>> for Evaluations<F> {
+    type Output = Evaluations<F>;
+
+    #[inline]
+    fn add(mut self, other: &'a Evaluations<F>) -> Evaluations<F> {
+        self += other;
+        self
+    }
+}
+
 impl<'a, F: PrimeField> AddAssign<&'a Evaluations<F>> for Evaluations<F> {
     #[inline]
     fn add_assign(&mut self, other: &'a Evaluations<F>) {
-        assert_eq!(self.domain, other.domain, "domains are unequal");
-        self.evals
-            .iter_mut()
-            .zip(&other.evals)
-            .for_each(|(a, b)| *a += b);
+        self.zip_with(other, |a, b| *a += b)
+            .unwrap_or_else(|e| panic!("{}", e));
     }
 }
 
@@ -106,14 +197,21 @@ impl<'a, 'b, F: PrimeField> Sub<&'a Evaluations<F>> for &'b Evaluations<F> {
     }
 }
 
+impl<'a, F: PrimeField> Sub<&'a Evaluations<F>> for Evaluations<F> {
+    type Output = Evaluations<F>;
+
+    #[inline]
+    fn sub(mut self, other: &'a Evaluations<F>) -> Evaluations<F> {
+        self -= other;
+        self
+    }
+}
+
 impl<'a, F: PrimeField> SubAssign<&'a Evaluations<F>> for Evaluations<F> {
     #[inline]
     fn sub_assign(&mut self, other: &'a Evaluations<F>) {
-        assert_eq!(self.domain, other.domain, "domains are unequal");
-        self.evals
-            .iter_mut()
-            .zip(&other.evals)
-            .for_each(|(a, b)| *a -= b);
+        self.zip_with(other, |a, b| *a -= b)
+            .unwrap_or_else(|e| panic!("{}", e));
     }
 }
 
@@ -128,13 +226,20 @@ impl<'a, 'b, F: PrimeField> Div<&'a Evaluations<F>> for &'b Evaluations<F> {
     }
 }
 
+impl<'a, F: PrimeField> Div<&'a Evaluations<F>> for Evaluations<F> {
+    type Output = Evaluations<F>;
+
+    #[inline]
+    fn div(mut self, other: &'a Evaluations<F>) -> Evaluations<F> {
+        self /= other;
+        self
+    }
+}
+
 impl<'a, F: PrimeField> DivAssign<&'a Evaluations<F>> for Evaluations<F> {
     #[inline]
     fn div_assign(&mut self, other: &'a Evaluations<F>) {
-        assert_eq!(self.domain, other.domain, "domains are unequal");
-        self.evals
-            .iter_mut()
-            .zip(&other.evals)
-            .for_each(|(a, b)| *a /= b);
+        self.zip_with(other, |a, b| *a /= b)
+            .unwrap_or_else(|e| panic!("{}", e));
     }
 }
@@ -4,5 +4,5 @@ pub mod evaluations;
 pub mod polynomial;
 
 pub use domain::EvaluationDomain;
-pub use evaluations::Evaluations;
+pub use evaluations::{DomainMismatch, Evaluations};
 pub use polynomial::{DenseOrSparsePolynomial, DensePolynomial, SparsePolynomial};
@@ -14,6 +14,14 @@ use crate::{Field, PrimeField, Vec};
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+/// The number of points from which `DensePolynomial::batch_evaluate` switches
+/// from per-point Horner evaluation to subproduct-tree evaluation.
+pub const BATCH_EVALUATION_THRESHOLD: usize = 64;
+
+/// Below this number of coefficients, polynomial products and divisions in the
+/// subproduct tree are done naively instead of through FFTs.
+const NAIVE_ARITHMETIC_THRESHOLD: usize = 64;
+
 /// Stores a polynomial in coefficient form.
 #[derive(Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct DensePolynomial<F> {
@@ -187,6 +195,157 @@ impl<F: PrimeField> DensePolynomial<F> {
     }
 }
 
+impl<F: PrimeField> DensePolynomial<F> {
+    /// Evaluates `self` at each of the given `points`.
+    ///
+    /// With fewer than `BATCH_EVALUATION_THRESHOLD` points this uses Horner's
+    /// rule per point. Otherwise a subproduct tree is built over the points and
+    /// `self` is reduced modulo each of its nodes, which costs `O(M(n) log n)`
+    /// instead of `O(n * k)`.
+    pub fn batch_evaluate(&self, points: &[F]) -> Vec<F> {
+        if self.is_zero() {
+            return vec![F::zero(); points.len()];
+        }
+        if points.len() < BATCH_EVALUATION_THRESHOLD {
+            return points.iter().map(|p| self.horner(p)).collect();
+        }
+
+        let tree = subproduct_tree(points);
+        let root = tree.len() - 1;
+        let remainder = self.remainder(&tree[root][0]);
+        let mut evals = Vec::with_capacity(points.len());
+        remainder_tree_evaluate(&tree, points, root, 0, remainder, &mut evals);
+        evals
+    }
+
+    /// Evaluates `self` at `point` using Horner's rule.
+    fn horner(&self, point: &F) -> F {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(F::zero(), |acc, coeff| acc * point + coeff)
+    }
+
+    /// Computes `self mod divisor`, through Newton iteration when both the
+    /// divisor and the quotient are large.
+    fn remainder(&self, divisor: &Self) -> Self {
+        if self.degree() < divisor.degree() {
+            return self.clone();
+        }
+        let quotient_len = self.degree() - divisor.degree() + 1;
+        if divisor.coeffs.len() < NAIVE_ARITHMETIC_THRESHOLD
+            || quotient_len < NAIVE_ARITHMETIC_THRESHOLD
+        {
+            let dividend: DenseOrSparsePolynomial<F> = self.into();
+            return dividend
+                .divide_with_q_and_r(&divisor.into())
+                .expect("division failed")
+                .1;
+        }
+
+        // rev(q) = rev(self) * rev(divisor)^-1 mod x^quotient_len
+        let mut rev_self = self.coeffs.clone();
+        rev_self.reverse();
+        let mut rev_divisor = divisor.coeffs.clone();
+        rev_divisor.reverse();
+        let rev_divisor = Self::from_coefficients_vec(rev_divisor);
+        let rev_divisor_inv = rev_divisor.inverse_mod_x_pow(quotient_len);
+
+        let mut rev_self = Self::from_coefficients_vec(rev_self);
+        rev_self.truncate_mod_x_pow(quotient_len);
+        let mut quotient = &rev_self * &rev_divisor_inv;
+        quotient.coeffs.resize(quotient_len, F::zero());
+        quotient.coeffs.reverse();
+        quotient.truncate_leading_zeros();
+
+        self - &(&quotient * divisor)
+    }
+
+    /// Computes the inverse of `self` modulo `x^k` by Newton iteration.
+    /// `self` must have a non-zero constant coefficient.
+    fn inverse_mod_x_pow(&self, k: usize) -> Self {
+        let two = F::one().double();
+        let mut inverse = Self::from_coefficients_vec(vec![self.coeffs[0]
+            .inverse()
+            .expect("constant coefficient must be non-zero")]);
+        let mut precision = 1;
+        while precision < k {
+            precision = core::cmp::min(2 * precision, k);
+            let mut truncated = self.clone();
+            truncated.truncate_mod_x_pow(precision);
+            // inverse = inverse * (2 - self * inverse) mod x^precision
+            let mut error = &truncated * &inverse;
+            error.truncate_mod_x_pow(precision);
+            let mut correction = -error;
+            if correction.coeffs.is_empty() {
+                correction.coeffs.push(F::zero());
+            }
+            correction.coeffs[0] += &two;
+            correction.truncate_leading_zeros();
+            inverse = &inverse * &correction;
+            inverse.truncate_mod_x_pow(precision);
+        }
+        inverse
+    }
+
+    fn truncate_mod_x_pow(&mut self, k: usize) {
+        self.coeffs.truncate(k);
+        self.truncate_leading_zeros();
+    }
+
+    fn product(&self, other: &Self) -> Self {
+        if self.coeffs.len() < NAIVE_ARITHMETIC_THRESHOLD
+            || other.coeffs.len() < NAIVE_ARITHMETIC_THRESHOLD
+        {
+            self.naive_mul(other)
+        } else {
+            self * other
+        }
+    }
+}
+
+/// Builds the subproduct tree of `points`: level 0 holds the linear factors
+/// `x - p` and each following level holds the products of adjacent pairs of
+/// the level below, until a single root remains.
+fn subproduct_tree<F: PrimeField>(points: &[F]) -> Vec<Vec<DensePolynomial<F>>> {
+    let leaves = points
+        .iter()
+        .map(|p| DensePolynomial::from_coefficients_vec(vec![-*p, F::one()]))
+        .collect::<Vec<_>>();
+    let mut tree = vec![leaves];
+    while tree.last().unwrap().len() > 1 {
+        let level = cfg_chunks!(tree.last().unwrap(), 2)
+            .map(|pair| match pair {
+                [left, right] => left.product(right),
+                [single] => single.clone(),
+                _ => unreachable!(),
+            })
+            .collect();
+        tree.push(level);
+    }
+    tree
+}
+
+/// Walks down the subproduct tree from `(level, index)`, reducing `remainder`
+/// modulo each child, and pushes the evaluations at the covered points in order.
+fn remainder_tree_evaluate<F: PrimeField>(
+    tree: &[Vec<DensePolynomial<F>>],
+    points: &[F],
+    level: usize,
+    index: usize,
+    remainder: DensePolynomial<F>,
+    evals: &mut Vec<F>,
+) {
+    if level == 0 {
+        evals.push(remainder.horner(&points[index]));
+        return;
+    }
+    for child in 2 * index..core::cmp::min(2 * index + 2, tree[level - 1].len()) {
+        let child_remainder = remainder.remainder(&tree[level - 1][child]);
+        remainder_tree_evaluate(tree, points, level - 1, child, child_remainder, evals);
+    }
+}
+
 impl<'a, 'b, F: Field> Add<&'a DensePolynomial<F>> for &'b DensePolynomial<F> {
     type Output = DensePolynomial<F>;
 
@@ -12,7 +12,7 @@ use DenseOrSparsePolynomial::*;
 mod dense;
 mod sparse;
 
-pub use dense::DensePolynomial;
+pub use dense::{DensePolynomial, BATCH_EVALUATION_THRESHOLD};
 pub use sparse::SparsePolynomial;
 
 /// Represents either a sparse polynomial or a dense one.
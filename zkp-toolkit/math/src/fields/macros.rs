@@ -12,7 +12,6 @@ macro_rules! impl_Fp {
             PartialEq(bound = ""),
             Eq(bound = ""),
         )]
-        #[derive(Serialize, Deserialize)]
         pub struct $Fp<P>(
             pub BigInteger,
             #[derivative(Debug = "ignore")]
@@ -75,10 +74,16 @@ macro_rules! impl_Fp {
 
             #[inline]
             fn double_in_place(&mut self) -> &mut Self {
-                // This cannot exceed the backing capacity.
+                // The top bit is only set for moduli which use the full
+                // width of the representation.
+                let carry = (self.0).0[$limbs - 1] >> 63 != 0;
                 self.0.mul2();
-                // However, it may need to be reduced.
-                self.reduce();
+                if carry {
+                    // The result overflowed the limbs, it is below 2 * MODULUS.
+                    self.0.sub_noborrow(&P::MODULUS);
+                } else {
+                    self.reduce();
+                }
                 self
             }
 
@@ -97,7 +102,7 @@ macro_rules! impl_Fp {
                 let mask: u64 = 0xffffffffffffffff >> P::REPR_SHAVE_BITS;
                 // the flags will be at the same byte with the lowest shaven bits or the one after
                 let flags_byte_position: usize = 7 - P::REPR_SHAVE_BITS as usize / 8;
-                let flags_mask: u8 = ((1 << P::REPR_SHAVE_BITS % 8) - 1) << (8 - P::REPR_SHAVE_BITS % 8);
+                let flags_mask: u8 = 0xffu8.checked_shl(8 - P::REPR_SHAVE_BITS % 8).unwrap_or(0);
                 // take the last 8 bytes and pass the mask
                 let last_bytes = &mut result_bytes[($limbs - 1) * 8..];
                 let mut flags: u8 = 0;
@@ -144,8 +149,12 @@ macro_rules! impl_Fp {
                             if b.0.is_even() {
                                 b.0.div2();
                             } else {
-                                b.0.add_nocarry(&P::MODULUS);
+                                // The carry of a full width modulus is shifted back in.
+                                let carry = b.0.add_nocarry(&P::MODULUS);
                                 b.0.div2();
+                                if carry {
+                                    (b.0).0[$limbs - 1] |= 1 << 63;
+                                }
                             }
                         }
 
@@ -155,8 +164,12 @@ macro_rules! impl_Fp {
                             if c.0.is_even() {
                                 c.0.div2();
                             } else {
-                                c.0.add_nocarry(&P::MODULUS);
+                                // The carry of a full width modulus is shifted back in.
+                                let carry = c.0.add_nocarry(&P::MODULUS);
                                 c.0.div2();
+                                if carry {
+                                    (c.0).0[$limbs - 1] |= 1 << 63;
+                                }
                             }
                         }
 
@@ -295,6 +308,28 @@ macro_rules! impl_Fp {
             }
         }
 
+        impl<P: $FpParameters> ConstSerializedSize for $Fp<P> {
+            const SERIALIZED_SIZE: usize = <BigInteger as ConstSerializedSize>::SERIALIZED_SIZE;
+        }
+
+        impl<P: $FpParameters> serde::Serialize for $Fp<P> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                crate::bytes::serialize_prime_field(self, stringify!($Fp), &self.0, serializer)
+            }
+        }
+
+        impl<'de, P: $FpParameters> serde::Deserialize<'de> for $Fp<P> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                crate::bytes::deserialize_prime_field(
+                    stringify!($Fp),
+                    |limbs| Some($Fp::new(limbs)).filter($Fp::is_valid),
+                    deserializer,
+                )
+            }
+        }
+
+        const _: () = assert!(<BigInteger as ConstSerializedSize>::SERIALIZED_SIZE == $limbs * 8);
+
         impl<P: $FpParameters> FromStr for $Fp<P> {
             type Err = ();
 
@@ -416,10 +451,12 @@ macro_rules! impl_Fp {
         impl<'a, P: $FpParameters> AddAssign<&'a Self> for $Fp<P> {
             #[inline]
             fn add_assign(&mut self, other: &Self) {
-                // This cannot exceed the backing capacity.
-                self.0.add_nocarry(&other.0);
-                // However, it may need to be reduced
-                self.reduce();
+                if self.0.add_nocarry(&other.0) {
+                    // The result overflowed the limbs, it is below 2 * MODULUS.
+                    self.0.sub_noborrow(&P::MODULUS);
+                } else {
+                    self.reduce();
+                }
             }
         }
 
@@ -497,7 +534,7 @@ macro_rules! impl_field_mul_assign {
                     r[$limbs + i] = carry;
                 }
                 // Montgomery reduction
-                let mut _carry2 = 0;
+                let mut carry2 = 0;
                 for i in 0..$limbs {
                     let k = r[i].wrapping_mul(P::INV);
                     let mut carry = 0;
@@ -505,11 +542,16 @@ macro_rules! impl_field_mul_assign {
                     for j in 1..$limbs {
                         r[j + i] = fa::mac_with_carry(r[j + i], k, P::MODULUS.0[j], &mut carry);
                     }
-                    r[$limbs + i] = fa::adc(r[$limbs + i], _carry2, &mut carry);
-                    _carry2 = carry;
+                    r[$limbs + i] = fa::adc(r[$limbs + i], carry2, &mut carry);
+                    carry2 = carry;
                 }
                 (self.0).0.copy_from_slice(&r[$limbs..]);
-                self.reduce();
+                if carry2 != 0 {
+                    // The result overflowed the limbs, it is below 2 * MODULUS.
+                    self.0.sub_noborrow(&P::MODULUS);
+                } else {
+                    self.reduce();
+                }
             }
         }
     };
@@ -579,7 +621,7 @@ macro_rules! impl_field_square_in_place {
                 r[2 * i + 1] = fa::adc(r[2 * i + 1], 0, &mut carry);
             }
             // Montgomery reduction
-            let mut _carry2 = 0;
+            let mut carry2 = 0;
             for i in 0..$limbs {
                 let k = r[i].wrapping_mul(P::INV);
                 let mut carry = 0;
@@ -587,12 +629,17 @@ macro_rules! impl_field_square_in_place {
                 for j in 1..$limbs {
                     r[j + i] = fa::mac_with_carry(r[j + i], k, P::MODULUS.0[j], &mut carry);
                 }
-                r[$limbs + i] = fa::adc(r[$limbs + i], _carry2, &mut carry);
-                _carry2 = carry;
+                r[$limbs + i] = fa::adc(r[$limbs + i], carry2, &mut carry);
+                carry2 = carry;
             }
 
             (self.0).0.copy_from_slice(&r[$limbs..]);
-            self.reduce();
+            if carry2 != 0 {
+                // The result overflowed the limbs, it is below 2 * MODULUS.
+                self.0.sub_noborrow(&P::MODULUS);
+            } else {
+                self.reduce();
+            }
             self
         }
     };
@@ -1,6 +1,6 @@
 use crate::{
     biginteger::BigInteger,
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     UniformRand, Vec,
 };
 use core::{
@@ -12,6 +12,9 @@ use core::{
 
 use num_traits::{One, Zero};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 #[macro_use]
 pub mod macros;
 pub mod models;
@@ -48,6 +51,7 @@ pub trait Field:
     'static
     + ToBytes
     + FromBytes
+    + ConstSerializedSize
     + Copy
     + Clone
     + Debug
@@ -363,3 +367,43 @@ pub fn batch_inversion<F: Field>(v: &mut [F]) {
         tmp = new_tmp;
     }
 }
+
+/// Raises each element of `bases` to the same power `exp`, given with `u64`
+/// limbs least significant limb first.
+///
+/// The bits of `exp` are scanned once for the whole batch: every step squares
+/// all the accumulators and multiplies in the bases when the bit is set.
+pub fn pow_many<F: Field>(bases: &[F], exp: &[u64]) -> Vec<F> {
+    let bits = BitIterator::new(exp)
+        .skip_while(|bit| !bit)
+        .collect::<Vec<_>>();
+
+    let mut results = bases.to_vec();
+    if bits.is_empty() {
+        results.iter_mut().for_each(|r| *r = F::one());
+        return results;
+    }
+
+    // The leading bit is set, so every accumulator starts from its base.
+    #[cfg(feature = "parallel")]
+    let chunk_size = core::cmp::max(1, bases.len() / rayon::current_num_threads());
+    #[cfg(not(feature = "parallel"))]
+    let chunk_size = core::cmp::max(1, bases.len());
+
+    cfg_chunks_mut!(results, chunk_size)
+        .zip(cfg_chunks!(bases, chunk_size))
+        .for_each(|(results, bases)| {
+            for bit in &bits[1..] {
+                results.iter_mut().for_each(|r| {
+                    r.square_in_place();
+                });
+                if *bit {
+                    results
+                        .iter_mut()
+                        .zip(bases)
+                        .for_each(|(r, base)| *r *= base);
+                }
+            }
+        });
+    results
+}
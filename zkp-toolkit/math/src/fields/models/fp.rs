@@ -0,0 +1,626 @@
+//! A prime field implementation generic over the number of limbs.
+//!
+//! `Fp<P, N>` implements the Montgomery arithmetic of the `FpN` macro types
+//! once for every limb count `N`, so generic code can be written over "any
+//! prime field with `N` limbs".
+
+use core::{
+    cmp::{Ord, Ordering, PartialOrd},
+    fmt::{Display, Formatter, Result as FmtResult},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+use num_traits::{One, Zero};
+
+use crate::{
+    biginteger::{arithmetic as fa, BigInt, BigInteger},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
+    fields::{Field, FpParameters, LegendreSymbol, PrimeField, SquareRootField},
+    io::{Read, Result as IoResult, Write},
+};
+
+/// A prime field element in Montgomery form, backed by `N` 64-bit limbs.
+#[derive(Derivative)]
+#[derivative(
+    Default(bound = ""),
+    Hash(bound = ""),
+    Clone(bound = ""),
+    Copy(bound = ""),
+    Debug(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+pub struct Fp<P: FpParameters<BigInt = BigInt<N>>, const N: usize>(
+    pub BigInt<N>,
+    #[derivative(Debug = "ignore")]
+    #[doc(hidden)]
+    pub PhantomData<P>,
+);
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Fp<P, N> {
+    #[inline]
+    pub const fn new(element: BigInt<N>) -> Self {
+        Self(element, PhantomData)
+    }
+
+    #[inline]
+    pub(crate) fn is_valid(&self) -> bool {
+        self.0 < P::MODULUS
+    }
+
+    #[inline]
+    fn reduce(&mut self) {
+        if !self.is_valid() {
+            self.0.sub_noborrow(&P::MODULUS);
+        }
+    }
+
+    /// Montgomery multiplication of `a` and `b` with the CIOS method, which
+    /// only needs `N + 2` words of scratch space for any modulus.
+    #[inline]
+    fn mont_mul(a: &BigInt<N>, b: &BigInt<N>) -> Self {
+        let (a, modulus) = (&a.0, &P::MODULUS.0);
+
+        let mut t = [0u64; N];
+        let mut t_n = 0u64;
+        for b_i in b.0.iter() {
+            let mut carry = 0u64;
+            for j in 0..N {
+                t[j] = fa::mac_with_carry(t[j], a[j], *b_i, &mut carry);
+            }
+            t_n = fa::adc(t_n, 0, &mut carry);
+            let t_n1 = carry;
+
+            let k = t[0].wrapping_mul(P::INV);
+            let mut carry = 0u64;
+            fa::mac_with_carry(t[0], k, modulus[0], &mut carry);
+            for j in 1..N {
+                t[j - 1] = fa::mac_with_carry(t[j], k, modulus[j], &mut carry);
+            }
+            t[N - 1] = fa::adc(t_n, 0, &mut carry);
+            t_n = t_n1 + carry;
+        }
+
+        let mut result = Self(BigInt(t), PhantomData);
+        if t_n != 0 {
+            // The result overflowed the limbs, it is below 2 * MODULUS.
+            result.0.sub_noborrow(&P::MODULUS);
+        } else {
+            result.reduce();
+        }
+        result
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Zero for Fp<P, N> {
+    #[inline]
+    fn zero() -> Self {
+        Self(BigInt::<N>::from(0), PhantomData)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> One for Fp<P, N> {
+    #[inline]
+    fn one() -> Self {
+        Self(P::R, PhantomData)
+    }
+
+    #[inline]
+    fn is_one(&self) -> bool {
+        self.0 == P::R
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Field for Fp<P, N> {
+    #[inline]
+    fn double(&self) -> Self {
+        let mut temp = *self;
+        temp.double_in_place();
+        temp
+    }
+
+    #[inline]
+    fn double_in_place(&mut self) -> &mut Self {
+        // The top bit is only set for moduli which use the full width of the
+        // representation.
+        let carry = self.0.as_ref()[N - 1] >> 63 != 0;
+        self.0.mul2();
+        if carry {
+            // The result overflowed the limbs, it is below 2 * MODULUS.
+            self.0.sub_noborrow(&P::MODULUS);
+        } else {
+            self.reduce();
+        }
+        self
+    }
+
+    #[inline]
+    fn characteristic<'a>() -> &'a [u64] {
+        P::MODULUS.as_ref()
+    }
+
+    #[inline]
+    fn from_random_bytes_with_flags(bytes: &[u8]) -> Option<(Self, u8)> {
+        let mut repr = BigInt::<N>::default();
+        for (limb, chunk) in repr.as_mut().iter_mut().zip(bytes.chunks(8)) {
+            let mut limb_bytes = [0u8; 8];
+            limb_bytes[..chunk.len()].copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(limb_bytes);
+        }
+
+        let mask: u64 = 0xffffffffffffffff >> P::REPR_SHAVE_BITS;
+        // the flags will be at the same byte with the lowest shaven bits or the one after
+        let flags_byte_position: usize = 7 - P::REPR_SHAVE_BITS as usize / 8;
+        let flags_mask: u8 = 0xffu8.checked_shl(8 - P::REPR_SHAVE_BITS % 8).unwrap_or(0);
+        let last_limb = &mut repr.as_mut()[N - 1];
+        let flags = last_limb.to_le_bytes()[flags_byte_position] & flags_mask;
+        *last_limb &= mask;
+
+        Self::from_checked_repr(repr).map(|f| (f, flags))
+    }
+
+    #[inline]
+    fn square(&self) -> Self {
+        let mut temp = *self;
+        temp.square_in_place();
+        temp
+    }
+
+    #[inline]
+    fn square_in_place(&mut self) -> &mut Self {
+        *self = Self::mont_mul(&self.0, &self.0);
+        self
+    }
+
+    #[inline]
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            // Guajardo Kumar Paar Pelzl
+            // Efficient Software-Implementation of Finite Fields with Applications to
+            // Cryptography
+            // Algorithm 16 (BEA for Inversion in Fp)
+
+            let one = BigInt::<N>::from(1);
+
+            let mut u = self.0;
+            let mut v = P::MODULUS;
+            let mut b = Self(P::R2, PhantomData); // Avoids unnecessary reduction step.
+            let mut c = Self::zero();
+
+            while u != one && v != one {
+                while u.is_even() {
+                    u.div2();
+
+                    if b.0.is_even() {
+                        b.0.div2();
+                    } else {
+                        // The carry of a full width modulus is shifted back in.
+                        let carry = b.0.add_nocarry(&P::MODULUS);
+                        b.0.div2();
+                        if carry {
+                            b.0.as_mut()[N - 1] |= 1 << 63;
+                        }
+                    }
+                }
+
+                while v.is_even() {
+                    v.div2();
+
+                    if c.0.is_even() {
+                        c.0.div2();
+                    } else {
+                        // The carry of a full width modulus is shifted back in.
+                        let carry = c.0.add_nocarry(&P::MODULUS);
+                        c.0.div2();
+                        if carry {
+                            c.0.as_mut()[N - 1] |= 1 << 63;
+                        }
+                    }
+                }
+
+                if v < u {
+                    u.sub_noborrow(&v);
+                    b.sub_assign(&c);
+                } else {
+                    v.sub_noborrow(&u);
+                    c.sub_assign(&b);
+                }
+            }
+
+            if u == one {
+                Some(b)
+            } else {
+                Some(c)
+            }
+        }
+    }
+
+    fn inverse_in_place(&mut self) -> Option<&mut Self> {
+        if let Some(inverse) = self.inverse() {
+            *self = inverse;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn frobenius_map(&mut self, _: usize) {
+        // No-op: No effect in a prime field.
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Fp<P, N> {
+    /// Converts a canonical representation into a field element, failing for
+    /// representations that are not reduced modulo `P::MODULUS`.
+    #[inline]
+    fn from_checked_repr(repr: BigInt<N>) -> Option<Self> {
+        if repr.is_zero() {
+            Some(Self::zero())
+        } else {
+            let f = Self::from_repr(repr);
+            if f.is_zero() {
+                None
+            } else {
+                Some(f)
+            }
+        }
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> PrimeField for Fp<P, N> {
+    type Params = P;
+    type BigInt = BigInt<N>;
+
+    #[inline]
+    fn from_repr(r: BigInt<N>) -> Self {
+        let mut r = Self(r, PhantomData);
+        if r.is_valid() {
+            r.mul_assign(&Self(P::R2, PhantomData));
+            r
+        } else {
+            Self::zero()
+        }
+    }
+
+    #[inline]
+    fn into_repr(&self) -> BigInt<N> {
+        // Montgomery reduction is a Montgomery multiplication by 1.
+        let one = BigInt::<N>::from(1);
+        Self::mont_mul(&self.0, &one).0
+    }
+
+    #[inline]
+    fn multiplicative_generator() -> Self {
+        Self(P::GENERATOR, PhantomData)
+    }
+
+    #[inline]
+    fn root_of_unity() -> Self {
+        Self(P::ROOT_OF_UNITY, PhantomData)
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> SquareRootField for Fp<P, N> {
+    #[inline]
+    fn legendre(&self) -> LegendreSymbol {
+        use crate::fields::LegendreSymbol::*;
+
+        // s = self^((MODULUS - 1) // 2)
+        let s = self.pow(P::MODULUS_MINUS_ONE_DIV_TWO);
+        if s.is_zero() {
+            Zero
+        } else if s.is_one() {
+            QuadraticResidue
+        } else {
+            QuadraticNonResidue
+        }
+    }
+
+    #[inline]
+    fn sqrt(&self) -> Option<Self> {
+        sqrt_impl!(Self, P, self)
+    }
+
+    fn sqrt_in_place(&mut self) -> Option<&mut Self> {
+        (*self).sqrt().map(|sqrt| {
+            *self = sqrt;
+            self
+        })
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Ord for Fp<P, N> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.into_repr().cmp(&other.into_repr())
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> PartialOrd for Fp<P, N> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> From<Fp<P, N>> for BigInt<N> {
+    fn from(f: Fp<P, N>) -> Self {
+        f.into_repr()
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> From<BigInt<N>> for Fp<P, N> {
+    fn from(int: BigInt<N>) -> Self {
+        Self::from_repr(int)
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> From<u128> for Fp<P, N> {
+    fn from(other: u128) -> Self {
+        let mut default_int = BigInt::<N>::default();
+        default_int.as_mut()[0] = other as u64;
+        default_int.as_mut()[1] = (other >> 64) as u64;
+        Self::from_repr(default_int)
+    }
+}
+
+macro_rules! impl_fp_from_int {
+    ($int: ident) => {
+        impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> From<$int> for Fp<P, N> {
+            fn from(other: $int) -> Self {
+                Self::from_repr(BigInt::<N>::from(u64::from(other)))
+            }
+        }
+    };
+}
+
+impl_fp_from_int!(u64);
+impl_fp_from_int!(u32);
+impl_fp_from_int!(u16);
+impl_fp_from_int!(u8);
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> rand::distributions::Distribution<Fp<P, N>>
+    for rand::distributions::Standard
+{
+    #[inline]
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Fp<P, N> {
+        loop {
+            let mut tmp = Fp(rng.sample(rand::distributions::Standard), PhantomData);
+            // Mask away the unused bits at the beginning.
+            tmp.0 .0[N - 1] &= u64::MAX >> P::REPR_SHAVE_BITS;
+
+            if tmp.is_valid() {
+                return tmp;
+            }
+        }
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> ToBytes for Fp<P, N> {
+    #[inline]
+    fn write<W: Write>(&self, writer: W) -> IoResult<()> {
+        self.into_repr().write(writer)
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> FromBytes for Fp<P, N> {
+    #[inline]
+    fn read<R: Read>(reader: R) -> IoResult<Self> {
+        BigInt::<N>::read(reader).and_then(|b| {
+            Self::from_checked_repr(b).ok_or_else(|| crate::error("FromBytes::read failed"))
+        })
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> ConstSerializedSize for Fp<P, N> {
+    const SERIALIZED_SIZE: usize = N * 8;
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> serde::Serialize for Fp<P, N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bytes::serialize_prime_field(self, "Fp", &self.0, serializer)
+    }
+}
+
+impl<'de, P: FpParameters<BigInt = BigInt<N>>, const N: usize> serde::Deserialize<'de>
+    for Fp<P, N>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bytes::deserialize_prime_field(
+            "Fp",
+            |limbs| Some(Fp::new(limbs)).filter(Fp::is_valid),
+            deserializer,
+        )
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> FromStr for Fp<P, N> {
+    type Err = ();
+
+    /// Interpret a string of numbers as a (congruent) prime field element.
+    /// Does not accept unnecessary leading zeroes or a blank string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(());
+        }
+
+        if s == "0" {
+            return Ok(Self::zero());
+        }
+
+        let mut res = Self::zero();
+
+        let ten = Self::from_repr(BigInt::<N>::from(10));
+
+        let mut first_digit = true;
+
+        for c in s.chars() {
+            match c.to_digit(10) {
+                Some(c) => {
+                    if first_digit {
+                        if c == 0 {
+                            return Err(());
+                        }
+
+                        first_digit = false;
+                    }
+
+                    res.mul_assign(&ten);
+                    res.add_assign(&Self::from_repr(BigInt::<N>::from(u64::from(c))));
+                }
+                None => {
+                    return Err(());
+                }
+            }
+        }
+        if !res.is_valid() {
+            Err(())
+        } else {
+            Ok(res)
+        }
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Display for Fp<P, N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Fp{}({})", N * 64, self.into_repr())
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> Neg for Fp<P, N> {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        if !self.is_zero() {
+            let mut tmp = P::MODULUS;
+            tmp.sub_noborrow(&self.0);
+            Self(tmp, PhantomData)
+        } else {
+            self
+        }
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> AddAssign<&Self> for Fp<P, N> {
+    #[inline]
+    fn add_assign(&mut self, other: &Self) {
+        if self.0.add_nocarry(&other.0) {
+            // The result overflowed the limbs, it is below 2 * MODULUS.
+            self.0.sub_noborrow(&P::MODULUS);
+        } else {
+            self.reduce();
+        }
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> SubAssign<&Self> for Fp<P, N> {
+    #[inline]
+    fn sub_assign(&mut self, other: &Self) {
+        // If `other` is larger than `self`, add the modulus to self first.
+        if other.0 > self.0 {
+            self.0.add_nocarry(&P::MODULUS);
+        }
+        self.0.sub_noborrow(&other.0);
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> MulAssign<&Self> for Fp<P, N> {
+    #[inline]
+    fn mul_assign(&mut self, other: &Self) {
+        *self = Self::mont_mul(&self.0, &other.0);
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> DivAssign<&Self> for Fp<P, N> {
+    #[inline]
+    fn div_assign(&mut self, other: &Self) {
+        self.mul_assign(&other.inverse().unwrap());
+    }
+}
+
+macro_rules! impl_fp_ops_from_ref {
+    ($Op: ident, $op: ident, $OpAssign: ident, $op_assign: ident) => {
+        impl<'a, P: FpParameters<BigInt = BigInt<N>>, const N: usize> $Op<&'a Self> for Fp<P, N> {
+            type Output = Self;
+
+            #[inline]
+            fn $op(mut self, other: &Self) -> Self {
+                self.$op_assign(other);
+                self
+            }
+        }
+
+        impl<'a, P: FpParameters<BigInt = BigInt<N>>, const N: usize> $Op<&'a mut Self> for Fp<P, N> {
+            type Output = Self;
+
+            #[inline]
+            fn $op(mut self, other: &'a mut Self) -> Self {
+                self.$op_assign(&*other);
+                self
+            }
+        }
+
+        impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> $Op<Self> for Fp<P, N> {
+            type Output = Self;
+
+            #[inline]
+            fn $op(mut self, other: Self) -> Self {
+                self.$op_assign(&other);
+                self
+            }
+        }
+
+        impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> $OpAssign<Self> for Fp<P, N> {
+            #[inline]
+            fn $op_assign(&mut self, other: Self) {
+                self.$op_assign(&other)
+            }
+        }
+
+        impl<'a, P: FpParameters<BigInt = BigInt<N>>, const N: usize> $OpAssign<&'a mut Self> for Fp<P, N> {
+            #[inline]
+            fn $op_assign(&mut self, other: &'a mut Self) {
+                self.$op_assign(&*other)
+            }
+        }
+    };
+}
+
+impl_fp_ops_from_ref!(Add, add, AddAssign, add_assign);
+impl_fp_ops_from_ref!(Sub, sub, SubAssign, sub_assign);
+impl_fp_ops_from_ref!(Mul, mul, MulAssign, mul_assign);
+impl_fp_ops_from_ref!(Div, div, DivAssign, div_assign);
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> core::iter::Sum<Self> for Fp<P, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a, P: FpParameters<BigInt = BigInt<N>>, const N: usize> core::iter::Sum<&'a Self> for Fp<P, N> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<P: FpParameters<BigInt = BigInt<N>>, const N: usize> core::iter::Product<Self> for Fp<P, N> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+impl<'a, P: FpParameters<BigInt = BigInt<N>>, const N: usize> core::iter::Product<&'a Self> for Fp<P, N> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
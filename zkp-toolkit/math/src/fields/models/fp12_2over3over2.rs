@@ -15,7 +15,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{fp6_3over2::*, Field, Fp2, Fp2Parameters},
     BitIterator,
 };
@@ -38,7 +38,6 @@ pub trait Fp12Parameters: 'static + Send + Sync + Copy {
     PartialEq(bound = "P: Fp12Parameters"),
     Eq(bound = "P: Fp12Parameters")
 )]
-#[derive(Serialize, Deserialize)]
 pub struct Fp12<P: Fp12Parameters> {
     pub c0: Fp6<P::Fp6Params>,
     pub c1: Fp6<P::Fp6Params>,
@@ -187,6 +186,23 @@ impl<P: Fp12Parameters> Fp12<P> {
         result
     }
 
+    /// Compresses an element of the cyclotomic subgroup, such as a pairing
+    /// output, to half its size by the torus map `c0 + c1 * w -> c1 / (1 + c0)`,
+    /// where `w^2` is the cubic nonresidue of Fp6.
+    /// Returns `None` for `-1`, the only element of norm one without an image.
+    pub fn compress(&self) -> Option<Fp6<P::Fp6Params>> {
+        (self.c0 + &Fp6::one()).inverse().map(|inv| self.c1 * &inv)
+    }
+
+    /// Recovers an element of the cyclotomic subgroup from its compressed
+    /// form `b` as `(1 + b * w) / (1 - b * w)`.
+    pub fn decompress(b: &Fp6<P::Fp6Params>) -> Self {
+        // b^2 * w^2 is never one since w^2 is not a square in Fp6.
+        let b2w2 = Self::mul_fp6_by_nonresidue(&b.square());
+        let inv = (Fp6::one() - &b2w2).inverse().unwrap();
+        Self::new((Fp6::one() + &b2w2) * &inv, b.double() * &inv)
+    }
+
     pub fn cyclotomic_exp<S: AsRef<[u64]>>(&self, exp: S) -> Self {
         let mut res = Self::one();
 
@@ -488,6 +504,11 @@ impl<P: Fp12Parameters> From<u8> for Fp12<P> {
     }
 }
 
+/// Writes `c0 || c1` in the `Fp6` layout, that is, the six `Fp2`
+/// coefficients in tower order `c0.c0, c0.c1, c0.c2, c1.c0, c1.c1, c1.c2`,
+/// each as two little-endian `Fp` elements. The serde encoding uses the same
+/// bytes, and the layout is stable: it is pinned by test vectors in the
+/// curve crate.
 impl<P: Fp12Parameters> ToBytes for Fp12<P> {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -504,3 +525,19 @@ impl<P: Fp12Parameters> FromBytes for Fp12<P> {
         Ok(Fp12::new(c0, c1))
     }
 }
+
+impl<P: Fp12Parameters> ConstSerializedSize for Fp12<P> {
+    const SERIALIZED_SIZE: usize = 2 * <Fp6<P::Fp6Params> as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
+impl<P: Fp12Parameters> serde::Serialize for Fp12<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bytes::serialize_as_bytes(self, serializer)
+    }
+}
+
+impl<'de, P: Fp12Parameters> serde::Deserialize<'de> for Fp12<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bytes::deserialize_from_bytes(deserializer)
+    }
+}
@@ -15,7 +15,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
 };
 
@@ -45,7 +45,6 @@ pub trait Fp2Parameters: 'static + Send + Sync {
     PartialEq(bound = "P: Fp2Parameters"),
     Eq(bound = "P: Fp2Parameters")
 )]
-#[derive(Serialize, Deserialize)]
 pub struct Fp2<P: Fp2Parameters> {
     pub c0: P::Fp,
     pub c1: P::Fp,
@@ -197,34 +196,55 @@ where
         self.norm().legendre()
     }
 
+    /// Returns a square root of `self`, if it exists.
+    ///
+    /// Of the two roots `r` and `-r`, the smaller one under the lexicographic
+    /// ordering of `Fp2` is returned, so the result is canonical.
     fn sqrt(&self) -> Option<Self> {
         use crate::LegendreSymbol::*;
-        if self.c1.is_zero() {
-            return self.c0.sqrt().map(|c0| Self::new(c0, P::Fp::zero()));
-        }
-        match self.legendre() {
-            // Square root based on the complex method. See
-            // https://eprint.iacr.org/2012/685.pdf (page 15, algorithm 8)
-            Zero => Some(*self),
-            QuadraticNonResidue => None,
-            QuadraticResidue => {
-                let two_inv = P::Fp::one()
-                    .double()
-                    .inverse()
-                    .expect("Two should always have an inverse");
-                let alpha = self
-                    .norm()
-                    .sqrt()
-                    .expect("We are in the QR case, the norm should have a square root");
-                let mut delta = (alpha + &self.c0) * &two_inv;
-                if delta.legendre().is_qnr() {
-                    delta -= &alpha;
+        let root = if self.c1.is_zero() {
+            // Every element of the base field is a square in `Fp2`: either
+            // `c0` is already a square in `Fp`, or `c0 / NONRESIDUE` is and
+            // the root lies on the `u` axis.
+            match self.c0.sqrt() {
+                Some(c0) => Self::new(c0, P::Fp::zero()),
+                None => {
+                    let nonresidue_inv = P::NONRESIDUE
+                        .inverse()
+                        .expect("NONRESIDUE should always have an inverse");
+                    let c1 = (self.c0 * &nonresidue_inv)
+                        .sqrt()
+                        .expect("c0 / NONRESIDUE must have a square root");
+                    Self::new(P::Fp::zero(), c1)
                 }
-                let c0 = delta.sqrt().expect("Delta must have a square root");
-                let c0_inv = c0.inverse().expect("c0 must have an inverse");
-                Some(Self::new(c0, self.c1 * &two_inv * &c0_inv))
             }
-        }
+        } else {
+            match self.legendre() {
+                // Square root based on the complex method. See
+                // https://eprint.iacr.org/2012/685.pdf (page 15, algorithm 8)
+                Zero => return Some(*self),
+                QuadraticNonResidue => return None,
+                QuadraticResidue => {
+                    let two_inv = P::Fp::one()
+                        .double()
+                        .inverse()
+                        .expect("Two should always have an inverse");
+                    let alpha = self
+                        .norm()
+                        .sqrt()
+                        .expect("We are in the QR case, the norm should have a square root");
+                    let mut delta = (alpha + &self.c0) * &two_inv;
+                    if delta.legendre().is_qnr() {
+                        delta -= &alpha;
+                    }
+                    let c0 = delta.sqrt().expect("Delta must have a square root");
+                    let c0_inv = c0.inverse().expect("c0 must have an inverse");
+                    Self::new(c0, self.c1 * &two_inv * &c0_inv)
+                }
+            }
+        };
+        let neg_root = -root;
+        Some(if root <= neg_root { root } else { neg_root })
     }
 
     fn sqrt_in_place(&mut self) -> Option<&mut Self> {
@@ -284,6 +304,9 @@ impl<P: Fp2Parameters> From<u8> for Fp2<P> {
     }
 }
 
+/// Writes `c0 || c1`, each coefficient as its canonical (non-Montgomery)
+/// integer in little-endian 64-bit limbs. The serde encoding uses the same
+/// bytes.
 impl<P: Fp2Parameters> ToBytes for Fp2<P> {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -301,6 +324,22 @@ impl<P: Fp2Parameters> FromBytes for Fp2<P> {
     }
 }
 
+impl<P: Fp2Parameters> ConstSerializedSize for Fp2<P> {
+    const SERIALIZED_SIZE: usize = 2 * <P::Fp as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
+impl<P: Fp2Parameters> serde::Serialize for Fp2<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bytes::serialize_as_bytes(self, serializer)
+    }
+}
+
+impl<'de, P: Fp2Parameters> serde::Deserialize<'de> for Fp2<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bytes::deserialize_from_bytes(deserializer)
+    }
+}
+
 impl<P: Fp2Parameters> Neg for Fp2<P> {
     type Output = Self;
     #[inline]
@@ -13,7 +13,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{Field, LegendreSymbol, PrimeField, SquareRootField},
     io::{Read, Result as IoResult, Write},
 };
@@ -354,6 +354,10 @@ impl<P: Fp3Parameters> FromBytes for Fp3<P> {
     }
 }
 
+impl<P: Fp3Parameters> ConstSerializedSize for Fp3<P> {
+    const SERIALIZED_SIZE: usize = 3 * <P::Fp as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
 impl<P: Fp3Parameters> Neg for Fp3<P> {
     type Output = Self;
     #[inline]
@@ -15,7 +15,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{Field, Fp2, Fp2Parameters},
 };
 
@@ -290,6 +290,10 @@ impl<P: Fp4Parameters> FromBytes for Fp4<P> {
     }
 }
 
+impl<P: Fp4Parameters> ConstSerializedSize for Fp4<P> {
+    const SERIALIZED_SIZE: usize = 2 * <Fp2<P::Fp2Params> as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
 impl<P: Fp4Parameters> Neg for Fp4<P> {
     type Output = Self;
     #[inline]
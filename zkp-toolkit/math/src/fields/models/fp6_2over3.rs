@@ -13,7 +13,7 @@ use rand::{
 
 use crate::{
     biginteger::BigInteger,
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{Field, Fp3, Fp3Parameters},
     io::{Read, Result as IoResult, Write},
 };
@@ -51,6 +51,8 @@ pub struct Fp6<P: Fp6Parameters> {
     pub _parameters: PhantomData<P>,
 }
 
+type Fp3Fp<P> = <<P as Fp6Parameters>::Fp3Params as Fp3Parameters>::Fp;
+
 impl<P: Fp6Parameters> Fp6<P> {
     pub fn new(c0: Fp3<P::Fp3Params>, c1: Fp3<P::Fp3Params>) -> Self {
         Fp6 {
@@ -75,6 +77,56 @@ impl<P: Fp6Parameters> Fp6<P> {
         Self::new(self.c0, -self.c1)
     }
 
+    /// Multiplies by the sparse element `c0 + c1 * u + c4 * u * v`, where
+    /// `u` generates Fp3 and `v^2 = u`.
+    pub fn mul_by_014(&mut self, c0: &Fp3Fp<P>, c1: &Fp3Fp<P>, c4: &Fp3Fp<P>) {
+        let z0 = self.c0.c0;
+        let z1 = self.c0.c1;
+        let z2 = self.c0.c2;
+        let z3 = self.c1.c0;
+        let z4 = self.c1.c1;
+        let z5 = self.c1.c2;
+
+        let x0 = *c0;
+        let x1 = *c1;
+        let x4 = *c4;
+
+        let nr_x1 = P::Fp3Params::mul_fp_by_nonresidue(&x1);
+        let nr_x4 = P::Fp3Params::mul_fp_by_nonresidue(&x4);
+
+        self.c0.c0 = x0 * &z0 + &(nr_x1 * &z2) + &(nr_x4 * &z4);
+        self.c0.c1 = x0 * &z1 + &(x1 * &z0) + &(nr_x4 * &z5);
+        self.c0.c2 = x0 * &z2 + &(x1 * &z1) + &(x4 * &z3);
+        self.c1.c0 = x0 * &z3 + &(nr_x1 * &z5) + &(nr_x4 * &z2);
+        self.c1.c1 = x0 * &z4 + &(x1 * &z3) + &(x4 * &z0);
+        self.c1.c2 = x0 * &z5 + &(x1 * &z4) + &(x4 * &z1);
+    }
+
+    /// Multiplies by the sparse element `c0 + c3 * v + c4 * u * v`, where
+    /// `u` generates Fp3 and `v^2 = u`.
+    pub fn mul_by_034(&mut self, c0: &Fp3Fp<P>, c3: &Fp3Fp<P>, c4: &Fp3Fp<P>) {
+        let z0 = self.c0.c0;
+        let z1 = self.c0.c1;
+        let z2 = self.c0.c2;
+        let z3 = self.c1.c0;
+        let z4 = self.c1.c1;
+        let z5 = self.c1.c2;
+
+        let x0 = *c0;
+        let x3 = *c3;
+        let x4 = *c4;
+
+        let nr_x3 = P::Fp3Params::mul_fp_by_nonresidue(&x3);
+        let nr_x4 = P::Fp3Params::mul_fp_by_nonresidue(&x4);
+
+        self.c0.c0 = x0 * &z0 + &(nr_x3 * &z5) + &(nr_x4 * &z4);
+        self.c0.c1 = x0 * &z1 + &(x3 * &z3) + &(nr_x4 * &z5);
+        self.c0.c2 = x0 * &z2 + &(x3 * &z4) + &(x4 * &z3);
+        self.c1.c0 = x0 * &z3 + &(x3 * &z0) + &(nr_x4 * &z2);
+        self.c1.c1 = x0 * &z4 + &(x3 * &z1) + &(x4 * &z0);
+        self.c1.c2 = x0 * &z5 + &(x3 * &z2) + &(x4 * &z1);
+    }
+
     pub fn cyclotomic_exp<B: BigInteger>(&self, exponent: &B) -> Self {
         let mut res = Self::one();
         let self_inverse = self.unitary_inverse();
@@ -296,6 +348,10 @@ impl<P: Fp6Parameters> FromBytes for Fp6<P> {
     }
 }
 
+impl<P: Fp6Parameters> ConstSerializedSize for Fp6<P> {
+    const SERIALIZED_SIZE: usize = 2 * <Fp3<P::Fp3Params> as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
 impl<P: Fp6Parameters> Neg for Fp6<P> {
     type Output = Self;
     #[inline]
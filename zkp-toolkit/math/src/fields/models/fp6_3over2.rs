@@ -15,7 +15,7 @@ use rand::{
 };
 
 use crate::{
-    bytes::{FromBytes, ToBytes},
+    bytes::{ConstSerializedSize, FromBytes, ToBytes},
     fields::{Field, Fp2, Fp2Parameters},
 };
 
@@ -45,7 +45,6 @@ pub trait Fp6Parameters: 'static + Send + Sync + Copy {
     PartialEq(bound = "P: Fp6Parameters"),
     Eq(bound = "P: Fp6Parameters")
 )]
-#[derive(Serialize, Deserialize)]
 pub struct Fp6<P: Fp6Parameters> {
     pub c0: Fp2<P::Fp2Params>,
     pub c1: Fp2<P::Fp2Params>,
@@ -479,6 +478,8 @@ impl<P: Fp6Parameters> From<u8> for Fp6<P> {
     }
 }
 
+/// Writes `c0 || c1 || c2` in the `Fp2` layout. The serde encoding uses the
+/// same bytes.
 impl<P: Fp6Parameters> ToBytes for Fp6<P> {
     #[inline]
     fn write<W: Write>(&self, mut writer: W) -> IoResult<()> {
@@ -497,3 +498,19 @@ impl<P: Fp6Parameters> FromBytes for Fp6<P> {
         Ok(Fp6::new(c0, c1, c2))
     }
 }
+
+impl<P: Fp6Parameters> ConstSerializedSize for Fp6<P> {
+    const SERIALIZED_SIZE: usize = 3 * <Fp2<P::Fp2Params> as ConstSerializedSize>::SERIALIZED_SIZE;
+}
+
+impl<P: Fp6Parameters> serde::Serialize for Fp6<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::bytes::serialize_as_bytes(self, serializer)
+    }
+}
+
+impl<'de, P: Fp6Parameters> serde::Deserialize<'de> for Fp6<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::bytes::deserialize_from_bytes(deserializer)
+    }
+}
@@ -1,3 +1,6 @@
+pub mod fp;
+pub use self::fp::*;
+
 pub mod fp_256;
 pub use self::fp_256::*;
 
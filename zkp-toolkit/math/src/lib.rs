@@ -126,6 +126,8 @@ pub use self::msm::*;
 
 pub use num_traits::{One, Zero};
 
+pub use math_derive::derive_field_params;
+
 pub mod fft;
 
 pub mod prelude {
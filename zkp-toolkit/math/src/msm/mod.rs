@@ -2,8 +2,10 @@
 
 mod fixed_base;
 mod variable_base;
+mod wnaf;
 pub use fixed_base::*;
 pub use variable_base::*;
+pub use wnaf::*;
 
 /// The result of this function is only approximately `ln(a)`
 /// [`Explanation of usage`]
@@ -1,7 +1,7 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
 use crate::{
-    prelude::{AffineCurve, BigInteger, FpParameters, One, PrimeField, ProjectiveCurve, Zero},
+    prelude::{AffineCurve, BigInteger, One, PrimeField, ProjectiveCurve, Zero},
     Vec,
 };
 #[cfg(feature = "parallel")]
@@ -9,6 +9,15 @@ use rayon::prelude::*;
 
 pub struct VariableBaseMSM;
 
+/// Each thread gets at least this many points, below that the cost of
+/// combining the partial results outweighs the gain.
+#[cfg(feature = "parallel")]
+const MIN_CHUNK_SIZE: usize = 1 << 14;
+
+/// From this many points on, the buckets are accumulated with batched affine
+/// additions, which share one inversion among all additions of a round.
+const BATCH_AFFINE_THRESHOLD: usize = 1 << 10;
+
 impl VariableBaseMSM {
     fn msm_inner<G: AffineCurve>(
         bases: &[G],
@@ -23,8 +32,16 @@ impl VariableBaseMSM {
             super::ln_without_floats(scalars.len()) + 2
         };
 
-        let num_bits = <G::ScalarField as PrimeField>::Params::MODULUS_BITS as usize;
+        // Scalars coming from a GLV split are only about half as long as the
+        // modulus, so we only process as many windows as the longest scalar needs.
+        let num_bits = scalars
+            .iter()
+            .map(|s| s.num_bits() as usize)
+            .max()
+            .unwrap_or(0)
+            .max(1);
         let fr_one = G::ScalarField::one().into_repr();
+        let batch_affine = scalars.len() >= BATCH_AFFINE_THRESHOLD;
 
         let zero = G::Projective::zero();
         let window_starts: Vec<_> = (0..num_bits).step_by(c).collect();
@@ -41,7 +58,9 @@ impl VariableBaseMSM {
             .map(|w_start| {
                 let mut res = zero;
                 // We don't need the "zero" bucket, so we only have 2^c - 1 buckets
-                let mut buckets = vec![zero; (1 << c) - 1];
+                let num_buckets = (1 << c) - 1;
+                let mut buckets = vec![zero; if batch_affine { 0 } else { num_buckets }];
+                let mut bucket_bases = vec![Vec::new(); if batch_affine { num_buckets } else { 0 }];
                 scalars
                     .iter()
                     .zip(bases)
@@ -66,11 +85,19 @@ impl VariableBaseMSM {
                             // bucket.
                             // (Recall that `buckets` doesn't have a zero bucket.)
                             if scalar != 0 {
-                                buckets[(scalar - 1) as usize].add_assign_mixed(base);
+                                if batch_affine {
+                                    bucket_bases[(scalar - 1) as usize].push(*base);
+                                } else {
+                                    buckets[(scalar - 1) as usize].add_assign_mixed(base);
+                                }
                             }
                         }
                     });
-                let buckets = G::Projective::batch_normalization_into_affine(&buckets);
+                let buckets = if batch_affine {
+                    Self::batch_sum_affine(bucket_bases)
+                } else {
+                    G::Projective::batch_normalization_into_affine(&buckets)
+                };
 
                 let mut running_sum = G::Projective::zero();
                 for b in buckets.into_iter().rev() {
@@ -99,10 +126,107 @@ impl VariableBaseMSM {
                 })
     }
 
+    /// Sums up each of the `sets` in affine coordinates. Every round halves
+    /// all the sets at once by adding pairs of their points with one call to
+    /// `AffineCurve::batch_add_in_place_affine`.
+    fn batch_sum_affine<G: AffineCurve>(mut sets: Vec<Vec<G>>) -> Vec<G> {
+        let mut lhs = Vec::new();
+        let mut rhs = Vec::new();
+        while sets.iter().any(|set| set.len() > 1) {
+            lhs.clear();
+            rhs.clear();
+            // The first half of each set is added to the last half, a middle
+            // point of an odd sized set is left for the next round.
+            for set in sets.iter() {
+                let half = set.len() / 2;
+                lhs.extend_from_slice(&set[..half]);
+                rhs.extend_from_slice(&set[set.len() - half..]);
+            }
+
+            G::batch_add_in_place_affine(&mut lhs, &rhs);
+
+            let mut sums = lhs.iter();
+            for set in sets.iter_mut() {
+                let half = set.len() / 2;
+                for (point, sum) in set.iter_mut().zip(&mut sums).take(half) {
+                    *point = *sum;
+                }
+                let len = set.len() - half;
+                set.truncate(len);
+            }
+        }
+
+        sets.into_iter()
+            .map(|set| set.first().cloned().unwrap_or_else(G::zero))
+            .collect()
+    }
+
+    /// Rewrites every `base * scalar` as two products with scalars of about
+    /// half the bit size, if the curve has an efficiently computable
+    /// endomorphism. See `AffineCurve::glv_split`.
+    fn glv_split<G: AffineCurve>(
+        bases: &[G],
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> Option<(Vec<G>, Vec<<G::ScalarField as PrimeField>::BigInt>)> {
+        #[cfg(feature = "parallel")]
+        let iter = bases.par_iter().zip(scalars.par_iter());
+        #[cfg(not(feature = "parallel"))]
+        let iter = bases.iter().zip(scalars.iter());
+
+        let splits = iter
+            .map(|(base, scalar)| base.glv_split(scalar))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(splits.iter().flatten().cloned().unzip())
+    }
+
     pub fn multi_scalar_mul<G: AffineCurve>(
         bases: &[G],
         scalars: &[<G::ScalarField as PrimeField>::BigInt],
     ) -> G::Projective {
+        match Self::glv_split(bases, scalars) {
+            Some((bases, scalars)) => Self::msm_parallel(&bases, &scalars),
+            None => Self::msm_parallel(bases, scalars),
+        }
+    }
+
+    fn msm_parallel<G: AffineCurve>(
+        bases: &[G],
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+    ) -> G::Projective {
+        #[cfg(feature = "parallel")]
+        {
+            let size = core::cmp::min(bases.len(), scalars.len());
+            let num_threads = rayon::current_num_threads();
+            let chunk_size = core::cmp::max(MIN_CHUNK_SIZE, (size + num_threads - 1) / num_threads);
+            Self::multi_scalar_mul_chunked(bases, scalars, chunk_size)
+        }
+
+        #[cfg(not(feature = "parallel"))]
         Self::msm_inner(bases, scalars)
     }
+
+    /// Splits the input into chunks of `chunk_size` points, runs the MSM on
+    /// each chunk (in parallel when the `parallel` feature is enabled) and
+    /// sums up the partial results.
+    pub fn multi_scalar_mul_chunked<G: AffineCurve>(
+        bases: &[G],
+        scalars: &[<G::ScalarField as PrimeField>::BigInt],
+        chunk_size: usize,
+    ) -> G::Projective {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        let size = core::cmp::min(bases.len(), scalars.len());
+        if size <= chunk_size {
+            return Self::msm_inner(bases, scalars);
+        }
+
+        let partial_sums: Vec<_> = cfg_chunks!(bases[..size], chunk_size)
+            .zip(cfg_chunks!(scalars[..size], chunk_size))
+            .map(|(bases, scalars)| Self::msm_inner(bases, scalars))
+            .collect();
+
+        partial_sums
+            .iter()
+            .fold(G::Projective::zero(), |total, sum_i| total + sum_i)
+    }
 }
@@ -0,0 +1,186 @@
+use crate::{BigInteger, FpParameters, PrimeField, ProjectiveCurve, Vec};
+
+/// Scalar multiplication in windowed non-adjacent form, reusing the table of
+/// a base or the wNAF form of a scalar across many multiplications.
+///
+/// ```ignore
+/// let mut wnaf = Wnaf::new();
+///
+/// // one base, many scalars
+/// let mut base = wnaf.base(g, scalars.len());
+/// let points = scalars.iter().map(|s| base.scalar(s)).collect::<Vec<_>>();
+///
+/// // one scalar, many bases
+/// let mut scalar = wnaf.scalar(&s);
+/// let points = bases.iter().map(|g| scalar.base(*g)).collect::<Vec<_>>();
+/// ```
+#[derive(Debug)]
+pub struct Wnaf<W, B, S> {
+    base: B,
+    scalar: S,
+    window_size: W,
+}
+
+impl<G: ProjectiveCurve> Wnaf<(), Vec<G>, Vec<i64>> {
+    /// Creates a context with empty base table and scalar buffers.
+    pub fn new() -> Self {
+        Wnaf {
+            base: Vec::new(),
+            scalar: Vec::new(),
+            window_size: (),
+        }
+    }
+
+    /// Prepares `base` to be multiplied by about `num_scalars` scalars.
+    pub fn base(&mut self, base: G, num_scalars: usize) -> Wnaf<usize, &[G], &mut Vec<i64>> {
+        let window_size = recommended_window_for_num_scalars(num_scalars);
+        wnaf_table(&mut self.base, base, window_size);
+
+        Wnaf {
+            base: &self.base[..],
+            scalar: &mut self.scalar,
+            window_size,
+        }
+    }
+
+    /// Prepares `scalar` to be multiplied with many bases.
+    pub fn scalar(&mut self, scalar: &G::ScalarField) -> Wnaf<usize, &mut Vec<G>, &[i64]> {
+        let window_size = recommended_window_for_scalar::<G::ScalarField>();
+        wnaf_form(&mut self.scalar, scalar.into_repr(), window_size);
+
+        Wnaf {
+            base: &mut self.base,
+            scalar: &self.scalar[..],
+            window_size,
+        }
+    }
+}
+
+impl<G: ProjectiveCurve> Default for Wnaf<(), Vec<G>, Vec<i64>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, G: ProjectiveCurve> Wnaf<usize, &'a [G], &'a mut Vec<i64>> {
+    /// Returns a context sharing the base table, with its own scalar buffer,
+    /// e.g. for use in another thread.
+    pub fn shared(&self) -> Wnaf<usize, &'a [G], Vec<i64>> {
+        Wnaf {
+            base: self.base,
+            scalar: Vec::new(),
+            window_size: self.window_size,
+        }
+    }
+}
+
+impl<'a, G: ProjectiveCurve> Wnaf<usize, &'a mut Vec<G>, &'a [i64]> {
+    /// Returns a context sharing the scalar form, with its own table buffer,
+    /// e.g. for use in another thread.
+    pub fn shared(&self) -> Wnaf<usize, Vec<G>, &'a [i64]> {
+        Wnaf {
+            base: Vec::new(),
+            scalar: self.scalar,
+            window_size: self.window_size,
+        }
+    }
+}
+
+impl<G: ProjectiveCurve, S: AsMut<Vec<i64>>> Wnaf<usize, &[G], S> {
+    /// Multiplies the prepared base by `scalar`.
+    pub fn scalar(&mut self, scalar: &G::ScalarField) -> G {
+        let scalar_form = self.scalar.as_mut();
+        wnaf_form(scalar_form, scalar.into_repr(), self.window_size);
+        wnaf_exp(self.base, scalar_form)
+    }
+}
+
+impl<B, S: AsRef<[i64]>> Wnaf<usize, B, S> {
+    /// Multiplies `base` by the prepared scalar.
+    pub fn base<G>(&mut self, base: G) -> G
+    where
+        G: ProjectiveCurve,
+        B: AsMut<Vec<G>>,
+    {
+        let table = self.base.as_mut();
+        wnaf_table(table, base, self.window_size);
+        wnaf_exp(table, self.scalar.as_ref())
+    }
+}
+
+/// The window size for a base multiplied by `num_scalars` scalars, balancing
+/// the table size against the additions saved per scalar.
+fn recommended_window_for_num_scalars(num_scalars: usize) -> usize {
+    const RECOMMENDATIONS: [usize; 12] = [1, 3, 7, 20, 43, 120, 273, 563, 1630, 3128, 7933, 62569];
+
+    2 + RECOMMENDATIONS
+        .iter()
+        .filter(|r| num_scalars >= **r)
+        .count()
+}
+
+/// The window size for a single multiplication by a scalar of `F`.
+fn recommended_window_for_scalar<F: PrimeField>() -> usize {
+    const RECOMMENDATIONS: [u32; 4] = [13, 37, 103, 264];
+
+    let num_bits = F::Params::MODULUS_BITS;
+    2 + RECOMMENDATIONS.iter().filter(|r| num_bits > **r).count()
+}
+
+/// Fills `table` with the odd multiples `base, 3 * base, ...,
+/// (2^window - 1) * base`.
+fn wnaf_table<G: ProjectiveCurve>(table: &mut Vec<G>, mut base: G, window: usize) {
+    table.truncate(0);
+    table.reserve(1 << (window - 1));
+
+    let double = base.double();
+    for _ in 0..(1 << (window - 1)) {
+        table.push(base);
+        base += &double;
+    }
+}
+
+/// Writes the wNAF form of `c` with the given window into `wnaf`, least
+/// significant digit first.
+fn wnaf_form<B: BigInteger>(wnaf: &mut Vec<i64>, mut c: B, window: usize) {
+    wnaf.truncate(0);
+
+    while !c.is_zero() {
+        let mut u = 0;
+        if c.is_odd() {
+            u = (c.as_ref()[0] % (1 << (window + 1))) as i64;
+            if u > (1 << window) {
+                u -= 1 << (window + 1);
+            }
+            if u > 0 {
+                c.sub_noborrow(&B::from(u as u64));
+            } else {
+                c.add_nocarry(&B::from((-u) as u64));
+            }
+        }
+        wnaf.push(u);
+        c.div2();
+    }
+}
+
+/// Evaluates the wNAF form `wnaf` against the odd multiples in `table`.
+fn wnaf_exp<G: ProjectiveCurve>(table: &[G], wnaf: &[i64]) -> G {
+    let mut result = G::zero();
+    let mut found_nonzero = false;
+
+    for n in wnaf.iter().rev() {
+        if found_nonzero {
+            result.double_in_place();
+        }
+        if *n != 0 {
+            found_nonzero = true;
+            if *n > 0 {
+                result += &table[(n / 2) as usize];
+            } else {
+                result -= &table[((-n) / 2) as usize];
+            }
+        }
+    }
+
+    result
+}
@@ -1,7 +1,7 @@
 use curve::bls12_381::{Fr, G1Projective};
 use math::fft::domain::*;
 use math::fft::polynomial::*;
-use math::fft::{DensePolynomial, EvaluationDomain, SparsePolynomial};
+use math::fft::{DensePolynomial, DomainMismatch, EvaluationDomain, Evaluations, SparsePolynomial};
 use math::{test_rng, Field, One, PrimeField, UniformRand, Zero};
 use rand::Rng;
 
@@ -161,6 +161,29 @@ fn evaluate_polynomials() {
     }
 }
 
+#[test]
+fn batch_evaluate_polynomials() {
+    let rng = &mut test_rng();
+    for &(degree, num_points) in &[
+        (0, 5),
+        (10, BATCH_EVALUATION_THRESHOLD - 1),
+        (50, BATCH_EVALUATION_THRESHOLD),
+        (70, 200),
+        (1000, 300),
+        (3000, 1025),
+    ] {
+        let p = DensePolynomial::<Fr>::rand(degree, rng);
+        let points = (0..num_points).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let expected = points.iter().map(|x| p.evaluate(*x)).collect::<Vec<_>>();
+        assert_eq!(p.batch_evaluate(&points), expected);
+    }
+
+    let zero = DensePolynomial::<Fr>::zero();
+    let points = (0..100).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+    assert_eq!(zero.batch_evaluate(&points), vec![Fr::zero(); 100]);
+    assert!(zero.batch_evaluate(&[]).is_empty());
+}
+
 #[test]
 fn mul_polynomials_random() {
     let rng = &mut test_rng();
@@ -309,3 +332,82 @@ fn parallel_fft_consistency() {
 
     test_consistency::<Bls12_381, _>(rng);
 }
+
+#[test]
+fn evaluations_arithmetic() {
+    let rng = &mut test_rng();
+    for degree in 0..20 {
+        let p1 = DensePolynomial::<Fr>::rand(degree, rng);
+        let p2 = DensePolynomial::<Fr>::rand(degree, rng);
+        // The product has degree 2 * degree, so the domain must hold it.
+        let domain = EvaluationDomain::<Fr>::new(2 * degree + 1).unwrap();
+
+        let e1 = Evaluations::from_poly(&p1, domain);
+        let e2 = Evaluations::from_poly(&p2, domain);
+        assert_eq!(e1.domain(), domain);
+        assert_eq!(e1.interpolate_by_ref(), p1);
+
+        assert_eq!((&e1 + &e2).interpolate(), &p1 + &p2);
+        assert_eq!((&e1 - &e2).interpolate(), &p1 - &p2);
+        assert_eq!((&e1 * &e2).interpolate(), &p1 * &p2);
+        assert_eq!((e1.clone() * &e2).evals, (&e1 * &e2).evals);
+    }
+}
+
+#[test]
+fn evaluations_checked_arithmetic() {
+    let rng = &mut test_rng();
+    let p1 = DensePolynomial::<Fr>::rand(3, rng);
+    let p2 = DensePolynomial::<Fr>::rand(3, rng);
+    let domain = EvaluationDomain::<Fr>::new(8).unwrap();
+    let e1 = Evaluations::from_poly(&p1, domain);
+    let e2 = Evaluations::from_poly(&p2, domain);
+    assert_eq!(e1.checked_mul(&e2), Ok(&e1 * &e2));
+    assert_eq!(e1.checked_add(&e2), Ok(&e1 + &e2));
+    assert_eq!(e1.checked_sub(&e2), Ok(&e1 - &e2));
+    assert_eq!(e1.checked_div(&e2), Ok(&e1 / &e2));
+
+    let e3 = Evaluations::from_poly(&p2, EvaluationDomain::new(4).unwrap());
+    assert_eq!(e1.checked_mul(&e3), Err(DomainMismatch));
+    assert_eq!(e1.checked_add(&e3), Err(DomainMismatch));
+    assert_eq!(e1.checked_sub(&e3), Err(DomainMismatch));
+    assert_eq!(e3.checked_div(&e1), Err(DomainMismatch));
+}
+
+#[test]
+#[should_panic(expected = "domains are unequal")]
+fn evaluations_mul_mismatched_domains() {
+    let rng = &mut test_rng();
+    let p = DensePolynomial::<Fr>::rand(3, rng);
+    let e1 = Evaluations::from_poly(&p, EvaluationDomain::new(4).unwrap());
+    let e2 = Evaluations::from_poly(&p, EvaluationDomain::new(8).unwrap());
+    let _ = &e1 * &e2;
+}
+
+#[test]
+#[should_panic(expected = "domains are unequal")]
+fn evaluations_add_mismatched_domains() {
+    let rng = &mut test_rng();
+    let p = DensePolynomial::<Fr>::rand(3, rng);
+    let e1 = Evaluations::from_poly(&p, EvaluationDomain::new(4).unwrap());
+    let e2 = Evaluations::from_poly(&p, EvaluationDomain::new(8).unwrap());
+    let _ = e1 + &e2;
+}
+
+#[test]
+#[should_panic(expected = "domains are unequal")]
+fn evaluations_sub_mismatched_domains() {
+    let rng = &mut test_rng();
+    let p = DensePolynomial::<Fr>::rand(3, rng);
+    let e1 = Evaluations::from_poly(&p, EvaluationDomain::new(4).unwrap());
+    let e2 = Evaluations::from_poly(&p, EvaluationDomain::new(8).unwrap());
+    let _ = &e1 - &e2;
+}
+
+#[test]
+#[should_panic(expected = "polynomial degree exceeds the domain size")]
+fn evaluations_from_poly_too_large() {
+    let rng = &mut test_rng();
+    let p = DensePolynomial::<Fr>::rand(8, rng);
+    let _ = Evaluations::from_poly(&p, EvaluationDomain::new(4).unwrap());
+}
@@ -0,0 +1,38 @@
+use curve::bn_256::FrParameters;
+use math::biginteger::BigInteger256;
+use math::fields::FpParameters;
+use math::derive_field_params;
+
+struct DerivedFrParameters;
+
+impl FpParameters for DerivedFrParameters {
+    type BigInt = BigInteger256;
+
+    derive_field_params!(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        generator = 7
+    );
+}
+
+#[test]
+fn derive_bn_256_fr_parameters() {
+    assert_eq!(DerivedFrParameters::MODULUS, FrParameters::MODULUS);
+    assert_eq!(DerivedFrParameters::MODULUS_BITS, FrParameters::MODULUS_BITS);
+    assert_eq!(DerivedFrParameters::CAPACITY, FrParameters::CAPACITY);
+    assert_eq!(DerivedFrParameters::REPR_SHAVE_BITS, FrParameters::REPR_SHAVE_BITS);
+    assert_eq!(DerivedFrParameters::R, FrParameters::R);
+    assert_eq!(DerivedFrParameters::R2, FrParameters::R2);
+    assert_eq!(DerivedFrParameters::INV, FrParameters::INV);
+    assert_eq!(DerivedFrParameters::GENERATOR, FrParameters::GENERATOR);
+    assert_eq!(DerivedFrParameters::TWO_ADICITY, FrParameters::TWO_ADICITY);
+    assert_eq!(DerivedFrParameters::ROOT_OF_UNITY, FrParameters::ROOT_OF_UNITY);
+    assert_eq!(
+        DerivedFrParameters::MODULUS_MINUS_ONE_DIV_TWO,
+        FrParameters::MODULUS_MINUS_ONE_DIV_TWO
+    );
+    assert_eq!(DerivedFrParameters::T, FrParameters::T);
+    assert_eq!(
+        DerivedFrParameters::T_MINUS_ONE_DIV_TWO,
+        FrParameters::T_MINUS_ONE_DIV_TWO
+    );
+}
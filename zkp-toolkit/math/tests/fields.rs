@@ -0,0 +1,28 @@
+use curve::bls12_381::{Fq2, Fr};
+use math::fields::pow_many;
+use math::{test_rng, Field, One, UniformRand};
+use rand::Rng;
+
+#[test]
+fn pow_many_matches_pow() {
+    let rng = &mut test_rng();
+    for &len in &[0, 1, 7, 100] {
+        let bases = (0..len).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        for exp in &[
+            vec![0u64],
+            vec![1u64],
+            vec![rng.gen::<u64>()],
+            vec![rng.gen::<u64>(), rng.gen::<u64>(), 0, 0],
+            vec![0u64, 0, rng.gen::<u64>(), rng.gen::<u64>()],
+        ] {
+            let expected = bases.iter().map(|b| b.pow(exp)).collect::<Vec<_>>();
+            assert_eq!(pow_many(&bases, exp), expected);
+        }
+    }
+
+    let bases = (0..20).map(|_| Fq2::rand(rng)).collect::<Vec<_>>();
+    let exp = [rng.gen::<u64>(), rng.gen::<u64>()];
+    let expected = bases.iter().map(|b| b.pow(&exp)).collect::<Vec<_>>();
+    assert_eq!(pow_many(&bases, &exp), expected);
+    assert_eq!(pow_many(&bases, &[]), vec![Fq2::one(); 20]);
+}
@@ -0,0 +1,287 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use curve::{bn_256::Bn_256, curve25519::Curve25519};
+use math::{test_rng, Curve, PrimeField, UniformRand};
+use scheme::bulletproofs::{
+    batch_verify, prove, prove_with_shape, setup, synthesize_shape, verify, verify_with_tables,
+    VerifierGens,
+};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+const NUM_CONSTRAINTS: [u32; 3] = [16, 64, 256];
+
+const MIMC_ROUNDS: usize = 322;
+
+const NUM_BATCH_PROOFS: usize = 32;
+
+const TABLES_CONSTRAINTS: u32 = 1 << 10;
+
+const TABLES_WINDOWS: [usize; 2] = [2, 4];
+
+/// Proves `x * (y + 2) = z`, repeated `num` times.
+struct Mini<F: PrimeField> {
+    x: F,
+    y: F,
+    z: F,
+    num: u32,
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for Mini<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let var_x = cs.alloc(|| "x", || Ok(self.x))?;
+        let var_y = cs.alloc(|| "y", || Ok(self.y))?;
+        let var_z = cs.alloc_input(|| "z(output)", || Ok(self.z))?;
+
+        for _ in 0..self.num {
+            cs.enforce(
+                || "x * (y + 2) = z",
+                |lc| lc + var_x,
+                |lc| lc + var_y + (F::from(2u32), CS::one()),
+                |lc| lc + var_z,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn mini<F: PrimeField>(num: u32) -> Mini<F> {
+    Mini {
+        x: F::from(2u32),
+        y: F::from(3u32),
+        z: F::from(10u32),
+        num,
+    }
+}
+
+/// Proves the knowledge of the preimage of a MiMC (`LongsightF322p3`) hash
+/// invocation, as in `tests/mimc.rs`.
+struct MiMC<'a, F: PrimeField> {
+    xl: Option<F>,
+    xr: Option<F>,
+    constants: &'a [F],
+}
+
+impl<'a, F: PrimeField> ConstraintSynthesizer<F> for MiMC<'a, F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut xl_value = self.xl;
+        let mut xl = cs.alloc(
+            || "preimage xl",
+            || xl_value.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+        let mut xr_value = self.xr;
+        let mut xr = cs.alloc(
+            || "preimage xr",
+            || xr_value.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+
+        for (i, constant) in self.constants.iter().enumerate() {
+            // tmp = (xL + Ci)^2
+            let tmp_value = xl_value.map(|e| (e + constant).square());
+            let tmp = cs.alloc(
+                || "tmp",
+                || tmp_value.ok_or(SynthesisError::AssignmentMissing),
+            )?;
+            cs.enforce(
+                || "tmp = (xL + Ci)^2",
+                |lc| lc + xl + (*constant, CS::one()),
+                |lc| lc + xl + (*constant, CS::one()),
+                |lc| lc + tmp,
+            );
+
+            // new_xL = xR + (xL + Ci)^3
+            let new_xl_value =
+                xl_value.map(|e| (e + constant) * &tmp_value.unwrap() + &xr_value.unwrap());
+            let new_xl_fn = || new_xl_value.ok_or(SynthesisError::AssignmentMissing);
+            let new_xl = if i == self.constants.len() - 1 {
+                cs.alloc_input(|| "image", new_xl_fn)?
+            } else {
+                cs.alloc(|| "new_xl", new_xl_fn)?
+            };
+            cs.enforce(
+                || "new_xL = xR + (xL + Ci)^3",
+                |lc| lc + tmp,
+                |lc| lc + xl + (*constant, CS::one()),
+                |lc| lc + new_xl - xr,
+            );
+
+            xr = xl;
+            xr_value = xl_value;
+            xl = new_xl;
+            xl_value = new_xl_value;
+        }
+
+        Ok(())
+    }
+}
+
+fn bench_prove<G: Curve>(c: &mut Criterion, name: &str) {
+    let rng = &mut test_rng();
+
+    // one setup for the circuits of all the sizes.
+    let max_constraints = *NUM_CONSTRAINTS.iter().max().unwrap() as usize;
+    let gens = setup::<G, _>(max_constraints, rng);
+
+    let mut group = c.benchmark_group("bulletproofs_prove");
+    group.sample_size(10);
+    for num in NUM_CONSTRAINTS.iter() {
+        group.bench_with_input(BenchmarkId::new(name, num), num, |b, &num| {
+            b.iter(|| prove(&gens, mini::<G::Fr>(num), rng).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// The synthesis of the constraints, repeated by `prove` and done once for
+/// `prove_with_shape`.
+fn bench_prove_mimc<G: Curve>(c: &mut Criterion, name: &str) {
+    let rng = &mut test_rng();
+    let constants = (0..MIMC_ROUNDS)
+        .map(|_| G::Fr::rand(rng))
+        .collect::<Vec<_>>();
+    let (xl, xr) = (G::Fr::rand(rng), G::Fr::rand(rng));
+    let mimc = || MiMC {
+        xl: Some(xl),
+        xr: Some(xr),
+        constants: &constants,
+    };
+    let shape = synthesize_shape::<G, _>(MiMC {
+        xl: None,
+        xr: None,
+        constants: &constants,
+    })
+    .unwrap();
+    let gens = setup::<G, _>(shape.generators_size(), rng);
+
+    let mut group = c.benchmark_group("bulletproofs_prove_mimc");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("prove", name), |b| {
+        b.iter(|| prove(&gens, mimc(), rng).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("prove_with_shape", name), |b| {
+        b.iter(|| prove_with_shape(&gens, &shape, mimc(), rng).unwrap())
+    });
+    group.finish();
+}
+
+/// The proofs of `NUM_BATCH_PROOFS` preimages, verified one by one and at
+/// once.
+fn bench_verify_mimc<G: Curve>(c: &mut Criterion, name: &str) {
+    let rng = &mut test_rng();
+    let constants = (0..MIMC_ROUNDS)
+        .map(|_| G::Fr::rand(rng))
+        .collect::<Vec<_>>();
+    let gens = setup::<G, _>(
+        synthesize_shape::<G, _>(MiMC {
+            xl: None,
+            xr: None,
+            constants: &constants,
+        })
+        .unwrap()
+        .generators_size(),
+        rng,
+    );
+
+    let items = (0..NUM_BATCH_PROOFS)
+        .map(|_| {
+            let (xl, xr) = (G::Fr::rand(rng), G::Fr::rand(rng));
+            let mimc = MiMC {
+                xl: Some(xl),
+                xr: Some(xr),
+                constants: &constants,
+            };
+            let image = mimc_image(xl, xr, &constants);
+            let (shape, proof) = prove(&gens, mimc, rng).unwrap();
+            (shape, proof, vec![image])
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("bulletproofs_verify_mimc");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("verify", name), |b| {
+        b.iter(|| {
+            for (shape, proof, public_inputs) in items.iter() {
+                assert!(verify(&gens, shape, proof, public_inputs).unwrap());
+            }
+        })
+    });
+    group.bench_function(BenchmarkId::new("batch_verify", name), |b| {
+        b.iter(|| assert!(batch_verify(&gens, &items, rng).unwrap()))
+    });
+    group.finish();
+}
+
+/// A proof of `TABLES_CONSTRAINTS` constraints, verified of the generators
+/// and of their precomputed tables of many window sizes.
+fn bench_verify_tables<G: Curve>(c: &mut Criterion, name: &str) {
+    let rng = &mut test_rng();
+    let gens = setup::<G, _>(TABLES_CONSTRAINTS as usize, rng);
+    let (shape, proof) = prove(&gens, mini::<G::Fr>(TABLES_CONSTRAINTS), rng).unwrap();
+    let z = [G::Fr::from(10u32)];
+
+    let mut group = c.benchmark_group("bulletproofs_verify_tables");
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("verify", name), |b| {
+        b.iter(|| assert!(verify(&gens, &shape, &proof, &z).unwrap()))
+    });
+    for window in TABLES_WINDOWS.iter() {
+        let tables = VerifierGens::new(&gens, *window).unwrap();
+        let id = format!(
+            "{}_window_{}_{}MiB",
+            name,
+            window,
+            VerifierGens::<G>::memory_usage(gens.max_constraints(), *window) >> 20
+        );
+        group.bench_function(BenchmarkId::new("verify_with_tables", id), |b| {
+            b.iter(|| assert!(verify_with_tables(&tables, &shape, &proof, &z).unwrap()))
+        });
+    }
+    group.finish();
+}
+
+/// The image of `MiMC` of the preimage `(xl, xr)`.
+fn mimc_image<F: PrimeField>(mut xl: F, mut xr: F, constants: &[F]) -> F {
+    for constant in constants {
+        let tmp = (xl + constant).square();
+        let new_xl = (xl + constant) * &tmp + &xr;
+        xr = xl;
+        xl = new_xl;
+    }
+    xl
+}
+
+fn bench_prove_mimc_bn_256(c: &mut Criterion) {
+    bench_prove_mimc::<Bn_256>(c, "bn_256_g1");
+}
+
+fn bench_verify_mimc_bn_256(c: &mut Criterion) {
+    bench_verify_mimc::<Bn_256>(c, "bn_256_g1");
+}
+
+fn bench_verify_tables_bn_256(c: &mut Criterion) {
+    bench_verify_tables::<Bn_256>(c, "bn_256_g1");
+}
+
+fn bench_prove_ristretto(c: &mut Criterion) {
+    bench_prove::<Curve25519>(c, "ristretto");
+}
+
+fn bench_prove_bn_256(c: &mut Criterion) {
+    bench_prove::<Bn_256>(c, "bn_256_g1");
+}
+
+criterion_group!(
+    benches,
+    bench_prove_ristretto,
+    bench_prove_bn_256,
+    bench_prove_mimc_bn_256,
+    bench_verify_mimc_bn_256,
+    bench_verify_tables_bn_256
+);
+criterion_main!(benches);
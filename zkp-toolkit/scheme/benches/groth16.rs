@@ -0,0 +1,92 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use curve::bn_256::{Bn_256, Fr};
+use math::{test_rng, UniformRand};
+use scheme::groth16::{
+    create_random_proof, generate_random_parameters, prepare_inputs_partial, prepare_verifying_key,
+    verify_proof, verify_proof_with_prepared_inputs, Verifier,
+};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, LinearCombination, SynthesisError};
+
+const NUM_INPUTS: usize = 8;
+
+const NUM_PROOFS: usize = 1000;
+
+/// Proves the knowledge of the sum of the public inputs, e.g. of a fixed
+/// root and the varying nullifier last.
+struct Sum {
+    inputs: Vec<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for Sum {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut lc = LinearCombination::zero();
+        for (i, input) in self.inputs.iter().enumerate() {
+            lc = lc + cs.alloc_input(|| format!("input {}", i), || Ok(*input))?;
+        }
+        let sum = self.inputs.iter().fold(Fr::from(0u32), |acc, x| acc + x);
+        let sum = cs.alloc(|| "sum", || Ok(sum))?;
+        cs.enforce(|| "sum", |_| lc, |lc| lc + CS::one(), |lc| lc + sum);
+
+        Ok(())
+    }
+}
+
+/// Verifies `NUM_PROOFS` proofs, in turn, of the first `NUM_INPUTS - 1`
+/// inputs fixed, summed once for `verify_proof_with_prepared_inputs`.
+fn bench_verify_fixed_inputs(c: &mut Criterion) {
+    let rng = &mut test_rng();
+    let fixed: Vec<Fr> = (1..NUM_INPUTS).map(|_| Fr::rand(rng)).collect();
+    let sum = |nullifier: Fr| Sum {
+        inputs: fixed.iter().copied().chain(Some(nullifier)).collect(),
+    };
+    let params = generate_random_parameters::<Bn_256, _, _>(sum(Fr::rand(rng)), rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+    let proofs: Vec<_> = (0..NUM_PROOFS)
+        .map(|_| {
+            let nullifier = Fr::rand(rng);
+            let proof = create_random_proof(&params, sum(nullifier), rng).unwrap();
+            (nullifier, proof)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("groth16_verify_fixed_inputs");
+    group.sample_size(10);
+    group.bench_function("verify_proof", |b| {
+        let mut proofs = proofs.iter().cycle();
+        b.iter(|| {
+            let (nullifier, proof) = proofs.next().unwrap();
+            let mut inputs = fixed.clone();
+            inputs.push(*nullifier);
+            assert!(verify_proof(&pvk, proof, &inputs).unwrap());
+        })
+    });
+    group.bench_function("verifier", |b| {
+        let verifier = Verifier::new(pvk.clone());
+        let mut inputs = fixed.clone();
+        inputs.push(Fr::rand(rng));
+        let mut proofs = proofs.iter().cycle();
+        b.iter(|| {
+            let (nullifier, proof) = proofs.next().unwrap();
+            inputs[NUM_INPUTS - 1] = *nullifier;
+            assert!(verifier.verify(proof, &inputs).unwrap());
+        })
+    });
+    group.bench_function("verify_proof_with_prepared_inputs", |b| {
+        let fixed: Vec<_> = fixed.iter().copied().enumerate().collect();
+        let partial = prepare_inputs_partial(&pvk, &fixed).unwrap();
+        let mut proofs = proofs.iter().cycle();
+        b.iter(|| {
+            let (nullifier, proof) = proofs.next().unwrap();
+            let prepared = partial.finish(&[(NUM_INPUTS - 1, *nullifier)]).unwrap();
+            assert!(verify_proof_with_prepared_inputs(&pvk, proof, &prepared).unwrap());
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_verify_fixed_inputs);
+criterion_main!(benches);
@@ -12,14 +12,15 @@ use std::collections::BTreeMap;
 
 use crate::{String, Vec};
 
+use crate::ipa::{inner_product, random_bytes_to_fr};
 use crate::r1cs::{
-    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+    CircuitFingerprint, ConstraintSynthesizer, ConstraintSystem, FingerprintHasher, Index,
+    LinearCombination, SynthesisError, Variable,
 };
 
 use super::{
-    hadamard_product, inner_product, inner_product_proof, push_constraints, quick_multiexp,
-    random_bytes_to_fr, vector_map_product, vector_matrix_product, vector_matrix_product_t,
-    vector_product, VecPoly5,
+    hadamard_product, inner_product_proof, push_constraints, quick_multiexp, vector_map_product,
+    vector_matrix_product, vector_matrix_product_t, vector_product, VecPoly5, VerifierGens,
 };
 
 // use rayon::prelude::*; // TODO: use rayon to accelerate
@@ -103,19 +104,33 @@ impl<F: Field> ConstraintSystem<F> for ProvingAssignment<F> {
     fn num_constraints(&self) -> usize {
         self.at.len()
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.input_assignment.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
 }
 
+/// The generators of the commitments and of the inner product argument,
+/// made once by `setup` for all the circuits of at most `max_constraints`.
 #[derive(Serialize, Deserialize)]
 pub struct Generators<G: Curve> {
-    g_vec_N: Vec<G::Affine>,
-    h_vec_N: Vec<G::Affine>,
-    g: G::Affine,
-    h: G::Affine,
-    u: G::Affine,
-    n: usize,
-    N: usize,
-    k: usize,
-    n_w: usize,
+    pub(super) g_vec_N: Vec<G::Affine>,
+    pub(super) h_vec_N: Vec<G::Affine>,
+    pub(super) g: G::Affine,
+    pub(super) h: G::Affine,
+    pub(super) u: G::Affine,
+}
+
+impl<G: Curve> Generators<G> {
+    /// The largest number of constraints, and of auxiliary variables, of the
+    /// circuits the generators prove.
+    pub fn max_constraints(&self) -> usize {
+        self.g_vec_N.len()
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -184,100 +199,331 @@ pub struct Proof<G: Curve> {
     t_x: G::Fr,
     IPP: inner_product_proof::Proof<G>,
     IPP_P: G::Projective,
+    /// The digest of the shape of the circuit, in place of its constraints,
+    /// which the verifier synthesizes itself.
+    digest: ShapeDigest,
 }
 
-// very basic support for R1CS ConstraintSystem
-// TODO: refactor this then we do not need to return Generators, R1csCircuit, and Assignment.
-pub fn create_random_proof<G, C, R>(
-    circuit: C,
-    rng: &mut R,
-) -> Result<(Generators<G>, R1csCircuit<G>, Proof<G>), SynthesisError>
-where
-    G: Curve,
-    C: ConstraintSynthesizer<G::Fr>,
-    R: Rng,
-{
-    let mut prover = ProvingAssignment::<G::Fr> {
-        at: vec![],
-        bt: vec![],
-        ct: vec![],
-        input_assignment: vec![],
-        aux_assignment: vec![],
-    };
+impl<G: Curve> Proof<G> {
+    /// The digest of the shape of the circuit the proof is of.
+    pub fn digest(&self) -> &ShapeDigest {
+        &self.digest
+    }
+}
 
-    // Allocate the "one" input variable
-    prover.alloc_input(|| "", || Ok(G::Fr::one()))?;
+/// A constraint system that synthesizes the constraints of a circuit only,
+/// of no values.
+struct ShapeAssembly<F: Field> {
+    at: Vec<Vec<(F, Index)>>,
+    bt: Vec<Vec<(F, Index)>>,
+    ct: Vec<Vec<(F, Index)>>,
+    num_inputs: usize,
+    num_aux: usize,
+}
 
-    // Synthesize the circuit.
-    circuit.generate_constraints(&mut prover)?; // TODO: maybe we should move this out becasue we do not need a trusted setup for bp
+impl<F: Field> ConstraintSystem<F> for ShapeAssembly<F> {
+    type Root = Self;
 
-    let num_constraints = prover.at.len();
-    assert_eq!(num_constraints, prover.bt.len());
-    assert_eq!(num_constraints, prover.ct.len());
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_aux;
+        self.num_aux += 1;
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
 
-    let f = [&prover.input_assignment[..], &prover.aux_assignment[..]].concat();
-    let num_inputs = prover.input_assignment.len();
-    let num_assignments = f.len();
-    // println!(
-    //     "num_constraints = {}, num_inputs = {}, num_assignments = {}",
-    //     num_constraints, num_inputs, num_assignments
-    // );
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.num_inputs;
+        self.num_inputs += 1;
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let num_constraints = self.num_constraints();
+
+        self.at.push(Vec::new());
+        self.bt.push(Vec::new());
+        self.ct.push(Vec::new());
+
+        push_constraints(a(LinearCombination::zero()), &mut self.at, num_constraints);
+
+        push_constraints(b(LinearCombination::zero()), &mut self.bt, num_constraints);
+
+        push_constraints(c(LinearCombination::zero()), &mut self.ct, num_constraints);
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.at.len()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_aux
+    }
+}
+
+/// A constraint system that assigns the variables of a circuit only, of no
+/// linear combinations, for circuits of a `R1csShape`.
+struct ValueAssignment<F: Field> {
+    num_constraints: usize,
+    input_assignment: Vec<F>,
+    aux_assignment: Vec<F>,
+}
+
+impl<F: Field> ConstraintSystem<F> for ValueAssignment<F> {
+    type Root = Self;
+
+    #[inline]
+    fn alloc<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.aux_assignment.len();
+        self.aux_assignment.push(f()?);
+        Ok(Variable::new_unchecked(Index::Aux(index)))
+    }
+
+    #[inline]
+    fn alloc_input<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.input_assignment.len();
+        self.input_assignment.push(f()?);
+        Ok(Variable::new_unchecked(Index::Input(index)))
+    }
+
+    #[inline]
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn pop_namespace(&mut self) {
+        // Do nothing; we don't care about namespaces in this context.
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.input_assignment.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
+}
+
+/// The constraints of a circuit, synthesized once by `synthesize_shape` to
+/// prove it of many witnesses by `prove_with_shape`, and to verify its
+/// proofs with.
+#[derive(Serialize, Deserialize)]
+pub struct R1csShape<G: Curve> {
+    /// The public inputs, the "one" input included.
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    /// The matrices, to verify the proofs with too.
+    pub r1cs_circuit: R1csCircuit<G>,
+    /// The application the proofs are of, the first message of their
+    /// transcripts, so the proofs of one application do not verify in
+    /// another of the same circuit. Empty by default, of no message, as
+    /// the proofs before domains.
+    pub domain: Vec<u8>,
+}
+
+impl<G: Curve> R1csShape<G> {
+    /// The shape of the proofs of the application `domain`, of
+    /// `prove_with_shape` and of the verifiers.
+    pub fn with_domain(mut self, domain: &[u8]) -> Self {
+        self.domain = domain.to_vec();
+        self
+    }
+
+    /// The size of the generators the proofs of the circuit take, the
+    /// smallest `max_constraints` to `setup` for it.
+    pub fn generators_size(&self) -> usize {
+        cmp::max(self.r1cs_circuit.CL.len(), self.num_aux).next_power_of_two()
+    }
+
+    /// The digest of the constraints, the one the proofs of the circuit
+    /// carry.
+    pub fn digest(&self) -> ShapeDigest {
+        shape_digest(&self.r1cs_circuit, self.num_inputs, self.num_aux)
+    }
+}
+
+/// The digest of the constraints of a circuit, which its proofs carry for
+/// the verifier to check against the shape it derives itself. It is the
+/// `r1cs::fingerprint` of the circuit, the one of its keys in the other
+/// schemes and of its witnesses.
+pub type ShapeDigest = CircuitFingerprint;
+
+/// The `r1cs::fingerprint` of the circuit of `num_inputs` public inputs and
+/// `num_aux` auxiliary variables, of the non-zero entries of the rows of its
+/// matrices, the columns of the inputs first.
+fn shape_digest<G: Curve>(
+    r1cs_circuit: &R1csCircuit<G>,
+    num_inputs: usize,
+    num_aux: usize,
+) -> ShapeDigest {
+    let terms = |row: &Vec<G::Fr>| {
+        row.iter()
+            .enumerate()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(j, coeff)| {
+                let index = if j < num_inputs {
+                    Index::Input(j)
+                } else {
+                    Index::Aux(j - num_inputs)
+                };
+                (index, *coeff)
+            })
+            .collect()
+    };
+
+    let mut hasher = FingerprintHasher::new();
+    for ((a, b), c) in r1cs_circuit
+        .CL
+        .iter()
+        .zip(&r1cs_circuit.CR)
+        .zip(&r1cs_circuit.CO)
+    {
+        hasher.constraint_of_terms::<G::Fr>(terms(a), terms(b), terms(c));
+    }
+    hasher.finish(num_inputs, num_aux)
+}
+
+/// The transcript of the proofs of the application `domain`.
+fn transcript(domain: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(b"protocol3");
+    if !domain.is_empty() {
+        transcript.append_message(b"domain", domain);
+    }
+    transcript
+}
+
+/// The full matrices of the sparse rows of the constraints.
+fn r1cs_circuit<G: Curve>(
+    at: &[Vec<(G::Fr, Index)>],
+    bt: &[Vec<(G::Fr, Index)>],
+    ct: &[Vec<(G::Fr, Index)>],
+    num_inputs: usize,
+    num_assignments: usize,
+) -> R1csCircuit<G> {
+    let num_constraints = at.len();
+    assert_eq!(num_constraints, bt.len());
+    assert_eq!(num_constraints, ct.len());
 
     let mut CL: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); num_assignments]; num_constraints];
     let mut CR: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); num_assignments]; num_constraints];
     let mut CO: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); num_assignments]; num_constraints];
 
-    // Convert vec with index to full matrix
+    // Convert vec with index to full matrix, the terms of a variable repeated
+    // in a linear combination adding up.
     // TODO: compute with at, bt, ct directly
     for i in 0..num_constraints {
-        for &(ref coeff, index) in prover.at[i].iter() {
+        for &(ref coeff, index) in at[i].iter() {
             let index = match index {
                 Index::Input(i) => i,
                 Index::Aux(i) => num_inputs + i,
             };
-            CL[i][index] = *coeff;
+            CL[i][index] += coeff;
         }
-        for &(ref coeff, index) in prover.bt[i].iter() {
+        for &(ref coeff, index) in bt[i].iter() {
             let index = match index {
                 Index::Input(i) => i,
                 Index::Aux(i) => num_inputs + i,
             };
-            CR[i][index] = *coeff;
+            CR[i][index] += coeff;
         }
-        for &(ref coeff, index) in prover.ct[i].iter() {
+        for &(ref coeff, index) in ct[i].iter() {
             let index = match index {
                 Index::Input(i) => i,
                 Index::Aux(i) => num_inputs + i,
             };
-            CO[i][index] = *coeff;
+            CO[i][index] += coeff;
         }
     }
 
-    let r1cs_circuit = R1csCircuit {
+    R1csCircuit {
         CL,
         CR,
         CO,
         CL_T: Default::default(),
         CR_T: Default::default(),
         CO_T: Default::default(),
-    };
-
-    let aL = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CL);
-    let aR = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CR);
-    let aO = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CO);
-
-    let input = Assignment {
-        aL: aL,
-        aR: aR,
-        aO: aO,
-        s: prover.input_assignment,
-        w: prover.aux_assignment,
-    };
+    }
+}
 
-    // create generators
-    // n_max
-    let n_max = cmp::max(input.aL.len(), input.w.len());
-    let N = n_max.next_power_of_two(); // N must be greater than or equal to n & n_w
+/// Makes the generators of the circuits of at most `max_constraints`
+/// constraints and auxiliary variables, once for all their proofs.
+pub fn setup<G, R>(max_constraints: usize, rng: &mut R) -> Generators<G>
+where
+    G: Curve,
+    R: Rng,
+{
+    let N = max_constraints.next_power_of_two();
     let g_vec_N = create_generators::<G, _>(rng, N);
     let h_vec_N = create_generators::<G, _>(rng, N);
     let gh = create_generators::<G, _>(rng, 2);
@@ -285,39 +531,207 @@ where
     let h = gh[1];
     let u = G::Projective::rand(rng).into_affine();
 
-    let n = num_constraints;
-    let k = input.s.len();
-    let n_w = input.w.len();
-    let generators = Generators {
+    Generators {
         g_vec_N,
         h_vec_N,
         g,
         h,
         u,
-        n,
-        N,
-        k,
-        n_w,
+    }
+}
+
+/// Proves the assignment of `r1cs_circuit`, of the application `domain`.
+fn prove_values<G, R>(
+    gens: &Generators<G>,
+    r1cs_circuit: &R1csCircuit<G>,
+    domain: &[u8],
+    input_assignment: Vec<G::Fr>,
+    aux_assignment: Vec<G::Fr>,
+    rng: &mut R,
+) -> Result<Proof<G>, SynthesisError>
+where
+    G: Curve,
+    R: Rng,
+{
+    let f = [&input_assignment[..], &aux_assignment[..]].concat();
+
+    let aL = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CL);
+    let aR = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CR);
+    let aO = vector_matrix_product_t::<G::Fr>(&f, &r1cs_circuit.CO);
+
+    let input = Assignment {
+        aL,
+        aR,
+        aO,
+        s: input_assignment,
+        w: aux_assignment,
+    };
+
+    prove_with_transcript(gens, r1cs_circuit, &input, transcript(domain), rng)
+}
+
+/// Proves `circuit` of the generators `gens`, which must be of its size or
+/// larger. The shape is the one `synthesize_shape` makes of the circuit, to
+/// verify the proof with.
+pub fn prove<G, C, R>(
+    gens: &Generators<G>,
+    circuit: C,
+    rng: &mut R,
+) -> Result<(R1csShape<G>, Proof<G>), SynthesisError>
+where
+    G: Curve,
+    C: ConstraintSynthesizer<G::Fr>,
+    R: Rng,
+{
+    let mut prover = ProvingAssignment::<G::Fr> {
+        at: vec![],
+        bt: vec![],
+        ct: vec![],
+        input_assignment: vec![],
+        aux_assignment: vec![],
+    };
+
+    // Allocate the "one" input variable
+    prover.alloc_input(|| "", || Ok(G::Fr::one()))?;
+
+    // Synthesize the circuit.
+    circuit.generate_constraints(&mut prover)?;
+
+    let num_inputs = prover.input_assignment.len();
+    let num_aux = prover.aux_assignment.len();
+    let r1cs_circuit = r1cs_circuit::<G>(
+        &prover.at,
+        &prover.bt,
+        &prover.ct,
+        num_inputs,
+        num_inputs + num_aux,
+    );
+
+    let proof = prove_values(
+        gens,
+        &r1cs_circuit,
+        &[],
+        prover.input_assignment,
+        prover.aux_assignment,
+        rng,
+    )?;
+
+    let shape = R1csShape {
+        num_inputs,
+        num_aux,
+        r1cs_circuit: r1cs_circuit.matrix_to_map(),
+        domain: vec![],
+    };
+    Ok((shape, proof))
+}
+
+/// Synthesizes the constraints of `circuit`, of no values, once for all its
+/// proofs by `prove_with_shape`.
+pub fn synthesize_shape<G, C>(circuit: C) -> Result<R1csShape<G>, SynthesisError>
+where
+    G: Curve,
+    C: ConstraintSynthesizer<G::Fr>,
+{
+    let mut assembly = ShapeAssembly::<G::Fr> {
+        at: vec![],
+        bt: vec![],
+        ct: vec![],
+        num_inputs: 0,
+        num_aux: 0,
     };
 
-    let proof = prove(&generators, &r1cs_circuit, &input, rng);
+    // Allocate the "one" input variable
+    assembly.alloc_input(|| "", || Ok(G::Fr::one()))?;
 
-    Ok((generators, r1cs_circuit.matrix_to_map(), proof))
+    // Synthesize the circuit.
+    circuit.generate_constraints(&mut assembly)?;
+
+    let num_inputs = assembly.num_inputs;
+    let num_aux = assembly.num_aux;
+    let r1cs_circuit = r1cs_circuit::<G>(
+        &assembly.at,
+        &assembly.bt,
+        &assembly.ct,
+        num_inputs,
+        num_inputs + num_aux,
+    )
+    .matrix_to_map();
+
+    Ok(R1csShape {
+        num_inputs,
+        num_aux,
+        r1cs_circuit,
+        domain: vec![],
+    })
+}
+
+/// Proves `circuit` of the constraints of `shape`, synthesizing its values
+/// only. The proof is the one `prove` makes of the same `rng`.
+pub fn prove_with_shape<G, C, R>(
+    gens: &Generators<G>,
+    shape: &R1csShape<G>,
+    circuit: C,
+    rng: &mut R,
+) -> Result<Proof<G>, SynthesisError>
+where
+    G: Curve,
+    C: ConstraintSynthesizer<G::Fr>,
+    R: Rng,
+{
+    let mut prover = ValueAssignment::<G::Fr> {
+        num_constraints: 0,
+        input_assignment: vec![],
+        aux_assignment: vec![],
+    };
+
+    // Allocate the "one" input variable
+    prover.alloc_input(|| "", || Ok(G::Fr::one()))?;
+
+    // Synthesize the values of the circuit.
+    circuit.generate_constraints(&mut prover)?;
+
+    if prover.num_constraints != shape.r1cs_circuit.CL.len()
+        || prover.input_assignment.len() != shape.num_inputs
+        || prover.aux_assignment.len() != shape.num_aux
+    {
+        return Err(SynthesisError::WitnessMismatch);
+    }
+
+    prove_values(
+        gens,
+        &shape.r1cs_circuit,
+        &shape.domain,
+        prover.input_assignment,
+        prover.aux_assignment,
+        rng,
+    )
 }
 
 // bulletproofs arithmetic circuit proof with R1CS format
-pub fn prove<G, R>(
+pub fn prove_assignment<G, R>(
     gens: &Generators<G>,
     r1cs_circuit: &R1csCircuit<G>,
     input: &Assignment<G>,
     rng: &mut R,
-) -> Proof<G>
+) -> Result<Proof<G>, SynthesisError>
 where
     G: Curve,
     R: Rng,
 {
-    let mut transcript = Transcript::new(b"protocol3");
+    prove_with_transcript(gens, r1cs_circuit, input, transcript(&[]), rng)
+}
 
+fn prove_with_transcript<G, R>(
+    gens: &Generators<G>,
+    r1cs_circuit: &R1csCircuit<G>,
+    input: &Assignment<G>,
+    mut transcript: Transcript,
+    rng: &mut R,
+) -> Result<Proof<G>, SynthesisError>
+where
+    G: Curve,
+    R: Rng,
+{
     let n = input.aL.len();
     assert_eq!(n, input.aR.len());
     assert_eq!(n, input.aO.len());
@@ -325,6 +739,12 @@ where
     let k = input.s.len();
     let n_w = input.w.len();
 
+    // the generators of larger circuits prove it too, of their first N.
+    let N = cmp::max(n, n_w).next_power_of_two(); // N must be greater than or equal to n & n_w
+    if gens.max_constraints() < N {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
     // generators
     let mut g_vec: Vec<G::Affine> = vec![G::Affine::default(); n];
     let mut h_vec: Vec<G::Affine> = vec![G::Affine::default(); n];
@@ -337,7 +757,6 @@ where
 
     // choose blinding vectors sL, sR
     let n_max = cmp::max(n, n_w);
-    let N = n_max.next_power_of_two(); // N must be greater than or equal to n & n_w
     transcript.append_u64(b"n", n as u64);
     transcript.append_u64(b"N", N as u64);
     let mut sL: Vec<G::Fr> = (0..n_max).map(|_| G::Fr::rand(rng)).collect();
@@ -547,17 +966,12 @@ where
     let x_1 = random_bytes_to_fr::<G::Fr>(&buf_x_1);
     let ux = (gens.u.mul(x_1)).into_affine();
 
-    let IPP_P = quick_multiexp::<G>(&l_x, &gens.g_vec_N)
-        + &quick_multiexp::<G>(&r_x, &gens.h_vec_N)
-        + &ux.mul(t_x);
+    let g_vec_N = gens.g_vec_N[0..N].to_vec();
+    let h_vec_N = gens.h_vec_N[0..N].to_vec();
+    let IPP_P =
+        quick_multiexp::<G>(&l_x, &g_vec_N) + &quick_multiexp::<G>(&r_x, &h_vec_N) + &ux.mul(t_x);
 
-    let IPP = inner_product_proof::prove(
-        gens.g_vec_N.clone(),
-        gens.h_vec_N.clone(),
-        ux,
-        l_x.clone(),
-        r_x.clone(),
-    );
+    let IPP = inner_product_proof::prove(g_vec_N, h_vec_N, ux, l_x.clone(), r_x.clone());
 
     // let bp_circuit = BpCircuit {
     //     n,
@@ -590,29 +1004,54 @@ where
         // V,
         IPP,
         IPP_P,
+        digest: shape_digest(r1cs_circuit, k, n_w),
     };
 
-    proof
+    Ok(proof)
 }
 
-pub fn verify_proof<G: Curve>(
-    gens: &Generators<G>,
+/// The challenges of `proof` and the scalars of the generators of its checks,
+/// which `verify` makes one by one and `batch_verify` of many proofs at once.
+struct VerificationScalars<G: Curve> {
+    N: usize,
+    x: G::Fr,
+    x_1: G::Fr,
+    y_n: Vec<G::Fr>,
+    y_n_inv: Vec<G::Fr>,
+    zQ_WL: Vec<G::Fr>,
+    ynInvZQWR: Vec<G::Fr>,
+    zQ_WO: Vec<G::Fr>,
+    zQ_neg_WV: Vec<G::Fr>,
+    delta_yz: G::Fr,
+    zQ_c: G::Fr,
+}
+
+fn verification_scalars<G: Curve>(
+    max_constraints: usize,
+    shape: &R1csShape<G>,
     proof: &Proof<G>,
-    r1cs_circuit: &R1csCircuit<G>,
     public_inputs: &[G::Fr],
-) -> Result<bool, SynthesisError> {
-    let mut transcript = Transcript::new(b"protocol3");
+) -> Result<VerificationScalars<G>, SynthesisError> {
+    if proof.digest != shape.digest() {
+        return Err(SynthesisError::CircuitMismatch);
+    }
+
+    let mut transcript = transcript(&shape.domain);
     let zero = G::Fr::zero();
     let one = G::Fr::one();
 
-    // generators
-    let g_vec: Vec<G::Affine> = gens.g_vec_N.clone();
-    let h_vec: Vec<G::Affine> = gens.h_vec_N.clone();
-    let g = gens.g.clone();
-    let h = gens.h.clone();
+    // the sizes are of the circuit, the generators may be of a larger one.
+    let r1cs_circuit = &shape.r1cs_circuit;
+    let n = r1cs_circuit.CL.len();
+    let k = shape.num_inputs;
+    let n_w = shape.num_aux;
+    let N = shape.generators_size();
+    if max_constraints < N {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
 
-    transcript.append_u64(b"n", gens.n as u64);
-    transcript.append_u64(b"N", gens.N as u64);
+    transcript.append_u64(b"n", n as u64);
+    transcript.append_u64(b"N", N as u64);
 
     transcript.append_message(b"A_I", &math::to_bytes!(proof.A_I).unwrap());
     transcript.append_message(b"A_O", &math::to_bytes!(proof.A_O).unwrap());
@@ -628,8 +1067,8 @@ pub fn verify_proof<G: Curve>(
     let z = random_bytes_to_fr::<G::Fr>(&buf_z);
 
     // compute y, z vectors, and delta(y, z)
-    let mut y_n: Vec<G::Fr> = vec![zero; gens.N]; // challenge per witness
-    for i in 0..gens.N {
+    let mut y_n: Vec<G::Fr> = vec![zero; N]; // challenge per witness
+    for i in 0..N {
         if i == 0 {
             y_n[i] = one;
         } else {
@@ -637,13 +1076,13 @@ pub fn verify_proof<G: Curve>(
         }
     }
 
-    let mut y_n_inv: Vec<G::Fr> = vec![zero; gens.N];
-    for i in 0..gens.N {
+    let mut y_n_inv: Vec<G::Fr> = vec![zero; N];
+    for i in 0..N {
         y_n_inv[i] = y_n[i].inverse().unwrap();
     }
 
-    let mut z_Q: Vec<G::Fr> = vec![zero; gens.n]; // challenge per constraint
-    for i in 0..gens.n {
+    let mut z_Q: Vec<G::Fr> = vec![zero; n]; // challenge per constraint
+    for i in 0..n {
         if i == 0 {
             z_Q[i] = z;
         } else {
@@ -651,36 +1090,28 @@ pub fn verify_proof<G: Curve>(
         }
     }
 
-    let z_Q_neg: Vec<G::Fr> = (0..gens.n).map(|i| -one * &z_Q[i]).collect();
+    let z_Q_neg: Vec<G::Fr> = (0..n).map(|i| -one * &z_Q[i]).collect();
 
-    //println!("gens.N: {}, gens.n: {}", gens.N, gens.n);
     // WL, WR, WO with padding
-    //let mut WL: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); gens.N]; gens.n]; // Qxn, Q=n, n=N
-    //let mut WR: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); gens.N]; gens.n]; // Qxn, Q=n, n=N
-    //let mut WO: Vec<Vec<G::Fr>> = vec![vec![G::Fr::zero(); gens.N]; gens.n]; // Qxn, Q=n, n=Nw
-    let mut WL: Vec<G::Fr> = vec![zero; gens.n]; // Qxn, Q=n, n=N
-    let mut WR: Vec<G::Fr> = vec![zero; gens.n]; // Qxn, Q=n, n=N
-    let mut WO: Vec<G::Fr> = vec![zero; gens.n]; // Qxn, Q=n, n=N
-
-    let zn = z_Q[gens.n - 1];
+    let mut WL: Vec<G::Fr> = vec![zero; n]; // Qxn, Q=n, n=N
+    let mut WR: Vec<G::Fr> = vec![zero; n]; // Qxn, Q=n, n=N
+    let mut WO: Vec<G::Fr> = vec![zero; n]; // Qxn, Q=n, n=N
+
+    let zn = z_Q[n - 1];
     let zn_sq = zn * &zn;
-    for i in 0..gens.n {
-        //WL[i][i] = G::Fr::one();
-        //WR[i][i] = zn * &(G::Fr::one());
-        //WO[i][i] = zn_sq * &(G::Fr::one());
+    for i in 0..n {
         WL[i] = one;
         WR[i] = zn * &one;
         WO[i] = zn_sq * &one;
     }
 
     // c, WV
-    let m = gens.k + gens.n_w;
-    let mut C1: Vec<Vec<G::Fr>> = vec![vec![zero; gens.k]; gens.n];
-    //let mut WV: Vec<Vec<G::Fr>> = vec![vec![zero; gens.N]; gens.n]; // C2
+    let m = k + n_w;
+    let mut C1: Vec<Vec<G::Fr>> = vec![vec![zero; k]; n];
     let mut WV: BTreeMap<(u32, u32), G::Fr> = BTreeMap::new();
 
-    for i in 0..gens.n {
-        for j in 0..gens.k {
+    for i in 0..n {
+        for j in 0..k {
             let cl = r1cs_circuit
                 .CL_T
                 .get(&(i as u32, j as u32))
@@ -695,7 +1126,7 @@ pub fn verify_proof<G: Curve>(
                 .unwrap_or(&zero);
             C1[i][j] = *cl + &(zn * cr) + &(zn_sq * co);
         }
-        for j in gens.k..m {
+        for j in k..m {
             let cl = r1cs_circuit
                 .CL_T
                 .get(&(i as u32, j as u32))
@@ -709,10 +1140,9 @@ pub fn verify_proof<G: Curve>(
                 .get(&(i as u32, j as u32))
                 .unwrap_or(&zero);
 
-            //WV[i][j - gens.k] = *cl + &(zn * cr) + &(zn_sq * co);
             let res = *cl + &(zn * cr) + &(zn_sq * co);
             if res != zero {
-                WV.insert((i as u32, (j - gens.k) as u32), res);
+                WV.insert((i as u32, (j - k) as u32), res);
             }
         }
     }
@@ -721,10 +1151,10 @@ pub fn verify_proof<G: Curve>(
     let c = vector_matrix_product_t::<G::Fr>(&r1_public_inputs, &C1);
 
     // zQ * WL, zQ * WR
-    let zQ_WL: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WL, gens.N, gens.n);
-    let zQ_WR: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WR, gens.N, gens.n);
-    let zQ_WO: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WO, gens.N, gens.n);
-    let zQ_neg_WV: Vec<G::Fr> = vector_map_product::<G::Fr>(&z_Q_neg, &WV, gens.N);
+    let zQ_WL: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WL, N, n);
+    let zQ_WR: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WR, N, n);
+    let zQ_WO: Vec<G::Fr> = vector_product::<G::Fr>(&z_Q, &WO, N, n);
+    let zQ_neg_WV: Vec<G::Fr> = vector_map_product::<G::Fr>(&z_Q_neg, &WV, N);
 
     let ynInvZQWR: Vec<G::Fr> = hadamard_product::<G::Fr>(&y_n_inv, &zQ_WR);
     let delta_yz = inner_product::<G::Fr>(&ynInvZQWR, &zQ_WL);
@@ -744,8 +1174,62 @@ pub fn verify_proof<G: Curve>(
     transcript.challenge_bytes(b"x", &mut buf_x);
     let x = random_bytes_to_fr::<G::Fr>(&buf_x);
 
+    transcript.append_message(b"t_x", &math::to_bytes!(proof.t_x).unwrap());
+    transcript.append_message(b"tau_x", &math::to_bytes!(proof.tau_x).unwrap());
+    transcript.append_message(b"mu", &math::to_bytes!(proof.mu).unwrap());
+    let mut buf_x_1 = [0u8; 31];
+    transcript.challenge_bytes(b"x_1", &mut buf_x_1); // notice: challenge x in protocol1 to avoid cheating from prover
+    let x_1 = random_bytes_to_fr::<G::Fr>(&buf_x_1);
+
+    let zQ_c = inner_product::<G::Fr>(&z_Q, &c);
+
+    Ok(VerificationScalars {
+        N,
+        x,
+        x_1,
+        y_n,
+        y_n_inv,
+        zQ_WL,
+        ynInvZQWR,
+        zQ_WO,
+        zQ_neg_WV,
+        delta_yz,
+        zQ_c,
+    })
+}
+
+/// Verifies `proof` of the circuit of `shape` and of `public_inputs`, with
+/// the generators it was proven of. Fails with `CircuitMismatch` of a proof
+/// of another circuit, of another digest than the shape's.
+pub fn verify<G: Curve>(
+    gens: &Generators<G>,
+    shape: &R1csShape<G>,
+    proof: &Proof<G>,
+    public_inputs: &[G::Fr],
+) -> Result<bool, SynthesisError> {
+    let VerificationScalars {
+        N,
+        x,
+        x_1,
+        y_n,
+        y_n_inv,
+        zQ_WL,
+        ynInvZQWR,
+        zQ_WO,
+        zQ_neg_WV,
+        delta_yz,
+        zQ_c,
+    } = verification_scalars(gens.max_constraints(), shape, proof, public_inputs)?;
+    let one = G::Fr::one();
+
+    // generators
+    let g_vec: Vec<G::Affine> = gens.g_vec_N[0..N].to_vec();
+    let h_vec: Vec<G::Affine> = gens.h_vec_N[0..N].to_vec();
+    let g = gens.g.clone();
+    let h = gens.h.clone();
+
     // V computes and checks:
-    let h_vec_inv: Vec<G::Affine> = (0..gens.N)
+    let h_vec_inv: Vec<G::Affine> = (0..N)
         .map(|i| h_vec[i].mul(y_n_inv[i]).into_affine())
         .collect();
 
@@ -754,32 +1238,17 @@ pub fn verify_proof<G: Curve>(
     let wO: G::Projective = quick_multiexp::<G>(&zQ_WO, &h_vec_inv);
     let wV: G::Projective = quick_multiexp::<G>(&zQ_neg_WV, &h_vec_inv);
 
-    transcript.append_message(b"t_x", &math::to_bytes!(proof.t_x).unwrap());
-    transcript.append_message(b"tau_x", &math::to_bytes!(proof.tau_x).unwrap());
-    transcript.append_message(b"mu", &math::to_bytes!(proof.mu).unwrap());
-    let mut buf_x_1 = [0u8; 31];
-    transcript.challenge_bytes(b"x_1", &mut buf_x_1); // notice: challenge x in protocol1 to avoid cheating from prover
-    let x_1 = random_bytes_to_fr::<G::Fr>(&buf_x_1);
     let ux = (gens.u.mul(x_1)).into_affine();
 
     // check tx ?= <lx, rx>
     // USE IPP here
-    // assert_eq!(proof.t_x, inner_product::<G::Fr>(&proof.l_x, &proof.r_x));
-    if !inner_product_proof::verify(
-        gens.g_vec_N.clone(),
-        gens.h_vec_N.clone(),
-        ux,
-        &proof.IPP_P,
-        &proof.IPP,
-    ) {
+    if !inner_product_proof::verify(g_vec.clone(), h_vec.clone(), ux, &proof.IPP_P, &proof.IPP) {
         return Ok(false);
     }
 
     // check ti
     let checkT_lhs: G::Projective = quick_multiexp::<G>(&vec![proof.t_x, proof.tau_x], &vec![g, h]);
 
-    let zQ_c = inner_product::<G::Fr>(&z_Q, &c);
-
     let xx = x * &x;
     let xxxx = xx * &xx;
     let checkT_rhs: G::Projective =
@@ -793,9 +1262,11 @@ pub fn verify_proof<G: Curve>(
             + &proof.T_9.mul(xxxx * &(xxxx * &x))
             + &proof.T_10.mul(xxxx * &(xxxx * &xx));
 
-    assert_eq!(checkT_lhs, checkT_rhs);
+    if checkT_lhs != checkT_rhs {
+        return Ok(false);
+    }
 
-    let y_n_neg: Vec<G::Fr> = (0..gens.N).map(|i| -one * &y_n[i]).collect();
+    let y_n_neg: Vec<G::Fr> = (0..N).map(|i| -one * &y_n[i]).collect();
     let P = proof.A_I.mul(xx)
         + &proof.A_O.mul(xx * &x)
         + &proof.A_W.mul(xxxx)
@@ -812,6 +1283,274 @@ pub fn verify_proof<G: Curve>(
     Ok(P == checkP)
 }
 
+/// Verifies `proof` of `circuit`, of no values, synthesizing the shape of
+/// its constraints of the default domain. The verifiers of many proofs of
+/// a circuit synthesize its shape once by `synthesize_shape`, or store it,
+/// and `verify` of it.
+pub fn verify_circuit<G, C>(
+    gens: &Generators<G>,
+    circuit: C,
+    proof: &Proof<G>,
+    public_inputs: &[G::Fr],
+) -> Result<bool, SynthesisError>
+where
+    G: Curve,
+    C: ConstraintSynthesizer<G::Fr>,
+{
+    let shape = synthesize_shape::<G, C>(circuit)?;
+    verify(gens, &shape, proof, public_inputs)
+}
+
+/// The scalars of the generators of the inner product check and of the P
+/// check of a proof, of the `h_vec` the verifier scales by `y^-n` folded in.
+struct CheckScalars<F: Field> {
+    /// `<ipp_g, g_vec> + <ipp_h, h_vec> + ipp_u * u
+    ///   - <x_sq, L_vec> - <x_inv_sq, R_vec> - P_IPP = 0`
+    ipp_g: Vec<F>,
+    ipp_h: Vec<F>,
+    ipp_u: F,
+    x_sq: Vec<F>,
+    x_inv_sq: Vec<F>,
+    /// `x^2 * A_I + x^3 * A_O + x^4 * A_W + x^5 * S - mu * h
+    ///   + <p_g, g_vec> + <p_h, h_vec> = 0`
+    p_g: Vec<F>,
+    p_h: Vec<F>,
+    /// `1, x, ..., x^10`
+    x_i: Vec<F>,
+}
+
+/// None of a proof of an inner product argument of another size than the
+/// circuit.
+fn check_scalars<G: Curve>(
+    v: &VerificationScalars<G>,
+    proof: &Proof<G>,
+) -> Option<CheckScalars<G::Fr>> {
+    let N = v.N;
+    let mut ipp_transcript = Transcript::new(b"protocol2");
+    let (x_sq, x_inv_sq, s) = proof.IPP.verification_scalars(&mut ipp_transcript, N)?;
+
+    let (a, b) = (proof.IPP.a, proof.IPP.b);
+    let ipp_g = (0..N).map(|i| a * &s[i]).collect();
+    let ipp_h = (0..N).map(|i| b * &s[N - 1 - i]).collect();
+    let ipp_u = a * &b * &v.x_1;
+
+    let mut x_i = vec![G::Fr::one()];
+    for i in 1..11 {
+        x_i.push(x_i[i - 1] * &v.x);
+    }
+
+    let zero = G::Fr::zero();
+    let p_g = (0..N)
+        .map(|i| x_i[2] * &v.ynInvZQWR[i] - &proof.l_x.get(i).cloned().unwrap_or(zero))
+        .collect();
+    let p_h = (0..N)
+        .map(|i| {
+            let h_i = x_i[2] * &v.zQ_WL[i] + &(v.x * &(v.zQ_WO[i] - &v.y_n[i])) + &v.zQ_neg_WV[i]
+                - &proof.r_x.get(i).cloned().unwrap_or(zero);
+            h_i * &v.y_n_inv[i]
+        })
+        .collect();
+
+    Some(CheckScalars {
+        ipp_g,
+        ipp_h,
+        ipp_u,
+        x_sq,
+        x_inv_sq,
+        p_g,
+        p_h,
+        x_i,
+    })
+}
+
+/// Points of their scalars.
+type Terms<G> = Vec<(<G as Curve>::Fr, <G as Curve>::Affine)>;
+
+/// `t_x * g + tau_x * h - x^4 * (delta(y, z) + <zQ, c>) * g - sum_i x^i * T_i`,
+/// of the scalar of `g` and of the points `T_i` of their scalars.
+fn t_check<G: Curve>(
+    v: &VerificationScalars<G>,
+    proof: &Proof<G>,
+    x_i: &[G::Fr],
+) -> (G::Fr, Terms<G>) {
+    let g_scalar = proof.t_x - &(x_i[4] * &(v.delta_yz + &v.zQ_c));
+    let T = vec![
+        (-x_i[2], proof.T_2),
+        (-x_i[3], proof.T_3),
+        (-x_i[5], proof.T_5),
+        (-x_i[6], proof.T_6),
+        (-x_i[7], proof.T_7),
+        (-x_i[8], proof.T_8),
+        (-x_i[9], proof.T_9),
+        (-x_i[10], proof.T_10),
+    ];
+    (g_scalar, T)
+}
+
+/// `x^2 * A_I + x^3 * A_O + x^4 * A_W + x^5 * S` of the P check.
+fn p_points<G: Curve>(proof: &Proof<G>, x_i: &[G::Fr]) -> Terms<G> {
+    vec![
+        (x_i[2], proof.A_I),
+        (x_i[3], proof.A_O),
+        (x_i[4], proof.A_W),
+        (x_i[5], proof.S),
+    ]
+}
+
+/// `verify`, of the generators of the precomputed tables of `gens`. Accepts
+/// and rejects the same proofs as `verify`.
+pub fn verify_with_tables<G: Curve>(
+    gens: &VerifierGens<G>,
+    shape: &R1csShape<G>,
+    proof: &Proof<G>,
+    public_inputs: &[G::Fr],
+) -> Result<bool, SynthesisError> {
+    let v = verification_scalars(gens.max_constraints(), shape, proof, public_inputs)?;
+    let c = match check_scalars(&v, proof) {
+        Some(c) => c,
+        None => return Ok(false),
+    };
+
+    // the inner product argument
+    let ipp_check = gens.g_vec_mul(&c.ipp_g) + &gens.h_vec_mul(&c.ipp_h) + &gens.u.mul(c.ipp_u)
+        - &quick_multiexp::<G>(&c.x_sq, &proof.IPP.L_vec)
+        - &quick_multiexp::<G>(&c.x_inv_sq, &proof.IPP.R_vec)
+        - &proof.IPP_P;
+    if !ipp_check.is_zero() {
+        return Ok(false);
+    }
+
+    // t(x)
+    let (g_scalar, T) = t_check(&v, proof, &c.x_i);
+    let mut t_check = gens.g.mul(g_scalar) + &gens.h.mul(proof.tau_x);
+    for (e, T_i) in T.iter() {
+        t_check += &T_i.mul(*e);
+    }
+    if !t_check.is_zero() {
+        return Ok(false);
+    }
+
+    // P
+    let mut p_check = gens.g_vec_mul(&c.p_g) + &gens.h_vec_mul(&c.p_h) - &gens.h.mul(proof.mu);
+    for (e, point) in p_points(proof, &c.x_i).iter() {
+        p_check += &point.mul(*e);
+    }
+    Ok(p_check.is_zero())
+}
+
+/// A proof to verify in a batch, of the shape of its circuit and of its
+/// public inputs.
+pub type BatchItem<G> = (R1csShape<G>, Proof<G>, Vec<<G as Curve>::Fr>);
+
+/// Verifies the proofs of `items`, each of its circuit's shape and of its
+/// public inputs, of the generators they were proven of. The checks of all
+/// the proofs are weighted by random scalars of `rng` and summed into one
+/// multi-scalar multiplication, the generators shared by all the proofs.
+///
+/// When the sum fails, the proofs are verified one by one, to fail with
+/// `InvalidBatchProof` of the index of the first proof which does not
+/// verify. A proof of another circuit than its shape fails the batch with
+/// `CircuitMismatch`.
+pub fn batch_verify<G, R>(
+    gens: &Generators<G>,
+    items: &[BatchItem<G>],
+    rng: &mut R,
+) -> Result<bool, SynthesisError>
+where
+    G: Curve,
+    R: Rng,
+{
+    let zero = G::Fr::zero();
+    let max_N = items
+        .iter()
+        .map(|(shape, _, _)| shape.generators_size())
+        .max()
+        .unwrap_or(0);
+    if gens.max_constraints() < max_N {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    // the scalars of the generators, and of the points of the proofs.
+    let mut g_vec_scalars = vec![zero; max_N];
+    let mut h_vec_scalars = vec![zero; max_N];
+    let mut g_scalar = zero;
+    let mut h_scalar = zero;
+    let mut u_scalar = zero;
+    let mut scalars: Vec<G::Fr> = Vec::new();
+    let mut points: Vec<G::Affine> = Vec::new();
+
+    for (shape, proof, public_inputs) in items {
+        let v = verification_scalars(gens.max_constraints(), shape, proof, public_inputs)?;
+        let c = match check_scalars(&v, proof) {
+            Some(c) => c,
+            None => return verify_each(gens, items),
+        };
+
+        // the weights of the inner product argument, of t(x), and of P.
+        let w_ipp = G::Fr::rand(rng);
+        let w_t = G::Fr::rand(rng);
+        let w_p = G::Fr::rand(rng);
+
+        for i in 0..v.N {
+            g_vec_scalars[i] += &(w_ipp * &c.ipp_g[i] + &(w_p * &c.p_g[i]));
+            h_vec_scalars[i] += &(w_ipp * &c.ipp_h[i] + &(w_p * &c.p_h[i]));
+        }
+        u_scalar += &(w_ipp * &c.ipp_u);
+        for (x_sq, L) in c.x_sq.iter().zip(&proof.IPP.L_vec) {
+            scalars.push(-(w_ipp * x_sq));
+            points.push(*L);
+        }
+        for (x_inv_sq, R) in c.x_inv_sq.iter().zip(&proof.IPP.R_vec) {
+            scalars.push(-(w_ipp * x_inv_sq));
+            points.push(*R);
+        }
+        scalars.push(-w_ipp);
+        points.push(proof.IPP_P.into_affine());
+
+        let (t_g_scalar, T) = t_check(&v, proof, &c.x_i);
+        g_scalar += &(w_t * &t_g_scalar);
+        h_scalar += &(w_t * &proof.tau_x);
+        for (e, T_i) in T.iter() {
+            scalars.push(w_t * e);
+            points.push(*T_i);
+        }
+
+        for (e, point) in p_points(proof, &c.x_i).iter() {
+            scalars.push(w_p * e);
+            points.push(*point);
+        }
+        h_scalar -= &(w_p * &proof.mu);
+    }
+
+    scalars.extend(g_vec_scalars);
+    scalars.extend(h_vec_scalars);
+    scalars.extend_from_slice(&[g_scalar, h_scalar, u_scalar]);
+    let mut bases = points;
+    bases.extend_from_slice(&gens.g_vec_N[0..max_N]);
+    bases.extend_from_slice(&gens.h_vec_N[0..max_N]);
+    bases.extend_from_slice(&[gens.g, gens.h, gens.u]);
+
+    if quick_multiexp::<G>(&scalars, &bases).is_zero() {
+        Ok(true)
+    } else {
+        verify_each(gens, items)
+    }
+}
+
+/// Verifies the proofs of `items` one by one, failing with the index of the
+/// first one which does not verify.
+fn verify_each<G: Curve>(
+    gens: &Generators<G>,
+    items: &[BatchItem<G>],
+) -> Result<bool, SynthesisError> {
+    for (i, (shape, proof, public_inputs)) in items.iter().enumerate() {
+        if !verify(gens, shape, proof, public_inputs)? {
+            return Err(SynthesisError::InvalidBatchProof(i));
+        }
+    }
+    Ok(true)
+}
+
 pub fn create_generators<G: Curve, R: Rng>(rng: &mut R, len: usize) -> Vec<G::Affine> {
     let mut generators = Vec::new();
     for _ in 0..len {
@@ -823,6 +1562,7 @@ pub fn create_generators<G: Curve, R: Rng>(rng: &mut R, len: usize) -> Vec<G::Af
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_circuits::{Powers, Square};
     use curve::{Bls12_381, Bn_256};
 
     fn run_protocol3_r1cs_helper<G: Curve>(
@@ -860,34 +1600,17 @@ mod tests {
             w: witness,
         };
 
-        // create generators
-        // n_max
         let n_max = cmp::max(input.aL.len(), input.w.len());
-        let N = n_max.next_power_of_two(); // N must be greater than or equal to n & n_w
-        let g_vec_N = create_generators::<G, _>(rng, N);
-        let h_vec_N = create_generators::<G, _>(rng, N);
-        let gh = create_generators::<G, _>(rng, 2);
-        let g = gh[0];
-        let h = gh[1];
-        let u = G::Projective::rand(rng).into_affine();
-
-        let n = input.aL.len();
-        let k = input.s.len();
-        let n_w = input.w.len();
-        let generators = Generators::<G> {
-            g_vec_N,
-            h_vec_N,
-            g,
-            h,
-            u,
-            n,
-            N,
-            k,
-            n_w,
+        let generators = setup::<G, _>(n_max, rng);
+
+        let proof = prove_assignment(&generators, &r1cs_circuit, &input, rng).unwrap();
+        let shape = R1csShape {
+            num_inputs: input.s.len(),
+            num_aux: input.w.len(),
+            r1cs_circuit,
+            domain: vec![],
         };
-
-        let proof = prove(&generators, &r1cs_circuit, &input, rng);
-        assert!(verify_proof(&generators, &proof, &r1cs_circuit, &statement).unwrap());
+        assert!(verify(&generators, &shape, &proof, &statement).unwrap());
     }
 
     #[test]
@@ -1102,4 +1825,364 @@ mod tests {
 
         run_protocol3_r1cs_helper::<G>(CL, CR, CO, statement, witness);
     }
+
+    #[test]
+    fn prove_with_shape_is_prove() {
+        let gens = setup::<Bn_256, _>(1, &mut math::test_rng());
+        let shape = synthesize_shape::<Bn_256, _>(Square { x: None }).unwrap();
+
+        for x in 2..4u8 {
+            let x = <Bn_256 as Curve>::Fr::from(x);
+            let (prove_shape, proof) =
+                prove(&gens, Square { x: Some(x) }, &mut math::test_rng()).unwrap();
+            let shape_proof =
+                prove_with_shape(&gens, &shape, Square { x: Some(x) }, &mut math::test_rng())
+                    .unwrap();
+
+            assert_eq!(
+                postcard::to_allocvec(&shape).unwrap(),
+                postcard::to_allocvec(&prove_shape).unwrap()
+            );
+            assert_eq!(
+                postcard::to_allocvec(&shape_proof).unwrap(),
+                postcard::to_allocvec(&proof).unwrap()
+            );
+            assert!(verify(&gens, &shape, &shape_proof, &[x.square()]).unwrap());
+        }
+    }
+
+    /// `(x + x) * 1 = y`, of the public `y`, of `x` repeated in the linear
+    /// combination rather than of the coefficient 2.
+    struct Double<F: Field> {
+        x: Option<F>,
+    }
+
+    impl<F: Field> ConstraintSynthesizer<F> for Double<F> {
+        fn generate_constraints<CS: ConstraintSystem<F>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let x_value = self.x;
+            let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+            let y = cs.alloc_input(
+                || "y",
+                || {
+                    x_value
+                        .map(|x| x.double())
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?;
+            let mut x_plus_x = LinearCombination::zero();
+            x_plus_x.0.push((x, F::one()));
+            x_plus_x.0.push((x, F::one()));
+            cs.enforce(
+                || "(x + x) * 1 = y",
+                |_| x_plus_x,
+                |lc| lc + CS::one(),
+                |lc| lc + y,
+            );
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn repeated_variables_add_up() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<Bn_256, _>(1, rng);
+        let x = <Bn_256 as Curve>::Fr::from(3u8);
+
+        let (shape, proof) = prove(&gens, Double { x: Some(x) }, rng).unwrap();
+        assert!(verify(&gens, &shape, &proof, &[x.double()]).unwrap());
+        assert!(!verify(&gens, &shape, &proof, &[x]).unwrap());
+    }
+
+    fn one_setup_for_circuits_of_smaller_sizes<G: Curve>() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<G, _>(16, rng);
+        assert_eq!(gens.max_constraints(), 16);
+
+        let x = G::Fr::from(3u8);
+        for num in [1, 3, 7, 16].iter() {
+            let y = (0..*num).fold(x, |x, _| x.square());
+            let circuit = Powers {
+                x: Some(x),
+                num: *num,
+            };
+            let (shape, proof) = prove(&gens, circuit, rng).unwrap();
+            assert!(shape.generators_size() <= gens.max_constraints());
+            assert!(verify(&gens, &shape, &proof, &[y]).unwrap());
+            assert!(!verify(&gens, &shape, &proof, &[y.double()]).unwrap());
+        }
+
+        // the generators of smaller circuits do not prove it, nor verify it.
+        let (shape, proof) = prove(&gens, Powers { x: Some(x), num: 4 }, rng).unwrap();
+        let small_gens = setup::<G, _>(2, rng);
+        assert!(matches!(
+            prove(&small_gens, Powers { x: Some(x), num: 4 }, rng),
+            Err(SynthesisError::PolynomialDegreeTooLarge)
+        ));
+        assert!(matches!(
+            verify(&small_gens, &shape, &proof, &[x]),
+            Err(SynthesisError::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    #[test]
+    fn one_setup_for_circuits_of_smaller_sizes_bn256() {
+        one_setup_for_circuits_of_smaller_sizes::<Bn_256>();
+    }
+
+    #[test]
+    fn one_setup_for_circuits_of_smaller_sizes_bls12_381() {
+        one_setup_for_circuits_of_smaller_sizes::<Bls12_381>();
+    }
+
+    fn batch_verify_succeed<G: Curve>() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<G, _>(16, rng);
+        assert!(batch_verify(&gens, &[], rng).unwrap());
+
+        // circuits of many sizes, of prefixes of the same generators.
+        let mut items = Vec::new();
+        for (i, num) in [1, 3, 7, 16, 3, 1].iter().enumerate() {
+            let x = G::Fr::from(i as u8 + 2);
+            let y = (0..*num).fold(x, |x, _| x.square());
+            let circuit = Powers {
+                x: Some(x),
+                num: *num,
+            };
+            let (shape, proof) = prove(&gens, circuit, rng).unwrap();
+            items.push((shape, proof, vec![y]));
+        }
+        assert!(batch_verify(&gens, &items, rng).unwrap());
+
+        // one corrupted member, of its public input or of its proof.
+        items[2].2[0].double_in_place();
+        assert!(matches!(
+            batch_verify(&gens, &items, rng),
+            Err(SynthesisError::InvalidBatchProof(2))
+        ));
+        items[2].2[0] = items[2].2[0] / &G::Fr::from(2u8);
+        items[4].1.t_x += &G::Fr::one();
+        assert!(matches!(
+            batch_verify(&gens, &items, rng),
+            Err(SynthesisError::InvalidBatchProof(4))
+        ));
+        items[4].1.t_x -= &G::Fr::one();
+        items[0].1.IPP.a += &G::Fr::one();
+        assert!(matches!(
+            batch_verify(&gens, &items, rng),
+            Err(SynthesisError::InvalidBatchProof(0))
+        ));
+        items[0].1.IPP.a -= &G::Fr::one();
+        assert!(batch_verify(&gens, &items, rng).unwrap());
+
+        let small_gens = setup::<G, _>(8, rng);
+        assert!(matches!(
+            batch_verify(&small_gens, &items, rng),
+            Err(SynthesisError::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    #[test]
+    fn batch_verify_bn256() {
+        batch_verify_succeed::<Bn_256>();
+    }
+
+    #[test]
+    fn batch_verify_bls12_381() {
+        batch_verify_succeed::<Bls12_381>();
+    }
+
+    fn verify_with_tables_is_verify<G: Curve>() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<G, _>(16, rng);
+
+        let x = G::Fr::from(3u8);
+        let mut items = Vec::new();
+        for num in [1, 3, 7, 16].iter() {
+            let y = (0..*num).fold(x, |x, _| x.square());
+            let circuit = Powers {
+                x: Some(x),
+                num: *num,
+            };
+            let (shape, proof) = prove(&gens, circuit, rng).unwrap();
+            items.push((shape, proof, vec![y]));
+        }
+
+        for window in [1, 3, 4].iter() {
+            let tables = VerifierGens::new(&gens, *window).unwrap();
+            assert_eq!(tables.max_constraints(), gens.max_constraints());
+
+            for (shape, proof, public_inputs) in items.iter_mut() {
+                let check = |proof: &Proof<G>, public_inputs: &[G::Fr]| {
+                    let verified = verify(&gens, shape, proof, public_inputs).unwrap();
+                    assert_eq!(
+                        verify_with_tables(&tables, shape, proof, public_inputs).unwrap(),
+                        verified
+                    );
+                    verified
+                };
+                assert!(check(proof, public_inputs));
+                assert!(!check(proof, &[public_inputs[0].double()]));
+
+                // a proof tampered with in each of the checks.
+                proof.IPP.b += &G::Fr::one();
+                assert!(!check(proof, public_inputs));
+                proof.IPP.b -= &G::Fr::one();
+                proof.tau_x += &G::Fr::one();
+                assert!(!check(proof, public_inputs));
+                proof.tau_x -= &G::Fr::one();
+                proof.r_x[0] += &G::Fr::one();
+                assert!(!check(proof, public_inputs));
+                proof.r_x[0] -= &G::Fr::one();
+                assert!(check(proof, public_inputs));
+            }
+        }
+
+        for window in [0, 16].iter() {
+            assert!(matches!(
+                VerifierGens::new(&gens, *window),
+                Err(SynthesisError::MalformedParameters)
+            ));
+        }
+
+        let small_tables = VerifierGens::new(&setup::<G, _>(8, rng), 2).unwrap();
+        let (shape, proof, public_inputs) = &items[3];
+        assert!(matches!(
+            verify_with_tables(&small_tables, shape, proof, public_inputs),
+            Err(SynthesisError::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    #[test]
+    fn verify_with_tables_is_verify_bn256() {
+        verify_with_tables_is_verify::<Bn_256>();
+    }
+
+    #[test]
+    fn verify_with_tables_is_verify_bls12_381() {
+        verify_with_tables_is_verify::<Bls12_381>();
+    }
+
+    #[test]
+    fn verifier_gens_memory_usage() {
+        use core::mem::size_of;
+
+        // 2 * 16 generators, of 63 windows of 4 bits of 16 multiples each,
+        // and a last one of the remaining 2 bits of the 254 of the scalars.
+        let affine = size_of::<<Bn_256 as Curve>::Affine>();
+        assert_eq!(
+            VerifierGens::<Bn_256>::memory_usage(16, 4),
+            2 * 16 * (63 * 16 + 4) * affine
+        );
+        assert!(
+            VerifierGens::<Bn_256>::memory_usage(1024, 8)
+                > 7 * VerifierGens::<Bn_256>::memory_usage(1024, 4)
+        );
+    }
+
+    fn square_shape(domain: &[u8]) -> R1csShape<Bn_256> {
+        synthesize_shape::<Bn_256, _>(Square { x: None })
+            .unwrap()
+            .with_domain(domain)
+    }
+
+    #[test]
+    fn proofs_of_one_domain_do_not_verify_in_another() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<Bn_256, _>(1, rng);
+        let tables = VerifierGens::new(&gens, 2).unwrap();
+        let x = <Bn_256 as Curve>::Fr::from(3u8);
+        let y = [x.square()];
+        let (app_a, app_b, default) = (
+            square_shape(b"app-A"),
+            square_shape(b"app-B"),
+            square_shape(b""),
+        );
+
+        let proof = prove_with_shape(&gens, &app_a, Square { x: Some(x) }, rng).unwrap();
+        assert!(verify(&gens, &app_a, &proof, &y).unwrap());
+        assert!(verify_with_tables(&tables, &app_a, &proof, &y).unwrap());
+        for shape in [&app_b, &default].iter() {
+            assert!(!verify(&gens, shape, &proof, &y).unwrap());
+            assert!(!verify_with_tables(&tables, shape, &proof, &y).unwrap());
+        }
+        assert!(matches!(
+            batch_verify(&gens, &[(app_b, proof, y.to_vec())], rng),
+            Err(SynthesisError::InvalidBatchProof(0))
+        ));
+
+        // the proofs of the default domain are of no application.
+        let (shape, proof) = prove(&gens, Square { x: Some(x) }, rng).unwrap();
+        assert!(shape.domain.is_empty());
+        assert!(verify(&gens, &default, &proof, &y).unwrap());
+        assert!(!verify(&gens, &app_a, &proof, &y).unwrap());
+    }
+
+    #[test]
+    fn default_domain_verifies_old_proofs() {
+        // a proof of `x = 3` made before domains, of the generators of
+        // `setup(1)` of the test rng. The encoding of the proofs changed
+        // with the shape digests, which they end with: the snapshot is the
+        // proof re-encoded with its digest, the bytes before do not
+        // deserialize.
+        let bytes = &include_bytes!("../../tests/snapshots/bulletproofs_square.proof")[..];
+        let proof: Proof<Bn_256> = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(proof.digest(), &square_shape(b"").digest());
+        assert!(postcard::from_bytes::<Proof<Bn_256>>(&bytes[..bytes.len() - 32]).is_err());
+        let gens = setup::<Bn_256, _>(1, &mut math::test_rng());
+        let y = [<Bn_256 as Curve>::Fr::from(9u8)];
+
+        assert!(verify(&gens, &square_shape(b""), &proof, &y).unwrap());
+        assert!(!verify(&gens, &square_shape(b"app-A"), &proof, &y).unwrap());
+    }
+
+    #[test]
+    fn proofs_of_another_circuit_do_not_verify() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<Bn_256, _>(2, rng);
+        let tables = VerifierGens::new(&gens, 2).unwrap();
+        let x = <Bn_256 as Curve>::Fr::from(3u8);
+        let y = [x.square()];
+
+        let (shape, proof) = prove(&gens, Square { x: Some(x) }, rng).unwrap();
+        assert_eq!(proof.digest(), &shape.digest());
+        assert_eq!(
+            shape.digest(),
+            crate::r1cs::fingerprint::<<Bn_256 as Curve>::Fr, _>(Square { x: None }).unwrap()
+        );
+        assert!(verify_circuit(&gens, Square { x: None }, &proof, &y).unwrap());
+
+        // `x^4 = y` is of another digest, one square is the same circuit of
+        // other names.
+        let powers = synthesize_shape::<Bn_256, _>(Powers { x: None, num: 2 }).unwrap();
+        let squares = synthesize_shape::<Bn_256, _>(Powers { x: None, num: 1 }).unwrap();
+        assert_ne!(powers.digest(), shape.digest());
+        assert_eq!(squares.digest(), shape.digest());
+        assert!(verify(&gens, &squares, &proof, &y).unwrap());
+
+        assert!(matches!(
+            verify(&gens, &powers, &proof, &y),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+        assert!(matches!(
+            verify_with_tables(&tables, &powers, &proof, &y),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+        assert!(matches!(
+            verify_circuit(
+                &gens,
+                Powers::<<Bn_256 as Curve>::Fr> { x: None, num: 2 },
+                &proof,
+                &y
+            ),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+        assert!(matches!(
+            batch_verify(&gens, &[(powers, proof, y.to_vec())], rng),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+    }
 }
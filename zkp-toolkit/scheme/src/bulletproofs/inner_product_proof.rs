@@ -1,101 +1,36 @@
 #![allow(non_snake_case)]
-use math::{AffineCurve, Curve, Field, One, ProjectiveCurve, ToBytes};
+use math::Curve;
 use merlin::Transcript;
 
+use crate::ipa;
 use crate::Vec;
 
-use super::{inner_product, quick_multiexp, random_bytes_to_fr};
-
-#[derive(Serialize, Deserialize)]
-pub struct Proof<G: Curve> {
-    L_vec: Vec<G::Affine>,
-    R_vec: Vec<G::Affine>,
-    a: G::Fr,
-    b: G::Fr,
-}
+/// the proof of the inner product argument of `crate::ipa`.
+pub use crate::ipa::Proof;
 
 // protocol2 should not be used independently
 pub fn prove<G: Curve>(
-    mut g_vec: Vec<G::Affine>,
-    mut h_vec: Vec<G::Affine>,
+    g_vec: Vec<G::Affine>,
+    h_vec: Vec<G::Affine>,
     u: G::Affine,
-    mut a_vec: Vec<G::Fr>,
-    mut b_vec: Vec<G::Fr>,
+    a_vec: Vec<G::Fr>,
+    b_vec: Vec<G::Fr>,
 ) -> Proof<G> {
     let mut transcript = Transcript::new(b"protocol2");
-    let mut n = a_vec.len();
-    assert!(n.is_power_of_two());
-    assert_eq!(n, b_vec.len());
-
-    let lg_n = n.trailing_zeros() as usize;
-    let mut L_vec: Vec<G::Affine> = Vec::with_capacity(lg_n);
-    let mut R_vec: Vec<G::Affine> = Vec::with_capacity(lg_n);
-    //let mut i = 1;
-    while n > 1 {
-        //println!("fold, i={}, full_n={}", i, n);
-        //i += 1;
-        // P computes:
-        n = n / 2;
-        let (aL, aR) = a_vec.split_at(n);
-        let (bL, bR) = b_vec.split_at(n);
-
-        let cL = inner_product::<G::Fr>(aL, bR);
-        let cR = inner_product::<G::Fr>(aR, bL);
-
-        let (gL, gR) = g_vec.split_at(n);
-        let (hL, hR) = h_vec.split_at(n);
-
-        let L: G::Projective = quick_multiexp::<G>(&aL.to_vec(), &gR.to_vec())
-            + &(quick_multiexp::<G>(&bR.to_vec(), &hL.to_vec()))
-            + &(u.mul(cL));
-        let R: G::Projective = quick_multiexp::<G>(&aR.to_vec(), &gL.to_vec())
-            + &(quick_multiexp::<G>(&bL.to_vec(), &hR.to_vec()))
-            + &(u.mul(cR));
-
-        // P -> V: L, R
-        let l_aff = L.into_affine();
-        let r_aff = R.into_affine();
-        L_vec.push(l_aff);
-        R_vec.push(r_aff);
-
-        // V challenge x, send to P
-        transcript.append_message(b"L", &math::to_bytes![l_aff].unwrap());
-        transcript.append_message(b"R", &math::to_bytes![r_aff].unwrap());
-
-        // V challenge x
-        let mut buf_x = [0u8; 31];
-        transcript.challenge_bytes(b"x", &mut buf_x);
-        // let x = <F as PrimeField>::from_random_bytes(&buf_x).unwrap();
-        let x = random_bytes_to_fr::<G::Fr>(&buf_x);
-        let x_inv = x.inverse().unwrap();
-
-        // P & V compute:
-        let g_new: Vec<G::Affine> = (0..n)
-            .map(|i| (gL[i].mul(x_inv) + &(gR[i].mul(x))).into_affine())
-            .collect();
-        let h_new: Vec<G::Affine> = (0..n)
-            .map(|i| (hL[i].mul(x) + &(hR[i].mul(x_inv))).into_affine())
-            .collect();
-        // let P_new = L * x*x + P + R * x_inv*x_inv;
-
-        // P computes:
-        let a_new: Vec<G::Fr> = (0..n).map(|i| aL[i] * &x + &(aR[i] * &x_inv)).collect();
-        let b_new: Vec<G::Fr> = (0..n).map(|i| bL[i] * &x_inv + &(bR[i] * &x)).collect();
-
-        a_vec = a_new;
-        b_vec = b_new;
-        g_vec = g_new;
-        h_vec = h_new;
-        // P = P_new;
-    }
-
-    assert_eq!(a_vec.len(), 1);
-    assert_eq!(b_vec.len(), 1);
-
-    let a = a_vec[0];
-    let b = b_vec[0];
+    prove_with_transcript(&mut transcript, g_vec, h_vec, u, a_vec, b_vec)
+}
 
-    Proof { L_vec, R_vec, a, b }
+/// `prove`, of the challenges of `transcript`, which binds them to the
+/// protocol the argument is of.
+pub fn prove_with_transcript<G: Curve>(
+    transcript: &mut Transcript,
+    g_vec: Vec<G::Affine>,
+    h_vec: Vec<G::Affine>,
+    u: G::Affine,
+    a_vec: Vec<G::Fr>,
+    b_vec: Vec<G::Fr>,
+) -> Proof<G> {
+    ipa::prove_with_generators(transcript, g_vec, h_vec, u, a_vec, b_vec)
 }
 
 pub fn verify<G: Curve>(
@@ -106,62 +41,31 @@ pub fn verify<G: Curve>(
     proof: &Proof<G>,
 ) -> bool {
     let mut transcript = Transcript::new(b"protocol2");
-    let lg_n = proof.L_vec.len();
-    let n = 1 << lg_n;
-    assert_eq!(lg_n, proof.R_vec.len());
-
-    let mut x_sq_vec = Vec::with_capacity(lg_n);
-    let mut x_inv_sq_vec = Vec::with_capacity(lg_n);
-    let mut allinv = G::Fr::one();
-    for i in 0..lg_n {
-        transcript.append_message(b"L", &math::to_bytes![proof.L_vec[i]].unwrap());
-        transcript.append_message(b"R", &math::to_bytes![proof.R_vec[i]].unwrap());
-
-        // V challenge x
-        let mut buf_x = [0u8; 31];
-        transcript.challenge_bytes(b"x", &mut buf_x);
-        let x = random_bytes_to_fr::<G::Fr>(&buf_x);
-        let x_inv = x.inverse().unwrap();
-        x_sq_vec.push(x * &x);
-        x_inv_sq_vec.push(x_inv * &x_inv);
-        allinv = allinv * &x_inv;
-    }
-
-    // Compute s values inductively. Here adpots optimization from Dalek.
-    let mut s: Vec<G::Fr> = Vec::with_capacity(n);
-    s.push(allinv);
-    for i in 1..n {
-        let lg_i = (32 - 1 - (i as u32).leading_zeros()) as usize;
-        let k = 1 << lg_i;
-        // The challenges are stored in "creation order" as [u_k,...,u_1],
-        // so u_{lg(i)+1} = is indexed by (lg_n-1) - lg_i
-        let u_lg_i_sq = x_sq_vec[(lg_n - 1) - lg_i];
-        s.push(s[i - k] * &u_lg_i_sq);
-    }
-
-    let mut inv_s = s.clone();
-    inv_s.reverse();
-    let a_s: Vec<G::Fr> = (0..n).map(|i| proof.a * &s[i]).collect();
-    let b_s: Vec<G::Fr> = (0..n).map(|i| proof.b * &inv_s[i]).collect();
-
-    let c_final = proof.a * &proof.b;
-    let CheckP_lhs: G::Projective = quick_multiexp::<G>(&a_s, &g_vec.to_vec())
-        + &(quick_multiexp::<G>(&b_s, &h_vec.to_vec()))
-        + &(u.mul(c_final));
-    let CheckP_rhs: G::Projective = quick_multiexp::<G>(&x_sq_vec, &proof.L_vec)
-        + &(quick_multiexp::<G>(&x_inv_sq_vec, &proof.R_vec))
-        + P;
+    verify_with_transcript(&mut transcript, g_vec, h_vec, u, P, proof)
+}
 
-    CheckP_lhs == CheckP_rhs
+/// `verify`, of the challenges of `transcript`. Proofs of another length
+/// than the generators are rejected.
+pub fn verify_with_transcript<G: Curve>(
+    transcript: &mut Transcript,
+    g_vec: Vec<G::Affine>,
+    h_vec: Vec<G::Affine>,
+    u: G::Affine,
+    P: &G::Projective,
+    proof: &Proof<G>,
+) -> bool {
+    ipa::verify_with_generators(transcript, &g_vec, &h_vec, u, P, proof)
 }
 
 #[cfg(test)]
 mod tests {
     use curve::{Bls12_381, Bn_256};
-    use math::UniformRand;
+    use math::{AffineCurve, ProjectiveCurve, UniformRand};
     use std::time::Instant;
 
+    use super::super::quick_multiexp;
     use super::*;
+    use crate::ipa::inner_product;
 
     #[cfg(test)]
     fn run_protocol2_helper<G: Curve>(n: usize) {
@@ -1,10 +1,13 @@
 use math::{Curve, Field};
 
+use crate::ipa::inner_product;
 use crate::r1cs::{Index, LinearCombination};
 use crate::Vec;
 
 pub mod arithmetic_circuit;
 pub mod inner_product_proof;
+pub mod range_proof;
+mod verifier_gens;
 
 #[cfg(not(feature = "std"))]
 use alloc::collections::BTreeMap;
@@ -12,11 +15,27 @@ use alloc::collections::BTreeMap;
 #[cfg(feature = "std")]
 use std::collections::BTreeMap;
 
+/// standard interface for setup the generators, once for circuits of many sizes.
+pub use arithmetic_circuit::setup;
+
 /// standard interface for create proof.
-pub use arithmetic_circuit::create_random_proof;
+pub use arithmetic_circuit::prove;
 
 /// standard interface for verify proof.
-pub use arithmetic_circuit::verify_proof;
+pub use arithmetic_circuit::verify;
+
+/// interface for verify proof of the circuit, of its shape synthesized.
+pub use arithmetic_circuit::verify_circuit;
+
+/// standard interface for verify many proofs at once.
+pub use arithmetic_circuit::{batch_verify, BatchItem};
+
+/// interface for verify many proofs of the precomputed generators.
+pub use arithmetic_circuit::verify_with_tables;
+pub use verifier_gens::VerifierGens;
+
+/// interface for repeated proofs of one circuit.
+pub use arithmetic_circuit::{prove_with_shape, synthesize_shape, R1csShape, ShapeDigest};
 
 pub use arithmetic_circuit::{Generators, Proof, R1csCircuit};
 
@@ -95,22 +114,6 @@ pub fn hadamard_product<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
     out
 }
 
-/// Computes an inner product of two vectors
-/// \\[
-///    {\langle {\mathbf{a}}, {\mathbf{b}} \rangle} = \sum\_{i=0}^{n-1} a\_i \cdot b\_i.
-/// \\]
-/// Panics if the lengths of \\(\mathbf{a}\\) and \\(\mathbf{b}\\) are not equal.
-pub fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
-    let mut out = F::zero();
-    if a.len() != b.len() {
-        panic!("inner_product(a,b): lengths of vectors do not match");
-    }
-    for i in 0..a.len() {
-        out += &(a[i] * &(b[i]));
-    }
-    out
-}
-
 /// Represents a degree-3 vector polynomial
 /// \\(\mathbf{a} + \mathbf{b} \cdot x + \mathbf{c} \cdot x^2 + \mathbf{d} \cdot x^3 \\).
 pub struct VecPoly3<F: Field>(pub Vec<F>, pub Vec<F>, pub Vec<F>, pub Vec<F>);
@@ -294,15 +297,6 @@ where
     G::vartime_multiscalar_mul(exponents, bases)
 }
 
-fn random_bytes_to_fr<F: Field>(bytes: &[u8]) -> F {
-    let mut r_bytes = [0u8; 31];
-    // only use the first 31 bytes, to avoid value over modulus
-    // we could mod modulus here too to keep value in range
-    r_bytes.copy_from_slice(&bytes[0..31]);
-    let r = <F as Field>::from_random_bytes(&r_bytes);
-    r.unwrap()
-}
-
 fn push_constraints<F: Field>(
     l: LinearCombination<F>,
     constraints: &mut [Vec<(F, Index)>],
@@ -0,0 +1,469 @@
+//! Aggregated range proofs of Bulletproofs (section 4.3 of the paper): one
+//! proof that each of `m` committed values is in `[0, 2^n)`, of
+//! `2·log2(n·m) + 4` points and 5 scalars, proven directly and not as a
+//! circuit.
+//!
+//! A value `v` of the blinding `γ` is committed as `v·g + γ·h` of the `g`
+//! and `h` of the generators, and the bits of the values of their vectors,
+//! so the generators of `setup(n·m)` prove them. A number of values which is
+//! not a power of two is padded with zeros of zero blindings, whose
+//! commitments are the identity the verifier pads the commitments with.
+#![allow(non_snake_case)]
+use math::{AffineCurve, Curve, Field, One, ProjectiveCurve, ToBytes, UniformRand, Zero};
+use merlin::Transcript;
+use rand::Rng;
+
+use crate::ipa::{inner_product, random_bytes_to_fr};
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::{inner_product_proof, quick_multiexp, Generators};
+
+#[derive(Serialize, Deserialize)]
+pub struct Proof<G: Curve> {
+    A: G::Affine,
+    S: G::Affine,
+    T_1: G::Affine,
+    T_2: G::Affine,
+    t_x: G::Fr,
+    t_x_blinding: G::Fr,
+    e_blinding: G::Fr,
+    IPP: inner_product_proof::Proof<G>,
+}
+
+/// The number of values padded to a power of two, of `n_bits` a power of
+/// two of at most 64.
+fn padded_size(n_bits: usize, m: usize) -> Result<usize, SynthesisError> {
+    if !n_bits.is_power_of_two() || n_bits > 64 || m == 0 {
+        return Err(SynthesisError::InvalidRangeSize);
+    }
+    Ok(m.next_power_of_two())
+}
+
+/// `1, x, x^2, ..., x^(n - 1)`.
+fn powers<F: Field>(x: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = F::one();
+    for _ in 0..n {
+        powers.push(power);
+        power *= &x;
+    }
+    powers
+}
+
+fn append_point<G: Curve>(transcript: &mut Transcript, label: &'static [u8], point: &G::Affine) {
+    transcript.append_message(label, &math::to_bytes!(point).unwrap());
+}
+
+fn append_scalar<F: Field>(transcript: &mut Transcript, label: &'static [u8], scalar: &F) {
+    transcript.append_message(label, &math::to_bytes!(scalar).unwrap());
+}
+
+fn challenge<F: Field>(transcript: &mut Transcript, label: &'static [u8]) -> F {
+    let mut buf = [0u8; 31];
+    transcript.challenge_bytes(label, &mut buf);
+    random_bytes_to_fr::<F>(&buf)
+}
+
+/// Starts the proof of the `commitments`, padded to `m` of the identity.
+fn append_commitments<G: Curve>(
+    transcript: &mut Transcript,
+    commitments: &[G::Affine],
+    n_bits: usize,
+    m: usize,
+) {
+    transcript.append_message(b"dom-sep", b"rangeproof");
+    transcript.append_u64(b"n", n_bits as u64);
+    transcript.append_u64(b"m", m as u64);
+    for j in 0..m {
+        let V = commitments.get(j).cloned().unwrap_or_else(G::Affine::zero);
+        append_point::<G>(transcript, b"V", &V);
+    }
+}
+
+/// The vector `h_vec` of the generators scaled by `y^-i`, which the inner
+/// product argument is of.
+fn h_prime<G: Curve>(h_vec: &[G::Affine], y: G::Fr) -> Vec<G::Affine> {
+    let y_inv = y.inverse().unwrap();
+    h_vec
+        .iter()
+        .zip(powers(y_inv, h_vec.len()))
+        .map(|(h, y_inv_i)| h.mul(y_inv_i).into_affine())
+        .collect()
+}
+
+/// Proves that each of the `values` is in `[0, 2^n_bits)`, returning the
+/// proof and the commitments to the values of the `blindings`. Fails on a
+/// value out of the range, and when the generators are smaller than `n_bits`
+/// times the number of values padded to a power of two.
+pub fn prove<G, R>(
+    gens: &Generators<G>,
+    values: &[u64],
+    blindings: &[G::Fr],
+    n_bits: usize,
+    transcript: &mut Transcript,
+    rng: &mut R,
+) -> Result<(Proof<G>, Vec<G::Affine>), SynthesisError>
+where
+    G: Curve,
+    R: Rng,
+{
+    if n_bits < 64 && values.iter().any(|v| v >> n_bits != 0) {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    prove_unchecked(gens, values, blindings, n_bits, transcript, rng)
+}
+
+/// `prove` of the values in range or not, the proofs of which do not verify.
+fn prove_unchecked<G, R>(
+    gens: &Generators<G>,
+    values: &[u64],
+    blindings: &[G::Fr],
+    n_bits: usize,
+    transcript: &mut Transcript,
+    rng: &mut R,
+) -> Result<(Proof<G>, Vec<G::Affine>), SynthesisError>
+where
+    G: Curve,
+    R: Rng,
+{
+    if values.len() != blindings.len() {
+        return Err(SynthesisError::InvalidRangeSize);
+    }
+    let m = padded_size(n_bits, values.len())?;
+    let N = n_bits * m;
+    if gens.max_constraints() < N {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    let zero = G::Fr::zero();
+    let one = G::Fr::one();
+
+    // generators
+    let g_vec: Vec<G::Affine> = gens.g_vec_N[0..N].to_vec();
+    let h_vec: Vec<G::Affine> = gens.h_vec_N[0..N].to_vec();
+    let g = gens.g;
+    let h = gens.h;
+
+    // V_j = v_j * g + gamma_j * h
+    let commitments: Vec<G::Affine> = values
+        .iter()
+        .zip(blindings)
+        .map(|(v, gamma)| (g.mul(G::Fr::from(*v)) + &h.mul(*gamma)).into_affine())
+        .collect();
+    append_commitments::<G>(transcript, &commitments, n_bits, m);
+
+    // the bits of the values, aL, and aR = aL - 1
+    let mut aL: Vec<G::Fr> = Vec::with_capacity(N);
+    for j in 0..m {
+        let v = values.get(j).cloned().unwrap_or(0);
+        for i in 0..n_bits {
+            aL.push(if (v >> i) & 1 == 1 { one } else { zero });
+        }
+    }
+    let aR: Vec<G::Fr> = aL.iter().map(|a| *a - &one).collect();
+
+    // commit aL, aR, and the blinding vectors sL, sR
+    let alpha = G::Fr::rand(rng);
+    let A = (h.mul(alpha) + &quick_multiexp::<G>(&aL, &g_vec) + &quick_multiexp::<G>(&aR, &h_vec))
+        .into_affine();
+
+    let sL: Vec<G::Fr> = (0..N).map(|_| G::Fr::rand(rng)).collect();
+    let sR: Vec<G::Fr> = (0..N).map(|_| G::Fr::rand(rng)).collect();
+    let rho = G::Fr::rand(rng);
+    let S = (h.mul(rho) + &quick_multiexp::<G>(&sL, &g_vec) + &quick_multiexp::<G>(&sR, &h_vec))
+        .into_affine();
+
+    append_point::<G>(transcript, b"A", &A);
+    append_point::<G>(transcript, b"S", &S);
+
+    // V challenge y, z
+    let y: G::Fr = challenge(transcript, b"y");
+    let z: G::Fr = challenge(transcript, b"z");
+
+    // l(X) = (aL - z) + sL * X
+    // r(X) = y^N o (aR + z + sR * X) + sum_j z^(2+j) * (0^(j*n) || 2^n || 0)
+    let y_N = powers(y, N);
+    let z_m = powers(z, m + 2);
+    let two_n = powers(G::Fr::from(2u8), n_bits);
+
+    let l0: Vec<G::Fr> = aL.iter().map(|a| *a - &z).collect();
+    let l1 = sL;
+    let r0: Vec<G::Fr> = (0..N)
+        .map(|i| y_N[i] * &(aR[i] + &z) + &(z_m[2 + i / n_bits] * &two_n[i % n_bits]))
+        .collect();
+    let r1: Vec<G::Fr> = (0..N).map(|i| y_N[i] * &sR[i]).collect();
+
+    // t(X) = <l(X), r(X)> = t0 + t1 * X + t2 * X^2
+    let t1 = inner_product(&l0, &r1) + &inner_product(&l1, &r0);
+    let t2 = inner_product(&l1, &r1);
+
+    let tau_1 = G::Fr::rand(rng);
+    let tau_2 = G::Fr::rand(rng);
+    let T_1 = (g.mul(t1) + &h.mul(tau_1)).into_affine();
+    let T_2 = (g.mul(t2) + &h.mul(tau_2)).into_affine();
+
+    append_point::<G>(transcript, b"T_1", &T_1);
+    append_point::<G>(transcript, b"T_2", &T_2);
+
+    // V challenge x
+    let x: G::Fr = challenge(transcript, b"x");
+
+    let l: Vec<G::Fr> = (0..N).map(|i| l0[i] + &(l1[i] * &x)).collect();
+    let r: Vec<G::Fr> = (0..N).map(|i| r0[i] + &(r1[i] * &x)).collect();
+    let t_x = inner_product(&l, &r);
+
+    // the blindings of t(x), and of l(x), r(x)
+    let mut t_x_blinding = tau_1 * &x + &(tau_2 * &(x * &x));
+    for (j, gamma) in blindings.iter().enumerate() {
+        t_x_blinding += &(z_m[2 + j] * gamma);
+    }
+    let e_blinding = alpha + &(rho * &x);
+
+    append_scalar(transcript, b"t_x", &t_x);
+    append_scalar(transcript, b"t_x_blinding", &t_x_blinding);
+    append_scalar(transcript, b"e_blinding", &e_blinding);
+
+    // V challenge w, of the base of <l, r>
+    let w: G::Fr = challenge(transcript, b"w");
+    let Q = gens.u.mul(w).into_affine();
+
+    let IPP = inner_product_proof::prove_with_transcript(
+        transcript,
+        g_vec,
+        h_prime::<G>(&h_vec, y),
+        Q,
+        l,
+        r,
+    );
+
+    let proof = Proof {
+        A,
+        S,
+        T_1,
+        T_2,
+        t_x,
+        t_x_blinding,
+        e_blinding,
+        IPP,
+    };
+
+    Ok((proof, commitments))
+}
+
+/// Verifies that the values of the `commitments` are in `[0, 2^n_bits)`, of
+/// a `transcript` in the state the prover's was in.
+pub fn verify<G: Curve>(
+    gens: &Generators<G>,
+    commitments: &[G::Affine],
+    proof: &Proof<G>,
+    n_bits: usize,
+    transcript: &mut Transcript,
+) -> Result<bool, SynthesisError> {
+    let m = padded_size(n_bits, commitments.len())?;
+    let N = n_bits * m;
+    if gens.max_constraints() < N {
+        return Err(SynthesisError::PolynomialDegreeTooLarge);
+    }
+
+    // generators
+    let g_vec: Vec<G::Affine> = gens.g_vec_N[0..N].to_vec();
+    let h_vec: Vec<G::Affine> = gens.h_vec_N[0..N].to_vec();
+    let g = gens.g;
+    let h = gens.h;
+
+    append_commitments::<G>(transcript, commitments, n_bits, m);
+    append_point::<G>(transcript, b"A", &proof.A);
+    append_point::<G>(transcript, b"S", &proof.S);
+    let y: G::Fr = challenge(transcript, b"y");
+    let z: G::Fr = challenge(transcript, b"z");
+    append_point::<G>(transcript, b"T_1", &proof.T_1);
+    append_point::<G>(transcript, b"T_2", &proof.T_2);
+    let x: G::Fr = challenge(transcript, b"x");
+    append_scalar(transcript, b"t_x", &proof.t_x);
+    append_scalar(transcript, b"t_x_blinding", &proof.t_x_blinding);
+    append_scalar(transcript, b"e_blinding", &proof.e_blinding);
+    let w: G::Fr = challenge(transcript, b"w");
+    let Q = gens.u.mul(w).into_affine();
+
+    let y_N = powers(y, N);
+    let z_m = powers(z, m + 3);
+    let two_n = powers(G::Fr::from(2u8), n_bits);
+
+    // check t(x) * g + t_x_blinding * h
+    //   ?= delta(y, z) * g + sum_j z^(2+j) * V_j + x * T_1 + x^2 * T_2
+    // delta(y, z) = (z - z^2) * <1, y^N> - sum_j z^(3+j) * <1, 2^n>
+    let sum_y: G::Fr = y_N.iter().sum();
+    let sum_two: G::Fr = two_n.iter().sum();
+    let sum_z: G::Fr = z_m[3..].iter().sum();
+    let delta = (z - &z_m[2]) * &sum_y - &(sum_z * &sum_two);
+
+    let mut checkT_rhs = g.mul(delta) + &proof.T_1.mul(x) + &proof.T_2.mul(x * &x);
+    for (j, V) in commitments.iter().enumerate() {
+        checkT_rhs += &V.mul(z_m[2 + j]);
+    }
+    let checkT_lhs = g.mul(proof.t_x) + &h.mul(proof.t_x_blinding);
+    if checkT_lhs != checkT_rhs {
+        return Ok(false);
+    }
+
+    // P = A + x * S - z * <1, g_vec> + <z * y^N + z^(2+j) * 2^n, h'>
+    //   - e_blinding * h + t(x) * Q ?= <l, g_vec> + <r, h'> + <l, r> * Q
+    let h_prime = h_prime::<G>(&h_vec, y);
+    let g_exp: Vec<G::Fr> = (0..N).map(|_| -z).collect();
+    let h_exp: Vec<G::Fr> = (0..N)
+        .map(|i| z * &y_N[i] + &(z_m[2 + i / n_bits] * &two_n[i % n_bits]))
+        .collect();
+    let P = proof.A.into_projective()
+        + &proof.S.mul(x)
+        + &quick_multiexp::<G>(&g_exp, &g_vec)
+        + &quick_multiexp::<G>(&h_exp, &h_prime)
+        - &h.mul(proof.e_blinding)
+        + &Q.mul(proof.t_x);
+
+    Ok(inner_product_proof::verify_with_transcript(
+        transcript, g_vec, h_prime, Q, &P, &proof.IPP,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bulletproofs::setup;
+    use curve::{Bls12_381, Bn_256};
+
+    const LABEL: &[u8] = b"range proof test";
+
+    fn prove_values<G: Curve>(
+        gens: &Generators<G>,
+        values: &[u64],
+        n_bits: usize,
+    ) -> (Proof<G>, Vec<G::Affine>) {
+        let rng = &mut math::test_rng();
+        let blindings: Vec<G::Fr> = values.iter().map(|_| G::Fr::rand(rng)).collect();
+        let transcript = &mut Transcript::new(LABEL);
+        prove(gens, values, &blindings, n_bits, transcript, rng).unwrap()
+    }
+
+    fn verify_values<G: Curve>(
+        gens: &Generators<G>,
+        commitments: &[G::Affine],
+        proof: &Proof<G>,
+        n_bits: usize,
+    ) -> bool {
+        let transcript = &mut Transcript::new(LABEL);
+        verify(gens, commitments, proof, n_bits, transcript).unwrap()
+    }
+
+    fn prove_and_verify<G: Curve>(values: &[u64], n_bits: usize) {
+        let gens = setup::<G, _>(n_bits * values.len(), &mut math::test_rng());
+        let (proof, commitments) = prove_values(&gens, values, n_bits);
+        assert_eq!(commitments.len(), values.len());
+        assert!(verify_values(&gens, &commitments, &proof, n_bits));
+    }
+
+    fn single_value_succeed<G: Curve>() {
+        prove_and_verify::<G>(&[0], 8);
+        prove_and_verify::<G>(&[42], 32);
+        prove_and_verify::<G>(&[u64::max_value()], 64);
+        // the largest values of their ranges.
+        prove_and_verify::<G>(&[(1 << 8) - 1], 8);
+        prove_and_verify::<G>(&[(1 << 32) - 1], 32);
+    }
+
+    #[test]
+    fn single_value_bn256() {
+        single_value_succeed::<Bn_256>();
+    }
+
+    #[test]
+    fn single_value_bls12_381() {
+        single_value_succeed::<Bls12_381>();
+    }
+
+    #[test]
+    fn aggregated_values() {
+        let values: Vec<u64> = (0..16).map(|i| (i * 7919) % (1 << 16)).collect();
+        prove_and_verify::<Bn_256>(&values, 16);
+        prove_and_verify::<Bls12_381>(&[1, 2, 3, 4], 32);
+
+        // padded to 4 values, of the identity commitments.
+        prove_and_verify::<Bn_256>(&[1, 255, 0], 8);
+    }
+
+    #[test]
+    fn out_of_range_rejected() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<Bn_256, _>(16, rng);
+        let blindings = [<Bn_256 as Curve>::Fr::rand(rng)];
+
+        let transcript = &mut Transcript::new(LABEL);
+        assert!(matches!(
+            prove(&gens, &[1 << 8], &blindings, 8, transcript, rng),
+            Err(SynthesisError::Unsatisfiable)
+        ));
+
+        // the bits of 2^8 in 8 bits are of 0, not of the committed value.
+        let transcript = &mut Transcript::new(LABEL);
+        let (proof, commitments) =
+            prove_unchecked(&gens, &[1 << 8], &blindings, 8, transcript, rng).unwrap();
+        assert!(!verify_values(&gens, &commitments, &proof, 8));
+
+        // a value of 16 bits is not of 8 bits.
+        let (proof, commitments) = prove_values(&gens, &[300], 16);
+        assert!(verify_values(&gens, &commitments, &proof, 16));
+        assert!(!verify_values(&gens, &commitments, &proof, 8));
+
+        // nor of another commitment, or of another transcript.
+        let (other, _) = prove_values(&gens, &[301], 16);
+        assert!(!verify_values(&gens, &commitments, &other, 16));
+        let transcript = &mut Transcript::new(b"another transcript");
+        assert!(!verify(&gens, &commitments, &proof, 16, transcript).unwrap());
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        let rng = &mut math::test_rng();
+        let gens = setup::<Bn_256, _>(16, rng);
+        let blindings = [<Bn_256 as Curve>::Fr::rand(rng); 3];
+        let transcript = &mut Transcript::new(LABEL);
+
+        for (values, n_bits) in [(&[1u64][..], 12), (&[1u64, 2], 8), (&[][..], 8)].iter() {
+            let blindings = &blindings[..values.len().min(1)];
+            assert!(matches!(
+                prove(&gens, values, blindings, *n_bits, transcript, rng),
+                Err(SynthesisError::InvalidRangeSize)
+            ));
+        }
+        assert!(matches!(
+            prove(&gens, &[1, 2, 3], &blindings, 8, transcript, rng),
+            Err(SynthesisError::PolynomialDegreeTooLarge)
+        ));
+    }
+
+    fn proof_size(m: usize, n_bits: usize) -> usize {
+        let gens = setup::<Bn_256, _>(n_bits * m, &mut math::test_rng());
+        let values: Vec<u64> = (0..m as u64).collect();
+        let (proof, _) = prove_values(&gens, &values, n_bits);
+        postcard::to_allocvec(&proof).unwrap().len()
+    }
+
+    #[test]
+    fn proof_sizes() {
+        let point = postcard::to_allocvec(&<Bn_256 as Curve>::Affine::prime_subgroup_generator())
+            .unwrap()
+            .len();
+        let scalar = postcard::to_allocvec(&<Bn_256 as Curve>::Fr::one())
+            .unwrap()
+            .len();
+
+        // 4 points and 2·log2(n·m) of the inner product argument, 5 scalars,
+        // and the lengths of its vectors.
+        for (m, n_bits, lg_nm) in [(1, 8, 3), (1, 64, 6), (16, 32, 9), (3, 16, 6)].iter() {
+            assert_eq!(
+                proof_size(*m, *n_bits),
+                (4 + 2 * lg_nm) * point + 5 * scalar + 2
+            );
+        }
+    }
+}
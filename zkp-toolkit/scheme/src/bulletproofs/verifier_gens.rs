@@ -0,0 +1,112 @@
+//! The generators of a verifier which checks many proofs, of the multiples of
+//! every generator of `g_vec` and `h_vec` precomputed window by window, so
+//! the multi-scalar multiplications of a proof are additions only.
+use core::mem::size_of;
+use math::{
+    AffineCurve, BigInteger, Curve, FixedBaseMSM, FpParameters, PrimeField, ProjectiveCurve, Zero,
+};
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::Generators;
+
+pub struct VerifierGens<G: Curve> {
+    window: usize,
+    /// The `d * 2^(window * j)` multiples of each generator, of the digits
+    /// `d` of each window `j`, as `FixedBaseMSM::get_window_table` gives
+    /// them.
+    g_tables: Vec<Vec<Vec<G::Affine>>>,
+    h_tables: Vec<Vec<Vec<G::Affine>>>,
+    pub(super) g: G::Affine,
+    pub(super) h: G::Affine,
+    pub(super) u: G::Affine,
+}
+
+fn scalar_bits<G: Curve>() -> usize {
+    <G::Fr as PrimeField>::Params::MODULUS_BITS as usize
+}
+
+/// The number of multiples of a table of windows of `window` bits, the last
+/// window of the remaining bits only.
+fn table_len<G: Curve>(window: usize) -> usize {
+    let num_windows = (scalar_bits::<G>() + window - 1) / window;
+    (num_windows - 1) * (1 << window) + (1 << (scalar_bits::<G>() - (num_windows - 1) * window))
+}
+
+/// The multiples of `base` of all the digits of all the windows.
+fn window_table<G: Curve>(base: &G::Affine, window: usize) -> Vec<Vec<G::Affine>> {
+    FixedBaseMSM::get_window_table(scalar_bits::<G>(), window, base.into_projective())
+        .into_iter()
+        .map(G::Projective::batch_into_affine)
+        .collect()
+}
+
+impl<G: Curve> VerifierGens<G> {
+    /// Precomputes the tables of windows of `window` bits of the generators,
+    /// of `VerifierGens::memory_usage(gens.max_constraints(), window)` bytes.
+    /// Wider windows take fewer additions and exponentially more memory.
+    /// Fails of windows of no bits or of more than 15.
+    pub fn new(gens: &Generators<G>, window: usize) -> Result<Self, SynthesisError> {
+        if window == 0 || window >= 16 {
+            return Err(SynthesisError::MalformedParameters);
+        }
+        let tables = |generators: &[G::Affine]| {
+            generators
+                .iter()
+                .map(|base| window_table::<G>(base, window))
+                .collect()
+        };
+
+        Ok(VerifierGens {
+            window,
+            g_tables: tables(&gens.g_vec_N),
+            h_tables: tables(&gens.h_vec_N),
+            g: gens.g,
+            h: gens.h,
+            u: gens.u,
+        })
+    }
+
+    /// The bytes of the tables of `max_constraints` generators of windows of
+    /// `window` bits, to choose the window before precomputing them.
+    pub fn memory_usage(max_constraints: usize, window: usize) -> usize {
+        2 * max_constraints * table_len::<G>(window) * size_of::<G::Affine>()
+    }
+
+    /// The largest number of constraints, and of auxiliary variables, of the
+    /// circuits the generators verify.
+    pub fn max_constraints(&self) -> usize {
+        self.g_tables.len()
+    }
+
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// `<scalars, g_vec>`, of the first `scalars.len()` generators.
+    pub(super) fn g_vec_mul(&self, scalars: &[G::Fr]) -> G::Projective {
+        self.multi_scalar_mul(&self.g_tables, scalars)
+    }
+
+    /// `<scalars, h_vec>`, of the first `scalars.len()` generators.
+    pub(super) fn h_vec_mul(&self, scalars: &[G::Fr]) -> G::Projective {
+        self.multi_scalar_mul(&self.h_tables, scalars)
+    }
+
+    fn multi_scalar_mul(&self, tables: &[Vec<Vec<G::Affine>>], scalars: &[G::Fr]) -> G::Projective {
+        let mut acc = G::Projective::zero();
+        for (table, scalar) in tables.iter().zip(scalars) {
+            let bits = scalar.into_repr().to_bits();
+            // the bits are of the most significant first, the windows of the
+            // table of the least significant first.
+            for (window, chunk) in table.iter().zip(bits.rchunks(self.window)) {
+                let digit = chunk.iter().fold(0, |d, bit| (d << 1) | *bit as usize);
+                if digit != 0 {
+                    acc.add_assign_mixed(&window[digit]);
+                }
+            }
+        }
+        acc
+    }
+}
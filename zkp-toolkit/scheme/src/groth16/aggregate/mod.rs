@@ -0,0 +1,293 @@
+//! Aggregation of Groth16 proofs of one circuit into a proof of logarithmic
+//! size, after [SnarkPack](https://eprint.iacr.org/2021/529).
+//!
+//! The `n` proofs are committed to by pairing products with the keys
+//! `v = (h^{a^i}, h^{b^i})` and `w = (g^{a^{n + i}}, g^{b^{n + i}})` of two
+//! powers-of-tau strings. Of the challenge `r`, the aggregate shows that
+//! `ip_ab = prod_i e(A_i, B_i)^{r^i}` and `agg_c = sum_i r^i C_i` are of the
+//! committed proofs, by a GIPA argument (TIPP for `ip_ab`, MIPP for `agg_c`)
+//! whose final commitment keys are opened by KZG, and the verifier checks
+//! `ip_ab = e(alpha, beta)^{sum_i r^i} e(sum_i r^i vk_x_i, gamma) e(agg_c, delta)`.
+
+use blake2::{Blake2s, Digest};
+use math::{
+    msm::VariableBaseMSM, AffineCurve, Field, PairingEngine, PrimeField, ProjectiveCurve, ToBytes,
+};
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// The parameters of aggregation, of powers-of-tau strings.
+pub mod srs;
+
+/// Aggregate the proofs.
+pub mod prover;
+
+/// Verify the aggregates.
+pub mod verifier;
+
+pub use prover::aggregate_proofs;
+pub use srs::{AggregationParams, AggregationProverKey, AggregationVerifyKey};
+pub use verifier::verify_aggregate_proof;
+
+/// A commitment of a pairing product, of the keys of `a` and of `b`.
+pub type Commitment<E> = (<E as PairingEngine>::Fqk, <E as PairingEngine>::Fqk);
+
+/// The aggregate of many Groth16 proofs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregateProof<E: PairingEngine> {
+    /// The commitment of the `A` and the `B` of the proofs.
+    pub com_ab: Commitment<E>,
+    /// The commitment of the `C` of the proofs.
+    pub com_c: Commitment<E>,
+    /// `prod_i e(A_i, B_i)^{r^i}`.
+    pub ip_ab: E::Fqk,
+    /// `sum_i r^i C_i`.
+    pub agg_c: E::G1Affine,
+    pub gipa: GipaProof<E>,
+    pub kzg: KzgOpenings<E>,
+}
+
+/// The rounds of the GIPA argument, each halving the vectors, and the
+/// elements and the commitment keys the last round leaves.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GipaProof<E: PairingEngine> {
+    /// The cross commitments of `A` and `B`, left and right, of every round.
+    pub comms_ab: Vec<(Commitment<E>, Commitment<E>)>,
+    /// The cross commitments of `C`, left and right, of every round.
+    pub comms_c: Vec<(Commitment<E>, Commitment<E>)>,
+    /// The cross pairing products of `A` and `B` of every round.
+    pub z_ab: Vec<(E::Fqk, E::Fqk)>,
+    /// The cross multiexponentiations of `C` of every round.
+    pub z_c: Vec<(E::G1Affine, E::G1Affine)>,
+    pub final_a: E::G1Affine,
+    pub final_b: E::G2Affine,
+    pub final_c: E::G1Affine,
+    /// The last key of `v`, of `a` and of `b`.
+    pub final_vkey: (E::G2Affine, E::G2Affine),
+    /// The last key of `w`, of `a` and of `b`.
+    pub final_wkey: (E::G1Affine, E::G1Affine),
+}
+
+/// The KZG openings of the last commitment keys at the challenge `z`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KzgOpenings<E: PairingEngine> {
+    /// Of `v`, at `a` and at `b`.
+    pub vkey: (E::G1Affine, E::G1Affine),
+    /// Of `w`, at `a` and at `b`.
+    pub wkey: (E::G2Affine, E::G2Affine),
+}
+
+/// A Fiat-Shamir transcript of BLAKE2s, shared by the prover and the
+/// verifier of the aggregates.
+struct Transcript(Blake2s);
+
+impl Transcript {
+    /// A transcript of the number of proofs and the public inputs of each.
+    fn new<F: PrimeField>(n: usize, public_inputs: &[Vec<F>]) -> Result<Self, SynthesisError> {
+        let mut transcript = Transcript(Blake2s::new());
+        transcript.0.input(b"zkp-toolkit groth16 aggregation");
+        transcript.append(&(n as u64))?;
+        for inputs in public_inputs {
+            transcript.append(&(inputs.len() as u64))?;
+            for input in inputs {
+                transcript.append(input)?;
+            }
+        }
+        Ok(transcript)
+    }
+
+    fn append<T: ToBytes>(&mut self, value: &T) -> Result<(), SynthesisError> {
+        let mut bytes = Vec::new();
+        value.write(&mut bytes)?;
+        self.0.input(&bytes);
+        Ok(())
+    }
+
+    /// A nonzero challenge, of the first `BLAKE2s(state || i)` of the counter
+    /// `i` that is a field element. The state goes on of the challenge.
+    fn challenge<F: PrimeField>(&mut self) -> F {
+        let digest = self.0.clone().result();
+        let challenge = (0u64..)
+            .find_map(|i| {
+                let mut hasher = Blake2s::new();
+                hasher.input(&digest);
+                hasher.input(&i.to_le_bytes());
+                F::from_random_bytes(&hasher.result()).filter(|f| !f.is_zero())
+            })
+            .unwrap();
+        self.0 = Blake2s::new();
+        self.0.input(&digest);
+        challenge
+    }
+}
+
+/// `prod_i e(a_i, b_i)`, of one Miller loop.
+fn pairing_product<'a, E: PairingEngine>(
+    pairs: impl Iterator<Item = (&'a E::G1Affine, &'a E::G2Affine)>,
+) -> E::Fqk {
+    let pairs = pairs.collect::<Vec<_>>();
+    let prepared = cfg_iter!(pairs)
+        .map(|(a, b)| ((**a).into(), (**b).into()))
+        .collect::<Vec<(E::G1Prepared, E::G2Prepared)>>();
+    E::product_of_pairings(&prepared)
+}
+
+/// The commitment of `a` of the keys `v`.
+fn commit_single<E: PairingEngine>(
+    v: (&[E::G2Affine], &[E::G2Affine]),
+    a: &[E::G1Affine],
+) -> Commitment<E> {
+    (
+        pairing_product::<E>(a.iter().zip(v.0)),
+        pairing_product::<E>(a.iter().zip(v.1)),
+    )
+}
+
+/// The commitment of `a` of the keys `v` and of `b` of the keys `w`.
+fn commit_double<E: PairingEngine>(
+    v: (&[E::G2Affine], &[E::G2Affine]),
+    w: (&[E::G1Affine], &[E::G1Affine]),
+    a: &[E::G1Affine],
+    b: &[E::G2Affine],
+) -> Commitment<E> {
+    (
+        pairing_product::<E>(a.iter().zip(v.0).chain(w.0.iter().zip(b))),
+        pairing_product::<E>(a.iter().zip(v.1).chain(w.1.iter().zip(b))),
+    )
+}
+
+/// `sum_i scalars_i * bases_i`.
+fn msm<G: AffineCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G::Projective {
+    let scalars = cfg_iter!(scalars)
+        .map(|s| s.into_repr())
+        .collect::<Vec<_>>();
+    VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+}
+
+/// `scalars_i * points_i`.
+fn scale_points<G: AffineCurve>(points: &[G], scalars: &[G::ScalarField]) -> Vec<G> {
+    let scaled = cfg_iter!(points)
+        .zip(scalars)
+        .map(|(p, s)| p.mul(s.into_repr()))
+        .collect::<Vec<_>>();
+    G::Projective::batch_into_affine(scaled)
+}
+
+/// `left_i + x * right_i`.
+fn fold_points<G: AffineCurve>(left: &[G], right: &[G], x: G::ScalarField) -> Vec<G> {
+    let x = x.into_repr();
+    let folded = cfg_iter!(left)
+        .zip(right)
+        .map(|(l, r)| r.mul(x).add_mixed(l))
+        .collect::<Vec<_>>();
+    G::Projective::batch_into_affine(folded)
+}
+
+/// `left_i + x * right_i`.
+fn fold_scalars<F: Field>(left: &[F], right: &[F], x: F) -> Vec<F> {
+    left.iter().zip(right).map(|(l, r)| x * r + l).collect()
+}
+
+/// The coefficients of `f(X) = prod_j (1 + x_j X^{n / 2^j})`, of the
+/// challenges `x_j` of the rounds `j` from 1 of vectors of `n`: the `i`-th
+/// element of a vector folds into the last one times the `i`-th coefficient.
+fn fold_coefficients<F: Field>(challenges: &[F]) -> Vec<F> {
+    let mut coefficients = vec![F::one()];
+    // from the last round, of vectors of two.
+    for x in challenges.iter().rev() {
+        let high = coefficients.iter().map(|c| *c * x).collect::<Vec<_>>();
+        coefficients.extend(high);
+    }
+    coefficients
+}
+
+/// `f(y)` of `fold_coefficients`, in a multiplication a round.
+fn fold_evaluation<F: Field>(challenges: &[F], y: F) -> F {
+    let mut y_half = y;
+    let mut result = F::one();
+    for x in challenges.iter().rev() {
+        result *= &(F::one() + &(*x * &y_half));
+        y_half.square_in_place();
+    }
+    result
+}
+
+/// The quotient `(p(X) - p(z)) / (X - z)` of the coefficients of `p`, low
+/// degree first.
+fn kzg_quotient<F: Field>(p: &[F], z: F) -> Vec<F> {
+    let mut quotient = vec![F::zero(); p.len().saturating_sub(1)];
+    let mut acc = F::zero();
+    for i in (1..p.len()).rev() {
+        acc = acc * &z + &p[i];
+        quotient[i - 1] = acc;
+    }
+    quotient
+}
+
+/// `r^0, .., r^{n - 1}`.
+fn powers<F: Field>(r: F, n: usize) -> Vec<F> {
+    let mut powers = Vec::with_capacity(n);
+    let mut power = F::one();
+    for _ in 0..n {
+        powers.push(power);
+        power *= &r;
+    }
+    powers
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::{Bn_256, Fr};
+    use math::{test_rng, UniformRand};
+
+    use super::*;
+    use crate::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key};
+    use crate::test_circuits::Square;
+
+    #[test]
+    fn test_aggregate() {
+        let rng = &mut test_rng();
+        let x = Fr::rand(rng);
+        let params =
+            generate_random_parameters::<Bn_256, _, _>(Square { x: Some(x) }, rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let srs = AggregationParams::<Bn_256>::setup(8, rng);
+        let (pk, vk) = srs.specialize(4).unwrap();
+        assert!(matches!(
+            srs.specialize(3),
+            Err(SynthesisError::InvalidAggregationSize)
+        ));
+
+        let xs = (0..4).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+        let proofs = xs
+            .iter()
+            .map(|x| create_random_proof(&params, Square { x: Some(*x) }, rng).unwrap())
+            .collect::<Vec<_>>();
+        let inputs = xs.iter().map(|x| vec![x.square()]).collect::<Vec<_>>();
+
+        let aggregate = aggregate_proofs(&pk, &proofs, &inputs).unwrap();
+        assert_eq!(aggregate.gipa.comms_ab.len(), 2);
+        assert!(verify_aggregate_proof(&pvk, &vk, &inputs, &aggregate).unwrap());
+
+        let mut swapped = inputs.clone();
+        swapped.swap(0, 1);
+        assert!(!verify_aggregate_proof(&pvk, &vk, &swapped, &aggregate).unwrap());
+
+        let mut tampered = aggregate.clone();
+        tampered.ip_ab.square_in_place();
+        assert!(!verify_aggregate_proof(&pvk, &vk, &inputs, &tampered).unwrap());
+
+        assert!(matches!(
+            verify_aggregate_proof(&pvk, &vk, &inputs[..2], &aggregate),
+            Err(SynthesisError::InvalidAggregationSize)
+        ));
+        assert!(matches!(
+            aggregate_proofs(&pk, &proofs[..2], &inputs[..2]),
+            Err(SynthesisError::InvalidAggregationSize)
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+use math::{Field, PairingEngine, ProjectiveCurve, Zero};
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::super::Proof;
+use super::{
+    commit_double, commit_single, fold_coefficients, fold_points, fold_scalars, kzg_quotient, msm,
+    pairing_product, powers, scale_points, AggregateProof, AggregationProverKey, GipaProof,
+    KzgOpenings, Transcript,
+};
+
+/// Aggregates the `proofs` of one circuit, of the `public_inputs` of each,
+/// into a proof of size logarithmic in their number, which must be the `n`
+/// the key `pk` is specialized to.
+///
+/// The public inputs are not checked here but bound into the challenges, so
+/// that the aggregate shows the proofs of these inputs only.
+pub fn aggregate_proofs<E: PairingEngine>(
+    pk: &AggregationProverKey<E>,
+    proofs: &[Proof<E>],
+    public_inputs: &[Vec<E::Fr>],
+) -> Result<AggregateProof<E>, SynthesisError> {
+    let n = pk.n;
+    if proofs.len() != n || public_inputs.len() != n {
+        return Err(SynthesisError::InvalidAggregationSize);
+    }
+    if pk.g_alpha_powers.len() < 2 * n
+        || pk.g_beta_powers.len() < 2 * n
+        || pk.h_alpha_powers.len() < 2 * n
+        || pk.h_beta_powers.len() < 2 * n
+    {
+        return Err(SynthesisError::MalformedParameters);
+    }
+
+    let a = proofs.iter().map(|p| p.a).collect::<Vec<_>>();
+    let b = proofs.iter().map(|p| p.b).collect::<Vec<_>>();
+    let c = proofs.iter().map(|p| p.c).collect::<Vec<_>>();
+    let v = (&pk.h_alpha_powers[..n], &pk.h_beta_powers[..n]);
+    let w = (&pk.g_alpha_powers[n..2 * n], &pk.g_beta_powers[n..2 * n]);
+
+    let com_ab = commit_double::<E>(v, w, &a, &b);
+    let com_c = commit_single::<E>(v, &c);
+
+    let mut transcript = Transcript::new(n, public_inputs)?;
+    transcript.append(&com_ab.0)?;
+    transcript.append(&com_ab.1)?;
+    transcript.append(&com_c.0)?;
+    transcript.append(&com_c.1)?;
+    let r = transcript.challenge::<E::Fr>();
+    let r_inverse = r.inverse().unwrap();
+
+    // `e(A_i, r^i B_i)` and `e(r^{-i} w_i, r^i B_i)` are of the committed
+    // proofs and keys, so the rescaled vectors still open the commitment.
+    let r_powers = powers(r, n);
+    let r_inverse_powers = powers(r_inverse, n);
+    let b = scale_points(&b, &r_powers);
+    let w = (
+        scale_points(w.0, &r_inverse_powers),
+        scale_points(w.1, &r_inverse_powers),
+    );
+
+    let ip_ab = pairing_product::<E>(a.iter().zip(&b));
+    let agg_c = msm(&c, &r_powers).into_affine();
+    transcript.append(&ip_ab)?;
+    transcript.append(&agg_c)?;
+
+    let (gipa, challenges) = prove_gipa::<E>(
+        &mut transcript,
+        a,
+        b,
+        c,
+        r_powers,
+        (v.0.to_vec(), v.1.to_vec()),
+        w,
+    )?;
+
+    transcript.append(&gipa.final_a)?;
+    transcript.append(&gipa.final_b)?;
+    transcript.append(&gipa.final_c)?;
+    transcript.append(&gipa.final_vkey.0)?;
+    transcript.append(&gipa.final_vkey.1)?;
+    transcript.append(&gipa.final_wkey.0)?;
+    transcript.append(&gipa.final_wkey.1)?;
+    let z = transcript.challenge::<E::Fr>();
+
+    // the last `v` is of `f(X) = prod_j (1 + x_j^{-1} X^{n / 2^j})` at the
+    // secrets, and the last `w` of `X^n f'(X / r)` of the challenges `x_j`.
+    let inverses = challenges
+        .iter()
+        .map(|x| x.inverse().unwrap())
+        .collect::<Vec<_>>();
+    let f_v = fold_coefficients(&inverses);
+    let mut f_w = vec![E::Fr::zero(); n];
+    f_w.extend(
+        fold_coefficients(&challenges)
+            .iter()
+            .zip(&r_inverse_powers)
+            .map(|(c, r)| *c * r),
+    );
+
+    let quotient_v = kzg_quotient(&f_v, z);
+    let quotient_w = kzg_quotient(&f_w, z);
+    let kzg = KzgOpenings {
+        vkey: (
+            msm(&pk.g_alpha_powers[..quotient_v.len()], &quotient_v).into_affine(),
+            msm(&pk.g_beta_powers[..quotient_v.len()], &quotient_v).into_affine(),
+        ),
+        wkey: (
+            msm(&pk.h_alpha_powers[..quotient_w.len()], &quotient_w).into_affine(),
+            msm(&pk.h_beta_powers[..quotient_w.len()], &quotient_w).into_affine(),
+        ),
+    };
+
+    Ok(AggregateProof {
+        com_ab,
+        com_c,
+        ip_ab,
+        agg_c,
+        gipa,
+        kzg,
+    })
+}
+
+/// The rounds of GIPA of `A`, `B` and `C`, of the keys `v` of `A` and `C` and
+/// `w` of `B`, and of the scalars `r` of `C`, returning the proof and the
+/// challenges of the rounds.
+fn prove_gipa<E: PairingEngine>(
+    transcript: &mut Transcript,
+    mut a: Vec<E::G1Affine>,
+    mut b: Vec<E::G2Affine>,
+    mut c: Vec<E::G1Affine>,
+    mut r: Vec<E::Fr>,
+    mut v: (Vec<E::G2Affine>, Vec<E::G2Affine>),
+    mut w: (Vec<E::G1Affine>, Vec<E::G1Affine>),
+) -> Result<(GipaProof<E>, Vec<E::Fr>), SynthesisError> {
+    let mut comms_ab = Vec::new();
+    let mut comms_c = Vec::new();
+    let mut z_ab = Vec::new();
+    let mut z_c = Vec::new();
+    let mut challenges = Vec::new();
+
+    while a.len() > 1 {
+        let h = a.len() / 2;
+        let (a_l, a_r) = a.split_at(h);
+        let (b_l, b_r) = b.split_at(h);
+        let (c_l, c_r) = c.split_at(h);
+        let (r_l, r_r) = r.split_at(h);
+        let v_l = (&v.0[..h], &v.1[..h]);
+        let v_r = (&v.0[h..], &v.1[h..]);
+        let w_l = (&w.0[..h], &w.1[..h]);
+        let w_r = (&w.0[h..], &w.1[h..]);
+
+        let com_ab = (
+            commit_double::<E>(v_l, w_r, a_r, b_l),
+            commit_double::<E>(v_r, w_l, a_l, b_r),
+        );
+        let com_c = (commit_single::<E>(v_l, c_r), commit_single::<E>(v_r, c_l));
+        let zs_ab = (
+            pairing_product::<E>(a_r.iter().zip(b_l)),
+            pairing_product::<E>(a_l.iter().zip(b_r)),
+        );
+        let zs_c = (msm(c_r, r_l).into_affine(), msm(c_l, r_r).into_affine());
+
+        for com in &[com_ab.0, com_ab.1, com_c.0, com_c.1] {
+            transcript.append(&com.0)?;
+            transcript.append(&com.1)?;
+        }
+        transcript.append(&zs_ab.0)?;
+        transcript.append(&zs_ab.1)?;
+        transcript.append(&zs_c.0)?;
+        transcript.append(&zs_c.1)?;
+        let x = transcript.challenge::<E::Fr>();
+        let x_inverse = x.inverse().unwrap();
+
+        a = fold_points(a_l, a_r, x);
+        c = fold_points(c_l, c_r, x);
+        b = fold_points(b_l, b_r, x_inverse);
+        r = fold_scalars(r_l, r_r, x_inverse);
+        v = (
+            fold_points(v_l.0, v_r.0, x_inverse),
+            fold_points(v_l.1, v_r.1, x_inverse),
+        );
+        w = (fold_points(w_l.0, w_r.0, x), fold_points(w_l.1, w_r.1, x));
+
+        comms_ab.push(com_ab);
+        comms_c.push(com_c);
+        z_ab.push(zs_ab);
+        z_c.push(zs_c);
+        challenges.push(x);
+    }
+
+    let gipa = GipaProof {
+        comms_ab,
+        comms_c,
+        z_ab,
+        z_c,
+        final_a: a[0],
+        final_b: b[0],
+        final_c: c[0],
+        final_vkey: (v.0[0], v.1[0]),
+        final_wkey: (w.0[0], w.1[0]),
+    };
+    Ok((gipa, challenges))
+}
@@ -0,0 +1,136 @@
+use math::{msm::FixedBaseMSM, PairingEngine, PrimeField, ProjectiveCurve, UniformRand, Zero};
+use rand::Rng;
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::powers;
+
+/// The powers `g^{a^i}`, `h^{a^i}`, `g^{b^i}` and `h^{b^i}` of two
+/// powers-of-tau strings of the same generators and of independent secrets
+/// `a` and `b`, e.g. of two ceremonies, of which aggregation keys of up to
+/// half as many proofs are specialized.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregationParams<E: PairingEngine> {
+    pub g_alpha_powers: Vec<E::G1Affine>,
+    pub h_alpha_powers: Vec<E::G2Affine>,
+    pub g_beta_powers: Vec<E::G1Affine>,
+    pub h_beta_powers: Vec<E::G2Affine>,
+}
+
+/// The key of aggregating `n` proofs: the first `2n` powers of each string.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregationProverKey<E: PairingEngine> {
+    pub n: usize,
+    pub g_alpha_powers: Vec<E::G1Affine>,
+    pub h_alpha_powers: Vec<E::G2Affine>,
+    pub g_beta_powers: Vec<E::G1Affine>,
+    pub h_beta_powers: Vec<E::G2Affine>,
+}
+
+/// The key of verifying aggregates of any number of proofs the parameters
+/// allow.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AggregationVerifyKey<E: PairingEngine> {
+    pub g: E::G1Affine,
+    pub h: E::G2Affine,
+    pub g_alpha: E::G1Affine,
+    pub g_beta: E::G1Affine,
+    pub h_alpha: E::G2Affine,
+    pub h_beta: E::G2Affine,
+}
+
+fn fixed_base_powers<G: ProjectiveCurve>(
+    generator: G,
+    scalars: &[G::ScalarField],
+) -> Vec<G::Affine> {
+    let scalar_bits = <G::ScalarField as PrimeField>::size_in_bits();
+    let window = FixedBaseMSM::get_mul_window_size(scalars.len());
+    let table = FixedBaseMSM::get_window_table::<G>(scalar_bits, window, generator);
+    let powers = FixedBaseMSM::multi_scalar_mul::<G>(scalar_bits, window, &table, scalars);
+    G::batch_into_affine(powers)
+}
+
+impl<E: PairingEngine> AggregationParams<E> {
+    /// Generates the parameters of aggregating up to `max_proofs` of random
+    /// secrets, which must then be forgotten: as the parameters of
+    /// `generate_random_parameters`, for testing, or of a trusted party.
+    pub fn setup<R: Rng>(max_proofs: usize, rng: &mut R) -> Self {
+        let g = E::G1Projective::rand(rng);
+        let h = E::G2Projective::rand(rng);
+        let alpha = powers(E::Fr::rand(rng), 2 * max_proofs);
+        let beta = powers(E::Fr::rand(rng), 2 * max_proofs);
+
+        AggregationParams {
+            g_alpha_powers: fixed_base_powers(g, &alpha),
+            h_alpha_powers: fixed_base_powers(h, &alpha),
+            g_beta_powers: fixed_base_powers(g, &beta),
+            h_beta_powers: fixed_base_powers(h, &beta),
+        }
+    }
+
+    /// The number of proofs the parameters aggregate at most.
+    pub fn max_proofs(&self) -> usize {
+        [
+            self.g_alpha_powers.len(),
+            self.h_alpha_powers.len(),
+            self.g_beta_powers.len(),
+            self.h_beta_powers.len(),
+        ]
+        .iter()
+        .min()
+        .unwrap()
+            / 2
+    }
+
+    /// The keys of aggregating `n` proofs, a power of two, at least two, of
+    /// at most `max_proofs`, and of verifying the aggregates.
+    pub fn specialize(
+        &self,
+        n: usize,
+    ) -> Result<(AggregationProverKey<E>, AggregationVerifyKey<E>), SynthesisError> {
+        if n < 2 || !n.is_power_of_two() || n > self.max_proofs() {
+            return Err(SynthesisError::InvalidAggregationSize);
+        }
+        let same_generators = self.g_alpha_powers[0] == self.g_beta_powers[0]
+            && self.h_alpha_powers[0] == self.h_beta_powers[0];
+        if !same_generators {
+            return Err(SynthesisError::MalformedParameters);
+        }
+
+        let pk = AggregationProverKey {
+            n,
+            g_alpha_powers: self.g_alpha_powers[..2 * n].to_vec(),
+            h_alpha_powers: self.h_alpha_powers[..2 * n].to_vec(),
+            g_beta_powers: self.g_beta_powers[..2 * n].to_vec(),
+            h_beta_powers: self.h_beta_powers[..2 * n].to_vec(),
+        };
+        let vk = AggregationVerifyKey {
+            g: self.g_alpha_powers[0],
+            h: self.h_alpha_powers[0],
+            g_alpha: self.g_alpha_powers[1],
+            g_beta: self.g_beta_powers[1],
+            h_alpha: self.h_alpha_powers[1],
+            h_beta: self.h_beta_powers[1],
+        };
+        Ok((pk, vk))
+    }
+}
+
+impl<E: PairingEngine> AggregationVerifyKey<E> {
+    /// Checks every point of the key is on the curve and in the prime order
+    /// subgroup, and none is the identity.
+    pub fn check(&self) -> Result<(), SynthesisError> {
+        let g1 = [self.g, self.g_alpha, self.g_beta];
+        let g2 = [self.h, self.h_alpha, self.h_beta];
+        let valid = super::super::all_valid(&g1)
+            && super::super::all_valid(&g2)
+            && !g1.iter().any(|p| p.is_zero())
+            && !g2.iter().any(|p| p.is_zero());
+        if valid {
+            Ok(())
+        } else {
+            Err(SynthesisError::MalformedParameters)
+        }
+    }
+}
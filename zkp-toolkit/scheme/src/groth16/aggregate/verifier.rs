@@ -0,0 +1,205 @@
+use math::{AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve, Zero};
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::super::{all_valid, verifier::AsPreparedVerifyingKey};
+use super::{
+    fold_evaluation, pairing_product, powers, AggregateProof, AggregationVerifyKey, Commitment,
+    Transcript,
+};
+
+/// Verifies `aggregate` of the proofs of `vk` of the `public_inputs` of each,
+/// of the key `srs_vk` of the aggregation parameters, after checking the
+/// points of the aggregate are on the curve and in the prime order subgroup.
+pub fn verify_aggregate_proof<E: PairingEngine, K: AsPreparedVerifyingKey<E>>(
+    vk: &K,
+    srs_vk: &AggregationVerifyKey<E>,
+    public_inputs: &[Vec<E::Fr>],
+    aggregate: &AggregateProof<E>,
+) -> Result<bool, SynthesisError> {
+    let pvk = vk.as_prepared();
+    if public_inputs
+        .iter()
+        .any(|inputs| inputs.len() + 1 != pvk.gamma_abc_g1.len())
+    {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    let gipa = &aggregate.gipa;
+    let rounds = gipa.comms_ab.len();
+    let n = public_inputs.len();
+    if n < 2 || !n.is_power_of_two() || n != 1 << rounds {
+        return Err(SynthesisError::InvalidAggregationSize);
+    }
+    if gipa.comms_c.len() != rounds || gipa.z_ab.len() != rounds || gipa.z_c.len() != rounds {
+        return Ok(false);
+    }
+    check_points(aggregate)?;
+
+    let mut transcript = Transcript::new(n, public_inputs)?;
+    transcript.append(&aggregate.com_ab.0)?;
+    transcript.append(&aggregate.com_ab.1)?;
+    transcript.append(&aggregate.com_c.0)?;
+    transcript.append(&aggregate.com_c.1)?;
+    let r = transcript.challenge::<E::Fr>();
+    transcript.append(&aggregate.ip_ab)?;
+    transcript.append(&aggregate.agg_c)?;
+
+    // the commitments and the inner products of the last round, of the
+    // cross terms of every round to the challenge and to its inverse.
+    let mut com_ab = aggregate.com_ab;
+    let mut com_c = aggregate.com_c;
+    let mut z_ab = aggregate.ip_ab;
+    let mut z_c = aggregate.agg_c.into_projective();
+    let mut challenges = Vec::with_capacity(rounds);
+    for i in 0..rounds {
+        let (com_ab_l, com_ab_r) = gipa.comms_ab[i];
+        let (com_c_l, com_c_r) = gipa.comms_c[i];
+        for com in &[com_ab_l, com_ab_r, com_c_l, com_c_r] {
+            transcript.append(&com.0)?;
+            transcript.append(&com.1)?;
+        }
+        transcript.append(&gipa.z_ab[i].0)?;
+        transcript.append(&gipa.z_ab[i].1)?;
+        transcript.append(&gipa.z_c[i].0)?;
+        transcript.append(&gipa.z_c[i].1)?;
+        let x = transcript.challenge::<E::Fr>();
+        let x_inverse = x.inverse().unwrap();
+
+        com_ab = fold_commitment::<E>(com_ab, com_ab_l, com_ab_r, x, x_inverse);
+        com_c = fold_commitment::<E>(com_c, com_c_l, com_c_r, x, x_inverse);
+        z_ab *= &(gipa.z_ab[i].0.pow(x.into_repr()) * &gipa.z_ab[i].1.pow(x_inverse.into_repr()));
+        z_c += &gipa.z_c[i].0.mul(x.into_repr());
+        z_c += &gipa.z_c[i].1.mul(x_inverse.into_repr());
+        challenges.push(x);
+    }
+
+    transcript.append(&gipa.final_a)?;
+    transcript.append(&gipa.final_b)?;
+    transcript.append(&gipa.final_c)?;
+    transcript.append(&gipa.final_vkey.0)?;
+    transcript.append(&gipa.final_vkey.1)?;
+    transcript.append(&gipa.final_wkey.0)?;
+    transcript.append(&gipa.final_wkey.1)?;
+    let z = transcript.challenge::<E::Fr>();
+
+    let inverses = challenges
+        .iter()
+        .map(|x| x.inverse().unwrap())
+        .collect::<Vec<_>>();
+    let r_final = fold_evaluation(&inverses, r);
+
+    let (v_a, v_b) = gipa.final_vkey;
+    let (w_a, w_b) = gipa.final_wkey;
+    let (a, b, c) = (gipa.final_a, gipa.final_b, gipa.final_c);
+    let gipa_valid = pairing_product::<E>([(&a, &v_a), (&w_a, &b)].iter().cloned()) == com_ab.0
+        && pairing_product::<E>([(&a, &v_b), (&w_b, &b)].iter().cloned()) == com_ab.1
+        && E::pairing(a, b) == z_ab
+        && E::pairing(c, v_a) == com_c.0
+        && E::pairing(c, v_b) == com_c.1
+        && c.mul(r_final.into_repr()) == z_c;
+    if !gipa_valid {
+        return Ok(false);
+    }
+
+    // the last keys are of the polynomials of the challenges at the secrets.
+    let f_v = fold_evaluation(&inverses, z);
+    let f_w = z.pow(&[n as u64]) * &fold_evaluation(&challenges, z * &r.inverse().unwrap());
+    let g = srs_vk.g.into_projective();
+    let h = srs_vk.h.into_projective();
+    let kzg = &aggregate.kzg;
+    let opens = |pi: E::G1Affine, h_secret: E::G2Affine, key: E::G2Affine| {
+        let divisor = h_secret.into_projective() - &h.mul(z.into_repr());
+        let value = key.into_projective() - &h.mul(f_v.into_repr());
+        pairing_product::<E>(
+            [
+                (&pi, &divisor.into_affine()),
+                (&(-srs_vk.g), &value.into_affine()),
+            ]
+            .iter()
+            .cloned(),
+        )
+        .is_one()
+    };
+    let opens_w = |pi: E::G2Affine, g_secret: E::G1Affine, key: E::G1Affine| {
+        let divisor = g_secret.into_projective() - &g.mul(z.into_repr());
+        let value = key.into_projective() - &g.mul(f_w.into_repr());
+        pairing_product::<E>(
+            [
+                (&value.into_affine(), &srs_vk.h),
+                (&(-divisor).into_affine(), &pi),
+            ]
+            .iter()
+            .cloned(),
+        )
+        .is_one()
+    };
+    let kzg_valid = opens(kzg.vkey.0, srs_vk.h_alpha, v_a)
+        && opens(kzg.vkey.1, srs_vk.h_beta, v_b)
+        && opens_w(kzg.wkey.0, srs_vk.g_alpha, w_a)
+        && opens_w(kzg.wkey.1, srs_vk.g_beta, w_b);
+    if !kzg_valid {
+        return Ok(false);
+    }
+
+    // `prod_i (e(alpha, beta) e(vk_x_i, gamma) e(C_i, delta))^{r^i}`, of the
+    // sum of the inputs of the same index to the powers of `r`.
+    let r_powers = powers(r, n);
+    let r_sum = r_powers.iter().fold(E::Fr::zero(), |sum, r| sum + r);
+    let mut vk_x = pvk.gamma_abc_g1[0].mul(r_sum.into_repr());
+    for (j, ic) in pvk.gamma_abc_g1.iter().enumerate().skip(1) {
+        let input = public_inputs
+            .iter()
+            .zip(&r_powers)
+            .fold(E::Fr::zero(), |sum, (inputs, r)| sum + &(inputs[j - 1] * r));
+        vk_x += &ic.mul(input.into_repr());
+    }
+    let groth16 = aggregate.ip_ab
+        * &E::product_of_pairings(&[
+            (vk_x.into_affine().into(), pvk.gamma_g2_neg_pc.clone()),
+            (aggregate.agg_c.into(), pvk.delta_g2_neg_pc.clone()),
+        ]);
+
+    Ok(groth16 == pvk.alpha_g1_beta_g2.pow(r_sum.into_repr()))
+}
+
+/// `com * left^x * right^{x^{-1}}`, of both keys.
+fn fold_commitment<E: PairingEngine>(
+    com: Commitment<E>,
+    left: Commitment<E>,
+    right: Commitment<E>,
+    x: E::Fr,
+    x_inverse: E::Fr,
+) -> Commitment<E> {
+    let fold = |com: E::Fqk, left: E::Fqk, right: E::Fqk| {
+        com * &left.pow(x.into_repr()) * &right.pow(x_inverse.into_repr())
+    };
+    (fold(com.0, left.0, right.0), fold(com.1, left.1, right.1))
+}
+
+fn check_points<E: PairingEngine>(aggregate: &AggregateProof<E>) -> Result<(), SynthesisError> {
+    let gipa = &aggregate.gipa;
+    let mut g1 = vec![
+        aggregate.agg_c,
+        gipa.final_a,
+        gipa.final_c,
+        gipa.final_wkey.0,
+        gipa.final_wkey.1,
+        aggregate.kzg.vkey.0,
+        aggregate.kzg.vkey.1,
+    ];
+    g1.extend(gipa.z_c.iter().flat_map(|z| vec![z.0, z.1]));
+    let g2 = [
+        gipa.final_b,
+        gipa.final_vkey.0,
+        gipa.final_vkey.1,
+        aggregate.kzg.wkey.0,
+        aggregate.kzg.wkey.1,
+    ];
+    if all_valid(&g1) && all_valid(&g2) {
+        Ok(())
+    } else {
+        Err(SynthesisError::MalformedProof)
+    }
+}
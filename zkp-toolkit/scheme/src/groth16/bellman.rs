@@ -0,0 +1,300 @@
+//! The layout of bellman's `Parameters::write`, which the zcash Sapling
+//! parameters are distributed in:
+//!
+//! ```text
+//! alpha_g1 beta_g1 beta_g2 gamma_g2 delta_g1 delta_g2 [ic]
+//! [h] [l] [a] [b_g1] [b_g2]
+//! ```
+//!
+//! Every point is zcash-uncompressed, as of `ZcashEncoding`, and every `[..]`
+//! is a big-endian `u32` length followed by as many points, none at infinity.
+//!
+//! bellman leaves out the points at infinity of `a`, `b_g1` and `b_g2`, of
+//! the variables in no term of the A or the B of any constraint. Reading them
+//! back takes the circuit to tell which ones they were, and to record its
+//! fingerprint in the verifying key.
+
+use curve::bls12_381::{Bls12_381, Fr, G1Affine, G2Affine, ZcashEncoding};
+use math::fft::EvaluationDomain;
+use math::io::{Read, Result as IoResult, Write};
+use math::{error, AffineCurve, One, Zero};
+
+use crate::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, FingerprintCircuit, Index, SynthesisError,
+};
+use crate::Vec;
+
+use super::{generator::KeypairAssembly, streaming::MAX_RESERVED_POINTS, Parameters, VerifyKey};
+
+fn write_point<G: ZcashEncoding, W: Write>(point: &G, mut writer: W) -> IoResult<()> {
+    writer.write_all(&point.to_zcash_bytes())
+}
+
+fn read_point<G: ZcashEncoding, R: Read>(mut reader: R, checked: bool) -> IoResult<G> {
+    let mut bytes = vec![0u8; G::ZCASH_SIZE];
+    reader.read_exact(&mut bytes)?;
+    G::from_zcash_bytes(&bytes, checked)
+}
+
+fn write_points<G: ZcashEncoding + AffineCurve, W: Write>(
+    points: &[G],
+    mut writer: W,
+) -> IoResult<()> {
+    if points.len() > u32::max_value() as usize {
+        return Err(error("too many points for a bellman section"));
+    }
+    if points.iter().any(|p| p.is_zero()) {
+        return Err(error("point at infinity, which bellman does not read"));
+    }
+    writer.write_all(&(points.len() as u32).to_be_bytes())?;
+    for point in points {
+        write_point(point, &mut writer)?;
+    }
+    Ok(())
+}
+
+fn read_points<G: ZcashEncoding + AffineCurve, R: Read>(
+    mut reader: R,
+    checked: bool,
+) -> IoResult<Vec<G>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as u64;
+    let mut points = Vec::with_capacity(len.min(MAX_RESERVED_POINTS) as usize);
+    for _ in 0..len {
+        let point: G = read_point(&mut reader, checked)?;
+        if point.is_zero() {
+            return Err(error("point at infinity in a bellman section"));
+        }
+        points.push(point);
+    }
+    Ok(points)
+}
+
+/// The points of `query` but the ones at infinity, as bellman keeps them.
+fn dense<G: AffineCurve>(query: &[G]) -> Vec<G> {
+    query.iter().filter(|p| !p.is_zero()).cloned().collect()
+}
+
+/// `points` back at the variables of `density`, of the point at infinity at
+/// the others, failing unless there are as many points as variables.
+fn expand<G: AffineCurve>(points: Vec<G>, density: &[bool]) -> Result<Vec<G>, SynthesisError> {
+    if points.len() != density.iter().filter(|d| **d).count() {
+        return Err(SynthesisError::MalformedParameters);
+    }
+    let mut points = points.into_iter();
+    Ok(density
+        .iter()
+        .map(|d| {
+            if *d {
+                points.next().unwrap()
+            } else {
+                G::zero()
+            }
+        })
+        .collect())
+}
+
+/// Whether each variable, of the inputs first, is in a term of the
+/// constraints `rows`, as bellman's prover tracks the density of the queries.
+fn density(rows: &[Vec<(Fr, Index)>], num_inputs: usize, num_aux: usize) -> Vec<bool> {
+    let mut density = vec![false; num_inputs + num_aux];
+    for (coeff, index) in rows.iter().flatten() {
+        if !coeff.is_zero() {
+            match index {
+                Index::Input(i) => density[*i] = true,
+                Index::Aux(i) => density[num_inputs + i] = true,
+            }
+        }
+    }
+    density
+}
+
+impl Parameters<Bls12_381> {
+    /// Writes the parameters in the layout of bellman's `Parameters::write`,
+    /// leaving out the points at infinity of `a_query`, `b_g1_query` and
+    /// `b_g2_query`. Fails on a point at infinity of the other sections,
+    /// which bellman would not read, e.g. of an unconstrained variable.
+    pub fn write_bellman<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        let vk = &self.vk;
+        write_point(&vk.alpha_g1, &mut writer)?;
+        write_point(&self.beta_g1, &mut writer)?;
+        write_point(&vk.beta_g2, &mut writer)?;
+        write_point(&vk.gamma_g2, &mut writer)?;
+        write_point(&self.delta_g1, &mut writer)?;
+        write_point(&vk.delta_g2, &mut writer)?;
+        write_points(&vk.gamma_abc_g1, &mut writer)?;
+        write_points(&self.h_query, &mut writer)?;
+        write_points(&self.l_query, &mut writer)?;
+        write_points(&dense(&self.a_query), &mut writer)?;
+        write_points(&dense(&self.b_g1_query), &mut writer)?;
+        write_points(&dense(&self.b_g2_query), &mut writer)
+    }
+
+    /// Reads parameters of `circuit` written by bellman's `Parameters::write`,
+    /// e.g. the zcash Sapling parameters of a circuit synthesized here as
+    /// bellman does. If `checked`, every point is checked to be on the curve
+    /// and in the prime order subgroup, as bellman's `read` does.
+    ///
+    /// Unlike bellman's `Parameters::read(reader, checked)`, it also takes the
+    /// circuit: the bytes alone do not tell which variables the points at
+    /// infinity left out of `a`, `b_g1` and `b_g2` were of, nor the
+    /// fingerprint the prover checks.
+    ///
+    /// Fails with `SynthesisError::MalformedParameters` when the sizes of the
+    /// sections are not the ones of the circuit.
+    pub fn read_bellman<R, C>(
+        mut reader: R,
+        checked: bool,
+        circuit: C,
+    ) -> Result<Self, SynthesisError>
+    where
+        R: Read,
+        C: ConstraintSynthesizer<Fr>,
+    {
+        let alpha_g1: G1Affine = read_point(&mut reader, checked)?;
+        let beta_g1: G1Affine = read_point(&mut reader, checked)?;
+        let beta_g2: G2Affine = read_point(&mut reader, checked)?;
+        let gamma_g2: G2Affine = read_point(&mut reader, checked)?;
+        let delta_g1: G1Affine = read_point(&mut reader, checked)?;
+        let delta_g2: G2Affine = read_point(&mut reader, checked)?;
+        let gamma_abc_g1 = read_points(&mut reader, checked)?;
+        let h_query = read_points(&mut reader, checked)?;
+        let l_query = read_points(&mut reader, checked)?;
+        let a_query = read_points(&mut reader, checked)?;
+        let b_g1_query = read_points(&mut reader, checked)?;
+        let b_g2_query = read_points(&mut reader, checked)?;
+
+        let mut assembly = KeypairAssembly::<Bls12_381> {
+            num_inputs: 0,
+            num_aux: 0,
+            num_constraints: 0,
+            at: vec![],
+            bt: vec![],
+            ct: vec![],
+        };
+        assembly.alloc_input(|| "", || Ok(Fr::one()))?;
+        let mut fingerprint = [0u8; 32];
+        FingerprintCircuit::record(circuit, &mut fingerprint)
+            .generate_constraints(&mut assembly)?;
+
+        let (num_inputs, num_aux) = (assembly.num_inputs, assembly.num_aux);
+        let domain = EvaluationDomain::<Fr>::new(assembly.num_constraints + num_inputs)
+            .ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
+        if gamma_abc_g1.len() != num_inputs
+            || l_query.len() != num_aux
+            || h_query.len() != domain.size() - 1
+        {
+            return Err(SynthesisError::MalformedParameters);
+        }
+
+        // every input is in the A of the constraint bellman adds for it.
+        let mut a_density = density(&assembly.at, num_inputs, num_aux);
+        a_density[..num_inputs].iter_mut().for_each(|d| *d = true);
+        let b_density = density(&assembly.bt, num_inputs, num_aux);
+
+        Ok(Parameters {
+            vk: VerifyKey {
+                alpha_g1,
+                beta_g2,
+                gamma_g2,
+                delta_g2,
+                gamma_abc_g1,
+                fingerprint,
+            },
+            beta_g1,
+            delta_g1,
+            a_query: expand(a_query, &a_density)?,
+            b_g1_query: expand(b_g1_query, &b_density)?,
+            b_g2_query: expand(b_g2_query, &b_density)?,
+            h_query,
+            l_query,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::{test_rng, Field, UniformRand};
+
+    use super::*;
+    use crate::groth16::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+        Proof,
+    };
+    use crate::test_circuits::{Square, Toy};
+
+    #[test]
+    fn test_bellman_fixture() {
+        // written by bellman 0.14 over bls12_381 0.8, of the same `Toy`, the
+        // parameters of `generate_random_parameters` and the proof of x = 3,
+        // of an `XorShiftRng` of the seed `[7; 16]`.
+        let bytes = &include_bytes!("../../tests/snapshots/bellman_toy.params")[..];
+        let proof = &include_bytes!("../../tests/snapshots/bellman_toy.proof")[..];
+
+        let params = Parameters::<Bls12_381>::read_bellman(bytes, true, Toy { x: None }).unwrap();
+        let mut written = vec![];
+        params.write_bellman(&mut written).unwrap();
+        assert_eq!(written, bytes);
+
+        // bellman writes its proofs of the compressed points, as here.
+        let pvk = prepare_verifying_key(&params.vk);
+        let proof = Proof::<Bls12_381>::from_compressed_bytes(proof).unwrap();
+        assert!(verify_proof(&pvk, &proof, &[Fr::from(12u64)]).unwrap());
+        assert!(!verify_proof(&pvk, &proof, &[Fr::from(3u64)]).unwrap());
+
+        let rng = &mut test_rng();
+        let x = Fr::from(5u64);
+        let proof = create_random_proof(&params, Toy { x: Some(x) }, rng).unwrap();
+        assert!(verify_proof(&pvk, &proof, &[Fr::from(30u64)]).unwrap());
+    }
+
+    #[test]
+    fn test_bellman() {
+        let rng = &mut test_rng();
+        let params = generate_random_parameters::<Bls12_381, _, _>(Toy { x: None }, rng).unwrap();
+
+        let mut bytes = vec![];
+        params.write_bellman(&mut bytes).unwrap();
+
+        // the vk, then the sections of 2 inputs, 2 aux and a domain of 4,
+        // of `a` of all of the 4 variables and `b` of `1` and `x` only.
+        let vk_size = 3 * G1Affine::ZCASH_SIZE + 3 * G2Affine::ZCASH_SIZE;
+        let sections = [(2, 96), (3, 96), (2, 96), (4, 96), (2, 96), (2, 192)];
+        let size = vk_size + sections.iter().map(|(n, s)| 4 + n * s).sum::<usize>();
+        assert_eq!(bytes.len(), size);
+        assert_eq!(&bytes[..96], &params.vk.alpha_g1.to_zcash_bytes()[..]);
+        assert_eq!(&bytes[vk_size..vk_size + 4], &[0, 0, 0, 2]);
+
+        let read =
+            Parameters::<Bls12_381>::read_bellman(&bytes[..], true, Toy { x: None }).unwrap();
+        assert_eq!(read, params);
+        let mut written = vec![];
+        read.write_bellman(&mut written).unwrap();
+        assert_eq!(written, bytes);
+
+        let x = Fr::rand(rng);
+        let y = x.square() + &x;
+        let proof = create_random_proof(&read, Toy { x: Some(x) }, rng).unwrap();
+        let pvk = prepare_verifying_key(&read.vk);
+        assert!(verify_proof(&pvk, &proof, &[y]).unwrap());
+        assert!(!verify_proof(&pvk, &proof, &[x]).unwrap());
+
+        // the sections are not of a circuit of another shape.
+        let result = Parameters::<Bls12_381>::read_bellman(&bytes[..], true, Square { x: None });
+        assert!(matches!(result, Err(SynthesisError::MalformedParameters)));
+
+        let result =
+            Parameters::<Bls12_381>::read_bellman(&bytes[..size - 1], true, Toy { x: None });
+        assert!(matches!(result, Err(SynthesisError::IoError(_))));
+
+        // a point off the curve is read only unchecked.
+        let mut corrupted = bytes.clone();
+        corrupted[95] ^= 1;
+        let result = Parameters::<Bls12_381>::read_bellman(&corrupted[..], true, Toy { x: None });
+        assert!(matches!(result, Err(SynthesisError::IoError(_))));
+        assert!(
+            Parameters::<Bls12_381>::read_bellman(&corrupted[..], false, Toy { x: None }).is_ok()
+        );
+    }
+}
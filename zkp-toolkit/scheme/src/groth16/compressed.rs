@@ -0,0 +1,119 @@
+use math::curves::models::{short_weierstrass_jacobian::GroupAffine, SWModelParameters};
+use math::{error, PairingEngine};
+
+use crate::r1cs::SynthesisError;
+use crate::Vec;
+
+use super::Proof;
+
+impl<E, P1, P2> Proof<E>
+where
+    E: PairingEngine<G1Affine = GroupAffine<P1>, G2Affine = GroupAffine<P2>>,
+    P1: SWModelParameters,
+    P2: SWModelParameters,
+{
+    /// The size in bytes of `to_compressed_bytes`: 128 on bn_256, 192 on
+    /// bls12_381 and bls12_377.
+    pub fn compressed_size() -> usize {
+        2 * GroupAffine::<P1>::compressed_size() + GroupAffine::<P2>::compressed_size()
+    }
+
+    /// Returns `a`, `b` and `c`, each as written by `serialize_compressed`,
+    /// of no framing, e.g. for the witness of an on-chain verifier.
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>, SynthesisError> {
+        let mut bytes = Vec::with_capacity(Self::compressed_size());
+        self.a.serialize_compressed(&mut bytes)?;
+        self.b.serialize_compressed(&mut bytes)?;
+        self.c.serialize_compressed(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Reads a proof written by `to_compressed_bytes`. Fails unless `bytes`
+    /// are exactly `compressed_size` long and every point is on the curve and
+    /// in the prime order subgroup.
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self, SynthesisError> {
+        if bytes.len() != Self::compressed_size() {
+            return Err(error("invalid length of a compressed proof").into());
+        }
+        let (a, bytes) = bytes.split_at(GroupAffine::<P1>::compressed_size());
+        let (b, c) = bytes.split_at(GroupAffine::<P2>::compressed_size());
+        let a = GroupAffine::<P1>::deserialize_compressed(a)?;
+        let b = GroupAffine::<P2>::deserialize_compressed(b)?;
+        let c = GroupAffine::<P1>::deserialize_compressed(c)?;
+        let proof = Proof { a, b, c };
+        proof.check()?;
+
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::{test_rng, Field, UniformRand};
+
+    use super::*;
+    use crate::groth16::{
+        create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+    };
+    use crate::test_circuits::Square;
+
+    fn compressed_test<E, P1, P2>(size: usize)
+    where
+        E: PairingEngine<G1Affine = GroupAffine<P1>, G2Affine = GroupAffine<P2>>,
+        P1: SWModelParameters,
+        P2: SWModelParameters,
+    {
+        let rng = &mut test_rng();
+        let x = E::Fr::rand(rng);
+        let params = generate_random_parameters::<E, _, _>(Square { x: Some(x) }, rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let proof = create_random_proof(&params, Square { x: Some(x) }, rng).unwrap();
+
+        let bytes = proof.to_compressed_bytes().unwrap();
+        assert_eq!(bytes.len(), size);
+        assert_eq!(Proof::<E>::compressed_size(), size);
+        let decoded = Proof::<E>::from_compressed_bytes(&bytes).unwrap();
+        assert_eq!(
+            (decoded.a, decoded.b, decoded.c),
+            (proof.a, proof.b, proof.c)
+        );
+        assert!(verify_proof(&pvk, &decoded, &[x.square()]).unwrap());
+
+        assert!(Proof::<E>::from_compressed_bytes(&bytes[..size - 1]).is_err());
+        assert!(Proof::<E>::from_compressed_bytes(&[&bytes[..], &[0]].concat()).is_err());
+
+        // an x-coordinate of `a` off the curve.
+        let g1_size = GroupAffine::<P1>::compressed_size();
+        let mut off_curve = bytes.clone();
+        while GroupAffine::<P1>::deserialize_compressed(&off_curve[..]).is_ok() {
+            off_curve[g1_size - 1] = off_curve[g1_size - 1].wrapping_add(1);
+        }
+        assert!(Proof::<E>::from_compressed_bytes(&off_curve).is_err());
+
+        // a `b` on the curve, out of the prime order subgroup.
+        let b = loop {
+            let x = P2::BaseField::rand(rng);
+            if let Some(b) = GroupAffine::<P2>::get_point_from_x(x, false) {
+                if !b.is_in_correct_subgroup_assuming_on_curve() {
+                    break b;
+                }
+            }
+        };
+        let b_size = GroupAffine::<P2>::compressed_size();
+        let mut b_bytes = Vec::new();
+        b.serialize_compressed(&mut b_bytes).unwrap();
+        let mut off_subgroup = bytes;
+        off_subgroup[g1_size..g1_size + b_size].copy_from_slice(&b_bytes);
+        assert!(Proof::<E>::from_compressed_bytes(&off_subgroup).is_err());
+    }
+
+    #[test]
+    fn test_compressed_bn_256() {
+        compressed_test::<curve::bn_256::Bn_256, _, _>(128);
+    }
+
+    #[test]
+    fn test_compressed_bls12_381() {
+        compressed_test::<curve::bls12_381::Bls12_381, _, _>(192);
+    }
+}
@@ -0,0 +1,20 @@
+use curve::bn_256::{Bn_256, EthereumEncoding};
+
+use super::Proof;
+
+impl Proof<Bn_256> {
+    /// Returns the proof as the eight 32-byte words taken by Solidity
+    /// verifiers on bn254: `a.x, a.y, b.x.c1, b.x.c0, b.y.c1, b.y.c0, c.x, c.y`,
+    /// each coordinate big-endian as in EIP-197.
+    pub fn to_ethereum_encoding(&self) -> [[u8; 32]; 8] {
+        let mut bytes = self.a.to_ethereum_bytes();
+        bytes.extend(self.b.to_ethereum_bytes());
+        bytes.extend(self.c.to_ethereum_bytes());
+
+        let mut words = [[0u8; 32]; 8];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(32)) {
+            word.copy_from_slice(chunk);
+        }
+        words
+    }
+}
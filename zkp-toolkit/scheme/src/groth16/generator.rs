@@ -1,8 +1,9 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
 use math::{
-    fft::EvaluationDomain, msm::FixedBaseMSM, Field, One, PairingEngine, PrimeField,
-    ProjectiveCurve, UniformRand, Zero,
+    fft::EvaluationDomain,
+    msm::{FixedBaseMSM, Wnaf},
+    Field, One, PairingEngine, PrimeField, ProjectiveCurve, UniformRand, Zero,
 };
 use rand::Rng;
 
@@ -10,7 +11,8 @@ use rand::Rng;
 use rayon::prelude::*;
 
 use crate::r1cs::{
-    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+    ConstraintSynthesizer, ConstraintSystem, FingerprintCircuit, Index, LinearCombination,
+    SynthesisError, Variable,
 };
 use crate::{String, Vec};
 
@@ -131,6 +133,14 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for KeypairAssembly<E> {
     fn num_constraints(&self) -> usize {
         self.num_constraints
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_aux
+    }
 }
 
 /// Create parameters for a circuit, given some toxic waste.
@@ -160,7 +170,8 @@ where
     assembly.alloc_input(|| "", || Ok(E::Fr::one()))?;
 
     // Synthesize the circuit.
-    circuit.generate_constraints(&mut assembly)?;
+    let mut fingerprint = [0u8; 32];
+    FingerprintCircuit::record(circuit, &mut fingerprint).generate_constraints(&mut assembly)?;
 
     ///////////////////////////////////////////////////////////////////////////
 
@@ -210,18 +221,24 @@ where
         FixedBaseMSM::get_window_table::<E::G1Projective>(scalar_bits, g1_window, g1_generator);
 
     // Generate the R1CS proving key
-    let alpha_g1 = g1_generator.mul(alpha);
-    let beta_g1 = g1_generator.mul(beta);
-    let beta_g2 = g2_generator.mul(beta);
-    let delta_g1 = g1_generator.mul(delta);
-    let delta_g2 = g2_generator.mul(delta);
+    let mut g1_wnaf = Wnaf::new();
+    let mut g1_wnaf = g1_wnaf.base(g1_generator, 3);
+    let alpha_g1 = g1_wnaf.scalar(&alpha);
+    let beta_g1 = g1_wnaf.scalar(&beta);
+    let delta_g1 = g1_wnaf.scalar(&delta);
+
+    let mut g2_wnaf = Wnaf::new();
+    let mut g2_wnaf = g2_wnaf.base(g2_generator, 3);
+    let beta_g2 = g2_wnaf.scalar(&beta);
+    let gamma_g2 = g2_wnaf.scalar(&gamma);
+    let delta_g2 = g2_wnaf.scalar(&delta);
 
     // Compute the A-query
-    let mut a_query =
+    let a_query =
         FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(scalar_bits, g1_window, &g1_table, &a);
 
     // Compute the B-query in G1
-    let mut b_g1_query =
+    let b_g1_query =
         FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(scalar_bits, g1_window, &g1_table, &b);
 
     // Compute B window table
@@ -230,11 +247,11 @@ where
         FixedBaseMSM::get_window_table::<E::G2Projective>(scalar_bits, g2_window, g2_generator);
 
     // Compute the B-query in G2
-    let mut b_g2_query =
+    let b_g2_query =
         FixedBaseMSM::multi_scalar_mul::<E::G2Projective>(scalar_bits, g2_window, &g2_table, &b);
 
     // Compute the H-query
-    let mut h_query = FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(
+    let h_query = FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(
         scalar_bits,
         g1_window,
         &g1_table,
@@ -246,10 +263,9 @@ where
     // Compute the L-query
     let l_query =
         FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(scalar_bits, g1_window, &g1_table, &l);
-    let mut l_query = l_query[assembly.num_inputs..].to_vec();
+    let l_query = l_query[assembly.num_inputs..].to_vec();
 
     // Generate R1CS verification key
-    let gamma_g2 = g2_generator.mul(gamma);
     let gamma_abc_g1 = FixedBaseMSM::multi_scalar_mul::<E::G1Projective>(
         scalar_bits,
         g1_window,
@@ -264,25 +280,18 @@ where
         beta_g2: beta_g2.into_affine(),
         gamma_g2: gamma_g2.into_affine(),
         delta_g2: delta_g2.into_affine(),
-        gamma_abc_g1: cfg_iter!(gamma_abc_g1)
-            .map(|p| p.into_affine())
-            .collect::<Vec<_>>(),
+        gamma_abc_g1: E::G1Projective::batch_into_affine(gamma_abc_g1),
+        fingerprint,
     };
 
-    E::G1Projective::batch_normalization(a_query.as_mut_slice());
-    E::G1Projective::batch_normalization(b_g1_query.as_mut_slice());
-    E::G2Projective::batch_normalization(b_g2_query.as_mut_slice());
-    E::G1Projective::batch_normalization(h_query.as_mut_slice());
-    E::G1Projective::batch_normalization(l_query.as_mut_slice());
-
     Ok(Parameters {
         vk,
         beta_g1: beta_g1.into_affine(),
         delta_g1: delta_g1.into_affine(),
-        a_query: a_query.into_iter().map(Into::into).collect(),
-        b_g1_query: b_g1_query.into_iter().map(Into::into).collect(),
-        b_g2_query: b_g2_query.into_iter().map(Into::into).collect(),
-        h_query: h_query.into_iter().map(Into::into).collect(),
-        l_query: l_query.into_iter().map(Into::into).collect(),
+        a_query: E::G1Projective::batch_into_affine(a_query),
+        b_g1_query: E::G1Projective::batch_into_affine(b_g1_query),
+        b_g2_query: E::G2Projective::batch_into_affine(b_g2_query),
+        h_query: E::G1Projective::batch_into_affine(h_query),
+        l_query: E::G1Projective::batch_into_affine(l_query),
     })
 }
@@ -1,6 +1,7 @@
-use math::{Field, PairingEngine};
+use math::{AffineCurve, Field, PairingEngine};
 
-use crate::r1cs::{Index, LinearCombination, SynthesisError};
+use crate::key_fingerprint::{key_fingerprint, KeyFingerprint, SchemeTag};
+use crate::r1cs::{CircuitFingerprint, Index, LinearCombination, SynthesisError};
 use crate::Vec;
 
 /// Reduce an R1CS instance to a *Quadratic Arithmetic Program* instance.
@@ -15,18 +16,65 @@ pub mod prover;
 /// Verify proofs for the Groth16 zkSNARK construction.
 pub mod verifier;
 
+/// Compact encoding of proofs, of compressed points.
+pub mod compressed;
+
+/// Streaming encoding of parameters, and the sources the prover reads them of.
+pub mod streaming;
+
+/// Aggregation of many proofs of one circuit into a proof of logarithmic size.
+pub mod aggregate;
+
+/// Ethereum-compatible encoding of proofs over bn_256.
+#[cfg(feature = "ethereum")]
+pub mod ethereum;
+
+/// Solidity verifier contracts of bn_256 verifying keys.
+#[cfg(feature = "ethereum")]
+pub mod solidity;
+
+/// bellman-compatible encoding of bls12_381 parameters, e.g. of zcash Sapling.
+#[cfg(feature = "bellman")]
+pub mod bellman;
+
 /// standard interface for setup with circuit.
 pub use generator::generate_random_parameters;
 
 /// standard interface for create proof.
 pub use prover::create_random_proof;
 
+/// standard interface for create proof with a witness generated apart.
+pub use prover::prove_with_witness;
+
+/// interface for create proof of the blinding derived from a seed.
+pub use prover::create_proof_deterministic;
+
+/// interface for create proof of parameters read apart, e.g. of a mapped file.
+pub use prover::create_random_proof_from_source;
+
+#[cfg(feature = "mmap")]
+pub use streaming::MappedParameters;
+/// interface for the parameters the prover takes a query at a time.
+pub use streaming::ParametersSource;
+
+/// interface for aggregate many proofs of one circuit, and verify the aggregates.
+pub use aggregate::{aggregate_proofs, verify_aggregate_proof, AggregateProof, AggregationParams};
+
 /// standard interface for verify proof.
 pub use verifier::verify_proof;
 
+/// interface for verify proof which was checked apart.
+pub use verifier::verify_proof_unchecked;
+
 /// standard interface for prepare compute verify key.
 pub use verifier::prepare_verifying_key;
 
+/// interface for verify many proofs of one key, of no allocation per proof.
+pub use verifier::Verifier;
+
+/// interface for verify many proofs which only vary some public inputs.
+pub use verifier::{prepare_inputs_partial, verify_proof_with_prepared_inputs, PartialInputs};
+
 /// A proof in the Groth16 SNARK.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Proof<E: PairingEngine> {
@@ -43,6 +91,8 @@ pub struct VerifyKey<E: PairingEngine> {
     pub gamma_g2: E::G2Affine,
     pub delta_g2: E::G2Affine,
     pub gamma_abc_g1: Vec<E::G1Affine>,
+    /// The fingerprint of the circuit the key was generated for.
+    pub fingerprint: CircuitFingerprint,
 }
 
 impl<E: PairingEngine> Default for VerifyKey<E> {
@@ -53,10 +103,19 @@ impl<E: PairingEngine> Default for VerifyKey<E> {
             gamma_g2: E::G2Affine::default(),
             delta_g2: E::G2Affine::default(),
             gamma_abc_g1: Vec::new(),
+            fingerprint: [0u8; 32],
         }
     }
 }
 
+impl<E: PairingEngine> VerifyKey<E> {
+    /// The fingerprint of the key, of the scheme and the curve, to tell which
+    /// key a file is of. Not `fingerprint`, the circuit's the key is of.
+    pub fn key_fingerprint(&self) -> KeyFingerprint {
+        key_fingerprint::<E::G1Affine, _>(SchemeTag::Groth16, self)
+    }
+}
+
 /// Full public (prover and verifier) parameters for the Groth16 zkSNARK.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Parameters<E: PairingEngine> {
@@ -105,6 +164,59 @@ impl<E: PairingEngine> Default for PreparedVerifyingKey<E> {
     }
 }
 
+/// Whether all the `points` are on the curve and in the prime order subgroup.
+fn all_valid<G: AffineCurve>(points: &[G]) -> bool {
+    points
+        .iter()
+        .all(|p| p.is_on_curve() && p.is_in_correct_subgroup_assuming_on_curve())
+}
+
+impl<E: PairingEngine> Proof<E> {
+    /// Checks every point of the proof is on the curve and in the prime order
+    /// subgroup, as `verify_proof` does.
+    pub fn check(&self) -> Result<(), SynthesisError> {
+        if all_valid(&[self.a, self.c]) && all_valid(&[self.b]) {
+            Ok(())
+        } else {
+            Err(SynthesisError::MalformedProof)
+        }
+    }
+}
+
+impl<E: PairingEngine> VerifyKey<E> {
+    /// Checks every point of the key is on the curve and in the prime order
+    /// subgroup, e.g. of a key read from untrusted bytes.
+    pub fn check(&self) -> Result<(), SynthesisError> {
+        if all_valid(&[self.alpha_g1])
+            && all_valid(&self.gamma_abc_g1)
+            && all_valid(&[self.beta_g2, self.gamma_g2, self.delta_g2])
+        {
+            Ok(())
+        } else {
+            Err(SynthesisError::MalformedVerifyingKey)
+        }
+    }
+}
+
+impl<E: PairingEngine> Parameters<E> {
+    /// Checks every point of the parameters, the verifying key's included,
+    /// is on the curve and in the prime order subgroup.
+    pub fn check(&self) -> Result<(), SynthesisError> {
+        self.vk.check()?;
+        if all_valid(&[self.beta_g1, self.delta_g1])
+            && all_valid(&self.a_query)
+            && all_valid(&self.b_g1_query)
+            && all_valid(&self.h_query)
+            && all_valid(&self.l_query)
+            && all_valid(&self.b_g2_query)
+        {
+            Ok(())
+        } else {
+            Err(SynthesisError::MalformedParameters)
+        }
+    }
+}
+
 fn push_constraints<F: Field>(
     l: LinearCombination<F>,
     constraints: &mut [Vec<(F, Index)>],
@@ -1,16 +1,20 @@
+use blake2::{Blake2s, Digest};
 use math::{
-    msm::VariableBaseMSM, AffineCurve, One, PairingEngine, PrimeField, ProjectiveCurve,
-    UniformRand, Zero,
+    msm::VariableBaseMSM, AffineCurve, Field, One, PairingEngine, PrimeField, ProjectiveCurve,
+    ToBytes, UniformRand, Zero,
 };
 use rand::Rng;
 
 use crate::r1cs::{
-    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+    ConstraintSynthesizer, ConstraintSystem, FingerprintCircuit, Index, LinearCombination,
+    SynthesisError, Variable, Witness, WitnessCircuit,
 };
 
 use crate::{String, Vec};
 
-use super::{push_constraints, r1cs_to_qap::R1CStoQAP, Parameters, Proof};
+use super::{
+    push_constraints, r1cs_to_qap::R1CStoQAP, streaming::ParametersSource, Parameters, Proof,
+};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -94,6 +98,14 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for ProvingAssignment<E> {
     fn num_constraints(&self) -> usize {
         self.at.len()
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.input_assignment.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
 }
 
 pub fn create_random_proof<E, C, R>(
@@ -112,6 +124,43 @@ where
     create_proof::<E, C>(params, circuit, r, s)
 }
 
+/// Proves `circuit` of the parameters of `source`, e.g. `MappedParameters`
+/// of a file, taking every query of it only while it is used.
+pub fn create_random_proof_from_source<E, C, R, P>(
+    source: &P,
+    circuit: C,
+    rng: &mut R,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    R: Rng,
+    P: ParametersSource<E>,
+{
+    let r = E::Fr::rand(rng);
+    let s = E::Fr::rand(rng);
+
+    let prover = synthesize(source, circuit)?;
+    prove_assignment(source, prover, r, s)
+}
+
+/// Proves `circuit` of the values of `witness`, generated apart, e.g. on
+/// another machine. Only the shape of the constraints of `circuit` is
+/// synthesized, as it is not in `params`.
+pub fn prove_with_witness<E, C, R>(
+    params: &Parameters<E>,
+    circuit: C,
+    witness: &Witness<E::Fr>,
+    rng: &mut R,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    R: Rng,
+{
+    create_random_proof(params, WitnessCircuit::new(circuit, witness)?, rng)
+}
+
 pub fn create_proof_no_zk<E, C>(
     params: &Parameters<E>,
     circuit: C,
@@ -132,6 +181,62 @@ pub fn create_proof<E, C>(
 where
     E: PairingEngine,
     C: ConstraintSynthesizer<E::Fr>,
+{
+    let prover = synthesize(params, circuit)?;
+    prove_assignment(params, prover, r, s)
+}
+
+/// Proves `circuit` of the blinding scalars `r` and `s` derived from `seed`,
+/// the fingerprint of the circuit and its witness, so that the same seed
+/// and witness give the same proof, e.g. for test fixtures. The seed is to
+/// be kept secret as the randomness of `create_random_proof` is: the proofs
+/// of a known seed do not hide the witness.
+///
+/// The derivation is `d = BLAKE2s(seed || fingerprint || witness)`, of the
+/// `ToBytes` of every input, the "one" input first, and then of every aux
+/// value, and `r` (`s`) is the first `BLAKE2s(d || "r" || i)` (`"s"`), of the
+/// counter `i` as a little-endian `u64` from zero, that `from_random_bytes`
+/// takes as a field element.
+pub fn create_proof_deterministic<E, C>(
+    params: &Parameters<E>,
+    circuit: C,
+    seed: [u8; 32],
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+{
+    let prover = synthesize(params, circuit)?;
+
+    let mut hasher = Blake2s::new();
+    hasher.input(&seed);
+    hasher.input(&params.vk.fingerprint);
+    for value in prover.input_assignment.iter().chain(&prover.aux_assignment) {
+        hasher.input(&math::to_bytes!(value)?);
+    }
+    let digest = hasher.result();
+    let derive = |label: &[u8]| {
+        (0u64..)
+            .find_map(|i| {
+                let mut hasher = Blake2s::new();
+                hasher.input(&digest);
+                hasher.input(label);
+                hasher.input(&i.to_le_bytes());
+                E::Fr::from_random_bytes(&hasher.result())
+            })
+            .unwrap()
+    };
+    let (r, s) = (derive(b"r"), derive(b"s"));
+
+    prove_assignment(params, prover, r, s)
+}
+
+/// Synthesizes `circuit`, checking it is of the fingerprint of the key.
+fn synthesize<E, C, P>(params: &P, circuit: C) -> Result<ProvingAssignment<E>, SynthesisError>
+where
+    E: PairingEngine,
+    C: ConstraintSynthesizer<E::Fr>,
+    P: ParametersSource<E>,
 {
     let mut prover = ProvingAssignment {
         at: vec![],
@@ -144,9 +249,25 @@ where
     // Allocate the "one" input variable
     prover.alloc_input(|| "", || Ok(E::Fr::one()))?;
 
-    // Synthesize the circuit.
-    circuit.generate_constraints(&mut prover)?;
+    // Synthesize the circuit, of the fingerprint of the key.
+    FingerprintCircuit::check(circuit, &params.vk().fingerprint)
+        .generate_constraints(&mut prover)?;
+
+    Ok(prover)
+}
 
+/// Proves the assignment of `prover`, taking the queries of `params` one
+/// after another, so that a source decoding them holds one at a time.
+fn prove_assignment<E, P>(
+    params: &P,
+    prover: ProvingAssignment<E>,
+    r: E::Fr,
+    s: E::Fr,
+) -> Result<Proof<E>, SynthesisError>
+where
+    E: PairingEngine,
+    P: ParametersSource<E>,
+{
     let h = R1CStoQAP::witness_map::<E>(&prover)?;
 
     let input_assignment = prover.input_assignment[1..]
@@ -163,17 +284,18 @@ where
     let h_assignment = cfg_into_iter!(h).map(|s| s.into_repr()).collect::<Vec<_>>();
 
     // Compute A
-    let a_query = params.get_a_query_full()?;
-    let r_g1 = params.delta_g1.mul(r);
+    let a_query = params.a_query()?;
+    let r_g1 = params.delta_g1().mul(r);
 
-    let g_a = calculate_coeff(r_g1, a_query, params.vk.alpha_g1, &assignment);
+    let g_a = calculate_coeff(r_g1, &a_query, params.vk().alpha_g1, &assignment);
+    drop(a_query);
 
     // Compute B in G1 if needed
     let g1_b = if r != E::Fr::zero() {
-        let s_g1 = params.delta_g1.mul(s);
-        let b_query = params.get_b_g1_query_full()?;
+        let s_g1 = params.delta_g1().mul(s);
+        let b_query = params.b_g1_query()?;
 
-        let g1_b = calculate_coeff(s_g1, b_query, params.beta_g1, &assignment);
+        let g1_b = calculate_coeff(s_g1, &b_query, params.beta_g1(), &assignment);
 
         g1_b
     } else {
@@ -181,19 +303,22 @@ where
     };
 
     // Compute B in G2
-    let b_query = params.get_b_g2_query_full()?;
-    let s_g2 = params.vk.delta_g2.mul(s);
-    let g2_b = calculate_coeff(s_g2, b_query, params.vk.beta_g2, &assignment);
+    let b_query = params.b_g2_query()?;
+    let s_g2 = params.vk().delta_g2.mul(s);
+    let g2_b = calculate_coeff(s_g2, &b_query, params.vk().beta_g2, &assignment);
+    drop(b_query);
 
-    let h_query = params.get_h_query_full()?;
+    let h_query = params.h_query()?;
     let h_acc = VariableBaseMSM::multi_scalar_mul(&h_query, &h_assignment);
+    drop(h_query);
 
-    let l_aux_source = params.get_l_query_full()?;
-    let l_aux_acc = VariableBaseMSM::multi_scalar_mul(l_aux_source, &aux_assignment);
+    let l_aux_source = params.l_query()?;
+    let l_aux_acc = VariableBaseMSM::multi_scalar_mul(&l_aux_source, &aux_assignment);
+    drop(l_aux_source);
 
     let s_g_a = g_a.mul(s);
     let r_g1_b = g1_b.mul(r);
-    let r_s_delta_g1 = params.delta_g1.into_projective().mul(r).mul(s);
+    let r_s_delta_g1 = params.delta_g1().into_projective().mul(r).mul(s);
 
     let mut g_c = s_g_a;
     g_c += &r_g1_b;
@@ -201,10 +326,12 @@ where
     g_c += &l_aux_acc;
     g_c += &h_acc;
 
+    let g1 = E::G1Projective::batch_into_affine(vec![g_a, g_c]);
+
     Ok(Proof {
-        a: g_a.into_affine(),
+        a: g1[0],
         b: g2_b.into_affine(),
-        c: g_c.into_affine(),
+        c: g1[1],
     })
 }
 
@@ -224,3 +351,45 @@ fn calculate_coeff<G: AffineCurve>(
 
     res
 }
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::{Bn_256, Fr};
+    use math::test_rng;
+
+    use super::*;
+    use crate::groth16::{generate_random_parameters, prepare_verifying_key, verify_proof};
+    use crate::test_circuits::Square;
+
+    #[test]
+    fn test_create_proof_deterministic() {
+        let rng = &mut test_rng();
+        let (x, other) = (Fr::from(3u32), Fr::from(5u32));
+        let params =
+            generate_random_parameters::<Bn_256, _, _>(Square { x: Some(x) }, rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let seed = [7u8; 32];
+
+        let proof = create_proof_deterministic(&params, Square { x: Some(x) }, seed).unwrap();
+        assert!(verify_proof(&pvk, &proof, &[x.square()]).unwrap());
+        let again = create_proof_deterministic(&params, Square { x: Some(x) }, seed).unwrap();
+        assert_eq!(
+            postcard::to_allocvec(&proof).unwrap(),
+            postcard::to_allocvec(&again).unwrap()
+        );
+
+        // of another witness, or another seed.
+        let proof_other =
+            create_proof_deterministic(&params, Square { x: Some(other) }, seed).unwrap();
+        assert!(verify_proof(&pvk, &proof_other, &[other.square()]).unwrap());
+        assert_ne!(proof_other, proof);
+        let reseeded =
+            create_proof_deterministic(&params, Square { x: Some(x) }, [8u8; 32]).unwrap();
+        assert!(verify_proof(&pvk, &reseeded, &[x.square()]).unwrap());
+        assert_ne!(reseeded, proof);
+
+        // blinded, unlike the proof of no zero knowledge.
+        let no_zk = create_proof_no_zk(&params, Square { x: Some(x) }).unwrap();
+        assert_ne!(no_zk, proof);
+    }
+}
@@ -1,7 +1,7 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
 use core::ops::AddAssign;
-use math::fft::EvaluationDomain;
+use math::fft::{EvaluationDomain, Evaluations};
 use math::{One, PairingEngine, Zero};
 
 use crate::r1cs::{ConstraintSystem, Index, SynthesisError};
@@ -146,9 +146,9 @@ impl R1CStoQAP {
         domain.coset_fft_in_place(&mut a);
         domain.coset_fft_in_place(&mut b);
 
-        let mut ab = domain.mul_polynomials_in_evaluation_domain(&a, &b);
-        drop(a);
-        drop(b);
+        // All of a, b and c are evaluated over the same coset of `domain`.
+        let mut h = Evaluations::from_vec_and_domain(a, domain)
+            * &Evaluations::from_vec_and_domain(b, domain);
 
         let mut c = vec![zero; domain_size];
         cfg_iter_mut!(c[..prover.num_constraints()])
@@ -160,13 +160,12 @@ impl R1CStoQAP {
         domain.ifft_in_place(&mut c);
         domain.coset_fft_in_place(&mut c);
 
-        cfg_iter_mut!(ab)
-            .zip(c)
-            .for_each(|(ab_i, c_i)| *ab_i -= &c_i);
+        h -= &Evaluations::from_vec_and_domain(c, domain);
 
-        domain.divide_by_vanishing_poly_on_coset_in_place(&mut ab);
-        domain.coset_ifft_in_place(&mut ab);
+        let mut h = h.evals;
+        domain.divide_by_vanishing_poly_on_coset_in_place(&mut h);
+        domain.coset_ifft_in_place(&mut h);
 
-        Ok(ab)
+        Ok(h)
     }
 }
@@ -0,0 +1,133 @@
+use core::fmt::Write;
+use curve::bn_256::{Bn_256, EthereumEncoding, G1Affine, G2Affine};
+
+use crate::String;
+
+use super::VerifyKey;
+
+/// The pairing check and the curve operations of the contract, by the
+/// precompiles of EIP-196 and EIP-197 at the addresses 6, 7 and 8.
+const TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+// A Groth16 verifier on bn254 of {num_inputs} public inputs, generated by zkp-toolkit.
+pragma solidity ^0.8.0;
+
+contract Groth16Verifier {
+    uint256 constant PRIME_Q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+    uint256 constant SNARK_SCALAR_FIELD = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+
+{constants}
+    /// The proof is of the words of `Proof::to_ethereum_encoding`, the
+    /// imaginary part of each coordinate of `b` first.
+    function verifyProof(
+        uint256[2] memory a,
+        uint256[2][2] memory b,
+        uint256[2] memory c,
+        uint256[] memory input
+    ) public view returns (bool) {
+        require(input.length == {num_inputs}, "invalid number of public inputs");
+        require(a[0] < PRIME_Q && a[1] < PRIME_Q, "invalid proof");
+        for (uint256 i = 0; i < input.length; i++) {
+            require(input[i] < SNARK_SCALAR_FIELD, "input is not in the scalar field");
+        }
+
+        uint256[2] memory vkX = [IC0_X, IC0_Y];
+{accumulate}
+        // e(-a, b) e(alpha, beta) e(vkX, gamma) e(c, delta) == 1
+        uint256[24] memory args;
+        (args[0], args[1]) = (a[0], (PRIME_Q - a[1]) % PRIME_Q);
+        (args[2], args[3], args[4], args[5]) = (b[0][0], b[0][1], b[1][0], b[1][1]);
+        (args[6], args[7]) = (ALPHA_X, ALPHA_Y);
+        (args[8], args[9], args[10], args[11]) = (BETA_X1, BETA_X0, BETA_Y1, BETA_Y0);
+        (args[12], args[13]) = (vkX[0], vkX[1]);
+        (args[14], args[15], args[16], args[17]) = (GAMMA_X1, GAMMA_X0, GAMMA_Y1, GAMMA_Y0);
+        (args[18], args[19]) = (c[0], c[1]);
+        (args[20], args[21], args[22], args[23]) = (DELTA_X1, DELTA_X0, DELTA_Y1, DELTA_Y0);
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 8, args, 768, out, 32)
+        }
+        require(ok, "pairing check failed");
+        return out[0] == 1;
+    }
+
+    function add(uint256[2] memory p, uint256[2] memory q) internal view returns (uint256[2] memory r) {
+        uint256[4] memory args = [p[0], p[1], q[0], q[1]];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 6, args, 128, r, 64)
+        }
+        require(ok, "point addition failed");
+    }
+
+    function mul(uint256[2] memory p, uint256 s) internal view returns (uint256[2] memory r) {
+        uint256[3] memory args = [p[0], p[1], s];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 7, args, 96, r, 64)
+        }
+        require(ok, "scalar multiplication failed");
+    }
+}
+"#;
+
+/// Writes `uint256 constant NAME = 0x..;` of every 32-byte big-endian word
+/// of `bytes`, named `prefix` and the suffixes of `names`.
+fn constants(out: &mut String, prefix: &str, names: &[&str], bytes: &[u8]) {
+    for (name, word) in names.iter().zip(bytes.chunks(32)) {
+        write!(out, "    uint256 constant {}{} = 0x", prefix, name).unwrap();
+        for byte in word {
+            write!(out, "{:02x}", byte).unwrap();
+        }
+        out.push_str(";\n");
+    }
+}
+
+fn g1_constants(out: &mut String, prefix: &str, point: &G1Affine) {
+    constants(out, prefix, &["_X", "_Y"], &point.to_ethereum_bytes());
+}
+
+fn g2_constants(out: &mut String, prefix: &str, point: &G2Affine) {
+    let names = ["_X1", "_X0", "_Y1", "_Y0"];
+    constants(out, prefix, &names, &point.to_ethereum_bytes());
+}
+
+impl VerifyKey<Bn_256> {
+    /// Returns the source of a self-contained Solidity contract verifying
+    /// the proofs of this key by the EIP-196 and EIP-197 precompiles, of the
+    /// entry point `verifyProof(uint[2] a, uint[2][2] b, uint[2] c, uint[] input)`.
+    ///
+    /// The points of the key are embedded as constants, of the words of
+    /// `EthereumEncoding`: `_X` and `_Y` of a G1 point, and `_X1`, `_X0`, `_Y1`
+    /// and `_Y0` of a G2 point, as `IC{i}` the `i`-th of `gamma_abc_g1`. The
+    /// proof is of the words of `Proof::to_ethereum_encoding`.
+    pub fn to_solidity(&self) -> String {
+        let num_inputs = self.gamma_abc_g1.len().saturating_sub(1);
+
+        let mut constants = String::new();
+        g1_constants(&mut constants, "ALPHA", &self.alpha_g1);
+        g2_constants(&mut constants, "BETA", &self.beta_g2);
+        g2_constants(&mut constants, "GAMMA", &self.gamma_g2);
+        g2_constants(&mut constants, "DELTA", &self.delta_g2);
+        for (i, ic) in self.gamma_abc_g1.iter().enumerate() {
+            g1_constants(&mut constants, &format!("IC{}", i), ic);
+        }
+
+        let mut accumulate = String::new();
+        for i in 1..=num_inputs {
+            writeln!(
+                accumulate,
+                "        vkX = add(vkX, mul([IC{}_X, IC{}_Y], input[{}]));",
+                i,
+                i,
+                i - 1
+            )
+            .unwrap();
+        }
+
+        TEMPLATE
+            .replace("{num_inputs}", &format!("{}", num_inputs))
+            .replace("{constants}", &constants)
+            .replace("{accumulate}", &accumulate)
+    }
+}
@@ -0,0 +1,341 @@
+//! A layout of the parameters written and read a section at a time, so that
+//! a key of gigabytes needs no buffer of its whole encoding:
+//!
+//! ```text
+//! "groth16" 0x01
+//! alpha_g1 beta_g2 gamma_g2 delta_g2 [gamma_abc_g1] fingerprint
+//! beta_g1 delta_g1 [a_query] [b_g1_query] [b_g2_query] [h_query] [l_query]
+//! ```
+//!
+//! Every point is written by `ToBytes`, uncompressed, and every `[..]` is a
+//! little-endian `u64` length followed by as many points.
+
+#[cfg(feature = "mmap")]
+use core::ops::Range;
+use math::io::{Read, Result as IoResult, Write};
+use math::{error, FromBytes, PairingEngine, ToBytes};
+#[cfg(feature = "mmap")]
+use math::{AffineCurve, ConstSerializedSize};
+
+use crate::r1cs::SynthesisError;
+use crate::{Cow, Vec};
+
+use super::{Parameters, VerifyKey};
+
+const MAGIC: &[u8; 8] = b"groth16\x01";
+
+/// The points reserved ahead of reading a section, at most, so that a
+/// corrupted length fails on reading rather than on allocating.
+pub(super) const MAX_RESERVED_POINTS: u64 = 1 << 24;
+
+/// The parts of the parameters the prover takes, a query at a time, e.g. of
+/// `Parameters` in memory or of a file mapped by `MappedParameters`.
+pub trait ParametersSource<E: PairingEngine> {
+    fn vk(&self) -> &VerifyKey<E>;
+
+    fn beta_g1(&self) -> E::G1Affine;
+
+    fn delta_g1(&self) -> E::G1Affine;
+
+    fn a_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError>;
+
+    fn b_g1_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError>;
+
+    fn b_g2_query(&self) -> Result<Cow<'_, [E::G2Affine]>, SynthesisError>;
+
+    fn h_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError>;
+
+    fn l_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError>;
+}
+
+impl<E: PairingEngine> ParametersSource<E> for Parameters<E> {
+    fn vk(&self) -> &VerifyKey<E> {
+        &self.vk
+    }
+
+    fn beta_g1(&self) -> E::G1Affine {
+        self.beta_g1
+    }
+
+    fn delta_g1(&self) -> E::G1Affine {
+        self.delta_g1
+    }
+
+    fn a_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        Ok(Cow::Borrowed(&self.a_query))
+    }
+
+    fn b_g1_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        Ok(Cow::Borrowed(&self.b_g1_query))
+    }
+
+    fn b_g2_query(&self) -> Result<Cow<'_, [E::G2Affine]>, SynthesisError> {
+        Ok(Cow::Borrowed(&self.b_g2_query))
+    }
+
+    fn h_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        Ok(Cow::Borrowed(&self.h_query))
+    }
+
+    fn l_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        Ok(Cow::Borrowed(&self.l_query))
+    }
+}
+
+fn write_points<G: ToBytes, W: Write>(points: &[G], mut writer: W) -> IoResult<()> {
+    (points.len() as u64).write(&mut writer)?;
+    for point in points {
+        point.write(&mut writer)?;
+    }
+    Ok(())
+}
+
+fn read_points<G: FromBytes, R: Read>(mut reader: R) -> IoResult<Vec<G>> {
+    let len = u64::read(&mut reader)?;
+    let mut points = Vec::with_capacity(len.min(MAX_RESERVED_POINTS) as usize);
+    for _ in 0..len {
+        points.push(G::read(&mut reader)?);
+    }
+    Ok(points)
+}
+
+fn write_vk<E: PairingEngine, W: Write>(vk: &VerifyKey<E>, mut writer: W) -> IoResult<()> {
+    vk.alpha_g1.write(&mut writer)?;
+    vk.beta_g2.write(&mut writer)?;
+    vk.gamma_g2.write(&mut writer)?;
+    vk.delta_g2.write(&mut writer)?;
+    write_points(&vk.gamma_abc_g1, &mut writer)?;
+    writer.write_all(&vk.fingerprint)
+}
+
+fn read_vk<E: PairingEngine, R: Read>(mut reader: R) -> IoResult<VerifyKey<E>> {
+    let alpha_g1 = E::G1Affine::read(&mut reader)?;
+    let beta_g2 = E::G2Affine::read(&mut reader)?;
+    let gamma_g2 = E::G2Affine::read(&mut reader)?;
+    let delta_g2 = E::G2Affine::read(&mut reader)?;
+    let gamma_abc_g1 = read_points(&mut reader)?;
+    let mut fingerprint = [0u8; 32];
+    reader.read_exact(&mut fingerprint)?;
+
+    Ok(VerifyKey {
+        alpha_g1,
+        beta_g2,
+        gamma_g2,
+        delta_g2,
+        gamma_abc_g1,
+        fingerprint,
+    })
+}
+
+/// The header and the verifying key, and `beta_g1` and `delta_g1`.
+fn read_head<E: PairingEngine, R: Read>(
+    mut reader: R,
+) -> IoResult<(VerifyKey<E>, E::G1Affine, E::G1Affine)> {
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(error("not a streamed groth16 proving key"));
+    }
+    let vk = read_vk(&mut reader)?;
+    let beta_g1 = E::G1Affine::read(&mut reader)?;
+    let delta_g1 = E::G1Affine::read(&mut reader)?;
+    Ok((vk, beta_g1, delta_g1))
+}
+
+impl<E: PairingEngine> Parameters<E> {
+    /// Writes the parameters a section at a time, e.g. to a buffered file,
+    /// in the layout of this module. Unlike `postcard::to_allocvec`, nothing
+    /// of the size of the key is allocated.
+    pub fn write_streaming<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        writer.write_all(MAGIC)?;
+        write_vk(&self.vk, &mut writer)?;
+        self.beta_g1.write(&mut writer)?;
+        self.delta_g1.write(&mut writer)?;
+        write_points(&self.a_query, &mut writer)?;
+        write_points(&self.b_g1_query, &mut writer)?;
+        write_points(&self.b_g2_query, &mut writer)?;
+        write_points(&self.h_query, &mut writer)?;
+        write_points(&self.l_query, &mut writer)
+    }
+
+    /// Reads parameters written by `write_streaming`, a point at a time. The
+    /// points are not checked, `check` does it for a key of untrusted bytes.
+    pub fn read_streaming<R: Read>(mut reader: R) -> IoResult<Self> {
+        let (vk, beta_g1, delta_g1) = read_head(&mut reader)?;
+        Ok(Parameters {
+            vk,
+            beta_g1,
+            delta_g1,
+            a_query: read_points(&mut reader)?,
+            b_g1_query: read_points(&mut reader)?,
+            b_g2_query: read_points(&mut reader)?,
+            h_query: read_points(&mut reader)?,
+            l_query: read_points(&mut reader)?,
+        })
+    }
+}
+
+/// The byte range of the points of a section, from `offset` of the length
+/// prefix, checked to be within `bytes`.
+#[cfg(feature = "mmap")]
+fn section<G: ConstSerializedSize>(bytes: &[u8], offset: usize) -> IoResult<Range<usize>> {
+    let mut prefix = bytes.get(offset..).unwrap_or(&[]);
+    let len = u64::read(&mut prefix)?;
+    let start = offset + u64::SERIALIZED_SIZE;
+    let end = (len as usize)
+        .checked_mul(G::SERIALIZED_SIZE)
+        .and_then(|size| start.checked_add(size))
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(|| error("section out of the proving key"))?;
+    Ok(start..end)
+}
+
+#[cfg(feature = "mmap")]
+fn decode_points<G: AffineCurve>(bytes: &[u8]) -> Result<Vec<G>, SynthesisError> {
+    let mut points = Vec::with_capacity(bytes.len() / G::SERIALIZED_SIZE);
+    for mut point in bytes.chunks_exact(G::SERIALIZED_SIZE) {
+        points.push(G::read(&mut point)?);
+    }
+    Ok(points)
+}
+
+/// Parameters of a file written by `write_streaming`, mapped into memory, of
+/// which every query is decoded only when the prover takes it. The file must
+/// not change while it is mapped.
+#[cfg(feature = "mmap")]
+pub struct MappedParameters<E: PairingEngine> {
+    map: memmap2::Mmap,
+    vk: VerifyKey<E>,
+    beta_g1: E::G1Affine,
+    delta_g1: E::G1Affine,
+    a_query: Range<usize>,
+    b_g1_query: Range<usize>,
+    b_g2_query: Range<usize>,
+    h_query: Range<usize>,
+    l_query: Range<usize>,
+}
+
+#[cfg(feature = "mmap")]
+impl<E: PairingEngine> MappedParameters<E> {
+    /// Maps the file at `path`, reading the verifying key and the offsets of
+    /// the queries.
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> IoResult<Self> {
+        let file = std::fs::File::open(path)?;
+        // the file is read only, and documented not to change while mapped.
+        #[allow(unsafe_code)]
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        let mut head = &map[..];
+        let (vk, beta_g1, delta_g1) = read_head(&mut head)?;
+        let a_query = section::<E::G1Affine>(&map, map.len() - head.len())?;
+        let b_g1_query = section::<E::G1Affine>(&map, a_query.end)?;
+        let b_g2_query = section::<E::G2Affine>(&map, b_g1_query.end)?;
+        let h_query = section::<E::G1Affine>(&map, b_g2_query.end)?;
+        let l_query = section::<E::G1Affine>(&map, h_query.end)?;
+
+        Ok(MappedParameters {
+            map,
+            vk,
+            beta_g1,
+            delta_g1,
+            a_query,
+            b_g1_query,
+            b_g2_query,
+            h_query,
+            l_query,
+        })
+    }
+
+    fn decode<G: AffineCurve>(&self, range: &Range<usize>) -> Result<Cow<'_, [G]>, SynthesisError> {
+        Ok(Cow::Owned(decode_points(&self.map[range.clone()])?))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<E: PairingEngine> ParametersSource<E> for MappedParameters<E> {
+    fn vk(&self) -> &VerifyKey<E> {
+        &self.vk
+    }
+
+    fn beta_g1(&self) -> E::G1Affine {
+        self.beta_g1
+    }
+
+    fn delta_g1(&self) -> E::G1Affine {
+        self.delta_g1
+    }
+
+    fn a_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        self.decode(&self.a_query)
+    }
+
+    fn b_g1_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        self.decode(&self.b_g1_query)
+    }
+
+    fn b_g2_query(&self) -> Result<Cow<'_, [E::G2Affine]>, SynthesisError> {
+        self.decode(&self.b_g2_query)
+    }
+
+    fn h_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        self.decode(&self.h_query)
+    }
+
+    fn l_query(&self) -> Result<Cow<'_, [E::G1Affine]>, SynthesisError> {
+        self.decode(&self.l_query)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::{Bn_256, Fr};
+    use math::{test_rng, Field, UniformRand};
+
+    use super::*;
+    use crate::groth16::{
+        create_random_proof_from_source, generate_random_parameters, prepare_verifying_key,
+        verify_proof,
+    };
+    use crate::test_circuits::Square;
+
+    #[test]
+    fn test_streaming() {
+        let rng = &mut test_rng();
+        let x = Fr::rand(rng);
+        let params =
+            generate_random_parameters::<Bn_256, _, _>(Square { x: Some(x) }, rng).unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+
+        let mut bytes = vec![];
+        params.write_streaming(&mut bytes).unwrap();
+        let read = Parameters::<Bn_256>::read_streaming(&bytes[..]).unwrap();
+        assert_eq!(read, params);
+
+        let proof = create_random_proof_from_source(&read, Square { x: Some(x) }, rng).unwrap();
+        assert!(verify_proof(&pvk, &proof, &[x.square()]).unwrap());
+
+        for len in 0..bytes.len() {
+            assert!(Parameters::<Bn_256>::read_streaming(&bytes[..len]).is_err());
+        }
+        let mut bad_magic = bytes.clone();
+        bad_magic[7] = 2;
+        assert!(Parameters::<Bn_256>::read_streaming(&bad_magic[..]).is_err());
+
+        #[cfg(feature = "mmap")]
+        {
+            let path = std::env::temp_dir().join("groth16-test-streaming.pk");
+            std::fs::write(&path, &bytes).unwrap();
+            let mapped = MappedParameters::<Bn_256>::open(&path).unwrap();
+            assert_eq!(mapped.vk(), &params.vk);
+            assert_eq!(mapped.h_query().unwrap().as_ref(), &params.h_query[..]);
+            let proof =
+                create_random_proof_from_source(&mapped, Square { x: Some(x) }, rng).unwrap();
+            assert!(verify_proof(&pvk, &proof, &[x.square()]).unwrap());
+            drop(mapped);
+
+            std::fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+            assert!(MappedParameters::<Bn_256>::open(&path).is_err());
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use core::ops::{AddAssign, Neg};
-use math::{AffineCurve, PairingEngine, PrimeField, ProjectiveCurve};
+use math::{AffineCurve, PairingEngine, PrimeField, ProjectiveCurve, Zero};
 
 use crate::r1cs::SynthesisError;
+use crate::{Cow, Vec};
 
 use super::{PreparedVerifyingKey, Proof, VerifyKey};
 
@@ -15,11 +16,109 @@ pub fn prepare_verifying_key<E: PairingEngine>(vk: &VerifyKey<E>) -> PreparedVer
     }
 }
 
-pub fn verify_proof<E: PairingEngine>(
+/// A verification key accepted by `verify_proof`. A raw `VerifyKey` is
+/// prepared on every call, so verifiers checking many proofs against the same
+/// key should prepare it once with `prepare_verifying_key`.
+pub trait AsPreparedVerifyingKey<E: PairingEngine> {
+    fn as_prepared(&self) -> Cow<PreparedVerifyingKey<E>>;
+}
+
+impl<E: PairingEngine> AsPreparedVerifyingKey<E> for VerifyKey<E> {
+    fn as_prepared(&self) -> Cow<PreparedVerifyingKey<E>> {
+        Cow::Owned(prepare_verifying_key(self))
+    }
+}
+
+impl<E: PairingEngine> AsPreparedVerifyingKey<E> for PreparedVerifyingKey<E> {
+    fn as_prepared(&self) -> Cow<PreparedVerifyingKey<E>> {
+        Cow::Borrowed(self)
+    }
+}
+
+/// The sum `gamma_abc_0 + sum_i input_i * gamma_abc_{i + 1}` of the public
+/// inputs fixed across many verifications, e.g. a merkle root, of the bases
+/// of the inputs left to vary, e.g. a nullifier. Inputs are indexed as in
+/// the `public_inputs` of `verify_proof`.
+#[derive(Clone, Debug)]
+pub struct PartialInputs<E: PairingEngine> {
+    sum: E::G1Projective,
+    varying: Vec<Option<E::G1Affine>>,
+}
+
+/// Sums the `fixed` public inputs, of their indexes, once for all the
+/// verifications which only vary the others.
+pub fn prepare_inputs_partial<E: PairingEngine>(
     pvk: &PreparedVerifyingKey<E>,
+    fixed: &[(usize, E::Fr)],
+) -> Result<PartialInputs<E>, SynthesisError> {
+    let mut varying: Vec<_> = pvk.gamma_abc_g1.iter().skip(1).copied().map(Some).collect();
+    let mut sum = pvk
+        .gamma_abc_g1
+        .first()
+        .ok_or(SynthesisError::MalformedVerifyingKey)?
+        .into_projective();
+    for (i, input) in fixed {
+        let base = varying
+            .get_mut(*i)
+            .and_then(Option::take)
+            .ok_or(SynthesisError::MalformedVerifyingKey)?;
+        sum.add_assign(&base.mul(input.into_repr()));
+    }
+
+    Ok(PartialInputs { sum, varying })
+}
+
+impl<E: PairingEngine> PartialInputs<E> {
+    /// Adds the `varying` public inputs, every input not fixed exactly
+    /// once, to the sum of the fixed ones, for
+    /// `verify_proof_with_prepared_inputs`.
+    pub fn finish(&self, varying: &[(usize, E::Fr)]) -> Result<E::G1Projective, SynthesisError> {
+        let mut assigned = vec![false; self.varying.len()];
+        let mut sum = self.sum;
+        for (i, input) in varying {
+            let base = match (self.varying.get(*i), assigned.get_mut(*i)) {
+                (Some(Some(base)), Some(assigned)) if !*assigned => {
+                    *assigned = true;
+                    base
+                }
+                _ => return Err(SynthesisError::MalformedVerifyingKey),
+            };
+            sum.add_assign(&base.mul(input.into_repr()));
+        }
+
+        let complete = self
+            .varying
+            .iter()
+            .zip(assigned)
+            .all(|(base, assigned)| base.is_none() || assigned);
+        if !complete {
+            return Err(SynthesisError::AssignmentMissing);
+        }
+
+        Ok(sum)
+    }
+}
+
+/// Verifies `proof` of the `public_inputs`, after checking its points are on
+/// the curve and in the prime order subgroup, as deserializing does not.
+pub fn verify_proof<E: PairingEngine, K: AsPreparedVerifyingKey<E>>(
+    vk: &K,
     proof: &Proof<E>,
     public_inputs: &[E::Fr],
 ) -> Result<bool, SynthesisError> {
+    proof.check()?;
+    verify_proof_unchecked(vk, proof, public_inputs)
+}
+
+/// `verify_proof`, for callers which checked the proof elsewhere, e.g. with
+/// `Proof::check` or `Proof::from_compressed_bytes`. The pairing of points
+/// off the curve or out of the subgroup tells nothing.
+pub fn verify_proof_unchecked<E: PairingEngine, K: AsPreparedVerifyingKey<E>>(
+    vk: &K,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr],
+) -> Result<bool, SynthesisError> {
+    let pvk = vk.as_prepared();
     if (public_inputs.len() + 1) != pvk.gamma_abc_g1.len() {
         return Err(SynthesisError::MalformedVerifyingKey);
     }
@@ -29,16 +128,315 @@ pub fn verify_proof<E: PairingEngine>(
         g_ic.add_assign(&b.mul(i.into_repr()));
     }
 
-    let qap = E::miller_loop(
-        [
-            (proof.a.into(), proof.b.into()),
-            (g_ic.into_affine().into(), pvk.gamma_g2_neg_pc.clone()),
-            (proof.c.into(), pvk.delta_g2_neg_pc.clone()),
-        ]
-        .iter(),
-    );
+    check_pairing(&*pvk, proof, &g_ic)
+}
+
+/// Verifies `proof` of the sum of the public inputs `prepared`, of
+/// `PartialInputs::finish`, as `verify_proof` does of the inputs.
+pub fn verify_proof_with_prepared_inputs<E: PairingEngine>(
+    pvk: &PreparedVerifyingKey<E>,
+    proof: &Proof<E>,
+    prepared: &E::G1Projective,
+) -> Result<bool, SynthesisError> {
+    proof.check()?;
+    check_pairing(pvk, proof, prepared)
+}
+
+/// A verifier of many proofs of one key, which allocates nothing per proof:
+/// the public inputs are summed in place, and the line functions of the `b`
+/// of each proof are evaluated along the Miller loop rather than prepared,
+/// of the engines of the bls12 and bn curves.
+#[derive(Clone, Debug)]
+pub struct Verifier<E: PairingEngine> {
+    pvk: PreparedVerifyingKey<E>,
+}
+
+impl<E: PairingEngine> Verifier<E> {
+    pub fn new(pvk: PreparedVerifyingKey<E>) -> Self {
+        Verifier { pvk }
+    }
+
+    pub fn prepared_verifying_key(&self) -> &PreparedVerifyingKey<E> {
+        &self.pvk
+    }
+
+    /// Sums the `public_inputs` to their bases into `prepared`, as
+    /// `verify_proof` does, of no allocation.
+    pub fn prepare_inputs(
+        &self,
+        public_inputs: &[E::Fr],
+        prepared: &mut E::G1Projective,
+    ) -> Result<(), SynthesisError> {
+        let gamma_abc_g1 = &self.pvk.gamma_abc_g1;
+        if (public_inputs.len() + 1) != gamma_abc_g1.len() {
+            return Err(SynthesisError::MalformedVerifyingKey);
+        }
+
+        *prepared = gamma_abc_g1[0].into_projective();
+        for (i, b) in public_inputs.iter().zip(gamma_abc_g1.iter().skip(1)) {
+            prepared.add_assign(&b.mul(i.into_repr()));
+        }
+        Ok(())
+    }
+
+    /// `verify_proof` of the key of the verifier.
+    pub fn verify(
+        &self,
+        proof: &Proof<E>,
+        public_inputs: &[E::Fr],
+    ) -> Result<bool, SynthesisError> {
+        proof.check()?;
+        let mut prepared = E::G1Projective::zero();
+        self.prepare_inputs(public_inputs, &mut prepared)?;
+        Ok(self.check_pairing(proof, &prepared))
+    }
+
+    /// `verify_proof_with_prepared_inputs` of the key of the verifier.
+    pub fn verify_with_prepared_inputs(
+        &self,
+        proof: &Proof<E>,
+        prepared: &E::G1Projective,
+    ) -> Result<bool, SynthesisError> {
+        proof.check()?;
+        Ok(self.check_pairing(proof, prepared))
+    }
+
+    fn check_pairing(&self, proof: &Proof<E>, prepared: &E::G1Projective) -> bool {
+        let pvk = &self.pvk;
+        let pairs = [
+            (prepared.into_affine().into(), &pvk.gamma_g2_neg_pc),
+            (proof.c.into(), &pvk.delta_g2_neg_pc),
+        ];
+        let test = E::miller_loop_with_affine(&proof.a, &proof.b, &pairs);
 
-    let test = E::final_exponentiation(&qap).ok_or(SynthesisError::UnexpectedIdentity)?;
+        E::final_exponentiation(&test) == Some(pvk.alpha_g1_beta_g2)
+    }
+}
+
+impl<E: PairingEngine> From<PreparedVerifyingKey<E>> for Verifier<E> {
+    fn from(pvk: PreparedVerifyingKey<E>) -> Self {
+        Verifier::new(pvk)
+    }
+}
+
+fn check_pairing<E: PairingEngine>(
+    pvk: &PreparedVerifyingKey<E>,
+    proof: &Proof<E>,
+    prepared: &E::G1Projective,
+) -> Result<bool, SynthesisError> {
+    let test = E::multi_pairing(
+        &[
+            proof.a.into(),
+            prepared.into_affine().into(),
+            proof.c.into(),
+        ],
+        &[
+            proof.b.into(),
+            pvk.gamma_g2_neg_pc.clone(),
+            pvk.delta_g2_neg_pc.clone(),
+        ],
+    )
+    .ok_or(SynthesisError::UnexpectedIdentity)?;
 
     Ok(test == pvk.alpha_g1_beta_g2)
 }
+
+#[cfg(test)]
+mod test {
+    use curve::bls12_381::{Bls12_381, Fr as FrBls};
+    use curve::bn_256::{Bn_256, Fq2, Fr, G2Affine};
+    use math::{test_rng, Field, UniformRand};
+
+    use super::*;
+    use crate::groth16::{create_random_proof, generate_random_parameters, Parameters};
+    use crate::test_circuits::Sum;
+
+    #[test]
+    fn test_prepared_inputs() {
+        let rng = &mut test_rng();
+        let inputs: Vec<Fr> = (0..8).map(|_| Fr::rand(rng)).collect();
+        let params = generate_random_parameters::<Bn_256, _, _>(
+            Sum {
+                inputs: inputs.clone(),
+            },
+            rng,
+        )
+        .unwrap();
+        let pvk = prepare_verifying_key(&params.vk);
+        let proof = create_random_proof(
+            &params,
+            Sum {
+                inputs: inputs.clone(),
+            },
+            rng,
+        )
+        .unwrap();
+        assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+        // 7 of the 8 inputs fixed, in any order, the nullifier varying.
+        let fixed: Vec<_> = (0..7).rev().map(|i| (i, inputs[i])).collect();
+        let partial = prepare_inputs_partial(&pvk, &fixed).unwrap();
+        let prepared = partial.finish(&[(7, inputs[7])]).unwrap();
+        assert!(verify_proof_with_prepared_inputs(&pvk, &proof, &prepared).unwrap());
+        let prepared = partial.finish(&[(7, Fr::rand(rng))]).unwrap();
+        assert!(!verify_proof_with_prepared_inputs(&pvk, &proof, &prepared).unwrap());
+
+        // the same sum as the one of verify_proof, however the inputs split.
+        let all: Vec<_> = inputs.iter().copied().enumerate().collect();
+        let full = prepare_inputs_partial(&pvk, &all)
+            .unwrap()
+            .finish(&[])
+            .unwrap();
+        for split in 0..=8 {
+            let partial = prepare_inputs_partial(&pvk, &all[..split]).unwrap();
+            assert_eq!(partial.finish(&all[split..]).unwrap(), full);
+        }
+        let none = prepare_inputs_partial(&pvk, &[]).unwrap();
+        assert!(
+            verify_proof_with_prepared_inputs(&pvk, &proof, &none.finish(&all).unwrap()).unwrap()
+        );
+
+        // every input is given exactly once.
+        let input = inputs[0];
+        assert!(prepare_inputs_partial(&pvk, &[(8, input)]).is_err());
+        assert!(prepare_inputs_partial(&pvk, &[(0, input), (0, input)]).is_err());
+        assert!(partial.finish(&[(6, input)]).is_err());
+        assert!(partial.finish(&[(7, input), (7, input)]).is_err());
+        assert!(matches!(
+            partial.finish(&[]),
+            Err(SynthesisError::AssignmentMissing)
+        ));
+    }
+
+    fn prove(inputs: &[Fr]) -> (Parameters<Bn_256>, Proof<Bn_256>) {
+        let rng = &mut test_rng();
+        let sum = || Sum {
+            inputs: inputs.to_vec(),
+        };
+        let params = generate_random_parameters::<Bn_256, _, _>(sum(), rng).unwrap();
+        let proof = create_random_proof(&params, sum(), rng).unwrap();
+        (params, proof)
+    }
+
+    #[test]
+    fn test_malformed_proofs() {
+        let inputs = [Fr::from(3u32), Fr::from(5u32)];
+        let (params, proof) = prove(&inputs);
+        let pvk = prepare_verifying_key(&params.vk);
+        assert!(proof.check().is_ok());
+
+        let mut off_curve = proof.clone();
+        off_curve.a.y.double_in_place();
+        assert!(matches!(
+            verify_proof(&pvk, &off_curve, &inputs),
+            Err(SynthesisError::MalformedProof)
+        ));
+        let prepared = prepare_inputs_partial(&pvk, &[(0, inputs[0]), (1, inputs[1])])
+            .unwrap()
+            .finish(&[])
+            .unwrap();
+        assert!(verify_proof_with_prepared_inputs(&pvk, &off_curve, &prepared).is_err());
+
+        // every byte of the proof mutated, of no panic: the proof is either
+        // not decoded, decoded to the same or rejected.
+        let bytes = postcard::to_allocvec(&proof).unwrap();
+        for i in 0..bytes.len() {
+            for mask in &[0x01u8, 0x80, 0xff] {
+                let mut mutated = bytes.clone();
+                mutated[i] ^= mask;
+                let mutated: Proof<Bn_256> = match postcard::from_bytes(&mutated) {
+                    Ok(mutated) => mutated,
+                    Err(_) => continue,
+                };
+                let result = verify_proof(&pvk, &mutated, &inputs);
+                if mutated == proof {
+                    assert!(result.unwrap());
+                } else {
+                    assert!(!result.unwrap_or(false));
+                }
+            }
+        }
+        for len in 0..bytes.len() {
+            assert!(postcard::from_bytes::<Proof<Bn_256>>(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_verifier() {
+        let rng = &mut test_rng();
+        let inputs = [Fr::from(3u32), Fr::from(5u32)];
+        let (params, proof) = prove(&inputs);
+        let pvk = prepare_verifying_key(&params.vk);
+        let verifier = Verifier::new(pvk.clone());
+
+        // the same results as the ones of verify_proof.
+        let wrong = [Fr::from(3u32), Fr::rand(rng)];
+        for inputs in &[&inputs, &wrong] {
+            assert_eq!(
+                verifier.verify(&proof, *inputs).unwrap(),
+                verify_proof(&pvk, &proof, *inputs).unwrap()
+            );
+        }
+        assert!(verifier.verify(&proof, &inputs).unwrap());
+        assert!(!verifier.verify(&proof, &wrong).unwrap());
+        assert!(matches!(
+            verifier.verify(&proof, &inputs[..1]),
+            Err(SynthesisError::MalformedVerifyingKey)
+        ));
+        let mut off_curve = proof.clone();
+        off_curve.a.y.double_in_place();
+        assert!(matches!(
+            verifier.verify(&off_curve, &inputs),
+            Err(SynthesisError::MalformedProof)
+        ));
+
+        let mut prepared = <Bn_256 as PairingEngine>::G1Projective::zero();
+        verifier.prepare_inputs(&inputs, &mut prepared).unwrap();
+        assert!(verifier
+            .verify_with_prepared_inputs(&proof, &prepared)
+            .unwrap());
+        assert!(verify_proof_with_prepared_inputs(&pvk, &proof, &prepared).unwrap());
+
+        // the pairings of bls12 curves too.
+        let sum = || Sum {
+            inputs: vec![FrBls::from(3u32), FrBls::from(5u32)],
+        };
+        let params = generate_random_parameters::<Bls12_381, _, _>(sum(), rng).unwrap();
+        let proof = create_random_proof(&params, sum(), rng).unwrap();
+        let verifier = Verifier::from(prepare_verifying_key(&params.vk));
+        assert!(verifier.verify(&proof, &sum().inputs).unwrap());
+        assert!(!verifier.verify(&proof, &[FrBls::from(3u32); 2]).unwrap());
+    }
+
+    #[test]
+    fn test_check_keys() {
+        let (params, _) = prove(&[Fr::from(3u32), Fr::from(5u32)]);
+        assert!(params.check().is_ok());
+
+        let mut off_curve = params.clone();
+        off_curve.h_query[0].y.double_in_place();
+        assert!(matches!(
+            off_curve.check(),
+            Err(SynthesisError::MalformedParameters)
+        ));
+
+        // a point of G2 on the curve, out of the prime order subgroup.
+        let rng = &mut test_rng();
+        let off_subgroup = loop {
+            if let Some(p) = G2Affine::get_point_from_x(Fq2::rand(rng), false) {
+                if !p.is_in_correct_subgroup_assuming_on_curve() {
+                    break p;
+                }
+            }
+        };
+        let mut vk = params.vk.clone();
+        vk.delta_g2 = off_subgroup;
+        assert!(matches!(
+            vk.check(),
+            Err(SynthesisError::MalformedVerifyingKey)
+        ));
+        let mut params = params;
+        params.vk = vk;
+        assert!(params.check().is_err());
+    }
+}
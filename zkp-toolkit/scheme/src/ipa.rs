@@ -0,0 +1,609 @@
+//! The inner product argument of [`Bulletproofs`], and the Pedersen vector
+//! commitments it opens.
+//!
+//! `prove_inner_product` proves the knowledge of the vectors `a` and `b` of
+//! `P = <a, g_vec> + <b, h_vec> + <a, b> * u`, in `2 * log2(n)` points and
+//! two scalars. The vectors are padded with zeros to a power of two.
+//!
+//! `prove_reduction` is the argument of a public `b` and of a blinded
+//! `P = <a, g_vec> + <a, b> * u + r * h`. It folds the vectors to one element
+//! without opening them, for a proof of knowledge of the last ones, as the
+//! `DotProductProof` of spartan.
+//!
+//! [`Bulletproofs`]: https://eprint.iacr.org/2017/1066.pdf
+#![allow(non_snake_case)]
+use math::{AffineCurve, Curve, Field, One, ProjectiveCurve, ToBytes, UniformRand, Zero};
+use merlin::Transcript;
+use rand::Rng;
+
+use crate::Vec;
+
+pub struct Generators<G: Curve> {
+    pub g_vec: Vec<G::Affine>,
+    pub h_vec: Vec<G::Affine>,
+    /// the generator of the inner product.
+    pub u: G::Affine,
+    /// the generator of the blindings of the commitments.
+    pub h: G::Affine,
+}
+
+impl<G: Curve> Generators<G> {
+    /// Random generators of vectors of up to `n` elements.
+    pub fn new<R: Rng>(n: usize, rng: &mut R) -> Self {
+        let n = padded_len(n);
+        let points = G::Projective::batch_into_affine(
+            (0..2 * n + 2).map(|_| G::Projective::rand(rng)).collect(),
+        );
+
+        Generators {
+            g_vec: points[0..n].to_vec(),
+            h_vec: points[n..2 * n].to_vec(),
+            u: points[2 * n],
+            h: points[2 * n + 1],
+        }
+    }
+
+    /// The largest length of the vectors of the arguments.
+    pub fn size(&self) -> usize {
+        self.g_vec.len()
+    }
+}
+
+/// The length of the argument of vectors of `n` elements, the power of two
+/// they are padded to.
+pub fn padded_len(n: usize) -> usize {
+    n.next_power_of_two()
+}
+
+/// The Pedersen commitment `<vector, g_vec> + blinding * h`.
+pub fn commit<G: Curve>(gens: &Generators<G>, vector: &[G::Fr], blinding: G::Fr) -> G::Projective {
+    assert!(vector.len() <= gens.size(), "too few generators");
+    G::vartime_multiscalar_mul(vector, &gens.g_vec[0..vector.len()]) + gens.h.mul(blinding)
+}
+
+/// The commitment `<a, g_vec> + <b, h_vec> + <a, b> * u` of the argument of
+/// `a` and `b`.
+pub fn commit_inner_product<G: Curve>(
+    gens: &Generators<G>,
+    a: &[G::Fr],
+    b: &[G::Fr],
+) -> G::Projective {
+    assert_eq!(a.len(), b.len());
+    assert!(a.len() <= gens.size(), "too few generators");
+    G::vartime_multiscalar_mul(a, &gens.g_vec[0..a.len()])
+        + G::vartime_multiscalar_mul(b, &gens.h_vec[0..b.len()])
+        + gens.u.mul(inner_product(a, b))
+}
+
+/// The proof of the inner product of `a` and `b`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Proof<G: Curve> {
+    pub(crate) L_vec: Vec<G::Affine>,
+    pub(crate) R_vec: Vec<G::Affine>,
+    pub(crate) a: G::Fr,
+    pub(crate) b: G::Fr,
+}
+
+/// The rounds of a reduction, of its vectors folded to one element.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Reduction<G: Curve> {
+    pub(crate) L_vec: Vec<G::Affine>,
+    pub(crate) R_vec: Vec<G::Affine>,
+}
+
+/// The last round of a reduction, of vectors of one element.
+pub struct Folded<G: Curve> {
+    pub a: G::Fr,
+    pub b: G::Fr,
+    pub g: G::Affine,
+    /// the blinding of the folded commitment.
+    pub blinding: G::Fr,
+}
+
+/// The squares of the challenges of the rounds and of their inverses, and
+/// the scalars `s` of the generators.
+pub(crate) type VerificationScalars<F> = (Vec<F>, Vec<F>, Vec<F>);
+
+impl<G: Curve> Proof<G> {
+    /// The scalars of the checks of an argument of `n` generators, none of a
+    /// proof of another length.
+    pub(crate) fn verification_scalars(
+        &self,
+        transcript: &mut Transcript,
+        n: usize,
+    ) -> Option<VerificationScalars<G::Fr>> {
+        verification_scalars::<G>(transcript, &self.L_vec, &self.R_vec, n)
+    }
+}
+
+/// Computes an inner product of two vectors
+/// \\[
+///    {\langle {\mathbf{a}}, {\mathbf{b}} \rangle} = \sum\_{i=0}^{n-1} a\_i \cdot b\_i.
+/// \\]
+/// Panics if the lengths of \\(\mathbf{a}\\) and \\(\mathbf{b}\\) are not equal.
+pub(crate) fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b).map(|(a, b)| *a * b).sum()
+}
+
+pub(crate) fn random_bytes_to_fr<F: Field>(bytes: &[u8]) -> F {
+    let mut r_bytes = [0u8; 31];
+    // only use the first 31 bytes, to avoid value over modulus
+    r_bytes.copy_from_slice(&bytes[0..31]);
+    F::from_random_bytes(&r_bytes).unwrap()
+}
+
+/// The challenge `x` of the round of `L` and `R`.
+fn challenge<G: Curve>(transcript: &mut Transcript, L: &G::Affine, R: &G::Affine) -> G::Fr {
+    transcript.append_message(b"L", &math::to_bytes![L].unwrap());
+    transcript.append_message(b"R", &math::to_bytes![R].unwrap());
+
+    let mut buf_x = [0u8; 31];
+    transcript.challenge_bytes(b"x", &mut buf_x);
+    random_bytes_to_fr::<G::Fr>(&buf_x)
+}
+
+/// `a` padded with zeros to `n` elements.
+fn pad<F: Field>(a: &[F], n: usize) -> Vec<F> {
+    let mut a = a.to_vec();
+    a.resize(n, F::zero());
+    a
+}
+
+/// The generator of the blindings, and the blindings of `L` and `R` of
+/// each round.
+type Blinds<'a, G> = (
+    <G as Curve>::Affine,
+    &'a [(<G as Curve>::Fr, <G as Curve>::Fr)],
+);
+
+/// Folds `a`, `b` and the generators in halves until they are of one
+/// element. `h_vec` is none of a public `b`, and `blinding` none of a
+/// commitment which is not blinded.
+fn reduce<G: Curve>(
+    transcript: &mut Transcript,
+    mut g_vec: Vec<G::Affine>,
+    mut h_vec: Option<Vec<G::Affine>>,
+    u: G::Affine,
+    blinding: Option<Blinds<G>>,
+    mut a_vec: Vec<G::Fr>,
+    mut b_vec: Vec<G::Fr>,
+) -> (Reduction<G>, Folded<G>) {
+    let mut n = a_vec.len();
+    assert!(n.is_power_of_two());
+    assert_eq!(n, b_vec.len());
+    assert_eq!(n, g_vec.len());
+
+    let lg_n = n.trailing_zeros() as usize;
+    if let Some((_, blinds)) = blinding {
+        assert_eq!(blinds.len(), lg_n);
+    }
+    let mut L_vec: Vec<G::Affine> = Vec::with_capacity(lg_n);
+    let mut R_vec: Vec<G::Affine> = Vec::with_capacity(lg_n);
+    let mut blind_fin = G::Fr::zero();
+    for round in 0..lg_n {
+        // P computes:
+        n /= 2;
+        let (aL, aR) = a_vec.split_at(n);
+        let (bL, bR) = b_vec.split_at(n);
+
+        let cL = inner_product(aL, bR);
+        let cR = inner_product(aR, bL);
+
+        let (gL, gR) = g_vec.split_at(n);
+
+        let mut L = G::vartime_multiscalar_mul(aL, gR) + u.mul(cL);
+        let mut R = G::vartime_multiscalar_mul(aR, gL) + u.mul(cR);
+        if let Some(h_vec) = &h_vec {
+            let (hL, hR) = h_vec.split_at(n);
+            L += G::vartime_multiscalar_mul(bR, hL);
+            R += G::vartime_multiscalar_mul(bL, hR);
+        }
+        if let Some((h, blinds)) = blinding {
+            let (blind_L, blind_R) = blinds[round];
+            L += h.mul(blind_L);
+            R += h.mul(blind_R);
+        }
+
+        // P -> V: L, R
+        let LR = G::Projective::batch_into_affine(vec![L, R]);
+        L_vec.push(LR[0]);
+        R_vec.push(LR[1]);
+
+        // V challenge x, send to P
+        let x = challenge::<G>(transcript, &LR[0], &LR[1]);
+        let x_inv = x.inverse().unwrap();
+
+        // P & V compute:
+        let g_new = G::Projective::batch_into_affine(
+            (0..n).map(|i| gL[i].mul(x_inv) + gR[i].mul(x)).collect(),
+        );
+        if let Some(hs) = h_vec {
+            let (hL, hR) = hs.split_at(n);
+            h_vec = Some(G::Projective::batch_into_affine(
+                (0..n).map(|i| hL[i].mul(x) + hR[i].mul(x_inv)).collect(),
+            ));
+        }
+
+        // P computes:
+        let a_new: Vec<G::Fr> = (0..n).map(|i| aL[i] * x + aR[i] * x_inv).collect();
+        let b_new: Vec<G::Fr> = (0..n).map(|i| bL[i] * x_inv + bR[i] * x).collect();
+
+        a_vec = a_new;
+        b_vec = b_new;
+        g_vec = g_new;
+
+        if let Some((_, blinds)) = blinding {
+            let (blind_L, blind_R) = blinds[round];
+            blind_fin += x * x * blind_L + x_inv * x_inv * blind_R;
+        }
+    }
+
+    let folded = Folded {
+        a: a_vec[0],
+        b: b_vec[0],
+        g: g_vec[0],
+        blinding: blind_fin,
+    };
+    (Reduction { L_vec, R_vec }, folded)
+}
+
+/// The scalars of the checks of a reduction of `n` generators, none of a
+/// reduction of another length.
+fn verification_scalars<G: Curve>(
+    transcript: &mut Transcript,
+    L_vec: &[G::Affine],
+    R_vec: &[G::Affine],
+    n: usize,
+) -> Option<VerificationScalars<G::Fr>> {
+    let lg_n = L_vec.len();
+    if !n.is_power_of_two() || n.trailing_zeros() as usize != lg_n || R_vec.len() != lg_n {
+        return None;
+    }
+
+    let mut x_sq_vec = Vec::with_capacity(lg_n);
+    let mut x_inv_sq_vec = Vec::with_capacity(lg_n);
+    let mut allinv = G::Fr::one();
+    for (L, R) in L_vec.iter().zip(R_vec) {
+        let x = challenge::<G>(transcript, L, R);
+        let x_inv = x.inverse().unwrap();
+        x_sq_vec.push(x * x);
+        x_inv_sq_vec.push(x_inv * x_inv);
+        allinv *= x_inv;
+    }
+
+    // Compute s values inductively. Here adpots optimization from Dalek.
+    let mut s: Vec<G::Fr> = Vec::with_capacity(n);
+    s.push(allinv);
+    for i in 1..n {
+        let lg_i = (32 - 1 - (i as u32).leading_zeros()) as usize;
+        let k = 1 << lg_i;
+        // The challenges are stored in "creation order" as [u_k,...,u_1],
+        // so u_{lg(i)+1} = is indexed by (lg_n-1) - lg_i
+        let u_lg_i_sq = x_sq_vec[(lg_n - 1) - lg_i];
+        s.push(s[i - k] * u_lg_i_sq);
+    }
+
+    Some((x_sq_vec, x_inv_sq_vec, s))
+}
+
+/// `prove_inner_product` of the generators of the length of `a` and `b`, a
+/// power of two.
+pub(crate) fn prove_with_generators<G: Curve>(
+    transcript: &mut Transcript,
+    g_vec: Vec<G::Affine>,
+    h_vec: Vec<G::Affine>,
+    u: G::Affine,
+    a_vec: Vec<G::Fr>,
+    b_vec: Vec<G::Fr>,
+) -> Proof<G> {
+    assert_eq!(g_vec.len(), h_vec.len());
+    let (reduction, folded) = reduce::<G>(transcript, g_vec, Some(h_vec), u, None, a_vec, b_vec);
+
+    Proof {
+        L_vec: reduction.L_vec,
+        R_vec: reduction.R_vec,
+        a: folded.a,
+        b: folded.b,
+    }
+}
+
+/// `verify` of the generators of the length of the argument. Proofs of
+/// another length are rejected.
+pub(crate) fn verify_with_generators<G: Curve>(
+    transcript: &mut Transcript,
+    g_vec: &[G::Affine],
+    h_vec: &[G::Affine],
+    u: G::Affine,
+    P: &G::Projective,
+    proof: &Proof<G>,
+) -> bool {
+    let n = g_vec.len();
+    if h_vec.len() != n {
+        return false;
+    }
+    let (x_sq_vec, x_inv_sq_vec, s) = match proof.verification_scalars(transcript, n) {
+        Some(scalars) => scalars,
+        None => return false,
+    };
+
+    let a_s: Vec<G::Fr> = (0..n).map(|i| proof.a * s[i]).collect();
+    let b_s: Vec<G::Fr> = (0..n).map(|i| proof.b * s[n - 1 - i]).collect();
+
+    let c_final = proof.a * proof.b;
+    let CheckP_lhs: G::Projective = G::vartime_multiscalar_mul(&a_s, g_vec)
+        + G::vartime_multiscalar_mul(&b_s, h_vec)
+        + u.mul(c_final);
+    let CheckP_rhs: G::Projective = G::vartime_multiscalar_mul(&x_sq_vec, &proof.L_vec)
+        + G::vartime_multiscalar_mul(&x_inv_sq_vec, &proof.R_vec)
+        + P;
+
+    CheckP_lhs == CheckP_rhs
+}
+
+/// Proves the inner product of `a` and `b` of the commitment
+/// `commit_inner_product(gens, a, b)`, of the challenges of `transcript`.
+pub fn prove_inner_product<G: Curve>(
+    gens: &Generators<G>,
+    a: &[G::Fr],
+    b: &[G::Fr],
+    transcript: &mut Transcript,
+) -> Proof<G> {
+    assert_eq!(a.len(), b.len());
+    let n = padded_len(a.len());
+    assert!(n <= gens.size(), "too few generators");
+
+    prove_with_generators(
+        transcript,
+        gens.g_vec[0..n].to_vec(),
+        gens.h_vec[0..n].to_vec(),
+        gens.u,
+        pad(a, n),
+        pad(b, n),
+    )
+}
+
+/// Verifies the proof of the inner product of vectors of `n` elements of
+/// the commitment `P`, of the challenges of `transcript`. Proofs of vectors
+/// of another padded length are rejected.
+pub fn verify<G: Curve>(
+    gens: &Generators<G>,
+    n: usize,
+    P: &G::Projective,
+    proof: &Proof<G>,
+    transcript: &mut Transcript,
+) -> bool {
+    let n = padded_len(n);
+    if n > gens.size() {
+        return false;
+    }
+
+    verify_with_generators(
+        transcript,
+        &gens.g_vec[0..n],
+        &gens.h_vec[0..n],
+        gens.u,
+        P,
+        proof,
+    )
+}
+
+/// Folds the argument of the public `b` of the commitment
+/// `commit(gens, a, blinding) + <a, b> * u`, of the blindings of `L` and
+/// `R` of each round, `log2(padded_len(n))` of them.
+pub fn prove_reduction<G: Curve>(
+    gens: &Generators<G>,
+    a: &[G::Fr],
+    b: &[G::Fr],
+    blinding: G::Fr,
+    blinds: &[(G::Fr, G::Fr)],
+    transcript: &mut Transcript,
+) -> (Reduction<G>, Folded<G>) {
+    assert_eq!(a.len(), b.len());
+    let n = padded_len(a.len());
+    assert!(n <= gens.size(), "too few generators");
+
+    let (reduction, mut folded) = reduce(
+        transcript,
+        gens.g_vec[0..n].to_vec(),
+        None,
+        gens.u,
+        Some((gens.h, blinds)),
+        pad(a, n),
+        pad(b, n),
+    );
+    folded.blinding += blinding;
+
+    (reduction, folded)
+}
+
+/// The folded `<b, s>`, `g` and commitment of the reduction of the public
+/// `b` and of `commitment`, of the challenges of `transcript`, which the
+/// proof of knowledge of the last round opens. None of a reduction of
+/// another length than `b`.
+pub fn verify_reduction<G: Curve>(
+    gens: &Generators<G>,
+    b: &[G::Fr],
+    commitment: &G::Projective,
+    reduction: &Reduction<G>,
+    transcript: &mut Transcript,
+) -> Option<(G::Fr, G::Affine, G::Affine)> {
+    let n = padded_len(b.len());
+    if n > gens.size() {
+        return None;
+    }
+    let (x_sq_vec, x_inv_sq_vec, s) =
+        verification_scalars::<G>(transcript, &reduction.L_vec, &reduction.R_vec, n)?;
+
+    let b_s = inner_product(&pad(b, n), &s);
+    let g_hat = G::vartime_multiscalar_mul(&s, &gens.g_vec[0..n]);
+    let P_hat = G::vartime_multiscalar_mul(&x_sq_vec, &reduction.L_vec)
+        + G::vartime_multiscalar_mul(&x_inv_sq_vec, &reduction.R_vec)
+        + commitment;
+
+    let hats = G::Projective::batch_into_affine(vec![g_hat, P_hat]);
+    Some((b_s, hats[0], hats[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::{Bls12_381, Bn_256};
+    use math::test_rng;
+
+    use super::*;
+
+    fn random_vec<F: Field>(n: usize) -> Vec<F> {
+        let rng = &mut test_rng();
+        (0..n).map(|_| F::rand(rng)).collect()
+    }
+
+    fn inner_product_helper<G: Curve>(n: usize) {
+        let gens = Generators::<G>::new(n, &mut test_rng());
+        let a = random_vec::<G::Fr>(n);
+        let b = random_vec::<G::Fr>(n);
+        let P = commit_inner_product(&gens, &a, &b);
+
+        let proof = prove_inner_product(&gens, &a, &b, &mut Transcript::new(b"ipa test"));
+        assert_eq!(proof.L_vec.len(), padded_len(n).trailing_zeros() as usize);
+        assert!(verify(
+            &gens,
+            n,
+            &P,
+            &proof,
+            &mut Transcript::new(b"ipa test")
+        ));
+
+        // the proof is of its commitment only.
+        let other = P + gens.u.into_projective();
+        assert!(!verify(
+            &gens,
+            n,
+            &other,
+            &proof,
+            &mut Transcript::new(b"ipa test")
+        ));
+    }
+
+    #[test]
+    fn inner_product_of_one_element() {
+        inner_product_helper::<Bn_256>(1);
+        inner_product_helper::<Bls12_381>(1);
+    }
+
+    #[test]
+    fn inner_product_of_powers_of_two() {
+        inner_product_helper::<Bn_256>(2);
+        inner_product_helper::<Bn_256>(64);
+        inner_product_helper::<Bls12_381>(32);
+    }
+
+    #[test]
+    fn inner_product_padded() {
+        inner_product_helper::<Bn_256>(5);
+        inner_product_helper::<Bls12_381>(33);
+
+        let gens = Generators::<Bn_256>::new(8, &mut test_rng());
+        let a = random_vec(5);
+        let b = random_vec(5);
+        let P = commit_inner_product(&gens, &a, &b);
+        let proof = prove_inner_product(&gens, &a, &b, &mut Transcript::new(b"ipa test"));
+
+        // the argument is of the padded length, a proof of 8 elements is not
+        // one of 4 or 16.
+        assert!(verify(
+            &gens,
+            8,
+            &P,
+            &proof,
+            &mut Transcript::new(b"ipa test")
+        ));
+        assert!(!verify(
+            &gens,
+            4,
+            &P,
+            &proof,
+            &mut Transcript::new(b"ipa test")
+        ));
+        assert!(!verify(
+            &gens,
+            16,
+            &P,
+            &proof,
+            &mut Transcript::new(b"ipa test")
+        ));
+    }
+
+    #[test]
+    fn inner_product_of_another_transcript() {
+        let gens = Generators::<Bn_256>::new(16, &mut test_rng());
+        let a = random_vec(16);
+        let b = random_vec(16);
+        let P = commit_inner_product(&gens, &a, &b);
+
+        let proof = prove_inner_product(&gens, &a, &b, &mut Transcript::new(b"ipa test"));
+        assert!(!verify(
+            &gens,
+            16,
+            &P,
+            &proof,
+            &mut Transcript::new(b"ipa other")
+        ));
+
+        // nor of the same label of other messages before it.
+        let mut transcript = Transcript::new(b"ipa test");
+        transcript.append_message(b"P", &math::to_bytes![P.into_affine()].unwrap());
+        assert!(!verify(&gens, 16, &P, &proof, &mut transcript));
+    }
+
+    #[test]
+    fn commit_padded() {
+        let gens = Generators::<Bn_256>::new(8, &mut test_rng());
+        let v = random_vec(5);
+        let r = random_vec(1)[0];
+
+        // the commitment of a vector is the one of it padded with zeros.
+        assert_eq!(commit(&gens, &v, r), commit(&gens, &pad(&v, 8), r));
+        assert_eq!(
+            commit(&gens, &v, r) - commit(&gens, &v, Zero::zero()),
+            gens.h.mul(r)
+        );
+    }
+
+    /// The reduction of a public `b` and the check of its last round of
+    /// `a`, of the folded blinding.
+    fn reduction_helper<G: Curve>(
+        n: usize,
+        transcript: &'static [u8],
+        other: &'static [u8],
+    ) -> bool {
+        let rng = &mut test_rng();
+        let gens = Generators::<G>::new(n, rng);
+        let a = random_vec::<G::Fr>(n);
+        let b = random_vec::<G::Fr>(n);
+        let r = G::Fr::rand(rng);
+        let blinds: Vec<_> = (0..padded_len(n).trailing_zeros())
+            .map(|_| (G::Fr::rand(rng), G::Fr::rand(rng)))
+            .collect();
+        let P = commit(&gens, &a, r) + gens.u.mul(inner_product(&a, &b));
+
+        let (reduction, folded) =
+            prove_reduction(&gens, &a, &b, r, &blinds, &mut Transcript::new(transcript));
+        let (b_s, g_hat, P_hat) =
+            match verify_reduction(&gens, &b, &P, &reduction, &mut Transcript::new(other)) {
+                Some(hats) => hats,
+                None => return false,
+            };
+
+        // P_hat = a * g_hat + a * b * u + blinding * h.
+        let opened = g_hat.mul(folded.a) + gens.u.mul(folded.a * b_s) + gens.h.mul(folded.blinding);
+        b_s == folded.b && opened == P_hat.into_projective()
+    }
+
+    #[test]
+    fn reduction() {
+        assert!(reduction_helper::<Bn_256>(1, b"ipa test", b"ipa test"));
+        assert!(reduction_helper::<Bn_256>(16, b"ipa test", b"ipa test"));
+        assert!(reduction_helper::<Bls12_381>(6, b"ipa test", b"ipa test"));
+        assert!(!reduction_helper::<Bn_256>(16, b"ipa test", b"ipa other"));
+    }
+}
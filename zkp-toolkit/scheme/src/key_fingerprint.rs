@@ -0,0 +1,202 @@
+//! Fingerprints of verifying keys, to tell which key a file is of without
+//! deserializing it into the types of one curve.
+//!
+//! The fingerprint is BLAKE2s over the scheme, the curve and the postcard
+//! bytes of the key, so that it is the same of a key and of the key read
+//! back of any serde format.
+
+use blake2::{Blake2s, Digest};
+use math::{AffineCurve, One, ToBytes};
+use serde::Serialize;
+
+/// The fingerprint of a verifying key, of `key_fingerprint`.
+pub type KeyFingerprint = [u8; 32];
+
+/// The id of a curve, of `curve_id`.
+pub type CurveId = [u8; 32];
+
+/// The version of the encoding the fingerprints are of, bumped when it or
+/// the layout of a key changes.
+pub const KEY_FINGERPRINT_VERSION: u8 = 2;
+
+/// The schemes of which the verifying keys are fingerprinted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemeTag {
+    Groth16,
+    Marlin,
+    SpartanSnark,
+    SpartanNizk,
+}
+
+impl SchemeTag {
+    /// The name of the scheme, as the cli calls it.
+    pub fn name(self) -> &'static str {
+        match self {
+            SchemeTag::Groth16 => "groth16",
+            SchemeTag::Marlin => "marlin",
+            SchemeTag::SpartanSnark => "spartan_snark",
+            SchemeTag::SpartanNizk => "spartan_nizk",
+        }
+    }
+
+    fn tag(self) -> u8 {
+        match self {
+            SchemeTag::Groth16 => 1,
+            SchemeTag::Marlin => 2,
+            SchemeTag::SpartanSnark => 3,
+            SchemeTag::SpartanNizk => 4,
+        }
+    }
+}
+
+/// BLAKE2s over the generator of the group `G` and the largest element of
+/// its scalar field, which tell the curves apart.
+pub fn curve_id<G: AffineCurve>() -> CurveId {
+    let mut bytes = vec![];
+    G::prime_subgroup_generator().write(&mut bytes).unwrap();
+    (-G::ScalarField::one()).write(&mut bytes).unwrap();
+
+    let mut hasher = Blake2s::new();
+    hasher.input(b"zkp-toolkit curve id");
+    hasher.input(&bytes);
+    let mut id = [0u8; 32];
+    id.copy_from_slice(&hasher.result());
+    id
+}
+
+/// The fingerprint of the verifying `key` of `scheme` over the curve of the
+/// group `G`, of `KEY_FINGERPRINT_VERSION`.
+pub fn key_fingerprint<G: AffineCurve, K: Serialize>(scheme: SchemeTag, key: &K) -> KeyFingerprint {
+    let mut hasher = Blake2s::new();
+    hasher.input(b"zkp-toolkit key fingerprint");
+    hasher.input(&[KEY_FINGERPRINT_VERSION, scheme.tag()]);
+    hasher.input(&curve_id::<G>());
+    // postcard fails of sequences of unknown lengths only, of which the
+    // derived `Serialize` of the keys has none.
+    hasher.input(&postcard::to_allocvec(key).unwrap());
+
+    let mut fingerprint = [0u8; 32];
+    fingerprint.copy_from_slice(&hasher.result());
+    fingerprint
+}
+
+#[cfg(test)]
+mod test {
+    use curve::{bls12_381::Bls12_381, bn_256::Bn_256};
+    use math::PairingEngine;
+
+    use super::*;
+
+    #[cfg(feature = "groth16")]
+    fn vk<E: PairingEngine>(num: usize) -> crate::groth16::VerifyKey<E> {
+        use crate::test_circuits::Powers;
+        use math::{test_rng, UniformRand};
+
+        let rng = &mut test_rng();
+        let c = Powers::<E::Fr> {
+            x: Some(E::Fr::rand(rng)),
+            num,
+        };
+        crate::groth16::generate_random_parameters::<E, _, _>(c, rng)
+            .unwrap()
+            .vk
+    }
+
+    #[cfg(feature = "groth16")]
+    #[test]
+    fn test_key_fingerprint() {
+        use crate::groth16::VerifyKey;
+
+        let square = vk::<Bn_256>(1);
+        let fingerprint = square.key_fingerprint();
+        assert_eq!(square.clone().key_fingerprint(), fingerprint);
+
+        let bytes = postcard::to_allocvec(&square).unwrap();
+        let read: VerifyKey<Bn_256> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(read.key_fingerprint(), fingerprint);
+        let json = serde_json::to_string(&square).unwrap();
+        let read: VerifyKey<Bn_256> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.key_fingerprint(), fingerprint);
+
+        // of another circuit, of another curve, and of another setup.
+        assert_ne!(vk::<Bn_256>(2).key_fingerprint(), fingerprint);
+        assert_ne!(vk::<Bls12_381>(1).key_fingerprint(), fingerprint);
+        let mut other = square.clone();
+        other.delta_g2 = other.gamma_g2;
+        assert_ne!(other.key_fingerprint(), fingerprint);
+    }
+
+    #[cfg(feature = "marlin")]
+    #[test]
+    fn test_marlin_key_fingerprint() {
+        use crate::marlin::{index, max_degree, universal_setup, IndexVerifierKey};
+        use crate::test_circuits::Powers;
+        use curve::bn_256::Fr;
+        use math::test_rng;
+
+        let rng = &mut test_rng();
+        let powers = |num| Powers::<Fr> { x: None, num };
+        let degree = max_degree::<Bn_256, _>(powers(6)).unwrap();
+        let srs = universal_setup::<Bn_256, _>(degree, rng).unwrap();
+        let (_, ivk) = index(&srs, powers(5)).unwrap();
+        let fingerprint = ivk.key_fingerprint();
+
+        let bytes = postcard::to_allocvec(&ivk).unwrap();
+        let read: IndexVerifierKey<Bn_256> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(read.key_fingerprint(), fingerprint);
+        let json = serde_json::to_string(&ivk).unwrap();
+        let read: IndexVerifierKey<Bn_256> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.key_fingerprint(), fingerprint);
+
+        // of another circuit, and of another universal setup.
+        let (_, other) = index(&srs, powers(6)).unwrap();
+        assert_ne!(other.key_fingerprint(), fingerprint);
+        let srs = universal_setup::<Bn_256, _>(degree, rng).unwrap();
+        let (_, other) = index(&srs, powers(5)).unwrap();
+        assert_ne!(other.key_fingerprint(), fingerprint);
+    }
+
+    #[cfg(feature = "spartan")]
+    #[test]
+    fn test_spartan_key_fingerprint() {
+        use crate::spartan::{nizk, snark};
+        use crate::test_circuits::Powers;
+        use curve::bn_256::Fr;
+        use math::test_rng;
+
+        let rng = &mut test_rng();
+        let powers = |num| Powers::<Fr> { x: None, num };
+        let (_, vk) = snark::generate_random_parameters::<Bn_256, _, _>(powers(5), rng)
+            .unwrap()
+            .keypair();
+        let fingerprint = vk.key_fingerprint();
+        let bytes = postcard::to_allocvec(&vk).unwrap();
+        let read: snark::VerifyKey<Bn_256> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(read.key_fingerprint(), fingerprint);
+        let (_, other) = snark::generate_random_parameters::<Bn_256, _, _>(powers(6), rng)
+            .unwrap()
+            .keypair();
+        assert_ne!(other.key_fingerprint(), fingerprint);
+
+        let (_, vk) = nizk::generate_random_parameters::<Bn_256, _, _>(powers(5), rng)
+            .unwrap()
+            .keypair();
+        let nizk_fingerprint = vk.key_fingerprint();
+        let bytes = postcard::to_allocvec(&vk).unwrap();
+        let read: nizk::VerifyKey<Bn_256> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(read.key_fingerprint(), nizk_fingerprint);
+        assert_ne!(nizk_fingerprint, fingerprint);
+
+        // of the scheme, even of the same bytes.
+        let as_snark =
+            key_fingerprint::<<Bn_256 as PairingEngine>::G1Affine, _>(SchemeTag::SpartanSnark, &vk);
+        assert_ne!(as_snark, nizk_fingerprint);
+    }
+
+    #[test]
+    fn test_curve_id() {
+        let curve = curve_id::<<Bn_256 as PairingEngine>::G1Affine>();
+        assert_ne!(curve_id::<<Bls12_381 as PairingEngine>::G1Affine>(), curve);
+        assert_ne!(curve, [0u8; 32]);
+    }
+}
@@ -8,7 +8,9 @@
 #![deny(unused_attributes, unused_imports, unused_mut)]
 #![deny(renamed_and_removed_lints, stable_features, unused_allocation)]
 #![deny(unused_comparisons, bare_trait_objects, unused_must_use, const_err)]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "mmap"), forbid(unsafe_code))]
+// mapping a file is unsafe, allowed only where `MappedParameters` does it.
+#![cfg_attr(feature = "mmap", deny(unsafe_code))]
 #![deny(trivial_casts)]
 
 #[allow(unused_imports)]
@@ -25,6 +27,7 @@ extern crate alloc;
 #[allow(unused_imports)]
 use alloc::{
     borrow::Cow,
+    boxed::Box,
     collections::{BTreeMap, BTreeSet},
     string::{String, ToString},
     vec::Vec,
@@ -34,6 +37,7 @@ use alloc::{
 #[allow(unused_imports)]
 use std::{
     borrow::Cow,
+    boxed::Box,
     collections::{BTreeMap, BTreeSet},
     string::{String, ToString},
     vec::Vec,
@@ -47,9 +51,19 @@ extern crate curve;
 
 pub mod r1cs;
 
+pub mod key_fingerprint;
+
+// of the circuits of the enabled schemes only.
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_circuits;
+
 #[cfg(feature = "groth16")]
 pub mod groth16;
 
+#[cfg(feature = "ipa")]
+pub mod ipa;
+
 #[cfg(feature = "bulletproofs")]
 pub mod bulletproofs;
 
@@ -7,10 +7,8 @@ use crate::{String, Vec};
 
 use crate::marlin::ahp::arithmetic::{matrix_density, sort_matrix_columns, Matrix};
 
-fn make_constraint_matrices_square<F: Field, CS: ConstraintSystem<F>>(
-    cs: &mut CS,
-    num_formatted_variables: usize,
-) {
+fn make_constraint_matrices_square<F: Field, CS: ConstraintSystem<F>>(cs: &mut CS) {
+    let num_formatted_variables = cs.num_public_inputs() + cs.num_private_variables();
     let num_constraints = cs.num_constraints();
     let num_paddings = ((num_formatted_variables as isize) - (num_constraints as isize)).abs();
     if num_formatted_variables < num_constraints {
@@ -68,8 +66,7 @@ impl<F: Field> IndexerConstraintSystem<F> {
     }
 
     pub(crate) fn make_matrices_square(&mut self) {
-        let num_formatted_variables = self.num_input_variables + self.num_witness_variables;
-        make_constraint_matrices_square(self, num_formatted_variables);
+        make_constraint_matrices_square(self);
     }
 
     pub(crate) fn process_matrices(&mut self) {
@@ -192,6 +189,14 @@ impl<F: Field> ConstraintSystem<F> for IndexerConstraintSystem<F> {
     fn num_constraints(&self) -> usize {
         self.num_constraints
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_input_variables
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_witness_variables
+    }
 }
 
 pub(crate) struct ProverConstraintSystem<F: Field> {
@@ -214,8 +219,7 @@ impl<F: Field> ProverConstraintSystem<F> {
     }
 
     pub(crate) fn make_matrices_square(&mut self) {
-        let num_formatted_variables = self.num_input_variables + self.num_witness_variables;
-        make_constraint_matrices_square(self, num_formatted_variables);
+        make_constraint_matrices_square(self);
     }
 
     pub(crate) fn format_public_input(public_input: &[F]) -> Vec<F> {
@@ -282,4 +286,12 @@ impl<F: Field> ConstraintSystem<F> for ProverConstraintSystem<F> {
     fn num_constraints(&self) -> usize {
         self.num_constraints
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_input_variables
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_witness_variables
+    }
 }
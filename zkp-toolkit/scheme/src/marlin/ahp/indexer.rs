@@ -1,7 +1,7 @@
 use math::fft::EvaluationDomain;
 use math::PrimeField;
 
-use crate::r1cs::{ConstraintSynthesizer, SynthesisError};
+use crate::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
 
 use crate::marlin::ahp::arithmetic::{compose_matrix_polynomials, Matrix, MatrixPolynomials};
 use crate::marlin::ahp::constraint_systems::IndexerConstraintSystem;
@@ -68,16 +68,34 @@ impl<'a, F: PrimeField> Index<'a, F> {
 }
 
 impl<F: PrimeField> AHP<F> {
-    pub fn index<'a, C: ConstraintSynthesizer<F>>(c: C) -> Result<Index<'a, F>, Error> {
+    fn synthesize<C: ConstraintSynthesizer<F>>(
+        c: C,
+    ) -> Result<(IndexerConstraintSystem<F>, IndexInfo), Error> {
         let mut ics = IndexerConstraintSystem::new();
         c.generate_constraints(&mut ics)?;
         ics.make_matrices_square();
         ics.process_matrices();
 
-        let num_inputs = ics.num_input_variables;
-        let num_constraints = ics.num_constraints;
-        let num_variables = ics.num_input_variables + ics.num_witness_variables;
-        let num_non_zeros = ics.num_non_zeros();
+        let index_info = IndexInfo {
+            num_constraints: ics.num_constraints(),
+            num_variables: ics.num_public_inputs() + ics.num_private_variables(),
+            num_non_zeros: ics.num_non_zeros(),
+        };
+
+        Ok((ics, index_info))
+    }
+
+    /// The sizes of the index of `c`, without computing its polynomials.
+    pub fn index_info<C: ConstraintSynthesizer<F>>(c: C) -> Result<IndexInfo, Error> {
+        Ok(Self::synthesize(c)?.1)
+    }
+
+    pub fn index<'a, C: ConstraintSynthesizer<F>>(c: C) -> Result<Index<'a, F>, Error> {
+        let (ics, index_info) = Self::synthesize(c)?;
+
+        let num_inputs = ics.num_public_inputs();
+        let num_variables = index_info.num_variables;
+        let num_non_zeros = index_info.num_non_zeros;
 
         let domain_x =
             EvaluationDomain::new(num_inputs).ok_or(SynthesisError::PolynomialDegreeTooLarge)?;
@@ -98,12 +116,6 @@ impl<F: PrimeField> AHP<F> {
         let c_star_polys =
             compose_matrix_polynomials("c", &c, domain_x, domain_h, domain_k, domain_b)?;
 
-        let index_info = IndexInfo {
-            num_constraints,
-            num_variables,
-            num_non_zeros,
-        };
-
         Ok(Index {
             index_info,
             a,
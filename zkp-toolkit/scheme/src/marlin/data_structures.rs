@@ -1,5 +1,7 @@
 use math::PairingEngine;
 
+use crate::key_fingerprint::{key_fingerprint, KeyFingerprint, SchemeTag};
+use crate::r1cs::CircuitFingerprint;
 use crate::Vec;
 
 use crate::marlin::ahp::indexer::{Index, IndexInfo};
@@ -10,12 +12,23 @@ pub struct IndexVerifierKey<E: PairingEngine> {
     pub index_info: IndexInfo,
     pub index_comms: Vec<Commitment<E>>,
     pub verifier_key: VerifierKey<E>,
+    /// The fingerprint of the circuit the key was indexed for.
+    pub fingerprint: CircuitFingerprint,
 }
 
 impl<E: PairingEngine> IndexVerifierKey<E> {
     pub fn iter(&self) -> impl Iterator<Item = &Commitment<E>> {
         self.index_comms.iter()
     }
+
+    /// The fingerprint of the key, of the scheme and the curve, to tell which
+    /// key a file is of. Not `fingerprint`, the circuit's the key is of.
+    pub fn key_fingerprint(&self) -> KeyFingerprint
+    where
+        Self: serde::Serialize,
+    {
+        key_fingerprint::<E::G1Affine, _>(SchemeTag::Marlin, self)
+    }
 }
 
 impl<E: PairingEngine> math::ToBytes for IndexVerifierKey<E> {
@@ -26,8 +26,11 @@ impl From<PCError> for Error {
 }
 
 impl From<Error> for SynthesisError {
-    fn from(_err: Error) -> SynthesisError {
-        SynthesisError::Unsatisfiable // Maybe can better.
+    fn from(err: Error) -> SynthesisError {
+        match err {
+            Error::AHPError(err) => err.into(),
+            _ => SynthesisError::Unsatisfiable, // Maybe can better.
+        }
     }
 }
 
@@ -38,7 +41,10 @@ impl From<PCError> for SynthesisError {
 }
 
 impl From<AHPError> for SynthesisError {
-    fn from(_err: AHPError) -> SynthesisError {
-        SynthesisError::Unsatisfiable // Maybe can better.
+    fn from(err: AHPError) -> SynthesisError {
+        match err {
+            AHPError::ConstraintSystemError(err) => err,
+            _ => SynthesisError::Unsatisfiable, // Maybe can better.
+        }
     }
 }
@@ -2,7 +2,7 @@ use math::fft::EvaluationDomain;
 use math::{PairingEngine, ToBytes, UniformRand};
 use rand::Rng;
 
-use crate::r1cs::{ConstraintSynthesizer, SynthesisError};
+use crate::r1cs::{stats::analyze, ConstraintSynthesizer, FingerprintCircuit, SynthesisError};
 use crate::{ToString, Vec};
 
 mod pc;
@@ -35,11 +35,25 @@ pub fn universal_setup<E: PairingEngine, R: Rng>(
     Ok(srs)
 }
 
+/// The `max_degree` of the universal setup that indexing `c` needs, of the
+/// entries of the rows of its matrices only, not of the matrices.
+pub fn max_degree<E: PairingEngine, C: ConstraintSynthesizer<E::Fr>>(
+    c: C,
+) -> Result<usize, Error> {
+    let stats = analyze(c).map_err(ahp::Error::from)?;
+    Ok(AHP::<E::Fr>::max_degree(
+        stats.num_constraints,
+        stats.num_inputs + stats.num_aux,
+        stats.marlin.domain_k,
+    )?)
+}
+
 pub fn index<E: PairingEngine, C: ConstraintSynthesizer<E::Fr>>(
     srs: &UniversalParams<E>,
     c: C,
 ) -> Result<(IndexProverKey<E>, IndexVerifierKey<E>), Error> {
-    let index = AHP::index(c)?;
+    let mut fingerprint = [0u8; 32];
+    let index = AHP::index(FingerprintCircuit::record(c, &mut fingerprint))?;
     if srs.max_degree() < index.max_degree() {
         return Err(Error::IndexTooLarge);
     }
@@ -54,6 +68,7 @@ pub fn index<E: PairingEngine, C: ConstraintSynthesizer<E::Fr>>(
         index_info: index.index_info,
         index_comms,
         verifier_key,
+        fingerprint,
     };
     let ipk = IndexProverKey {
         index,
@@ -71,6 +86,7 @@ pub fn create_random_proof<E: PairingEngine, R: Rng, C: ConstraintSynthesizer<E:
     zk_rng: &mut R,
 ) -> Result<Proof<E>, SynthesisError> {
     // init
+    let c = FingerprintCircuit::check(c, &ipk.index_verifier_key.fingerprint);
     let pstate = AHP::<E::Fr>::prover_init(&ipk.index, c)?;
     let public_input = pstate.public_input();
     let mut fs_rng =
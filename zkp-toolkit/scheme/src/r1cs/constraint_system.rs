@@ -48,6 +48,39 @@ pub trait ConstraintSystem<F: Field>: Sized {
         LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
         LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>;
 
+    /// Allocate a variable fixed to the constant `value`, for the gadgets
+    /// that need a variable where a linear combination would not do, e.g.
+    /// the zero state of a hash. The constant one is the "one" input and
+    /// costs nothing; any other costs a private variable and a constraint
+    /// fixing it to `value` times "one". Constants in linear combinations
+    /// are cheaper still, as coefficients of `one()`.
+    fn alloc_constant<A, AR>(&mut self, annotation: A, value: F) -> Result<Variable, SynthesisError>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        if value == F::one() {
+            return Ok(Self::one());
+        }
+
+        let mut cs = self.ns(annotation);
+        let var = cs.alloc(|| "constant", || Ok(value))?;
+        cs.enforce(
+            || "constant is fixed",
+            |lc| lc + var,
+            |lc| lc + Self::one(),
+            |lc| {
+                if value.is_zero() {
+                    lc
+                } else {
+                    lc + (value, Self::one())
+                }
+            },
+        );
+
+        Ok(var)
+    }
+
     /// Create a new (sub)namespace and enter into it. Not intended
     /// for downstream use; use `namespace` instead.
     fn push_namespace<NR, N>(&mut self, name_fn: N)
@@ -76,6 +109,13 @@ pub trait ConstraintSystem<F: Field>: Sized {
 
     /// Output the number of constraints in the system.
     fn num_constraints(&self) -> usize;
+
+    /// Output the number of public inputs in the system, the "one" input
+    /// included.
+    fn num_public_inputs(&self) -> usize;
+
+    /// Output the number of private variables in the system.
+    fn num_private_variables(&self) -> usize;
 }
 
 /// This is a "namespaced" constraint system which borrows a constraint system
@@ -134,6 +174,15 @@ impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F> for Namespace<'_, F,
         self.0.enforce(annotation, a, b, c)
     }
 
+    #[inline]
+    fn alloc_constant<A, AR>(&mut self, annotation: A, value: F) -> Result<Variable, SynthesisError>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.0.alloc_constant(annotation, value)
+    }
+
     // Downstream users who use `namespace` will never interact with these
     // functions and they will never be invoked because the namespace is
     // never a root constraint system.
@@ -161,6 +210,16 @@ impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F> for Namespace<'_, F,
     fn num_constraints(&self) -> usize {
         self.0.num_constraints()
     }
+
+    #[inline]
+    fn num_public_inputs(&self) -> usize {
+        self.0.num_public_inputs()
+    }
+
+    #[inline]
+    fn num_private_variables(&self) -> usize {
+        self.0.num_private_variables()
+    }
 }
 
 impl<F: Field, CS: ConstraintSystem<F>> Drop for Namespace<'_, F, CS> {
@@ -212,6 +271,15 @@ impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F> for &mut CS {
         (**self).enforce(annotation, a, b, c)
     }
 
+    #[inline]
+    fn alloc_constant<A, AR>(&mut self, annotation: A, value: F) -> Result<Variable, SynthesisError>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        (**self).alloc_constant(annotation, value)
+    }
+
     #[inline]
     fn push_namespace<NR, N>(&mut self, name_fn: N)
     where
@@ -235,4 +303,14 @@ impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F> for &mut CS {
     fn num_constraints(&self) -> usize {
         (**self).num_constraints()
     }
+
+    #[inline]
+    fn num_public_inputs(&self) -> usize {
+        (**self).num_public_inputs()
+    }
+
+    #[inline]
+    fn num_private_variables(&self) -> usize {
+        (**self).num_private_variables()
+    }
 }
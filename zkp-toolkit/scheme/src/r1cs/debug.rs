@@ -0,0 +1,263 @@
+use core::fmt;
+use math::Field;
+
+use crate::{String, Vec};
+
+use super::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+
+/// The first constraint `a * b = c` that a witness violates, of its full
+/// namespace path, with `a`, `b` and `c` evaluated at the witness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsatisfiedConstraint<F: Field> {
+    pub path: String,
+    pub a: F,
+    pub b: F,
+    pub c: F,
+}
+
+impl<F: Field> fmt::Display for UnsatisfiedConstraint<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unsatisfied constraint \"{}\": {} * {} != {}",
+            self.path, self.a, self.b, self.c
+        )
+    }
+}
+
+/// A constraint system that computes the assignment of a circuit, with the
+/// namespace paths of its public inputs. If `check`, it checks every
+/// constraint against the assignment as it is enforced, keeping the first
+/// violated one.
+pub(super) struct AssignmentConstraintSystem<F: Field> {
+    check: bool,
+    current_namespace: Vec<String>,
+    num_constraints: usize,
+    pub(super) inputs: Vec<(String, F)>,
+    aux_assignment: Vec<F>,
+    pub(super) unsatisfied: Option<UnsatisfiedConstraint<F>>,
+}
+
+impl<F: Field> AssignmentConstraintSystem<F> {
+    /// Synthesizes `circuit` with its witness, the "one" input first.
+    pub(super) fn synthesize<C: ConstraintSynthesizer<F>>(
+        circuit: C,
+        check: bool,
+    ) -> Result<Self, SynthesisError> {
+        let mut cs = AssignmentConstraintSystem {
+            check,
+            current_namespace: Vec::new(),
+            num_constraints: 0,
+            inputs: Vec::new(),
+            aux_assignment: Vec::new(),
+            unsatisfied: None,
+        };
+        cs.alloc_input(|| "one", || Ok(F::one()))?;
+        circuit.generate_constraints(&mut cs)?;
+
+        Ok(cs)
+    }
+
+    fn path(&self, name: String) -> String {
+        let mut path = self.current_namespace.join("/");
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str(&name);
+        path
+    }
+
+    fn eval(&self, lc: &LinearCombination<F>) -> F {
+        lc.as_ref()
+            .iter()
+            .fold(F::zero(), |acc, (var, coeff)| match var.get_unchecked() {
+                Index::Input(i) => acc + &(self.inputs[i].1 * coeff),
+                Index::Aux(i) => acc + &(self.aux_assignment[i] * coeff),
+            })
+    }
+}
+
+impl<F: Field> ConstraintSystem<F> for AssignmentConstraintSystem<F> {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let value = f().map_err(|e| e.in_context(self.path(annotation().into())))?;
+        self.aux_assignment.push(value);
+
+        Ok(Variable::new_unchecked(Index::Aux(
+            self.aux_assignment.len() - 1,
+        )))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let path = self.path(annotation().into());
+        let value = f().map_err(|e| e.in_context(path.clone()))?;
+        self.inputs.push((path, value));
+
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+        if !self.check || self.unsatisfied.is_some() {
+            return;
+        }
+
+        let a = self.eval(&a(LinearCombination::zero()));
+        let b = self.eval(&b(LinearCombination::zero()));
+        let c = self.eval(&c(LinearCombination::zero()));
+        if a * &b != c {
+            self.unsatisfied = Some(UnsatisfiedConstraint {
+                path: self.path(annotation().into()),
+                a,
+                b,
+                c,
+            });
+        }
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.current_namespace.push(name_fn().into());
+    }
+
+    fn pop_namespace(&mut self) {
+        assert!(self.current_namespace.pop().is_some());
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
+}
+
+/// Synthesizes `circuit` with its witness and checks every constraint,
+/// returning the first violated one, e.g. to learn why a proof of it fails
+/// to verify, or the error of the synthesis, as proving it would fail too,
+/// with the namespace path of the variable that failed.
+pub fn debug_satisfied<F, C>(circuit: C) -> Result<Option<UnsatisfiedConstraint<F>>, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    Ok(AssignmentConstraintSystem::synthesize(circuit, true)?.unsatisfied)
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+
+    use super::*;
+    use crate::ToString;
+
+    /// `x * x = y`, of any witness.
+    struct Square {
+        x: Fr,
+        y: Fr,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Square {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let x = cs.alloc(|| "x", || Ok(self.x))?;
+            let mut cs = cs.ns(|| "square");
+            let y = cs.alloc(|| "y", || Ok(self.y))?;
+            cs.enforce(|| "x is x", |lc| lc + x, |lc| lc + CS::one(), |lc| lc + x);
+            cs.enforce(|| "x * x = y", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+            assert_eq!(cs.num_constraints(), 2);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_debug_satisfied() {
+        let three = Fr::from(3u32);
+        assert_eq!(
+            debug_satisfied(Square {
+                x: three,
+                y: Fr::from(9u32)
+            })
+            .unwrap(),
+            None
+        );
+
+        let unsatisfied = debug_satisfied(Square {
+            x: three,
+            y: Fr::from(10u32),
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            unsatisfied,
+            UnsatisfiedConstraint {
+                path: "square/x * x = y".to_string(),
+                a: three,
+                b: three,
+                c: Fr::from(10u32),
+            }
+        );
+    }
+
+    #[test]
+    fn test_debug_satisfied_missing_assignment() {
+        struct Missing;
+
+        impl ConstraintSynthesizer<Fr> for Missing {
+            fn generate_constraints<CS: ConstraintSystem<Fr>>(
+                self,
+                cs: &mut CS,
+            ) -> Result<(), SynthesisError> {
+                Square {
+                    x: Fr::from(3u32),
+                    y: Fr::from(9u32),
+                }
+                .generate_constraints(cs)?;
+                let mut cs = cs.ns(|| "square");
+                cs.alloc(|| "y", || Err(SynthesisError::AssignmentMissing))?;
+
+                Ok(())
+            }
+        }
+
+        assert_eq!(
+            debug_satisfied(Missing).unwrap_err().to_string(),
+            "square/y: an assignment for a variable could not be computed"
+        );
+    }
+}
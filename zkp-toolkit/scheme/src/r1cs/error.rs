@@ -3,6 +3,8 @@
 use core::fmt;
 use math::io;
 
+use crate::{Box, String};
+
 /// This is an error that could occur during circuit synthesis contexts,
 /// such as CRS generation, proving or verification.
 #[derive(Debug)]
@@ -21,8 +23,54 @@ pub enum SynthesisError {
     IoError(io::Error),
     /// During verification, our verifying key was malformed.
     MalformedVerifyingKey,
+    /// During verification, the proof had a point off the curve or out of the
+    /// prime order subgroup.
+    MalformedProof,
+    /// The parameters had a point off the curve or out of the prime order
+    /// subgroup, or were out of their range.
+    MalformedParameters,
+    /// During aggregation, the number of proofs was not a power of two, or
+    /// not the one the aggregation key was specialized to.
+    InvalidAggregationSize,
+    /// During a range proof, the number of bits was not a power of two of at
+    /// most 64, or there were no values, or not as many blindings as values.
+    InvalidRangeSize,
+    /// During batch verification, the proof of the index did not verify.
+    InvalidBatchProof(usize),
     /// During CRS generation, we observed an unconstrained auxiliary variable
     UnconstrainedVariable,
+    /// During proving with a witness, it turned out to be of another circuit.
+    WitnessMismatch,
+    /// During proving with a witness, it was of an unsupported version.
+    UnsupportedWitnessVersion,
+    /// During proving, the circuit was of another fingerprint than the one
+    /// the key was generated for.
+    CircuitMismatch,
+    /// An error in the allocation of a variable, of its namespace path.
+    /// Only the constraint systems that track namespaces, for testing and
+    /// debugging, attach it, so proving pays nothing for it.
+    Context {
+        path: String,
+        error: Box<SynthesisError>,
+    },
+}
+
+impl SynthesisError {
+    /// Attaches the namespace `path` of the variable whose allocation failed.
+    pub fn in_context(self, path: String) -> Self {
+        SynthesisError::Context {
+            path,
+            error: Box::new(self),
+        }
+    }
+
+    /// The error without the namespace path of its variable.
+    pub fn without_context(&self) -> &SynthesisError {
+        match self {
+            SynthesisError::Context { error, .. } => error.without_context(),
+            error => error,
+        }
+    }
 }
 
 impl From<io::Error> for SynthesisError {
@@ -34,7 +82,10 @@ impl From<io::Error> for SynthesisError {
 #[cfg(feature = "std")]
 impl std::error::Error for SynthesisError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            SynthesisError::Context { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
     }
 }
 
@@ -52,9 +103,28 @@ impl fmt::Display for SynthesisError {
             }
             SynthesisError::IoError(err) => write!(f, "I/O error: {:?}", err),
             SynthesisError::MalformedVerifyingKey => write!(f, "malformed verifying key"),
+            SynthesisError::MalformedProof => write!(f, "malformed proof"),
+            SynthesisError::MalformedParameters => write!(f, "malformed parameters"),
+            SynthesisError::InvalidAggregationSize => {
+                write!(f, "invalid number of proofs to aggregate")
+            }
+            SynthesisError::InvalidRangeSize => {
+                write!(f, "invalid number of bits or of values of a range proof")
+            }
+            SynthesisError::InvalidBatchProof(i) => {
+                write!(f, "proof {} of the batch does not verify", i)
+            }
             SynthesisError::UnconstrainedVariable => {
                 write!(f, "auxiliary variable was unconstrained")
             }
+            SynthesisError::WitnessMismatch => write!(f, "the witness is of another circuit"),
+            SynthesisError::CircuitMismatch => {
+                write!(f, "key/circuit mismatch: the key is of another circuit")
+            }
+            SynthesisError::UnsupportedWitnessVersion => {
+                write!(f, "unsupported version of the witness format")
+            }
+            SynthesisError::Context { path, error } => write!(f, "{}: {}", path, error),
         }
     }
 }
@@ -0,0 +1,429 @@
+use blake2::{Blake2s, Digest};
+use math::{Field, ToBytes};
+
+use crate::{String, Vec};
+
+use super::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, ShapeCS, SynthesisError,
+    Variable,
+};
+
+/// The fingerprint of the constraint matrices of a circuit, to tell the keys
+/// of one circuit from those of another.
+pub type CircuitFingerprint = [u8; 32];
+
+/// BLAKE2s over the constraints as they are enforced, of the terms of every
+/// linear combination merged and sorted, and then over the sizes.
+pub(crate) struct Hasher {
+    hasher: Blake2s,
+    num_constraints: usize,
+}
+
+impl Hasher {
+    pub(crate) fn new() -> Self {
+        Hasher {
+            hasher: Blake2s::new(),
+            num_constraints: 0,
+        }
+    }
+
+    fn update_usize(&mut self, n: usize) {
+        self.hasher.input(&(n as u64).to_le_bytes());
+    }
+
+    /// Hashes the constraint `a * b = c`.
+    pub(crate) fn constraint<F: Field>(
+        &mut self,
+        a: &LinearCombination<F>,
+        b: &LinearCombination<F>,
+        c: &LinearCombination<F>,
+    ) {
+        let terms = |lc: &LinearCombination<F>| {
+            lc.as_ref()
+                .iter()
+                .map(|(var, coeff)| (var.get_unchecked(), *coeff))
+                .collect()
+        };
+        self.constraint_of_terms(terms(a), terms(b), terms(c));
+    }
+
+    /// Hashes the constraint of the terms `a * b = c`, in any order, of
+    /// repeated indices or not.
+    pub(crate) fn constraint_of_terms<F: Field>(
+        &mut self,
+        a: Vec<(Index, F)>,
+        b: Vec<(Index, F)>,
+        c: Vec<(Index, F)>,
+    ) {
+        self.num_constraints += 1;
+        self.terms(a);
+        self.terms(b);
+        self.terms(c);
+    }
+
+    fn terms<F: Field>(&mut self, mut terms: Vec<(Index, F)>) {
+        terms.sort_by_key(|(index, _)| *index);
+        terms.dedup_by(|(index, coeff), (kept_index, kept_coeff)| {
+            let same = index == kept_index;
+            if same {
+                *kept_coeff += &*coeff;
+            }
+            same
+        });
+        terms.retain(|(_, coeff)| !coeff.is_zero());
+
+        self.update_usize(terms.len());
+        for (index, coeff) in terms {
+            match index {
+                Index::Input(i) => {
+                    self.hasher.input(&[0]);
+                    self.update_usize(i);
+                }
+                Index::Aux(i) => {
+                    self.hasher.input(&[1]);
+                    self.update_usize(i);
+                }
+            }
+            self.hasher.input(&math::to_bytes!(coeff).unwrap());
+        }
+    }
+
+    /// The fingerprint of the constraints hashed, of `num_inputs` public
+    /// inputs, the "one" input included, and `num_aux` auxiliary variables.
+    pub(crate) fn finish(mut self, num_inputs: usize, num_aux: usize) -> CircuitFingerprint {
+        let num_constraints = self.num_constraints;
+        self.update_usize(num_constraints);
+        self.update_usize(num_inputs);
+        self.update_usize(num_aux);
+
+        let mut fingerprint = [0u8; 32];
+        fingerprint.copy_from_slice(self.hasher.result().as_ref());
+        fingerprint
+    }
+}
+
+/// A constraint system that fingerprints the constraints of a circuit on
+/// the way to another.
+struct FingerprintConstraintSystem<'a, CS> {
+    cs: &'a mut CS,
+    hasher: Hasher,
+    num_inputs: usize,
+    num_aux: usize,
+}
+
+impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F>
+    for FingerprintConstraintSystem<'_, CS>
+{
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_aux += 1;
+        self.cs.alloc(annotation, f)
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_inputs += 1;
+        self.cs.alloc_input(annotation, f)
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+        self.hasher.constraint(&a, &b, &c);
+        self.cs.enforce(annotation, |_| a, |_| b, |_| c);
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.cs.get_root().push_namespace(name_fn);
+    }
+
+    fn pop_namespace(&mut self) {
+        self.cs.get_root().pop_namespace();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.cs.num_constraints()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.cs.num_public_inputs()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.cs.num_private_variables()
+    }
+}
+
+enum Mode<'a> {
+    Record(&'a mut CircuitFingerprint),
+    #[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+    Check(&'a CircuitFingerprint),
+}
+
+/// A circuit that fingerprints its constraints as it synthesizes, for the
+/// schemes to bind their keys to it.
+pub(crate) struct FingerprintCircuit<'a, C> {
+    circuit: C,
+    mode: Mode<'a>,
+}
+
+impl<'a, C> FingerprintCircuit<'a, C> {
+    /// `circuit`, of its fingerprint recorded into `fingerprint`, at setup.
+    pub(crate) fn record(circuit: C, fingerprint: &'a mut CircuitFingerprint) -> Self {
+        FingerprintCircuit {
+            circuit,
+            mode: Mode::Record(fingerprint),
+        }
+    }
+
+    /// `circuit`, failing to synthesize with
+    /// `SynthesisError::CircuitMismatch` unless its fingerprint is
+    /// `expected`, the one of the key it is proven with.
+    #[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+    pub(crate) fn check(circuit: C, expected: &'a CircuitFingerprint) -> Self {
+        FingerprintCircuit {
+            circuit,
+            mode: Mode::Check(expected),
+        }
+    }
+}
+
+impl<F: Field, C: ConstraintSynthesizer<F>> ConstraintSynthesizer<F> for FingerprintCircuit<'_, C> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut cs = FingerprintConstraintSystem {
+            cs,
+            hasher: Hasher::new(),
+            num_inputs: 0,
+            num_aux: 0,
+        };
+        self.circuit.generate_constraints(&mut cs)?;
+
+        // the "one" input, allocated by every scheme.
+        let fingerprint = cs.hasher.finish(cs.num_inputs + 1, cs.num_aux);
+        match self.mode {
+            Mode::Record(recorded) => *recorded = fingerprint,
+            #[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+            Mode::Check(expected) if *expected != fingerprint => {
+                return Err(SynthesisError::CircuitMismatch)
+            }
+            #[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+            Mode::Check(_) => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// The fingerprint of the constraint matrices of `circuit`, of their sizes
+/// and their terms, not of the names or the values. The schemes keep it in
+/// their keys, and fail with `SynthesisError::CircuitMismatch` to prove a
+/// circuit of another fingerprint.
+pub fn fingerprint<F, C>(circuit: C) -> Result<CircuitFingerprint, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    let mut fingerprint = [0u8; 32];
+    FingerprintCircuit::record(circuit, &mut fingerprint)
+        .generate_constraints(&mut ShapeCS::<F>::new())?;
+    Ok(fingerprint)
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::One;
+
+    use super::*;
+
+    /// `x * x = y`, of the public `y`, of its terms in either order.
+    struct Square {
+        swapped: bool,
+        cubed: bool,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Square {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let x = cs.alloc(|| "x", || Ok(Fr::one()))?;
+            let y = cs.alloc_input(|| "y", || Ok(Fr::one()))?;
+            if self.swapped {
+                cs.enforce(
+                    || "x * x = y",
+                    |lc| lc + CS::one() + x - CS::one(),
+                    |lc| lc + x,
+                    |lc| lc + y,
+                );
+            } else {
+                cs.enforce(|| "x * x = y", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+            }
+            if self.cubed {
+                cs.enforce(|| "x * y = y", |lc| lc + x, |lc| lc + y, |lc| lc + y);
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let square = |swapped, cubed| fingerprint(Square { swapped, cubed }).unwrap();
+
+        // the terms are canonical, the cancelled ones dropped.
+        assert_eq!(square(false, false), square(true, false));
+        assert_ne!(square(false, false), square(false, true));
+
+        let mut recorded = [0u8; 32];
+        FingerprintCircuit::record(
+            Square {
+                swapped: true,
+                cubed: false,
+            },
+            &mut recorded,
+        )
+        .generate_constraints(&mut ShapeCS::<Fr>::new())
+        .unwrap();
+        assert_eq!(recorded, square(false, false));
+
+        // checked of the schemes which bind their keys to the circuit only.
+        #[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+        {
+            let checked = |cubed| {
+                FingerprintCircuit::check(
+                    Square {
+                        swapped: false,
+                        cubed,
+                    },
+                    &recorded,
+                )
+                .generate_constraints(&mut ShapeCS::<Fr>::new())
+            };
+            assert!(checked(false).is_ok());
+            assert!(matches!(
+                checked(true),
+                Err(SynthesisError::CircuitMismatch)
+            ));
+        }
+    }
+
+    /// `x^(2^n)`, of the public result, for the schemes, which refuse
+    /// circuits of a constraint or two.
+    struct Squares {
+        n: usize,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Squares {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let mut value = Fr::from(3u32);
+            let mut x = cs.alloc(|| "x", || Ok(value))?;
+            for _ in 0..self.n {
+                value = value * &value;
+                let square = cs.alloc(|| "square", || Ok(value))?;
+                cs.enforce(|| "square", |lc| lc + x, |lc| lc + x, |lc| lc + square);
+                x = square;
+            }
+            let out = cs.alloc_input(|| "out", || Ok(value))?;
+            cs.enforce(|| "out", |lc| lc + x, |lc| lc + CS::one(), |lc| lc + out);
+
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "groth16")]
+    #[test]
+    fn test_groth16_mismatch() {
+        use crate::groth16::{create_random_proof, generate_random_parameters};
+        use curve::bn_256::Bn_256;
+
+        let rng = &mut rand::thread_rng();
+        let params = generate_random_parameters::<Bn_256, _, _>(Squares { n: 5 }, rng).unwrap();
+        assert_eq!(
+            params.vk.fingerprint,
+            fingerprint(Squares { n: 5 }).unwrap()
+        );
+        assert!(create_random_proof(&params, Squares { n: 5 }, rng).is_ok());
+        assert!(matches!(
+            create_random_proof(&params, Squares { n: 6 }, rng),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+    }
+
+    #[cfg(feature = "marlin")]
+    #[test]
+    fn test_marlin_mismatch() {
+        use crate::marlin::{create_random_proof, index, max_degree, universal_setup};
+        use curve::bn_256::Bn_256;
+
+        let rng = &mut rand::thread_rng();
+        let degree = max_degree::<Bn_256, _>(Squares { n: 6 }).unwrap();
+        let srs = universal_setup::<Bn_256, _>(degree, rng).unwrap();
+        let (ipk, ivk) = index(&srs, Squares { n: 5 }).unwrap();
+        assert_eq!(ivk.fingerprint, fingerprint(Squares { n: 5 }).unwrap());
+        assert!(create_random_proof(&ipk, Squares { n: 5 }, rng).is_ok());
+        assert!(matches!(
+            create_random_proof(&ipk, Squares { n: 6 }, rng),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+    }
+
+    #[cfg(feature = "spartan")]
+    #[test]
+    fn test_spartan_mismatch() {
+        use crate::spartan::{nizk, snark};
+        use curve::bn_256::Bn_256;
+
+        let rng = &mut rand::thread_rng();
+        let (pk, vk) = snark::generate_random_parameters::<Bn_256, _, _>(Squares { n: 5 }, rng)
+            .unwrap()
+            .keypair();
+        assert_eq!(vk.fingerprint(), &fingerprint(Squares { n: 5 }).unwrap());
+        assert!(snark::create_random_proof(&pk, Squares { n: 5 }, rng).is_ok());
+        assert!(matches!(
+            snark::create_random_proof(&pk, Squares { n: 6 }, rng),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+
+        let (pk, _) = nizk::generate_random_parameters::<Bn_256, _, _>(Squares { n: 5 }, rng)
+            .unwrap()
+            .keypair();
+        assert!(nizk::create_random_proof(&pk, Squares { n: 5 }, rng).is_ok());
+        assert!(matches!(
+            nizk::create_random_proof(&pk, Squares { n: 6 }, rng),
+            Err(SynthesisError::CircuitMismatch)
+        ));
+    }
+}
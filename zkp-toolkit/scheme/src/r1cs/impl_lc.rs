@@ -1,6 +1,6 @@
 // The following code is from (scipr-lab's zexe)[https://github.com/scipr-lab/zexe] and thanks for their work
 
-use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 use math::Field;
 use smallvec::smallvec;
 
@@ -27,6 +27,15 @@ impl<F: Field> From<Variable> for LinearCombination<F> {
     }
 }
 
+impl<F: Field> Mul<F> for Variable {
+    type Output = LinearCombination<F>;
+
+    #[inline]
+    fn mul(self, coeff: F) -> LinearCombination<F> {
+        (coeff, self).into()
+    }
+}
+
 impl<F: Field> LinearCombination<F> {
     /// Outputs an empty linear combination.
     #[inline]
@@ -59,16 +68,14 @@ impl<F: Field> LinearCombination<F> {
     #[inline]
     pub fn get_var_loc(&self, search_var: &Variable) -> Result<usize, usize> {
         if self.0.len() < 6 {
-            let mut found_index = 0;
             for (i, (var, _)) in self.0.iter().enumerate() {
-                if var >= search_var {
-                    found_index = i;
-                    break;
-                } else {
-                    found_index += 1;
+                if var == search_var {
+                    return Ok(i);
+                } else if var > search_var {
+                    return Err(i);
                 }
             }
-            Err(found_index)
+            Err(self.0.len())
         } else {
             self.0
                 .binary_search_by_key(search_var, |&(cur_var, _)| cur_var)
@@ -105,6 +112,13 @@ impl<F: Field> Sub<(F, Variable)> for LinearCombination<F> {
     }
 }
 
+impl<F: Field> SubAssign<(F, Variable)> for LinearCombination<F> {
+    #[inline]
+    fn sub_assign(&mut self, (coeff, var): (F, Variable)) {
+        *self += (-coeff, var);
+    }
+}
+
 impl<F: Field> Neg for LinearCombination<F> {
     type Output = Self;
 
@@ -150,6 +164,20 @@ impl<F: Field> Sub<Variable> for LinearCombination<F> {
     }
 }
 
+impl<F: Field> AddAssign<Variable> for LinearCombination<F> {
+    #[inline]
+    fn add_assign(&mut self, other: Variable) {
+        *self += (F::one(), other);
+    }
+}
+
+impl<F: Field> SubAssign<Variable> for LinearCombination<F> {
+    #[inline]
+    fn sub_assign(&mut self, other: Variable) {
+        *self -= (F::one(), other);
+    }
+}
+
 fn op_impl<F: Field, F1, F2>(
     cur: &LinearCombination<F>,
     other: &LinearCombination<F>,
@@ -449,3 +477,118 @@ impl<'a, F: Field> Sub<(F, LinearCombination<F>)> for LinearCombination<F> {
         self + (-coeff, other)
     }
 }
+
+impl<F: Field> AddAssign<&LinearCombination<F>> for LinearCombination<F> {
+    #[inline]
+    fn add_assign(&mut self, other: &LinearCombination<F>) {
+        let cur = core::mem::replace(self, LinearCombination::zero());
+        *self = cur + other;
+    }
+}
+
+impl<F: Field> AddAssign<LinearCombination<F>> for LinearCombination<F> {
+    #[inline]
+    fn add_assign(&mut self, other: LinearCombination<F>) {
+        let cur = core::mem::replace(self, LinearCombination::zero());
+        *self = cur + other;
+    }
+}
+
+impl<F: Field> SubAssign<&LinearCombination<F>> for LinearCombination<F> {
+    #[inline]
+    fn sub_assign(&mut self, other: &LinearCombination<F>) {
+        let cur = core::mem::replace(self, LinearCombination::zero());
+        *self = cur - other;
+    }
+}
+
+impl<F: Field> SubAssign<LinearCombination<F>> for LinearCombination<F> {
+    #[inline]
+    fn sub_assign(&mut self, other: LinearCombination<F>) {
+        let cur = core::mem::replace(self, LinearCombination::zero());
+        *self = cur - other;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{One, Zero};
+
+    use super::*;
+    use crate::r1cs::Index;
+    use crate::Vec;
+
+    fn aux(i: usize) -> Variable {
+        Variable::new_unchecked(Index::Aux(i))
+    }
+
+    fn terms(lc: &LinearCombination<Fr>) -> Vec<(Variable, Fr)> {
+        lc.as_ref().to_vec()
+    }
+
+    #[test]
+    fn test_repeated_variables() {
+        let (a, b) = (aux(0), aux(1));
+        let one = Variable::new_unchecked(Index::Input(0));
+
+        let lc = LinearCombination::<Fr>::zero() + b + a + b + (Fr::from(3u32), one);
+        assert_eq!(
+            terms(&lc),
+            vec![(one, Fr::from(3u32)), (a, Fr::one()), (b, Fr::from(2u32))]
+        );
+
+        // past the linear search, of at most 5 variables.
+        let mut lc = LinearCombination::<Fr>::zero();
+        for i in 0..8 {
+            lc += aux(i);
+        }
+        for i in (0..8).rev() {
+            lc += aux(i);
+        }
+        assert_eq!(
+            terms(&lc),
+            (0..8).map(|i| (aux(i), Fr::from(2u32))).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_coefficients() {
+        let (a, b, c) = (aux(0), aux(1), aux(2));
+        let two = Fr::from(2u32);
+        let three = Fr::from(3u32);
+
+        let lc = LinearCombination::<Fr>::zero() + a - (b * three);
+        assert_eq!(terms(&lc), vec![(a, Fr::one()), (b, -three)]);
+        assert_eq!(
+            terms(&(lc.clone() - b)),
+            vec![(a, Fr::one()), (b, -three - Fr::one())]
+        );
+        assert_eq!(terms(&-lc.clone()), vec![(a, -Fr::one()), (b, three)]);
+        assert_eq!(
+            terms(&(lc.clone() * two)),
+            vec![(a, two), (b, -(two * three))]
+        );
+
+        let other = LinearCombination::<Fr>::zero() + (two, b) + c;
+        let mut sum = lc.clone();
+        sum += &other;
+        assert_eq!(terms(&sum), terms(&(lc.clone() + &other)));
+        assert_eq!(
+            terms(&sum),
+            vec![(a, Fr::one()), (b, -Fr::one()), (c, Fr::one())]
+        );
+
+        sum -= other.clone();
+        assert_eq!(
+            terms(&sum),
+            vec![(a, Fr::one()), (b, -three), (c, Fr::zero())]
+        );
+
+        let mut lc = LinearCombination::<Fr>::zero();
+        lc += (three, a);
+        lc -= a;
+        lc -= (two, a);
+        assert_eq!(terms(&lc), vec![(a, Fr::zero())]);
+    }
+}
@@ -5,12 +5,27 @@ use math::Field;
 use smallvec::SmallVec as StackVec;
 
 mod constraint_system;
+mod debug;
 mod error;
+mod fingerprint;
 mod impl_constraint_var;
 mod impl_lc;
+mod public_inputs;
+mod shape;
+pub mod stats;
+mod witness;
 
 pub use constraint_system::{ConstraintSynthesizer, ConstraintSystem, Namespace};
+pub use debug::{debug_satisfied, UnsatisfiedConstraint};
 pub use error::SynthesisError;
+#[cfg(any(feature = "groth16", feature = "marlin", feature = "spartan"))]
+pub(crate) use fingerprint::FingerprintCircuit;
+#[cfg(feature = "bulletproofs")]
+pub(crate) use fingerprint::Hasher as FingerprintHasher;
+pub use fingerprint::{fingerprint, CircuitFingerprint};
+pub use public_inputs::{assert_public_inputs_match, PublicInputs};
+pub use shape::{num_constraints_of, shape_of, ShapeCS};
+pub use witness::{Witness, WitnessCircuit, WITNESS_VERSION};
 
 type SmallVec<F> = StackVec<[(Variable, F); 16]>;
 
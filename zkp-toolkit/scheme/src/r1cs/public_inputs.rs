@@ -0,0 +1,121 @@
+use math::Field;
+
+use crate::Vec;
+
+use super::debug::AssignmentConstraintSystem;
+use super::ConstraintSynthesizer;
+
+/// The public inputs a circuit allocates, computed off the circuit, in the
+/// order it allocates them and the "one" input excluded, as the verifiers
+/// take them.
+pub trait PublicInputs<F: Field> {
+    fn public_inputs(&self) -> Vec<F>;
+}
+
+/// Synthesizes `circuit` with its witness and checks the public inputs it
+/// allocates are `claimed`, in order, e.g. against `PublicInputs` of it or
+/// the inputs a verifier is given.
+///
+/// # Panics
+///
+/// If the inputs differ, with the path of the first that does, or if the
+/// circuit fails to synthesize.
+pub fn assert_public_inputs_match<F, C>(circuit: C, claimed: &[F])
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    let cs = AssignmentConstraintSystem::synthesize(circuit, false)
+        .unwrap_or_else(|e| panic!("the circuit failed to synthesize: {}", e));
+
+    let inputs = &cs.inputs[1..];
+    for (i, ((path, value), claimed)) in inputs.iter().zip(claimed).enumerate() {
+        assert!(
+            value == claimed,
+            "public input {} \"{}\" is {}, but {} is claimed",
+            i,
+            path,
+            value,
+            claimed
+        );
+    }
+    assert!(
+        inputs.len() == claimed.len(),
+        "the circuit allocates {} public inputs, but {} are claimed",
+        inputs.len(),
+        claimed.len()
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+
+    use super::*;
+    use crate::r1cs::{ConstraintSystem, SynthesisError};
+
+    /// `a * b = c`, of the public inputs `a` and `c`.
+    struct Product {
+        a: Fr,
+        b: Fr,
+        swapped: bool,
+    }
+
+    impl PublicInputs<Fr> for Product {
+        fn public_inputs(&self) -> Vec<Fr> {
+            vec![self.a, self.a * &self.b]
+        }
+    }
+
+    impl ConstraintSynthesizer<Fr> for Product {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let c = self.a * &self.b;
+            let mut cs = cs.ns(|| "product");
+            let (a, c) = if self.swapped {
+                let c = cs.alloc_input(|| "c", || Ok(c))?;
+                (cs.alloc_input(|| "a", || Ok(self.a))?, c)
+            } else {
+                let a = cs.alloc_input(|| "a", || Ok(self.a))?;
+                (a, cs.alloc_input(|| "c", || Ok(c))?)
+            };
+            let b = cs.alloc(|| "b", || Ok(self.b))?;
+            cs.enforce(|| "a * b = c", |lc| lc + a, |lc| lc + b, |lc| lc + c);
+
+            Ok(())
+        }
+    }
+
+    fn product(swapped: bool) -> Product {
+        Product {
+            a: Fr::from(3u32),
+            b: Fr::from(5u32),
+            swapped,
+        }
+    }
+
+    #[test]
+    fn test_public_inputs_match() {
+        let c = product(false);
+        let inputs = c.public_inputs();
+        assert_public_inputs_match(c, &inputs);
+    }
+
+    #[test]
+    #[should_panic(expected = "public input 0 \"product/c\" is")]
+    fn test_public_inputs_order() {
+        let c = product(true);
+        let inputs = c.public_inputs();
+        assert_public_inputs_match(c, &inputs);
+    }
+
+    #[test]
+    #[should_panic(expected = "the circuit allocates 2 public inputs, but 1 are claimed")]
+    fn test_public_inputs_missing() {
+        let c = product(false);
+        let inputs = c.public_inputs();
+        assert_public_inputs_match(c, &inputs[..1]);
+    }
+}
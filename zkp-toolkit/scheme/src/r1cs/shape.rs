@@ -0,0 +1,206 @@
+use core::marker::PhantomData;
+use math::Field;
+
+use crate::String;
+
+use super::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+
+/// A constraint system that only counts the variables and the constraints,
+/// evaluating neither the values nor the linear combinations, e.g. to size
+/// the parameters of a circuit before synthesizing it for real.
+pub struct ShapeCS<F: Field> {
+    /// The public inputs, the "one" input included.
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    pub num_constraints: usize,
+    _f: PhantomData<F>,
+}
+
+impl<F: Field> ShapeCS<F> {
+    /// The system of the "one" input only.
+    pub fn new() -> Self {
+        ShapeCS {
+            num_inputs: 1,
+            num_aux: 0,
+            num_constraints: 0,
+            _f: PhantomData,
+        }
+    }
+}
+
+impl<F: Field> Default for ShapeCS<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> ConstraintSystem<F> for ShapeCS<F> {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_aux += 1;
+
+        Ok(Variable::new_unchecked(Index::Aux(self.num_aux - 1)))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_inputs += 1;
+
+        Ok(Variable::new_unchecked(Index::Input(self.num_inputs - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, _: LA, _: LB, _: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_aux
+    }
+}
+
+/// Synthesizes `circuit` into a `ShapeCS`, of no values, to count its
+/// variables and constraints.
+pub fn shape_of<F, C>(circuit: C) -> Result<ShapeCS<F>, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    let mut cs = ShapeCS::new();
+    circuit.generate_constraints(&mut cs)?;
+    Ok(cs)
+}
+
+/// The number of constraints of `circuit`, of no values.
+pub fn num_constraints_of<F, C>(circuit: C) -> Result<usize, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    Ok(shape_of(circuit)?.num_constraints)
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+
+    use super::*;
+    use crate::r1cs::{stats, Witness};
+
+    /// `x^(2^n)`, of value closures that panic without a witness.
+    struct Squares {
+        x: Option<Fr>,
+        n: usize,
+    }
+
+    impl ConstraintSynthesizer<Fr> for Squares {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let mut value = self.x;
+            let mut x = cs.alloc(|| "x", || Ok(value.unwrap()))?;
+            for i in 0..self.n {
+                value = value.map(|v| v * &v);
+                let square = cs.alloc(|| format!("x^2^{}", i + 1), || Ok(value.unwrap()))?;
+                cs.enforce(|| "square", |lc| lc + x, |lc| lc + x, |lc| lc + square);
+                x = square;
+            }
+            cs.alloc_input(|| "out", || Ok(value.unwrap()))?;
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_shape_of() {
+        let shape = shape_of(Squares { x: None, n: 5 }).unwrap();
+        assert_eq!(
+            (shape.num_inputs, shape.num_aux, shape.num_constraints),
+            (2, 6, 5)
+        );
+        assert_eq!(num_constraints_of(Squares { x: None, n: 5 }).unwrap(), 5);
+
+        let stats = stats::analyze(Squares { x: None, n: 5 }).unwrap();
+        assert_eq!(
+            (stats.num_inputs, stats.num_aux, stats.num_constraints),
+            (shape.num_inputs, shape.num_aux, shape.num_constraints)
+        );
+
+        let witness = Witness::generate(Squares {
+            x: Some(Fr::from(3u32)),
+            n: 5,
+        })
+        .unwrap();
+        assert_eq!(witness.public_inputs.len() + 1, shape.num_inputs);
+        assert_eq!(witness.aux_assignment.len(), shape.num_aux);
+    }
+
+    #[cfg(feature = "spartan")]
+    #[test]
+    fn test_spartan_setup_of_shape() {
+        use crate::spartan::snark::generate_random_parameters;
+        use curve::bn_256::Bn_256;
+
+        // the value closures are not evaluated, or they would panic.
+        let rng = &mut math::test_rng();
+        let params = generate_random_parameters::<Bn_256, _, _>(Squares { x: None, n: 5 }, rng);
+        assert!(params.is_ok());
+    }
+
+    #[cfg(feature = "marlin")]
+    #[test]
+    fn test_marlin_max_degree() {
+        use crate::marlin::{index, max_degree, universal_setup};
+        use curve::bn_256::Bn_256;
+
+        // the index of one constraint is degenerate, of constant polynomials
+        // the commitments refuse.
+        let rng = &mut math::test_rng();
+        for n in 2..20 {
+            let degree = max_degree::<Bn_256, _>(Squares { x: None, n }).unwrap();
+            let srs = universal_setup::<Bn_256, _>(degree, rng).unwrap();
+            let (ipk, _) = index(&srs, Squares { x: None, n }).unwrap();
+            assert_eq!(ipk.index.max_degree(), degree);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! The shape of the constraint matrices of a circuit, e.g. to choose the
+//! scheme to prove it with: groth16 costs by the constraints, marlin by the
+//! entries of the densest matrix, and spartan by both, padded.
+
+use core::cmp;
+use math::Field;
+
+use crate::{String, Vec};
+
+use super::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+
+/// The statistics of the constraint matrices `A`, `B` and `C` of a circuit.
+/// Entries are the terms of the linear combinations, zero coefficients of
+/// cancelled terms included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csStats {
+    pub num_constraints: usize,
+    /// The public inputs, the "one" input included.
+    pub num_inputs: usize,
+    pub num_aux: usize,
+    pub a_non_zeros: usize,
+    pub b_non_zeros: usize,
+    pub c_non_zeros: usize,
+    /// The most entries in a row of any matrix.
+    pub max_row_density: usize,
+    pub groth16: Groth16Sizes,
+    pub marlin: MarlinSizes,
+    pub spartan: SpartanSizes,
+}
+
+/// The padded sizes of groth16.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Groth16Sizes {
+    /// The evaluation domain of the QAP, of the constraints and the inputs.
+    pub domain: usize,
+}
+
+/// The padded sizes of marlin, of its square matrices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarlinSizes {
+    /// The domain of the inputs.
+    pub domain_x: usize,
+    /// The domain of the rows and columns.
+    pub domain_h: usize,
+    /// The domain of the entries of the densest matrix, after `A` and `B`
+    /// are balanced.
+    pub domain_k: usize,
+}
+
+/// The padded sizes of spartan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpartanSizes {
+    pub num_constraints: usize,
+    /// The length of `z`, of the aux variables and the inputs, each padded
+    /// to the larger.
+    pub num_variables: usize,
+    /// The entries of the densest matrix, of the snark.
+    pub num_non_zeros: usize,
+}
+
+/// A constraint system that counts the entries of every row, of no values.
+struct StatsConstraintSystem {
+    num_inputs: usize,
+    num_aux: usize,
+    a_rows: Vec<usize>,
+    b_rows: Vec<usize>,
+    c_rows: Vec<usize>,
+}
+
+impl<F: Field> ConstraintSystem<F> for StatsConstraintSystem {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_aux += 1;
+
+        Ok(Variable::new_unchecked(Index::Aux(self.num_aux - 1)))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, _: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.num_inputs += 1;
+
+        Ok(Variable::new_unchecked(Index::Input(self.num_inputs - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.a_rows
+            .push(a(LinearCombination::zero()).as_ref().len());
+        self.b_rows
+            .push(b(LinearCombination::zero()).as_ref().len());
+        self.c_rows
+            .push(c(LinearCombination::zero()).as_ref().len());
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.a_rows.len()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_aux
+    }
+}
+
+/// The entries of `A` and `B` once marlin balances them, swapping the rows
+/// while `A` is the denser.
+fn marlin_balance(a_rows: &[usize], b_rows: &[usize]) -> (usize, usize) {
+    let mut a_density: usize = a_rows.iter().sum();
+    let mut b_density: usize = b_rows.iter().sum();
+    for (a_row, b_row) in a_rows.iter().zip(b_rows) {
+        if a_density > b_density {
+            a_density = a_density - a_row + b_row;
+            b_density = b_density - b_row + a_row;
+        }
+    }
+    (a_density, b_density)
+}
+
+/// Synthesizes the constraints of `circuit`, of no values, to report the
+/// shape of its matrices and the padded sizes each scheme would use.
+pub fn analyze<F, C>(circuit: C) -> Result<R1csStats, SynthesisError>
+where
+    F: Field,
+    C: ConstraintSynthesizer<F>,
+{
+    let mut cs = StatsConstraintSystem {
+        num_inputs: 0,
+        num_aux: 0,
+        a_rows: Vec::new(),
+        b_rows: Vec::new(),
+        c_rows: Vec::new(),
+    };
+    ConstraintSystem::<F>::alloc_input(&mut cs, || "one", || Ok(F::one()))?;
+    circuit.generate_constraints(&mut cs)?;
+
+    let num_constraints = cs.a_rows.len();
+    let num_inputs = cs.num_inputs;
+    let num_aux = cs.num_aux;
+    let a_non_zeros = cs.a_rows.iter().sum();
+    let b_non_zeros = cs.b_rows.iter().sum();
+    let c_non_zeros = cs.c_rows.iter().sum();
+    let max_row_density = cs
+        .a_rows
+        .iter()
+        .chain(&cs.b_rows)
+        .chain(&cs.c_rows)
+        .copied()
+        .max()
+        .unwrap_or(0);
+
+    let groth16 = Groth16Sizes {
+        domain: (num_constraints + num_inputs).next_power_of_two(),
+    };
+
+    let (a_balanced, b_balanced) = marlin_balance(&cs.a_rows, &cs.b_rows);
+    let marlin = MarlinSizes {
+        domain_x: num_inputs.next_power_of_two(),
+        domain_h: cmp::max(num_constraints, num_inputs + num_aux).next_power_of_two(),
+        domain_k: cmp::max(cmp::max(a_balanced, b_balanced), c_non_zeros).next_power_of_two(),
+    };
+
+    let num_variables = cmp::max(num_aux, num_inputs).next_power_of_two();
+    let spartan = SpartanSizes {
+        num_constraints: num_constraints.next_power_of_two(),
+        num_variables: 2 * num_variables,
+        num_non_zeros: cmp::max(cmp::max(a_non_zeros, b_non_zeros), c_non_zeros)
+            .next_power_of_two(),
+    };
+
+    Ok(R1csStats {
+        num_constraints,
+        num_inputs,
+        num_aux,
+        a_non_zeros,
+        b_non_zeros,
+        c_non_zeros,
+        max_row_density,
+        groth16,
+        marlin,
+        spartan,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_marlin_balance() {
+        // A is the denser until its second row moves to B.
+        assert_eq!(marlin_balance(&[1, 3, 1], &[1, 0, 1]), (2, 5));
+        assert_eq!(marlin_balance(&[1, 1], &[2, 2]), (2, 4));
+    }
+}
@@ -0,0 +1,333 @@
+use math::Field;
+
+use crate::{String, Vec};
+
+use super::fingerprint::Hasher;
+use super::{
+    CircuitFingerprint, ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination,
+    SynthesisError, Variable,
+};
+
+/// The version of the format of `Witness`, bumped on every change to it.
+pub const WITNESS_VERSION: u16 = 2;
+
+/// The assignment of a circuit, kept apart from proving it, e.g. to generate
+/// it on one machine and prove on another. It serializes, the version first,
+/// with serde.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "F: serde::Serialize",
+    deserialize = "F: for<'e> serde::Deserialize<'e>"
+))]
+pub struct Witness<F: Field> {
+    /// The version of the format, `WITNESS_VERSION` when generated.
+    pub version: u16,
+    /// The fingerprint of the constraints of the circuit, `r1cs::fingerprint`,
+    /// the one of the keys of the circuit.
+    pub fingerprint: CircuitFingerprint,
+    /// The public inputs, the "one" input excluded.
+    pub public_inputs: Vec<F>,
+    pub aux_assignment: Vec<F>,
+}
+
+impl<F: Field> Witness<F> {
+    /// Synthesizes `circuit` with its values, to record them.
+    pub fn generate<C: ConstraintSynthesizer<F>>(circuit: C) -> Result<Self, SynthesisError> {
+        let mut cs = RecordingConstraintSystem {
+            num_constraints: 0,
+            input_assignment: Vec::new(),
+            aux_assignment: Vec::new(),
+            fingerprint: Hasher::new(),
+        };
+        cs.alloc_input(|| "one", || Ok(F::one()))?;
+        circuit.generate_constraints(&mut cs)?;
+
+        let fingerprint = cs
+            .fingerprint
+            .finish(cs.input_assignment.len(), cs.aux_assignment.len());
+        cs.input_assignment.remove(0);
+
+        Ok(Witness {
+            version: WITNESS_VERSION,
+            fingerprint,
+            public_inputs: cs.input_assignment,
+            aux_assignment: cs.aux_assignment,
+        })
+    }
+}
+
+/// A constraint system that records the values of the variables, and the
+/// fingerprint of the constraints.
+struct RecordingConstraintSystem<F: Field> {
+    num_constraints: usize,
+    input_assignment: Vec<F>,
+    aux_assignment: Vec<F>,
+    fingerprint: Hasher,
+}
+
+impl<F: Field> ConstraintSystem<F> for RecordingConstraintSystem<F> {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.aux_assignment.push(f()?);
+
+        Ok(Variable::new_unchecked(Index::Aux(
+            self.aux_assignment.len() - 1,
+        )))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, _: A, f: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.input_assignment.push(f()?);
+
+        Ok(Variable::new_unchecked(Index::Input(
+            self.input_assignment.len() - 1,
+        )))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, _: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        self.num_constraints += 1;
+        self.fingerprint.constraint(
+            &a(LinearCombination::zero()),
+            &b(LinearCombination::zero()),
+            &c(LinearCombination::zero()),
+        );
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self) {}
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.input_assignment.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
+}
+
+/// A circuit of its values taken from a `Witness` of it, so that proving it
+/// with any scheme synthesizes the shape of its constraints only. It fails to
+/// synthesize with `SynthesisError::WitnessMismatch` if the witness is of
+/// another circuit.
+pub struct WitnessCircuit<'a, F: Field, C: ConstraintSynthesizer<F>> {
+    circuit: C,
+    witness: &'a Witness<F>,
+}
+
+impl<'a, F: Field, C: ConstraintSynthesizer<F>> WitnessCircuit<'a, F, C> {
+    /// Fails with `SynthesisError::UnsupportedWitnessVersion` if `witness`
+    /// is of another version of the format.
+    pub fn new(circuit: C, witness: &'a Witness<F>) -> Result<Self, SynthesisError> {
+        if witness.version != WITNESS_VERSION {
+            return Err(SynthesisError::UnsupportedWitnessVersion);
+        }
+
+        Ok(WitnessCircuit { circuit, witness })
+    }
+}
+
+impl<F: Field, C: ConstraintSynthesizer<F>> ConstraintSynthesizer<F> for WitnessCircuit<'_, F, C> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut cs = WitnessConstraintSystem {
+            cs,
+            witness: self.witness,
+            num_inputs: 0,
+            num_aux: 0,
+            fingerprint: Hasher::new(),
+        };
+        self.circuit.generate_constraints(&mut cs)?;
+
+        // the "one" input, allocated by the prover.
+        let fingerprint = cs.fingerprint.finish(cs.num_inputs + 1, cs.num_aux);
+        if cs.num_inputs != self.witness.public_inputs.len()
+            || cs.num_aux != self.witness.aux_assignment.len()
+            || fingerprint != self.witness.fingerprint
+        {
+            return Err(SynthesisError::WitnessMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// A constraint system that assigns the variables of a circuit from a
+/// `Witness`, and fingerprints its constraints, on the way to another.
+struct WitnessConstraintSystem<'a, F: Field, CS: ConstraintSystem<F>> {
+    cs: &'a mut CS,
+    witness: &'a Witness<F>,
+    num_inputs: usize,
+    num_aux: usize,
+    fingerprint: Hasher,
+}
+
+impl<F: Field, CS: ConstraintSystem<F>> ConstraintSystem<F> for WitnessConstraintSystem<'_, F, CS> {
+    type Root = Self;
+
+    fn alloc<FN, A, AR>(&mut self, annotation: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let value = self.witness.aux_assignment.get(self.num_aux).copied();
+        self.num_aux += 1;
+        self.cs
+            .alloc(annotation, || value.ok_or(SynthesisError::WitnessMismatch))
+    }
+
+    fn alloc_input<FN, A, AR>(&mut self, annotation: A, _: FN) -> Result<Variable, SynthesisError>
+    where
+        FN: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let value = self.witness.public_inputs.get(self.num_inputs).copied();
+        self.num_inputs += 1;
+        self.cs
+            .alloc_input(annotation, || value.ok_or(SynthesisError::WitnessMismatch))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+        self.fingerprint.constraint(&a, &b, &c);
+        self.cs.enforce(annotation, |_| a, |_| b, |_| c);
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.cs.get_root().push_namespace(name_fn);
+    }
+
+    fn pop_namespace(&mut self) {
+        self.cs.get_root().pop_namespace();
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.cs.num_constraints()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.cs.num_public_inputs()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.cs.num_private_variables()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::One;
+
+    use super::*;
+    use crate::test_circuits::{Cube, Square};
+
+    #[test]
+    fn test_witness() {
+        let three = Fr::from(3u32);
+        let witness = Witness::generate(Square { x: Some(three) }).unwrap();
+        assert_eq!(witness.version, WITNESS_VERSION);
+        assert_eq!(witness.public_inputs, vec![Fr::from(9u32)]);
+        assert_eq!(witness.aux_assignment, vec![three]);
+
+        // the fingerprint is of the shape, not of the values.
+        let other = Witness::generate(Square {
+            x: Some(Fr::from(4u32)),
+        })
+        .unwrap();
+        assert_eq!(witness.fingerprint, other.fingerprint);
+        let cube = Witness::generate(Cube { x: Some(three) }).unwrap();
+        assert_ne!(witness.fingerprint, cube.fingerprint);
+        // the one of the keys of the circuit.
+        assert_eq!(
+            witness.fingerprint,
+            crate::r1cs::fingerprint(Square::<Fr> { x: None }).unwrap()
+        );
+
+        let bytes = postcard::to_allocvec(&witness).unwrap();
+        let witness: Witness<Fr> = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(witness.fingerprint, other.fingerprint);
+
+        // the values of the witness stand in for the missing ones.
+        let mut cs = RecordingConstraintSystem {
+            num_constraints: 0,
+            input_assignment: vec![Fr::one()],
+            aux_assignment: Vec::new(),
+            fingerprint: Hasher::new(),
+        };
+        let circuit = WitnessCircuit::new(Square { x: None }, &witness).unwrap();
+        circuit.generate_constraints(&mut cs).unwrap();
+        assert_eq!(cs.input_assignment, vec![Fr::one(), Fr::from(9u32)]);
+        assert_eq!(cs.aux_assignment, vec![three]);
+
+        let mut cs = RecordingConstraintSystem {
+            num_constraints: 0,
+            input_assignment: vec![Fr::one()],
+            aux_assignment: Vec::new(),
+            fingerprint: Hasher::new(),
+        };
+        let circuit = WitnessCircuit::new(Cube { x: None }, &witness).unwrap();
+        assert!(matches!(
+            circuit.generate_constraints(&mut cs),
+            Err(SynthesisError::WitnessMismatch)
+        ));
+
+        let mut outdated = witness.clone();
+        outdated.version = WITNESS_VERSION + 1;
+        assert!(matches!(
+            WitnessCircuit::new(Square { x: None }, &outdated),
+            Err(SynthesisError::UnsupportedWitnessVersion)
+        ));
+    }
+}
@@ -29,13 +29,12 @@ pub fn packing_poly_commit<G: Curve, R: Rng>(
         }
         blinds.push(blind);
 
-        let commit =
-            poly_commit_vec::<G>(&generators, &values[i * r_size..(i + 1) * r_size], h, blind)
-                .unwrap()
-                .commit;
+        let mut commit =
+            G::vartime_multiscalar_mul(&values[i * r_size..(i + 1) * r_size], generators);
+        commit.add_assign(&(h.mul(blind)));
         commits.push(commit);
     }
-    Ok((commits, blinds))
+    Ok((G::Projective::batch_into_affine(commits), blinds))
 }
 
 pub fn poly_commit_vec<G: Curve>(
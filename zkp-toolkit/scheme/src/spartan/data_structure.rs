@@ -156,11 +156,7 @@ pub struct EqProof<G: Curve> {
     pub z: G::Fr,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
-pub struct InnerProductProof<G: Curve> {
-    pub l_vec: Vec<G::Affine>,
-    pub r_vec: Vec<G::Affine>,
-}
+pub type InnerProductProof<G> = crate::ipa::Reduction<G>;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct DotProductProof<G: Curve> {
@@ -10,10 +10,15 @@ pub mod test;
 pub mod verify;
 
 pub mod snark {
+    use curve::hash_to_curve::HashToCurve;
     use math::Curve;
-    use rand::Rng;
+    use rand::{rngs::mock::StepRng, Rng};
 
-    use crate::r1cs::{ConstraintSynthesizer, SynthesisError};
+    use crate::key_fingerprint::{key_fingerprint, KeyFingerprint, SchemeTag};
+    use crate::r1cs::{
+        CircuitFingerprint, ConstraintSynthesizer, FingerprintCircuit, SynthesisError, Witness,
+        WitnessCircuit,
+    };
 
     use super::data_structure::{EncodeCommit, EncodeMemory, SnarkParameters};
     use super::r1cs::R1CSInstance;
@@ -24,6 +29,7 @@ pub mod snark {
     pub struct Parameters<G: Curve> {
         params: SnarkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
         encode: EncodeMemory<G>,
         encode_comm: EncodeCommit<G>,
     }
@@ -32,6 +38,7 @@ pub mod snark {
     pub struct ProveKey<G: Curve> {
         params: SnarkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
         encode: EncodeMemory<G>,
     }
 
@@ -39,6 +46,7 @@ pub mod snark {
     pub struct VerifyKey<G: Curve> {
         params: SnarkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
         encode_comm: EncodeCommit<G>,
     }
 
@@ -48,22 +56,42 @@ pub mod snark {
                 ProveKey {
                     params: self.params.clone(),
                     r1cs: self.r1cs.clone(),
+                    fingerprint: self.fingerprint,
                     encode: self.encode,
                 },
                 VerifyKey {
                     params: self.params,
                     r1cs: self.r1cs,
+                    fingerprint: self.fingerprint,
                     encode_comm: self.encode_comm,
                 },
             )
         }
     }
 
+    impl<G: Curve> VerifyKey<G> {
+        /// The fingerprint of the circuit the key was generated for.
+        pub fn fingerprint(&self) -> &CircuitFingerprint {
+            &self.fingerprint
+        }
+
+        /// The fingerprint of the key, of the scheme and the curve, to tell
+        /// which key a file is of.
+        pub fn key_fingerprint(&self) -> KeyFingerprint
+        where
+            Self: serde::Serialize,
+        {
+            key_fingerprint::<G::Affine, _>(SchemeTag::SpartanSnark, self)
+        }
+    }
+
     pub fn generate_random_parameters<G: Curve, C: ConstraintSynthesizer<G::Fr>, R: Rng>(
         c: C,
         rng: &mut R,
     ) -> Result<Parameters<G>, SynthesisError> {
-        let r1cs = super::r1cs::generate_r1cs::<G, C>(c)?;
+        let mut fingerprint = [0u8; 32];
+        let r1cs =
+            super::r1cs::generate_r1cs::<G, _>(FingerprintCircuit::record(c, &mut fingerprint))?;
 
         let params = super::setup::generate_setup_snark_parameters::<G, R>(
             rng,
@@ -77,6 +105,42 @@ pub mod snark {
         Ok(Parameters {
             params,
             r1cs,
+            fingerprint,
+            encode,
+            encode_comm,
+        })
+    }
+
+    /// Like `generate_random_parameters`, but derives the commitment
+    /// generators from the public `label`, so that the parameters can be
+    /// recomputed by anyone instead of being trusted or shipped.
+    pub fn generate_deterministic_parameters<G: Curve, C: ConstraintSynthesizer<G::Fr>>(
+        c: C,
+        label: &[u8],
+    ) -> Result<Parameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let mut fingerprint = [0u8; 32];
+        let r1cs =
+            super::r1cs::generate_r1cs::<G, _>(FingerprintCircuit::record(c, &mut fingerprint))?;
+
+        let params = super::setup::generate_deterministic_setup_snark_parameters::<G>(
+            label,
+            r1cs.num_aux,
+            r1cs.num_inputs,
+            r1cs.num_constraints,
+        )?;
+
+        // the commitments of the encoding are not blinded, so the rng is
+        // never drawn from.
+        let rng = &mut StepRng::new(0, 0);
+        let (encode, encode_comm) = super::spark::encode::<G, _>(&params, &r1cs, rng)?;
+
+        Ok(Parameters {
+            params,
+            r1cs,
+            fingerprint,
             encode,
             encode_comm,
         })
@@ -87,9 +151,22 @@ pub mod snark {
         c: C,
         rng: &mut R,
     ) -> Result<Proof<G>, SynthesisError> {
+        let c = FingerprintCircuit::check(c, &pk.fingerprint);
         super::prover::create_snark_proof(&pk.params, &pk.r1cs, c, &pk.encode, rng)
     }
 
+    /// Proves `c` of the values of `witness`, generated apart, synthesizing
+    /// only the shape of its constraints.
+    pub fn prove_with_witness<G: Curve, C: ConstraintSynthesizer<G::Fr>, R: Rng>(
+        pk: &ProveKey<G>,
+        c: C,
+        witness: &Witness<G::Fr>,
+        rng: &mut R,
+    ) -> Result<Proof<G>, SynthesisError> {
+        let c = WitnessCircuit::new(c, witness)?;
+        create_random_proof(pk, c, rng)
+    }
+
     pub fn verify_proof<G: Curve>(
         vk: &VerifyKey<G>,
         proof: &Proof<G>,
@@ -106,10 +183,15 @@ pub mod snark {
 }
 
 pub mod nizk {
+    use curve::hash_to_curve::HashToCurve;
     use math::Curve;
     use rand::Rng;
 
-    use crate::r1cs::{ConstraintSynthesizer, SynthesisError};
+    use crate::key_fingerprint::{key_fingerprint, KeyFingerprint, SchemeTag};
+    use crate::r1cs::{
+        CircuitFingerprint, ConstraintSynthesizer, FingerprintCircuit, SynthesisError, Witness,
+        WitnessCircuit,
+    };
 
     use super::data_structure::NizkParameters;
     use super::r1cs::R1CSInstance;
@@ -120,18 +202,21 @@ pub mod nizk {
     pub struct Parameters<G: Curve> {
         params: NizkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
     }
 
     #[derive(Serialize, Deserialize)]
     pub struct ProveKey<G: Curve> {
         params: NizkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
     }
 
     #[derive(Serialize, Deserialize)]
     pub struct VerifyKey<G: Curve> {
         params: NizkParameters<G>,
         r1cs: R1CSInstance<G>,
+        fingerprint: CircuitFingerprint,
     }
 
     impl<G: Curve> Parameters<G> {
@@ -140,20 +225,40 @@ pub mod nizk {
                 ProveKey {
                     params: self.params.clone(),
                     r1cs: self.r1cs.clone(),
+                    fingerprint: self.fingerprint,
                 },
                 VerifyKey {
                     params: self.params,
                     r1cs: self.r1cs,
+                    fingerprint: self.fingerprint,
                 },
             )
         }
     }
 
+    impl<G: Curve> VerifyKey<G> {
+        /// The fingerprint of the circuit the key was generated for.
+        pub fn fingerprint(&self) -> &CircuitFingerprint {
+            &self.fingerprint
+        }
+
+        /// The fingerprint of the key, of the scheme and the curve, to tell
+        /// which key a file is of.
+        pub fn key_fingerprint(&self) -> KeyFingerprint
+        where
+            Self: serde::Serialize,
+        {
+            key_fingerprint::<G::Affine, _>(SchemeTag::SpartanNizk, self)
+        }
+    }
+
     pub fn generate_random_parameters<G: Curve, C: ConstraintSynthesizer<G::Fr>, R: Rng>(
         c: C,
         rng: &mut R,
     ) -> Result<Parameters<G>, SynthesisError> {
-        let r1cs = super::r1cs::generate_r1cs::<G, C>(c)?;
+        let mut fingerprint = [0u8; 32];
+        let r1cs =
+            super::r1cs::generate_r1cs::<G, _>(FingerprintCircuit::record(c, &mut fingerprint))?;
 
         let params = super::setup::generate_setup_nizk_parameters::<G, R>(
             rng,
@@ -161,7 +266,38 @@ pub mod nizk {
             r1cs.num_inputs,
         )?;
 
-        Ok(Parameters { params, r1cs })
+        Ok(Parameters {
+            params,
+            r1cs,
+            fingerprint,
+        })
+    }
+
+    /// Like `generate_random_parameters`, but derives the commitment
+    /// generators from the public `label`, so that the parameters can be
+    /// recomputed by anyone instead of being trusted or shipped.
+    pub fn generate_deterministic_parameters<G: Curve, C: ConstraintSynthesizer<G::Fr>>(
+        c: C,
+        label: &[u8],
+    ) -> Result<Parameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let mut fingerprint = [0u8; 32];
+        let r1cs =
+            super::r1cs::generate_r1cs::<G, _>(FingerprintCircuit::record(c, &mut fingerprint))?;
+
+        let params = super::setup::generate_deterministic_setup_nizk_parameters::<G>(
+            label,
+            r1cs.num_aux,
+            r1cs.num_inputs,
+        )?;
+
+        Ok(Parameters {
+            params,
+            r1cs,
+            fingerprint,
+        })
     }
 
     pub fn create_random_proof<G: Curve, C: ConstraintSynthesizer<G::Fr>, R: Rng>(
@@ -169,9 +305,22 @@ pub mod nizk {
         c: C,
         rng: &mut R,
     ) -> Result<Proof<G>, SynthesisError> {
+        let c = FingerprintCircuit::check(c, &pk.fingerprint);
         super::prover::create_nizk_proof(&pk.params, &pk.r1cs, c, rng)
     }
 
+    /// Proves `c` of the values of `witness`, generated apart, synthesizing
+    /// only the shape of its constraints.
+    pub fn prove_with_witness<G: Curve, C: ConstraintSynthesizer<G::Fr>, R: Rng>(
+        pk: &ProveKey<G>,
+        c: C,
+        witness: &Witness<G::Fr>,
+        rng: &mut R,
+    ) -> Result<Proof<G>, SynthesisError> {
+        let c = WitnessCircuit::new(c, witness)?;
+        create_random_proof(pk, c, rng)
+    }
+
     pub fn verify_proof<G: Curve>(
         vk: &VerifyKey<G>,
         proof: &Proof<G>,
@@ -98,6 +98,14 @@ impl<G: Curve> ConstraintSystem<G::Fr> for ProvingAssignment<G> {
     fn num_constraints(&self) -> usize {
         self.num_constraints
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.input_assignment.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux_assignment.len()
+    }
 }
 
 pub fn create_nizk_proof<G, C, R>(
@@ -119,8 +127,7 @@ where
         circuit,
         rng,
         &mut transcript,
-    )
-    .unwrap();
+    )?;
     let proof = NIZKProof::<G> {
         r1cs_satisfied_proof: r1cs_sat_proof,
         r: (rx, ry),
@@ -148,8 +155,7 @@ where
         circuit,
         rng,
         &mut transcript,
-    )
-    .unwrap();
+    )?;
 
     let eval_a_r = evaluate_mle::<G>(&r1cs.a_matrix, &rx, &ry);
     let eval_b_r = evaluate_mle::<G>(&r1cs.b_matrix, &rx, &ry);
@@ -1005,17 +1011,9 @@ fn inner_product_proof<G: Curve, R: Rng>(
     transcript.append_message(b"Cy", &math::to_bytes!(commit_ry).unwrap());
 
     let blind_gamma = lz_blind + &ry_blind;
-    let (proof, a, b, g, blind_fin) = bullet_inner_product_proof::<G>(
-        &params.gen_n.generators,
-        params.gen_1.generators[0],
-        params.gen_n.h,
-        &lz,
-        &r_eq_ry,
-        blind_gamma,
-        &blind_vec,
-        transcript,
-    )
-    .unwrap();
+    let (proof, folded) =
+        bullet_inner_product_proof::<G>(params, &lz, &r_eq_ry, blind_gamma, &blind_vec, transcript);
+    let (a, b, g, blind_fin) = (folded.a, folded.b, folded.g, folded.blinding);
 
     let delta = poly_commit_vec::<G>(&[g].to_vec(), &[d], &params.gen_1.h, r_delta)
         .unwrap()
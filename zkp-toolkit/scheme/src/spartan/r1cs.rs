@@ -101,6 +101,14 @@ impl<G: Curve> ConstraintSystem<G::Fr> for R1CSInstance<G> {
     fn num_constraints(&self) -> usize {
         self.num_constraints
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.num_aux
+    }
 }
 
 fn push_constraints<F: Field>(
@@ -5,6 +5,7 @@ use crate::spartan::data_structure::{
 };
 use crate::Vec;
 use core::cmp;
+use curve::hash_to_curve::{generators_from_label, HashToCurve};
 use math::{log2, Curve, ProjectiveCurve, UniformRand};
 use rand::Rng;
 
@@ -49,6 +50,65 @@ where
     })
 }
 
+/// Derives the nizk parameters from the public `label` instead of sampling
+/// them, so that anyone can recompute and check them.
+pub fn generate_deterministic_setup_nizk_parameters<G>(
+    label: &[u8],
+    num_aux: usize,
+    num_inputs: usize,
+) -> Result<NizkParameters<G>, SynthesisError>
+where
+    G: Curve,
+    G::Affine: HashToCurve,
+{
+    let r1cs_satisfied_params = R1CSSatisfiedParameters::deterministic(
+        &sub_label(label, b"r1cs_satisfied"),
+        num_aux,
+        num_inputs,
+    )?;
+
+    Ok(NizkParameters {
+        r1cs_satisfied_params,
+    })
+}
+
+/// Derives the snark parameters from the public `label` instead of sampling
+/// them, so that anyone can recompute and check them.
+pub fn generate_deterministic_setup_snark_parameters<G>(
+    label: &[u8],
+    num_aux: usize,
+    num_inputs: usize,
+    num_constraints: usize,
+) -> Result<SnarkParameters<G>, SynthesisError>
+where
+    G: Curve,
+    G::Affine: HashToCurve,
+{
+    let r1cs_satisfied_params = R1CSSatisfiedParameters::deterministic(
+        &sub_label(label, b"r1cs_satisfied"),
+        num_aux,
+        num_inputs,
+    )?;
+
+    let t = cmp::max(num_aux, num_inputs).next_power_of_two();
+    let n = cmp::max(t * 2, num_constraints).next_power_of_two();
+    let r1cs_eval_params = R1CSEvalsParameters::deterministic(
+        &sub_label(label, b"r1cs_evals"),
+        num_constraints * (t * 2),
+        n,
+    )?;
+
+    Ok(SnarkParameters {
+        r1cs_satisfied_params,
+        r1cs_eval_params,
+    })
+}
+
+/// The label of the parameters named `name` within those of `label`.
+fn sub_label(label: &[u8], name: &[u8]) -> Vec<u8> {
+    [label, b"/", name].concat()
+}
+
 impl<G: Curve> R1CSSatisfiedParameters<G> {
     pub fn new<R>(
         rng: &mut R,
@@ -69,6 +129,41 @@ impl<G: Curve> R1CSSatisfiedParameters<G> {
             n,
         })
     }
+
+    pub fn deterministic(
+        label: &[u8],
+        num_aux: usize,
+        num_inputs: usize,
+    ) -> Result<R1CSSatisfiedParameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let n = log2(cmp::max(num_aux, num_inputs).next_power_of_two()) as usize;
+        let pc_params = PolyCommitmentParameters::deterministic(&sub_label(label, b"pc"), n)?;
+        let sc_params = SumCheckCommitmentParameters::deterministic(
+            &sub_label(label, b"sc"),
+            pc_params.gen_1.clone(),
+        )?;
+        Ok(R1CSSatisfiedParameters {
+            pc_params,
+            sc_params,
+            n,
+        })
+    }
+}
+
+impl<G: Curve> MultiCommitmentParameters<G> {
+    /// Derives `n` generators and the blinding generator `h` from `label`
+    /// by hashing to the curve, so that no discrete logarithm relation
+    /// between them is known to anyone.
+    pub fn deterministic(label: &[u8], n: usize) -> MultiCommitmentParameters<G>
+    where
+        G::Affine: HashToCurve,
+    {
+        let mut generators = generators_from_label::<G::Affine>(label, n + 1);
+        let h = generators.pop().unwrap();
+        MultiCommitmentParameters { n, generators, h }
+    }
 }
 
 impl<G: Curve> PolyCommitmentParameters<G> {
@@ -95,6 +190,26 @@ impl<G: Curve> PolyCommitmentParameters<G> {
 
         Ok(pc_params)
     }
+
+    pub fn deterministic(
+        label: &[u8],
+        num: usize,
+    ) -> Result<PolyCommitmentParameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let n = (2usize).pow((num - num / 2) as u32);
+        let gen_n = MultiCommitmentParameters::deterministic(&sub_label(label, b"gen_n"), n);
+        let gen_1 = MultiCommitmentParameters {
+            n: 1,
+            generators: generators_from_label(&sub_label(label, b"gen_1"), 1),
+            h: gen_n.h,
+        };
+
+        let pc_params = PolyCommitmentParameters { n, gen_n, gen_1 };
+
+        Ok(pc_params)
+    }
 }
 
 impl<G: Curve> SumCheckCommitmentParameters<G> {
@@ -126,6 +241,25 @@ impl<G: Curve> SumCheckCommitmentParameters<G> {
 
         Ok(sc_params)
     }
+
+    pub fn deterministic(
+        label: &[u8],
+        gen_1: MultiCommitmentParameters<G>,
+    ) -> Result<SumCheckCommitmentParameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let gen_3 = MultiCommitmentParameters::deterministic(&sub_label(label, b"gen_3"), 3);
+        let gen_4 = MultiCommitmentParameters::deterministic(&sub_label(label, b"gen_4"), 4);
+
+        let sc_params = SumCheckCommitmentParameters {
+            gen_1,
+            gen_3,
+            gen_4,
+        };
+
+        Ok(sc_params)
+    }
 }
 
 impl<G: Curve> R1CSEvalsParameters<G> {
@@ -151,4 +285,35 @@ impl<G: Curve> R1CSEvalsParameters<G> {
 
         Ok(params)
     }
+
+    pub fn deterministic(
+        label: &[u8],
+        n: usize,
+        m: usize,
+    ) -> Result<R1CSEvalsParameters<G>, SynthesisError>
+    where
+        G::Affine: HashToCurve,
+    {
+        let num_ops_params = log2(n) as usize + 4;
+        let ops_params =
+            PolyCommitmentParameters::deterministic(&sub_label(label, b"ops"), num_ops_params)?;
+
+        let num_mem_params = log2(m * 2) as usize + 1;
+        let mem_params =
+            PolyCommitmentParameters::deterministic(&sub_label(label, b"mem"), num_mem_params)?;
+
+        let num_derefs_params = log2(n) as usize + 3;
+        let derefs_params = PolyCommitmentParameters::deterministic(
+            &sub_label(label, b"derefs"),
+            num_derefs_params,
+        )?;
+
+        let params = R1CSEvalsParameters::<G> {
+            ops_params,
+            mem_params,
+            derefs_params,
+        };
+
+        Ok(params)
+    }
 }
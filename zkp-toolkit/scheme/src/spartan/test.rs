@@ -214,6 +214,116 @@ mod bls12_381 {
     }
 }
 
+#[cfg(test)]
+mod bls12_377 {
+    use super::*;
+    use crate::spartan::prover::{create_nizk_proof, create_snark_proof};
+    use crate::spartan::r1cs::generate_r1cs;
+    use crate::spartan::setup::*;
+    use crate::spartan::spark::encode;
+    use crate::spartan::verify::{verify_nizk_proof, verify_snark_proof};
+    use curve::bls12_377::{Bls12_377, Fr};
+    use math::{One, PairingEngine};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_nizk_spartan_bls12_377() {
+        let rng = &mut thread_rng();
+        let c = TestDemo::<Fr> {
+            lhs: None,
+            rhs: None,
+            ohs: None,
+            phs: None,
+        };
+
+        println!("Generate parameters...");
+        let r1cs = generate_r1cs::<Bls12_377, _>(c).unwrap();
+        let params =
+            generate_setup_nizk_parameters::<Bls12_377, _>(rng, r1cs.num_aux, r1cs.num_inputs)
+                .unwrap();
+        let c1 = TestDemo::<Fr> {
+            lhs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+            rhs: Some(
+                <Bls12_377 as PairingEngine>::Fr::one() + &<Bls12_377 as PairingEngine>::Fr::one(),
+            ),
+            ohs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+            phs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+        };
+
+        // let mut transcript = Transcript::new(b"spartan nizk");
+        println!("Creating proof...");
+        let proof = create_nizk_proof(&params, &r1cs, c1, rng).unwrap();
+
+        println!("Verify proof...");
+        // let mut transcript = Transcript::new(b"spartan nizk");
+        let result = verify_nizk_proof::<Bls12_377>(
+            &params,
+            &r1cs,
+            &vec![<Bls12_377 as PairingEngine>::Fr::one()],
+            &proof,
+        )
+        .unwrap();
+
+        assert!(result);
+    }
+
+    #[test]
+    fn test_snark_spartan_bls12_377() {
+        println!("\n spartan snark...");
+        let rng = &mut thread_rng();
+        let c = TestDemo::<Fr> {
+            lhs: None,
+            rhs: None,
+            ohs: None,
+            phs: None,
+        };
+
+        println!("[snark_spartan]Generate parameters...");
+        let r1cs = generate_r1cs::<Bls12_377, _>(c).unwrap();
+
+        let params = generate_setup_snark_parameters::<Bls12_377, _>(
+            rng,
+            r1cs.num_aux,
+            r1cs.num_inputs,
+            r1cs.num_constraints,
+        )
+        .unwrap();
+
+        let c1 = TestDemo::<Fr> {
+            lhs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+            rhs: Some(
+                <Bls12_377 as PairingEngine>::Fr::one() + &<Bls12_377 as PairingEngine>::Fr::one(),
+            ),
+            ohs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+            phs: Some(<Bls12_377 as PairingEngine>::Fr::one()),
+        };
+        println!("[snark_spartan]Generate parameters...ok");
+
+        println!("[snark_spartan]Encode...");
+        let (encode, encode_commit) = encode::<Bls12_377, _>(&params, &r1cs, rng).unwrap();
+        println!("[snark_spartan]Encode...ok");
+
+        // let mut transcript = Transcript::new(b"spartan snark");
+        println!("[snark_spartan]Creating proof...");
+        let proof = create_snark_proof(&params, &r1cs, c1, &encode, rng).unwrap();
+        println!("[snark_spartan]Creating proof...ok");
+
+        println!("[snark_spartan]Verify proof...");
+        // let mut transcript = Transcript::new(b"spartan snark");
+        let result = verify_snark_proof::<Bls12_377>(
+            &params,
+            &r1cs,
+            &vec![<Bls12_377 as PairingEngine>::Fr::one()],
+            &proof,
+            &encode_commit,
+        )
+        .is_ok();
+        println!("[snark_spartan]Verify proof...ok");
+
+        assert!(result);
+    }
+}
+
 #[cfg(test)]
 mod bn_256 {
     use super::*;
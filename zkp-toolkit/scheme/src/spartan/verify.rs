@@ -492,13 +492,16 @@ fn inner_product_verify<G: Curve>(
 
     let gamma = commit_lz + commit_ry;
 
-    let (b_s, g_hat, gamma_hat) = bullet_inner_product_verify::<G>(
-        &params.gen_n.generators,
+    let (b_s, g_hat, gamma_hat) = match bullet_inner_product_verify::<G>(
+        params,
         &proof.inner_product_proof,
         gamma,
         &r_eq_ry,
         transcript,
-    )?;
+    ) {
+        Some(hats) => hats,
+        None => return Ok(false),
+    };
     transcript.append_message(b"delta", &math::to_bytes!(proof.delta)?);
     transcript.append_message(b"beta", &math::to_bytes!(proof.beta)?);
     let mut buf = [0u8; 31];
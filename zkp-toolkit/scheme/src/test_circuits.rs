@@ -0,0 +1,147 @@
+//! The circuits of the tests of the schemes, of their witness optional so
+//! that the setups synthesize them without one.
+use math::Field;
+
+use crate::r1cs::{ConstraintSynthesizer, ConstraintSystem, LinearCombination, SynthesisError};
+use crate::Vec;
+
+/// `x * x = y`, of the public `y`.
+pub(crate) struct Square<F: Field> {
+    pub(crate) x: Option<F>,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Square<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let x_value = self.x;
+        let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+        let y = cs.alloc_input(
+            || "y",
+            || {
+                x_value
+                    .map(|x| x.square())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        cs.enforce(|| "x * x = y", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+
+        Ok(())
+    }
+}
+
+/// `x * x * x = y`, of the public `y`.
+pub(crate) struct Cube<F: Field> {
+    pub(crate) x: Option<F>,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Cube<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let x_value = self.x;
+        let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+        let y = cs.alloc_input(
+            || "y",
+            || {
+                x_value
+                    .map(|x| x.square() * &x)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let x2 = cs.alloc(
+            || "x^2",
+            || {
+                x_value
+                    .map(|x| x.square())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        cs.enforce(|| "x * x = x^2", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+        cs.enforce(|| "x^2 * x = y", |lc| lc + x2, |lc| lc + x, |lc| lc + y);
+
+        Ok(())
+    }
+}
+
+/// `x^(2^num) = y`, of the public `y`, of `num` constraints.
+pub(crate) struct Powers<F: Field> {
+    pub(crate) x: Option<F>,
+    pub(crate) num: usize,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Powers<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut value = self.x;
+        let mut x = cs.alloc(|| "x", || value.ok_or(SynthesisError::AssignmentMissing))?;
+        for i in 0..self.num {
+            let square = value.map(|x| x.square());
+            let y_fn = || square.ok_or(SynthesisError::AssignmentMissing);
+            let y = if i == self.num - 1 {
+                cs.alloc_input(|| "y", y_fn)?
+            } else {
+                cs.alloc(|| "square", y_fn)?
+            };
+            cs.enforce(|| "x * x = square", |lc| lc + x, |lc| lc + x, |lc| lc + y);
+            x = y;
+            value = square;
+        }
+
+        Ok(())
+    }
+}
+
+/// Public inputs, of their sum private.
+pub(crate) struct Sum<F: Field> {
+    pub(crate) inputs: Vec<F>,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Sum<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut lc = LinearCombination::zero();
+        for (i, input) in self.inputs.iter().enumerate() {
+            lc = lc + cs.alloc_input(|| format!("input {}", i), || Ok(*input))?;
+        }
+        let sum = self.inputs.iter().fold(F::zero(), |acc, x| acc + x);
+        let sum = cs.alloc(|| "sum", || Ok(sum))?;
+        cs.enforce(|| "sum", |_| lc, |lc| lc + CS::one(), |lc| lc + sum);
+
+        Ok(())
+    }
+}
+
+/// `x * x = t` and `(t + x) * 1 = y`, of the public input `y`, so that
+/// `y` and `t` are in no B.
+pub(crate) struct Toy<F: Field> {
+    pub(crate) x: Option<F>,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Toy<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let t_value = self.x.map(|x| x.square());
+        let y_value = self.x.map(|x| x.square() + &x);
+        let x = cs.alloc(|| "x", || self.x.ok_or(SynthesisError::AssignmentMissing))?;
+        let t = cs.alloc(|| "t", || t_value.ok_or(SynthesisError::AssignmentMissing))?;
+        let y = cs.alloc_input(|| "y", || y_value.ok_or(SynthesisError::AssignmentMissing))?;
+        cs.enforce(|| "x * x = t", |lc| lc + x, |lc| lc + x, |lc| lc + t);
+        cs.enforce(
+            || "(t + x) * 1 = y",
+            |lc| lc + t + x,
+            |lc| lc + CS::one(),
+            |lc| lc + y,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! The global allocator of the tests measuring allocations, counting them
+//! and the bytes held. Each of these tests is alone in its binary, so no
+//! other thread allocates meanwhile.
+#![allow(dead_code)]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Runs `f`, returning its result and the number of allocations it made.
+pub fn allocations_of<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+    (result, ALLOCATIONS.load(Ordering::SeqCst) - base)
+}
+
+/// Runs `f`, returning its result and the most bytes it held at once.
+pub fn peak_of<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let base = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(base, Ordering::SeqCst);
+    let result = f();
+    (result, PEAK.load(Ordering::SeqCst) - base)
+}
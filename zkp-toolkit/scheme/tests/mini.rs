@@ -82,9 +82,363 @@ fn mini_groth16() {
     assert!(verify_proof(&pvk, &proof, &[Fr::from(10u32)]).unwrap());
 }
 
+#[test]
+fn mini_groth16_witness() {
+    use scheme::groth16::{
+        generate_random_parameters, prove_with_witness, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+    use scheme::r1cs::Witness;
+
+    let rng = &mut test_rng();
+    let off = |num| Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: num,
+    };
+
+    let params = generate_random_parameters::<E, _, _>(off(10), rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // the witness is generated apart from the proving.
+    let witness = Witness::generate(Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: 10,
+    })
+    .unwrap();
+    let witness_bytes = postcard::to_allocvec(&witness).unwrap();
+    let witness: Witness<Fr> = postcard::from_bytes(&witness_bytes).unwrap();
+
+    let proof = prove_with_witness(&params, off(10), &witness, rng).unwrap();
+    assert!(verify_proof(&pvk, &proof, &[Fr::from(10u32)]).unwrap());
+
+    assert!(matches!(
+        prove_with_witness(&params, off(11), &witness, rng),
+        Err(SynthesisError::WitnessMismatch)
+    ));
+}
+
+#[test]
+fn mini_groth16_bls12_377() {
+    use curve::bls12_377::{Bls12_377, Fr};
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+    let num = 10;
+
+    let params = {
+        let c = Mini::<Fr> {
+            x: None,
+            y: None,
+            z: None,
+            num: num,
+        };
+
+        generate_random_parameters::<Bls12_377, _, _>(c, rng).unwrap()
+    };
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let c = Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: num,
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    assert!(verify_proof(&pvk, &proof, &[Fr::from(10u32)]).unwrap());
+    assert!(!verify_proof(&pvk, &proof, &[Fr::from(11u32)]).unwrap());
+}
+
+#[test]
+fn mini_groth16_bw6_761() {
+    use curve::bw6_761::{Fr, BW6_761};
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+    let num = 10;
+
+    let params = {
+        let c = Mini::<Fr> {
+            x: None,
+            y: None,
+            z: None,
+            num: num,
+        };
+
+        generate_random_parameters::<BW6_761, _, _>(c, rng).unwrap()
+    };
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let c = Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: num,
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    assert!(verify_proof(&pvk, &proof, &[Fr::from(10u32)]).unwrap());
+    assert!(!verify_proof(&pvk, &proof, &[Fr::from(11u32)]).unwrap());
+}
+
+#[cfg(feature = "ethereum")]
+#[test]
+fn mini_groth16_ethereum() {
+    use curve::bn_256::{Bn_256, EthereumEncoding, Fr, G1Affine, G2Affine};
+    use scheme::groth16::{create_random_proof, generate_random_parameters, verify_proof, Proof};
+
+    let rng = &mut test_rng();
+    let num = 10;
+
+    let params = {
+        let c = Mini::<Fr> {
+            x: None,
+            y: None,
+            z: None,
+            num: num,
+        };
+
+        generate_random_parameters::<Bn_256, _, _>(c, rng).unwrap()
+    };
+
+    let c = Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: num,
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    let words = proof.to_ethereum_encoding();
+    assert_eq!(words[0..2].concat(), proof.a.to_ethereum_bytes());
+    assert_eq!(words[2..6].concat(), proof.b.to_ethereum_bytes());
+    assert_eq!(words[6..8].concat(), proof.c.to_ethereum_bytes());
+
+    let decoded = Proof::<Bn_256> {
+        a: G1Affine::from_ethereum_bytes(&words[0..2].concat()).unwrap(),
+        b: G2Affine::from_ethereum_bytes(&words[2..6].concat()).unwrap(),
+        c: G1Affine::from_ethereum_bytes(&words[6..8].concat()).unwrap(),
+    };
+    assert_eq!(decoded, proof);
+    assert!(verify_proof(&params.vk, &decoded, &[Fr::from(10u32)]).unwrap());
+}
+
+#[cfg(feature = "ethereum")]
+#[test]
+fn mini_groth16_solidity() {
+    use curve::bn_256::{Bn_256, EthereumEncoding, Fr};
+    use scheme::groth16::generate_random_parameters;
+
+    // of `test_rng`, the key is the same on every run, and so the contract.
+    let rng = &mut test_rng();
+    let c = Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: 10,
+    };
+    let vk = generate_random_parameters::<Bn_256, _, _>(c, rng)
+        .unwrap()
+        .vk;
+
+    let source = vk.to_solidity();
+    assert_eq!(source, include_str!("snapshots/mini_groth16_verifier.sol"));
+
+    // the constants are the big-endian coordinates of the key.
+    let constant = |name: &str| {
+        let prefix = format!("uint256 constant {} = 0x", name);
+        let start = source.find(&prefix).unwrap() + prefix.len();
+        (0..32)
+            .map(|i| u8::from_str_radix(&source[start + 2 * i..start + 2 * i + 2], 16).unwrap())
+            .collect::<Vec<u8>>()
+    };
+    let g1 = |name: &str| {
+        let words = ["_X", "_Y"]
+            .iter()
+            .map(|c| constant(&format!("{}{}", name, c)));
+        words.collect::<Vec<_>>().concat()
+    };
+    let g2 = |name: &str| {
+        let words = ["_X1", "_X0", "_Y1", "_Y0"];
+        let words = words.iter().map(|c| constant(&format!("{}{}", name, c)));
+        words.collect::<Vec<_>>().concat()
+    };
+    assert_eq!(g1("ALPHA"), vk.alpha_g1.to_ethereum_bytes());
+    assert_eq!(g2("BETA"), vk.beta_g2.to_ethereum_bytes());
+    assert_eq!(g2("GAMMA"), vk.gamma_g2.to_ethereum_bytes());
+    assert_eq!(g2("DELTA"), vk.delta_g2.to_ethereum_bytes());
+    assert_eq!(vk.gamma_abc_g1.len(), 2);
+    for (i, ic) in vk.gamma_abc_g1.iter().enumerate() {
+        assert_eq!(g1(&format!("IC{}", i)), ic.to_ethereum_bytes());
+    }
+    assert!(!source.contains("IC2_X"));
+    assert!(source.contains("require(input.length == 1, "));
+}
+
+#[test]
+fn mini_groth16_prepared_vk() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof, PreparedVerifyingKey,
+    };
+
+    let rng = &mut test_rng();
+    let num = 10;
+
+    let params = {
+        let c = Mini::<Fr> {
+            x: None,
+            y: None,
+            z: None,
+            num: num,
+        };
+
+        generate_random_parameters::<E, _, _>(c, rng).unwrap()
+    };
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // the prepared key is shipped as is, in postcard or in JSON
+    let pvk_bytes = postcard::to_allocvec(&pvk).unwrap();
+    let pvk: PreparedVerifyingKey<E> = postcard::from_bytes(&pvk_bytes).unwrap();
+    assert_eq!(pvk, prepare_verifying_key(&params.vk));
+    let json = serde_json::to_string(&pvk).unwrap();
+    let json_pvk: PreparedVerifyingKey<E> = serde_json::from_str(&json).unwrap();
+    assert_eq!(postcard::to_allocvec(&json_pvk).unwrap(), pvk_bytes);
+
+    let c = Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: num,
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    for (input, expected) in [(Fr::from(10u32), true), (Fr::from(11u32), false)].iter() {
+        assert_eq!(
+            verify_proof(&params.vk, &proof, &[*input]).unwrap(),
+            *expected
+        );
+        assert_eq!(verify_proof(&pvk, &proof, &[*input]).unwrap(), *expected);
+        assert_eq!(
+            verify_proof(&json_pvk, &proof, &[*input]).unwrap(),
+            *expected
+        );
+    }
+}
+
+#[test]
+fn mini_groth16_aggregate() {
+    use scheme::groth16::{
+        aggregate_proofs, create_random_proof, generate_random_parameters,
+        verifier::prepare_verifying_key, verify_aggregate_proof, AggregationParams,
+    };
+
+    let rng = &mut test_rng();
+    let num = 10;
+
+    let params = {
+        let c = Mini::<Fr> {
+            x: None,
+            y: None,
+            z: None,
+            num: num,
+        };
+
+        generate_random_parameters::<E, _, _>(c, rng).unwrap()
+    };
+    let pvk = prepare_verifying_key(&params.vk);
+    let srs = AggregationParams::<E>::setup(256, rng);
+
+    for n in [8u32, 256].iter() {
+        let (pk, vk) = srs.specialize(*n as usize).unwrap();
+
+        // x * (3 + 2) = z, of every x from 1.
+        let mut proofs = vec![];
+        let mut inputs = vec![];
+        for x in 1..=*n {
+            let c = Mini::<Fr> {
+                x: Some(Fr::from(x)),
+                y: Some(Fr::from(3u32)),
+                z: Some(Fr::from(5 * x)),
+                num: num,
+            };
+            proofs.push(create_random_proof(&params, c, rng).unwrap());
+            inputs.push(vec![Fr::from(5 * x)]);
+        }
+
+        let aggregate = aggregate_proofs(&pk, &proofs, &inputs).unwrap();
+        let aggregate_bytes = postcard::to_allocvec(&aggregate).unwrap();
+        println!(
+            "Groth16 aggregate of {} proofs...ok, size: {}",
+            n,
+            aggregate_bytes.len()
+        );
+        assert!(verify_aggregate_proof(&pvk, &vk, &inputs, &aggregate).unwrap());
+
+        // a proof of other inputs, among the valid ones.
+        let mut wrong_inputs = inputs.clone();
+        wrong_inputs[*n as usize / 2][0] += &Fr::one();
+        assert!(!verify_aggregate_proof(&pvk, &vk, &wrong_inputs, &aggregate).unwrap());
+
+        // a proof of which `c` is of another proof.
+        let mut corrupted = proofs.clone();
+        corrupted[1].c = proofs[0].c;
+        let aggregate = aggregate_proofs(&pk, &corrupted, &inputs).unwrap();
+        assert!(!verify_aggregate_proof(&pvk, &vk, &inputs, &aggregate).unwrap());
+    }
+}
+
+#[test]
+fn mini_r1cs_stats() {
+    use scheme::r1cs::stats::{analyze, Groth16Sizes, MarlinSizes, R1csStats, SpartanSizes};
+
+    // x * (y + 2) = z, 10 times.
+    let stats = analyze(Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: 10,
+    })
+    .unwrap();
+    assert_eq!(
+        stats,
+        R1csStats {
+            num_constraints: 10,
+            num_inputs: 2,
+            num_aux: 2,
+            a_non_zeros: 10,
+            b_non_zeros: 20,
+            c_non_zeros: 10,
+            max_row_density: 2,
+            groth16: Groth16Sizes { domain: 16 },
+            marlin: MarlinSizes {
+                domain_x: 2,
+                domain_h: 16,
+                domain_k: 32,
+            },
+            spartan: SpartanSizes {
+                num_constraints: 16,
+                num_variables: 4,
+                num_non_zeros: 32,
+            },
+        }
+    );
+}
+
 #[test]
 fn mini_marlin() {
-    use scheme::marlin::{create_random_proof, index, universal_setup, verify_proof};
+    use scheme::marlin::{create_random_proof, index, max_degree, universal_setup, verify_proof};
+    use scheme::r1cs::stats::analyze;
 
     let rng = &mut test_rng();
     let num = 10;
@@ -98,10 +452,35 @@ fn mini_marlin() {
         num: num,
     };
 
-    let srs = universal_setup::<E, _>(2usize.pow(10), rng).unwrap();
+    let degree = max_degree::<E, _>(Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: num,
+    })
+    .unwrap();
+    let srs = universal_setup::<E, _>(degree, rng).unwrap();
     println!("marlin indexer...");
     let (ipk, ivk) = index(&srs, c).unwrap();
 
+    // the stats pad as the indexer does.
+    let stats = analyze(Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: num,
+    })
+    .unwrap();
+    let info = &ivk.index_info;
+    assert_eq!(
+        stats.marlin.domain_h,
+        info.num_variables.next_power_of_two()
+    );
+    assert_eq!(
+        stats.marlin.domain_k,
+        info.num_non_zeros.next_power_of_two()
+    );
+
     let circuit = Mini {
         x: Some(Fr::from(2u32)),
         y: Some(Fr::from(3u32)),
@@ -120,13 +499,15 @@ fn mini_marlin() {
 fn mini_bulletproofs() {
     //use curve::baby_jubjub::{BabyJubJub as G, Fr}; // size: 100%, time: 100%, 100%
     use curve::curve25519::{Curve25519 as G, Fr}; // size: 71%, time: 13%, 14%
-    use scheme::bulletproofs::{create_random_proof, verify_proof};
+    use scheme::bulletproofs::{prove, setup, synthesize_shape, verify};
     use std::time::Instant;
 
     let rng = &mut test_rng();
     let num = 10;
 
-    // TRUSTED SETUP
+    // NO TRUSTED SETUP, the generators are random.
+    let gens = setup::<G, _>(num as usize, rng);
+
     println!("Bulletproofs prove...");
     let c = Mini::<Fr> {
         x: Some(Fr::from(2u32)),
@@ -136,14 +517,14 @@ fn mini_bulletproofs() {
     };
 
     let start = Instant::now();
-    let (gens, r1cs, proof) = create_random_proof::<G, _, _>(c, rng).unwrap();
+    let (_shape, proof) = prove(&gens, c, rng).unwrap();
     println!("prove time: {:?}", start.elapsed());
 
     let proof_bytes = postcard::to_allocvec(&proof).unwrap();
     println!("Bulletproof proof...ok, size: {}", proof_bytes.len());
 
     println!("Bulletproof verify...");
-    let _c = Mini::<Fr> {
+    let c = Mini::<Fr> {
         x: None,
         y: None,
         z: None,
@@ -151,10 +532,36 @@ fn mini_bulletproofs() {
     };
 
     let start = Instant::now();
-    assert!(verify_proof(&gens, &proof, &r1cs, &[Fr::from(10u32)]).unwrap());
+    let shape = synthesize_shape::<G, _>(c).unwrap();
+    assert!(verify(&gens, &shape, &proof, &[Fr::from(10u32)]).unwrap());
     println!("verify time: {:?}", start.elapsed());
 }
 
+fn mini_bulletproofs_on<G: math::Curve>() {
+    use scheme::bulletproofs::{prove, setup, verify};
+
+    let rng = &mut test_rng();
+    let gens = setup::<G, _>(10, rng);
+    let c = Mini::<G::Fr> {
+        x: Some(G::Fr::from(2u32)),
+        y: Some(G::Fr::from(3u32)),
+        z: Some(G::Fr::from(10u32)),
+        num: 10,
+    };
+
+    let (shape, proof) = prove(&gens, c, rng).unwrap();
+    assert!(verify(&gens, &shape, &proof, &[G::Fr::from(10u32)]).unwrap());
+    assert!(!verify(&gens, &shape, &proof, &[G::Fr::from(11u32)]).unwrap());
+}
+
+#[test]
+fn mini_bulletproofs_ristretto_and_bn_256() {
+    // the same circuit proves and verifies over the Ristretto group and over
+    // the G1 of a pairing curve.
+    mini_bulletproofs_on::<curve::curve25519::Curve25519>();
+    mini_bulletproofs_on::<curve::bn_256::Bn_256>();
+}
+
 use scheme::clinkv2::r1cs as clinkv2_r1cs;
 
 pub struct Clinkv2Mini<F: PrimeField> {
@@ -358,3 +765,97 @@ fn test_mini_spartan_nizk() {
     assert!(verify_proof::<G>(&vk, &proof, &vec![Fr::from(10u32)].to_vec(),).unwrap());
     println!("[nizk_spartan]Verify proof...ok");
 }
+
+#[test]
+fn test_mini_spartan_witness() {
+    use curve::curve25519::{Curve25519 as G, Fr};
+    use math::test_rng;
+    use scheme::r1cs::Witness;
+    use scheme::spartan::{nizk, snark};
+
+    let rng = &mut test_rng();
+    let off = |num| Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: num,
+    };
+
+    let witness = Witness::generate(Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: 10,
+    })
+    .unwrap();
+    let witness_bytes = postcard::to_allocvec(&witness).unwrap();
+    let witness: Witness<Fr> = postcard::from_bytes(&witness_bytes).unwrap();
+
+    let params = snark::generate_random_parameters(off(10), rng).unwrap();
+    let (pk, vk) = params.keypair();
+    let proof = snark::prove_with_witness(&pk, off(10), &witness, rng).unwrap();
+    assert!(snark::verify_proof::<G>(&vk, &proof, &[Fr::from(10u32)]).unwrap());
+    assert!(matches!(
+        snark::prove_with_witness(&pk, off(11), &witness, rng),
+        Err(SynthesisError::WitnessMismatch)
+    ));
+
+    let params = nizk::generate_random_parameters(off(10), rng).unwrap();
+    let (pk, vk) = params.keypair();
+    let proof = nizk::prove_with_witness(&pk, off(10), &witness, rng).unwrap();
+    assert!(nizk::verify_proof::<G>(&vk, &proof, &[Fr::from(10u32)]).unwrap());
+    assert!(matches!(
+        nizk::prove_with_witness(&pk, off(11), &witness, rng),
+        Err(SynthesisError::WitnessMismatch)
+    ));
+}
+
+#[test]
+fn test_mini_spartan_deterministic() {
+    use curve::bn_256::{Bn_256 as G, Fr};
+    use math::test_rng;
+    use scheme::spartan::{nizk, snark};
+
+    let label = b"mini spartan parameters";
+    let rng = &mut test_rng();
+    let off = || Mini::<Fr> {
+        x: None,
+        y: None,
+        z: None,
+        num: 10,
+    };
+    let on = || Mini::<Fr> {
+        x: Some(Fr::from(2u32)),
+        y: Some(Fr::from(3u32)),
+        z: Some(Fr::from(10u32)),
+        num: 10,
+    };
+
+    // two independent derivations agree
+    let params = snark::generate_deterministic_parameters::<G, _>(off(), label).unwrap();
+    let other = snark::generate_deterministic_parameters::<G, _>(off(), label).unwrap();
+    assert_eq!(
+        postcard::to_allocvec(&params).unwrap(),
+        postcard::to_allocvec(&other).unwrap()
+    );
+    let other = snark::generate_deterministic_parameters::<G, _>(off(), b"other").unwrap();
+    assert_ne!(
+        postcard::to_allocvec(&params).unwrap(),
+        postcard::to_allocvec(&other).unwrap()
+    );
+
+    let (pk, vk) = params.keypair();
+    let proof = snark::create_random_proof(&pk, on(), rng).unwrap();
+    assert!(snark::verify_proof::<G>(&vk, &proof, &[Fr::from(10u32)]).unwrap());
+
+    let params = nizk::generate_deterministic_parameters::<G, _>(off(), label).unwrap();
+    let other = nizk::generate_deterministic_parameters::<G, _>(off(), label).unwrap();
+    assert_eq!(
+        postcard::to_allocvec(&params).unwrap(),
+        postcard::to_allocvec(&other).unwrap()
+    );
+
+    let (pk, vk) = params.keypair();
+    let proof = nizk::create_random_proof(&pk, on(), rng).unwrap();
+    assert!(nizk::verify_proof::<G>(&vk, &proof, &[Fr::from(10u32)]).unwrap());
+}
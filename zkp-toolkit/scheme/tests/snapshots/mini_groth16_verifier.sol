@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT
+// A Groth16 verifier on bn254 of 1 public inputs, generated by zkp-toolkit.
+pragma solidity ^0.8.0;
+
+contract Groth16Verifier {
+    uint256 constant PRIME_Q = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+    uint256 constant SNARK_SCALAR_FIELD = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+
+    uint256 constant ALPHA_X = 0x1f86460646688a3854eadf0cf3d6f7ea425c81be30e0f51986acf158dc67a725;
+    uint256 constant ALPHA_Y = 0x02fbd1e80785513643a8ec03a06d872b7e9968a67d78f7e0698d5361cc32a99a;
+    uint256 constant BETA_X1 = 0x2f3dd64e9e9038a91863e339b5258ebbd5d78eaa18c7f492a26cefad735ecdd9;
+    uint256 constant BETA_X0 = 0x0e646e94e8417948dec23d4172eab31677480d1f98ae7b46d896aa78e9ff9e02;
+    uint256 constant BETA_Y1 = 0x14b0b695a72d14807587335c145975cd7b64a19137a9764ef10217f624deefda;
+    uint256 constant BETA_Y0 = 0x28fb6d2023719a6cb56b736d5650acf3b1ab3e44c251b9c8077a20e1e4bb8870;
+    uint256 constant GAMMA_X1 = 0x0923e4ca0d6a054477f54ae7f437c0fb052b8a5add1b247a4b37c5da5965fc74;
+    uint256 constant GAMMA_X0 = 0x17af474c7d375f760f93c4c021957f32995b93573663efa60d94ef2b66a03b97;
+    uint256 constant GAMMA_Y1 = 0x21fec9c465b0fca1d2bd2b1ae853c9edbf8a7d48fa691aa83bb660aa79352d02;
+    uint256 constant GAMMA_Y0 = 0x26486b5b8adbfccbd3e7eef555aa29be6e5b45a78a5bbccfec6f3496388aa59c;
+    uint256 constant DELTA_X1 = 0x16170096a40861f74a5e6529213a0a25ec2e139991ecb38aa3e8606b94288fab;
+    uint256 constant DELTA_X0 = 0x2a1248b0bf08e42faeb776bcf756b36d2ce0f3b39e963b078d63d8b599a08782;
+    uint256 constant DELTA_Y1 = 0x114c9142373b95e3f7101d859691957c642503ba251954bce4e8690f609b1e0c;
+    uint256 constant DELTA_Y0 = 0x12152158dd4091ca232955de8cc9f0e1d830542fb4d613669ea802c1ae1af6f3;
+    uint256 constant IC0_X = 0x0164aa4efc6e755bee652d4c2e2481d91c131c6460d59d7a1ad7f282fa4f05be;
+    uint256 constant IC0_Y = 0x164b2bdbd6122a8fe2e02b755d8a7d917f4c00164950b7b3c631747e209a7d47;
+    uint256 constant IC1_X = 0x00cf78e8f13a8d57ec1e31589acad01992a20f35d937fa4f76abf25e306bd888;
+    uint256 constant IC1_Y = 0x0a70b0d73240cabc4912144b8583920b82886ee8388a9f1b92273e6c44378e5c;
+
+    /// The proof is of the words of `Proof::to_ethereum_encoding`, the
+    /// imaginary part of each coordinate of `b` first.
+    function verifyProof(
+        uint256[2] memory a,
+        uint256[2][2] memory b,
+        uint256[2] memory c,
+        uint256[] memory input
+    ) public view returns (bool) {
+        require(input.length == 1, "invalid number of public inputs");
+        require(a[0] < PRIME_Q && a[1] < PRIME_Q, "invalid proof");
+        for (uint256 i = 0; i < input.length; i++) {
+            require(input[i] < SNARK_SCALAR_FIELD, "input is not in the scalar field");
+        }
+
+        uint256[2] memory vkX = [IC0_X, IC0_Y];
+        vkX = add(vkX, mul([IC1_X, IC1_Y], input[0]));
+
+        // e(-a, b) e(alpha, beta) e(vkX, gamma) e(c, delta) == 1
+        uint256[24] memory args;
+        (args[0], args[1]) = (a[0], (PRIME_Q - a[1]) % PRIME_Q);
+        (args[2], args[3], args[4], args[5]) = (b[0][0], b[0][1], b[1][0], b[1][1]);
+        (args[6], args[7]) = (ALPHA_X, ALPHA_Y);
+        (args[8], args[9], args[10], args[11]) = (BETA_X1, BETA_X0, BETA_Y1, BETA_Y0);
+        (args[12], args[13]) = (vkX[0], vkX[1]);
+        (args[14], args[15], args[16], args[17]) = (GAMMA_X1, GAMMA_X0, GAMMA_Y1, GAMMA_Y0);
+        (args[18], args[19]) = (c[0], c[1]);
+        (args[20], args[21], args[22], args[23]) = (DELTA_X1, DELTA_X0, DELTA_Y1, DELTA_Y0);
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 8, args, 768, out, 32)
+        }
+        require(ok, "pairing check failed");
+        return out[0] == 1;
+    }
+
+    function add(uint256[2] memory p, uint256[2] memory q) internal view returns (uint256[2] memory r) {
+        uint256[4] memory args = [p[0], p[1], q[0], q[1]];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 6, args, 128, r, 64)
+        }
+        require(ok, "point addition failed");
+    }
+
+    function mul(uint256[2] memory p, uint256 s) internal view returns (uint256[2] memory r) {
+        uint256[3] memory args = [p[0], p[1], s];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 7, args, 96, r, 64)
+        }
+        require(ok, "scalar multiplication failed");
+    }
+}
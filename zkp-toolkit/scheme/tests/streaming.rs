@@ -0,0 +1,64 @@
+//! The peak memory of streaming a large Groth16 proving key, counted by the
+//! global allocator: one test only, so no other thread allocates meanwhile.
+mod common;
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+use curve::bn_256::{Bn_256, G1Affine};
+use math::AffineCurve;
+use scheme::groth16::{Parameters, VerifyKey};
+
+use common::peak_of;
+
+#[test]
+fn test_streaming_peak_memory() {
+    // a key of no circuit, only as large as one of 2^18 constraints.
+    let n = 1 << 18;
+    let g1 = G1Affine::prime_subgroup_generator();
+    let params = Parameters::<Bn_256> {
+        vk: VerifyKey::default(),
+        beta_g1: g1,
+        delta_g1: g1,
+        a_query: vec![g1; 2],
+        b_g1_query: vec![g1; 2],
+        b_g2_query: vec![],
+        h_query: vec![g1; n],
+        l_query: vec![g1; n],
+    };
+    let path = std::env::temp_dir().join("groth16-streaming-peak.pk");
+
+    let (_, write_peak) = peak_of(|| {
+        let mut writer = BufWriter::new(File::create(&path).unwrap());
+        params.write_streaming(&mut writer).unwrap();
+    });
+    let key_size = std::fs::metadata(&path).unwrap().len() as usize;
+    assert!(key_size > 2 * n * 64);
+    assert!(write_peak < key_size / 4, "{} of {}", write_peak, key_size);
+
+    let (read, read_peak) = peak_of(|| {
+        Parameters::<Bn_256>::read_streaming(BufReader::new(File::open(&path).unwrap())).unwrap()
+    });
+    assert!(read == params);
+    // the parameters themselves, and no copy of the bytes of the key.
+    assert!(
+        read_peak < key_size * 3 / 2,
+        "{} of {}",
+        read_peak,
+        key_size
+    );
+    drop(read);
+
+    #[cfg(feature = "mmap")]
+    {
+        use scheme::groth16::{MappedParameters, ParametersSource};
+
+        let (mapped, open_peak) = peak_of(|| MappedParameters::<Bn_256>::open(&path).unwrap());
+        assert!(open_peak < key_size / 4, "{} of {}", open_peak, key_size);
+        let (h_query, h_peak) = peak_of(|| mapped.h_query().unwrap().into_owned());
+        assert_eq!(h_query, params.h_query);
+        assert!(h_peak < key_size * 3 / 4, "{} of {}", h_peak, key_size);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,61 @@
+//! The allocations of verifying a Groth16 proof with a `Verifier`, counted by
+//! the global allocator: one test only, so no other thread allocates meanwhile.
+mod common;
+
+use curve::bls12_381::{Bls12_381, Fr as Fr381};
+use curve::bn_256::{Bn_256, Fr};
+use math::{test_rng, Field, PairingEngine, UniformRand};
+use scheme::groth16::{
+    create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof, Verifier,
+};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+use common::allocations_of;
+
+/// `x * x * x = y`, of the public inputs `x` and `y`.
+struct Cube<F> {
+    x: F,
+}
+
+impl<F: Field> ConstraintSynthesizer<F> for Cube<F> {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let x2_value = self.x.square();
+        let x = cs.alloc_input(|| "x", || Ok(self.x))?;
+        let x2 = cs.alloc(|| "x2", || Ok(x2_value))?;
+        let y = cs.alloc_input(|| "y", || Ok(x2_value * &self.x))?;
+        cs.enforce(|| "x * x = x2", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+        cs.enforce(|| "x2 * x = y", |lc| lc + x2, |lc| lc + x, |lc| lc + y);
+
+        Ok(())
+    }
+}
+
+fn check_allocations<E: PairingEngine>(x: E::Fr) {
+    let rng = &mut test_rng();
+    let params = generate_random_parameters::<E, _, _>(Cube { x }, rng).unwrap();
+    let proof = create_random_proof(&params, Cube { x }, rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+    let verifier = Verifier::new(pvk.clone());
+    let inputs = [x, x.square() * &x];
+    let wrong = [x, E::Fr::rand(rng)];
+
+    for inputs in &[inputs, wrong] {
+        let (result, allocations) = allocations_of(|| verifier.verify(&proof, inputs).unwrap());
+        assert_eq!(result, verify_proof(&pvk, &proof, inputs).unwrap());
+        assert_eq!(allocations, 0);
+    }
+
+    // of the existing interface, the prepared `b` of the proof at least.
+    let (result, allocations) = allocations_of(|| verify_proof(&pvk, &proof, &inputs).unwrap());
+    assert!(result);
+    assert!(allocations > 0);
+}
+
+#[test]
+fn test_verifier_allocations() {
+    check_allocations::<Bn_256>(Fr::from(3u32));
+    check_allocations::<Bls12_381>(Fr381::from(3u32));
+}
@@ -0,0 +1,204 @@
+//! The range proof of a committed value, `0 <= v < 2^n_bits`. The value is
+//! decomposed into `n_bits` bits, which are packed back into it and
+//! committed to with the Pedersen commitment `v * G + r * H` over the
+//! twisted Edwards curve `P` embedded in the circuit field (baby_jubjub for
+//! bn_256, jubjub for bls12_381, edwards_bls12 for bls12_377).
+//!
+//! The public inputs are the coordinates of the commitment, as
+//! `public_inputs`. The circuit takes no scheme specific gadget, so any
+//! scheme of the field proves it, e.g. groth16 or bulletproofs. It costs
+//! about 3 constraints per bit of the value and of the randomness.
+
+use core::marker::PhantomData;
+use math::{
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine},
+    Field, FpParameters, One, PrimeField,
+};
+use scheme::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, LinearCombination, PublicInputs, SynthesisError,
+};
+
+use crate::gadgets::boolean::{AllocatedBit, Boolean};
+use crate::gadgets::fr::AllocatedFr;
+use crate::gadgets::pedersen::commit;
+use crate::Vec;
+
+/// The number of bits of the randomness, the ones of the order of the prime
+/// subgroup of `P`, so that the commitment hides the value.
+pub fn randomness_bits<P: TEModelParameters>() -> usize {
+    <P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize
+}
+
+/// The little-endian `n_bits` bits of `value`.
+fn value_bits(value: u64, n_bits: usize) -> Vec<bool> {
+    (0..n_bits).map(|i| (value >> i) & 1 == 1).collect()
+}
+
+/// The commitment of `value` with the little-endian bits of `randomness`,
+/// as the circuit of `n_bits` computes it.
+pub fn commitment<P: TEModelParameters>(
+    value: u64,
+    n_bits: usize,
+    randomness: &[bool],
+) -> GroupAffine<P> {
+    crate::gadgets::pedersen::commitment::<P>(&value_bits(value, n_bits), randomness)
+}
+
+/// The public inputs of the range proof of `commitment`, in the order of
+/// the circuit.
+pub fn public_inputs<P: TEModelParameters>(commitment: &GroupAffine<P>) -> Vec<P::BaseField> {
+    vec![commitment.x, commitment.y]
+}
+
+pub struct RangeCircuit<P: TEModelParameters> {
+    value: Option<u64>,
+    randomness: Option<Vec<bool>>,
+    n_bits: usize,
+    _p: PhantomData<P>,
+}
+
+impl<P: TEModelParameters> RangeCircuit<P> {
+    /// The proof that `value` is below `2^n_bits`, of its commitment with
+    /// the `randomness_bits` bits of `randomness`. A value out of the range
+    /// gives a circuit which is not satisfied.
+    pub fn new(value: u64, randomness: Vec<bool>, n_bits: usize) -> Self {
+        assert!(n_bits > 0 && n_bits <= 64, "invalid range size");
+        assert_eq!(randomness.len(), randomness_bits::<P>());
+
+        RangeCircuit {
+            value: Some(value),
+            randomness: Some(randomness),
+            n_bits,
+            _p: PhantomData,
+        }
+    }
+
+    /// The circuit without witnesses for the range of `n_bits`, to
+    /// generate the parameters with.
+    pub fn blank(n_bits: usize) -> Self {
+        assert!(n_bits > 0 && n_bits <= 64, "invalid range size");
+
+        RangeCircuit {
+            value: None,
+            randomness: None,
+            n_bits,
+            _p: PhantomData,
+        }
+    }
+
+    pub fn n_bits(&self) -> usize {
+        self.n_bits
+    }
+}
+
+impl<P> ConstraintSynthesizer<P::BaseField> for RangeCircuit<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    fn generate_constraints<CS: ConstraintSystem<P::BaseField>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let value = self.value;
+        let v = AllocatedFr::alloc(cs.ns(|| "value"), || {
+            value
+                .map(P::BaseField::from)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let bits = (0..self.n_bits)
+            .map(|i| {
+                AllocatedBit::alloc(
+                    cs.ns(|| format!("value bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )
+                .map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // the value is the number of its bits, so it is below 2^n_bits.
+        let mut lc = LinearCombination::zero();
+        let mut coeff = P::BaseField::one();
+        for bit in &bits {
+            lc = lc + &bit.lc(CS::one(), coeff);
+            coeff = coeff.double();
+        }
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + v.get_variable(),
+        );
+
+        let randomness = match self.randomness {
+            Some(randomness) => randomness.into_iter().map(Some).collect(),
+            None => vec![None; randomness_bits::<P>()],
+        };
+        let randomness = randomness
+            .into_iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.ns(|| format!("randomness bit {}", i)), b).map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        commit::<P, _>(cs.ns(|| "commitment"), &bits, &randomness)?;
+
+        Ok(())
+    }
+}
+
+impl<P: TEModelParameters> PublicInputs<P::BaseField> for RangeCircuit<P> {
+    fn public_inputs(&self) -> Vec<P::BaseField> {
+        match (self.value, &self.randomness) {
+            (Some(value), Some(randomness)) => {
+                public_inputs(&commitment::<P>(value, self.n_bits, randomness))
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "baby_jubjub"))]
+mod test {
+    use curve::baby_jubjub::EdwardsParameters;
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::Rng;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn randomness() -> Vec<bool> {
+        let rng = &mut test_rng();
+        (0..randomness_bits::<EdwardsParameters>())
+            .map(|_| rng.gen())
+            .collect()
+    }
+
+    #[test]
+    fn test_range() {
+        let r = randomness();
+        for (value, n_bits) in [(0, 1), (42, 8), (255, 8), (u64::MAX, 64)].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let c = RangeCircuit::<EdwardsParameters>::new(*value, r.clone(), *n_bits);
+            let inputs = c.public_inputs();
+            c.generate_constraints(&mut cs).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert!(cs.verify(&inputs));
+
+            // the commitment is of the value only.
+            let other = commitment::<EdwardsParameters>(value ^ 1, *n_bits, &r);
+            assert!(!cs.verify(&public_inputs(&other)));
+        }
+
+        // 256 is out of the range of 8 bits.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        RangeCircuit::<EdwardsParameters>::new(256, r, 8)
+            .generate_constraints(&mut cs)
+            .unwrap();
+        assert_eq!(cs.which_is_unsatisfied().as_deref(), Some("packing"));
+    }
+}
@@ -1,8 +1,8 @@
 use math::PrimeField;
 use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
 
-struct Isnonzero<F: PrimeField> {
-    check_num: Option<F>,
+pub struct Isnonzero<F: PrimeField> {
+    pub check_num: Option<F>,
 }
 
 impl<E: PrimeField> ConstraintSynthesizer<E> for Isnonzero<E> {
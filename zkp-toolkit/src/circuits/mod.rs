@@ -1,3 +1,5 @@
+pub mod committed_range;
 pub mod isnonzero;
 pub mod mini;
 pub mod rangeproof;
+pub mod spend;
@@ -0,0 +1,169 @@
+//! The spend of a note, as in Zerocash. A note of `value` owned by the
+//! holder of `secret` is committed as the leaf `H(secret, value)` of a
+//! merkle tree of depth `depth`, and is spent by proving the membership of
+//! the leaf with the public nullifier `H(secret, index)`, where `index` is
+//! the one of the leaf. The nullifier does not reveal the note, but the
+//! same note always gives the same one, so that it is only spent once.
+//!
+//! The public inputs are the root and the nullifier, as `public_inputs`.
+//! The hashes of the circuit are `H`, the native ones are its merge `M`,
+//! e.g. `AbstractHashMimc` and `MergeMimc`.
+
+use core::marker::PhantomData;
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+
+use crate::gadgets::abstract_hash::{AbstractHash, AbstractHashFrOutput, AbstractHashOutput};
+use crate::gadgets::merkletree::cbmt::{Merge, MerkleProof, MerkleTree, CBMT};
+use crate::gadgets::merkletree::cbmt_constraints::MerkleProofGadget;
+use crate::Vec;
+
+/// The commitment of a note, the leaf of the tree.
+pub fn commitment<F: PrimeField, M: Merge<Item = F>>(secret: &F, value: &F) -> F {
+    M::merge(secret, value)
+}
+
+/// The nullifier of the note of the leaf `index`.
+pub fn nullifier<F: PrimeField, M: Merge<Item = F>>(secret: &F, index: u32) -> F {
+    M::merge(secret, &F::from(index as u64))
+}
+
+/// Builds the tree of the commitments, padded with zero leaves to the
+/// `2^depth` leaves of a full tree, so that every proof is of `depth`.
+pub fn build_tree<F: PrimeField, M: Merge<Item = F>>(
+    commitments: &[F],
+    depth: usize,
+) -> MerkleTree<F, M> {
+    assert!(commitments.len() <= 1 << depth);
+
+    let mut leaves = commitments.to_vec();
+    leaves.resize(1 << depth, F::zero());
+
+    CBMT::build_merkle_tree(leaves)
+}
+
+/// The public inputs of the spend, in the order of the circuit.
+pub fn public_inputs<F: PrimeField>(root: &F, nullifier: &F) -> Vec<F> {
+    vec![*root, *nullifier]
+}
+
+pub struct Spend<F: PrimeField, H: AbstractHash<F, Output = AbstractHashFrOutput<F>>> {
+    secret: Option<F>,
+    value: Option<F>,
+    /// The index of the leaf in the nodes of the tree, as the proof.
+    node: u32,
+    lemmas: Vec<Option<F>>,
+    hash: PhantomData<H>,
+}
+
+impl<F, H> Spend<F, H>
+where
+    F: PrimeField,
+    H: AbstractHash<F, Output = AbstractHashFrOutput<F>>,
+{
+    /// The spend of the note of `secret` and `value`, with the proof of its
+    /// commitment in a tree of `build_tree`.
+    pub fn new<M: Merge<Item = F>>(secret: F, value: F, proof: &MerkleProof<F, M>) -> Self {
+        assert_eq!(proof.arity(), 2);
+
+        Spend {
+            secret: Some(secret),
+            value: Some(value),
+            node: *proof.index(),
+            lemmas: proof.lemmas().iter().cloned().map(Some).collect(),
+            hash: PhantomData,
+        }
+    }
+
+    /// The circuit without witnesses for the tree of `depth`, to generate
+    /// the parameters with.
+    pub fn blank(depth: usize) -> Self {
+        Spend {
+            secret: None,
+            value: None,
+            node: (1 << depth) - 1,
+            lemmas: vec![None; depth],
+            hash: PhantomData,
+        }
+    }
+}
+
+impl<F, H> ConstraintSynthesizer<F> for Spend<F, H>
+where
+    F: PrimeField,
+    H: AbstractHash<F, Output = AbstractHashFrOutput<F>>,
+{
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let secret = AbstractHashFrOutput::alloc(cs.ns(|| "secret"), self.secret)?;
+        let value = AbstractHashFrOutput::alloc(cs.ns(|| "value"), self.value)?;
+        let leaf = H::hash_enforce(cs.ns(|| "commitment"), &[&secret, &value])?;
+
+        let lemmas = self
+            .lemmas
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| AbstractHashFrOutput::alloc(cs.ns(|| format!("lemma {}", i)), l))
+            .collect::<Result<Vec<_>, _>>()?;
+        let g = MerkleProofGadget::<u32, F, H>::new(self.node, lemmas);
+        let (root, position) = g.compute_root_and_position(cs.ns(|| "membership"), leaf)?;
+
+        let position = AbstractHashFrOutput::from(position);
+        let nullifier = H::hash_enforce(cs.ns(|| "nullifier"), &[&secret, &position])?;
+
+        for (i, e) in root
+            .to_field_elements(cs.ns(|| "root elements"))?
+            .iter()
+            .enumerate()
+        {
+            e.inputize(cs.ns(|| format!("root input {}", i)))?;
+        }
+        nullifier
+            .to_allocated_fr()
+            .inputize(cs.ns(|| "nullifier input"))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, UniformRand};
+
+    use super::*;
+    use crate::gadgets::mimc::{AbstractHashMimc, MergeMimc};
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_spend() {
+        let rng = &mut test_rng();
+
+        let notes = (0..3)
+            .map(|_| (Fr::rand(rng), Fr::rand(rng)))
+            .collect::<Vec<_>>();
+        let commitments = notes
+            .iter()
+            .map(|(s, v)| commitment::<_, MergeMimc<Fr>>(s, v))
+            .collect::<Vec<_>>();
+        let tree = build_tree::<_, MergeMimc<Fr>>(&commitments, 2);
+
+        for (i, (secret, value)) in notes.iter().enumerate() {
+            let proof = tree.build_proof(&(i as u32)).unwrap();
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            Spend::<Fr, AbstractHashMimc<Fr>>::new(*secret, *value, &proof)
+                .generate_constraints(&mut cs)
+                .unwrap();
+
+            assert!(cs.is_satisfied());
+            let image = nullifier::<_, MergeMimc<Fr>>(secret, i as u32);
+            assert!(cs.verify(&public_inputs(&tree.root(), &image)));
+
+            // the nullifier is bound to the index of the leaf.
+            let other = nullifier::<_, MergeMimc<Fr>>(secret, (i as u32 + 1) % 4);
+            assert!(!cs.verify(&public_inputs(&tree.root(), &other)));
+        }
+    }
+}
@@ -1,12 +1,41 @@
-use math::PrimeField;
+use math::{PrimeField, ToBytes};
 use scheme::r1cs::{ConstraintSystem, SynthesisError, Variable};
 
 use crate::Vec;
 
+use super::boolean::Boolean;
+use super::fr::AllocatedFr;
+use super::uint8::{bits_to_bytes_le, UInt8};
+
 pub trait AbstractHashOutput<F: PrimeField>: Clone {
+    /// The native value of the output, the one the `Merge` of the hash
+    /// computes.
+    type Value;
+
     fn get_variables(&self) -> Vec<Variable>;
 
     fn get_variable_values(&self) -> Vec<Option<F>>;
+
+    /// The output with its first variables replaced by `variables`, of the
+    /// same values, e.g. the children the merkle gadgets select. The new
+    /// variables are enforced by the caller to take the old values.
+    fn with_variables(&self, variables: &[Variable]) -> Self;
+
+    /// The output as field elements, e.g. to make them the public inputs.
+    fn to_field_elements<CS: ConstraintSystem<F>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<AllocatedFr<F>>, SynthesisError>;
+
+    /// The canonical bytes of the output.
+    fn to_bytes<CS: ConstraintSystem<F>>(&self, cs: CS) -> Result<Vec<UInt8>, SynthesisError>;
+
+    /// The values of `to_field_elements` of the output of `value`, e.g. the
+    /// public inputs for the verifier.
+    fn native_field_elements(value: &Self::Value) -> Vec<F>;
+
+    /// The values of `to_bytes` of the output of `value`.
+    fn native_bytes(value: &Self::Value) -> Vec<u8>;
 }
 
 pub trait AbstractHash<F: PrimeField> {
@@ -16,3 +45,153 @@ pub trait AbstractHash<F: PrimeField> {
     where
         CS: ConstraintSystem<F>;
 }
+
+/// The output of the hashes into a field element, MiMC, Poseidon and
+/// Rescue, so that a circuit switches between them with the hash type only.
+#[derive(Clone)]
+pub struct AbstractHashFrOutput<F: PrimeField> {
+    fr: AllocatedFr<F>,
+}
+
+impl<F: PrimeField> AbstractHashFrOutput<F> {
+    pub fn alloc<CS: ConstraintSystem<F>>(cs: CS, f: Option<F>) -> Result<Self, SynthesisError> {
+        let fr = AllocatedFr::alloc(cs, || f.ok_or(SynthesisError::AssignmentMissing))?;
+
+        Ok(Self { fr })
+    }
+
+    pub fn alloc_input<CS: ConstraintSystem<F>>(
+        cs: CS,
+        f: Option<F>,
+    ) -> Result<Self, SynthesisError> {
+        let fr = AllocatedFr::alloc_input(cs, || f.ok_or(SynthesisError::AssignmentMissing))?;
+
+        Ok(Self { fr })
+    }
+
+    pub fn get_value(&self) -> Option<F> {
+        self.fr.get_value()
+    }
+
+    pub fn to_allocated_fr(&self) -> AllocatedFr<F> {
+        self.fr.clone()
+    }
+
+    /// The canonical little-endian bits of the output, see
+    /// `AllocatedFr::to_bits_le_strict`.
+    pub fn to_bits_le<CS: ConstraintSystem<F>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        self.fr.to_bits_le_strict(cs)
+    }
+}
+
+impl<F: PrimeField> From<AllocatedFr<F>> for AbstractHashFrOutput<F> {
+    fn from(fr: AllocatedFr<F>) -> Self {
+        Self { fr }
+    }
+}
+
+impl<F: PrimeField> AbstractHashOutput<F> for AbstractHashFrOutput<F> {
+    type Value = F;
+
+    fn get_variables(&self) -> Vec<Variable> {
+        vec![self.fr.get_variable()]
+    }
+
+    fn get_variable_values(&self) -> Vec<Option<F>> {
+        vec![self.fr.get_value()]
+    }
+
+    fn with_variables(&self, variables: &[Variable]) -> Self {
+        let fr = AllocatedFr::from_variable(variables[0], self.fr.get_value());
+
+        Self { fr }
+    }
+
+    fn to_field_elements<CS: ConstraintSystem<F>>(
+        &self,
+        _: CS,
+    ) -> Result<Vec<AllocatedFr<F>>, SynthesisError> {
+        Ok(vec![self.fr.clone()])
+    }
+
+    fn to_bytes<CS: ConstraintSystem<F>>(&self, cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        fr_to_bytes(cs, &self.fr)
+    }
+
+    fn native_field_elements(value: &F) -> Vec<F> {
+        vec![*value]
+    }
+
+    fn native_bytes(value: &F) -> Vec<u8> {
+        fr_native_bytes(value)
+    }
+}
+
+/// The little-endian bytes of the canonical bits of `fr`, as the ones of
+/// `ToBytes` of the number, the bits above the field size are zero.
+pub(super) fn fr_to_bytes<F, CS>(cs: CS, fr: &AllocatedFr<F>) -> Result<Vec<UInt8>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let mut bits = fr.to_bits_le_strict(cs)?;
+    bits.resize(fr_bytes_len::<F>() * 8, Boolean::constant(false));
+
+    Ok(bits_to_bytes_le(&bits))
+}
+
+/// The bytes of `fr_to_bytes` of `f`.
+pub(super) fn fr_native_bytes<F: PrimeField>(f: &F) -> Vec<u8> {
+    let mut bytes = vec![];
+    f.into_repr()
+        .write(&mut bytes)
+        .expect("writing to a vec does not fail");
+    bytes.truncate(fr_bytes_len::<F>());
+    bytes
+}
+
+fn fr_bytes_len<F: PrimeField>() -> usize {
+    (F::size_in_bits() + 7) / 8
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, One, UniformRand, Zero};
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_fr_output_conversions() {
+        let rng = &mut test_rng();
+
+        let values = vec![Fr::zero(), Fr::one(), -Fr::one(), Fr::rand(rng)];
+        for value in values {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let output = AbstractHashFrOutput::alloc(cs.ns(|| "output"), Some(value)).unwrap();
+
+            let elements = output.to_field_elements(cs.ns(|| "elements")).unwrap();
+            let bytes = output.to_bytes(cs.ns(|| "bytes")).unwrap();
+            assert!(cs.is_satisfied());
+
+            assert_eq!(
+                elements.iter().map(|e| e.get_value()).collect::<Vec<_>>(),
+                AbstractHashFrOutput::native_field_elements(&value)
+                    .into_iter()
+                    .map(Some)
+                    .collect::<Vec<_>>()
+            );
+            let native = AbstractHashFrOutput::native_bytes(&value);
+            assert_eq!(native.len(), 32);
+            assert_eq!(
+                bytes.iter().map(|b| b.get_value()).collect::<Vec<_>>(),
+                native.into_iter().map(Some).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -27,6 +27,11 @@ use super::{boolean::Boolean, multieq::MultiEq, uint32::UInt32};
               --------------+------------------+------------------+
 */
 
+/// The initialization vector, the same as the one of SHA-256.
+const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
 const R1: usize = 16;
 const R2: usize = 12;
 const R3: usize = 8;
@@ -83,6 +88,7 @@ const SIGMA: [[usize; 16]; 10] = [
        END FUNCTION.
 */
 
+#[allow(clippy::too_many_arguments)]
 fn mixing_g<F: PrimeField, CS: ConstraintSystem<F>, M>(
     mut cs: M,
     v: &mut [UInt32],
@@ -162,34 +168,24 @@ where
        END FUNCTION.
 */
 
-fn blake2s_compression<F: PrimeField, CS: ConstraintSystem<F>>(
-    mut cs: CS,
+/// The compression of a block, its additions batched in the `MultiEq` of
+/// the caller, along the ones of the other blocks.
+fn blake2s_compression<F: PrimeField, CS: ConstraintSystem<F>, M>(
+    mut cs: M,
     h: &mut [UInt32],
     m: &[UInt32],
     t: u64,
     f: bool,
-) -> Result<(), SynthesisError> {
+) -> Result<(), SynthesisError>
+where
+    M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+{
     assert_eq!(h.len(), 8);
     assert_eq!(m.len(), 16);
 
-    /*
-    static const uint32_t blake2s_iv[8] =
-    {
-        0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A,
-        0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19
-    };
-    */
-
     let mut v = Vec::with_capacity(16);
     v.extend_from_slice(h);
-    v.push(UInt32::constant(0x6A09E667));
-    v.push(UInt32::constant(0xBB67AE85));
-    v.push(UInt32::constant(0x3C6EF372));
-    v.push(UInt32::constant(0xA54FF53A));
-    v.push(UInt32::constant(0x510E527F));
-    v.push(UInt32::constant(0x9B05688C));
-    v.push(UInt32::constant(0x1F83D9AB));
-    v.push(UInt32::constant(0x5BE0CD19));
+    v.extend(IV.iter().map(|&word| UInt32::constant(word)));
 
     assert_eq!(v.len(), 16);
 
@@ -197,99 +193,95 @@ fn blake2s_compression<F: PrimeField, CS: ConstraintSystem<F>>(
     v[13] = v[13].xor(cs.ns(|| "second xor"), &UInt32::constant((t >> 32) as u32))?;
 
     if f {
-        v[14] = v[14].xor(cs.ns(|| "third xor"), &UInt32::constant(u32::max_value()))?;
+        v[14] = v[14].xor(cs.ns(|| "third xor"), &UInt32::constant(u32::MAX))?;
     }
 
-    {
-        let mut cs = MultiEq::new(&mut cs);
+    for i in 0..10 {
+        let mut cs = cs.ns(|| format!("round {}", i));
 
-        for i in 0..10 {
-            let mut cs = cs.ns(|| format!("round {}", i));
+        let s = SIGMA[i % 10];
 
-            let s = SIGMA[i % 10];
+        mixing_g(
+            cs.ns(|| "mixing invocation 1"),
+            &mut v,
+            0,
+            4,
+            8,
+            12,
+            &m[s[0]],
+            &m[s[1]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 2"),
+            &mut v,
+            1,
+            5,
+            9,
+            13,
+            &m[s[2]],
+            &m[s[3]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 3"),
+            &mut v,
+            2,
+            6,
+            10,
+            14,
+            &m[s[4]],
+            &m[s[5]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 4"),
+            &mut v,
+            3,
+            7,
+            11,
+            15,
+            &m[s[6]],
+            &m[s[7]],
+        )?;
 
-            mixing_g(
-                cs.ns(|| "mixing invocation 1"),
-                &mut v,
-                0,
-                4,
-                8,
-                12,
-                &m[s[0]],
-                &m[s[1]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 2"),
-                &mut v,
-                1,
-                5,
-                9,
-                13,
-                &m[s[2]],
-                &m[s[3]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 3"),
-                &mut v,
-                2,
-                6,
-                10,
-                14,
-                &m[s[4]],
-                &m[s[5]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 4"),
-                &mut v,
-                3,
-                7,
-                11,
-                15,
-                &m[s[6]],
-                &m[s[7]],
-            )?;
-
-            mixing_g(
-                cs.ns(|| "mixing invocation 5"),
-                &mut v,
-                0,
-                5,
-                10,
-                15,
-                &m[s[8]],
-                &m[s[9]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 6"),
-                &mut v,
-                1,
-                6,
-                11,
-                12,
-                &m[s[10]],
-                &m[s[11]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 7"),
-                &mut v,
-                2,
-                7,
-                8,
-                13,
-                &m[s[12]],
-                &m[s[13]],
-            )?;
-            mixing_g(
-                cs.ns(|| "mixing invocation 8"),
-                &mut v,
-                3,
-                4,
-                9,
-                14,
-                &m[s[14]],
-                &m[s[15]],
-            )?;
-        }
+        mixing_g(
+            cs.ns(|| "mixing invocation 5"),
+            &mut v,
+            0,
+            5,
+            10,
+            15,
+            &m[s[8]],
+            &m[s[9]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 6"),
+            &mut v,
+            1,
+            6,
+            11,
+            12,
+            &m[s[10]],
+            &m[s[11]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 7"),
+            &mut v,
+            2,
+            7,
+            8,
+            13,
+            &m[s[12]],
+            &m[s[13]],
+        )?;
+        mixing_g(
+            cs.ns(|| "mixing invocation 8"),
+            &mut v,
+            3,
+            4,
+            9,
+            14,
+            &m[s[14]],
+            &m[s[15]],
+        )?;
     }
 
     for i in 0..8 {
@@ -329,29 +321,53 @@ fn blake2s_compression<F: PrimeField, CS: ConstraintSystem<F>>(
         END FUNCTION.
 */
 
+/// Computes the unkeyed BLAKE2s hash with a 32-byte digest of `input`, whose
+/// length must be a whole number of bytes. Both the input and the output are
+/// little-endian bits of each byte, in byte order.
 pub fn blake2s<F: PrimeField, CS: ConstraintSystem<F>>(
-    mut cs: CS,
+    cs: CS,
+    input: &[Boolean],
+    personalization: &[u8; 8],
+) -> Result<Vec<Boolean>, SynthesisError> {
+    assert!(input.len() % 8 == 0);
+
+    blake2s_with_key_length(cs, input, 0, personalization)
+}
+
+/// Computes the keyed BLAKE2s hash with a 32-byte digest of `input`, the MAC
+/// of `key`, which is 1 to 32 bytes. The key is in the bits of `blake2s`, and
+/// is padded with zeros to the block hashed before the input.
+pub fn blake2s_keyed<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: CS,
+    key: &[Boolean],
     input: &[Boolean],
-    personalization: &[u8],
+    personalization: &[u8; 8],
 ) -> Result<Vec<Boolean>, SynthesisError> {
-    assert_eq!(personalization.len(), 8);
+    assert!(key.len() % 8 == 0 && !key.is_empty() && key.len() <= 256);
     assert!(input.len() % 8 == 0);
 
-    let mut h = Vec::with_capacity(8);
-    h.push(UInt32::constant(0x6A09E667 ^ 0x01010000 ^ 32));
-    h.push(UInt32::constant(0xBB67AE85));
-    h.push(UInt32::constant(0x3C6EF372));
-    h.push(UInt32::constant(0xA54FF53A));
-    h.push(UInt32::constant(0x510E527F));
-    h.push(UInt32::constant(0x9B05688C));
+    let mut blocks = key.to_vec();
+    blocks.resize(512, Boolean::constant(false));
+    blocks.extend_from_slice(input);
+
+    blake2s_with_key_length(cs, &blocks, key.len() / 8, personalization)
+}
 
+/// The BLAKE2s of the padded key block and the input in `input`, with the
+/// key length in the parameter block.
+fn blake2s_with_key_length<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    input: &[Boolean],
+    key_length: usize,
+    personalization: &[u8; 8],
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let mut h: Vec<u32> = IV.to_vec();
+    // Parameter block: the key length, a 32-byte digest
+    h[0] ^= 0x01010000 ^ ((key_length as u32) << 8) ^ 32;
     // Personalization is stored here
-    h.push(UInt32::constant(
-        0x1F83D9AB ^ LittleEndian::read_u32(&personalization[0..4]),
-    ));
-    h.push(UInt32::constant(
-        0x5BE0CD19 ^ LittleEndian::read_u32(&personalization[4..8]),
-    ));
+    h[6] ^= LittleEndian::read_u32(&personalization[0..4]);
+    h[7] ^= LittleEndian::read_u32(&personalization[4..8]);
+    let mut h: Vec<UInt32> = h.into_iter().map(UInt32::constant).collect();
 
     let mut blocks: Vec<Vec<UInt32>> = vec![];
 
@@ -374,6 +390,9 @@ pub fn blake2s<F: PrimeField, CS: ConstraintSystem<F>>(
         blocks.push((0..16).map(|_| UInt32::constant(0)).collect());
     }
 
+    // All the blocks share the batching of the additions
+    let mut cs = MultiEq::new(&mut cs);
+
     for (i, block) in blocks[0..blocks.len() - 1].iter().enumerate() {
         let cs = cs.ns(|| format!("block {}", i));
 
@@ -404,8 +423,10 @@ mod test {
     use scheme::r1cs::ConstraintSystem;
 
     use super::super::boolean::{AllocatedBit, Boolean};
-    use super::super::test_constraint_system::TestConstraintSystem;
-    use super::blake2s;
+    use super::super::multieq::MultiEq;
+    use super::super::uint32::UInt32;
+    use super::{blake2s, blake2s_compression, blake2s_keyed, IV};
+    use crate::test_cs::TestConstraintSystem;
 
     #[test]
     fn test_blank_hash() {
@@ -431,6 +452,223 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_blake2s_rfc7693_vectors() {
+        // Unkeyed BLAKE2s-256 without personalization: the empty message,
+        // "abc" from RFC 7693 Appendix B, and messages of one block and of
+        // one block and a byte.
+        let vectors: [(Vec<u8>, [u8; 32]); 4] = [
+            (
+                vec![],
+                hex_literal::hex!(
+                    "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9"
+                ),
+            ),
+            (
+                b"abc".to_vec(),
+                hex_literal::hex!(
+                    "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"
+                ),
+            ),
+            (
+                (0..64).collect(),
+                hex_literal::hex!(
+                    "56f34e8b96557e90c1f24b52d0c89d51086acf1b00f634cf1dde9233b8eaaa3e"
+                ),
+            ),
+            (
+                (0..65).collect(),
+                hex_literal::hex!(
+                    "1b53ee94aaf34e4b159d48de352c7f0661d0a40edff95a0b1639b4090e974472"
+                ),
+            ),
+        ];
+
+        for (data, expected) in vectors.iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let mut input_bits = vec![];
+            for (byte_i, input_byte) in data.iter().enumerate() {
+                for bit_i in 0..8 {
+                    let cs = cs.ns(|| format!("input bit {} {}", byte_i, bit_i));
+
+                    input_bits.push(
+                        AllocatedBit::alloc(cs, Some((input_byte >> bit_i) & 1u8 == 1u8))
+                            .unwrap()
+                            .into(),
+                    );
+                }
+            }
+
+            let r = blake2s(&mut cs, &input_bits, &[0u8; 8]).unwrap();
+            assert!(cs.is_satisfied());
+
+            let mut out = r.into_iter();
+            for b in expected.iter() {
+                for i in 0..8 {
+                    let c = out.next().unwrap().get_value().unwrap();
+
+                    assert_eq!(c, (b >> i) & 1u8 == 1u8);
+                }
+            }
+        }
+    }
+
+    fn alloc_bytes(cs: &mut TestConstraintSystem<Fr>, name: &str, data: &[u8]) -> Vec<Boolean> {
+        let mut bits = vec![];
+        for (byte_i, input_byte) in data.iter().enumerate() {
+            for bit_i in 0..8 {
+                let cs = cs.ns(|| format!("{} bit {} {}", name, byte_i, bit_i));
+
+                bits.push(
+                    AllocatedBit::alloc(cs, Some((input_byte >> bit_i) & 1u8 == 1u8))
+                        .unwrap()
+                        .into(),
+                );
+            }
+        }
+        bits
+    }
+
+    fn assert_bytes(r: &[Boolean], expected: &[u8]) {
+        let mut out = r.iter();
+        for b in expected.iter() {
+            for i in 0..8 {
+                let c = out.next().unwrap().get_value().unwrap();
+
+                assert_eq!(c, (b >> i) & 1u8 == 1u8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_blake2s_keyed_vectors() {
+        // The keyed vectors of the BLAKE2 reference, blake2s-kat.txt: the key
+        // 00 01 .. 1f and the messages 00 01 .. of 0, 1 and 255 bytes.
+        let key: Vec<u8> = (0..32).collect();
+        let vectors: [(usize, [u8; 32]); 3] = [
+            (
+                0,
+                hex_literal::hex!(
+                    "48a8997da407876b3d79c0d92325ad3b89cbb754d86ab71aee047ad345fd2c49"
+                ),
+            ),
+            (
+                1,
+                hex_literal::hex!(
+                    "40d15fee7c328830166ac3f918650f807e7e01e177258cdc0a39b11f598066f1"
+                ),
+            ),
+            (
+                255,
+                hex_literal::hex!(
+                    "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd"
+                ),
+            ),
+        ];
+
+        for (len, expected) in vectors.iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let data: Vec<u8> = (0..*len as u8).collect();
+            let key_bits = alloc_bytes(&mut cs, "key", &key);
+            let input_bits = alloc_bytes(&mut cs, "input", &data);
+
+            let r = blake2s_keyed(&mut cs, &key_bits, &input_bits, &[0u8; 8]).unwrap();
+            assert!(cs.is_satisfied());
+            assert_bytes(&r, expected);
+        }
+    }
+
+    #[test]
+    fn test_blake2s_personalized_vectors() {
+        // BLAKE2s-256 of the personalizations of the Sapling PRF^nf and
+        // CRH^ivk, unkeyed as Zcash uses them and keyed, of the key 00 01 ..
+        // and the message 80 81 .., as Python's hashlib.blake2s gives them.
+        let vectors: [(&[u8; 8], usize, usize, [u8; 32]); 8] = [
+            (
+                b"Zcash_nf",
+                0,
+                64,
+                hex_literal::hex!(
+                    "939c928d8cc500d70df12a7c7ae7fab7bd35ed77fe7483fe09363b1b590603d8"
+                ),
+            ),
+            (
+                b"Zcash_nf",
+                1,
+                0,
+                hex_literal::hex!(
+                    "d734357d81c1c02d081f76cd8f47617da5435c82e2a0723ebf139033e937e299"
+                ),
+            ),
+            (
+                b"Zcash_nf",
+                32,
+                32,
+                hex_literal::hex!(
+                    "e867637ebe7411f49c0272bcd10067e36529657bcb525a06f3703c17ccdb3db0"
+                ),
+            ),
+            (
+                b"Zcash_nf",
+                7,
+                65,
+                hex_literal::hex!(
+                    "ab7a669e79b049b29e66fc4944737a8a08752183845eaf4c6cb35716b8c2f9b8"
+                ),
+            ),
+            (
+                b"Zcashivk",
+                0,
+                64,
+                hex_literal::hex!(
+                    "77874e45b231d124c50c06089d9d3cd9f91daf17acebb1eaeab432060123ab0b"
+                ),
+            ),
+            (
+                b"Zcashivk",
+                1,
+                0,
+                hex_literal::hex!(
+                    "dffbb3f87febe4726e7aba0ca77dbe1b68c532cd2a25f52ef3f5b2c937e08e4a"
+                ),
+            ),
+            (
+                b"Zcashivk",
+                32,
+                32,
+                hex_literal::hex!(
+                    "4a171ffa4411441e9e74567d3a810c8a580ad7e27f1a75d18c20aed81cb464a7"
+                ),
+            ),
+            (
+                b"Zcashivk",
+                7,
+                65,
+                hex_literal::hex!(
+                    "bc5bba1636a42977a2e0c59658d4bff8755aba8ef6008034c070e981c4c304d0"
+                ),
+            ),
+        ];
+
+        for (personalization, key_len, input_len, expected) in vectors.iter() {
+            let key: Vec<u8> = (0..*key_len as u8).collect();
+            let data: Vec<u8> = (0..*input_len).map(|i| (0x80 + i) as u8).collect();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let key_bits = alloc_bytes(&mut cs, "key", &key);
+            let input_bits = alloc_bytes(&mut cs, "input", &data);
+
+            let r = if key.is_empty() {
+                blake2s(&mut cs, &input_bits, personalization).unwrap()
+            } else {
+                blake2s_keyed(&mut cs, &key_bits, &input_bits, personalization).unwrap()
+            };
+            assert!(cs.is_satisfied());
+            assert_bytes(&r, expected);
+        }
+    }
+
     #[test]
     fn test_blake2s_constraints() {
         let mut cs = TestConstraintSystem::<Fr>::new();
@@ -446,6 +684,49 @@ mod test {
         assert_eq!(cs.num_constraints(), 21518);
     }
 
+    #[test]
+    fn test_blake2s_batching_across_blocks() {
+        let rng = &mut test_rng();
+        let words: Vec<u32> = (0..16 * 7).map(|_| rng.next_u32()).collect();
+
+        let compress_all = |batched: bool| {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let m = words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| UInt32::alloc(cs.ns(|| format!("word {}", i)), Some(*w)).unwrap())
+                .collect::<Vec<_>>();
+            let mut h = IV.iter().map(|&w| UInt32::constant(w)).collect::<Vec<_>>();
+            {
+                let mut shared = MultiEq::new(&mut cs);
+                for (i, block) in m.chunks(16).enumerate() {
+                    let cs = shared.ns(|| format!("block {}", i));
+                    let t = (i as u64 + 1) * 64;
+                    if batched {
+                        blake2s_compression(cs, &mut h, block, t, false).unwrap();
+                    } else {
+                        blake2s_compression(MultiEq::new(cs), &mut h, block, t, false).unwrap();
+                    }
+                }
+            }
+            assert!(cs.is_satisfied());
+
+            let digest = h
+                .into_iter()
+                .flat_map(|e| e.into_bits())
+                .map(|b| b.get_value().unwrap())
+                .collect::<Vec<_>>();
+            (cs.num_constraints(), digest)
+        };
+
+        let (batched, digest) = compress_all(true);
+        let (unbatched, expected) = compress_all(false);
+        assert_eq!(digest, expected);
+        // the 320 additions of a block take 46 constraints of 7 of them, 7
+        // blocks together take 320 instead of 322.
+        assert_eq!(unbatched - batched, 2);
+    }
+
     #[test]
     fn test_blake2s_precomp_constraints() {
         // Test that 512 fixed leading bits (constants)
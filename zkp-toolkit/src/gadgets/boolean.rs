@@ -1,4 +1,4 @@
-use math::{BitIterator, Field, PrimeField};
+use math::{BigInteger, BitIterator, Field, PrimeField};
 use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
 
 use crate::Vec;
@@ -20,6 +20,12 @@ impl AllocatedBit {
         self.variable
     }
 
+    /// The bit of a `variable` allocated elsewhere, of `value`. It is not
+    /// enforced to be boolean, the caller constrains the variable.
+    pub(crate) fn from_variable(variable: Variable, value: Option<bool>) -> Self {
+        AllocatedBit { variable, value }
+    }
+
     /// Allocate a variable in the constraint system which can only be a
     /// boolean value. Further, constrain that the boolean is false
     /// unless the condition is false.
@@ -370,6 +376,90 @@ impl Boolean {
         }
     }
 
+    /// Enforces the big-endian `bits` to be at most `constant`. Along the
+    /// bits of `constant` from the top, every run of ones is ANDed with the
+    /// runs above it, and a bit at a zero of `constant` must be zero while
+    /// the bits above match `constant`. This costs one constraint per zero
+    /// below the top one of `constant`, and at most two per run of ones.
+    /// The bits above the length of `constant` must be zero.
+    pub fn enforce_smaller_or_equal_than<F, CS, B>(
+        mut cs: CS,
+        bits: &[Self],
+        constant: &B,
+    ) -> Result<(), SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+        B: BigInteger,
+    {
+        let num_bits = constant.num_bits() as usize;
+        if bits.len() < num_bits {
+            // always smaller.
+            return Ok(());
+        }
+
+        let (extra_bits, bits) = bits.split_at(bits.len() - num_bits);
+        for (i, bit) in extra_bits.iter().enumerate() {
+            let i = num_bits + extra_bits.len() - 1 - i;
+            Self::enforce_equal(
+                cs.ns(|| format!("bit {} is zero", i)),
+                bit,
+                &Boolean::constant(false),
+            )?;
+        }
+
+        // the AND of the runs of ones above is whether the bits above match
+        // `constant`.
+        let mut matching: Option<Boolean> = None;
+        let mut current_run = vec![];
+        let constant_bits = BitIterator::new(constant).skip_while(|b| !b);
+        for (j, (bit, b)) in bits.iter().zip(constant_bits).enumerate() {
+            let i = num_bits - 1 - j;
+            if b {
+                current_run.push(*bit);
+            } else {
+                if !current_run.is_empty() {
+                    current_run.extend(matching.take());
+                    matching = Some(Self::kary_and(
+                        cs.ns(|| format!("run ending at {}", i)),
+                        &current_run,
+                    )?);
+                    current_run.clear();
+                }
+
+                let matching = matching.as_ref().expect("the constant starts with a one");
+                cs.enforce(
+                    || format!("bit {} is zero if the bits above match", i),
+                    |_| bit.lc(CS::one(), F::one()),
+                    |_| matching.lc(CS::one(), F::one()),
+                    |lc| lc,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enforces the big-endian `bits` to be less than the non-zero
+    /// `constant`, as at most `constant - 1`.
+    pub fn enforce_smaller_than<F, CS, B>(
+        cs: CS,
+        bits: &[Self],
+        constant: &B,
+    ) -> Result<(), SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+        B: BigInteger,
+    {
+        assert!(!constant.is_zero());
+
+        let mut constant = *constant;
+        constant.sub_noborrow(&B::from(1));
+
+        Self::enforce_smaller_or_equal_than(cs, bits, &constant)
+    }
+
     pub fn get_value(&self) -> Option<bool> {
         match *self {
             Boolean::Constant(c) => Some(c),
@@ -459,6 +549,173 @@ impl Boolean {
         }
     }
 
+    /// Perform AND over all the operands, which must be fewer than the
+    /// characteristic of the field. Constants fold, two operands cost the
+    /// constraint of `and`, and more cost 2 constraints whatever their
+    /// number: with `s` the number of false operands, `s * inv = 1 - r`
+    /// and `s * r = 0` force the result `r` to be whether `s` is zero.
+    pub fn kary_and<F, CS>(mut cs: CS, bits: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(!bits.is_empty());
+
+        if bits
+            .iter()
+            .any(|b| b.is_constant() && b.get_value() == Some(false))
+        {
+            return Ok(Boolean::Constant(false));
+        }
+        let bits = bits.iter().filter(|b| !b.is_constant()).collect::<Vec<_>>();
+
+        match bits.len() {
+            0 => return Ok(Boolean::Constant(true)),
+            1 => return Ok(*bits[0]),
+            2 => return Self::and(cs, bits[0], bits[1]),
+            _ => {}
+        }
+
+        let num_false = bits.iter().try_fold(F::zero(), |s, b| {
+            b.get_value().map(|v| if v { s } else { s + &F::one() })
+        });
+        let and_value = num_false.map(|s| s.is_zero());
+
+        let inv = cs.alloc(
+            || "inverse",
+            || {
+                let s = num_false.ok_or(SynthesisError::AssignmentMissing)?;
+                Ok(s.inverse().unwrap_or_else(F::zero))
+            },
+        )?;
+        let and = cs.alloc(
+            || "and",
+            || {
+                and_value
+                    .map(|v| if v { F::one() } else { F::zero() })
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+
+        let num_false_lc = || {
+            bits.iter().fold(LinearCombination::zero(), |lc, b| {
+                lc + &b.not().lc(CS::one(), F::one())
+            })
+        };
+        cs.enforce(
+            || "s * inv = 1 - and",
+            |_| num_false_lc(),
+            |lc| lc + inv,
+            |lc| lc + CS::one() - and,
+        );
+        cs.enforce(|| "s * and = 0", |_| num_false_lc(), |lc| lc + and, |lc| lc);
+
+        Ok(AllocatedBit {
+            value: and_value,
+            variable: and,
+        }
+        .into())
+    }
+
+    /// Perform OR over all the operands, as the negated AND of the negated
+    /// operands.
+    pub fn kary_or<F, CS>(cs: CS, bits: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let not_bits = bits.iter().map(|b| b.not()).collect::<Vec<_>>();
+
+        Ok(Self::kary_and(cs, &not_bits)?.not())
+    }
+
+    /// Perform NAND over all the operands, as the negated AND.
+    pub fn kary_nand<F, CS>(cs: CS, bits: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self::kary_and(cs, bits)?.not())
+    }
+
+    /// Perform XOR over all the operands, with the pairwise `xor` or with
+    /// `parity`, whichever costs fewer constraints.
+    pub fn xor_many<F, CS>(mut cs: CS, bits: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(!bits.is_empty());
+
+        let num_allocated = bits.iter().filter(|b| !b.is_constant()).count();
+        if num_allocated > num_sum_bits(num_allocated) + 2 {
+            return Self::parity(cs, bits);
+        }
+
+        let mut result = bits[0];
+        for (i, b) in bits.iter().enumerate().skip(1) {
+            result = Self::xor(cs.ns(|| format!("xor {}", i)), &result, b)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Whether an odd number of the operands are set, which must be fewer
+    /// than the characteristic of the field. The `n` bits of the number of
+    /// set operands are allocated and enforced to pack into their sum, the
+    /// lowest one is the parity: `n + 1` constraints for fewer than `2^n`
+    /// operands. Constants fold.
+    pub fn parity<F, CS>(mut cs: CS, bits: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let odd_constants = bits
+            .iter()
+            .filter(|b| b.is_constant())
+            .fold(false, |p, b| p ^ (b.get_value() == Some(true)));
+        let bits = bits.iter().filter(|b| !b.is_constant()).collect::<Vec<_>>();
+
+        let parity = match bits.len() {
+            0 => Boolean::Constant(false),
+            1 => *bits[0],
+            _ => {
+                let sum = bits
+                    .iter()
+                    .try_fold(0usize, |s, b| b.get_value().map(|v| s + v as usize));
+
+                let sum_bits = (0..num_sum_bits(bits.len()))
+                    .map(|i| {
+                        AllocatedBit::alloc(
+                            cs.ns(|| format!("sum bit {}", i)),
+                            sum.map(|s| (s >> i) & 1 == 1),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut packed = LinearCombination::zero();
+                let mut coeff = F::one();
+                for bit in sum_bits.iter() {
+                    packed = packed + (coeff, bit.get_variable());
+                    coeff.double_in_place();
+                }
+                cs.enforce(
+                    || "sum",
+                    |lc| {
+                        bits.iter()
+                            .fold(lc, |lc, b| lc + &b.lc(CS::one(), F::one()))
+                    },
+                    |lc| lc + CS::one(),
+                    |_| packed,
+                );
+
+                Boolean::from(sum_bits[0])
+            }
+        };
+
+        Ok(if odd_constants { parity.not() } else { parity })
+    }
+
     /// Computes (a and b) xor ((not a) and c)
     pub fn sha256_ch<'a, F, CS>(
         mut cs: CS,
@@ -513,8 +770,8 @@ impl Boolean {
                 // equals
                 // (a and b) xor (not a)
                 // equals
-                // not (a and (not b))
-                return Ok(Boolean::and(cs, &a, &b.not())?.not());
+                // (not a) or b
+                return Boolean::kary_or(cs, &[a.not(), *b]);
             }
             (a, &Boolean::Constant(true), c) => {
                 // If b is true
@@ -522,8 +779,8 @@ impl Boolean {
                 // equals
                 // a xor ((not a) and c)
                 // equals
-                // not ((not a) and (not c))
-                return Ok(Boolean::and(cs, &a.not(), &c.not())?.not());
+                // a or c
+                return Boolean::kary_or(cs, &[*a, *c]);
             }
             (&Boolean::Constant(true), _, _) => {
                 // If a is true
@@ -618,22 +875,26 @@ impl Boolean {
                 // equals
                 // (a and b) xor (a) xor (b)
                 // equals
-                // not ((not a) and (not b))
-                return Ok(Boolean::and(cs, &a.not(), &b.not())?.not());
+                // a or b
+                return Boolean::kary_or(cs, &[*a, *b]);
             }
             (a, &Boolean::Constant(true), c) => {
                 // If b is true,
                 // (a and b) xor (a and c) xor (b and c)
                 // equals
                 // (a) xor (a and c) xor (c)
-                return Ok(Boolean::and(cs, &a.not(), &c.not())?.not());
+                // equals
+                // a or c
+                return Boolean::kary_or(cs, &[*a, *c]);
             }
             (&Boolean::Constant(true), b, c) => {
                 // If a is true,
                 // (a and b) xor (a and c) xor (b and c)
                 // equals
                 // (b) xor (c) xor (b and c)
-                return Ok(Boolean::and(cs, &b.not(), &c.not())?.not());
+                // equals
+                // b or c
+                return Boolean::kary_or(cs, &[*b, *c]);
             }
             (&Boolean::Is(_), &Boolean::Is(_), &Boolean::Is(_))
             | (&Boolean::Is(_), &Boolean::Is(_), &Boolean::Not(_))
@@ -684,6 +945,11 @@ impl Boolean {
     }
 }
 
+/// The number of bits of `n`.
+fn num_sum_bits(n: usize) -> usize {
+    (0..).find(|i| n >> i == 0).unwrap()
+}
+
 impl From<AllocatedBit> for Boolean {
     fn from(b: AllocatedBit) -> Boolean {
         Boolean::Is(b)
@@ -773,11 +1039,12 @@ pub fn field_into_allocated_bits_le<F: PrimeField, CS: ConstraintSystem<F>>(
 #[cfg(test)]
 mod test {
     use curve::bn_256::Fr;
+    use math::BigInteger64;
     use num_traits::{One, Zero};
     use scheme::r1cs::ConstraintSystem;
 
-    use super::super::test_constraint_system::TestConstraintSystem;
     use super::{u64_into_boolean_vec_le, AllocatedBit, Boolean};
+    use crate::test_cs::TestConstraintSystem;
 
     #[test]
     fn test_allocated_bit() {
@@ -790,7 +1057,10 @@ mod test {
         assert!(cs.is_satisfied());
         cs.set("boolean", Fr::from(2u32));
         assert!(!cs.is_satisfied());
-        assert!(cs.which_is_unsatisfied() == Some("boolean constraint"));
+        assert_eq!(
+            cs.which_is_unsatisfied().as_deref(),
+            Some("boolean constraint")
+        );
     }
 
     #[test]
@@ -1814,4 +2084,208 @@ mod test {
             assert!(cs.is_satisfied());
         }
     }
+
+    /// Allocates the values as `Is` and `Not` booleans alternately.
+    fn alloc_booleans(cs: &mut TestConstraintSystem<Fr>, values: &[bool]) -> Vec<Boolean> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let negated = i % 2 == 1;
+                let bit = AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), Some(*v ^ negated));
+                let bit = Boolean::from(bit.unwrap());
+                if negated {
+                    bit.not()
+                } else {
+                    bit
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kary_and_or() {
+        // the cost of the AND of k operands.
+        let cost = |k: usize| match k {
+            1 => 0,
+            2 => 1,
+            _ => 2,
+        };
+
+        for k in 1..=6 {
+            for mask in 0..1u32 << k {
+                let values = (0..k).map(|i| (mask >> i) & 1 == 1).collect::<Vec<_>>();
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let bits = alloc_booleans(&mut cs, &values);
+
+                let and = Boolean::kary_and(cs.ns(|| "and"), &bits).unwrap();
+                assert_eq!(cs.num_constraints(), k + cost(k));
+                let or = Boolean::kary_or(cs.ns(|| "or"), &bits).unwrap();
+                let nand = Boolean::kary_nand(cs.ns(|| "nand"), &bits).unwrap();
+                assert_eq!(cs.num_constraints(), k + 3 * cost(k));
+                assert!(cs.is_satisfied());
+
+                assert_eq!(and.get_value(), Some(values.iter().all(|v| *v)));
+                assert_eq!(or.get_value(), Some(values.iter().any(|v| *v)));
+                assert_eq!(nand.get_value(), Some(!values.iter().all(|v| *v)));
+
+                // the other result does not pass.
+                if k > 2 {
+                    let flipped = if cs.get("and/and").is_zero() {
+                        Fr::one()
+                    } else {
+                        Fr::zero()
+                    };
+                    cs.set("and/and", flipped);
+                    assert!(!cs.is_satisfied());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_kary_and_constants() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let mut bits = alloc_booleans(&mut cs, &[true, true, true]);
+
+        // true operands are dropped.
+        bits.push(Boolean::constant(true));
+        let and = Boolean::kary_and(cs.ns(|| "and"), &bits).unwrap();
+        assert_eq!(and.get_value(), Some(true));
+        assert_eq!(cs.num_constraints(), 3 + 2);
+
+        // and a false one is the result.
+        bits.push(Boolean::constant(false));
+        let and = Boolean::kary_and(cs.ns(|| "and false"), &bits).unwrap();
+        let or = Boolean::kary_or(cs.ns(|| "or"), &[Boolean::constant(true), bits[0]]).unwrap();
+        assert!(and.is_constant() && or.is_constant());
+        assert_eq!((and.get_value(), or.get_value()), (Some(false), Some(true)));
+        assert_eq!(cs.num_constraints(), 3 + 2);
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_xor_many_parity() {
+        for k in 1..=9 {
+            // the bits of the sum of k operands.
+            let n = (0..).find(|i| k >> i == 0).unwrap();
+            let parity_cost = if k == 1 { 0 } else { n + 1 };
+
+            for mask in 0..1u32 << k {
+                let values = (0..k).map(|i| (mask >> i) & 1 == 1).collect::<Vec<_>>();
+                let expected = values.iter().fold(false, |p, v| p ^ v);
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let bits = alloc_booleans(&mut cs, &values);
+
+                let xor = Boolean::xor_many(cs.ns(|| "xor"), &bits).unwrap();
+                let xor_cost = cs.num_constraints() - k;
+                assert_eq!(xor_cost, parity_cost.min(k - 1));
+
+                let parity = Boolean::parity(cs.ns(|| "parity"), &bits).unwrap();
+                assert_eq!(cs.num_constraints() - k - xor_cost, parity_cost);
+
+                // constants fold.
+                let mut with_constant = bits.clone();
+                with_constant.push(Boolean::constant(true));
+                with_constant.push(Boolean::constant(false));
+                let negated = Boolean::parity(cs.ns(|| "constant"), &with_constant).unwrap();
+                assert!(cs.is_satisfied());
+
+                assert_eq!(xor.get_value(), Some(expected));
+                assert_eq!(parity.get_value(), Some(expected));
+                assert_eq!(negated.get_value(), Some(!expected));
+
+                // the other parity does not pass.
+                if k > 1 {
+                    let bit = "parity/sum bit 0/boolean";
+                    let flipped = if cs.get(bit).is_zero() {
+                        Fr::one()
+                    } else {
+                        Fr::zero()
+                    };
+                    cs.set(bit, flipped);
+                    assert!(!cs.is_satisfied());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_enforce_smaller_or_equal_than() {
+        // all ones, a single leading one, and various runs.
+        let constants = [
+            0b0u64,
+            0b1111_1111,
+            0b1000_0000,
+            0b1011_0011,
+            0b1010_1010,
+            0b1110_0001,
+            0b0001_1001,
+        ];
+
+        for &constant in constants.iter() {
+            for (name, strict) in [("smaller or equal", false), ("smaller", true)].iter() {
+                if *strict && constant == 0 {
+                    continue;
+                }
+
+                // 9 bits, one above the length of the constants.
+                for x in 0u64..512 {
+                    let mut cs = TestConstraintSystem::<Fr>::new();
+                    let bits = (0..9)
+                        .rev()
+                        .map(|i| {
+                            AllocatedBit::alloc(
+                                cs.ns(|| format!("bit {}", i)),
+                                Some((x >> i) & 1 == 1),
+                            )
+                            .unwrap()
+                            .into()
+                        })
+                        .collect::<Vec<Boolean>>();
+
+                    let c = BigInteger64::from(constant);
+                    if *strict {
+                        Boolean::enforce_smaller_than(cs.ns(|| *name), &bits, &c).unwrap();
+                    } else {
+                        Boolean::enforce_smaller_or_equal_than(cs.ns(|| *name), &bits, &c).unwrap();
+                    }
+
+                    let expected = if *strict { x < constant } else { x <= constant };
+                    assert_eq!(cs.is_satisfied(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_enforce_smaller_or_equal_than_constraints() {
+        let num_constraints = |constant: u64| {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let bits = (0..8)
+                .map(|i| {
+                    AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), Some(false))
+                        .unwrap()
+                        .into()
+                })
+                .collect::<Vec<Boolean>>();
+            let before = cs.num_constraints();
+
+            Boolean::enforce_smaller_or_equal_than(&mut cs, &bits, &BigInteger64::from(constant))
+                .unwrap();
+            assert!(cs.is_satisfied());
+            cs.num_constraints() - before
+        };
+
+        // no zero, nothing to enforce.
+        assert_eq!(num_constraints(0b1111_1111), 0);
+        // one per zero, the single one needs no AND.
+        assert_eq!(num_constraints(0b1000_0000), 7);
+        // one per zero, and the AND of each run but the last one.
+        assert_eq!(num_constraints(0b1100_1110), 3 + 1 + 2);
+        // the top bit above the constant is zero.
+        assert_eq!(num_constraints(0b0111_1111), 1);
+    }
 }
@@ -0,0 +1,47 @@
+//! The parameters generated once per field and shared by the hashes, as the
+//! generation of the default ones of Poseidon, Rescue or MiMC costs much
+//! more than a hash.
+
+use core::any::{Any, TypeId};
+use spin::Mutex;
+
+use crate::{Arc, Vec};
+
+type Entry = (TypeId, &'static str, Arc<dyn Any + Send + Sync>);
+
+static CACHE: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// The value of type `T` named `label`, generated by `init` on the first
+/// call. The lock is not held while generating, so `init` may use the cache
+/// and two threads may both generate a value, only the first one is kept.
+pub(crate) fn cached<T, I>(label: &'static str, init: I) -> Arc<T>
+where
+    T: Any + Send + Sync,
+    I: FnOnce() -> T,
+{
+    let find = |entries: &[Entry]| {
+        entries
+            .iter()
+            .find(|(id, l, _)| *id == TypeId::of::<T>() && *l == label)
+            .map(|(_, _, value)| {
+                value
+                    .clone()
+                    .downcast::<T>()
+                    .expect("the entry is of the type of its id")
+            })
+    };
+
+    if let Some(value) = find(&CACHE.lock()) {
+        return value;
+    }
+
+    let value = Arc::new(init());
+    let mut entries = CACHE.lock();
+    match find(&entries) {
+        Some(value) => value,
+        None => {
+            entries.push((TypeId::of::<T>(), label, value.clone()));
+            value
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Comparisons of field elements, as the integers of their canonical
+//! representations.
+//!
+//! The operands are bounded by `2^num_bits`, and `2^(num_bits + 1)` must be
+//! below the modulus so that every decomposition below is the only one of
+//! its number. Otherwise the difference of elements close to the modulus
+//! wraps around, e.g. `p - 1 < 0` would pass.
+use math::{BigInteger, PrimeField};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+
+/// Allocates the `num_bits` little-endian bits of `value`, enforced to pack
+/// into `lc`. This proves the number of `lc` is below `2^num_bits`.
+fn enforce_bits<F, CS>(
+    mut cs: CS,
+    lc: LinearCombination<F>,
+    value: Option<F>,
+    num_bits: usize,
+) -> Result<Vec<AllocatedBit>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert!(num_bits < F::size_in_bits() - 1);
+
+    let repr = value.map(|v| v.into_repr());
+    let bits = (0..num_bits)
+        .map(|i| AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), repr.map(|r| r.get_bit(i))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut packed = LinearCombination::zero();
+    let mut coeff = F::one();
+    for bit in bits.iter() {
+        packed = packed + (coeff, bit.get_variable());
+        coeff.double_in_place();
+    }
+    cs.enforce(|| "packing", |_| lc, |lc| lc + CS::one(), |_| packed);
+
+    Ok(bits)
+}
+
+/// `2^num_bits`
+fn two_to_the<F: PrimeField>(num_bits: usize) -> F {
+    let mut r = F::one();
+    for _ in 0..num_bits {
+        r.double_in_place();
+    }
+    r
+}
+
+/// Enforces `a < 2^num_bits`.
+pub fn enforce_range<F, CS>(
+    cs: CS,
+    a: &AllocatedFr<F>,
+    num_bits: usize,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let lc = LinearCombination::zero() + a.get_variable();
+    enforce_bits(cs, lc, a.get_value(), num_bits)?;
+
+    Ok(())
+}
+
+/// Enforces `a < b`, where both are already known to be below
+/// `2^num_bits`. It decomposes `b - a - 1` into `num_bits` bits.
+pub fn enforce_less_than_unchecked<F, CS>(
+    cs: CS,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+    num_bits: usize,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let value = match (a.get_value(), b.get_value()) {
+        (Some(a), Some(b)) => Some(b - &a - &F::one()),
+        _ => None,
+    };
+    let lc = LinearCombination::zero() + b.get_variable() - a.get_variable() - CS::one();
+    enforce_bits(cs, lc, value, num_bits)?;
+
+    Ok(())
+}
+
+/// Enforces `a < b < 2^(num_bits + 1)` with `a < 2^num_bits`. `b` needs no
+/// range check of its own, it is `a + 1` plus a number below `2^num_bits`.
+pub fn enforce_less_than<F, CS>(
+    mut cs: CS,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+    num_bits: usize,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    enforce_range(cs.ns(|| "a range"), a, num_bits)?;
+    enforce_less_than_unchecked(cs.ns(|| "less than"), a, b, num_bits)
+}
+
+/// Whether `a < b`, where both are already known to be below
+/// `2^num_bits`. It decomposes `a - b + 2^num_bits` into `num_bits + 1`
+/// bits, whose top one is set exactly when `a >= b`.
+pub fn is_less_than_unchecked<F, CS>(
+    cs: CS,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+    num_bits: usize,
+) -> Result<Boolean, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let shift = two_to_the::<F>(num_bits);
+    let value = match (a.get_value(), b.get_value()) {
+        (Some(a), Some(b)) => Some(a - &b + &shift),
+        _ => None,
+    };
+    let lc = LinearCombination::zero() + a.get_variable() - b.get_variable() + (shift, CS::one());
+    let mut bits = enforce_bits(cs, lc, value, num_bits + 1)?;
+
+    Ok(Boolean::from(bits.pop().unwrap()).not())
+}
+
+/// Whether `a < b`, enforcing both are below `2^num_bits`.
+pub fn is_less_than<F, CS>(
+    mut cs: CS,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+    num_bits: usize,
+) -> Result<Boolean, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    enforce_range(cs.ns(|| "a range"), a, num_bits)?;
+    enforce_range(cs.ns(|| "b range"), b, num_bits)?;
+    is_less_than_unchecked(cs.ns(|| "less than"), a, b, num_bits)
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, One, PrimeField, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::fr::AllocatedFr;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    const NUM_BITS: usize = 64;
+
+    fn alloc_pair(
+        cs: &mut TestConstraintSystem<Fr>,
+        a: Fr,
+        b: Fr,
+    ) -> (AllocatedFr<Fr>, AllocatedFr<Fr>) {
+        let a = AllocatedFr::alloc(cs.ns(|| "a"), || Ok(a)).unwrap();
+        let b = AllocatedFr::alloc(cs.ns(|| "b"), || Ok(b)).unwrap();
+        (a, b)
+    }
+
+    fn check(a: Fr, b: Fr) -> (bool, Option<bool>) {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let (a_var, b_var) = alloc_pair(&mut cs, a, b);
+        enforce_less_than(cs.ns(|| "enforce"), &a_var, &b_var, NUM_BITS).unwrap();
+        let enforced = cs.is_satisfied();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let (a_var, b_var) = alloc_pair(&mut cs, a, b);
+        let lt = is_less_than(cs.ns(|| "is"), &a_var, &b_var, NUM_BITS).unwrap();
+        let lt = if cs.is_satisfied() {
+            lt.get_value()
+        } else {
+            None
+        };
+
+        (enforced, lt)
+    }
+
+    #[test]
+    fn test_less_than() {
+        let rng = &mut test_rng();
+
+        for _ in 0..100 {
+            let (a, b): (u64, u64) = (rng.gen(), rng.gen());
+            assert_eq!(check(Fr::from(a), Fr::from(b)), (a < b, Some(a < b)));
+        }
+
+        let max = Fr::from(u64::max_value());
+
+        // equal values
+        assert_eq!(check(Fr::from(7u64), Fr::from(7u64)), (false, Some(false)));
+        assert_eq!(check(Fr::zero(), Fr::zero()), (false, Some(false)));
+        assert_eq!(check(max, max), (false, Some(false)));
+
+        // adjacent values
+        assert_eq!(check(Fr::from(7u64), Fr::from(8u64)), (true, Some(true)));
+        assert_eq!(check(Fr::from(8u64), Fr::from(7u64)), (false, Some(false)));
+        assert_eq!(check(Fr::zero(), Fr::one()), (true, Some(true)));
+        assert_eq!(check(max - &Fr::one(), max), (true, Some(true)));
+        assert_eq!(check(max, max - &Fr::one()), (false, Some(false)));
+    }
+
+    #[test]
+    fn test_less_than_wrap_around() {
+        let minus_one = Fr::zero() - &Fr::one();
+        let half = Fr::from_repr(Fr::modulus_minus_one_div_two());
+
+        // p - 1 is not below 0, nor below anything else, though
+        // 0 - (p - 1) - 1 = 0 fits in any number of bits.
+        assert_eq!(check(minus_one, Fr::zero()), (false, None));
+        assert_eq!(check(minus_one, Fr::from(5u64)), (false, None));
+        assert_eq!(check(half, half + &Fr::one()), (false, None));
+        // the larger operand of `is_less_than` is out of range as well.
+        assert_eq!(check(Fr::from(5u64), minus_one).1, None);
+
+        // the unchecked variants trust the bounds, and are fooled.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let (a, b) = alloc_pair(&mut cs, minus_one, Fr::zero());
+        enforce_less_than_unchecked(cs.ns(|| "enforce"), &a, &b, NUM_BITS).unwrap();
+        let lt = is_less_than_unchecked(cs.ns(|| "is"), &a, &b, NUM_BITS).unwrap();
+        assert!(cs.is_satisfied());
+        assert_eq!(lt.get_value(), Some(true));
+    }
+
+    #[test]
+    fn test_less_than_cost() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let (a, b) = alloc_pair(&mut cs, Fr::from(1u64), Fr::from(2u64));
+
+        enforce_less_than_unchecked(cs.ns(|| "enforce unchecked"), &a, &b, NUM_BITS).unwrap();
+        assert_eq!(cs.num_constraints(), NUM_BITS + 1);
+
+        is_less_than_unchecked(cs.ns(|| "is unchecked"), &a, &b, NUM_BITS).unwrap();
+        assert_eq!(cs.num_constraints(), 2 * NUM_BITS + 3);
+
+        enforce_less_than(cs.ns(|| "enforce"), &a, &b, NUM_BITS).unwrap();
+        is_less_than(cs.ns(|| "is"), &a, &b, NUM_BITS).unwrap();
+        assert_eq!(
+            cs.num_constraints(),
+            2 * NUM_BITS + 3 + 2 * (NUM_BITS + 1) + 3 * (NUM_BITS + 1) + 1
+        );
+        assert!(cs.is_satisfied());
+    }
+}
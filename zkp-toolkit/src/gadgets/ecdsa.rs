@@ -0,0 +1,672 @@
+//! ECDSA verification over a short Weierstrass curve of prime order,
+//! secp256k1 of Bitcoin and Ethereum above all, in the circuits of another
+//! field, of the arithmetic of `nonnative` modulo the base field `p` and the
+//! scalar field `n` of the curve.
+//!
+//! `u1 * G + u2 * Q` is computed by Shamir's trick, a doubling and an
+//! addition of one of `D`, `D + G`, `D + Q` and `D + G + Q` a bit of the
+//! scalars from `2 * D`, of a point `D` of no known discrete logarithm, so
+//! that the incomplete affine formulas never meet the identity; the multiple
+//! of `D` is subtracted again at the end. The formulas enforce the points they add to differ,
+//! a prover of a pair of them which do not fails to prove.
+
+use core::marker::PhantomData;
+
+use math::{
+    curves::models::{short_weierstrass_jacobian::GroupAffine, SWModelParameters},
+    AffineCurve, Field, One, PrimeField, ProjectiveCurve, Zero,
+};
+use num_bigint::BigUint;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::{Rc, Vec};
+
+use super::boolean::{AllocatedBit, Boolean};
+use super::nonnative::{
+    biguint_to_field, field_to_biguint, NonNativeFieldParams, NonNativeFieldVar,
+};
+
+/// A point of the curve `P` other than the identity, of coordinates modulo
+/// the base field `p`.
+pub struct AffinePointVar<F: PrimeField, P: SWModelParameters> {
+    x: NonNativeFieldVar<F>,
+    y: NonNativeFieldVar<F>,
+    _curve: PhantomData<P>,
+}
+
+impl<F: PrimeField, P: SWModelParameters> Clone for AffinePointVar<F, P> {
+    fn clone(&self) -> Self {
+        AffinePointVar {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<F, P> AffinePointVar<F, P>
+where
+    F: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+{
+    /// The constant `point`, of coordinates modulo the modulus of `params`.
+    pub fn constant(params: &Rc<NonNativeFieldParams>, point: &GroupAffine<P>) -> Self {
+        assert!(!point.infinity, "the identity has no affine coordinates");
+        check_base_field::<P>(params);
+
+        AffinePointVar {
+            x: NonNativeFieldVar::constant(params, &field_to_biguint(&point.x)),
+            y: NonNativeFieldVar::constant(params, &field_to_biguint(&point.y)),
+            _curve: PhantomData,
+        }
+    }
+
+    /// Allocates `point`, enforced to be on the curve. It is not enforced to
+    /// be in the subgroup of prime order, of curves with a cofactor.
+    pub fn alloc<CS>(
+        cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        point: Option<&GroupAffine<P>>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::alloc_coordinates(cs, params, point, false)
+    }
+
+    /// Allocates `point` of coordinates which are public inputs, enforced to
+    /// be on the curve.
+    pub fn alloc_input<CS>(
+        cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        point: Option<&GroupAffine<P>>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Self::alloc_coordinates(cs, params, point, true)
+    }
+
+    fn alloc_coordinates<CS>(
+        mut cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        point: Option<&GroupAffine<P>>,
+        input: bool,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        check_base_field::<P>(params);
+        if point.map_or(false, |p| p.infinity) {
+            return Err(SynthesisError::UnexpectedIdentity);
+        }
+
+        let x = point.map(|p| field_to_biguint(&p.x));
+        let y = point.map(|p| field_to_biguint(&p.y));
+        let (x, y) = if input {
+            (
+                NonNativeFieldVar::alloc_input(cs.ns(|| "x"), params, x.as_ref())?,
+                NonNativeFieldVar::alloc_input(cs.ns(|| "y"), params, y.as_ref())?,
+            )
+        } else {
+            (
+                NonNativeFieldVar::alloc(cs.ns(|| "x"), params, x.as_ref())?,
+                NonNativeFieldVar::alloc(cs.ns(|| "y"), params, y.as_ref())?,
+            )
+        };
+
+        // y^2 = x^3 + a * x + b
+        let mut rhs = x
+            .square(cs.ns(|| "x^2"))?
+            .mul_without_reduce(cs.ns(|| "x^3"), &x)?
+            .add(&constant_of::<F, P>(params, &P::COEFF_B));
+        if !P::COEFF_A.is_zero() {
+            rhs = rhs.add(&x.mul_without_reduce(
+                cs.ns(|| "a * x"),
+                &constant_of::<F, P>(params, &P::COEFF_A),
+            )?);
+        }
+        y.mul_without_reduce(cs.ns(|| "y^2"), &y)?
+            .enforce_equal(cs.ns(|| "on curve"), &rhs)?;
+
+        Ok(AffinePointVar {
+            x,
+            y,
+            _curve: PhantomData,
+        })
+    }
+
+    pub fn x(&self) -> &NonNativeFieldVar<F> {
+        &self.x
+    }
+
+    pub fn y(&self) -> &NonNativeFieldVar<F> {
+        &self.y
+    }
+
+    pub fn get_value(&self) -> Option<GroupAffine<P>> {
+        match (self.x.get_value(), self.y.get_value()) {
+            (Some(x), Some(y)) => Some(GroupAffine::new(
+                biguint_to_field(&x),
+                biguint_to_field(&y),
+                false,
+            )),
+            _ => None,
+        }
+    }
+
+    /// `self + other` of the incomplete formulas, enforcing the points to
+    /// have different `x`, not to be equal or opposite.
+    pub fn add<CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let params = self.x.params();
+        let lambda = match (self.get_value(), other.get_value()) {
+            (Some(a), Some(b)) => Some(chord(&a, &b)?),
+            _ => None,
+        };
+
+        let dx = other.x.sub(&self.x);
+        dx.inverse(cs.ns(|| "x distinct"))?;
+
+        // lambda * (x2 - x1) = y2 - y1
+        let lambda = alloc_base::<F, P, _>(cs.ns(|| "lambda"), params, lambda)?;
+        lambda
+            .mul_without_reduce(cs.ns(|| "lambda * dx"), &dx)?
+            .enforce_equal(cs.ns(|| "lambda check"), &other.y.sub(&self.y))?;
+
+        let x = lambda
+            .mul_without_reduce(cs.ns(|| "lambda^2"), &lambda)?
+            .sub(&self.x)
+            .sub(&other.x)
+            .reduce(cs.ns(|| "x"))?;
+        let y = lambda
+            .mul_without_reduce(cs.ns(|| "lambda * (x1 - x3)"), &self.x.sub(&x))?
+            .sub(&self.y)
+            .reduce(cs.ns(|| "y"))?;
+
+        Ok(AffinePointVar {
+            x,
+            y,
+            _curve: PhantomData,
+        })
+    }
+
+    /// `2 * self` of the tangent. The point is not enforced to have a `y`
+    /// other than zero, which a point of a curve of odd order has.
+    pub fn double<CS>(&self, mut cs: CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let params = self.x.params();
+        let lambda = match self.get_value() {
+            Some(a) => {
+                let dy =
+                    a.y.double()
+                        .inverse()
+                        .ok_or(SynthesisError::DivisionByZero)?;
+                let x2 = a.x.square();
+                Some((x2.double() + &x2 + &P::COEFF_A) * &dy)
+            }
+            None => None,
+        };
+
+        // lambda * 2y = 3x^2 + a
+        let lambda = alloc_base::<F, P, _>(cs.ns(|| "lambda"), params, lambda)?;
+        let rhs = self
+            .x
+            .mul_without_reduce(cs.ns(|| "x^2"), &self.x)?
+            .scale(3)
+            .add(&constant_of::<F, P>(params, &P::COEFF_A));
+        lambda
+            .mul_without_reduce(cs.ns(|| "lambda * 2y"), &self.y.scale(2))?
+            .enforce_equal(cs.ns(|| "lambda check"), &rhs)?;
+
+        let x = lambda
+            .mul_without_reduce(cs.ns(|| "lambda^2"), &lambda)?
+            .sub(&self.x.scale(2))
+            .reduce(cs.ns(|| "x"))?;
+        let y = lambda
+            .mul_without_reduce(cs.ns(|| "lambda * (x1 - x3)"), &self.x.sub(&x))?
+            .sub(&self.y)
+            .reduce(cs.ns(|| "y"))?;
+
+        Ok(AffinePointVar {
+            x,
+            y,
+            _curve: PhantomData,
+        })
+    }
+
+    /// `2 * self + other`, as `(self + other) + self` of the incomplete
+    /// formulas of two slopes and no `y` of `self + other`, enforcing the
+    /// points to have different `x`.
+    ///
+    /// `self + other` and `self` are not enforced to differ: of the same `x`
+    /// the second slope is unsatisfiable but for a `y` of zero, a point of
+    /// order two, which a curve of odd order has none of.
+    fn double_and_add<CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let params = self.x.params();
+        let (lambda1, lambda2) = match (self.get_value(), other.get_value()) {
+            (Some(a), Some(t)) => {
+                let lambda1 = chord(&a, &t)?;
+                let x3 = lambda1.square() - &a.x - &t.x;
+                let dx = (x3 - &a.x)
+                    .inverse()
+                    .ok_or(SynthesisError::DivisionByZero)?;
+                let lambda2 = -lambda1 - &(a.y.double() * &dx);
+                (Some(lambda1), Some(lambda2))
+            }
+            _ => (None, None),
+        };
+
+        let dx = other.x.sub(&self.x);
+        dx.inverse(cs.ns(|| "x distinct"))?;
+
+        // lambda1 * (xt - xa) = yt - ya
+        let lambda1 = alloc_base::<F, P, _>(cs.ns(|| "lambda1"), params, lambda1)?;
+        lambda1
+            .mul_without_reduce(cs.ns(|| "lambda1 * dx"), &dx)?
+            .enforce_equal(cs.ns(|| "lambda1 check"), &other.y.sub(&self.y))?;
+        let x3 = lambda1
+            .mul_without_reduce(cs.ns(|| "lambda1^2"), &lambda1)?
+            .sub(&self.x)
+            .sub(&other.x)
+            .reduce(cs.ns(|| "x3"))?;
+
+        // (lambda1 + lambda2) * (x3 - xa) = -2 * ya
+        let lambda2 = alloc_base::<F, P, _>(cs.ns(|| "lambda2"), params, lambda2)?;
+        lambda1
+            .add(&lambda2)
+            .mul_without_reduce(cs.ns(|| "lambda2 * dx"), &x3.sub(&self.x))?
+            .enforce_equal(cs.ns(|| "lambda2 check"), &self.y.scale(2).neg())?;
+
+        let x = lambda2
+            .mul_without_reduce(cs.ns(|| "lambda2^2"), &lambda2)?
+            .sub(&self.x)
+            .sub(&x3)
+            .reduce(cs.ns(|| "x"))?;
+        let y = lambda2
+            .mul_without_reduce(cs.ns(|| "lambda2 * (xa - x)"), &self.x.sub(&x))?
+            .sub(&self.y)
+            .reduce(cs.ns(|| "y"))?;
+
+        Ok(AffinePointVar {
+            x,
+            y,
+            _curve: PhantomData,
+        })
+    }
+
+    /// Returns `a` if `cond` is true, and `b` otherwise.
+    pub fn conditionally_select<CS>(
+        mut cs: CS,
+        cond: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        Ok(AffinePointVar {
+            x: NonNativeFieldVar::conditionally_select(cs.ns(|| "x"), cond, &a.x, &b.x)?,
+            y: NonNativeFieldVar::conditionally_select(cs.ns(|| "y"), cond, &a.y, &b.y)?,
+            _curve: PhantomData,
+        })
+    }
+}
+
+fn check_base_field<P>(params: &NonNativeFieldParams)
+where
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+{
+    assert_eq!(
+        params.modulus(),
+        NonNativeFieldParams::of_field::<P::BaseField>(params.limb_width()).modulus(),
+        "the coordinates are modulo the base field"
+    );
+}
+
+fn constant_of<F, P>(
+    params: &Rc<NonNativeFieldParams>,
+    value: &P::BaseField,
+) -> NonNativeFieldVar<F>
+where
+    F: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+{
+    NonNativeFieldVar::constant(params, &field_to_biguint(value))
+}
+
+fn alloc_base<F, P, CS>(
+    cs: CS,
+    params: &Rc<NonNativeFieldParams>,
+    value: Option<P::BaseField>,
+) -> Result<NonNativeFieldVar<F>, SynthesisError>
+where
+    F: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    NonNativeFieldVar::alloc(cs, params, value.map(|v| field_to_biguint(&v)).as_ref())
+}
+
+/// The slope of the chord of `a` and `b`.
+fn chord<P>(a: &GroupAffine<P>, b: &GroupAffine<P>) -> Result<P::BaseField, SynthesisError>
+where
+    P: SWModelParameters,
+{
+    let dx = (b.x - &a.x)
+        .inverse()
+        .ok_or(SynthesisError::DivisionByZero)?;
+    Ok((b.y - &a.y) * &dx)
+}
+
+/// The offset point `D`, the point of the smallest `x` of the subgroup of
+/// prime order.
+pub fn offset_point<P: SWModelParameters>() -> GroupAffine<P> {
+    let mut x = P::BaseField::one();
+    loop {
+        if let Some(point) = GroupAffine::<P>::get_point_from_x(x, false) {
+            let point = point.scale_by_cofactor().into_affine();
+            if !point.is_zero() {
+                return point;
+            }
+        }
+        x += &P::BaseField::one();
+    }
+}
+
+/// Allocates the `n` little-endian bits of `value`, of the scalar field of
+/// `n` bits.
+fn alloc_scalar_bits<F, CS>(
+    mut cs: CS,
+    num_bits: usize,
+    value: Option<&BigUint>,
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    (0..num_bits)
+        .map(|i| {
+            AllocatedBit::alloc(
+                cs.ns(|| format!("bit {}", i)),
+                value.map(|v| v.bit(i as u64)),
+            )
+            .map(Boolean::from)
+        })
+        .collect()
+}
+
+/// Enforces `(r, s)` to be an ECDSA signature of the message of the hash
+/// `message` by the `public_key`, of `message`, `r` and `s` modulo the
+/// scalar field `n` of the curve, of the same limb width as the points.
+///
+/// `r` and `s` are enforced to be in `[1, n - 1]`, and the `x` of
+/// `u1 * G + u2 * Q` to equal `r` modulo `n`, of `u1 = message / s` and
+/// `u2 = r / s`. `message` is the leftmost bits of the hash, as many as `n`
+/// has, e.g. all the 256 bits of SHA-256 for secp256k1; it need not be
+/// reduced.
+pub fn enforce_verify<F, P, CS>(
+    mut cs: CS,
+    public_key: &AffinePointVar<F, P>,
+    message: &NonNativeFieldVar<F>,
+    r: &NonNativeFieldVar<F>,
+    s: &NonNativeFieldVar<F>,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    P: SWModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let params_p = public_key.x.params();
+    let params_n = r.params();
+    assert_eq!(
+        params_n.modulus(),
+        NonNativeFieldParams::of_field::<P::ScalarField>(params_n.limb_width()).modulus(),
+        "the signature is modulo the scalar field"
+    );
+
+    r.enforce_canonical(cs.ns(|| "r canonical"))?;
+    s.enforce_canonical(cs.ns(|| "s canonical"))?;
+    // s is not zero either, of u2 * s = r
+    r.inverse(cs.ns(|| "r not zero"))?;
+
+    let (u1, u2) = match (message.get_value(), r.get_value(), s.get_value()) {
+        (Some(z), Some(r), Some(s)) => {
+            let s_inverse = biguint_to_field::<P::ScalarField>(&s)
+                .inverse()
+                .ok_or(SynthesisError::DivisionByZero)?;
+            let u1 = biguint_to_field::<P::ScalarField>(&z) * &s_inverse;
+            let u2 = biguint_to_field::<P::ScalarField>(&r) * &s_inverse;
+            (Some(field_to_biguint(&u1)), Some(field_to_biguint(&u2)))
+        }
+        _ => (None, None),
+    };
+
+    // u1 and u2 of their bits, not necessarily below n: the points they
+    // multiply are of order n.
+    let num_bits = params_n.modulus().bits() as usize;
+    let u1_bits = alloc_scalar_bits(cs.ns(|| "u1"), num_bits, u1.as_ref())?;
+    let u2_bits = alloc_scalar_bits(cs.ns(|| "u2"), num_bits, u2.as_ref())?;
+    NonNativeFieldVar::from_bits_le::<CS>(params_n, &u1_bits)
+        .mul_without_reduce(cs.ns(|| "u1 * s"), s)?
+        .enforce_equal(cs.ns(|| "u1 * s = message"), message)?;
+    NonNativeFieldVar::from_bits_le::<CS>(params_n, &u2_bits)
+        .mul_without_reduce(cs.ns(|| "u2 * s"), s)?
+        .enforce_equal(cs.ns(|| "u2 * s = r"), r)?;
+
+    let d = offset_point::<P>();
+    let dg = d + GroupAffine::<P>::prime_subgroup_generator();
+    let d_var = AffinePointVar::constant(params_p, &d);
+    let dg_var = AffinePointVar::constant(params_p, &dg);
+    let dq_var = d_var.add(cs.ns(|| "D + Q"), public_key)?;
+    let dgq_var = dg_var.add(cs.ns(|| "D + G + Q"), public_key)?;
+
+    // A = 2 * A + D + u1_i * G + u2_i * Q from the top bit, of A = 2 * D
+    // first, not to add D to itself
+    let mut acc = AffinePointVar::constant(params_p, &(d + d));
+    for i in (0..num_bits).rev() {
+        let mut cs = cs.ns(|| format!("bit {}", i));
+
+        let without_q = AffinePointVar::conditionally_select(
+            cs.ns(|| "select G"),
+            &u1_bits[i],
+            &dg_var,
+            &d_var,
+        )?;
+        let with_q = AffinePointVar::conditionally_select(
+            cs.ns(|| "select G + Q"),
+            &u1_bits[i],
+            &dgq_var,
+            &dq_var,
+        )?;
+        let table = AffinePointVar::conditionally_select(
+            cs.ns(|| "select Q"),
+            &u2_bits[i],
+            &with_q,
+            &without_q,
+        )?;
+
+        acc = acc.double_and_add(cs.ns(|| "double and add"), &table)?;
+    }
+
+    // R = A - C, of C = (2^bits * 2 + 2^bits - 1) * D
+    let c = d
+        .mul(
+            (P::ScalarField::from(2u64).pow(&[num_bits as u64]) * &P::ScalarField::from(3u64)
+                - &P::ScalarField::one())
+                .into_repr(),
+        )
+        .into_affine();
+    let point = acc.add(cs.ns(|| "R"), &AffinePointVar::constant(params_p, &-c))?;
+
+    // x(R) < p, equal to r modulo n
+    point.x.enforce_canonical(cs.ns(|| "x(R) canonical"))?;
+    point
+        .x
+        .with_modulus(params_n)
+        .enforce_equal(cs.ns(|| "x(R) = r"), r)
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod test {
+    use curve::bn_256::Fr;
+    use curve::secp256k1::{Fq, Fr as Scalar, Secp256k1Affine, Secp256k1Parameters};
+    use math::{test_rng, UniformRand};
+    use scheme::r1cs::ConstraintSynthesizer;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    type PointVar = AffinePointVar<Fr, Secp256k1Parameters>;
+
+    const LIMB_WIDTH: usize = 64;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    /// A signature of "ckb-zkp ecdsa gadget" of SHA-256, by OpenSSL.
+    fn vector() -> (Secp256k1Affine, BigUint, BigUint, BigUint) {
+        let public_key = Secp256k1Affine::new(
+            biguint_to_field(&hex(
+                "a84c77ed8534b8001a5421ab9417071cb39a203978a395084ffddcb78525e111",
+            )),
+            biguint_to_field(&hex(
+                "f183564b56400b3d17014e70e00ed31a052e827d921a4b70f945991449e362c9",
+            )),
+            false,
+        );
+        let hash = hex("f8fb9c6c612e2f8d318da6b3e45ee42dfbe1aad5a19498847086ff85117d2ae4");
+        let r = hex("8c2b8e985c4954b45901e6e5d78be5409fd2cfbfd9c9400806ae91fe9224ad1a");
+        let s = hex("0a0bbfdf02405da5596a4a8777ad7f80ff14835a1080a329b38a9d5332349db4");
+        (public_key, hash, r, s)
+    }
+
+    struct EcdsaCircuit {
+        public_key: Option<Secp256k1Affine>,
+        hash: Option<BigUint>,
+        r: Option<BigUint>,
+        s: Option<BigUint>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for EcdsaCircuit {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let params_p = Rc::new(NonNativeFieldParams::of_field::<Fq>(LIMB_WIDTH));
+            let params_n = Rc::new(NonNativeFieldParams::of_field::<Scalar>(LIMB_WIDTH));
+
+            let public_key =
+                PointVar::alloc(cs.ns(|| "public key"), &params_p, self.public_key.as_ref())?;
+            let hash = NonNativeFieldVar::alloc(cs.ns(|| "hash"), &params_n, self.hash.as_ref())?;
+            let r = NonNativeFieldVar::alloc(cs.ns(|| "r"), &params_n, self.r.as_ref())?;
+            let s = NonNativeFieldVar::alloc(cs.ns(|| "s"), &params_n, self.s.as_ref())?;
+
+            enforce_verify(cs.ns(|| "verify"), &public_key, &hash, &r, &s)
+        }
+    }
+
+    fn circuit(
+        public_key: &Secp256k1Affine,
+        hash: &BigUint,
+        r: &BigUint,
+        s: &BigUint,
+    ) -> EcdsaCircuit {
+        EcdsaCircuit {
+            public_key: Some(*public_key),
+            hash: Some(hash.clone()),
+            r: Some(r.clone()),
+            s: Some(s.clone()),
+        }
+    }
+
+    fn is_satisfied(circuit: EcdsaCircuit) -> bool {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        circuit.generate_constraints(&mut cs).unwrap();
+        cs.is_satisfied()
+    }
+
+    #[test]
+    fn verify_openssl_signature() {
+        let (public_key, hash, r, s) = vector();
+        assert!(is_satisfied(circuit(&public_key, &hash, &r, &s)));
+
+        // another message
+        let other = &hash + 1u32;
+        assert!(!is_satisfied(circuit(&public_key, &other, &r, &s)));
+
+        // the malleated signature of n - s is valid too
+        let n = field_to_biguint(&-Scalar::one()) + 1u32;
+        assert!(is_satisfied(circuit(&public_key, &hash, &r, &(&n - &s))));
+
+        // another key
+        assert!(!is_satisfied(circuit(&-public_key, &hash, &r, &s)));
+    }
+
+    #[test]
+    fn verify_constraints() {
+        let (public_key, hash, r, s) = vector();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        circuit(&public_key, &hash, &r, &s)
+            .generate_constraints(&mut cs)
+            .unwrap();
+        let constraints = cs.num_constraints();
+        assert_eq!(constraints, 1161904);
+    }
+
+    #[test]
+    fn add_points() {
+        let rng = &mut test_rng();
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(LIMB_WIDTH));
+        let g = Secp256k1Affine::prime_subgroup_generator();
+        let a = g.mul(Scalar::rand(rng)).into_affine();
+        let b = g.mul(Scalar::rand(rng)).into_affine();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a_var = PointVar::alloc(cs.ns(|| "a"), &params, Some(&a)).unwrap();
+        let b_var = PointVar::alloc(cs.ns(|| "b"), &params, Some(&b)).unwrap();
+        let sum = a_var.add(cs.ns(|| "a + b"), &b_var).unwrap();
+        assert_eq!(sum.get_value().unwrap(), a + b);
+        let double = a_var.double(cs.ns(|| "2a")).unwrap();
+        assert_eq!(double.get_value().unwrap(), a + a);
+        let result = a_var.double_and_add(cs.ns(|| "2a + b"), &b_var).unwrap();
+        assert_eq!(result.get_value().unwrap(), a + a + b);
+        assert!(cs.is_satisfied());
+
+        // a point of the same x
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a_var = PointVar::alloc(cs.ns(|| "a"), &params, Some(&a)).unwrap();
+        let minus_a = PointVar::alloc(cs.ns(|| "-a"), &params, Some(&-a)).unwrap();
+        assert!(matches!(
+            a_var.add(cs.ns(|| "a - a"), &minus_a),
+            Err(SynthesisError::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn point_not_on_curve() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(LIMB_WIDTH));
+        let g = Secp256k1Affine::prime_subgroup_generator();
+        let point = Secp256k1Affine::new(g.x, g.y + &Fq::one(), false);
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        PointVar::alloc(cs.ns(|| "point"), &params, Some(&point)).unwrap();
+        assert_eq!(
+            cs.which_is_unsatisfied(),
+            Some("point/on curve/carry/group 0/carry".into())
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Circuits for the ElGamal encryption over a twisted Edwards curve embedded
+//! in the circuit field, as the Pedersen gadgets (baby_jubjub for bn_256,
+//! jubjub for bls12_381).
+//!
+//! The message `m` is encoded in the exponent: the encryption of `m` under
+//! the public key `PK = sk * G` with the randomness `r` is the ciphertext
+//! `(C1, C2) = (r * G, m * G + r * PK)`. The decryption recovers
+//! `m * G = C2 - sk * C1`, and then `m` by a search, so the messages should
+//! be small, e.g. the amounts of an auditable payment.
+//!
+//! The generator `G` is derived by hashing to the curve, as the generators
+//! of the Pedersen hash. The scalars are the little-endian bits of `sk`,
+//! `m` and `r`, reduced modulo the order of the prime subgroup, so the
+//! randomness should have as many bits as this order.
+
+use math::{
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine},
+    One, PrimeField, Zero,
+};
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use super::boolean::Boolean;
+use super::fr::AllocatedFr;
+use super::pedersen::{
+    add, fixed_base_tables, hash_to_curve, num_chunks, windowed_sum, windowed_sum_enforce,
+    AllocatedPoint, WINDOW,
+};
+
+/// The points `(C1, C2)` of an allocated ciphertext.
+pub type AllocatedCiphertext<F> = (AllocatedPoint<F>, AllocatedPoint<F>);
+
+/// The personalization of the hash to the curve deriving the generator.
+pub const PERSONALIZATION: &[u8] = b"zkp-toolkit_EG";
+
+/// The generator `G` of the keys and of the encoding of the messages.
+pub fn generator<P: TEModelParameters>() -> GroupAffine<P> {
+    hash_to_curve(PERSONALIZATION, 0)
+}
+
+/// The scalar multiple of `base` by the little-endian `bits`.
+fn mul<P: TEModelParameters>(base: GroupAffine<P>, bits: &[bool]) -> GroupAffine<P> {
+    let tables = fixed_base_tables(base, num_chunks(bits.len(), WINDOW), WINDOW);
+    windowed_sum(bits, &tables, WINDOW)
+}
+
+/// The public key `sk * G` of the little-endian bits of the secret key.
+pub fn public_key<P: TEModelParameters>(secret: &[bool]) -> GroupAffine<P> {
+    mul(generator::<P>(), secret)
+}
+
+/// ElGamal encryption `(r * G, m * G + r * PK)` of the little-endian bits of
+/// the message `m` with the ones of the randomness `r`.
+pub fn encryption<P: TEModelParameters>(
+    pk: &GroupAffine<P>,
+    message: &[bool],
+    randomness: &[bool],
+) -> (GroupAffine<P>, GroupAffine<P>) {
+    let c1 = mul(generator::<P>(), randomness);
+    let mut c2 = mul(generator::<P>(), message);
+    c2 += &mul(*pk, randomness);
+
+    (c1, c2)
+}
+
+/// Decrypts the ciphertext with the little-endian bits of the secret key,
+/// the message is searched up to `max`, `None` if it is larger.
+pub fn decrypt<P: TEModelParameters>(
+    secret: &[bool],
+    (c1, c2): &(GroupAffine<P>, GroupAffine<P>),
+    max: u64,
+) -> Option<u64> {
+    let encoded = *c2 - &mul(*c1, secret);
+
+    let g = generator::<P>();
+    let mut point = GroupAffine::<P>::zero();
+    for m in 0..=max {
+        if point == encoded {
+            return Some(m);
+        }
+        point += &g;
+    }
+
+    None
+}
+
+/// Allocates the public key as a public input, its coordinates are the
+/// inputs. The verifier checks that the key is a point of the prime
+/// subgroup, it is not enforced.
+pub fn alloc_public_key<P, CS>(
+    mut cs: CS,
+    pk: Option<GroupAffine<P>>,
+) -> Result<AllocatedPoint<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    let x = AllocatedFr::alloc_input(cs.ns(|| "pk x"), || {
+        pk.map(|p| p.x).ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    let y = AllocatedFr::alloc_input(cs.ns(|| "pk y"), || {
+        pk.map(|p| p.y).ok_or(SynthesisError::AssignmentMissing)
+    })?;
+
+    Ok((x, y))
+}
+
+/// Scalar multiplication gadget of the point `base` by the little-endian
+/// `bits`, double and add with the complete addition.
+///
+/// It costs 14 constraints per bit, the first addition and the last
+/// doubling are skipped.
+pub fn variable_base_mul<P, CS>(
+    mut cs: CS,
+    base: &AllocatedPoint<P::BaseField>,
+    bits: &[Boolean],
+) -> Result<AllocatedPoint<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    assert!(
+        !bits.is_empty(),
+        "the scalar of a multiplication is not empty"
+    );
+
+    let one = P::BaseField::one();
+    let mut base = base.clone();
+    let mut result: Option<AllocatedPoint<P::BaseField>> = None;
+    for (i, bit) in bits.iter().enumerate() {
+        let mut cs = cs.ns(|| format!("bit {}", i));
+
+        // the base if the bit is set, else the identity (0, 1).
+        let (x, y) = &base;
+        let sx = AllocatedFr::alloc(cs.ns(|| "selected x"), || {
+            match (bit.get_value(), x.get_value()) {
+                (Some(true), Some(x)) => Ok(x),
+                (Some(false), _) => Ok(P::BaseField::zero()),
+                _ => Err(SynthesisError::AssignmentMissing),
+            }
+        })?;
+        cs.enforce(
+            || "selected x = bit * x",
+            |_| bit.lc(CS::one(), one),
+            |lc| lc + x.get_variable(),
+            |lc| lc + sx.get_variable(),
+        );
+        let sy = AllocatedFr::alloc(cs.ns(|| "selected y"), || {
+            match (bit.get_value(), y.get_value()) {
+                (Some(true), Some(y)) => Ok(y),
+                (Some(false), _) => Ok(one),
+                _ => Err(SynthesisError::AssignmentMissing),
+            }
+        })?;
+        cs.enforce(
+            || "selected y - 1 = bit * (y - 1)",
+            |_| bit.lc(CS::one(), one),
+            |lc| lc + y.get_variable() - CS::one(),
+            |lc| lc + sy.get_variable() - CS::one(),
+        );
+
+        result = Some(match result {
+            Some(acc) => add::<P, _>(cs.ns(|| "add"), &acc, &(sx, sy))?,
+            None => (sx, sy),
+        });
+
+        if i + 1 < bits.len() {
+            base = add::<P, _>(cs.ns(|| "double"), &base, &base)?;
+        }
+    }
+
+    Ok(result.unwrap())
+}
+
+/// ElGamal encryption gadget of the little-endian bits of the message under
+/// the allocated public key, with the ones of the randomness. The
+/// coordinates of the ciphertext `(C1, C2)` are inputized, in this order,
+/// and returned.
+///
+/// It costs about 3 constraints per bit of the message, 17 per bit of the
+/// randomness, and the 4 public inputs.
+pub fn encrypt<P, CS>(
+    mut cs: CS,
+    pk: &AllocatedPoint<P::BaseField>,
+    message: &[Boolean],
+    randomness: &[Boolean],
+) -> Result<AllocatedCiphertext<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    assert!(!message.is_empty() && !randomness.is_empty());
+
+    let g = generator::<P>();
+    let message_tables = fixed_base_tables(g, num_chunks(message.len(), WINDOW), WINDOW);
+    let randomness_tables = fixed_base_tables(g, num_chunks(randomness.len(), WINDOW), WINDOW);
+
+    let c1 = windowed_sum_enforce(
+        cs.ns(|| "randomness"),
+        randomness,
+        &randomness_tables,
+        WINDOW,
+    )?;
+    let m = windowed_sum_enforce(cs.ns(|| "message"), message, &message_tables, WINDOW)?;
+    let shared = variable_base_mul::<P, _>(cs.ns(|| "shared"), pk, randomness)?;
+    let c2 = add::<P, _>(cs.ns(|| "c2"), &m, &shared)?;
+
+    c1.0.inputize(cs.ns(|| "c1 x"))?;
+    c1.1.inputize(cs.ns(|| "c1 y"))?;
+    c2.0.inputize(cs.ns(|| "c2 x"))?;
+    c2.1.inputize(cs.ns(|| "c2 y"))?;
+
+    Ok((c1, c2))
+}
+
+#[cfg(all(test, feature = "baby_jubjub"))]
+mod test {
+    use curve::baby_jubjub::{EdwardsParameters, Fq};
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::boolean::AllocatedBit;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    /// The number of bits of the randomness and of the keys, the ones of the
+    /// order of the baby_jubjub subgroup.
+    const SCALAR_BITS: usize = 251;
+
+    /// The little-endian bits of `m`.
+    fn u64_to_bits(m: u64, len: usize) -> Vec<bool> {
+        (0..len).map(|i| (m >> i) & 1 == 1).collect()
+    }
+
+    fn alloc_bits<CS: ConstraintSystem<Fq>>(mut cs: CS, bits: &[bool]) -> Vec<Boolean> {
+        bits.iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), Some(*b)).map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_native_elgamal() {
+        let mut rng = test_rng();
+
+        let secret: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+        let pk = public_key::<EdwardsParameters>(&secret);
+        assert!(pk.is_in_prime_subgroup());
+
+        for &m in [0u64, 1, 1000].iter() {
+            let randomness: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+            let ciphertext = encryption(&pk, &u64_to_bits(m, 16), &randomness);
+
+            assert_eq!(decrypt(&secret, &ciphertext, 1000), Some(m));
+            if m > 0 {
+                assert_eq!(decrypt(&secret, &ciphertext, m - 1), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_variable_base_mul() {
+        let mut rng = test_rng();
+
+        let base = public_key::<EdwardsParameters>(&[true, true]);
+        for &len in [1, 2, 16].iter() {
+            let scalar: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let bits = alloc_bits(cs.ns(|| "scalar"), &scalar);
+            let x = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(base.x)).unwrap();
+            let y = AllocatedFr::alloc(cs.ns(|| "y"), || Ok(base.y)).unwrap();
+            let num_constraints = cs.num_constraints();
+
+            let (rx, ry) =
+                variable_base_mul::<EdwardsParameters, _>(cs.ns(|| "mul"), &(x, y), &bits).unwrap();
+            assert!(cs.is_satisfied());
+
+            let expected = mul(base, &scalar);
+            assert_eq!(rx.get_value(), Some(expected.x));
+            assert_eq!(ry.get_value(), Some(expected.y));
+            assert_eq!(cs.num_constraints() - num_constraints, 14 * len - 12);
+        }
+    }
+
+    #[test]
+    fn test_elgamal_encrypt() {
+        let mut rng = test_rng();
+
+        let secret: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+        let pk = public_key::<EdwardsParameters>(&secret);
+        let message = u64_to_bits(42, 16);
+        let randomness: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+        let (c1, c2) = encryption(&pk, &message, &randomness);
+
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let pk_var = alloc_public_key(cs.ns(|| "pk"), Some(pk)).unwrap();
+        let message_bits = alloc_bits(cs.ns(|| "message"), &message);
+        let randomness_bits = alloc_bits(cs.ns(|| "randomness"), &randomness);
+        let num_constraints = cs.num_constraints();
+
+        encrypt::<EdwardsParameters, _>(
+            cs.ns(|| "encrypt"),
+            &pk_var,
+            &message_bits,
+            &randomness_bits,
+        )
+        .unwrap();
+
+        assert!(cs.verify(&[pk.x, pk.y, c1.x, c1.y, c2.x, c2.y]));
+        // the fixed base sums of 6 and 84 chunks, the shared point, the
+        // addition of C2 and the inputs.
+        let fixed = |chunks: usize| 3 * chunks + 6 * (chunks - 1) - 1;
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            fixed(6) + fixed(84) + 14 * SCALAR_BITS - 12 + 6 + 4
+        );
+
+        // the ciphertext of another message does not verify.
+        let (_, other) = encryption(&pk, &u64_to_bits(43, 16), &randomness);
+        assert!(!cs.verify(&[pk.x, pk.y, c1.x, c1.y, other.x, other.y]));
+        assert_eq!(decrypt(&secret, &(c1, c2), 100), Some(42));
+    }
+}
@@ -1,5 +1,9 @@
-use math::PrimeField;
-use scheme::r1cs::{ConstraintSystem, SynthesisError, Variable};
+use math::{BigInteger, PrimeField};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::boolean::{field_into_allocated_bits_le, AllocatedBit, Boolean};
 
 #[derive(Clone)]
 pub struct AllocatedFr<F: PrimeField> {
@@ -16,6 +20,12 @@ impl<F: PrimeField> AllocatedFr<F> {
         self.value
     }
 
+    /// The number of a `variable` allocated elsewhere, of `value`. Nothing
+    /// is enforced, the caller constrains the variable.
+    pub(crate) fn from_variable(variable: Variable, value: Option<F>) -> Self {
+        AllocatedFr { value, variable }
+    }
+
     pub fn alloc<FN, CS>(mut cs: CS, value: FN) -> Result<Self, SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -38,6 +48,43 @@ impl<F: PrimeField> AllocatedFr<F> {
         })
     }
 
+    /// The constant `value`, of a variable fixed to it by
+    /// `ConstraintSystem::alloc_constant`: the constant one costs nothing,
+    /// any other a constraint.
+    pub fn constant<CS>(mut cs: CS, value: F) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let var = cs.alloc_constant(|| "fr", value)?;
+
+        Ok(AllocatedFr {
+            value: Some(value),
+            variable: var,
+        })
+    }
+
+    pub fn alloc_input<FN, CS>(mut cs: CS, value: FN) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+        FN: FnOnce() -> Result<F, SynthesisError>,
+    {
+        let mut new_value = None;
+
+        let var = cs.alloc_input(
+            || "fr",
+            || {
+                let tmp = value()?;
+                new_value = Some(tmp);
+                Ok(tmp)
+            },
+        )?;
+
+        Ok(AllocatedFr {
+            value: new_value,
+            variable: var,
+        })
+    }
+
     pub fn inputize<CS>(&self, mut cs: CS) -> Result<(), SynthesisError>
     where
         CS: ConstraintSystem<F>,
@@ -56,4 +103,470 @@ impl<F: PrimeField> AllocatedFr<F> {
 
         Ok(())
     }
+
+    /// Deconstructs the number into its `F::size_in_bits()` little-endian
+    /// bits, enforced to pack into the number. The bits are not enforced to
+    /// be below the modulus, so `self + p` also passes when it fits: use
+    /// `to_bits_le_strict` wherever the bits are compared or hashed.
+    pub fn to_bits_le_unchecked<CS>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let bits = field_into_allocated_bits_le(cs.ns(|| "bits"), self.value)?;
+
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in bits.iter() {
+            lc = lc + (coeff, bit.get_variable());
+            coeff = coeff.double();
+        }
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + self.variable,
+        );
+
+        Ok(bits.into_iter().map(Boolean::from).collect())
+    }
+
+    /// Deconstructs the number into its canonical `F::size_in_bits()`
+    /// little-endian bits, also enforced to be at most `p - 1` by
+    /// `Boolean::enforce_smaller_or_equal_than`.
+    pub fn to_bits_le_strict<CS>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let repr = self.value.map(|v| v.into_repr());
+
+        // big-endian
+        let bits = (0..F::size_in_bits())
+            .rev()
+            .map(|i| {
+                let value = repr.map(|r| r.get_bit(i));
+                AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), value).map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Boolean::enforce_smaller_or_equal_than(&mut cs, &bits, &(-F::one()).into_repr())?;
+
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in bits.iter().rev() {
+            lc = lc + &bit.lc(CS::one(), coeff);
+            coeff = coeff.double();
+        }
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + self.variable,
+        );
+
+        Ok(bits.into_iter().rev().collect())
+    }
+
+    /// Whether the number is zero. The witness is its inverse `inv`, or
+    /// zero, with `x * inv = 1 - out` and `x * out = 0`: a non-zero `x`
+    /// forces `out` to zero by the second, a zero `x` forces it to one by
+    /// the first, whatever `inv` is.
+    pub fn is_zero<CS>(&self, cs: CS) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        is_zero_lc(cs, LinearCombination::zero() + self.variable, self.value)
+    }
+
+    /// Whether `a` equals `b`, as the zero test of `a - b`.
+    pub fn is_eq<CS>(cs: CS, a: &Self, b: &Self) -> Result<Boolean, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = match (a.value, b.value) {
+            (Some(a), Some(b)) => Some(a - &b),
+            _ => None,
+        };
+
+        is_zero_lc(
+            cs,
+            LinearCombination::zero() + a.variable - b.variable,
+            value,
+        )
+    }
+
+    /// Returns `a` if `cond` is true, and `b` otherwise, enforced by
+    /// `(a - b) * cond = result - b`.
+    pub fn conditionally_select<CS>(
+        mut cs: CS,
+        cond: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        if let Boolean::Constant(c) = *cond {
+            return Ok(if c { a.clone() } else { b.clone() });
+        }
+
+        let result = Self::alloc(cs.ns(|| "result"), || {
+            match cond.get_value().ok_or(SynthesisError::AssignmentMissing)? {
+                true => a.value.ok_or(SynthesisError::AssignmentMissing),
+                false => b.value.ok_or(SynthesisError::AssignmentMissing),
+            }
+        })?;
+
+        cs.enforce(
+            || "select",
+            |lc| lc + a.variable - b.variable,
+            |_| cond.lc(CS::one(), F::one()),
+            |lc| lc + result.variable - b.variable,
+        );
+
+        Ok(result)
+    }
+
+    /// Returns `numerator / denominator`, enforcing the denominator is not
+    /// zero with its zero test, in 5 constraints. A zero denominator would
+    /// let any quotient pass `quotient * 0 = 0`.
+    pub fn div<CS>(mut cs: CS, numerator: &Self, denominator: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let is_zero = denominator.is_zero(cs.ns(|| "denominator is zero"))?;
+        cs.enforce(
+            || "denominator is not zero",
+            |_| is_zero.lc(CS::one(), F::one()),
+            |lc| lc + CS::one(),
+            |lc| lc,
+        );
+
+        Self::div_unchecked(cs.ns(|| "div"), numerator, denominator)
+    }
+
+    /// Returns `numerator / denominator`, enforced by
+    /// `quotient * denominator = numerator`, where the denominator is
+    /// already known not to be zero.
+    pub fn div_unchecked<CS>(
+        mut cs: CS,
+        numerator: &Self,
+        denominator: &Self,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let quotient = Self::alloc(cs.ns(|| "quotient"), || {
+            let n = numerator.value.ok_or(SynthesisError::AssignmentMissing)?;
+            let d = denominator.value.ok_or(SynthesisError::AssignmentMissing)?;
+            Ok(n * &d.inverse().ok_or(SynthesisError::DivisionByZero)?)
+        })?;
+
+        cs.enforce(
+            || "quotient * denominator = numerator",
+            |lc| lc + quotient.variable,
+            |lc| lc + denominator.variable,
+            |lc| lc + numerator.variable,
+        );
+
+        Ok(quotient)
+    }
+}
+
+pub(crate) fn is_zero_lc<F, CS>(
+    mut cs: CS,
+    x: LinearCombination<F>,
+    value: Option<F>,
+) -> Result<Boolean, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let inv = cs.alloc(
+        || "inverse",
+        || {
+            let value = value.ok_or(SynthesisError::AssignmentMissing)?;
+            Ok(value.inverse().unwrap_or_else(F::zero))
+        },
+    )?;
+    let out = AllocatedBit::alloc(cs.ns(|| "is zero"), value.map(|v| v.is_zero()))?;
+
+    cs.enforce(
+        || "x * inv = 1 - out",
+        |_| x.clone(),
+        |lc| lc + inv,
+        |lc| lc + CS::one() - out.get_variable(),
+    );
+    cs.enforce(
+        || "x * out = 0",
+        |_| x,
+        |lc| lc + out.get_variable(),
+        |lc| lc,
+    );
+
+    Ok(Boolean::from(out))
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, BigInteger, FpParameters, One, PrimeField, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::boolean::{AllocatedBit, Boolean};
+    use super::AllocatedFr;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_constant() {
+        let rng = &mut test_rng();
+
+        for c in [Fr::zero(), rng.gen()].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let c_var = AllocatedFr::constant(cs.ns(|| "c"), *c).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 1);
+            assert_eq!(cs.num_private_variables(), 1);
+            assert_eq!(c_var.get_value(), Some(*c));
+            assert_eq!(cs.get("c/fr/constant"), *c);
+
+            cs.set("c/fr/constant", *c + &Fr::one());
+            assert!(!cs.is_satisfied());
+        }
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let one = AllocatedFr::constant(cs.ns(|| "one"), Fr::one()).unwrap();
+        assert_eq!(one.get_variable(), TestConstraintSystem::<Fr>::one());
+        assert_eq!(cs.num_constraints(), 0);
+        assert_eq!(cs.num_private_variables(), 0);
+    }
+
+    #[test]
+    fn test_is_zero() {
+        let rng = &mut test_rng();
+
+        for x in [Fr::zero(), Fr::one(), rng.gen()].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let x_var = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(*x)).unwrap();
+            let r = x_var.is_zero(cs.ns(|| "is zero")).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 3);
+            assert_eq!(r.get_value(), Some(x.is_zero()));
+
+            // flipping the result fails whatever the inverse is
+            let flipped = if x.is_zero() { Fr::zero() } else { Fr::one() };
+            cs.set("is zero/is zero/boolean", flipped);
+            for inv in [Fr::zero(), Fr::one(), rng.gen()].iter() {
+                cs.set("is zero/inverse", *inv);
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_eq() {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let a: Fr = rng.gen();
+            let b: Fr = rng.gen();
+
+            for (a, b) in [(a, a), (a, b)].iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_var = AllocatedFr::alloc(cs.ns(|| "a"), || Ok(*a)).unwrap();
+                let b_var = AllocatedFr::alloc(cs.ns(|| "b"), || Ok(*b)).unwrap();
+                let r = AllocatedFr::is_eq(cs.ns(|| "is eq"), &a_var, &b_var).unwrap();
+                assert!(cs.is_satisfied());
+                assert_eq!(r.get_value(), Some(a == b));
+
+                let flipped = if a == b { Fr::zero() } else { Fr::one() };
+                cs.set("is eq/is zero/boolean", flipped);
+                for inv in [Fr::zero(), Fr::one(), rng.gen()].iter() {
+                    cs.set("is eq/inverse", *inv);
+                    assert!(!cs.is_satisfied());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditionally_select() {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let a: Fr = rng.gen();
+            let b: Fr = rng.gen();
+
+            for cond in [true, false].iter() {
+                for negated in [true, false].iter() {
+                    let mut cs = TestConstraintSystem::<Fr>::new();
+
+                    let a_var = AllocatedFr::alloc(cs.ns(|| "a"), || Ok(a)).unwrap();
+                    let b_var = AllocatedFr::alloc(cs.ns(|| "b"), || Ok(b)).unwrap();
+                    let c = AllocatedBit::alloc(cs.ns(|| "cond"), Some(*cond ^ *negated)).unwrap();
+                    let c = if *negated {
+                        Boolean::from(c).not()
+                    } else {
+                        Boolean::from(c)
+                    };
+
+                    let r =
+                        AllocatedFr::conditionally_select(cs.ns(|| "select"), &c, &a_var, &b_var)
+                            .unwrap();
+                    assert!(cs.is_satisfied());
+                    assert_eq!(cs.num_constraints(), 2);
+
+                    let expected = if *cond { a } else { b };
+                    assert_eq!(r.get_value(), Some(expected));
+
+                    // the other branch does not pass
+                    let other = if *cond { b } else { a };
+                    cs.set("select/result/fr", other);
+                    assert!(!cs.is_satisfied());
+                }
+            }
+
+            for cond in [true, false].iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_var = AllocatedFr::alloc(cs.ns(|| "a"), || Ok(a)).unwrap();
+                let b_var = AllocatedFr::alloc(cs.ns(|| "b"), || Ok(b)).unwrap();
+                let r = AllocatedFr::conditionally_select(
+                    cs.ns(|| "select"),
+                    &Boolean::constant(*cond),
+                    &a_var,
+                    &b_var,
+                )
+                .unwrap();
+                assert_eq!(cs.num_constraints(), 0);
+                assert_eq!(r.get_value(), Some(if *cond { a } else { b }));
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_bits_le_strict() {
+        let rng = &mut test_rng();
+
+        for x in [Fr::zero(), Fr::one(), -Fr::one(), rng.gen()].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let x_var = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(*x)).unwrap();
+            let strict = x_var.to_bits_le_strict(cs.ns(|| "strict")).unwrap();
+            let lax = x_var.to_bits_le_unchecked(cs.ns(|| "lax")).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(strict.len(), Fr::size_in_bits());
+            assert_eq!(
+                strict.iter().map(|b| b.get_value()).collect::<Vec<_>>(),
+                lax.iter().map(|b| b.get_value()).collect::<Vec<_>>()
+            );
+        }
+
+        // the bits of p pack into zero, only the lax bits pass.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let x_var = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(-Fr::one())).unwrap();
+        x_var.to_bits_le_strict(cs.ns(|| "strict")).unwrap();
+        x_var.to_bits_le_unchecked(cs.ns(|| "lax")).unwrap();
+        cs.set("x/fr", Fr::zero());
+        cs.set("strict/bit 0/boolean", Fr::one());
+        cs.set("lax/bits/bit 0/boolean", Fr::one());
+        assert_eq!(
+            cs.which_is_unsatisfied().as_deref(),
+            Some("strict/bit 0 is zero if the bits above match")
+        );
+
+        // a small x also decomposes as x + p, only the lax bits pass.
+        let x = Fr::from(5u32);
+        let mut x_plus_p = <Fr as PrimeField>::Params::MODULUS;
+        x_plus_p.add_nocarry(&x.into_repr());
+        for (name, strict) in [("strict", true), ("lax", false)].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let x_var = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(x)).unwrap();
+            let (bits, path) = if *strict {
+                (x_var.to_bits_le_strict(cs.ns(|| *name)), "strict/bit")
+            } else {
+                (x_var.to_bits_le_unchecked(cs.ns(|| *name)), "lax/bits/bit")
+            };
+            assert_eq!(bits.unwrap().len(), Fr::size_in_bits());
+            assert!(cs.is_satisfied());
+
+            for i in 0..Fr::size_in_bits() {
+                let bit = if x_plus_p.get_bit(i) {
+                    Fr::one()
+                } else {
+                    Fr::zero()
+                };
+                cs.set(&format!("{} {}/boolean", path, i), bit);
+            }
+            assert_eq!(cs.is_satisfied(), !*strict);
+        }
+    }
+
+    #[test]
+    fn test_div() {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let (n, d): (Fr, Fr) = (rng.gen(), rng.gen());
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let nv = AllocatedFr::alloc(cs.ns(|| "n"), || Ok(n)).unwrap();
+            let dv = AllocatedFr::alloc(cs.ns(|| "d"), || Ok(d)).unwrap();
+            let q = AllocatedFr::div(cs.ns(|| "div"), &nv, &dv).unwrap();
+            let qu = AllocatedFr::div_unchecked(cs.ns(|| "div unchecked"), &nv, &dv).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 5 + 1);
+            assert_eq!(q.get_value().unwrap() * &d, n);
+            assert_eq!(qu.get_value(), q.get_value());
+
+            // another quotient does not pass.
+            cs.set("div/div/quotient/fr", n);
+            assert!(!cs.is_satisfied());
+        }
+
+        // a zero denominator has no quotient to witness.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let nv = AllocatedFr::alloc(cs.ns(|| "n"), || Ok(Fr::one())).unwrap();
+        let dv = AllocatedFr::alloc(cs.ns(|| "d"), || Ok(Fr::zero())).unwrap();
+        assert!(AllocatedFr::div(cs.ns(|| "div"), &nv, &dv).is_err());
+    }
+
+    #[test]
+    fn test_div_by_zero_soundness() {
+        let rng = &mut test_rng();
+        let q: Fr = rng.gen();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let nv = AllocatedFr::alloc(cs.ns(|| "n"), || Ok(Fr::from(6u32))).unwrap();
+        let dv = AllocatedFr::alloc(cs.ns(|| "d"), || Ok(Fr::from(3u32))).unwrap();
+        AllocatedFr::div_unchecked(cs.ns(|| "div unchecked"), &nv, &dv).unwrap();
+        AllocatedFr::div(cs.ns(|| "div"), &nv, &dv).unwrap();
+        assert!(cs.is_satisfied());
+
+        // 0 / 0 with an arbitrary quotient passes the unchecked division,
+        // whose constraint comes first.
+        cs.set("n/fr", Fr::zero());
+        cs.set("d/fr", Fr::zero());
+        cs.set("div/div/quotient/fr", q);
+        cs.set("div unchecked/quotient/fr", q);
+
+        // but not the division, whatever the witness of the zero test is.
+        let inv = "div/denominator is zero/inverse";
+        let out = "div/denominator is zero/is zero/boolean";
+        let unsatisfied = "div/denominator is zero/x * inv = 1 - out";
+        for &(i, o, constraint) in [
+            (Fr::zero(), Fr::one(), "div/denominator is not zero"),
+            (Fr::zero(), Fr::zero(), unsatisfied),
+            (q, Fr::zero(), unsatisfied),
+        ]
+        .iter()
+        {
+            cs.set(inv, i);
+            cs.set(out, o);
+            assert_eq!(cs.which_is_unsatisfied().as_deref(), Some(constraint));
+        }
+    }
 }
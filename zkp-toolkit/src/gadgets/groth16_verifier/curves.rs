@@ -0,0 +1,404 @@
+//! Points of the G1 and G2 groups of a BLS12 curve, of coordinates in its
+//! base field, the outer field.
+//!
+//! Points are not checked to be in the prime order subgroups, and the point
+//! at infinity has no affine representation: allocated points are only
+//! enforced to be on their curves.
+
+use math::curves::{
+    bls12::{Bls12Parameters, G1Affine, G2Affine},
+    SWModelParameters,
+};
+use math::{Field, Fp2, One, Zero};
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use super::super::boolean::Boolean;
+use super::fields::{Fp2Var, FpVar};
+
+/// A point of G1 in affine coordinates.
+pub struct G1AffineVar<P: Bls12Parameters> {
+    pub x: FpVar<P::Fp>,
+    pub y: FpVar<P::Fp>,
+}
+
+impl<P: Bls12Parameters> Clone for G1AffineVar<P> {
+    fn clone(&self) -> Self {
+        G1AffineVar {
+            x: self.x.clone(),
+            y: self.y.clone(),
+        }
+    }
+}
+
+impl<P: Bls12Parameters> G1AffineVar<P> {
+    pub fn constant(p: G1Affine<P>) -> Self {
+        assert!(!p.infinity, "the point at infinity is not supported");
+
+        G1AffineVar {
+            x: FpVar::constant(p.x),
+            y: FpVar::constant(p.y),
+        }
+    }
+
+    /// Allocates a point, enforced to be on the curve, in 3 constraints.
+    pub fn alloc<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        value: Option<G1Affine<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let x = FpVar::alloc(cs.ns(|| "x"), value.map(|p| p.x))?;
+        let y = FpVar::alloc(cs.ns(|| "y"), value.map(|p| p.y))?;
+
+        // y^2 = x^3 + a * x + b
+        let x2 = x.square(cs.ns(|| "x^2"))?;
+        let x3_ax = x2
+            .add(&FpVar::constant(P::G1Parameters::COEFF_A))
+            .mul(cs.ns(|| "x^3 + a * x"), &x)?;
+        let rhs = x3_ax.add(&FpVar::constant(P::G1Parameters::COEFF_B));
+        cs.enforce(
+            || "on curve",
+            |_| y.lc::<CS>(),
+            |_| y.lc::<CS>(),
+            |_| rhs.lc::<CS>(),
+        );
+
+        Ok(G1AffineVar { x, y })
+    }
+
+    pub fn get_value(&self) -> Option<G1Affine<P>> {
+        match (self.x.get_value(), self.y.get_value()) {
+            (Some(x), Some(y)) => Some(G1Affine::<P>::new(x, y, false)),
+            _ => None,
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        G1AffineVar {
+            x: self.x.clone(),
+            y: self.y.neg(),
+        }
+    }
+
+    pub fn to_projective(&self) -> G1ProjectiveVar<P> {
+        G1ProjectiveVar {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: FpVar::one(),
+        }
+    }
+}
+
+/// A point of G1 in homogeneous projective coordinates `(X : Y : Z)`, of the
+/// affine point `(X / Z, Y / Z)`, and `(0 : 1 : 0)` for the point at
+/// infinity.
+pub struct G1ProjectiveVar<P: Bls12Parameters> {
+    pub x: FpVar<P::Fp>,
+    pub y: FpVar<P::Fp>,
+    pub z: FpVar<P::Fp>,
+}
+
+impl<P: Bls12Parameters> Clone for G1ProjectiveVar<P> {
+    fn clone(&self) -> Self {
+        G1ProjectiveVar {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+        }
+    }
+}
+
+impl<P: Bls12Parameters> G1ProjectiveVar<P> {
+    pub fn zero() -> Self {
+        G1ProjectiveVar {
+            x: FpVar::zero(),
+            y: FpVar::one(),
+            z: FpVar::zero(),
+        }
+    }
+
+    /// The complete addition of Renes, Costello and Batina
+    /// (https://eprint.iacr.org/2015/1060.pdf, Algorithm 7) for `a = 0`,
+    /// which also doubles and adds the point at infinity, in 12 constraints,
+    /// fewer of constant coordinates.
+    pub fn add<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        assert!(P::G1Parameters::COEFF_A.is_zero());
+        let b3 = P::G1Parameters::COEFF_B.double() + &P::G1Parameters::COEFF_B;
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&other.x, &other.y, &other.z);
+
+        let t0 = x1.mul(cs.ns(|| "t0"), x2)?;
+        let t1 = y1.mul(cs.ns(|| "t1"), y2)?;
+        let t2 = z1.mul(cs.ns(|| "t2"), z2)?;
+        let t3 = x1
+            .add(y1)
+            .mul(cs.ns(|| "t3"), &x2.add(y2))?
+            .sub(&t0.add(&t1));
+        let t4 = y1
+            .add(z1)
+            .mul(cs.ns(|| "t4"), &y2.add(z2))?
+            .sub(&t1.add(&t2));
+        let y3 = x1
+            .add(z1)
+            .mul(cs.ns(|| "y3"), &x2.add(z2))?
+            .sub(&t0.add(&t2));
+        let t0 = t0.double().add(&t0);
+        let t2 = t2.scale(b3);
+        let z3 = t1.add(&t2);
+        let t1 = t1.sub(&t2);
+        let y3 = y3.scale(b3);
+
+        let x3 = t3
+            .mul(cs.ns(|| "t3 * t1"), &t1)?
+            .sub(&t4.mul(cs.ns(|| "t4 * y3"), &y3)?);
+        let y3 = t1
+            .mul(cs.ns(|| "t1 * z3"), &z3)?
+            .add(&y3.mul(cs.ns(|| "y3 * t0"), &t0)?);
+        let z3 = z3
+            .mul(cs.ns(|| "z3 * t4"), &t4)?
+            .add(&t0.mul(cs.ns(|| "t0 * t3"), &t3)?);
+
+        Ok(G1ProjectiveVar {
+            x: x3,
+            y: y3,
+            z: z3,
+        })
+    }
+
+    pub fn double<CS: ConstraintSystem<P::Fp>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        self.add(cs, self)
+    }
+
+    /// Returns `a` if `cond` is true, and `b` otherwise.
+    pub fn conditionally_select<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        cond: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError> {
+        Ok(G1ProjectiveVar {
+            x: FpVar::conditionally_select(cs.ns(|| "x"), cond, &a.x, &b.x)?,
+            y: FpVar::conditionally_select(cs.ns(|| "y"), cond, &a.y, &b.y)?,
+            z: FpVar::conditionally_select(cs.ns(|| "z"), cond, &a.z, &b.z)?,
+        })
+    }
+
+    /// `[k] self` of the little-endian bits of `k`, by double-and-add, in
+    /// about 15 constraints a bit, 9 of a constant point.
+    pub fn mul_bits<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        bits: &[Boolean],
+    ) -> Result<Self, SynthesisError> {
+        let mut result = Self::zero();
+        let mut base = self.clone();
+
+        for (i, bit) in bits.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("bit {}", i));
+
+            let sum = result.add(cs.ns(|| "add"), &base)?;
+            result = Self::conditionally_select(cs.ns(|| "select"), bit, &sum, &result)?;
+            if i + 1 < bits.len() {
+                base = base.double(cs.ns(|| "double"))?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// The affine point, unsatisfiable for the point at infinity.
+    pub fn to_affine<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<G1AffineVar<P>, SynthesisError> {
+        let z_inv = self.z.inverse(cs.ns(|| "z inverse"))?;
+
+        Ok(G1AffineVar {
+            x: self.x.mul(cs.ns(|| "x"), &z_inv)?,
+            y: self.y.mul(cs.ns(|| "y"), &z_inv)?,
+        })
+    }
+}
+
+/// A point of G2, on the twist over `Fp2`, in affine coordinates.
+pub struct G2AffineVar<P: Bls12Parameters> {
+    pub x: Fp2Var<P::Fp2Params>,
+    pub y: Fp2Var<P::Fp2Params>,
+}
+
+impl<P: Bls12Parameters> Clone for G2AffineVar<P> {
+    fn clone(&self) -> Self {
+        G2AffineVar {
+            x: self.x.clone(),
+            y: self.y.clone(),
+        }
+    }
+}
+
+impl<P: Bls12Parameters> G2AffineVar<P> {
+    pub fn constant(p: G2Affine<P>) -> Self {
+        assert!(!p.infinity, "the point at infinity is not supported");
+
+        G2AffineVar {
+            x: Fp2Var::constant(p.x),
+            y: Fp2Var::constant(p.y),
+        }
+    }
+
+    /// Allocates a point, enforced to be on the twist, in 9 constraints.
+    pub fn alloc<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        value: Option<G2Affine<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let x = Fp2Var::alloc(cs.ns(|| "x"), value.map(|p| p.x))?;
+        let y = Fp2Var::alloc(cs.ns(|| "y"), value.map(|p| p.y))?;
+
+        // y^2 = x^3 + a * x + b
+        let x2 = x.square(cs.ns(|| "x^2"))?;
+        let x3_ax = x2
+            .add(&Fp2Var::constant(P::G2Parameters::COEFF_A))
+            .mul(cs.ns(|| "x^3 + a * x"), &x)?;
+        let y2 = y.square(cs.ns(|| "y^2"))?;
+        y2.enforce_equal(
+            cs.ns(|| "on curve"),
+            &x3_ax.add(&Fp2Var::constant(P::G2Parameters::COEFF_B)),
+        )?;
+
+        Ok(G2AffineVar { x, y })
+    }
+
+    pub fn get_value(&self) -> Option<G2Affine<P>> {
+        match (self.x.get_value(), self.y.get_value()) {
+            (Some(x), Some(y)) => Some(G2Affine::<P>::new(x, y, false)),
+            _ => None,
+        }
+    }
+}
+
+/// A point of G2 in homogeneous projective coordinates, the running point
+/// of the Miller loop.
+pub(super) struct G2HomProjectiveVar<P: Bls12Parameters> {
+    pub x: Fp2Var<P::Fp2Params>,
+    pub y: Fp2Var<P::Fp2Params>,
+    pub z: Fp2Var<P::Fp2Params>,
+}
+
+impl<P: Bls12Parameters> G2HomProjectiveVar<P> {
+    pub fn from_affine(q: &G2AffineVar<P>) -> Self {
+        G2HomProjectiveVar {
+            x: q.x.clone(),
+            y: q.y.clone(),
+            z: Fp2Var::constant(Fp2::one()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bls12_377"))]
+mod test {
+    use curve::bls12_377::{Fq, Fr, G1Affine, G1Projective, G2Projective, Parameters};
+    use math::{test_rng, AffineCurve, BitIterator, PrimeField, ProjectiveCurve};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::super::boolean::{AllocatedBit, Boolean};
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn affine_value<CS: ConstraintSystem<Fq>>(cs: CS, p: &G1ProjectiveVar<Parameters>) -> G1Affine {
+        p.to_affine(cs).unwrap().get_value().unwrap()
+    }
+
+    #[test]
+    fn test_g1_add() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let a = rng.gen::<G1Projective>().into_affine();
+        let b = rng.gen::<G1Projective>().into_affine();
+        let a_var = G1AffineVar::<Parameters>::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+        let b_var = G1AffineVar::alloc(cs.ns(|| "b"), Some(b)).unwrap();
+        assert_eq!(cs.num_constraints(), 2 * 3);
+
+        let sum = a_var
+            .to_projective()
+            .add(cs.ns(|| "a + b"), &b_var.to_projective())
+            .unwrap();
+        // Z1 * Z2 of affine points is the constant one.
+        assert_eq!(cs.num_constraints(), 2 * 3 + 11);
+        assert_eq!(affine_value(cs.ns(|| "a + b affine"), &sum), a + b);
+
+        let double = a_var.to_projective().double(cs.ns(|| "2a")).unwrap();
+        assert_eq!(
+            affine_value(cs.ns(|| "2a affine"), &double),
+            a.into_projective().double().into_affine()
+        );
+
+        // the addition is complete.
+        let zero = G1ProjectiveVar::zero();
+        let sum = zero.add(cs.ns(|| "0 + a"), &a_var.to_projective()).unwrap();
+        assert_eq!(affine_value(cs.ns(|| "0 + a affine"), &sum), a);
+        let sum = a_var
+            .to_projective()
+            .add(cs.ns(|| "a - a"), &a_var.neg().to_projective())
+            .unwrap();
+        assert_eq!(sum.z.get_value(), Some(Fq::zero()));
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_g1_mul_bits() {
+        let rng = &mut test_rng();
+
+        for &constant in [false, true].iter() {
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let p = rng.gen::<G1Projective>().into_affine();
+            let k = rng.gen::<Fr>();
+            let p_var = if constant {
+                G1AffineVar::<Parameters>::constant(p)
+            } else {
+                G1AffineVar::alloc(cs.ns(|| "p"), Some(p)).unwrap()
+            };
+            let bits = BitIterator::new(k.into_repr())
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .take(Fr::size_in_bits())
+                .enumerate()
+                .map(|(i, b)| {
+                    Boolean::from(
+                        AllocatedBit::alloc(cs.ns(|| format!("k {}", i)), Some(b)).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let kp = p_var
+                .to_projective()
+                .mul_bits(cs.ns(|| "k * p"), &bits)
+                .unwrap();
+            assert_eq!(
+                affine_value(cs.ns(|| "k * p affine"), &kp),
+                p.mul(k).into_affine()
+            );
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_on_curve() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let p = rng.gen::<G1Projective>().into_affine();
+        let q = rng.gen::<G2Projective>().into_affine();
+        G1AffineVar::<Parameters>::alloc(cs.ns(|| "p"), Some(p)).unwrap();
+        let q_var = G2AffineVar::<Parameters>::alloc(cs.ns(|| "q"), Some(q)).unwrap();
+        assert_eq!(q_var.get_value(), Some(q));
+        assert!(cs.is_satisfied());
+
+        cs.set("p/y/fp", -p.y);
+        assert!(cs.is_satisfied());
+        cs.set("p/y/fp", p.x);
+        assert!(!cs.is_satisfied());
+        assert_eq!(cs.which_is_unsatisfied(), Some("p/on curve".to_string()));
+    }
+}
@@ -0,0 +1,1169 @@
+//! Elements of the base field of the inner curve and of its tower
+//! `Fp2 -> Fp6 -> Fp12`, over an outer field which is that base field, so
+//! that the arithmetic is native.
+//!
+//! An element of `Fp` is an affine combination of variables, additions and
+//! multiplications by constants are free, and a product costs a constraint
+//! unless one of its factors is constant. The extensions are built of it
+//! with the formulas of `math`, so that the values are the ones of the
+//! native fields step by step, and everything of constants is computed at
+//! synthesis time.
+
+use math::{BitIterator, Field, Fp12, Fp12Parameters, Fp2, Fp2Parameters, Fp6, Fp6Parameters};
+use math::{One, PrimeField, Zero};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError};
+
+use crate::Vec;
+
+use super::super::boolean::Boolean;
+use super::super::fr::{is_zero_lc, AllocatedFr};
+
+/// An element of the outer field, `lc + constant`, of `value`.
+#[derive(Clone)]
+pub struct FpVar<F: PrimeField> {
+    lc: LinearCombination<F>,
+    constant: F,
+    value: Option<F>,
+}
+
+impl<F: PrimeField> FpVar<F> {
+    pub fn constant(constant: F) -> Self {
+        FpVar {
+            lc: LinearCombination::zero(),
+            constant,
+            value: Some(constant),
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::constant(F::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::constant(F::one())
+    }
+
+    pub fn alloc<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        value: Option<F>,
+    ) -> Result<Self, SynthesisError> {
+        let variable = cs.alloc(|| "fp", || value.ok_or(SynthesisError::AssignmentMissing))?;
+
+        Ok(FpVar {
+            lc: LinearCombination::zero() + variable,
+            constant: F::zero(),
+            value,
+        })
+    }
+
+    pub fn get_value(&self) -> Option<F> {
+        self.value
+    }
+
+    /// Whether the element is a constant, then it costs nothing.
+    pub fn is_constant(&self) -> bool {
+        self.lc.as_ref().is_empty()
+    }
+
+    /// The linear combination of the element in `cs`.
+    pub fn lc<CS: ConstraintSystem<F>>(&self) -> LinearCombination<F> {
+        self.lc.clone() + (self.constant, CS::one())
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        FpVar {
+            lc: &self.lc + &other.lc,
+            constant: self.constant + &other.constant,
+            value: self.value.and_then(|a| other.value.map(|b| a + &b)),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn neg(&self) -> Self {
+        self.scale(-F::one())
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    /// `self * c` of a constant `c`.
+    pub fn scale(&self, c: F) -> Self {
+        FpVar {
+            lc: self.lc.clone() * c,
+            constant: self.constant * &c,
+            value: self.value.map(|v| v * &c),
+        }
+    }
+
+    /// `self * other` in a constraint, none if either is constant.
+    pub fn mul<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        if self.is_constant() {
+            return Ok(other.scale(self.constant));
+        }
+        if other.is_constant() {
+            return Ok(self.scale(other.constant));
+        }
+
+        let value = self.value.and_then(|a| other.value.map(|b| a * &b));
+        let product = Self::alloc(cs.ns(|| "product"), value)?;
+        cs.enforce(
+            || "product = a * b",
+            |_| self.lc::<CS>(),
+            |_| other.lc::<CS>(),
+            |_| product.lc::<CS>(),
+        );
+
+        Ok(product)
+    }
+
+    pub fn square<CS: ConstraintSystem<F>>(&self, cs: CS) -> Result<Self, SynthesisError> {
+        self.mul(cs, self)
+    }
+
+    /// `self^-1` in a constraint, unsatisfiable for zero.
+    pub fn inverse<CS: ConstraintSystem<F>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        if self.is_constant() {
+            let inverse = self
+                .constant
+                .inverse()
+                .ok_or(SynthesisError::DivisionByZero)?;
+            return Ok(Self::constant(inverse));
+        }
+
+        let inverse = Self::alloc(
+            cs.ns(|| "inverse"),
+            self.value.map(|v| v.inverse().unwrap_or_else(F::zero)),
+        )?;
+        cs.enforce(
+            || "self * inverse = 1",
+            |_| self.lc::<CS>(),
+            |_| inverse.lc::<CS>(),
+            |lc| lc + CS::one(),
+        );
+
+        Ok(inverse)
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        cs.enforce(
+            || "a = b",
+            |_| self.sub(other).lc::<CS>(),
+            |lc| lc + CS::one(),
+            |lc| lc,
+        );
+
+        Ok(())
+    }
+
+    /// Whether `self` equals `other`, in 2 constraints.
+    pub fn is_eq<CS: ConstraintSystem<F>>(
+        &self,
+        cs: CS,
+        other: &Self,
+    ) -> Result<Boolean, SynthesisError> {
+        let diff = self.sub(other);
+        if diff.is_constant() {
+            return Ok(Boolean::constant(diff.constant.is_zero()));
+        }
+
+        is_zero_lc(cs, diff.lc::<CS>(), diff.value)
+    }
+
+    /// Returns `a` if `cond` is true, and `b` otherwise, in a constraint,
+    /// none if both are constant.
+    pub fn conditionally_select<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        cond: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let value = match cond.get_value() {
+            Some(true) => a.value,
+            Some(false) => b.value,
+            None => None,
+        };
+
+        let diff = a.sub(b);
+        if let Boolean::Constant(c) = *cond {
+            return Ok(if c { a.clone() } else { b.clone() });
+        }
+        if diff.is_constant() {
+            // b + cond * (a - b) is linear.
+            return Ok(FpVar {
+                lc: &b.lc + &(cond.lc(CS::one(), diff.constant) - (diff.constant, CS::one())),
+                constant: b.constant + &diff.constant,
+                value,
+            });
+        }
+
+        let result = Self::alloc(cs.ns(|| "result"), value)?;
+        cs.enforce(
+            || "(a - b) * cond = result - b",
+            |_| diff.lc::<CS>(),
+            |_| cond.lc(CS::one(), F::one()),
+            |_| result.sub(b).lc::<CS>(),
+        );
+
+        Ok(result)
+    }
+}
+
+impl<F: PrimeField> From<AllocatedFr<F>> for FpVar<F> {
+    fn from(fr: AllocatedFr<F>) -> Self {
+        FpVar {
+            lc: LinearCombination::zero() + fr.get_variable(),
+            constant: F::zero(),
+            value: fr.get_value(),
+        }
+    }
+}
+
+/// Whether all the bits are set, without a constraint for constants.
+fn all<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: CS,
+    bits: &[Boolean],
+) -> Result<Boolean, SynthesisError> {
+    let bits = bits
+        .iter()
+        .filter(|b| !b.is_constant() || b.get_value() == Some(false))
+        .cloned()
+        .collect::<Vec<_>>();
+    match bits.len() {
+        0 => Ok(Boolean::constant(true)),
+        1 => Ok(bits[0]),
+        _ => Boolean::kary_and(cs, &bits),
+    }
+}
+
+/// An element `c0 + c1 * u` of `Fp2`.
+pub struct Fp2Var<P: Fp2Parameters> {
+    pub c0: FpVar<P::Fp>,
+    pub c1: FpVar<P::Fp>,
+}
+
+impl<P: Fp2Parameters> Clone for Fp2Var<P> {
+    fn clone(&self) -> Self {
+        Fp2Var {
+            c0: self.c0.clone(),
+            c1: self.c1.clone(),
+        }
+    }
+}
+
+impl<P: Fp2Parameters> Fp2Var<P> {
+    pub fn new(c0: FpVar<P::Fp>, c1: FpVar<P::Fp>) -> Self {
+        Fp2Var { c0, c1 }
+    }
+
+    pub fn constant(c: Fp2<P>) -> Self {
+        Self::new(FpVar::constant(c.c0), FpVar::constant(c.c1))
+    }
+
+    pub fn zero() -> Self {
+        Self::constant(Fp2::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::constant(Fp2::one())
+    }
+
+    pub fn alloc<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        value: Option<Fp2<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = FpVar::alloc(cs.ns(|| "c0"), value.map(|v| v.c0))?;
+        let c1 = FpVar::alloc(cs.ns(|| "c1"), value.map(|v| v.c1))?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    pub fn get_value(&self) -> Option<Fp2<P>> {
+        match (self.c0.get_value(), self.c1.get_value()) {
+            (Some(c0), Some(c1)) => Some(Fp2::new(c0, c1)),
+            _ => None,
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.c0.is_constant() && self.c1.is_constant()
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(self.c0.add(&other.c0), self.c1.add(&other.c1))
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(self.c0.sub(&other.c0), self.c1.sub(&other.c1))
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.c0.neg(), self.c1.neg())
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    fn mul_fp_by_nonresidue(fe: &FpVar<P::Fp>) -> FpVar<P::Fp> {
+        fe.scale(P::NONRESIDUE)
+    }
+
+    /// `self * c` of a constant `c`, free.
+    pub fn mul_by_constant(&self, c: &Fp2<P>) -> Self {
+        let c0 = self
+            .c0
+            .scale(c.c0)
+            .add(&Self::mul_fp_by_nonresidue(&self.c1.scale(c.c1)));
+        let c1 = self.c0.scale(c.c1).add(&self.c1.scale(c.c0));
+
+        Self::new(c0, c1)
+    }
+
+    /// `self * fe` of an element of `Fp`, in 2 constraints.
+    pub fn mul_by_fp<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        fe: &FpVar<P::Fp>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = self.c0.mul(cs.ns(|| "c0"), fe)?;
+        let c1 = self.c1.mul(cs.ns(|| "c1"), fe)?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// The Karatsuba product, in 3 constraints.
+    pub fn mul<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        if other.is_constant() {
+            return Ok(self.mul_by_constant(&other.get_value().unwrap()));
+        }
+        if self.is_constant() {
+            return Ok(other.mul_by_constant(&self.get_value().unwrap()));
+        }
+
+        let v0 = self.c0.mul(cs.ns(|| "v0"), &other.c0)?;
+        let v1 = self.c1.mul(cs.ns(|| "v1"), &other.c1)?;
+        let c0 = v0.add(&Self::mul_fp_by_nonresidue(&v1));
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(cs.ns(|| "v2"), &other.c0.add(&other.c1))?
+            .sub(&v0)
+            .sub(&v1);
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// The complex squaring, in 2 constraints.
+    pub fn square<CS: ConstraintSystem<P::Fp>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        if self.is_constant() {
+            return Ok(Self::constant(self.get_value().unwrap().square()));
+        }
+
+        let v0 = self.c0.sub(&self.c1);
+        let v3 = self.c0.sub(&Self::mul_fp_by_nonresidue(&self.c1));
+        let v2 = self.c0.mul(cs.ns(|| "v2"), &self.c1)?;
+        let v0 = v0.mul(cs.ns(|| "v0"), &v3)?.add(&v2);
+
+        let c1 = v2.double();
+        let c0 = v0.add(&Self::mul_fp_by_nonresidue(&v2));
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// `self^-1`, enforced by `self * inverse = 1`, unsatisfiable for zero.
+    pub fn inverse<CS: ConstraintSystem<P::Fp>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        if self.is_constant() {
+            let inverse = self.get_value().unwrap().inverse();
+            return inverse
+                .map(Self::constant)
+                .ok_or(SynthesisError::DivisionByZero);
+        }
+
+        let inverse = Self::alloc(
+            cs.ns(|| "inverse"),
+            self.get_value()
+                .map(|v| v.inverse().unwrap_or_else(Fp2::zero)),
+        )?;
+        let product = self.mul(cs.ns(|| "self * inverse"), &inverse)?;
+        product.enforce_equal(cs.ns(|| "self * inverse = 1"), &Self::one())?;
+
+        Ok(inverse)
+    }
+
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        Self::new(
+            self.c0.clone(),
+            self.c1.scale(P::FROBENIUS_COEFF_FP2_C1[power % 2]),
+        )
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.c0.enforce_equal(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.enforce_equal(cs.ns(|| "c1"), &other.c1)
+    }
+
+    fn is_eq_bits<CS: ConstraintSystem<P::Fp>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        Ok(vec![
+            self.c0.is_eq(cs.ns(|| "c0"), &other.c0)?,
+            self.c1.is_eq(cs.ns(|| "c1"), &other.c1)?,
+        ])
+    }
+}
+
+/// An element `c0 + c1 * v + c2 * v^2` of `Fp6`.
+pub struct Fp6Var<P: Fp6Parameters> {
+    pub c0: Fp2Var<P::Fp2Params>,
+    pub c1: Fp2Var<P::Fp2Params>,
+    pub c2: Fp2Var<P::Fp2Params>,
+}
+
+impl<P: Fp6Parameters> Clone for Fp6Var<P> {
+    fn clone(&self) -> Self {
+        Self::new(self.c0.clone(), self.c1.clone(), self.c2.clone())
+    }
+}
+
+type Fp<P> = <<P as Fp6Parameters>::Fp2Params as Fp2Parameters>::Fp;
+
+impl<P: Fp6Parameters> Fp6Var<P> {
+    pub fn new(
+        c0: Fp2Var<P::Fp2Params>,
+        c1: Fp2Var<P::Fp2Params>,
+        c2: Fp2Var<P::Fp2Params>,
+    ) -> Self {
+        Fp6Var { c0, c1, c2 }
+    }
+
+    pub fn constant(c: Fp6<P>) -> Self {
+        Self::new(
+            Fp2Var::constant(c.c0),
+            Fp2Var::constant(c.c1),
+            Fp2Var::constant(c.c2),
+        )
+    }
+
+    pub fn alloc<CS: ConstraintSystem<Fp<P>>>(
+        mut cs: CS,
+        value: Option<Fp6<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp2Var::alloc(cs.ns(|| "c0"), value.map(|v| v.c0))?;
+        let c1 = Fp2Var::alloc(cs.ns(|| "c1"), value.map(|v| v.c1))?;
+        let c2 = Fp2Var::alloc(cs.ns(|| "c2"), value.map(|v| v.c2))?;
+
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    pub fn get_value(&self) -> Option<Fp6<P>> {
+        match (
+            self.c0.get_value(),
+            self.c1.get_value(),
+            self.c2.get_value(),
+        ) {
+            (Some(c0), Some(c1), Some(c2)) => Some(Fp6::new(c0, c1, c2)),
+            _ => None,
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(
+            self.c0.add(&other.c0),
+            self.c1.add(&other.c1),
+            self.c2.add(&other.c2),
+        )
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        Self::new(
+            self.c0.sub(&other.c0),
+            self.c1.sub(&other.c1),
+            self.c2.sub(&other.c2),
+        )
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.c0.neg(), self.c1.neg(), self.c2.neg())
+    }
+
+    pub fn double(&self) -> Self {
+        self.add(self)
+    }
+
+    fn mul_fp2_by_nonresidue(fe: &Fp2Var<P::Fp2Params>) -> Fp2Var<P::Fp2Params> {
+        fe.mul_by_constant(&P::NONRESIDUE)
+    }
+
+    /// The Karatsuba product, in 6 products of `Fp2`.
+    pub fn mul<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let v0 = self.c0.mul(cs.ns(|| "v0"), &other.c0)?;
+        let v1 = self.c1.mul(cs.ns(|| "v1"), &other.c1)?;
+        let v2 = self.c2.mul(cs.ns(|| "v2"), &other.c2)?;
+
+        let c0 = Self::mul_fp2_by_nonresidue(
+            &self
+                .c1
+                .add(&self.c2)
+                .mul(cs.ns(|| "c0"), &other.c1.add(&other.c2))?
+                .sub(&v1)
+                .sub(&v2),
+        )
+        .add(&v0);
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(cs.ns(|| "c1"), &other.c0.add(&other.c1))?
+            .sub(&v0)
+            .sub(&v1)
+            .add(&Self::mul_fp2_by_nonresidue(&v2));
+        let c2 = self
+            .c0
+            .add(&self.c2)
+            .mul(cs.ns(|| "c2"), &other.c0.add(&other.c2))?
+            .sub(&v0)
+            .sub(&v2)
+            .add(&v1);
+
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    /// The squaring of Chung and Hasan, in 3 squares and 2 products of
+    /// `Fp2`.
+    pub fn square<CS: ConstraintSystem<Fp<P>>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let s0 = self.c0.square(cs.ns(|| "s0"))?;
+        let s1 = self.c0.mul(cs.ns(|| "s1"), &self.c1)?.double();
+        let s2 = self.c0.sub(&self.c1).add(&self.c2).square(cs.ns(|| "s2"))?;
+        let s3 = self.c1.mul(cs.ns(|| "s3"), &self.c2)?.double();
+        let s4 = self.c2.square(cs.ns(|| "s4"))?;
+
+        let c0 = s0.add(&Self::mul_fp2_by_nonresidue(&s3));
+        let c1 = s1.add(&Self::mul_fp2_by_nonresidue(&s4));
+        let c2 = s1.add(&s2).add(&s3).sub(&s0).sub(&s4);
+
+        Ok(Self::new(c0, c1, c2))
+    }
+
+    /// `self * fe` of an element of `Fp2`.
+    pub fn mul_by_fp2<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        fe: &Fp2Var<P::Fp2Params>,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self::new(
+            self.c0.mul(cs.ns(|| "c0"), fe)?,
+            self.c1.mul(cs.ns(|| "c1"), fe)?,
+            self.c2.mul(cs.ns(|| "c2"), fe)?,
+        ))
+    }
+
+    /// `self * c1 * v`.
+    pub fn mul_by_1<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        c1: &Fp2Var<P::Fp2Params>,
+    ) -> Result<Self, SynthesisError> {
+        let b_b = self.c1.mul(cs.ns(|| "b_b"), c1)?;
+        let t1 =
+            Self::mul_fp2_by_nonresidue(&c1.mul(cs.ns(|| "t1"), &self.c1.add(&self.c2))?.sub(&b_b));
+        let t2 = c1.mul(cs.ns(|| "t2"), &self.c0.add(&self.c1))?.sub(&b_b);
+
+        Ok(Self::new(t1, t2, b_b))
+    }
+
+    /// `self * (c0 + c1 * v)`.
+    pub fn mul_by_01<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        c0: &Fp2Var<P::Fp2Params>,
+        c1: &Fp2Var<P::Fp2Params>,
+    ) -> Result<Self, SynthesisError> {
+        let a_a = self.c0.mul(cs.ns(|| "a_a"), c0)?;
+        let b_b = self.c1.mul(cs.ns(|| "b_b"), c1)?;
+
+        let t1 =
+            Self::mul_fp2_by_nonresidue(&c1.mul(cs.ns(|| "t1"), &self.c1.add(&self.c2))?.sub(&b_b))
+                .add(&a_a);
+        let t3 = c0
+            .mul(cs.ns(|| "t3"), &self.c0.add(&self.c2))?
+            .sub(&a_a)
+            .add(&b_b);
+        let t2 = c0
+            .add(c1)
+            .mul(cs.ns(|| "t2"), &self.c0.add(&self.c1))?
+            .sub(&a_a)
+            .sub(&b_b);
+
+        Ok(Self::new(t1, t2, t3))
+    }
+
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        Self::new(
+            self.c0.frobenius_map(power),
+            self.c1
+                .frobenius_map(power)
+                .mul_by_constant(&P::FROBENIUS_COEFF_FP6_C1[power % 6]),
+            self.c2
+                .frobenius_map(power)
+                .mul_by_constant(&P::FROBENIUS_COEFF_FP6_C2[power % 6]),
+        )
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.c0.enforce_equal(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.enforce_equal(cs.ns(|| "c1"), &other.c1)?;
+        self.c2.enforce_equal(cs.ns(|| "c2"), &other.c2)
+    }
+
+    fn is_eq_bits<CS: ConstraintSystem<Fp<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Vec<Boolean>, SynthesisError> {
+        let mut bits = self.c0.is_eq_bits(cs.ns(|| "c0"), &other.c0)?;
+        bits.extend(self.c1.is_eq_bits(cs.ns(|| "c1"), &other.c1)?);
+        bits.extend(self.c2.is_eq_bits(cs.ns(|| "c2"), &other.c2)?);
+
+        Ok(bits)
+    }
+}
+
+/// An element `c0 + c1 * w` of `Fp12`.
+pub struct Fp12Var<P: Fp12Parameters> {
+    pub c0: Fp6Var<P::Fp6Params>,
+    pub c1: Fp6Var<P::Fp6Params>,
+}
+
+impl<P: Fp12Parameters> Clone for Fp12Var<P> {
+    fn clone(&self) -> Self {
+        Self::new(self.c0.clone(), self.c1.clone())
+    }
+}
+
+type Fp2Params<P> = <<P as Fp12Parameters>::Fp6Params as Fp6Parameters>::Fp2Params;
+type Fq<P> = <Fp2Params<P> as Fp2Parameters>::Fp;
+
+impl<P: Fp12Parameters> Fp12Var<P> {
+    pub fn new(c0: Fp6Var<P::Fp6Params>, c1: Fp6Var<P::Fp6Params>) -> Self {
+        Fp12Var { c0, c1 }
+    }
+
+    pub fn constant(c: Fp12<P>) -> Self {
+        Self::new(Fp6Var::constant(c.c0), Fp6Var::constant(c.c1))
+    }
+
+    pub fn one() -> Self {
+        Self::constant(Fp12::one())
+    }
+
+    pub fn alloc<CS: ConstraintSystem<Fq<P>>>(
+        mut cs: CS,
+        value: Option<Fp12<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let c0 = Fp6Var::alloc(cs.ns(|| "c0"), value.map(|v| v.c0))?;
+        let c1 = Fp6Var::alloc(cs.ns(|| "c1"), value.map(|v| v.c1))?;
+
+        Ok(Self::new(c0, c1))
+    }
+
+    pub fn get_value(&self) -> Option<Fp12<P>> {
+        match (self.c0.get_value(), self.c1.get_value()) {
+            (Some(c0), Some(c1)) => Some(Fp12::new(c0, c1)),
+            _ => None,
+        }
+    }
+
+    fn mul_fp6_by_nonresidue(fe: &Fp6Var<P::Fp6Params>) -> Fp6Var<P::Fp6Params> {
+        Fp6Var::new(
+            fe.c2
+                .mul_by_constant(&<P::Fp6Params as Fp6Parameters>::NONRESIDUE),
+            fe.c0.clone(),
+            fe.c1.clone(),
+        )
+    }
+
+    /// The Karatsuba product, in 3 products of `Fp6`.
+    pub fn mul<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Self, SynthesisError> {
+        let v0 = self.c0.mul(cs.ns(|| "v0"), &other.c0)?;
+        let v1 = self.c1.mul(cs.ns(|| "v1"), &other.c1)?;
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul(cs.ns(|| "c1"), &other.c0.add(&other.c1))?
+            .sub(&v0)
+            .sub(&v1);
+        let c0 = v0.add(&Self::mul_fp6_by_nonresidue(&v1));
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// The complex squaring, in 2 products of `Fp6`.
+    pub fn square<CS: ConstraintSystem<Fq<P>>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let ab = self.c0.mul(cs.ns(|| "ab"), &self.c1)?;
+        let c0c1 = self.c0.add(&self.c1);
+        let c0 = Self::mul_fp6_by_nonresidue(&self.c1)
+            .add(&self.c0)
+            .mul(cs.ns(|| "c0"), &c0c1)?
+            .sub(&ab)
+            .sub(&Self::mul_fp6_by_nonresidue(&ab));
+        let c1 = ab.double();
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// The squaring of Granger and Scott, for elements of the cyclotomic
+    /// subgroup only, in 6 products of `Fp2`.
+    pub fn cyclotomic_square<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+    ) -> Result<Self, SynthesisError> {
+        let fp2_nr = Fp6Var::<P::Fp6Params>::mul_fp2_by_nonresidue;
+
+        let z0 = &self.c0.c0;
+        let z4 = &self.c0.c1;
+        let z3 = &self.c0.c2;
+        let z2 = &self.c1.c0;
+        let z1 = &self.c1.c1;
+        let z5 = &self.c1.c2;
+
+        // t0 + t1*y = (z0 + z1*y)^2 = a^2
+        let tmp = z0.mul(cs.ns(|| "z0 * z1"), z1)?;
+        let t0 = z0
+            .add(z1)
+            .mul(cs.ns(|| "t0"), &z0.add(&fp2_nr(z1)))?
+            .sub(&tmp)
+            .sub(&fp2_nr(&tmp));
+        let t1 = tmp.double();
+
+        // t2 + t3*y = (z2 + z3*y)^2 = b^2
+        let tmp = z2.mul(cs.ns(|| "z2 * z3"), z3)?;
+        let t2 = z2
+            .add(z3)
+            .mul(cs.ns(|| "t2"), &z2.add(&fp2_nr(z3)))?
+            .sub(&tmp)
+            .sub(&fp2_nr(&tmp));
+        let t3 = tmp.double();
+
+        // t4 + t5*y = (z4 + z5*y)^2 = c^2
+        let tmp = z4.mul(cs.ns(|| "z4 * z5"), z5)?;
+        let t4 = z4
+            .add(z5)
+            .mul(cs.ns(|| "t4"), &z4.add(&fp2_nr(z5)))?
+            .sub(&tmp)
+            .sub(&fp2_nr(&tmp));
+        let t5 = tmp.double();
+
+        // z0 = 3 * t0 - 2 * z0, z1 = 3 * t1 + 2 * z1
+        let r00 = t0.sub(z0).double().add(&t0);
+        let r11 = t1.add(z1).double().add(&t1);
+        // z2 = 3 * (xi * t5) + 2 * z2, z3 = 3 * t4 - 2 * z3
+        let tmp = fp2_nr(&t5);
+        let r10 = tmp.add(z2).double().add(&tmp);
+        let r02 = t4.sub(z3).double().add(&t4);
+        // z4 = 3 * t2 - 2 * z4, z5 = 3 * t3 + 2 * z5
+        let r01 = t2.sub(z4).double().add(&t2);
+        let r12 = t3.add(z5).double().add(&t3);
+
+        Ok(Self::new(
+            Fp6Var::new(r00, r01, r02),
+            Fp6Var::new(r10, r11, r12),
+        ))
+    }
+
+    /// `self^exp` by cyclotomic squarings, for elements of the cyclotomic
+    /// subgroup only.
+    pub fn cyclotomic_exp<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        exp: &[u64],
+    ) -> Result<Self, SynthesisError> {
+        let mut res = Self::one();
+        let mut found_one = false;
+
+        for (i, bit) in BitIterator::new(exp).enumerate() {
+            if !found_one {
+                if bit {
+                    found_one = true;
+                } else {
+                    continue;
+                }
+            }
+
+            res = res.cyclotomic_square(cs.ns(|| format!("square {}", i)))?;
+            if bit {
+                res = res.mul(cs.ns(|| format!("mul {}", i)), self)?;
+            }
+        }
+
+        Ok(res)
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0.clone(), self.c1.neg())
+    }
+
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        let coeff = P::FROBENIUS_COEFF_FP12_C1[power % 12];
+        let c1 = self.c1.frobenius_map(power);
+
+        Self::new(
+            self.c0.frobenius_map(power),
+            Fp6Var::new(
+                c1.c0.mul_by_constant(&coeff),
+                c1.c1.mul_by_constant(&coeff),
+                c1.c2.mul_by_constant(&coeff),
+            ),
+        )
+    }
+
+    /// `self * (c0 + c3 * w + c4 * v * w)`, the line of a D-type twist.
+    pub fn mul_by_034<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        c0: &Fp2Var<Fp2Params<P>>,
+        c3: &Fp2Var<Fp2Params<P>>,
+        c4: &Fp2Var<Fp2Params<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let a = self.c0.mul_by_fp2(cs.ns(|| "a"), c0)?;
+        let b = self.c1.mul_by_01(cs.ns(|| "b"), c3, c4)?;
+
+        let e = self
+            .c0
+            .add(&self.c1)
+            .mul_by_01(cs.ns(|| "e"), &c0.add(c3), c4)?;
+        let c1 = e.sub(&a.add(&b));
+        let c0 = a.add(&Self::mul_fp6_by_nonresidue(&b));
+
+        Ok(Self::new(c0, c1))
+    }
+
+    /// `self * (c0 + c1 * v + c4 * v * w)`, the line of an M-type twist.
+    pub fn mul_by_014<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        c0: &Fp2Var<Fp2Params<P>>,
+        c1: &Fp2Var<Fp2Params<P>>,
+        c4: &Fp2Var<Fp2Params<P>>,
+    ) -> Result<Self, SynthesisError> {
+        let aa = self.c0.mul_by_01(cs.ns(|| "aa"), c0, c1)?;
+        let bb = self.c1.mul_by_1(cs.ns(|| "bb"), c4)?;
+        let o = c1.add(c4);
+
+        let new_c1 = self
+            .c1
+            .add(&self.c0)
+            .mul_by_01(cs.ns(|| "c1"), c0, &o)?
+            .sub(&aa)
+            .sub(&bb);
+        let new_c0 = Self::mul_fp6_by_nonresidue(&bb).add(&aa);
+
+        Ok(Self::new(new_c0, new_c1))
+    }
+
+    /// `self^-1`, enforced by `self * inverse = 1`, unsatisfiable for zero.
+    pub fn inverse<CS: ConstraintSystem<Fq<P>>>(&self, mut cs: CS) -> Result<Self, SynthesisError> {
+        let inverse = Self::alloc(
+            cs.ns(|| "inverse"),
+            self.get_value()
+                .map(|v| v.inverse().unwrap_or_else(Fp12::zero)),
+        )?;
+        let product = self.mul(cs.ns(|| "self * inverse"), &inverse)?;
+        product.enforce_equal(cs.ns(|| "self * inverse = 1"), &Self::one())?;
+
+        Ok(inverse)
+    }
+
+    pub fn enforce_equal<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(), SynthesisError> {
+        self.c0.enforce_equal(cs.ns(|| "c0"), &other.c0)?;
+        self.c1.enforce_equal(cs.ns(|| "c1"), &other.c1)
+    }
+
+    /// Whether `self` equals `other`, of the equality of the 12 elements of
+    /// `Fp`.
+    pub fn is_eq<CS: ConstraintSystem<Fq<P>>>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<Boolean, SynthesisError> {
+        let mut bits = self.c0.is_eq_bits(cs.ns(|| "c0"), &other.c0)?;
+        bits.extend(self.c1.is_eq_bits(cs.ns(|| "c1"), &other.c1)?);
+
+        all(cs.ns(|| "all equal"), &bits)
+    }
+}
+
+#[cfg(all(test, feature = "bls12_377"))]
+mod test {
+    use curve::bls12_377::{Fq, Fq12, Fq12Parameters, Fq2, Fq2Parameters, Fq6, Fq6Parameters};
+    use math::{test_rng, Field, One};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::super::boolean::{AllocatedBit, Boolean};
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_fp() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let (a, b, c) = (rng.gen::<Fq>(), rng.gen::<Fq>(), rng.gen::<Fq>());
+        let a_var = FpVar::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+        let b_var = FpVar::alloc(cs.ns(|| "b"), Some(b)).unwrap();
+        let c_var = FpVar::constant(c);
+
+        // linear combinations and products by constants are free.
+        let d = a_var.add(&c_var).sub(&b_var.double()).scale(c);
+        let d = d.mul(cs.ns(|| "by constant"), &c_var).unwrap();
+        assert_eq!(cs.num_constraints(), 0);
+        assert_eq!(d.get_value(), Some((a + &c - &b.double()) * &c * &c));
+
+        let e = a_var.mul(cs.ns(|| "a * b"), &b_var).unwrap();
+        let f = b_var.inverse(cs.ns(|| "b inverse")).unwrap();
+        assert_eq!(cs.num_constraints(), 2);
+        assert_eq!(e.get_value(), Some(a * &b));
+        assert_eq!(f.get_value(), b.inverse());
+
+        let is_eq = e
+            .is_eq(cs.ns(|| "is eq"), &FpVar::constant(a * &b))
+            .unwrap();
+        assert_eq!(is_eq.get_value(), Some(true));
+        let is_eq = e.is_eq(cs.ns(|| "is not eq"), &a_var).unwrap();
+        assert_eq!(is_eq.get_value(), Some(false));
+
+        for &cond in [false, true].iter() {
+            let bit = Boolean::from(
+                AllocatedBit::alloc(cs.ns(|| format!("cond {}", cond)), Some(cond)).unwrap(),
+            );
+            let expected = if cond { a } else { b };
+            let s = FpVar::conditionally_select(
+                cs.ns(|| format!("select {}", cond)),
+                &bit,
+                &a_var,
+                &b_var,
+            )
+            .unwrap();
+            assert_eq!(s.get_value(), Some(expected));
+
+            // constants are selected without constraints.
+            let before = cs.num_constraints();
+            let expected = if cond { c } else { a * &c };
+            let s = FpVar::conditionally_select(
+                cs.ns(|| format!("select constants {}", cond)),
+                &bit,
+                &c_var,
+                &FpVar::constant(a * &c),
+            )
+            .unwrap();
+            assert_eq!(cs.num_constraints(), before);
+            let s = s.mul(cs.ns(|| format!("use {}", cond)), &a_var).unwrap();
+            assert_eq!(s.get_value(), Some(expected * &a));
+        }
+        assert!(cs.is_satisfied());
+
+        cs.set("a * b/product/fp", a);
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_fp2() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let (a, b, c) = (rng.gen::<Fq2>(), rng.gen::<Fq2>(), rng.gen::<Fq2>());
+        let a_var = Fp2Var::<Fq2Parameters>::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+        let b_var = Fp2Var::alloc(cs.ns(|| "b"), Some(b)).unwrap();
+
+        assert_eq!(
+            a_var.mul(cs.ns(|| "a * b"), &b_var).unwrap().get_value(),
+            Some(a * &b)
+        );
+        assert_eq!(cs.num_constraints(), 3);
+        assert_eq!(
+            a_var.square(cs.ns(|| "a^2")).unwrap().get_value(),
+            Some(a.square())
+        );
+        assert_eq!(cs.num_constraints(), 3 + 2);
+        assert_eq!(a_var.mul_by_constant(&c).get_value(), Some(a * &c));
+        assert_eq!(
+            a_var
+                .mul_by_fp(cs.ns(|| "a * c0"), &b_var.c0)
+                .unwrap()
+                .get_value(),
+            Some(Fq2::new(a.c0 * &b.c0, a.c1 * &b.c0))
+        );
+        assert_eq!(
+            a_var.inverse(cs.ns(|| "a inverse")).unwrap().get_value(),
+            a.inverse()
+        );
+        for power in 0..2 {
+            let mut expected = a;
+            expected.frobenius_map(power);
+            assert_eq!(a_var.frobenius_map(power).get_value(), Some(expected));
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_fp6() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let (a, b) = (rng.gen::<Fq6>(), rng.gen::<Fq6>());
+        let (c0, c1) = (rng.gen::<Fq2>(), rng.gen::<Fq2>());
+        let a_var = Fp6Var::<Fq6Parameters>::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+        let b_var = Fp6Var::alloc(cs.ns(|| "b"), Some(b)).unwrap();
+        let c0_var = Fp2Var::alloc(cs.ns(|| "c0"), Some(c0)).unwrap();
+        let c1_var = Fp2Var::alloc(cs.ns(|| "c1"), Some(c1)).unwrap();
+
+        assert_eq!(
+            a_var.mul(cs.ns(|| "a * b"), &b_var).unwrap().get_value(),
+            Some(a * &b)
+        );
+        assert_eq!(
+            a_var.square(cs.ns(|| "a^2")).unwrap().get_value(),
+            Some(a.square())
+        );
+
+        let mut expected = a;
+        expected.mul_by_01(&c0, &c1);
+        assert_eq!(
+            a_var
+                .mul_by_01(cs.ns(|| "a * c01"), &c0_var, &c1_var)
+                .unwrap()
+                .get_value(),
+            Some(expected)
+        );
+        let mut expected = a;
+        expected.mul_by_1(&c1);
+        assert_eq!(
+            a_var
+                .mul_by_1(cs.ns(|| "a * c1"), &c1_var)
+                .unwrap()
+                .get_value(),
+            Some(expected)
+        );
+        for power in 0..6 {
+            let mut expected = a;
+            expected.frobenius_map(power);
+            assert_eq!(a_var.frobenius_map(power).get_value(), Some(expected));
+        }
+        assert!(cs.is_satisfied());
+    }
+
+    #[test]
+    fn test_fp12() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let (a, b) = (rng.gen::<Fq12>(), rng.gen::<Fq12>());
+        let (c0, c1, c2) = (rng.gen::<Fq2>(), rng.gen::<Fq2>(), rng.gen::<Fq2>());
+        let a_var = Fp12Var::<Fq12Parameters>::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+        let b_var = Fp12Var::alloc(cs.ns(|| "b"), Some(b)).unwrap();
+        let c0_var = Fp2Var::alloc(cs.ns(|| "c0"), Some(c0)).unwrap();
+        let c1_var = Fp2Var::alloc(cs.ns(|| "c1"), Some(c1)).unwrap();
+        let c2_var = Fp2Var::alloc(cs.ns(|| "c2"), Some(c2)).unwrap();
+
+        assert_eq!(
+            a_var.mul(cs.ns(|| "a * b"), &b_var).unwrap().get_value(),
+            Some(a * &b)
+        );
+        assert_eq!(
+            a_var.square(cs.ns(|| "a^2")).unwrap().get_value(),
+            Some(a.square())
+        );
+        assert_eq!(
+            a_var.inverse(cs.ns(|| "a inverse")).unwrap().get_value(),
+            a.inverse()
+        );
+
+        let mut expected = a;
+        expected.mul_by_034(&c0, &c1, &c2);
+        assert_eq!(
+            a_var
+                .mul_by_034(cs.ns(|| "034"), &c0_var, &c1_var, &c2_var)
+                .unwrap()
+                .get_value(),
+            Some(expected)
+        );
+        let mut expected = a;
+        expected.mul_by_014(&c0, &c1, &c2);
+        assert_eq!(
+            a_var
+                .mul_by_014(cs.ns(|| "014"), &c0_var, &c1_var, &c2_var)
+                .unwrap()
+                .get_value(),
+            Some(expected)
+        );
+        for power in 0..12 {
+            let mut expected = a;
+            expected.frobenius_map(power);
+            assert_eq!(a_var.frobenius_map(power).get_value(), Some(expected));
+        }
+
+        // an element of the cyclotomic subgroup, a^((p^6 - 1)(p^2 + 1)).
+        let mut f = a;
+        f.frobenius_map(6);
+        let f = f * &a.inverse().unwrap();
+        let mut g = f;
+        g.frobenius_map(2);
+        let g = g * &f;
+        let g_var = Fp12Var::alloc(cs.ns(|| "g"), Some(g)).unwrap();
+        assert_eq!(
+            g_var
+                .cyclotomic_square(cs.ns(|| "g^2"))
+                .unwrap()
+                .get_value(),
+            Some(g.square())
+        );
+        let exp = [0x8508c00000000001u64];
+        assert_eq!(
+            g_var
+                .cyclotomic_exp(cs.ns(|| "g^x"), &exp)
+                .unwrap()
+                .get_value(),
+            Some(g.pow(&exp))
+        );
+
+        let is_one = g_var.is_eq(cs.ns(|| "g is one"), &Fp12Var::one()).unwrap();
+        assert_eq!(is_one.get_value(), Some(false));
+        let one = Fp12Var::alloc(cs.ns(|| "one"), Some(Fq12::one())).unwrap();
+        let is_one = one.is_eq(cs.ns(|| "one is one"), &Fp12Var::one()).unwrap();
+        assert_eq!(is_one.get_value(), Some(true));
+        assert!(cs.is_satisfied());
+    }
+}
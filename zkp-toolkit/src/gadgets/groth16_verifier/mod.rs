@@ -0,0 +1,275 @@
+//! The verifier of Groth16 proofs over a BLS12 curve, in a circuit over its
+//! base field, such as BLS12-377 proofs in BW6-761 circuits, for proofs of
+//! proofs.
+//!
+//! The base field of the inner curve is the outer field, so its arithmetic
+//! is native, and the pairing check `e(A, B) = e(alpha, beta) *
+//! e(acc, gamma) * e(C, delta)` of `scheme::groth16::verify_proof` is one
+//! product of 4 Miller loops and a final exponentiation. With a verifying
+//! key of constants, a proof of one public input costs about 21k
+//! constraints, and every further input 2.3k more.
+//!
+//! The points of the proof are enforced to be on their curves, but not to
+//! be in the prime order subgroups, and none of the points may be at
+//! infinity.
+
+use math::curves::bls12::{Bls12, Bls12Parameters};
+use math::{BigInteger, Field, One, PairingEngine, PrimeField};
+use scheme::groth16::{Proof, VerifyKey};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+
+pub mod curves;
+pub mod fields;
+pub mod pairing;
+
+pub use curves::{G1AffineVar, G1ProjectiveVar, G2AffineVar};
+pub use fields::{Fp12Var, Fp2Var, Fp6Var, FpVar};
+pub use pairing::{final_exponentiation, miller_loop};
+
+/// The scalar field of the inner curve, of the public inputs.
+pub type InnerFr<P> = <Bls12<P> as PairingEngine>::Fr;
+
+/// A verifying key of `scheme::groth16`.
+pub struct VerifyKeyVar<P: Bls12Parameters> {
+    pub alpha_g1: G1AffineVar<P>,
+    pub beta_g2: G2AffineVar<P>,
+    pub gamma_g2: G2AffineVar<P>,
+    pub delta_g2: G2AffineVar<P>,
+    pub gamma_abc_g1: Vec<G1AffineVar<P>>,
+}
+
+impl<P: Bls12Parameters> VerifyKeyVar<P> {
+    /// The key of a fixed inner circuit, whose points cost nothing.
+    pub fn constant(vk: &VerifyKey<Bls12<P>>) -> Self {
+        VerifyKeyVar {
+            alpha_g1: G1AffineVar::constant(vk.alpha_g1),
+            beta_g2: G2AffineVar::constant(vk.beta_g2),
+            gamma_g2: G2AffineVar::constant(vk.gamma_g2),
+            delta_g2: G2AffineVar::constant(vk.delta_g2),
+            gamma_abc_g1: vk
+                .gamma_abc_g1
+                .iter()
+                .map(|p| G1AffineVar::constant(*p))
+                .collect(),
+        }
+    }
+
+    /// Allocates the key of an inner circuit of `num_inputs` public inputs,
+    /// of `vk` when it is known.
+    pub fn alloc<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        vk: Option<&VerifyKey<Bls12<P>>>,
+        num_inputs: usize,
+    ) -> Result<Self, SynthesisError> {
+        if let Some(vk) = vk {
+            assert_eq!(vk.gamma_abc_g1.len(), num_inputs + 1);
+        }
+
+        Ok(VerifyKeyVar {
+            alpha_g1: G1AffineVar::alloc(cs.ns(|| "alpha"), vk.map(|vk| vk.alpha_g1))?,
+            beta_g2: G2AffineVar::alloc(cs.ns(|| "beta"), vk.map(|vk| vk.beta_g2))?,
+            gamma_g2: G2AffineVar::alloc(cs.ns(|| "gamma"), vk.map(|vk| vk.gamma_g2))?,
+            delta_g2: G2AffineVar::alloc(cs.ns(|| "delta"), vk.map(|vk| vk.delta_g2))?,
+            gamma_abc_g1: (0..=num_inputs)
+                .map(|i| {
+                    G1AffineVar::alloc(
+                        cs.ns(|| format!("gamma_abc {}", i)),
+                        vk.map(|vk| vk.gamma_abc_g1[i]),
+                    )
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// A proof of `scheme::groth16`.
+pub struct ProofVar<P: Bls12Parameters> {
+    pub a: G1AffineVar<P>,
+    pub b: G2AffineVar<P>,
+    pub c: G1AffineVar<P>,
+}
+
+impl<P: Bls12Parameters> ProofVar<P> {
+    /// Allocates a proof, of points enforced to be on their curves.
+    pub fn alloc<CS: ConstraintSystem<P::Fp>>(
+        mut cs: CS,
+        proof: Option<&Proof<Bls12<P>>>,
+    ) -> Result<Self, SynthesisError> {
+        Ok(ProofVar {
+            a: G1AffineVar::alloc(cs.ns(|| "a"), proof.map(|p| p.a))?,
+            b: G2AffineVar::alloc(cs.ns(|| "b"), proof.map(|p| p.b))?,
+            c: G1AffineVar::alloc(cs.ns(|| "c"), proof.map(|p| p.c))?,
+        })
+    }
+}
+
+/// Deconstructs a public input of the inner circuit, a number of the outer
+/// field, into its `InnerFr::size_in_bits()` little-endian bits, enforced to
+/// be below the inner modulus, so that no other number passes for it.
+pub fn input_to_bits<P, CS>(
+    mut cs: CS,
+    input: &AllocatedFr<P::Fp>,
+) -> Result<Vec<Boolean>, SynthesisError>
+where
+    P: Bls12Parameters,
+    CS: ConstraintSystem<P::Fp>,
+{
+    let repr = input.get_value().map(|v| v.into_repr());
+
+    // big-endian
+    let bits = (0..InnerFr::<P>::size_in_bits())
+        .rev()
+        .map(|i| {
+            let value = repr.map(|r| r.get_bit(i));
+            AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), value).map(Boolean::from)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Boolean::enforce_smaller_or_equal_than(&mut cs, &bits, &(-InnerFr::<P>::one()).into_repr())?;
+
+    let mut lc = LinearCombination::zero();
+    let mut coeff = P::Fp::one();
+    for bit in bits.iter().rev() {
+        lc = lc + &bit.lc(CS::one(), coeff);
+        coeff = coeff.double();
+    }
+    cs.enforce(
+        || "packing",
+        |_| lc,
+        |lc| lc + CS::one(),
+        |lc| lc + input.get_variable(),
+    );
+
+    Ok(bits.into_iter().rev().collect())
+}
+
+/// Whether `proof` is valid for the little-endian bits of the public inputs
+/// under `vk`, as `scheme::groth16::verify_proof` checks it.
+pub fn verify<P, CS>(
+    mut cs: CS,
+    vk: &VerifyKeyVar<P>,
+    proof: &ProofVar<P>,
+    public_inputs: &[Vec<Boolean>],
+) -> Result<Boolean, SynthesisError>
+where
+    P: Bls12Parameters,
+    CS: ConstraintSystem<P::Fp>,
+{
+    if public_inputs.len() + 1 != vk.gamma_abc_g1.len() {
+        return Err(SynthesisError::MalformedVerifyingKey);
+    }
+
+    // acc = gamma_abc_0 + sum_i x_i * gamma_abc_i
+    let mut acc = vk.gamma_abc_g1[0].to_projective();
+    for (i, (bits, base)) in public_inputs
+        .iter()
+        .zip(vk.gamma_abc_g1.iter().skip(1))
+        .enumerate()
+    {
+        let mut cs = cs.ns(|| format!("input {}", i));
+        let term = base.to_projective().mul_bits(cs.ns(|| "mul"), bits)?;
+        acc = acc.add(cs.ns(|| "add"), &term)?;
+    }
+    let acc = acc.to_affine(cs.ns(|| "acc"))?;
+
+    // e(A, B) * e(-alpha, beta) * e(-acc, gamma) * e(-C, delta) = 1
+    let pairs = [
+        (proof.a.clone(), proof.b.clone()),
+        (vk.alpha_g1.neg(), vk.beta_g2.clone()),
+        (acc.neg(), vk.gamma_g2.clone()),
+        (proof.c.neg(), vk.delta_g2.clone()),
+    ];
+    let f = miller_loop::<P, _>(cs.ns(|| "miller loop"), &pairs)?;
+    let f = final_exponentiation::<P, _>(cs.ns(|| "final exponentiation"), &f)?;
+
+    f.is_eq(cs.ns(|| "is one"), &Fp12Var::one())
+}
+
+#[cfg(all(test, feature = "bls12_377"))]
+mod test {
+    use curve::bls12_377::{Bls12_377, Fq, Fr, Parameters};
+    use math::{test_rng, Field};
+    use scheme::groth16::{create_random_proof, generate_random_parameters};
+    use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    /// `x^3 + x + 5 = out`, of the public `out`.
+    struct CubicDemo {
+        x: Option<Fr>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for CubicDemo {
+        fn generate_constraints<CS: ConstraintSystem<Fr>>(
+            self,
+            cs: &mut CS,
+        ) -> Result<(), SynthesisError> {
+            let x_value = self.x;
+            let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+            let x2 = cs.alloc(
+                || "x^2",
+                || {
+                    x_value
+                        .map(|x| x.square())
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?;
+            let out = cs.alloc_input(
+                || "out",
+                || {
+                    x_value
+                        .map(|x| x.square() * &x + &x + &Fr::from(5u32))
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?;
+            cs.enforce(|| "x * x = x^2", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+            cs.enforce(
+                || "x^2 * x = out - x - 5",
+                |lc| lc + x2,
+                |lc| lc + x,
+                |lc| lc + out - x - (Fr::from(5u32), CS::one()),
+            );
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let rng = &mut test_rng();
+        let params =
+            generate_random_parameters::<Bls12_377, _, _>(CubicDemo { x: None }, rng).unwrap();
+        let proof = create_random_proof(
+            &params,
+            CubicDemo {
+                x: Some(Fr::from(3u32)),
+            },
+            rng,
+        )
+        .unwrap();
+
+        for &(out, valid) in [(35u32, true), (36u32, false)].iter() {
+            for &constant_vk in [true, false].iter() {
+                let mut cs = TestConstraintSystem::<Fq>::new();
+                let vk = if constant_vk {
+                    VerifyKeyVar::<Parameters>::constant(&params.vk)
+                } else {
+                    VerifyKeyVar::alloc(cs.ns(|| "vk"), Some(&params.vk), 1).unwrap()
+                };
+                let proof = ProofVar::alloc(cs.ns(|| "proof"), Some(&proof)).unwrap();
+                let input = AllocatedFr::alloc(cs.ns(|| "out"), || Ok(Fq::from(out))).unwrap();
+                let bits = input_to_bits::<Parameters, _>(cs.ns(|| "out bits"), &input).unwrap();
+
+                let result = verify(cs.ns(|| "verify"), &vk, &proof, &[bits]).unwrap();
+                assert_eq!(result.get_value(), Some(valid));
+                assert!(cs.is_satisfied());
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! The optimal ate pairing of a BLS12 curve: the Miller loop of the line
+//! functions of `math::curves::bls12`, and the final exponentiation of
+//! https://eprint.iacr.org/2016/130.pdf, Table 1, step by step as the native
+//! pairing computes them.
+
+use math::curves::{
+    bls12::{Bls12Parameters, TwistType},
+    SWModelParameters,
+};
+use math::{BitIterator, Field, Fp2, One, Zero};
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::curves::{G1AffineVar, G2AffineVar, G2HomProjectiveVar};
+use super::fields::{Fp12Var, Fp2Var};
+
+type LineCoeffs<P> = (
+    Fp2Var<<P as Bls12Parameters>::Fp2Params>,
+    Fp2Var<<P as Bls12Parameters>::Fp2Params>,
+    Fp2Var<<P as Bls12Parameters>::Fp2Params>,
+);
+
+/// Doubles `r`, the coefficients of the tangent line at it.
+fn doubling_step<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    r: &mut G2HomProjectiveVar<P>,
+) -> Result<LineCoeffs<P>, SynthesisError> {
+    let two_inv = Fp2::new(P::Fp::one().double().inverse().unwrap(), P::Fp::zero());
+
+    let a = r.x.mul(cs.ns(|| "a"), &r.y)?.mul_by_constant(&two_inv);
+    let b = r.y.square(cs.ns(|| "b"))?;
+    let c = r.z.square(cs.ns(|| "c"))?;
+    let e = c
+        .double()
+        .add(&c)
+        .mul_by_constant(&P::G2Parameters::COEFF_B);
+    let f = e.double().add(&e);
+    let g = b.add(&f).mul_by_constant(&two_inv);
+    let h = r.y.add(&r.z).square(cs.ns(|| "h"))?.sub(&b.add(&c));
+    let i = e.sub(&b);
+    let j = r.x.square(cs.ns(|| "j"))?;
+    let e_square = e.square(cs.ns(|| "e^2"))?;
+
+    r.x = a.mul(cs.ns(|| "x"), &b.sub(&f))?;
+    r.y = g
+        .square(cs.ns(|| "g^2"))?
+        .sub(&e_square.double().add(&e_square));
+    r.z = b.mul(cs.ns(|| "z"), &h)?;
+
+    Ok(match P::TWIST_TYPE {
+        TwistType::M => (i, j.double().add(&j), h.neg()),
+        TwistType::D => (h.neg(), j.double().add(&j), i),
+    })
+}
+
+/// Adds `q` to `r`, the coefficients of the line through them.
+fn addition_step<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    r: &mut G2HomProjectiveVar<P>,
+    q: &G2AffineVar<P>,
+) -> Result<LineCoeffs<P>, SynthesisError> {
+    let theta = r.y.sub(&q.y.mul(cs.ns(|| "q.y * r.z"), &r.z)?);
+    let lambda = r.x.sub(&q.x.mul(cs.ns(|| "q.x * r.z"), &r.z)?);
+    let c = theta.square(cs.ns(|| "c"))?;
+    let d = lambda.square(cs.ns(|| "d"))?;
+    let e = lambda.mul(cs.ns(|| "e"), &d)?;
+    let f = r.z.mul(cs.ns(|| "f"), &c)?;
+    let g = r.x.mul(cs.ns(|| "g"), &d)?;
+    let h = e.add(&f).sub(&g.double());
+    r.x = lambda.mul(cs.ns(|| "x"), &h)?;
+    r.y = theta
+        .mul(cs.ns(|| "theta * (g - h)"), &g.sub(&h))?
+        .sub(&e.mul(cs.ns(|| "e * r.y"), &r.y)?);
+    r.z = r.z.mul(cs.ns(|| "z"), &e)?;
+    let j = theta
+        .mul(cs.ns(|| "theta * q.x"), &q.x)?
+        .sub(&lambda.mul(cs.ns(|| "lambda * q.y"), &q.y)?);
+
+    Ok(match P::TWIST_TYPE {
+        TwistType::M => (j, theta.neg(), lambda),
+        TwistType::D => (lambda, theta.neg(), j),
+    })
+}
+
+/// The coefficients of the lines of the Miller loop of `q`, free for a
+/// constant `q`.
+fn prepare_g2<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    q: &G2AffineVar<P>,
+) -> Result<Vec<LineCoeffs<P>>, SynthesisError> {
+    let mut coeffs = vec![];
+    let mut r = G2HomProjectiveVar::from_affine(q);
+
+    for (i, bit) in BitIterator::new(P::X).skip(1).enumerate() {
+        coeffs.push(doubling_step(cs.ns(|| format!("double {}", i)), &mut r)?);
+
+        if bit {
+            coeffs.push(addition_step(cs.ns(|| format!("add {}", i)), &mut r, q)?);
+        }
+    }
+
+    Ok(coeffs)
+}
+
+/// `f * l(p)`, of the line `l` of `coeffs` evaluated at `p`.
+fn ell<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    f: &Fp12Var<P::Fp12Params>,
+    coeffs: &LineCoeffs<P>,
+    p: &G1AffineVar<P>,
+) -> Result<Fp12Var<P::Fp12Params>, SynthesisError> {
+    let (c0, c1, c2) = coeffs;
+
+    match P::TWIST_TYPE {
+        TwistType::M => {
+            let c2 = c2.mul_by_fp(cs.ns(|| "c2 * p.y"), &p.y)?;
+            let c1 = c1.mul_by_fp(cs.ns(|| "c1 * p.x"), &p.x)?;
+            f.mul_by_014(cs.ns(|| "mul by 014"), c0, &c1, &c2)
+        }
+        TwistType::D => {
+            let c0 = c0.mul_by_fp(cs.ns(|| "c0 * p.y"), &p.y)?;
+            let c1 = c1.mul_by_fp(cs.ns(|| "c1 * p.x"), &p.x)?;
+            f.mul_by_034(cs.ns(|| "mul by 034"), &c0, &c1, c2)
+        }
+    }
+}
+
+/// The product of the Miller loops of the pairs, of which none is at
+/// infinity.
+pub fn miller_loop<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    pairs: &[(G1AffineVar<P>, G2AffineVar<P>)],
+) -> Result<Fp12Var<P::Fp12Params>, SynthesisError> {
+    let coeffs = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, (_, q))| prepare_g2(cs.ns(|| format!("prepare {}", i)), q))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut coeffs = coeffs.iter().map(|c| c.iter()).collect::<Vec<_>>();
+
+    let mut f = Fp12Var::one();
+    for (i, bit) in BitIterator::new(P::X).skip(1).enumerate() {
+        let mut cs = cs.ns(|| format!("bit {}", i));
+        f = f.square(cs.ns(|| "square"))?;
+
+        for (j, ((p, _), coeffs)) in pairs.iter().zip(coeffs.iter_mut()).enumerate() {
+            f = ell(
+                cs.ns(|| format!("double {}", j)),
+                &f,
+                coeffs.next().unwrap(),
+                p,
+            )?;
+        }
+
+        if bit {
+            for (j, ((p, _), coeffs)) in pairs.iter().zip(coeffs.iter_mut()).enumerate() {
+                f = ell(
+                    cs.ns(|| format!("add {}", j)),
+                    &f,
+                    coeffs.next().unwrap(),
+                    p,
+                )?;
+            }
+        }
+    }
+
+    if P::X_IS_NEGATIVE {
+        f = f.conjugate();
+    }
+
+    Ok(f)
+}
+
+fn exp_by_x<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    cs: CS,
+    f: &Fp12Var<P::Fp12Params>,
+) -> Result<Fp12Var<P::Fp12Params>, SynthesisError> {
+    let f = f.cyclotomic_exp(cs, P::X)?;
+
+    Ok(if P::X_IS_NEGATIVE { f.conjugate() } else { f })
+}
+
+/// `f^((p^12 - 1) / r)`, unsatisfiable for zero.
+pub fn final_exponentiation<P: Bls12Parameters, CS: ConstraintSystem<P::Fp>>(
+    mut cs: CS,
+    f: &Fp12Var<P::Fp12Params>,
+) -> Result<Fp12Var<P::Fp12Params>, SynthesisError> {
+    // the easy part, r = f^((p^6 - 1)(p^2 + 1))
+    let f1 = f.frobenius_map(6);
+    let f2 = f.inverse(cs.ns(|| "f^-1"))?;
+    let r = f1.mul(cs.ns(|| "f^(p^6 - 1)"), &f2)?;
+    let f2 = r.clone();
+    let r = r.frobenius_map(2).mul(cs.ns(|| "r"), &f2)?;
+
+    // the hard part
+    let y0 = r.cyclotomic_square(cs.ns(|| "y0"))?.conjugate();
+    let y5 = exp_by_x::<P, _>(cs.ns(|| "y5"), &r)?;
+    let y1 = y5.cyclotomic_square(cs.ns(|| "y1"))?;
+    let y3 = y0.mul(cs.ns(|| "y3"), &y5)?;
+    let y0 = exp_by_x::<P, _>(cs.ns(|| "y0 = y3^x"), &y3)?;
+    let y2 = exp_by_x::<P, _>(cs.ns(|| "y2"), &y0)?;
+    let y4 = exp_by_x::<P, _>(cs.ns(|| "y4"), &y2)?.mul(cs.ns(|| "y4 * y1"), &y1)?;
+    let y1 = exp_by_x::<P, _>(cs.ns(|| "y1 = y4^x"), &y4)?;
+    let y3 = y3.conjugate();
+    let y1 = y1
+        .mul(cs.ns(|| "y1 * y3"), &y3)?
+        .mul(cs.ns(|| "y1 * r"), &r)?;
+    let y3 = r.conjugate();
+    let y0 = y0.mul(cs.ns(|| "y0 * r"), &r)?.frobenius_map(3);
+    let y4 = y4.mul(cs.ns(|| "y4 * y3"), &y3)?.frobenius_map(1);
+    let y5 = y5.mul(cs.ns(|| "y5 * y2"), &y2)?.frobenius_map(2);
+
+    y5.mul(cs.ns(|| "y5 * y0"), &y0)?
+        .mul(cs.ns(|| "y5 * y4"), &y4)?
+        .mul(cs.ns(|| "y5 * y1"), &y1)
+}
+
+#[cfg(all(test, feature = "bls12_377"))]
+mod test {
+    use curve::bls12_377::{Bls12_377, Fq, G1Projective, G2Projective, Parameters};
+    use math::{test_rng, PairingEngine, ProjectiveCurve};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_pairing() {
+        let rng = &mut test_rng();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let p = rng.gen::<G1Projective>().into_affine();
+        let q = rng.gen::<G2Projective>().into_affine();
+        let r = rng.gen::<G1Projective>().into_affine();
+        let s = rng.gen::<G2Projective>().into_affine();
+
+        // allocated points, and constant ones.
+        let pairs = [
+            (
+                G1AffineVar::<Parameters>::alloc(cs.ns(|| "p"), Some(p)).unwrap(),
+                G2AffineVar::alloc(cs.ns(|| "q"), Some(q)).unwrap(),
+            ),
+            (G1AffineVar::constant(r), G2AffineVar::constant(s)),
+        ];
+        let f = miller_loop(cs.ns(|| "miller loop"), &pairs).unwrap();
+        let f =
+            final_exponentiation::<Parameters, _>(cs.ns(|| "final exponentiation"), &f).unwrap();
+        assert_eq!(
+            f.get_value(),
+            Some(Bls12_377::pairing(p, q) * &Bls12_377::pairing(r, s))
+        );
+        assert!(cs.is_satisfied());
+    }
+}
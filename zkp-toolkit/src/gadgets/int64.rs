@@ -0,0 +1,649 @@
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+use super::uint64::UInt64;
+
+/// Represents an interpretation of 64 `Boolean` objects as a signed
+/// integer in two's complement.
+///
+/// The arithmetic packs the operands into one field element, so the field
+/// must be larger than 65 bits. As a field element, the number `v` is
+/// encoded with the offset `v + 2^63`, which is below `2^64` and keeps the
+/// order of the numbers.
+#[derive(Clone)]
+pub struct Int64 {
+    // Least significant bit first, the sign bit last
+    bits: Vec<Boolean>,
+    value: Option<i64>,
+}
+
+/// `2^num_bits`
+fn two_to_the<F: PrimeField>(num_bits: usize) -> F {
+    let mut r = F::one();
+    for _ in 0..num_bits {
+        r.double_in_place();
+    }
+    r
+}
+
+/// The offset encoding of `value`, the bits of `value` with the sign bit
+/// flipped.
+fn offset(value: i64) -> u64 {
+    (value as u64) ^ (1 << 63)
+}
+
+impl Int64 {
+    /// Construct a constant `Int64` from an `i64`
+    pub fn constant(value: i64) -> Self {
+        let bits = (0..64)
+            .map(|i| Boolean::constant((value >> i) & 1 == 1))
+            .collect();
+
+        Int64 {
+            bits,
+            value: Some(value),
+        }
+    }
+
+    /// Allocate an `Int64` in the constraint system
+    pub fn alloc<F, CS>(mut cs: CS, value: Option<i64>) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let bits = (0..64)
+            .map(|i| {
+                Ok(Boolean::from(AllocatedBit::alloc(
+                    cs.ns(|| format!("allocated bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )?))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        Ok(Int64 { bits, value })
+    }
+
+    pub fn get_value(&self) -> Option<i64> {
+        self.value
+    }
+
+    fn is_constant(&self) -> bool {
+        self.bits.iter().all(|b| b.is_constant())
+    }
+
+    /// The sign bit, set when the number is negative. It adds no
+    /// constraints.
+    pub fn is_negative(&self) -> Boolean {
+        self.bits[63]
+    }
+
+    /// Turns this `Int64` into its little-endian bits.
+    pub fn into_bits(self) -> Vec<Boolean> {
+        self.bits
+    }
+
+    /// Converts 64 little-endian two's complement bits into an `Int64`.
+    pub fn from_bits(bits: &[Boolean]) -> Self {
+        assert_eq!(bits.len(), 64);
+
+        let value = bits.iter().rev().try_fold(0u64, |v, b| {
+            b.get_value().map(|b| if b { (v << 1) | 1 } else { v << 1 })
+        });
+
+        Int64 {
+            bits: bits.to_vec(),
+            value: value.map(|v| v as i64),
+        }
+    }
+
+    /// Reinterprets the bits as an unsigned integer, this adds no
+    /// constraints.
+    pub fn into_uint64(self) -> UInt64 {
+        UInt64::from_bits(&self.bits)
+    }
+
+    /// The linear combination packing the bits as an unsigned integer.
+    fn lc<F: PrimeField>(&self, one: Variable) -> LinearCombination<F> {
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in &self.bits {
+            lc = lc + &bit.lc(one, coeff);
+            coeff.double_in_place();
+        }
+
+        lc
+    }
+
+    /// The linear combination of `coeff` times the number.
+    fn signed_lc<F: PrimeField>(&self, one: Variable, coeff: F) -> LinearCombination<F> {
+        let mut lc = LinearCombination::zero();
+        let mut coeff = coeff;
+        for bit in &self.bits[..63] {
+            lc = lc + &bit.lc(one, coeff);
+            coeff.double_in_place();
+        }
+
+        lc - &self.bits[63].lc(one, coeff)
+    }
+
+    /// The linear combination of the offset encoding of the number, whose
+    /// top bit is the negated sign bit.
+    fn offset_lc<F: PrimeField>(&self, one: Variable) -> LinearCombination<F> {
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in &self.bits[..63] {
+            lc = lc + &bit.lc(one, coeff);
+            coeff.double_in_place();
+        }
+
+        lc + &self.bits[63].not().lc(one, coeff)
+    }
+
+    /// Allocates the 65 bits of `value` and `top`, enforced to pack into
+    /// `lc`, and returns the low 64 bits along with the top one.
+    fn alloc_packing<F, CS>(
+        mut cs: CS,
+        lc: LinearCombination<F>,
+        value: Option<i64>,
+        top: Option<bool>,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 65);
+
+        let result = Self::alloc(cs.ns(|| "result"), value)?;
+        let top = Boolean::from(AllocatedBit::alloc(cs.ns(|| "top bit"), top)?);
+
+        let result_lc = result.lc(CS::one()) + &top.lc(CS::one(), two_to_the::<F>(64));
+
+        cs.enforce(|| "packing", |_| lc, |lc| lc + CS::one(), |_| result_lc);
+
+        Ok((result, top))
+    }
+
+    /// Adds `other`, returning the sum modulo 2^64 and whether the signed
+    /// addition overflowed, i.e. whether both operands have the same sign
+    /// and the sum another one.
+    pub fn overflowing_add<F, CS>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a.overflowing_add(b)),
+            _ => None,
+        };
+
+        if self.is_constant() && other.is_constant() {
+            let (sum, overflow) = value.unwrap();
+            return Ok((Self::constant(sum), Boolean::constant(overflow)));
+        }
+
+        let carry = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some((a as u64).overflowing_add(b as u64).1),
+            _ => None,
+        };
+        let lc = self.lc(CS::one()) + &other.lc(CS::one());
+        let (sum, _) = Self::alloc_packing(&mut cs, lc, value.map(|v| v.0), carry)?;
+
+        let (a, b, r) = (self.is_negative(), other.is_negative(), sum.is_negative());
+        let signs_differ = Boolean::xor(cs.ns(|| "operand signs"), &a, &b)?;
+        let sign_changed = Boolean::xor(cs.ns(|| "result sign"), &r, &a)?;
+        let overflow = Boolean::and(cs.ns(|| "overflow"), &signs_differ.not(), &sign_changed)?;
+
+        Ok((sum, overflow))
+    }
+
+    /// Adds `other` modulo 2^64.
+    pub fn wrapping_add<F, CS>(&self, cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_add(cs, other)?.0)
+    }
+
+    /// Subtracts `other`, returning the difference modulo 2^64 and whether
+    /// the signed subtraction overflowed, i.e. whether the operands have
+    /// different signs and the difference the sign of `other`.
+    pub fn overflowing_sub<F, CS>(
+        &self,
+        mut cs: CS,
+        other: &Self,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a.overflowing_sub(b)),
+            _ => None,
+        };
+
+        if self.is_constant() && other.is_constant() {
+            let (difference, overflow) = value.unwrap();
+            return Ok((Self::constant(difference), Boolean::constant(overflow)));
+        }
+
+        // self + 2^64 - other takes 65 bits, whose top one is set exactly
+        // when there is no unsigned borrow.
+        let no_borrow = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a as u64 >= b as u64),
+            _ => None,
+        };
+        let lc = self.lc(CS::one()) - &other.lc(CS::one()) + (two_to_the::<F>(64), CS::one());
+        let (difference, _) = Self::alloc_packing(&mut cs, lc, value.map(|v| v.0), no_borrow)?;
+
+        let (a, b, r) = (
+            self.is_negative(),
+            other.is_negative(),
+            difference.is_negative(),
+        );
+        let signs_differ = Boolean::xor(cs.ns(|| "operand signs"), &a, &b)?;
+        let sign_changed = Boolean::xor(cs.ns(|| "result sign"), &r, &a)?;
+        let overflow = Boolean::and(cs.ns(|| "overflow"), &signs_differ, &sign_changed)?;
+
+        Ok((difference, overflow))
+    }
+
+    /// Subtracts `other` modulo 2^64.
+    pub fn wrapping_sub<F, CS>(&self, cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_sub(cs, other)?.0)
+    }
+
+    /// Negates the number, returning `-self` modulo 2^64 and whether it
+    /// overflowed, which only `i64::MIN` does.
+    pub fn overflowing_neg<F, CS>(&self, cs: CS) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Self::constant(0).overflowing_sub(cs, self)
+    }
+
+    /// Negates the number modulo 2^64.
+    pub fn wrapping_neg<F, CS>(&self, cs: CS) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_neg(cs)?.0)
+    }
+
+    /// The absolute value modulo 2^64, and whether it overflowed, which
+    /// only `i64::MIN` does. Its bits are the ones of the absolute value
+    /// as an unsigned integer, see `into_uint64`.
+    ///
+    /// It is enforced by `(2 * self) * sign = self - result`, the bits of
+    /// the result packing as an unsigned integer.
+    pub fn overflowing_abs<F, CS>(&self, mut cs: CS) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        if self.is_constant() {
+            let (abs, overflow) = self.value.unwrap().overflowing_abs();
+            return Ok((Self::constant(abs), Boolean::constant(overflow)));
+        }
+
+        let result = Self::alloc(cs.ns(|| "result"), self.value.map(|v| v.wrapping_abs()))?;
+
+        let two = F::one().double();
+        let sign = self.is_negative();
+        let result_lc = result.lc(CS::one());
+        cs.enforce(
+            || "abs",
+            |_| self.signed_lc(CS::one(), two),
+            |_| sign.lc(CS::one(), F::one()),
+            |_| self.signed_lc(CS::one(), F::one()) - &result_lc,
+        );
+
+        let overflow = result.is_negative();
+        Ok((result, overflow))
+    }
+
+    /// The absolute value modulo 2^64.
+    pub fn wrapping_abs<F, CS>(&self, cs: CS) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_abs(cs)?.0)
+    }
+
+    /// Whether `self < other`. The offset encodings compare as unsigned
+    /// integers, the borrow of their subtraction is the result.
+    pub fn is_lt<F, CS>(&self, mut cs: CS, other: &Self) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        if self.is_constant() && other.is_constant() {
+            return Ok(Boolean::constant(self.value < other.value));
+        }
+
+        let difference = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(offset(a).wrapping_sub(offset(b)) as i64),
+            _ => None,
+        };
+        let no_borrow = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a >= b),
+            _ => None,
+        };
+        let lc = self.offset_lc(CS::one()) - &other.offset_lc(CS::one())
+            + (two_to_the::<F>(64), CS::one());
+        let (_, no_borrow) = Self::alloc_packing(&mut cs, lc, difference, no_borrow)?;
+
+        Ok(no_borrow.not())
+    }
+
+    /// Whether `self <= other`.
+    pub fn is_le<F, CS>(&self, cs: CS, other: &Self) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(other.is_lt(cs, self)?.not())
+    }
+
+    /// Whether `self > other`.
+    pub fn is_gt<F, CS>(&self, cs: CS, other: &Self) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        other.is_lt(cs, self)
+    }
+
+    /// Whether `self >= other`.
+    pub fn is_ge<F, CS>(&self, cs: CS, other: &Self) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.is_lt(cs, other)?.not())
+    }
+
+    /// Allocates the offset encoding `self + 2^63` of the number, which is
+    /// below `2^64`.
+    pub fn to_field_element<F, CS>(&self, mut cs: CS) -> Result<AllocatedFr<F>, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 64);
+
+        let num = AllocatedFr::alloc(cs.ns(|| "num"), || {
+            self.value
+                .map(|v| F::from(offset(v)))
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let lc = self.offset_lc(CS::one());
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + num.get_variable(),
+        );
+
+        Ok(num)
+    }
+
+    /// Decodes the offset encoding `num`, whose 64 bits are enforced to
+    /// pack into it, which proves `num < 2^64`.
+    pub fn from_field_element<F, CS>(
+        mut cs: CS,
+        num: &AllocatedFr<F>,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 64);
+
+        let value = num
+            .get_value()
+            .map(|v| (v.into_repr().as_ref()[0] ^ (1 << 63)) as i64);
+
+        let result = Self::alloc(cs.ns(|| "bits"), value)?;
+
+        let lc = result.offset_lc(CS::one());
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + num.get_variable(),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use num_traits::{One, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::fr::AllocatedFr;
+    use super::{two_to_the, Int64};
+    use crate::test_cs::TestConstraintSystem;
+
+    /// Random values along with the ones around the sign and the overflow
+    /// boundaries.
+    fn test_values<R: Rng>(rng: &mut R) -> Vec<i64> {
+        let mut values = vec![
+            i64::min_value(),
+            i64::min_value() + 1,
+            -(1 << 32),
+            -2,
+            -1,
+            0,
+            1,
+            1 << 32,
+            i64::max_value() - 1,
+            i64::max_value(),
+        ];
+        values.extend((0..6).map(|_| rng.gen::<i64>()));
+        values
+    }
+
+    #[test]
+    fn test_int64_add_sub() {
+        let rng = &mut test_rng();
+        let values = test_values(rng);
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_int = Int64::alloc(cs.ns(|| "a"), Some(*a)).unwrap();
+                let b_int = Int64::alloc(cs.ns(|| "b"), Some(*b)).unwrap();
+                let c_int = Int64::constant(*b);
+
+                let (sum, add_overflow) = a_int.overflowing_add(cs.ns(|| "add"), &b_int).unwrap();
+                assert_eq!(cs.num_constraints(), 2 * 64 + 65 + 1 + 3);
+                let (difference, sub_overflow) =
+                    a_int.overflowing_sub(cs.ns(|| "sub"), &b_int).unwrap();
+                let (sum_c, add_c_overflow) = a_int
+                    .overflowing_add(cs.ns(|| "add constant"), &c_int)
+                    .unwrap();
+                let (difference_c, sub_c_overflow) = Int64::constant(*a)
+                    .overflowing_sub(cs.ns(|| "sub from constant"), &b_int)
+                    .unwrap();
+                assert!(cs.is_satisfied());
+
+                let (expected, overflow) = a.overflowing_add(*b);
+                for (r, o) in [(&sum, &add_overflow), (&sum_c, &add_c_overflow)].iter() {
+                    assert_eq!(r.get_value(), Some(expected));
+                    assert_eq!(
+                        Int64::from_bits(&(*r).clone().into_bits()).get_value(),
+                        Some(expected)
+                    );
+                    assert_eq!(o.get_value(), Some(overflow));
+                }
+
+                let (expected, overflow) = a.overflowing_sub(*b);
+                for (r, o) in [
+                    (&difference, &sub_overflow),
+                    (&difference_c, &sub_c_overflow),
+                ]
+                .iter()
+                {
+                    assert_eq!(r.get_value(), Some(expected));
+                    assert_eq!(
+                        Int64::from_bits(&(*r).clone().into_bits()).get_value(),
+                        Some(expected)
+                    );
+                    assert_eq!(o.get_value(), Some(overflow));
+                }
+
+                // Flip a bit and see if the addition constraint still works
+                if cs.get("add/result/allocated bit 0/boolean").is_zero() {
+                    cs.set("add/result/allocated bit 0/boolean", Fr::one());
+                } else {
+                    cs.set("add/result/allocated bit 0/boolean", Fr::zero());
+                }
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_int64_cmp() {
+        let rng = &mut test_rng();
+        let values = test_values(rng);
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_int = Int64::alloc(cs.ns(|| "a"), Some(*a)).unwrap();
+                let b_int = Int64::alloc(cs.ns(|| "b"), Some(*b)).unwrap();
+                let c_int = Int64::constant(*b);
+
+                let lt = a_int.is_lt(cs.ns(|| "lt"), &b_int).unwrap();
+                let le = a_int.is_le(cs.ns(|| "le"), &b_int).unwrap();
+                let gt = a_int.is_gt(cs.ns(|| "gt"), &b_int).unwrap();
+                let ge = a_int.is_ge(cs.ns(|| "ge"), &b_int).unwrap();
+                let lt_c = a_int.is_lt(cs.ns(|| "lt constant"), &c_int).unwrap();
+                assert!(cs.is_satisfied());
+
+                assert_eq!(lt.get_value(), Some(a < b));
+                assert_eq!(le.get_value(), Some(a <= b));
+                assert_eq!(gt.get_value(), Some(a > b));
+                assert_eq!(ge.get_value(), Some(a >= b));
+                assert_eq!(lt_c.get_value(), Some(a < b));
+                assert_eq!(
+                    Int64::constant(*a)
+                        .is_lt(cs.ns(|| "constants"), &c_int)
+                        .unwrap()
+                        .get_value(),
+                    Some(a < b)
+                );
+
+                // the other answer does not pass.
+                let top = "lt/top bit/boolean";
+                let flipped = if cs.get(top).is_zero() {
+                    Fr::one()
+                } else {
+                    Fr::zero()
+                };
+                cs.set(top, flipped);
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_int64_neg_abs() {
+        let rng = &mut test_rng();
+
+        for a in test_values(rng) {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let a_int = Int64::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+            let sign = a_int.is_negative();
+            let (neg, neg_overflow) = a_int.overflowing_neg(cs.ns(|| "neg")).unwrap();
+            let num_constraints = cs.num_constraints();
+            let (abs, abs_overflow) = a_int.overflowing_abs(cs.ns(|| "abs")).unwrap();
+            assert_eq!(cs.num_constraints() - num_constraints, 64 + 1);
+            assert!(cs.is_satisfied());
+
+            assert_eq!(sign.get_value(), Some(a < 0));
+            assert_eq!(neg.get_value(), Some(a.wrapping_neg()));
+            assert_eq!(neg_overflow.get_value(), Some(a == i64::min_value()));
+            assert_eq!(abs.get_value(), Some(a.wrapping_abs()));
+            assert_eq!(abs_overflow.get_value(), Some(a == i64::min_value()));
+            assert_eq!(
+                abs.clone().into_uint64().get_value(),
+                Some(if a < 0 {
+                    (a as u64).wrapping_neg()
+                } else {
+                    a as u64
+                })
+            );
+
+            let (c, c_overflow) = Int64::constant(a)
+                .overflowing_abs(cs.ns(|| "abs constant"))
+                .unwrap();
+            assert_eq!(c.get_value(), abs.get_value());
+            assert_eq!(c_overflow.get_value(), abs_overflow.get_value());
+
+            // another absolute value does not pass.
+            if cs.get("abs/result/allocated bit 1/boolean").is_zero() {
+                cs.set("abs/result/allocated bit 1/boolean", Fr::one());
+            } else {
+                cs.set("abs/result/allocated bit 1/boolean", Fr::zero());
+            }
+            assert!(!cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_int64_field_element() {
+        let rng = &mut test_rng();
+
+        for a in test_values(rng) {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let a_int = Int64::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+            let num = a_int.to_field_element(cs.ns(|| "to")).unwrap();
+            let b_int = Int64::from_field_element(cs.ns(|| "from"), &num).unwrap();
+            assert!(cs.is_satisfied());
+
+            // the offset encoding keeps the order.
+            let expected = Fr::from(a as u64) + &two_to_the::<Fr>(63);
+            let expected = if a < 0 {
+                expected - &two_to_the::<Fr>(64)
+            } else {
+                expected
+            };
+            assert_eq!(num.get_value(), Some(expected));
+            assert_eq!(b_int.get_value(), Some(a));
+        }
+
+        // 2^64 is out of range.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let num = AllocatedFr::alloc(cs.ns(|| "num"), || Ok(two_to_the::<Fr>(64))).unwrap();
+        Int64::from_field_element(cs.ns(|| "from"), &num).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+}
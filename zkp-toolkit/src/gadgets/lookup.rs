@@ -4,6 +4,8 @@ use core::iter::IntoIterator;
 use math::PrimeField;
 use scheme::r1cs::{ConstraintSystem, SynthesisError};
 
+use crate::Vec;
+
 use super::{boolean::Boolean, fr::AllocatedFr};
 
 // Synthesize the constants for each base pattern.
@@ -330,6 +332,189 @@ where
     Ok((res_x, res_y))
 }
 
+/// The index selected by the little-endian `bits`.
+fn index(bits: &[Boolean]) -> Option<usize> {
+    bits.iter().enumerate().try_fold(0, |i, (j, b)| {
+        b.get_value().map(|v| i | ((v as usize) << j))
+    })
+}
+
+/// The products of the subsets of `bits[1..]`, the product of the subset
+/// `mask` at `mask`, whose bit `i` stands for `bits[i + 1]`. The empty
+/// product is the constant one, and every product of 2 bits or more costs a
+/// constraint.
+fn monomials<F, CS>(mut cs: CS, bits: &[Boolean]) -> Result<Vec<Boolean>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let mut monomials = vec![Boolean::constant(true)];
+    for (i, bit) in bits.iter().enumerate().skip(1) {
+        for mask in 0..monomials.len() {
+            let m = Boolean::and(
+                cs.ns(|| format!("monomial {}", mask | (1 << (i - 1)))),
+                &monomials[mask],
+                bit,
+            )?;
+            monomials.push(m);
+        }
+    }
+
+    Ok(monomials)
+}
+
+/// Enforces `res` to be the evaluation of the multilinear polynomial of
+/// `coeffs`, as computed by `synth`, split on the first bit:
+///
+/// (sum of the odd terms) * bits[0] = res - (sum of the even terms)
+fn enforce_polynomial<F, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    monomials: &[Boolean],
+    coeffs: &[F],
+    res: &AllocatedFr<F>,
+) where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let one = CS::one();
+    cs.enforce(
+        || "lookup",
+        |lc| {
+            monomials.iter().enumerate().fold(lc, |lc, (mask, m)| {
+                lc + &m.lc::<F>(one, coeffs[(mask << 1) | 1])
+            })
+        },
+        |lc| lc + &bits[0].lc::<F>(one, F::one()),
+        |lc| {
+            monomials
+                .iter()
+                .enumerate()
+                .fold(lc + res.get_variable(), |lc, (mask, m)| {
+                    lc - &m.lc::<F>(one, coeffs[mask << 1])
+                })
+        },
+    );
+}
+
+/// Performs a window table lookup of any constant table of `2^n` entries,
+/// for the `n` selector `bits` in little-endian order.
+///
+/// The multiplexer is the multilinear polynomial in the bits interpolating
+/// the table: a constraint, and a constraint per product of 2 bits or more
+/// among all but the first, `2^(n - 1) - n + 1` constraints in total.
+pub fn lookup_table<F, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    table: &[F],
+) -> Result<AllocatedFr<F>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert!(!bits.is_empty());
+    assert_eq!(table.len(), 1 << bits.len());
+
+    let i = index(bits);
+    let res = AllocatedFr::alloc(cs.ns(|| "x"), || {
+        Ok(table[i.ok_or(SynthesisError::AssignmentMissing)?])
+    })?;
+
+    let mut coeffs = vec![F::zero(); table.len()];
+    synth::<F, _>(bits.len(), table, &mut coeffs);
+
+    let monomials = monomials(cs.ns(|| "monomials"), bits)?;
+    enforce_polynomial(cs.ns(|| "x-coordinate"), bits, &monomials, &coeffs, &res);
+
+    Ok(res)
+}
+
+/// Performs a window table lookup of any constant table of `2^n` points,
+/// for the `n` selector `bits` in little-endian order. The products of the
+/// bits are shared by both coordinates, it costs `2^(n - 1) - n + 2`
+/// constraints.
+pub fn lookup_table_xy<F, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    coords: &[(F, F)],
+) -> Result<(AllocatedFr<F>, AllocatedFr<F>), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert!(!bits.is_empty());
+    assert_eq!(coords.len(), 1 << bits.len());
+
+    let i = index(bits);
+    let res_x = AllocatedFr::alloc(cs.ns(|| "x"), || {
+        Ok(coords[i.ok_or(SynthesisError::AssignmentMissing)?].0)
+    })?;
+    let res_y = AllocatedFr::alloc(cs.ns(|| "y"), || {
+        Ok(coords[i.ok_or(SynthesisError::AssignmentMissing)?].1)
+    })?;
+
+    let mut x_coeffs = vec![F::zero(); coords.len()];
+    let mut y_coeffs = vec![F::zero(); coords.len()];
+    synth::<F, _>(bits.len(), coords.iter().map(|c| &c.0), &mut x_coeffs);
+    synth::<F, _>(bits.len(), coords.iter().map(|c| &c.1), &mut y_coeffs);
+
+    let monomials = monomials(cs.ns(|| "monomials"), bits)?;
+    enforce_polynomial(
+        cs.ns(|| "x-coordinate"),
+        bits,
+        &monomials,
+        &x_coeffs,
+        &res_x,
+    );
+    enforce_polynomial(
+        cs.ns(|| "y-coordinate"),
+        bits,
+        &monomials,
+        &y_coeffs,
+        &res_y,
+    );
+
+    Ok((res_x, res_y))
+}
+
+/// Performs a 4-bit window table lookup. `bits` is in
+/// little-endian order. constants is 2D-coords.
+///
+/// It costs 6 constraints.
+pub fn lookup4_xy<F, CS>(
+    cs: CS,
+    bits: &[Boolean],
+    coords: &[(F, F)],
+) -> Result<(AllocatedFr<F>, AllocatedFr<F>), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(bits.len(), 4);
+    assert_eq!(coords.len(), 16);
+
+    lookup_table_xy(cs, bits, coords)
+}
+
+/// Performs a 5-bit window table lookup. `bits` is in
+/// little-endian order. constants is 2D-coords.
+///
+/// It costs 13 constraints.
+pub fn lookup5_xy<F, CS>(
+    cs: CS,
+    bits: &[Boolean],
+    coords: &[(F, F)],
+) -> Result<(AllocatedFr<F>, AllocatedFr<F>), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(bits.len(), 5);
+    assert_eq!(coords.len(), 32);
+
+    lookup_table_xy(cs, bits, coords)
+}
+
 #[cfg(test)]
 mod test {
     use curve::bn_256::Fr;
@@ -338,8 +523,8 @@ mod test {
     use scheme::r1cs::ConstraintSystem;
 
     use super::super::boolean::{AllocatedBit, Boolean};
-    use super::super::test_constraint_system::TestConstraintSystem;
     use super::*;
+    use crate::test_cs::TestConstraintSystem;
 
     fn get_booleans<CS: ConstraintSystem<Fr>>(cs: &mut CS, num: u32) -> (usize, Vec<Boolean>) {
         let rng = &mut test_rng();
@@ -447,4 +632,85 @@ mod test {
             assert_eq!(res.1.get_value().unwrap(), points[index].1);
         }
     }
+
+    #[test]
+    fn test_lookup4_xy() {
+        let rng = &mut test_rng();
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let (index, bits) = get_booleans(&mut cs, 4);
+            let points: Vec<(Fr, Fr)> = (0..16).map(|_| (rng.gen(), rng.gen())).collect();
+
+            let res = lookup4_xy(&mut cs, &bits, &points).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 4 + 6);
+
+            assert_eq!(res.0.get_value().unwrap(), points[index].0);
+            assert_eq!(res.1.get_value().unwrap(), points[index].1);
+        }
+    }
+
+    #[test]
+    fn test_lookup5_xy() {
+        let rng = &mut test_rng();
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let (index, bits) = get_booleans(&mut cs, 5);
+            let points: Vec<(Fr, Fr)> = (0..32).map(|_| (rng.gen(), rng.gen())).collect();
+
+            let res = lookup5_xy(&mut cs, &bits, &points).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 5 + 13);
+
+            assert_eq!(res.0.get_value().unwrap(), points[index].0);
+            assert_eq!(res.1.get_value().unwrap(), points[index].1);
+        }
+    }
+
+    #[test]
+    fn test_lookup_table() {
+        let rng = &mut test_rng();
+
+        for n in 1..=6 {
+            for _ in 0..20 {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let (index, bits) = get_booleans(&mut cs, n);
+                let table: Vec<Fr> = (0..1 << n).map(|_| rng.gen()).collect();
+
+                let res = lookup_table(&mut cs, &bits, &table).unwrap();
+
+                assert!(cs.is_satisfied());
+                assert_eq!(res.get_value().unwrap(), table[index]);
+
+                // 1, 1, 2, 5, 12 and 27 constraints.
+                let n = n as usize;
+                assert_eq!(cs.num_constraints(), n + (1 << (n - 1)) - n + 1);
+
+                // another result does not pass.
+                cs.set("x/fr", Fr::from(0u32));
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup_table_constant_bits() {
+        let rng = &mut test_rng();
+        let table: Vec<Fr> = (0..16).map(|_| rng.gen()).collect();
+
+        // a constant bit folds the products it is part of.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let (index, mut bits) = get_booleans(&mut cs, 3);
+        bits.push(Boolean::constant(true));
+
+        let res = lookup_table(&mut cs, &bits, &table).unwrap();
+
+        assert!(cs.is_satisfied());
+        assert_eq!(res.get_value().unwrap(), table[index + 8]);
+        assert_eq!(cs.num_constraints(), 3 + 2);
+    }
 }
@@ -1,5 +1,9 @@
 //! Complete Binary Merkle Tree, this implementation inspired by [Nervos CBMT].
 //!
+//! The trees of wider arity are laid out the same way, the children of the
+//! node `i` being `arity * i + 1..=arity * i + arity`. The leaves are
+//! padded with `T::default()` so that every internal node is full.
+//!
 //! [Nervos CBMT]: https://github.com/nervosnetwork/merkle-tree
 
 use core::marker::PhantomData;
@@ -15,10 +19,18 @@ use crate::Vec;
 pub trait Merge {
     type Item;
     fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item;
+
+    /// Merges the children of a node, in order. Override it for the trees
+    /// of arity above 2.
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        assert_eq!(items.len(), 2, "merge_many is not implemented");
+        Self::merge(&items[0], &items[1])
+    }
 }
 
 pub struct MerkleTree<T, M> {
     nodes: Vec<T>,
+    arity: usize,
     merge: PhantomData<M>,
 }
 
@@ -33,39 +45,31 @@ where
             return None;
         }
 
-        let leaves_count = ((self.nodes.len() >> 1) + 1) as u32;
-        let index = leaves_count + leaf_index - 1;
+        let arity = self.arity as u32;
+        let index = (self.nodes.len() as u32 - 1) / arity + leaf_index;
 
-        if index >= (leaves_count << 1) - 1 {
+        if index >= self.nodes.len() as u32 {
             return None;
         }
 
         let mut lemmas = Vec::new();
-
-        if index == 0 {
-            return Some(MerkleProof {
-                index,
-                lemmas,
-                merge: PhantomData,
-            });
-        }
-
         let mut new_index = index;
 
-        loop {
-            let sibling = new_index.sibling();
-            lemmas.push(self.nodes[sibling as usize].clone());
+        while !new_index.is_root() {
+            let parent = new_index.parent_in(self.arity);
+            let first_child = arity * parent + 1;
+            lemmas.extend(
+                (first_child..first_child + arity)
+                    .filter(|i| *i != new_index)
+                    .map(|i| self.nodes[i as usize].clone()),
+            );
 
-            let parent = new_index.parent();
-            if parent != 0 {
-                new_index = parent;
-            } else {
-                break;
-            }
+            new_index = parent;
         }
 
         Some(MerkleProof {
             index,
+            arity: self.arity,
             lemmas,
             merge: PhantomData,
         })
@@ -82,10 +86,15 @@ where
     pub fn nodes(&self) -> &Vec<T> {
         &self.nodes
     }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
 }
 
 pub struct MerkleProof<T, M> {
     index: u32,
+    arity: usize,
     lemmas: Vec<T>,
     merge: PhantomData<M>,
 }
@@ -96,8 +105,17 @@ where
     M: Merge<Item = T>,
 {
     pub fn new(index: u32, lemmas: Vec<T>) -> Self {
+        Self::new_with_arity(2, index, lemmas)
+    }
+
+    /// `lemmas`: The `arity - 1` siblings of each level in order, from the
+    /// leaf up.
+    pub fn new_with_arity(arity: usize, index: u32, lemmas: Vec<T>) -> Self {
+        assert!(arity >= 2);
+
         Self {
             index,
+            arity,
             lemmas,
             merge: PhantomData,
         }
@@ -107,22 +125,18 @@ where
         if self.index == 0 && self.lemmas.len() != 0 {
             return None;
         }
+        if self.lemmas.len() % (self.arity - 1) != 0 {
+            return None;
+        }
 
         let mut parent = leaf.clone();
         let mut index = self.index;
-        let mut lemmas_iter = self.lemmas.iter();
 
-        loop {
-            if let Some(sibling) = lemmas_iter.next() {
-                parent = if index.is_left() {
-                    M::merge(&parent, &sibling)
-                } else {
-                    M::merge(&sibling, &parent)
-                };
-                index = index.parent();
-            } else {
-                break;
-            }
+        for siblings in self.lemmas.chunks(self.arity - 1) {
+            let mut children = siblings.to_vec();
+            children.insert(index.position_in(self.arity), parent);
+            parent = M::merge_many(&children);
+            index = index.parent_in(self.arity);
         }
 
         Some(parent)
@@ -139,6 +153,10 @@ where
         &self.index
     }
 
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
     pub fn lemmas(&self) -> &[T] {
         &self.lemmas
     }
@@ -180,22 +198,34 @@ where
     }
 
     pub fn build_merkle_tree(leaves: Vec<T>) -> MerkleTree<T, M> {
+        Self::build_merkle_tree_with_arity(leaves, 2)
+    }
+
+    pub fn build_merkle_tree_with_arity(mut leaves: Vec<T>, arity: usize) -> MerkleTree<T, M> {
+        assert!(arity >= 2);
+
         let len = leaves.len();
         if len > 0 {
-            let mut nodes = vec![T::default(); len - 1];
+            let padding = (arity - 1 - (len - 1) % (arity - 1)) % (arity - 1);
+            leaves.resize(len + padding, T::default());
+
+            let internal = (leaves.len() - 1) / (arity - 1);
+            let mut nodes = vec![T::default(); internal];
             nodes.extend(leaves);
 
-            (0..len - 1)
+            (0..internal)
                 .rev()
-                .for_each(|i| nodes[i] = M::merge(&nodes[(i << 1) + 1], &nodes[(i << 1) + 2]));
+                .for_each(|i| nodes[i] = M::merge_many(&nodes[arity * i + 1..=arity * i + arity]));
 
             MerkleTree {
                 nodes,
+                arity,
                 merge: PhantomData,
             }
         } else {
             MerkleTree {
                 nodes: vec![],
+                arity,
                 merge: PhantomData,
             }
         }
@@ -204,6 +234,14 @@ where
     pub fn build_merkle_proof(leaves: &[T], index: &u32) -> Option<MerkleProof<T, M>> {
         Self::build_merkle_tree(leaves.to_vec()).build_proof(index)
     }
+
+    pub fn build_merkle_proof_with_arity(
+        leaves: &[T],
+        index: &u32,
+        arity: usize,
+    ) -> Option<MerkleProof<T, M>> {
+        Self::build_merkle_tree_with_arity(leaves.to_vec(), arity).build_proof(index)
+    }
 }
 
 pub trait TreeIndex: Clone {
@@ -211,6 +249,10 @@ pub trait TreeIndex: Clone {
     fn parent(&self) -> Self;
     fn is_left(&self) -> bool;
     fn is_root(&self) -> bool;
+    /// The parent in a tree of `arity`.
+    fn parent_in(&self, arity: usize) -> Self;
+    /// The position among the children of the parent, in a tree of `arity`.
+    fn position_in(&self, arity: usize) -> usize;
 }
 
 macro_rules! impl_tree_index {
@@ -239,6 +281,22 @@ macro_rules! impl_tree_index {
             fn is_root(&self) -> bool {
                 *self == 0
             }
+
+            fn parent_in(&self, arity: usize) -> $t {
+                if *self == 0 {
+                    0
+                } else {
+                    (self - 1) / arity as $t
+                }
+            }
+
+            fn position_in(&self, arity: usize) -> usize {
+                if *self == 0 {
+                    0
+                } else {
+                    ((self - 1) % arity as $t) as usize
+                }
+            }
         }
     };
 }
@@ -257,6 +315,10 @@ mod tests {
         fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
             right.wrapping_sub(*left)
         }
+
+        fn merge_many(items: &[Self::Item]) -> Self::Item {
+            items.iter().fold(0, |acc, i| i.wrapping_sub(acc))
+        }
     }
 
     type CBMTI32 = CBMT<i32, MergeI32>;
@@ -336,4 +398,38 @@ mod tests {
         assert!(proof.lemmas.is_empty());
         assert_eq!(Some(2), proof.root(&proof_leaf));
     }
+
+    #[test]
+    fn build_cbmt_arity() {
+        let leaves = vec![2i32, 3, 5, 7, 11];
+        let tree = CBMTI32::build_merkle_tree_with_arity(leaves, 3);
+        assert_eq!(&vec![10, 9, 2, 3, 5, 7, 11], tree.nodes());
+
+        // padded to 7 leaves
+        let leaves = vec![2i32, 3, 5, 7, 11];
+        let tree = CBMTI32::build_merkle_tree_with_arity(leaves, 4);
+        assert_eq!(&vec![0, 4, 2, 3, 5, 7, 11, 0, 0], tree.nodes());
+    }
+
+    #[test]
+    fn build_cbmt_proof_arity() {
+        let leaves = (0..20i32).map(|i| i * i).collect::<Vec<_>>();
+
+        for arity in [2, 3, 4, 8].iter() {
+            let tree = CBMTI32::build_merkle_tree_with_arity(leaves.clone(), *arity);
+            let root = tree.root();
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.build_proof(&(i as u32)).unwrap();
+                assert_eq!(proof.lemmas().len() % (arity - 1), 0);
+                assert!(proof.verify(&root, leaf));
+                assert!(!proof.verify(&root, &(leaf + 1)));
+
+                let rebuild_proof =
+                    CBMTI32Proof::new_with_arity(*arity, *proof.index(), proof.lemmas().to_vec());
+                assert_eq!(Some(root), rebuild_proof.root(leaf));
+            }
+            assert!(tree.build_proof(&(tree.nodes().len() as u32)).is_none());
+        }
+    }
 }
@@ -1,22 +1,37 @@
-//! Complete Binary Merkle Tree Proof gadgets.
+//! Complete Merkle Tree Proof gadgets, binary or of wider arity.
 
 use math::PrimeField;
-use scheme::r1cs::Variable;
-use scheme::r1cs::{ConstraintSystem, SynthesisError};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
 
 use crate::Vec;
 
 use super::super::abstract_hash::{AbstractHash, AbstractHashOutput};
+use super::super::boolean::AllocatedBit;
+use super::super::fr::AllocatedFr;
 use super::cbmt::TreeIndex;
 
 pub struct MerkleProofGadget<I: TreeIndex, F: PrimeField, H: AbstractHash<F>> {
     index: I,
+    arity: usize,
     lemmas: Vec<H::Output>,
 }
 
 impl<I: TreeIndex, F: PrimeField, H: AbstractHash<F>> MerkleProofGadget<I, F, H> {
     pub fn new(index: I, lemmas: Vec<H::Output>) -> Self {
-        MerkleProofGadget { index, lemmas }
+        Self::new_with_arity(2, index, lemmas)
+    }
+
+    /// `lemmas`: The `arity - 1` siblings of each level in order, from the
+    /// leaf up, as `MerkleProof::lemmas`.
+    pub fn new_with_arity(arity: usize, index: I, lemmas: Vec<H::Output>) -> Self {
+        assert!(arity >= 2);
+        assert_eq!(lemmas.len() % (arity - 1), 0);
+
+        MerkleProofGadget {
+            index,
+            arity,
+            lemmas,
+        }
     }
 
     pub fn set_membership<CS: ConstraintSystem<F>>(
@@ -25,75 +40,7 @@ impl<I: TreeIndex, F: PrimeField, H: AbstractHash<F>> MerkleProofGadget<I, F, H>
         root: H::Output,
         leaf: H::Output,
     ) -> Result<(), SynthesisError> {
-        let mut parent = leaf.clone();
-        let mut index = self.index.clone();
-        let mut lemmas_iter = self.lemmas.iter().enumerate();
-        loop {
-            if let Some((i, sibling)) = lemmas_iter.next() {
-                let parent_variable_vec = parent.get_variables();
-                let parent_value_vec = parent.get_variable_values();
-                let sibling_variable_vec = sibling.get_variables();
-                let sibling_value_vec = sibling.get_variable_values();
-                let is_left_value = if index.is_left() {
-                    Some(F::one())
-                } else {
-                    Some(F::zero())
-                };
-
-                let is_left_variable = cs.alloc(
-                    || format!("is_left_variable[{}]", i),
-                    || is_left_value.ok_or(SynthesisError::AssignmentMissing),
-                )?;
-
-                let input_value = if index.is_left() {
-                    parent_value_vec.clone()
-                } else {
-                    sibling_value_vec.clone()
-                };
-
-                let mut input_variable_vec: Vec<Variable> =
-                    Vec::with_capacity(input_value.len() as usize);
-                for j in 0..input_value.len() as usize {
-                    let input_variable = cs.alloc(
-                        || format!("input_variable[{}][{}]", i, j),
-                        || input_value[j].ok_or(SynthesisError::AssignmentMissing),
-                    )?;
-                    input_variable_vec.push(input_variable);
-                }
-
-                for j in 0..parent_variable_vec.len() as usize {
-                    // parent_variable_vec.len = 256; sibling_variable_vec.len = 8
-                    if j >= sibling_variable_vec.len() {
-                        break;
-                    }
-                    cs.enforce(
-                        || {
-                            format!(
-                                "is_left*(left[{}][{}]-right[{}][{}])=(input[{}]-right[{}][{}])",
-                                i, j, i, j, j, i, j
-                            )
-                        },
-                        |lc| lc + is_left_variable,
-                        |lc| lc + parent_variable_vec[j] - sibling_variable_vec[j],
-                        |lc| lc + input_variable_vec[j] - sibling_variable_vec[j],
-                    );
-                }
-                parent = if index.is_left() {
-                    H::hash_enforce(
-                        cs.ns(|| format!("hash_enforce_left_{}", i)),
-                        &[&parent, sibling],
-                    )?
-                } else {
-                    H::hash_enforce(
-                        cs.ns(|| format!("hash_enforce_right_{}", i)),
-                        &[sibling, &parent],
-                    )?
-                };
-                index = index.parent();
-            } else {
-                break;
-            }
-        }
+        let parent = self.compute_root(&mut cs, leaf)?;
 
         let pre = parent
             .get_variables()
@@ -113,50 +60,281 @@ impl<I: TreeIndex, F: PrimeField, H: AbstractHash<F>> MerkleProofGadget<I, F, H>
 
         Ok(())
     }
+
+    /// Enforces the membership of `leaf` in the tree of the root it
+    /// computes, and makes the `to_field_elements` of the root the public
+    /// inputs, the verifier computes them with `native_field_elements`.
+    pub fn inputize_root<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        leaf: H::Output,
+    ) -> Result<Vec<AllocatedFr<F>>, SynthesisError> {
+        let root = self.compute_root(&mut cs, leaf)?;
+
+        let elements = root.to_field_elements(cs.ns(|| "root elements"))?;
+        for (i, e) in elements.iter().enumerate() {
+            e.inputize(cs.ns(|| format!("root input {}", i)))?;
+        }
+
+        Ok(elements)
+    }
+
+    /// Recomputes the root from `leaf` and the lemmas, a namespace a level.
+    pub fn compute_root<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        leaf: H::Output,
+    ) -> Result<H::Output, SynthesisError> {
+        let (root, _) = self.compute_root_and_selectors(&mut cs, leaf)?;
+
+        Ok(root)
+    }
+
+    /// Like `compute_root`, and also returns the position of the leaf among
+    /// the `arity^depth` leaves of a full tree of the depth of the lemmas,
+    /// enforced to be the one of the selectors of the levels. Once the
+    /// root is fixed, it is the index of the leaf the tree was built from.
+    pub fn compute_root_and_position<CS: ConstraintSystem<F>>(
+        &self,
+        mut cs: CS,
+        leaf: H::Output,
+    ) -> Result<(H::Output, AllocatedFr<F>), SynthesisError> {
+        let (root, selectors) = self.compute_root_and_selectors(&mut cs, leaf)?;
+
+        // position = sum(arity^level * j * selector[level][j])
+        let arity = F::from(self.arity as u64);
+        let mut weight = F::one();
+        let mut lc = LinearCombination::<F>::zero();
+        let mut value = Some(F::zero());
+        for selector in selectors.iter() {
+            for (j, s) in selector.iter().enumerate().skip(1) {
+                let coeff = weight * F::from(j as u64);
+                lc += (coeff, s.get_variable());
+                value = match (value, s.get_value()) {
+                    (Some(v), Some(true)) => Some(v + coeff),
+                    (Some(v), Some(false)) => Some(v),
+                    _ => None,
+                };
+            }
+            weight *= arity;
+        }
+
+        let position = AllocatedFr::alloc(cs.ns(|| "position"), || {
+            value.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "position is the one of the selectors",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + position.get_variable(),
+        );
+
+        Ok((root, position))
+    }
+
+    fn compute_root_and_selectors<CS: ConstraintSystem<F>>(
+        &self,
+        cs: &mut CS,
+        leaf: H::Output,
+    ) -> Result<(H::Output, Vec<Vec<AllocatedBit>>), SynthesisError> {
+        let mut parent = leaf;
+        let mut index = self.index.clone();
+        let mut selectors = Vec::new();
+
+        for (i, siblings) in self.lemmas.chunks(self.arity - 1).enumerate() {
+            let mut cs = cs.ns(|| format!("level {}", i));
+            let position = index.position_in(self.arity);
+
+            // one-hot selector of the position of the parent. The selectors
+            // are allocated bits, not the decomposition of a number, so no
+            // other representation of the index is to rule out.
+            let selector = (0..self.arity)
+                .map(|j| {
+                    AllocatedBit::alloc(cs.ns(|| format!("selector {}", j)), Some(j == position))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            cs.enforce(
+                || "selector sums to one",
+                |lc| selector.iter().fold(lc, |lc, s| lc + s.get_variable()),
+                |lc| lc + CS::one(),
+                |lc| lc + CS::one(),
+            );
+
+            let mut children = siblings.iter().collect::<Vec<_>>();
+            children.insert(position, &parent);
+
+            let children = select_children(
+                cs.ns(|| "children"),
+                &selector,
+                &parent,
+                siblings,
+                &children,
+            )?;
+            let children = children.iter().collect::<Vec<_>>();
+
+            parent = H::hash_enforce(cs.ns(|| "hash_enforce"), &children)?;
+            index = index.parent_in(self.arity);
+            selectors.push(selector);
+        }
+
+        Ok((parent, selectors))
+    }
+}
+
+/// Allocates the variables of the children of a level, the ones of the
+/// parent at the position of the selector and the ones of the siblings
+/// around it. The child `j` is enforced by
+/// `child = s[j] + sel[j] * (parent - s[j]) + before[j] * (s[j - 1] - s[j])`,
+/// where `before[j]` is the sum of the selector bits below `j`, and the
+/// missing siblings at both ends are zero.
+///
+/// The children are returned of these variables, so that the constraints
+/// of the level are the same whatever the position, and one setup serves
+/// all the leaves of a tree.
+fn select_children<F, CS, O>(
+    mut cs: CS,
+    selector: &[AllocatedBit],
+    parent: &O,
+    siblings: &[O],
+    children: &[&O],
+) -> Result<Vec<O>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+    O: AbstractHashOutput<F>,
+{
+    let parent_variables = parent.get_variables();
+    let parent_values = parent.get_variable_values();
+    let sibling_variables = siblings
+        .iter()
+        .map(|s| s.get_variables())
+        .collect::<Vec<_>>();
+    let sibling_values = siblings
+        .iter()
+        .map(|s| s.get_variable_values())
+        .collect::<Vec<_>>();
+
+    let sibling = |j: usize, k: usize| -> (LinearCombination<F>, Option<F>) {
+        match sibling_variables.get(j) {
+            Some(variables) => (
+                LinearCombination::zero() + variables[k],
+                sibling_values[j][k],
+            ),
+            None => (LinearCombination::zero(), Some(F::zero())),
+        }
+    };
+
+    // the outputs of the leaves may be shorter than the ones of the hash.
+    let width = sibling_variables
+        .iter()
+        .map(|v| v.len())
+        .chain(Some(parent_variables.len()))
+        .min()
+        .unwrap();
+
+    let mut before = LinearCombination::<F>::zero();
+    let mut selected_children = Vec::with_capacity(children.len());
+    for (j, (sel, child)) in selector.iter().zip(children.iter()).enumerate() {
+        let child_values = child.get_variable_values();
+        let mut child_variables = Vec::with_capacity(width);
+
+        for (k, (parent_variable, parent_value)) in parent_variables
+            .iter()
+            .zip(parent_values.iter())
+            .take(width)
+            .enumerate()
+        {
+            let mut cs = cs.ns(|| format!("child {} variable {}", j, k));
+
+            let (current_lc, current_value) = sibling(j, k);
+            let (previous_lc, _) = if j > 0 {
+                sibling(j - 1, k)
+            } else {
+                (LinearCombination::zero(), None)
+            };
+
+            let child_variable: Variable = cs.alloc(
+                || "child",
+                || child_values[k].ok_or(SynthesisError::AssignmentMissing),
+            )?;
+            let selected = cs.alloc(
+                || "selected",
+                || match sel.get_value() {
+                    Some(true) => match (parent_value, current_value) {
+                        (Some(p), Some(s)) => Ok(*p - &s),
+                        _ => Err(SynthesisError::AssignmentMissing),
+                    },
+                    Some(false) => Ok(F::zero()),
+                    None => Err(SynthesisError::AssignmentMissing),
+                },
+            )?;
+
+            cs.enforce(
+                || "selected = sel * (parent - s[j])",
+                |lc| lc + sel.get_variable(),
+                |lc| lc + *parent_variable - &current_lc,
+                |lc| lc + selected,
+            );
+            cs.enforce(
+                || "child = s[j] + selected + before * (s[j - 1] - s[j])",
+                |_| before.clone(),
+                |_| previous_lc - &current_lc,
+                |lc| lc + child_variable - &current_lc - selected,
+            );
+            child_variables.push(child_variable);
+        }
+
+        before = before + sel.get_variable();
+        selected_children.push(child.with_variables(&child_variables));
+    }
+
+    Ok(selected_children)
 }
 
 #[cfg(test)]
 mod tests {
     use curve::bn_256::Fr;
-    use math::ToBytes;
+    use math::BigInteger;
     use scheme::r1cs::ConstraintSystem;
-    use sha2::{Digest, Sha256};
 
-    use super::super::super::mimc::{hash, AbstractHashMimc, AbstractHashMimcOutput};
-    use super::super::super::sha256::{AbstractHashSha256, AbstractHashSha256Output};
-    use super::super::super::test_constraint_system::TestConstraintSystem;
+    use super::super::super::abstract_hash::AbstractHashFrOutput;
+    use super::super::super::mimc::{AbstractHashMimc, AbstractHashMimcOutput, MergeMimc};
+    use super::super::super::multipack;
+    #[cfg(feature = "baby_jubjub")]
+    use super::super::super::pedersen::{AbstractHashPedersen, AbstractHashPedersenOutput};
+    use super::super::super::poseidon::{
+        AbstractHashPoseidon, AbstractHashPoseidonOutput, MergePoseidon,
+    };
+    use super::super::super::rescue::{AbstractHashRescue, MergeRescue};
+    use super::super::super::sha256::{AbstractHashSha256, AbstractHashSha256Output, MergeSha256};
     use super::super::cbmt::*;
     use super::*;
+    use crate::test_cs::TestConstraintSystem;
 
-    struct MergeSha256;
-    struct MergeMimc;
+    #[cfg(feature = "baby_jubjub")]
+    struct MergePedersen;
 
-    impl Merge for MergeSha256 {
-        type Item = Vec<u8>;
-
-        fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
-            let mut input = Vec::new();
-            input.extend(&mut left.iter().map(|i| *i));
-            input.extend(&mut right.iter().map(|i| *i));
-            let mut h = Sha256::new();
-            h.update(&input);
-            h.finalize().to_vec()
-        }
-    }
-
-    impl Merge for MergeMimc {
+    #[cfg(feature = "baby_jubjub")]
+    impl Merge for MergePedersen {
         type Item = Fr;
 
         fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
-            let mut bytes = vec![];
-            let _ = left.write(&mut bytes);
-            let _ = right.write(&mut bytes);
-            hash(&bytes)
+            use curve::baby_jubjub::EdwardsParameters;
+            use math::{BigInteger, PrimeField};
+
+            let mut bits = vec![];
+            for f in [left, right].iter() {
+                let repr = f.into_repr();
+                bits.extend((0..Fr::size_in_bits()).map(|i| repr.get_bit(i)));
+            }
+            super::super::super::pedersen::hash::<EdwardsParameters>(&bits)
         }
     }
 
     type CBMTSHA256 = CBMT<Vec<u8>, MergeSha256>;
-    type CBMTMIMC = CBMT<Fr, MergeMimc>;
+    type CBMTMIMC = CBMT<Fr, MergeMimc<Fr>>;
+    #[cfg(feature = "baby_jubjub")]
+    type CBMTPEDERSEN = CBMT<Fr, MergePedersen>;
 
     #[test]
     fn test_merkle_tree_sha256() {
@@ -176,15 +354,18 @@ mod tests {
         let tree = CBMTSHA256::build_merkle_tree(leaves.clone());
         let root = tree.root();
 
-        let n_root = AbstractHashSha256Output::alloc(cs.ns(|| "tree_root"), root.clone()).unwrap();
+        let n_root =
+            AbstractHashSha256Output::alloc(cs.ns(|| "tree_root"), Some(root.clone())).unwrap();
 
         for (i, leaf) in leaves.iter().enumerate() {
             let proof = tree.build_proof(&(i as u32)).unwrap();
             assert!(proof.verify(&root, leaf));
 
-            let n_leaf =
-                AbstractHashSha256Output::alloc(cs.ns(|| format!("leaf_{}", i)), leaf.clone())
-                    .unwrap();
+            let n_leaf = AbstractHashSha256Output::alloc(
+                cs.ns(|| format!("leaf_{}", i)),
+                Some(leaf.clone()),
+            )
+            .unwrap();
 
             let lemmas = proof
                 .lemmas()
@@ -193,7 +374,7 @@ mod tests {
                 .map(|(j, v)| {
                     AbstractHashSha256Output::alloc(
                         cs.ns(|| format!("proof_lemmas_{}_{}", i, j)),
-                        v.clone(),
+                        Some(v.clone()),
                     )
                     .unwrap()
                 })
@@ -213,6 +394,36 @@ mod tests {
         }
 
         assert!(cs.is_satisfied());
+
+        // the root is enforced to be the last digest.
+        let bit = Fr::from((root[0] >> 7 == 0) as u32);
+        cs.set("tree_root/output_bit_0/boolean", bit);
+        assert!(!cs.is_satisfied());
+
+        // the root as the public inputs, the packing of its bits.
+        let proof = tree.build_proof(&3).unwrap();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let leaf =
+            AbstractHashSha256Output::alloc(cs.ns(|| "leaf"), Some(leaves[3].clone())).unwrap();
+        let lemmas = proof
+            .lemmas()
+            .iter()
+            .enumerate()
+            .map(|(j, v)| {
+                AbstractHashSha256Output::alloc(cs.ns(|| format!("lemma {}", j)), Some(v.clone()))
+                    .unwrap()
+            })
+            .collect();
+        let g = MerkleProofGadget::<u32, Fr, AbstractHashSha256<Fr>>::new(*proof.index(), lemmas);
+        g.inputize_root(cs.ns(|| "membership"), leaf).unwrap();
+        assert!(cs.is_satisfied());
+        let inputs = AbstractHashSha256Output::native_field_elements(&root);
+        assert_eq!(inputs.len(), 2);
+        assert!(cs.verify(&inputs));
+        assert_eq!(
+            inputs,
+            multipack::compute_multipacking::<Fr>(&multipack::bytes_to_bits_be(&root))
+        );
     }
 
     #[test]
@@ -271,4 +482,192 @@ mod tests {
 
         assert!(cs.is_satisfied());
     }
+
+    #[cfg(feature = "baby_jubjub")]
+    #[test]
+    fn test_merkle_tree_pedersen() {
+        use curve::baby_jubjub::EdwardsParameters;
+
+        let leaves = (1..8u32).map(Fr::from).collect::<Vec<_>>();
+
+        let tree = CBMTPEDERSEN::build_merkle_tree(leaves.clone());
+        let root = tree.root();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let var_root =
+            AbstractHashPedersenOutput::alloc(cs.ns(|| "tree_root"), Some(root)).unwrap();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.build_proof(&(i as u32)).unwrap();
+            assert!(proof.verify(&root, leaf));
+
+            let var_leaf =
+                AbstractHashPedersenOutput::alloc(cs.ns(|| format!("leaf_{}", i)), Some(*leaf))
+                    .unwrap();
+
+            let lemmas = proof
+                .lemmas()
+                .iter()
+                .enumerate()
+                .map(|(j, v)| {
+                    AbstractHashPedersenOutput::alloc(
+                        cs.ns(|| format!("proof_lemmas_{}_{}", i, j)),
+                        Some(*v),
+                    )
+                    .unwrap()
+                })
+                .collect();
+
+            let g = MerkleProofGadget::<u32, Fr, AbstractHashPedersen<EdwardsParameters>>::new(
+                proof.index().clone(),
+                lemmas,
+            );
+
+            g.set_membership(
+                cs.ns(|| format!("set membership {}", i)),
+                var_root.clone(),
+                var_leaf,
+            )
+            .unwrap();
+        }
+
+        assert!(cs.is_satisfied());
+    }
+
+    type CBMTPOSEIDON = CBMT<Fr, MergePoseidon<Fr>>;
+
+    #[test]
+    fn test_merkle_tree_arity() {
+        let leaves = (1..=10u32).map(Fr::from).collect::<Vec<_>>();
+
+        for arity in [2, 4, 8].iter() {
+            let tree = CBMTPOSEIDON::build_merkle_tree_with_arity(leaves.clone(), *arity);
+            let root = tree.root();
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.build_proof(&(i as u32)).unwrap();
+                assert!(proof.verify(&root, leaf));
+
+                for tampered in [None, Some(0), Some(proof.lemmas().len() - 1)].iter() {
+                    let mut cs = TestConstraintSystem::<Fr>::new();
+
+                    let var_root =
+                        AbstractHashPoseidonOutput::alloc(cs.ns(|| "tree_root"), Some(root))
+                            .unwrap();
+                    let var_leaf =
+                        AbstractHashPoseidonOutput::alloc(cs.ns(|| "leaf"), Some(*leaf)).unwrap();
+
+                    let lemmas = proof
+                        .lemmas()
+                        .iter()
+                        .enumerate()
+                        .map(|(j, v)| {
+                            let v = if *tampered == Some(j) {
+                                *v + &Fr::from(1u32)
+                            } else {
+                                *v
+                            };
+                            AbstractHashPoseidonOutput::alloc(
+                                cs.ns(|| format!("proof_lemmas_{}", j)),
+                                Some(v),
+                            )
+                            .unwrap()
+                        })
+                        .collect();
+
+                    let g = MerkleProofGadget::<u32, Fr, AbstractHashPoseidon<Fr>>::new_with_arity(
+                        *arity,
+                        *proof.index(),
+                        lemmas,
+                    );
+
+                    g.set_membership(cs.ns(|| "set membership"), var_root, var_leaf)
+                        .unwrap();
+
+                    assert_eq!(cs.is_satisfied(), tampered.is_none());
+                }
+            }
+        }
+    }
+
+    /// Proves the membership of every leaf of a tree merged with `M` in the
+    /// circuit of `H`, whose root also converts to its bits.
+    fn check_membership<F, M, H>()
+    where
+        F: PrimeField,
+        M: Merge<Item = F>,
+        H: AbstractHash<F, Output = AbstractHashFrOutput<F>>,
+    {
+        let leaves = (1..=5u32).map(F::from).collect::<Vec<_>>();
+        let tree = CBMT::<F, M>::build_merkle_tree(leaves.clone());
+        let root = tree.root();
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            let proof = tree.build_proof(&(i as u32)).unwrap();
+            assert!(proof.verify(&root, leaf));
+
+            let mut cs = TestConstraintSystem::<F>::new();
+            let var_root = AbstractHashFrOutput::alloc(cs.ns(|| "root"), Some(root)).unwrap();
+            let var_leaf = AbstractHashFrOutput::alloc(cs.ns(|| "leaf"), Some(*leaf)).unwrap();
+            let lemmas = proof
+                .lemmas()
+                .iter()
+                .enumerate()
+                .map(|(j, v)| {
+                    AbstractHashFrOutput::alloc(cs.ns(|| format!("lemma {}", j)), Some(*v)).unwrap()
+                })
+                .collect();
+
+            let bits = var_root.to_bits_le(cs.ns(|| "root bits")).unwrap();
+            let root_repr = root.into_repr();
+            assert!(bits
+                .iter()
+                .enumerate()
+                .all(|(k, b)| b.get_value() == Some(root_repr.get_bit(k))));
+
+            MerkleProofGadget::<u32, F, H>::new(*proof.index(), lemmas)
+                .set_membership(cs.ns(|| "set membership"), var_root, var_leaf)
+                .unwrap();
+            assert!(cs.is_satisfied());
+
+            // the digests are tied to the leaf.
+            cs.set("leaf/fr", *leaf + &F::one());
+            assert!(!cs.is_satisfied());
+
+            // the root as the public input.
+            let mut cs = TestConstraintSystem::<F>::new();
+            let var_leaf = AbstractHashFrOutput::alloc(cs.ns(|| "leaf"), Some(*leaf)).unwrap();
+            let lemmas = proof
+                .lemmas()
+                .iter()
+                .enumerate()
+                .map(|(j, v)| {
+                    AbstractHashFrOutput::alloc(cs.ns(|| format!("lemma {}", j)), Some(*v)).unwrap()
+                })
+                .collect();
+            MerkleProofGadget::<u32, F, H>::new(*proof.index(), lemmas)
+                .inputize_root(cs.ns(|| "membership"), var_leaf)
+                .unwrap();
+            assert!(cs.verify(&AbstractHashFrOutput::native_field_elements(&root)));
+            assert!(!cs.verify(&[root + &F::one()]));
+        }
+    }
+
+    #[test]
+    fn test_merkle_tree_hashes() {
+        check_membership::<Fr, MergeMimc<Fr>, AbstractHashMimc<Fr>>();
+        check_membership::<Fr, MergePoseidon<Fr>, AbstractHashPoseidon<Fr>>();
+        check_membership::<Fr, MergeRescue<Fr>, AbstractHashRescue<Fr>>();
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_merkle_tree_hashes_bls12_381() {
+        use curve::bls12_381::Fr;
+
+        check_membership::<Fr, MergeMimc<Fr>, AbstractHashMimc<Fr>>();
+        check_membership::<Fr, MergePoseidon<Fr>, AbstractHashPoseidon<Fr>>();
+        check_membership::<Fr, MergeRescue<Fr>, AbstractHashRescue<Fr>>();
+    }
 }
@@ -1,2 +1,7 @@
 pub mod cbmt;
 pub mod cbmt_constraints;
+pub mod update;
+pub mod variable_depth;
+
+pub use self::update::update_gadget;
+pub use self::variable_depth::variable_depth_membership;
@@ -0,0 +1,262 @@
+//! Merkle root transitions: the proof that the tree of `new_root` is the one
+//! of `old_root` with a leaf updated, over a binary tree of any
+//! `AbstractHash`.
+//!
+//! Both roots are recomputed from the same siblings and index bits, which
+//! are allocated once. The two paths hash different children at every
+//! level, the old node and the new one, so their hashes are not shared and
+//! dominate the cost, about twice the one of a membership proof.
+
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::super::abstract_hash::{AbstractHash, AbstractHashOutput};
+use super::super::boolean::Boolean;
+use super::super::swap::conditionally_swap_outputs;
+use super::cbmt::{Merge, MerkleProof, TreeIndex, CBMT};
+
+/// The witness of a leaf update, as `update_gadget` takes it.
+pub struct UpdateWitness<T> {
+    pub old_root: T,
+    pub new_root: T,
+    /// The siblings from the leaf up.
+    pub path: Vec<T>,
+    /// Whether the node of each level is the right child.
+    pub index_bits: Vec<bool>,
+    pub old_leaf: T,
+    pub new_leaf: T,
+}
+
+/// The siblings of the proof from the leaf up, and whether the
+/// node of each level is the right child.
+pub fn path<T, M>(proof: &MerkleProof<T, M>) -> (Vec<T>, Vec<bool>)
+where
+    T: Ord + Default + Clone,
+    M: Merge<Item = T>,
+{
+    let mut index_bits = Vec::with_capacity(proof.lemmas().len());
+    let mut index = *proof.index();
+    while !index.is_root() {
+        index_bits.push(!index.is_left());
+        index = index.parent();
+    }
+
+    (proof.lemmas().to_vec(), index_bits)
+}
+
+/// Updates the leaf `leaf_index` to `new_leaf`, and returns the witness of
+/// the transition of the roots of the tree merged with `M`.
+pub fn update<T, M>(leaves: &mut [T], leaf_index: u32, new_leaf: T) -> Option<UpdateWitness<T>>
+where
+    T: Ord + Default + Clone,
+    M: Merge<Item = T>,
+{
+    let tree = CBMT::<T, M>::build_merkle_tree(leaves.to_vec());
+    let proof = tree.build_proof(&leaf_index)?;
+
+    let old_leaf = leaves[leaf_index as usize].clone();
+    leaves[leaf_index as usize] = new_leaf.clone();
+
+    let (path, index_bits) = path(&proof);
+
+    Some(UpdateWitness {
+        old_root: tree.root(),
+        new_root: proof.root(&new_leaf)?,
+        path,
+        index_bits,
+        old_leaf,
+        new_leaf,
+    })
+}
+
+/// Recomputes the root from `leaf` with `H`, the children of each level
+/// swapped if the bit is set.
+fn compute_root<F, H, CS>(
+    mut cs: CS,
+    leaf: &H::Output,
+    path: &[H::Output],
+    index_bits: &[Boolean],
+) -> Result<H::Output, SynthesisError>
+where
+    F: PrimeField,
+    H: AbstractHash<F>,
+    CS: ConstraintSystem<F>,
+{
+    let mut current = leaf.clone();
+    for (i, (sibling, bit)) in path.iter().zip(index_bits.iter()).enumerate() {
+        let mut cs = cs.ns(|| format!("level {}", i));
+
+        let (left, right) = conditionally_swap_outputs(cs.ns(|| "swap"), bit, &current, sibling)?;
+        current = H::hash_enforce(cs.ns(|| "hash_enforce"), &[&left, &right])?;
+    }
+
+    Ok(current)
+}
+
+/// Enforces the variables of `root` to be the ones of `computed`.
+fn enforce_root<F, O, CS>(mut cs: CS, computed: &O, root: &O)
+where
+    F: PrimeField,
+    O: AbstractHashOutput<F>,
+    CS: ConstraintSystem<F>,
+{
+    for (k, (i, j)) in computed
+        .get_variables()
+        .into_iter()
+        .zip(root.get_variables().into_iter())
+        .enumerate()
+    {
+        cs.enforce(
+            || format!("variable {}", k),
+            |lc| lc + i,
+            |lc| lc + CS::one(),
+            |lc| lc + j,
+        );
+    }
+}
+
+/// Enforces that `new_root` is `old_root` with `old_leaf` updated to
+/// `new_leaf`, both authenticated by the siblings `path` and the
+/// `index_bits`, whether the node of each level is the right child.
+pub fn update_gadget<F, H, CS>(
+    mut cs: CS,
+    old_root: &H::Output,
+    new_root: &H::Output,
+    path: &[H::Output],
+    index_bits: &[Boolean],
+    old_leaf: &H::Output,
+    new_leaf: &H::Output,
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    H: AbstractHash<F>,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(path.len(), index_bits.len());
+
+    let old = compute_root::<F, H, _>(cs.ns(|| "old path"), old_leaf, path, index_bits)?;
+    let new = compute_root::<F, H, _>(cs.ns(|| "new path"), new_leaf, path, index_bits)?;
+
+    enforce_root(cs.ns(|| "old root"), &old, old_root);
+    enforce_root(cs.ns(|| "new root"), &new, new_root);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::bn_256::Fr;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::super::abstract_hash::AbstractHashFrOutput;
+    use super::super::super::boolean::AllocatedBit;
+    use super::super::super::mimc::{AbstractHashMimc, MergeMimc};
+    use super::super::super::poseidon::{AbstractHashPoseidon, MergePoseidon};
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn check<H>(witness: &UpdateWitness<Fr>) -> TestConstraintSystem<Fr>
+    where
+        H: AbstractHash<Fr, Output = AbstractHashFrOutput<Fr>>,
+    {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let mut alloc =
+            |name: &str, v: Fr| AbstractHashFrOutput::alloc(cs.ns(|| name), Some(v)).unwrap();
+        let old_root = alloc("old root", witness.old_root);
+        let new_root = alloc("new root", witness.new_root);
+        let old_leaf = alloc("old leaf", witness.old_leaf);
+        let new_leaf = alloc("new leaf", witness.new_leaf);
+        let path = witness
+            .path
+            .iter()
+            .enumerate()
+            .map(|(i, v)| alloc(&format!("path {}", i), *v))
+            .collect::<Vec<_>>();
+        let index_bits = witness
+            .index_bits
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                Boolean::from(
+                    AllocatedBit::alloc(cs.ns(|| format!("index {}", i)), Some(*b)).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        update_gadget::<_, H, _>(
+            cs.ns(|| "update"),
+            &old_root,
+            &new_root,
+            &path,
+            &index_bits,
+            &old_leaf,
+            &new_leaf,
+        )
+        .unwrap();
+
+        cs
+    }
+
+    /// The constraints of a hash of two children.
+    fn hash_constraints<H>() -> usize
+    where
+        H: AbstractHash<Fr, Output = AbstractHashFrOutput<Fr>>,
+    {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let left = AbstractHashFrOutput::alloc(cs.ns(|| "left"), Some(Fr::from(1u32))).unwrap();
+        let right = AbstractHashFrOutput::alloc(cs.ns(|| "right"), Some(Fr::from(2u32))).unwrap();
+        H::hash_enforce(cs.ns(|| "hash"), &[&left, &right]).unwrap();
+
+        cs.num_constraints()
+    }
+
+    fn check_update<M, H>()
+    where
+        M: Merge<Item = Fr>,
+        H: AbstractHash<Fr, Output = AbstractHashFrOutput<Fr>>,
+    {
+        let leaves = (1..=8u32).map(Fr::from).collect::<Vec<_>>();
+        let root = CBMT::<Fr, M>::build_merkle_root(&leaves);
+
+        // the first leaf, the last one, and a no-op.
+        for &(index, new_leaf) in [(0u32, 100u32), (7, 200), (3, 4)].iter() {
+            let mut updated = leaves.clone();
+            let witness = update::<Fr, M>(&mut updated, index, Fr::from(new_leaf)).unwrap();
+
+            assert_eq!(witness.old_root, root);
+            assert_eq!(witness.new_root, CBMT::<Fr, M>::build_merkle_root(&updated));
+            assert_eq!(updated[index as usize], Fr::from(new_leaf));
+            assert_eq!(witness.index_bits.len(), 3);
+            if leaves == updated {
+                assert_eq!(witness.old_root, witness.new_root);
+            }
+
+            let mut cs = check::<H>(&witness);
+            assert!(cs.is_satisfied());
+
+            // the index bits, both paths and both roots: the siblings and
+            // the index bits are shared, the swaps and the hashes are not.
+            let level = 2 + hash_constraints::<H>();
+            assert_eq!(cs.num_constraints(), 3 + 2 * 3 * level + 2);
+
+            // another new root does not pass.
+            cs.set("new root/fr", Fr::from(1u32));
+            assert!(!cs.is_satisfied());
+        }
+
+        // nor another old leaf.
+        let mut updated = leaves.clone();
+        let mut witness = update::<Fr, M>(&mut updated, 5, Fr::from(0u32)).unwrap();
+        witness.old_leaf = Fr::from(5u32);
+        assert!(!check::<H>(&witness).is_satisfied());
+    }
+
+    #[test]
+    fn test_update() {
+        check_update::<MergeMimc<Fr>, AbstractHashMimc<Fr>>();
+        check_update::<MergePoseidon<Fr>, AbstractHashPoseidon<Fr>>();
+    }
+}
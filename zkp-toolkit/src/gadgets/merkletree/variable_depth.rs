@@ -0,0 +1,243 @@
+//! Membership proofs of a variable depth: one circuit of `depth` levels
+//! accepts the trees of any depth up to it, the actual depth being a
+//! witness. Every level is still hashed, the inactive ones just pass the
+//! running hash through.
+
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::super::boolean::Boolean;
+use super::super::fr::AllocatedFr;
+use super::super::mimc::{mimc_block_enforce, MimcParameters};
+use super::super::swap::conditionally_swap;
+
+/// Pads the `path` and the `index_bits` of `update::path` to `depth`
+/// levels, returning them along with whether each level is active.
+pub fn pad_path<F: PrimeField>(
+    path: &[F],
+    index_bits: &[bool],
+    depth: usize,
+) -> (Vec<F>, Vec<bool>, Vec<bool>) {
+    assert_eq!(path.len(), index_bits.len());
+    assert!(path.len() <= depth);
+
+    let mut padded_path = path.to_vec();
+    padded_path.resize(depth, F::zero());
+    let mut padded_bits = index_bits.to_vec();
+    padded_bits.resize(depth, false);
+    let is_active = (0..depth).map(|i| i < path.len()).collect();
+
+    (padded_path, padded_bits, is_active)
+}
+
+/// Enforces that `leaf` is in the tree of `root`, merged with
+/// `MergeMimc`, at the depth of the active levels. The active
+/// levels are enforced to be a prefix of the levels.
+pub fn variable_depth_membership<F, CS>(
+    mut cs: CS,
+    root: &AllocatedFr<F>,
+    leaf: &AllocatedFr<F>,
+    path: &[AllocatedFr<F>],
+    index_bits: &[Boolean],
+    is_active: &[Boolean],
+) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(path.len(), index_bits.len());
+    assert_eq!(path.len(), is_active.len());
+
+    let params = MimcParameters::shared();
+    let zero = alloc_zero(cs.ns(|| "zero"))?;
+
+    let mut current = leaf.clone();
+    for (i, ((sibling, bit), active)) in path
+        .iter()
+        .zip(index_bits.iter())
+        .zip(is_active.iter())
+        .enumerate()
+    {
+        let mut cs = cs.ns(|| format!("level {}", i));
+
+        // once inactive, always inactive: active[i] * (1 - active[i - 1]) = 0
+        if i > 0 {
+            cs.enforce(
+                || "active prefix",
+                |_| active.lc(CS::one(), F::one()),
+                |_| is_active[i - 1].not().lc(CS::one(), F::one()),
+                |lc| lc,
+            );
+        }
+
+        let merged = merge_enforce(cs.ns(|| "merge"), &zero, &current, sibling, bit, &params)?;
+        current = AllocatedFr::conditionally_select(cs.ns(|| "select"), active, &merged, &current)?;
+    }
+
+    cs.enforce(
+        || "root",
+        |lc| lc + current.get_variable(),
+        |lc| lc + CS::one(),
+        |lc| lc + root.get_variable(),
+    );
+
+    Ok(())
+}
+
+/// Allocates the zero which starts the first MiMC block of every merge.
+fn alloc_zero<F, CS>(mut cs: CS) -> Result<AllocatedFr<F>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    AllocatedFr::constant(cs.ns(|| "zero"), F::zero())
+}
+
+/// The `MergeMimc` of `current` and `sibling`, swapped if `bit` is set.
+fn merge_enforce<F, CS>(
+    mut cs: CS,
+    zero: &AllocatedFr<F>,
+    current: &AllocatedFr<F>,
+    sibling: &AllocatedFr<F>,
+    bit: &Boolean,
+    params: &MimcParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let (left, right) = conditionally_swap(cs.ns(|| "swap"), bit, current, sibling)?;
+
+    let h = mimc_block_enforce(cs.ns(|| "left block"), zero, &left, params)?;
+    mimc_block_enforce(cs.ns(|| "right block"), &h, &right, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::super::boolean::AllocatedBit;
+    use super::super::super::mimc::MergeMimc;
+    use super::super::cbmt::{Merge, CBMT};
+    use super::super::update::path;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    const DEPTH: usize = 32;
+
+    fn check(
+        root: Fr,
+        leaf: Fr,
+        path: &[Fr],
+        index_bits: &[bool],
+        is_active: &[bool],
+    ) -> TestConstraintSystem<Fr> {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let root = AllocatedFr::alloc(cs.ns(|| "root"), || Ok(root)).unwrap();
+        let leaf = AllocatedFr::alloc(cs.ns(|| "leaf"), || Ok(leaf)).unwrap();
+        let path = path
+            .iter()
+            .enumerate()
+            .map(|(i, v)| AllocatedFr::alloc(cs.ns(|| format!("path {}", i)), || Ok(*v)).unwrap())
+            .collect::<Vec<_>>();
+        let mut alloc_bits = |name: &str, bits: &[bool]| {
+            bits.iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    let bit = AllocatedBit::alloc(cs.ns(|| format!("{} {}", name, i)), Some(*b));
+                    Boolean::from(bit.unwrap())
+                })
+                .collect::<Vec<_>>()
+        };
+        let index_bits = alloc_bits("index", index_bits);
+        let is_active = alloc_bits("active", is_active);
+
+        variable_depth_membership(
+            cs.ns(|| "membership"),
+            &root,
+            &leaf,
+            &path,
+            &index_bits,
+            &is_active,
+        )
+        .unwrap();
+
+        cs
+    }
+
+    #[test]
+    fn test_variable_depth_membership() {
+        let rng = &mut test_rng();
+        let mut num_constraints = vec![];
+
+        // a depth-4 tree.
+        let leaves = (0..16u32).map(Fr::from).collect::<Vec<_>>();
+        let tree = CBMT::<Fr, MergeMimc<Fr>>::build_merkle_tree(leaves.clone());
+        for &i in [0u32, 9, 15].iter() {
+            let (p, bits) = path(&tree.build_proof(&i).unwrap());
+            assert_eq!(p.len(), 4);
+
+            let (p, bits, active) = pad_path(&p, &bits, DEPTH);
+            let cs = check(tree.root(), leaves[i as usize], &p, &bits, &active);
+            assert!(cs.is_satisfied());
+            num_constraints.push(cs.num_constraints());
+
+            // another leaf does not pass.
+            let cs = check(tree.root(), Fr::from(100u32), &p, &bits, &active);
+            assert!(!cs.is_satisfied());
+        }
+
+        // a path of a depth-16 tree.
+        let leaf: Fr = rng.gen();
+        let p = (0..16).map(|_| rng.gen()).collect::<Vec<Fr>>();
+        let bits = (0..16).map(|_| rng.gen()).collect::<Vec<bool>>();
+        let root = p.iter().zip(bits.iter()).fold(leaf, |current, (s, b)| {
+            if *b {
+                MergeMimc::merge(s, &current)
+            } else {
+                MergeMimc::merge(&current, s)
+            }
+        });
+
+        let (p, bits, active) = pad_path(&p, &bits, DEPTH);
+        let cs = check(root, leaf, &p, &bits, &active);
+        assert!(cs.is_satisfied());
+        num_constraints.push(cs.num_constraints());
+
+        // a shallower proof does not pass.
+        let active = (0..DEPTH).map(|i| i < 15).collect::<Vec<_>>();
+        assert!(!check(root, leaf, &p, &bits, &active).is_satisfied());
+
+        // the same circuit.
+        assert!(num_constraints.iter().all(|n| *n == num_constraints[0]));
+    }
+
+    #[test]
+    fn test_variable_depth_out_of_order() {
+        let leaves = (0..16u32).map(Fr::from).collect::<Vec<_>>();
+        let tree = CBMT::<Fr, MergeMimc<Fr>>::build_merkle_tree(leaves.clone());
+        let (p, bits) = path(&tree.build_proof(&5).unwrap());
+
+        // the 4 levels at 0, 1, 2 and 4, passing through 3: the root is
+        // recomputed, but the active levels are no prefix.
+        let mut p = p;
+        let mut bits = bits;
+        p.insert(3, Fr::from(0u32));
+        bits.insert(3, false);
+        let (p, bits, mut active) = pad_path(&p, &bits, DEPTH);
+        active[3] = false;
+
+        let cs = check(tree.root(), leaves[5], &p, &bits, &active);
+        assert!(!cs.is_satisfied());
+        assert_eq!(
+            cs.which_is_unsatisfied().as_deref(),
+            Some("membership/level 4/active prefix")
+        );
+    }
+}
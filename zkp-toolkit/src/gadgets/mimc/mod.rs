@@ -0,0 +1,598 @@
+//! Circuits for the [MiMC] hash function and its constants function.
+//!
+//! [MiMC]: http://eprint.iacr.org/2016/492
+
+use core::marker::PhantomData;
+
+use math::{Field, FromBytes, PrimeField};
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::abstract_hash::{AbstractHash, AbstractHashFrOutput};
+use super::fr::AllocatedFr;
+use super::merkletree::cbmt::Merge;
+
+pub mod params;
+
+pub use params::MimcParameters;
+
+/// This is we used MiMC rounds constant.
+pub const MIMC_ROUNDS: usize = 322;
+
+/// This is we used MiMC constants's seed, it can derived constants with different pairing curve.
+pub const SEED: [u8; 32] = [0; 32];
+
+/// it will return MiMC's constants, when use pairing curve as generic type.
+pub fn constants<F: Field>() -> [F; MIMC_ROUNDS] {
+    constants_with_seed(SEED)
+}
+
+/// it will return MiMC's constants, when use pairing curve as generic type, and use custom seed.
+pub fn constants_with_seed<F: Field>(seed: [u8; 32]) -> [F; MIMC_ROUNDS] {
+    use rand::{Rng, SeedableRng};
+    let rng = &mut rand::rngs::StdRng::from_seed(seed);
+
+    let mut constants = [F::zero(); MIMC_ROUNDS];
+
+    let mut i = 0;
+    loop {
+        let new_seed: [u8; 32] = rng.gen();
+        if let Some(f) = F::from_random_bytes(&new_seed) {
+            constants[i] = f;
+            i += 1;
+            if i == MIMC_ROUNDS {
+                break;
+            }
+        }
+    }
+
+    constants
+}
+
+/// This is an implementation of MiMC, the default parameters are the
+/// variant named `LongsightF322p3` for BN-256.
+/// See http://eprint.iacr.org/2016/492 for more
+/// information about this construction.
+pub fn mimc_block<F: PrimeField>(mut xl: F, mut xr: F, params: &MimcParameters<F>) -> F {
+    for c in params.constants.iter() {
+        let mut tmp = xl;
+        tmp.add_assign(c);
+        let mut tmp = tmp.pow([params.exponent]);
+        tmp.add_assign(&xr);
+        xr = xl;
+        xl = tmp;
+    }
+
+    xl
+}
+
+/// mimc hash function.
+fn mimc_hash<F: PrimeField>(b: &[u8], params: &MimcParameters<F>) -> (F, F, F) {
+    let mut v: Vec<F> = Vec::new();
+    let n = <F::BigInt as math::BigInteger>::NUM_LIMBS * 8;
+    for i in 0..(b.len() / n) {
+        let repr = F::BigInt::read(&b[i * n..(i + 1) * n]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    if b.len() % n != 0 {
+        let repr = F::BigInt::read(&b[(b.len() / n) * n..]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    let mut h: F = F::zero();
+    let xr = v[v.len() - 1].clone();
+    let mut xl = F::zero();
+
+    for i in 0..v.len() {
+        if i == v.len() - 1 {
+            xl = h.clone();
+        }
+
+        h = mimc_block(h, v[i], params);
+    }
+
+    (xl, xr, h)
+}
+
+pub fn hash<F: PrimeField>(b: &[u8], params: &MimcParameters<F>) -> F {
+    mimc_hash(b, params).2
+}
+
+pub fn mimc<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    b: Option<&[u8]>,
+    params: &MimcParameters<F>,
+) -> Result<Option<F>, SynthesisError> {
+    let (xl_value, xr_value, image_value) = match b {
+        Some(bytes) => {
+            let (xl, xr, image) = mimc_hash(bytes, params);
+            (Some(xl), Some(xr), Some(image))
+        }
+        None => (None, None, None),
+    };
+
+    let xl = AllocatedFr::alloc(cs.ns(|| "preimage xl"), || {
+        xl_value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    let xr = AllocatedFr::alloc(cs.ns(|| "preimage xr"), || {
+        xr_value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+
+    mimc_block_enforce(cs, &xl, &xr, params)?;
+
+    Ok(image_value)
+}
+
+/// Allocates `a * b`.
+fn mul_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let product = AllocatedFr::alloc(cs.ns(|| "product"), || {
+        match (a.get_value(), b.get_value()) {
+            (Some(a), Some(b)) => Ok(a * b),
+            _ => Err(SynthesisError::AssignmentMissing),
+        }
+    })?;
+
+    cs.enforce(
+        || "product = a * b",
+        |lc| lc + a.get_variable(),
+        |lc| lc + b.get_variable(),
+        |lc| lc + product.get_variable(),
+    );
+
+    Ok(product)
+}
+
+/// The parameters of the gadgets have an exponent of 3, 5 or 7 and one
+/// constant a round, their fields are public so they may be built without
+/// `MimcParameters::new`.
+fn check_parameters<F: PrimeField>(params: &MimcParameters<F>) -> Result<(), SynthesisError> {
+    match params.exponent {
+        3 | 5 | 7 if params.constants.len() == params.rounds => Ok(()),
+        _ => Err(SynthesisError::MalformedParameters),
+    }
+}
+
+/// Allocates `xR + (xL + c)^exponent`, the round of MiMC in
+/// `(exponent + 1) / 2` constraints.
+fn round_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    xl: &AllocatedFr<F>,
+    xr: &AllocatedFr<F>,
+    c: F,
+    exponent: u64,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let xl_c = xl.get_value().map(|xl| xl + c);
+    let tmp = AllocatedFr::alloc(cs.ns(|| "tmp"), || {
+        xl_c.map(|v| v.square())
+            .ok_or(SynthesisError::AssignmentMissing)
+    })?;
+
+    cs.enforce(
+        || "tmp = (xL + Ci)^2",
+        |lc| lc + xl.get_variable() + (c, CS::one()),
+        |lc| lc + xl.get_variable() + (c, CS::one()),
+        |lc| lc + tmp.get_variable(),
+    );
+
+    // (xL + Ci)^(exponent - 1)
+    let tmp = match exponent {
+        3 => tmp,
+        5 => mul_enforce(cs.ns(|| "tmp4 = tmp^2"), &tmp, &tmp)?,
+        7 => {
+            let tmp4 = mul_enforce(cs.ns(|| "tmp4 = tmp^2"), &tmp, &tmp)?;
+            mul_enforce(cs.ns(|| "tmp6 = tmp4 * tmp"), &tmp4, &tmp)?
+        }
+        _ => return Err(SynthesisError::MalformedParameters),
+    };
+
+    let new_xl = AllocatedFr::alloc(cs.ns(|| "new_xl"), || {
+        match (xl_c, tmp.get_value(), xr.get_value()) {
+            (Some(xl_c), Some(tmp), Some(xr)) => Ok(xl_c * tmp + xr),
+            _ => Err(SynthesisError::AssignmentMissing),
+        }
+    })?;
+
+    cs.enforce(
+        || format!("new_xL = xR + (xL + Ci)^{}", exponent),
+        |lc| lc + tmp.get_variable(),
+        |lc| lc + xl.get_variable() + (c, CS::one()),
+        |lc| lc + new_xl.get_variable() - xr.get_variable(),
+    );
+
+    Ok(new_xl)
+}
+
+/// The gadget of `mimc_block`, returns the allocated image. A round costs
+/// `(exponent + 1) / 2` constraints. Malformed parameters fail with
+/// `MalformedParameters`.
+pub fn mimc_block_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    xl: &AllocatedFr<F>,
+    xr: &AllocatedFr<F>,
+    params: &MimcParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    check_parameters(params)?;
+    let mut xl = xl.clone();
+    let mut xr = xr.clone();
+
+    for (i, c) in params.constants.iter().enumerate() {
+        let new_xl = round_enforce(
+            cs.ns(|| format!("rounds_{}", i)),
+            &xl,
+            &xr,
+            *c,
+            params.exponent,
+        )?;
+        xr = xl;
+        xl = new_xl;
+    }
+
+    Ok(xl)
+}
+
+/// The MiMC hash of the elements, one block each from zero, the one of
+/// their bytes.
+pub fn hash_elements<F: PrimeField>(elements: &[F], params: &MimcParameters<F>) -> F {
+    elements
+        .iter()
+        .fold(F::zero(), |h, e| mimc_block(h, *e, params))
+}
+
+/// The gadget of `hash_elements`, the zero the first block starts from
+/// costs a constraint.
+pub fn hash_elements_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    elements: &[AllocatedFr<F>],
+    params: &MimcParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let mut h = AllocatedFr::constant(cs.ns(|| "zero"), F::zero())?;
+
+    for (i, e) in elements.iter().enumerate() {
+        h = mimc_block_enforce(cs.ns(|| format!("block {}", i)), &h, e, params)?;
+    }
+
+    Ok(h)
+}
+
+/// The `MiMCFeistel` permutation of circomlib with the key `k`: the rounds
+/// of `mimc_block`, the last one without the swap of the halves.
+pub fn feistel<F: PrimeField>(mut xl: F, mut xr: F, k: F, params: &MimcParameters<F>) -> (F, F) {
+    for (i, c) in params.constants.iter().enumerate() {
+        let t = (xl + k + c).pow([params.exponent]);
+        if i + 1 < params.rounds {
+            let new_xl = xr + t;
+            xr = xl;
+            xl = new_xl;
+        } else {
+            xr += t;
+        }
+    }
+
+    (xl, xr)
+}
+
+/// The sponge of `feistel` with the zero key over the elements, one of them
+/// added to the left half before each permutation, the image is the left
+/// half at the end. This is `MiMCSponge(n, 220, 1)` of circomlib with the
+/// parameters of `MimcParameters::feistel`.
+pub fn sponge<F: PrimeField>(elements: &[F], params: &MimcParameters<F>) -> F {
+    let (mut r, mut c) = (F::zero(), F::zero());
+    for e in elements {
+        let (new_r, new_c) = feistel(r + e, c, F::zero(), params);
+        r = new_r;
+        c = new_c;
+    }
+
+    r
+}
+
+/// The 2-to-1 compression of the Feistel mode, the `sponge` of the two
+/// elements, as the merkle trees of circomlib hash their nodes.
+pub fn mimc_feistel<F: PrimeField>(left: F, right: F, params: &MimcParameters<F>) -> F {
+    sponge(&[left, right], params)
+}
+
+/// The gadget of `feistel`, the key is a constant. A round costs
+/// `(exponent + 1) / 2` constraints. Malformed parameters fail with
+/// `MalformedParameters`.
+pub fn feistel_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    xl: &AllocatedFr<F>,
+    xr: &AllocatedFr<F>,
+    k: F,
+    params: &MimcParameters<F>,
+) -> Result<(AllocatedFr<F>, AllocatedFr<F>), SynthesisError> {
+    check_parameters(params)?;
+    let mut xl = xl.clone();
+    let mut xr = xr.clone();
+
+    for (i, c) in params.constants.iter().enumerate() {
+        let t = round_enforce(
+            cs.ns(|| format!("rounds_{}", i)),
+            &xl,
+            &xr,
+            *c + k,
+            params.exponent,
+        )?;
+        if i + 1 < params.rounds {
+            xr = xl;
+            xl = t;
+        } else {
+            xr = t;
+        }
+    }
+
+    Ok((xl, xr))
+}
+
+/// The gadget of `sponge`, the zero state costs a constraint, and every
+/// element but the first one another for its addition.
+pub fn sponge_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    elements: &[AllocatedFr<F>],
+    params: &MimcParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let zero = AllocatedFr::constant(cs.ns(|| "zero"), F::zero())?;
+
+    let (mut r, mut c) = (zero.clone(), zero);
+    for (i, e) in elements.iter().enumerate() {
+        let mut n_cs = cs.ns(|| format!("absorb {}", i));
+        let sum = if i == 0 {
+            e.clone()
+        } else {
+            let sum =
+                AllocatedFr::alloc(n_cs.ns(|| "sum"), || match (r.get_value(), e.get_value()) {
+                    (Some(r), Some(e)) => Ok(r + e),
+                    _ => Err(SynthesisError::AssignmentMissing),
+                })?;
+            n_cs.enforce(
+                || "sum = R + e",
+                |lc| lc + r.get_variable() + e.get_variable(),
+                |lc| lc + CS::one(),
+                |lc| lc + sum.get_variable(),
+            );
+            sum
+        };
+
+        let (new_r, new_c) = feistel_enforce(n_cs.ns(|| "feistel"), &sum, &c, F::zero(), params)?;
+        r = new_r;
+        c = new_c;
+    }
+
+    Ok(r)
+}
+
+/// The gadget of `mimc_feistel`.
+pub fn mimc_feistel_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    cs: CS,
+    left: &AllocatedFr<F>,
+    right: &AllocatedFr<F>,
+    params: &MimcParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    sponge_enforce(cs, &[left.clone(), right.clone()], params)
+}
+
+/// Merges the nodes of a merkle tree into the MiMC hash of their bytes, as
+/// `AbstractHashMimc` does in the circuit.
+pub struct MergeMimc<F>(PhantomData<F>);
+
+impl<F: PrimeField> Merge for MergeMimc<F> {
+    type Item = F;
+
+    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
+        Self::merge_many(&[*left, *right])
+    }
+
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        hash_elements(items, &MimcParameters::shared())
+    }
+}
+
+pub type AbstractHashMimcOutput<F> = AbstractHashFrOutput<F>;
+
+/// The `hash_elements` of the nodes.
+pub struct AbstractHashMimc<F>(PhantomData<F>);
+
+impl<F: PrimeField> AbstractHash<F> for AbstractHashMimc<F> {
+    type Output = AbstractHashMimcOutput<F>;
+
+    fn hash_enforce<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        params: &[&Self::Output],
+    ) -> Result<Self::Output, SynthesisError> {
+        let elements = params
+            .iter()
+            .map(|o| o.to_allocated_fr())
+            .collect::<Vec<_>>();
+
+        hash_elements_enforce(cs.ns(|| "mimc_hash"), &elements, &MimcParameters::shared())
+            .map(Into::into)
+    }
+}
+
+/// Merges the nodes of a merkle tree into their `sponge`, as
+/// `AbstractHashMimcFeistel` does in the circuit.
+pub struct MergeMimcFeistel<F>(PhantomData<F>);
+
+impl<F: PrimeField> Merge for MergeMimcFeistel<F> {
+    type Item = F;
+
+    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
+        Self::merge_many(&[*left, *right])
+    }
+
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        sponge(items, &MimcParameters::shared_feistel())
+    }
+}
+
+/// The `sponge` of the nodes, with the parameters of
+/// `MimcParameters::feistel`.
+pub struct AbstractHashMimcFeistel<F>(PhantomData<F>);
+
+impl<F: PrimeField> AbstractHash<F> for AbstractHashMimcFeistel<F> {
+    type Output = AbstractHashMimcOutput<F>;
+
+    fn hash_enforce<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        params: &[&Self::Output],
+    ) -> Result<Self::Output, SynthesisError> {
+        let elements = params
+            .iter()
+            .map(|o| o.to_allocated_fr())
+            .collect::<Vec<_>>();
+
+        sponge_enforce(
+            cs.ns(|| "mimc_sponge"),
+            &elements,
+            &MimcParameters::shared_feistel(),
+        )
+        .map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, ToBytes, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn native_and_gadget<F: PrimeField>(params: &MimcParameters<F>) {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let bytes: Vec<u8> = (0..100).map(|_| rng.next_u32() as u8).collect();
+            let hash1 = hash::<F>(&bytes, params);
+            let mut cs = TestConstraintSystem::<F>::new();
+            let hash2 = mimc(cs.ns(|| "mimc hash"), Some(&bytes), params).unwrap();
+            assert_eq!(hash1, hash2.unwrap());
+            assert!(cs.is_satisfied());
+            // the last block only, its halves are allocated.
+            assert_eq!(
+                params.rounds * (params.exponent as usize + 1) / 2,
+                cs.num_constraints()
+            );
+        }
+    }
+
+    #[test]
+    fn test_mimc_hash() {
+        native_and_gadget(&MimcParameters::<Fr>::default());
+        native_and_gadget(&MimcParameters::<Fr>::bn_256());
+        native_and_gadget(&MimcParameters::<Fr>::with_label(b"mimc_x7", 182, 7));
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_mimc_hash_bls12_381() {
+        use curve::bls12_381::Fr;
+
+        native_and_gadget(&MimcParameters::<Fr>::default());
+        native_and_gadget(&MimcParameters::<Fr>::bls12_381());
+    }
+
+    #[test]
+    fn test_hash_elements() {
+        let rng = &mut test_rng();
+        let params = MimcParameters::<Fr>::default();
+
+        for n in 1..4 {
+            let elements: Vec<Fr> = (0..n).map(|_| rng.gen()).collect();
+            let mut bytes = vec![];
+            for e in elements.iter() {
+                e.write(&mut bytes).unwrap();
+            }
+            let image = hash_elements(&elements, &params);
+            assert_eq!(image, hash::<Fr>(&bytes, &params));
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let vars = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| AllocatedFr::alloc(cs.ns(|| format!("e {}", i)), || Ok(*e)).unwrap())
+                .collect::<Vec<_>>();
+            let r = hash_elements_enforce(cs.ns(|| "hash"), &vars, &params).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(r.get_value(), Some(image));
+            assert_eq!(cs.num_constraints(), 1 + 644 * n);
+        }
+    }
+
+    #[test]
+    fn test_mimc_feistel() {
+        // the empty leaves of the merkle tree of Tornado Cash, the first is
+        // keccak256("tornado") and the next ones are the `MiMCSponge`
+        // compressions of two of the previous.
+        let zeros = [
+            "21663839004416932945382355908790599225266501822907911457504978515578255421292",
+            "16923532097304556005972200564242292693309333953544141029519619077135960040221",
+            "7833458610320835472520144237082236871909694928684820466656733259024982655488",
+            "14506027710748750947258687001455876266559341618222612722926156490737302846427",
+        ];
+        let zeros = zeros
+            .iter()
+            .map(|z| z.parse::<Fr>().unwrap())
+            .collect::<Vec<_>>();
+        let params = MimcParameters::<Fr>::feistel();
+
+        for w in zeros.windows(2) {
+            assert_eq!(mimc_feistel(w[0], w[0], &params), w[1]);
+            assert_eq!(MergeMimcFeistel::merge(&w[0], &w[0]), w[1]);
+        }
+
+        let rng = &mut test_rng();
+        for n in 1..4 {
+            let elements: Vec<Fr> = (0..n).map(|_| rng.gen()).collect();
+            let image = sponge(&elements, &params);
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let vars = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| AllocatedFr::alloc(cs.ns(|| format!("e {}", i)), || Ok(*e)).unwrap())
+                .collect::<Vec<_>>();
+            let r = sponge_enforce(cs.ns(|| "sponge"), &vars, &params).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(r.get_value(), Some(image));
+            assert_eq!(cs.num_constraints(), 1 + (n - 1) + 660 * n);
+        }
+    }
+
+    #[test]
+    fn test_malformed_parameters() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let x = AllocatedFr::alloc(cs.ns(|| "x"), || Ok(Fr::from(1u32))).unwrap();
+
+        let mut exponent = MimcParameters::<Fr>::default();
+        exponent.exponent = 4;
+        let mut rounds = MimcParameters::<Fr>::default();
+        rounds.rounds += 1;
+
+        for (i, params) in [exponent, rounds].iter().enumerate() {
+            assert!(matches!(
+                mimc_block_enforce(cs.ns(|| format!("block {}", i)), &x, &x, params),
+                Err(SynthesisError::MalformedParameters)
+            ));
+            assert!(matches!(
+                feistel_enforce(
+                    cs.ns(|| format!("feistel {}", i)),
+                    &x,
+                    &x,
+                    Fr::zero(),
+                    params
+                ),
+                Err(SynthesisError::MalformedParameters)
+            ));
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Parameters of MiMC in the Feistel mode.
+//!
+//! The round constants of new instances are derived from a label, the
+//! `i`-th one is `Keccak256(label || i)` read big-endian and reduced modulo
+//! the field, so that they can be recomputed anywhere. The default instance
+//! keeps the constants the gadget always used, sampled from `StdRng` seeded
+//! with `SEED`, so that the proofs made with it still verify.
+use math::{FpParameters, PrimeField};
+use scheme::r1cs::SynthesisError;
+use sha3::{Digest, Keccak256};
+
+use crate::{Arc, Vec};
+
+use super::super::cache::cached;
+use super::{constants_with_seed, MIMC_ROUNDS, SEED};
+
+/// The parameters of MiMC over `F`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MimcParameters<F: PrimeField> {
+    /// the number of rounds.
+    pub rounds: usize,
+    /// the exponent of the round function, `(xL + Ci)^exponent`.
+    pub exponent: u64,
+    /// the round constants, one for every round.
+    pub constants: Vec<F>,
+}
+
+impl<F: PrimeField> MimcParameters<F> {
+    /// The parameters with the given constants, one for every round.
+    pub fn new(rounds: usize, exponent: u64, constants: Vec<F>) -> Self {
+        assert!(
+            exponent == 3 || exponent == 5 || exponent == 7,
+            "the exponent of mimc is 3, 5 or 7"
+        );
+        assert_eq!(constants.len(), rounds, "one constant a round");
+
+        Self {
+            rounds,
+            exponent,
+            constants,
+        }
+    }
+
+    /// The parameters with the constants derived from `label`.
+    pub fn with_label(label: &[u8], rounds: usize, exponent: u64) -> Self {
+        Self::new(rounds, exponent, label_constants(label, rounds))
+    }
+
+    /// The `x^5` instance with 220 rounds of `MiMCSponge` of circomlib, for
+    /// `mimc_feistel`, the constants of `chain_constants`.
+    pub fn feistel() -> Self {
+        Self::shared_feistel().as_ref().clone()
+    }
+
+    /// The parameters of `feistel`, generated once per field and then shared.
+    pub fn shared_feistel() -> Arc<Self> {
+        cached("mimc feistel", || {
+            let constants = chain_constants(b"mimcsponge", 220).expect("220 rounds");
+            Self::new(220, 5, constants)
+        })
+    }
+
+    /// The default parameters, generated once per field and then shared.
+    pub fn shared() -> Arc<Self> {
+        cached("mimc", || {
+            Self::new(MIMC_ROUNDS, 3, constants_with_seed::<F>(SEED).to_vec())
+        })
+    }
+}
+
+impl<F: PrimeField> Default for MimcParameters<F> {
+    /// The `x^3` instance with 322 rounds and the constants of `SEED`,
+    /// `LongsightF322p3` over bn_256.
+    fn default() -> Self {
+        Self::shared().as_ref().clone()
+    }
+}
+
+#[cfg(feature = "bn_256")]
+impl MimcParameters<curve::bn_256::Fr> {
+    /// The `x^5` instance over bn_256, the constants of `mimc_bn256_seed`.
+    pub fn bn_256() -> Self {
+        Self::with_label(b"mimc_bn256_seed", 220, 5)
+    }
+}
+
+#[cfg(feature = "bls12_381")]
+impl MimcParameters<curve::bls12_381::Fr> {
+    /// The `x^5` instance over bls12_381, the constants of
+    /// `mimc_bls12_381_seed`.
+    pub fn bls12_381() -> Self {
+        Self::with_label(b"mimc_bls12_381_seed", 220, 5)
+    }
+}
+
+/// `rounds` constants, `Keccak256(label || i)` with `i` as big-endian
+/// `u32`, reduced modulo the field.
+pub fn label_constants<F: PrimeField>(label: &[u8], rounds: usize) -> Vec<F> {
+    let base = F::from(256u32);
+
+    (0..rounds as u32)
+        .map(|i| {
+            let mut hasher = Keccak256::new();
+            hasher.update(label);
+            hasher.update(i.to_be_bytes());
+            hasher
+                .finalize()
+                .iter()
+                .fold(F::zero(), |acc, b| acc * base + F::from(*b as u32))
+        })
+        .collect()
+}
+
+/// `rounds` constants as circomlib derives them: the `i`-th one is the
+/// `i`-th `Keccak256` of `seed` read big-endian and reduced modulo the field,
+/// and the first and the last ones are zero, so there are at least 2 rounds.
+pub fn chain_constants<F: PrimeField>(
+    seed: &[u8],
+    rounds: usize,
+) -> Result<Vec<F>, SynthesisError> {
+    if rounds < 2 {
+        return Err(SynthesisError::MalformedParameters);
+    }
+    let base = F::from(256u32);
+
+    let mut digest = Keccak256::digest(seed);
+    let mut constants = vec![F::zero(); rounds];
+    for c in constants[1..rounds - 1].iter_mut() {
+        digest = Keccak256::digest(&digest);
+        *c = digest
+            .iter()
+            .fold(F::zero(), |acc, b| acc * base + F::from(*b as u32));
+    }
+    Ok(constants)
+}
+
+/// The number of rounds of the Feistel mode with `x^exponent`, twice the
+/// ones for the degree of the left half to reach the field size.
+#[cfg(feature = "std")]
+pub fn round_number<F: PrimeField>(exponent: u64) -> usize {
+    let n = F::Params::MODULUS_BITS as f64;
+    2 * (n / (exponent as f64).log2()).ceil() as usize
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+
+    use super::*;
+
+    #[test]
+    fn test_legacy_constants() {
+        // the constants of `SEED` printed by the gadget before the
+        // parameters, one decimal a line.
+        let expected = include_str!("../../../tests/snapshots/mimc_constants.txt")
+            .lines()
+            .map(|s| s.parse::<Fr>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), MIMC_ROUNDS);
+        assert_eq!(MimcParameters::<Fr>::default().constants, expected);
+    }
+
+    #[test]
+    fn test_label_constants() {
+        let params = MimcParameters::<Fr>::bn_256();
+        assert_eq!(params.constants.len(), 220);
+        assert_eq!(
+            params.constants,
+            label_constants::<Fr>(b"mimc_bn256_seed", 220)
+        );
+        assert_ne!(
+            params.constants,
+            label_constants::<Fr>(b"mimc_bn256_seed_", 220)
+        );
+        assert_eq!(
+            params.constants[0],
+            "12525475466787551845759954212236873018414543039339033856632014968287707188142"
+                .parse::<Fr>()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_chain_constants() {
+        let constants = chain_constants::<Fr>(b"mimcsponge", 2).unwrap();
+        assert_eq!(constants, vec![Fr::from(0u32); 2]);
+        for rounds in 0..2 {
+            assert!(matches!(
+                chain_constants::<Fr>(b"mimcsponge", rounds),
+                Err(SynthesisError::MalformedParameters)
+            ));
+        }
+        assert!(Arc::ptr_eq(
+            &MimcParameters::<Fr>::shared_feistel(),
+            &MimcParameters::<Fr>::shared_feistel()
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_round_number() {
+        assert_eq!(round_number::<Fr>(3), MIMC_ROUNDS);
+        assert_eq!(round_number::<Fr>(5), 220);
+        assert_eq!(round_number::<Fr>(7), 182);
+    }
+}
@@ -1,17 +1,33 @@
-//pub mod blake2s;
+pub mod blake2s;
 pub mod boolean;
+mod cache;
+pub mod cmp;
+#[cfg(feature = "ecdsa")]
+pub mod ecdsa;
+pub mod elgamal;
 pub mod fr;
+#[cfg(feature = "groth16")]
+pub mod groth16_verifier;
+pub mod int64;
 pub mod lookup;
 pub mod merkletree;
 pub mod mimc;
+#[cfg(feature = "ecdsa")]
+pub mod modexp;
 pub mod multieq;
+pub mod multipack;
+pub mod native;
+#[cfg(feature = "ecdsa")]
+pub mod nonnative;
+pub mod pedersen;
 pub mod poseidon;
 pub mod rescue;
 pub mod sha256;
+pub mod swap;
+pub mod uint128;
 pub mod uint32;
+pub mod uint64;
+pub mod uint8;
 
 // traits
 pub mod abstract_hash;
-
-#[cfg(test)]
-mod test_constraint_system;
@@ -0,0 +1,223 @@
+//! Modular exponentiation `base^exponent mod n` of the arithmetic of
+//! `nonnative`, e.g. to verify RSA signatures, RSA accumulator memberships
+//! or VDFs of a 2048-bit modulus.
+
+use math::{One, PrimeField};
+use num_bigint::BigUint;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::Boolean;
+use super::nonnative::NonNativeFieldVar;
+
+/// `base^exponent` of the little-endian bits of `exponent`, by square and
+/// multiply from the top bit, of a product and a selection a variable bit.
+/// A constant bit costs the product or nothing.
+pub fn pow<F, CS>(
+    mut cs: CS,
+    base: NonNativeFieldVar<F>,
+    exponent: Vec<Boolean>,
+) -> Result<NonNativeFieldVar<F>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let one = NonNativeFieldVar::constant(base.params(), &BigUint::one());
+
+    // no squaring of the one before the top bit
+    let mut acc: Option<NonNativeFieldVar<F>> = None;
+    for (i, bit) in exponent.iter().enumerate().rev() {
+        let mut cs = cs.ns(|| format!("bit {}", i));
+
+        let square = match acc {
+            Some(acc) => acc.square(cs.ns(|| "square"))?,
+            None => one.clone(),
+        };
+        acc = Some(match bit {
+            Boolean::Constant(false) => square,
+            Boolean::Constant(true) if square.is_constant() => base.clone(),
+            Boolean::Constant(true) => square.mul(cs.ns(|| "multiply"), &base)?,
+            _ if square.is_constant() => {
+                NonNativeFieldVar::conditionally_select(cs.ns(|| "select"), bit, &base, &square)?
+            }
+            _ => {
+                let product = square.mul(cs.ns(|| "multiply"), &base)?;
+                NonNativeFieldVar::conditionally_select(cs.ns(|| "select"), bit, &product, &square)?
+            }
+        });
+    }
+
+    Ok(acc.unwrap_or(one))
+}
+
+/// `base^65537`, the public exponent of about every RSA key, of 16 squarings
+/// and a product.
+pub fn pow_65537<F, CS>(
+    cs: CS,
+    base: NonNativeFieldVar<F>,
+) -> Result<NonNativeFieldVar<F>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let exponent = (0..17)
+        .map(|i| Boolean::constant(i == 0 || i == 16))
+        .collect::<Vec<_>>();
+    pow(cs, base, exponent)
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use curve::bn_256::Fr;
+
+    use super::super::boolean::AllocatedBit;
+    use super::super::nonnative::NonNativeFieldParams;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    const LIMB_WIDTH: usize = 64;
+
+    fn hex(s: &str) -> BigUint {
+        BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+    }
+
+    /// A 2048-bit key and its PKCS #1 v1.5 signature of "ckb-zkp modexp
+    /// gadget" of SHA-256, by OpenSSL.
+    fn vector() -> (BigUint, BigUint, BigUint) {
+        let n = hex(concat!(
+            "e003d52ff8729ea1e4e53d128bd9f85fd09078cb91b73cea08d2b8376158aa71",
+            "7f1a3db4c7073b73544e7aff34268e5cf436562326cc684c9b1310973540db70",
+            "3309b5b4a6e2a6057f58cc78b381599c3db1d33cb5a77479077782bc8510b24b",
+            "df1ff2946de24f08e82cd777fa39bd293b214f6bf8aa061329accd069dc92f4f",
+            "66c4182698c2c5ea4c5b06273f5e768c8563f3128c839e74e0306b4a6fd65cc9",
+            "24683713f9ed6d913262d8cafcc483e75de0c5623550bb3bd5c9410985b7a02a",
+            "23b25c379017678ae2b8753c4a2f240c39c1a8a306ae4afadbd3b0ec45a59eb2",
+            "c9f9a872f3bf7634855c015e62daac058ffb67c61a57edfca16308f957bbeacd",
+        ));
+        let s = hex(concat!(
+            "2cf15b4504d337c71fd2b402f693f9d9240dbe7e7534c4a58175f38937681e83",
+            "58ed4482841918e6d97603c59ec6e763c66767075b2abccf755a2ac20daf13ef",
+            "ef9b7a67083dc8a9c3af7dc9ed8427d0c43ed04cedaf8f144b5afcb97097a40f",
+            "5893c91d588e788367aad073bc6e8d9b5553e5bd2ee5a05d5abc90cf9a7f6b04",
+            "76155857664f7d0e4aa8ba2c6ad2517e18ca290265053790d1a0fe7ddd6ad042",
+            "cb048f48f895f4497ea1c64e1cb07f72e5935cbf57fa8d493be02120120672a2",
+            "18b6ff909cc35a08f8475461751e191176ddd959589260f8457bd01edded9549",
+            "b95f371cc6683a2dfdce823b4e231ade757ad358859b53778a3aebb46454415c",
+        ));
+        let hash = hex("5c9d844d38b276a7c3a2e8c2ce13ac0d3446567fd6eeebffe7f74f0263ce012a");
+        (n, s, hash)
+    }
+
+    /// The PKCS #1 v1.5 encoding of a SHA-256 `hash` of 256 bytes,
+    /// `00 01 ff .. ff 00 || DigestInfo || hash`.
+    fn encoding(hash: &BigUint) -> BigUint {
+        let digest_info = hex("3031300d060960864801650304020105000420");
+        let padding = (BigUint::one() << (8 * (256 - 3 - 19 - 32))) - 1u32;
+        let mut em = BigUint::one();
+        em = (em << (8 * (256 - 3 - 19 - 32))) + padding;
+        em = (em << (8 * (1 + 19))) + digest_info;
+        (em << (8 * 32)) + hash
+    }
+
+    #[test]
+    fn rsa_signature() {
+        let (n, s, hash) = vector();
+        let em = encoding(&hash);
+        assert_eq!(s.modpow(&BigUint::from(65537u32), &n), em);
+
+        let params = Rc::new(NonNativeFieldParams::new(n, LIMB_WIDTH));
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let s_var = NonNativeFieldVar::alloc(cs.ns(|| "s"), &params, Some(&s)).unwrap();
+        let before = cs.num_constraints();
+        let result = pow_65537(cs.ns(|| "s^e"), s_var).unwrap();
+        assert_eq!(cs.num_constraints() - before, 109871);
+
+        assert_eq!(result.get_value().unwrap(), em);
+        result
+            .enforce_equal(
+                cs.ns(|| "s^e = em"),
+                &NonNativeFieldVar::constant(&params, &em),
+            )
+            .unwrap();
+        assert!(cs.is_satisfied());
+
+        // the encoding of another hash
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let s_var = NonNativeFieldVar::alloc(cs.ns(|| "s"), &params, Some(&s)).unwrap();
+        pow_65537(cs.ns(|| "s^e"), s_var)
+            .unwrap()
+            .enforce_equal(
+                cs.ns(|| "s^e = em"),
+                &NonNativeFieldVar::constant(&params, &encoding(&(hash + 1u32))),
+            )
+            .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn variable_exponent() {
+        let (n, s, _) = vector();
+        let params = Rc::new(NonNativeFieldParams::new(n.clone(), LIMB_WIDTH));
+
+        for e in &[0u64, 1, 2, 0b1011_0110, 65537] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let s_var = NonNativeFieldVar::alloc(cs.ns(|| "s"), &params, Some(&s)).unwrap();
+            let bits = (0..17)
+                .map(|i| {
+                    AllocatedBit::alloc(cs.ns(|| format!("e {}", i)), Some((e >> i) & 1 == 1))
+                        .map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let before = cs.num_constraints();
+            let result = pow(cs.ns(|| "s^e"), s_var, bits).unwrap();
+            if *e == 65537 {
+                assert_eq!(cs.num_constraints() - before, 207360);
+            }
+
+            assert_eq!(
+                result.get_value().unwrap(),
+                s.modpow(&BigUint::from(*e), &n)
+            );
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn long_exponent() {
+        // a 300-bit exponent modulo 2^255 - 19
+        let n = (BigUint::one() << 255u32) - 19u32;
+        let params = Rc::new(NonNativeFieldParams::new(n.clone(), LIMB_WIDTH));
+        let base = hex("1d2f6a0c9e4b8375a6c1f0e2d4b69788a5c3e1f00f1e2d3c4b5a69788796a5b4");
+        let e = (BigUint::one() << 299u32)
+            + hex("9b3c2f1e0d4a5b6c7d8e9fa0b1c2d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f506");
+
+        for honest in &[true, false] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let base_var =
+                NonNativeFieldVar::alloc(cs.ns(|| "base"), &params, Some(&base)).unwrap();
+            let bits = (0..300)
+                .map(|i| {
+                    AllocatedBit::alloc(cs.ns(|| format!("e {}", i)), Some(e.bit(i)))
+                        .map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let result = pow(cs.ns(|| "base^e"), base_var, bits).unwrap();
+
+            let expected = base.modpow(&e, &n);
+            assert_eq!(result.get_value().unwrap(), expected);
+            let claimed = if *honest { expected } else { expected + 1u32 };
+            result
+                .enforce_equal(
+                    cs.ns(|| "base^e = claimed"),
+                    &NonNativeFieldVar::constant(&params, &claimed),
+                )
+                .unwrap();
+            assert_eq!(cs.is_satisfied(), *honest);
+        }
+    }
+}
@@ -3,6 +3,11 @@ use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable
 
 use crate::String;
 
+/// Packs the equalities of `enforce_equal` into as few constraints as the
+/// capacity of the field allows, the last one enforced on drop. The gadgets
+/// of a constraint system rooted at a `MultiEq` share its batching, so one
+/// `MultiEq` threaded through them, e.g. all the blocks of `sha256`, leaves
+/// a single partly filled constraint.
 pub struct MultiEq<F: PrimeField, CS: ConstraintSystem<F>> {
     cs: CS,
     ops: usize,
@@ -120,4 +125,12 @@ impl<F: PrimeField, CS: ConstraintSystem<F>> ConstraintSystem<F> for MultiEq<F,
     fn num_constraints(&self) -> usize {
         self.cs.num_constraints()
     }
+
+    fn num_public_inputs(&self) -> usize {
+        self.cs.num_public_inputs()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.cs.num_private_variables()
+    }
 }
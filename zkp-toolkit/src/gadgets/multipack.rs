@@ -0,0 +1,199 @@
+//! Packing of bits into public inputs: `F::CAPACITY` bits per element
+//! instead of a public input per bit, e.g. 2 inputs for a 256-bit digest.
+//!
+//! The bits of a chunk pack as a little-endian number, the prover packs
+//! them with `pack_into_inputs` and the verifier with `compute_multipacking`.
+//! `pack_bits` packs them the same way into auxiliary variables.
+//!
+//! A chunk of at most `F::CAPACITY` bits packs into a number below `2^CAPACITY`,
+//! and so below the modulus: no two chunks pack into the same element, with no
+//! strict decomposition of the kind of `AllocatedFr::to_bits_le_strict`.
+
+use math::{FpParameters, PrimeField};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::Boolean;
+use super::fr::AllocatedFr;
+
+/// The number of bits packed into an input.
+fn capacity<F: PrimeField>() -> usize {
+    <F as PrimeField>::Params::CAPACITY as usize
+}
+
+/// Inputizes `bits` packed into `F::CAPACITY` bits per input, with a
+/// constraint per input.
+pub fn pack_into_inputs<F, CS>(
+    cs: CS,
+    bits: &[Boolean],
+) -> Result<Vec<AllocatedFr<F>>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    pack(cs, bits, true)
+}
+
+/// Packs `bits` into `F::CAPACITY` bits per element as `pack_into_inputs`,
+/// the elements are auxiliary variables.
+pub fn pack_bits<F, CS>(cs: CS, bits: &[Boolean]) -> Result<Vec<AllocatedFr<F>>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    pack(cs, bits, false)
+}
+
+fn pack<F, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    input: bool,
+) -> Result<Vec<AllocatedFr<F>>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let mut inputs = Vec::with_capacity((bits.len() + capacity::<F>() - 1) / capacity::<F>());
+    for (i, chunk) in bits.chunks(capacity::<F>()).enumerate() {
+        let mut cs = cs.ns(|| format!("chunk {}", i));
+
+        let mut num = Some(F::zero());
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in chunk {
+            num = match (num, bit.get_value()) {
+                (Some(num), Some(true)) => Some(num + &coeff),
+                (Some(num), Some(false)) => Some(num),
+                _ => None,
+            };
+            lc += &bit.lc(CS::one(), coeff);
+            coeff.double_in_place();
+        }
+
+        let value = || num.ok_or(SynthesisError::AssignmentMissing);
+        let input = if input {
+            AllocatedFr::alloc_input(cs.ns(|| "input"), value)?
+        } else {
+            AllocatedFr::alloc(cs.ns(|| "input"), value)?
+        };
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + input.get_variable(),
+        );
+
+        inputs.push(input);
+    }
+
+    Ok(inputs)
+}
+
+/// The public inputs of `bits`, as `pack_into_inputs` allocates them.
+pub fn compute_multipacking<F: PrimeField>(bits: &[bool]) -> Vec<F> {
+    bits.chunks(capacity::<F>())
+        .map(|chunk| {
+            chunk.iter().rev().fold(F::zero(), |num, bit| {
+                let num = num.double();
+                if *bit {
+                    num + &F::one()
+                } else {
+                    num
+                }
+            })
+        })
+        .collect()
+}
+
+/// The little-endian bits of every byte of `bytes`.
+pub fn bytes_to_bits_le(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|b| (0..8).map(move |i| (b >> i) & 1 == 1))
+        .collect()
+}
+
+/// The big-endian bits of every byte of `bytes`.
+pub fn bytes_to_bits_be(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::boolean::AllocatedBit;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_multipacking() {
+        let mut rng = test_rng();
+        assert_eq!(capacity::<Fr>(), 253);
+
+        for &len in [0, 1, 8, 253, 254, 256, 600].iter() {
+            let bits: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+            let expected = compute_multipacking::<Fr>(&bits);
+            assert_eq!(expected.len(), (len + 252) / 253);
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let allocated = bits
+                .iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), Some(*b)).map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            let inputs = pack_into_inputs(cs.ns(|| "pack"), &allocated).unwrap();
+            assert!(cs.is_satisfied());
+            assert!(cs.verify(&expected));
+            assert_eq!(cs.num_constraints(), len + expected.len());
+            assert_eq!(
+                inputs.iter().map(|i| i.get_value()).collect::<Vec<_>>(),
+                expected.iter().cloned().map(Some).collect::<Vec<_>>()
+            );
+
+            // tampering any single bit changes some packed input.
+            for i in 0..len {
+                let mut tampered = bits.clone();
+                tampered[i] = !tampered[i];
+                assert_ne!(compute_multipacking::<Fr>(&tampered), expected);
+
+                cs.set(&format!("bit {}/boolean", i), Fr::from(!bits[i] as u32));
+                assert!(!cs.is_satisfied());
+                cs.set(&format!("bit {}/boolean", i), Fr::from(bits[i] as u32));
+            }
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn test_bytes_to_bits() {
+        assert_eq!(
+            bytes_to_bits_le(&[0b0000_0110, 0x80]),
+            vec![
+                false, true, true, false, false, false, false, false, //
+                false, false, false, false, false, false, false, true
+            ]
+        );
+        assert_eq!(
+            bytes_to_bits_be(&[0b0000_0110]),
+            vec![false, false, false, false, false, true, true, false]
+        );
+
+        // a digest packs into a chunk of 253 bits and one of 3 bits.
+        let digest = [0xffu8; 32];
+        let packed = compute_multipacking::<Fr>(&bytes_to_bits_le(&digest));
+        assert_eq!(packed.len(), 2);
+        assert_eq!(packed[1], Fr::from(7u32));
+    }
+}
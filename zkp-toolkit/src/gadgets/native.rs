@@ -0,0 +1,105 @@
+//! The native counterparts of the hash gadgets, for the verifiers and the
+//! tools outside the circuits: the hashes of field elements and the roots
+//! of the merkle trees, with the parameters of `AbstractHashMimc`,
+//! `AbstractHashPoseidon` and `AbstractHashRescue`.
+
+use math::PrimeField;
+
+use super::merkletree::cbmt::CBMT;
+use super::mimc::{self, MergeMimc, MimcParameters};
+use super::poseidon::{self, MergePoseidon, PoseidonParameters};
+use super::rescue::{self, MergeRescue, RescueParameters};
+
+/// The MiMC hash of the elements, the output of `AbstractHashMimc`.
+pub fn mimc_hash<F: PrimeField>(elements: &[F]) -> F {
+    mimc::hash_elements(elements, &MimcParameters::shared())
+}
+
+/// The Poseidon hash of the elements, the output of `AbstractHashPoseidon`.
+pub fn poseidon_hash<F: PrimeField>(elements: &[F]) -> F {
+    poseidon::hash_elements(elements, &PoseidonParameters::shared())
+}
+
+/// The Rescue hash of the elements, the output of `AbstractHashRescue`.
+pub fn rescue_hash<F: PrimeField>(elements: &[F]) -> F {
+    rescue::hash_elements(elements, &RescueParameters::shared())
+}
+
+/// The root of the binary merkle tree of the leaves merged with MiMC.
+pub fn mimc_merkle_root<F: PrimeField>(leaves: &[F]) -> F {
+    CBMT::<F, MergeMimc<F>>::build_merkle_root(leaves)
+}
+
+/// The root of the binary merkle tree of the leaves merged with Poseidon.
+pub fn poseidon_merkle_root<F: PrimeField>(leaves: &[F]) -> F {
+    CBMT::<F, MergePoseidon<F>>::build_merkle_root(leaves)
+}
+
+/// The root of the binary merkle tree of the leaves merged with Rescue.
+pub fn rescue_merkle_root<F: PrimeField>(leaves: &[F]) -> F {
+    CBMT::<F, MergeRescue<F>>::build_merkle_root(leaves)
+}
+
+#[cfg(test)]
+mod test {
+    use math::test_rng;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::abstract_hash::{AbstractHash, AbstractHashFrOutput};
+    use super::super::mimc::AbstractHashMimc;
+    use super::super::poseidon::AbstractHashPoseidon;
+    use super::super::rescue::AbstractHashRescue;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn gadget_and_native<F, H>(native: fn(&[F]) -> F, merkle_root: fn(&[F]) -> F)
+    where
+        F: PrimeField,
+        H: AbstractHash<F, Output = AbstractHashFrOutput<F>>,
+    {
+        let rng = &mut test_rng();
+
+        for n in 1..4 {
+            let elements: Vec<F> = (0..n).map(|_| F::rand(rng)).collect();
+
+            let mut cs = TestConstraintSystem::<F>::new();
+            let outputs = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| {
+                    AbstractHashFrOutput::alloc(cs.ns(|| format!("element {}", i)), Some(*e))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+            let params = outputs.iter().collect::<Vec<_>>();
+            let image = H::hash_enforce(cs.ns(|| "hash"), &params).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(image.get_value(), Some(native(&elements)));
+        }
+
+        let leaves: Vec<F> = (0..4).map(|_| F::rand(rng)).collect();
+        let left = native(&leaves[..2]);
+        let right = native(&leaves[2..]);
+        assert_eq!(merkle_root(&leaves), native(&[left, right]));
+    }
+
+    #[test]
+    fn test_native_hashes_bn_256() {
+        use curve::bn_256::Fr;
+
+        gadget_and_native::<Fr, AbstractHashMimc<Fr>>(mimc_hash, mimc_merkle_root);
+        gadget_and_native::<Fr, AbstractHashPoseidon<Fr>>(poseidon_hash, poseidon_merkle_root);
+        gadget_and_native::<Fr, AbstractHashRescue<Fr>>(rescue_hash, rescue_merkle_root);
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_native_hashes_bls12_381() {
+        use curve::bls12_381::Fr;
+
+        gadget_and_native::<Fr, AbstractHashMimc<Fr>>(mimc_hash, mimc_merkle_root);
+        gadget_and_native::<Fr, AbstractHashPoseidon<Fr>>(poseidon_hash, poseidon_merkle_root);
+        gadget_and_native::<Fr, AbstractHashRescue<Fr>>(rescue_hash, rescue_merkle_root);
+    }
+}
@@ -0,0 +1,1179 @@
+//! Arithmetic modulo an integer `n` other than the modulus of the
+//! constraint field, e.g. the base and scalar fields of secp256k1 or an RSA
+//! modulus, over bn_256 or bls12_381 Fr.
+//!
+//! An element is the integer of its signed limbs of `limb_width` bits,
+//! `sum limbs[i] * 2^(limb_width * i)`, each limb an affine combination of
+//! variables. Additions, subtractions and products by small constants only
+//! combine the limbs, they are free but grow the bound on the limbs. A
+//! product `a * b` is witnessed as `q * n + r`, the limbs of `q` and `r`
+//! decomposed into bits, and checked as an equation of integers:
+//!
+//! - the limbs of `a * b` are witnessed, and enforced to be the coefficients
+//!   of the product of the polynomials of the limbs at as many points as
+//!   there are coefficients;
+//! - the limbs of `a * b - q * n - r` are summed in groups, as many limbs as
+//!   the constraint field holds without wrapping around;
+//! - the groups are enforced to carry into zero, of carries decomposed into
+//!   bits.
+//!
+//! The bounds are tracked at synthesis time, and an operand whose limbs grew
+//! too large for a product is reduced first. A reduced element is below
+//! `2^bits(n)`, not necessarily below `n`: `enforce_canonical` pins the
+//! canonical representative where it matters.
+
+use core::cmp;
+
+use math::{FpParameters, One, PrimeField, Zero};
+use num_bigint::{BigInt, BigUint, Sign};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::{Rc, Vec};
+
+use super::boolean::{AllocatedBit, Boolean};
+
+/// The bits a product of limbs leaves free in the constraint field, for the
+/// additions after it, the quotient and remainder subtracted from it and the
+/// carries.
+const PRODUCT_HEADROOM: usize = 16;
+
+/// The bits the limbs leave free in the constraint field for the carries.
+const CARRY_HEADROOM: usize = 4;
+
+/// The modulus of the arithmetic, and the width of the limbs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonNativeFieldParams {
+    modulus: BigUint,
+    limb_width: usize,
+}
+
+impl NonNativeFieldParams {
+    pub fn new(modulus: BigUint, limb_width: usize) -> Self {
+        assert!(modulus > BigUint::one(), "the modulus is at least 2");
+        assert!(limb_width > 0);
+
+        NonNativeFieldParams {
+            modulus,
+            limb_width,
+        }
+    }
+
+    /// The parameters of the arithmetic of the prime field `G`.
+    pub fn of_field<G: PrimeField>(limb_width: usize) -> Self {
+        Self::new(field_to_biguint(&-G::one()) + 1u32, limb_width)
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    pub fn limb_width(&self) -> usize {
+        self.limb_width
+    }
+
+    /// The number of limbs of a reduced element.
+    pub fn num_limbs(&self) -> usize {
+        (self.bits() + self.limb_width - 1) / self.limb_width
+    }
+
+    fn bits(&self) -> usize {
+        self.modulus.bits() as usize
+    }
+}
+
+/// The integer of the canonical representative of `x`.
+pub fn field_to_biguint<G: PrimeField>(x: &G) -> BigUint {
+    let mut bytes = Vec::new();
+    for digit in x.into_repr().as_ref() {
+        bytes.extend_from_slice(&digit.to_le_bytes());
+    }
+    BigUint::from_bytes_le(&bytes)
+}
+
+/// The element of `G` of the integer `x`, modulo the modulus of `G`.
+pub fn biguint_to_field<G: PrimeField>(x: &BigUint) -> G {
+    let two_to_64 = G::from(1u128 << 64);
+    x.to_u64_digits()
+        .iter()
+        .rev()
+        .fold(G::zero(), |acc, digit| acc * &two_to_64 + &G::from(*digit))
+}
+
+fn bigint_to_field<G: PrimeField>(x: &BigInt) -> G {
+    let magnitude = biguint_to_field::<G>(x.magnitude());
+    if x.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// The inverse of `x` modulo `n`, by the extended Euclidean algorithm.
+fn mod_inverse(x: &BigUint, n: &BigUint) -> Option<BigUint> {
+    let (mut r0, mut r1) = (BigInt::from(n.clone()), BigInt::from(x % n));
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+    while !r1.is_zero() {
+        let q = &r0 / &r1;
+        let r2 = &r0 - &q * &r1;
+        r0 = r1;
+        r1 = r2;
+        let t2 = &t0 - &q * &t1;
+        t0 = t1;
+        t1 = t2;
+    }
+    if !r0.is_one() {
+        return None;
+    }
+
+    let n = BigInt::from(n.clone());
+    (((t0 % &n) + &n) % &n).to_biguint()
+}
+
+/// The widths of the limbs of an integer of `bits` bits, all `limb_width`
+/// but the top one.
+fn limb_widths(bits: usize, limb_width: usize) -> Vec<usize> {
+    let num_limbs = (bits + limb_width - 1) / limb_width;
+    (0..num_limbs)
+        .map(|i| cmp::min(limb_width, bits - i * limb_width))
+        .collect()
+}
+
+/// The `num_limbs` limbs of `x`, the top one holding all the bits above.
+fn split(x: &BigUint, limb_width: usize, num_limbs: usize) -> Vec<BigUint> {
+    let mask = (BigUint::one() << limb_width) - 1u32;
+    (0..num_limbs)
+        .map(|i| {
+            let shifted = x >> (limb_width * i);
+            if i + 1 == num_limbs {
+                shifted
+            } else {
+                shifted & &mask
+            }
+        })
+        .collect()
+}
+
+/// A limb, `lc + constant`.
+#[derive(Clone)]
+struct Limb<F: PrimeField> {
+    lc: LinearCombination<F>,
+    constant: F,
+}
+
+impl<F: PrimeField> Limb<F> {
+    fn constant(constant: F) -> Self {
+        Limb {
+            lc: LinearCombination::zero(),
+            constant,
+        }
+    }
+
+    fn variable(variable: Variable) -> Self {
+        Limb {
+            lc: LinearCombination::zero() + variable,
+            constant: F::zero(),
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.lc.as_ref().is_empty()
+    }
+
+    fn lc<CS: ConstraintSystem<F>>(&self) -> LinearCombination<F> {
+        self.lc.clone() + (self.constant, CS::one())
+    }
+
+    /// `self + c * other`
+    fn add_scaled(&self, c: F, other: &Self) -> Self {
+        Limb {
+            lc: &self.lc + (c, &other.lc),
+            constant: self.constant + &(c * &other.constant),
+        }
+    }
+}
+
+/// An element modulo the modulus of `params`.
+#[derive(Clone)]
+pub struct NonNativeFieldVar<F: PrimeField> {
+    params: Rc<NonNativeFieldParams>,
+    limbs: Vec<Limb<F>>,
+    values: Option<Vec<BigInt>>,
+    /// A bound on the absolute values of the limbs.
+    max_word: BigUint,
+}
+
+impl<F: PrimeField> NonNativeFieldVar<F> {
+    /// The constant `value`, reduced modulo `n`.
+    pub fn constant(params: &Rc<NonNativeFieldParams>, value: &BigUint) -> Self {
+        Self::constant_limbs(params, &(value % params.modulus()), params.num_limbs())
+    }
+
+    /// The constant `value` as it is, in `num_limbs` limbs.
+    fn constant_limbs(
+        params: &Rc<NonNativeFieldParams>,
+        value: &BigUint,
+        num_limbs: usize,
+    ) -> Self {
+        let words = split(value, params.limb_width, num_limbs);
+
+        NonNativeFieldVar {
+            params: params.clone(),
+            limbs: words
+                .iter()
+                .map(|w| Limb::constant(biguint_to_field(w)))
+                .collect(),
+            max_word: words.iter().max().cloned().unwrap_or_default(),
+            values: Some(words.into_iter().map(BigInt::from).collect()),
+        }
+    }
+
+    /// The integer zero, of no limbs.
+    fn zero(params: &Rc<NonNativeFieldParams>) -> Self {
+        Self::constant_limbs(params, &BigUint::zero(), 0)
+    }
+
+    /// Allocates `value` reduced modulo `n`, of limbs enforced to be below
+    /// `2^bits(n)` together.
+    pub fn alloc<CS>(
+        cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        value: Option<&BigUint>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = value.map(|v| v % params.modulus());
+        let widths = limb_widths(params.bits(), params.limb_width);
+        Self::alloc_limbs(cs, params, value.as_ref(), &widths, false)
+    }
+
+    /// Allocates `value` reduced modulo `n`, of limbs which are public
+    /// inputs, enforced to be below `2^bits(n)` together.
+    pub fn alloc_input<CS>(
+        cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        value: Option<&BigUint>,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = value.map(|v| v % params.modulus());
+        let widths = limb_widths(params.bits(), params.limb_width);
+        Self::alloc_limbs(cs, params, value.as_ref(), &widths, true)
+    }
+
+    /// Allocates the limbs of `value`, each decomposed into its `widths`
+    /// bits.
+    fn alloc_limbs<CS>(
+        mut cs: CS,
+        params: &Rc<NonNativeFieldParams>,
+        value: Option<&BigUint>,
+        widths: &[usize],
+        input: bool,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut limbs = Vec::with_capacity(widths.len());
+        let mut values = Vec::with_capacity(widths.len());
+        let mut shift = 0;
+        for (i, width) in widths.iter().enumerate() {
+            let mut cs = cs.ns(|| format!("limb {}", i));
+
+            let mask = (BigUint::one() << *width) - 1u32;
+            let word = value.map(|v| (v >> shift) & &mask);
+            let word_value = || {
+                word.as_ref()
+                    .map(biguint_to_field)
+                    .ok_or(SynthesisError::AssignmentMissing)
+            };
+            let variable = if input {
+                cs.alloc_input(|| "limb", word_value)?
+            } else {
+                cs.alloc(|| "limb", word_value)?
+            };
+
+            let mut packed = LinearCombination::zero();
+            let mut coeff = F::one();
+            for j in 0..*width {
+                let bit = AllocatedBit::alloc(
+                    cs.ns(|| format!("bit {}", j)),
+                    word.as_ref().map(|w| w.bit(j as u64)),
+                )?;
+                packed = packed + (coeff, bit.get_variable());
+                coeff.double_in_place();
+            }
+            cs.enforce(
+                || "packing",
+                |_| packed,
+                |lc| lc + CS::one(),
+                |lc| lc + variable,
+            );
+
+            limbs.push(Limb::variable(variable));
+            values.push(word.map(BigInt::from));
+            shift += width;
+        }
+
+        Ok(NonNativeFieldVar {
+            params: params.clone(),
+            limbs,
+            values: values.into_iter().collect(),
+            max_word: (BigUint::one() << params.limb_width) - 1u32,
+        })
+    }
+
+    /// The element of the little-endian `bits`, `limb_width` bits a limb.
+    /// It costs nothing, the bits are the limbs.
+    pub fn from_bits_le<CS>(params: &Rc<NonNativeFieldParams>, bits: &[Boolean]) -> Self
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let mut limbs = Vec::new();
+        let mut values = Vec::new();
+        for chunk in bits.chunks(params.limb_width) {
+            let mut lc = LinearCombination::zero();
+            let mut value = Some(BigUint::zero());
+            let mut coeff = F::one();
+            for (j, bit) in chunk.iter().enumerate() {
+                lc = lc + &bit.lc(CS::one(), coeff);
+                coeff.double_in_place();
+                value = match (value, bit.get_value()) {
+                    (Some(v), Some(true)) => Some(v + (BigUint::one() << j)),
+                    (Some(v), Some(false)) => Some(v),
+                    _ => None,
+                };
+            }
+            limbs.push(Limb {
+                lc,
+                constant: F::zero(),
+            });
+            values.push(value.map(BigInt::from));
+        }
+
+        NonNativeFieldVar {
+            params: params.clone(),
+            limbs,
+            values: values.into_iter().collect(),
+            max_word: (BigUint::one() << params.limb_width) - 1u32,
+        }
+    }
+
+    pub fn params(&self) -> &Rc<NonNativeFieldParams> {
+        &self.params
+    }
+
+    /// The value, reduced modulo `n`.
+    pub fn get_value(&self) -> Option<BigUint> {
+        let n = BigInt::from(self.params.modulus.clone());
+        self.value_int()
+            .and_then(|v| (((v % &n) + &n) % &n).to_biguint())
+    }
+
+    /// The integer of the limbs, not reduced.
+    fn value_int(&self) -> Option<BigInt> {
+        self.values.as_ref().map(|values| {
+            values
+                .iter()
+                .enumerate()
+                .fold(BigInt::zero(), |acc, (i, v)| {
+                    acc + (v << (self.params.limb_width * i))
+                })
+        })
+    }
+
+    /// A bound on the integer of the limbs.
+    fn max_value(&self) -> BigUint {
+        (0..self.limbs.len()).fold(BigUint::zero(), |acc, i| {
+            acc + (&self.max_word << (self.params.limb_width * i))
+        })
+    }
+
+    /// Whether the element is a constant, then its products cost nothing.
+    pub fn is_constant(&self) -> bool {
+        self.limbs.iter().all(Limb::is_constant)
+    }
+
+    /// Whether the limbs are the ones of a reduced element.
+    fn is_reduced(&self) -> bool {
+        self.limbs.len() <= self.params.num_limbs()
+            && self.max_word.bits() as usize <= self.params.limb_width
+    }
+
+    /// The same integer, as an element modulo the modulus of `params`, of
+    /// the same limb width. It is the same element only for a canonical
+    /// element below both moduli.
+    pub fn with_modulus(&self, params: &Rc<NonNativeFieldParams>) -> Self {
+        assert_eq!(self.params.limb_width, params.limb_width);
+
+        NonNativeFieldVar {
+            params: params.clone(),
+            limbs: self.limbs.clone(),
+            values: self.values.clone(),
+            max_word: self.max_word.clone(),
+        }
+    }
+
+    fn check_params(&self, other: &Self) {
+        assert!(
+            Rc::ptr_eq(&self.params, &other.params) || self.params == other.params,
+            "the elements are of different moduli"
+        );
+    }
+
+    /// `self + c * other` limb by limb, of `c` -1 or 1.
+    fn add_signed(&self, negate: bool, other: &Self) -> Self {
+        self.check_params(other);
+
+        let (c, c_int) = if negate {
+            (-F::one(), BigInt::from(-1))
+        } else {
+            (F::one(), BigInt::one())
+        };
+        let len = cmp::max(self.limbs.len(), other.limbs.len());
+        let zero = Limb::constant(F::zero());
+        let limbs = (0..len)
+            .map(|i| {
+                self.limbs
+                    .get(i)
+                    .unwrap_or(&zero)
+                    .add_scaled(c, other.limbs.get(i).unwrap_or(&zero))
+            })
+            .collect();
+        let values = match (&self.values, &other.values) {
+            (Some(a), Some(b)) => Some(
+                (0..len)
+                    .map(|i| {
+                        a.get(i).cloned().unwrap_or_default()
+                            + &c_int * b.get(i).cloned().unwrap_or_default()
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        NonNativeFieldVar {
+            params: self.params.clone(),
+            limbs,
+            values,
+            max_word: &self.max_word + &other.max_word,
+        }
+        .checked()
+    }
+
+    /// Panics if the limbs may wrap around the constraint field, as the
+    /// combination of the limbs of a product and too many additions do.
+    fn checked(self) -> Self {
+        assert!(
+            self.max_word.bits() as usize + CARRY_HEADROOM <= F::Params::CAPACITY as usize,
+            "the limbs overflow the constraint field, reduce first"
+        );
+        self
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        self.add_signed(false, other)
+    }
+
+    /// `self - other`, as `self + k * n - other` of a multiple `k * n` of
+    /// the modulus above `other`, so that the integer stays non-negative.
+    pub fn sub(&self, other: &Self) -> Self {
+        let n = self.params.modulus();
+        let multiple = (other.max_value() / n + 1u32) * n;
+        let len = cmp::max(self.limbs.len(), other.limbs.len());
+
+        self.add(&Self::constant_limbs(&self.params, &multiple, len))
+            .add_signed(true, other)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::zero(&self.params).sub(self)
+    }
+
+    /// `self * c` of a small constant `c`.
+    pub fn scale(&self, c: u64) -> Self {
+        let c_field = F::from(c);
+
+        NonNativeFieldVar {
+            params: self.params.clone(),
+            limbs: self
+                .limbs
+                .iter()
+                .map(|l| Limb::constant(F::zero()).add_scaled(c_field, l))
+                .collect(),
+            values: self
+                .values
+                .as_ref()
+                .map(|values| values.iter().map(|v| v * c).collect()),
+            max_word: &self.max_word * c,
+        }
+        .checked()
+    }
+
+    /// Whether the coefficients of the product of `a` and `b` leave room in
+    /// the constraint field for its reduction.
+    fn product_fits(a: &Self, b: &Self) -> bool {
+        let num_terms = cmp::min(a.limbs.len(), b.limbs.len());
+        let bound = BigUint::from(num_terms) * &a.max_word * &b.max_word;
+        bound.bits() as usize + PRODUCT_HEADROOM <= F::Params::CAPACITY as usize
+    }
+
+    /// The product of the integers, not reduced, of a limb a coefficient of
+    /// the product of the polynomials of the limbs. It costs a constraint a
+    /// coefficient, none if either is constant.
+    fn product<CS>(mut cs: CS, a: &Self, b: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let len = a.limbs.len() + b.limbs.len() - 1;
+        let values = match (&a.values, &b.values) {
+            (Some(x), Some(y)) => {
+                let mut values = vec![BigInt::zero(); len];
+                for (i, x) in x.iter().enumerate() {
+                    for (j, y) in y.iter().enumerate() {
+                        values[i + j] += x * y;
+                    }
+                }
+                Some(values)
+            }
+            _ => None,
+        };
+        let max_word =
+            BigUint::from(cmp::min(a.limbs.len(), b.limbs.len())) * &a.max_word * &b.max_word;
+
+        if a.is_constant() || b.is_constant() {
+            let (c, v) = if a.is_constant() { (a, b) } else { (b, a) };
+            let mut limbs = vec![Limb::constant(F::zero()); len];
+            for (i, c) in c.limbs.iter().enumerate() {
+                for (j, v) in v.limbs.iter().enumerate() {
+                    limbs[i + j] = limbs[i + j].add_scaled(c.constant, v);
+                }
+            }
+
+            return Ok(NonNativeFieldVar {
+                params: a.params.clone(),
+                limbs,
+                values,
+                max_word,
+            });
+        }
+
+        let mut limbs = Vec::with_capacity(len);
+        for j in 0..len {
+            let variable = cs.alloc(
+                || format!("coefficient {}", j),
+                || {
+                    values
+                        .as_ref()
+                        .map(|v| bigint_to_field(&v[j]))
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?;
+            limbs.push(Limb::variable(variable));
+        }
+
+        // The polynomials of degree len - 1 agree at len points.
+        let evaluate = |limbs: &[Limb<F>], x: F| {
+            let mut lc = LinearCombination::zero();
+            let mut power = F::one();
+            for limb in limbs {
+                lc = lc + (power, &limb.lc::<CS>());
+                power *= &x;
+                if power.is_zero() {
+                    break;
+                }
+            }
+            lc
+        };
+        for i in 0..len {
+            let x = F::from(i as u64);
+            cs.enforce(
+                || format!("evaluation at {}", i),
+                |_| evaluate(&a.limbs, x),
+                |_| evaluate(&b.limbs, x),
+                |_| evaluate(&limbs, x),
+            );
+        }
+
+        Ok(NonNativeFieldVar {
+            params: a.params.clone(),
+            limbs,
+            values,
+            max_word,
+        })
+    }
+
+    /// `self * other`, not reduced: the limbs are the witnessed coefficients
+    /// of the product, to be reduced or compared. An operand too large for
+    /// the product is reduced first.
+    pub fn mul_without_reduce<CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        self.check_params(other);
+
+        let (mut a, mut b) = (self.clone(), other.clone());
+        if !Self::product_fits(&a, &b) {
+            if a.max_word >= b.max_word {
+                a = a.reduce(cs.ns(|| "reduce left"))?;
+            } else {
+                b = b.reduce(cs.ns(|| "reduce right"))?;
+            }
+        }
+        if !Self::product_fits(&a, &b) {
+            if !a.is_reduced() {
+                a = a.reduce(cs.ns(|| "reduce left"))?;
+            } else if !b.is_reduced() {
+                b = b.reduce(cs.ns(|| "reduce right"))?;
+            }
+        }
+        assert!(
+            Self::product_fits(&a, &b),
+            "the limbs are too wide for the constraint field"
+        );
+
+        Self::product(cs.ns(|| "product"), &a, &b)
+    }
+
+    /// `self * other`, reduced.
+    pub fn mul<CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        self.mul_without_reduce(cs.ns(|| "mul"), other)?
+            .reduce(cs.ns(|| "reduce"))
+    }
+
+    pub fn square<CS>(&self, cs: CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        self.mul(cs, self)
+    }
+
+    /// The reduced element `r` of the integer `q * n + r` of the limbs.
+    pub fn reduce<CS>(&self, mut cs: CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let n = self.params.modulus();
+        let value = self
+            .value_int()
+            .map(|v| v.to_biguint().expect("the integer is not negative"));
+        let remainder = value.as_ref().map(|v| v % n);
+        let quotient = value.as_ref().map(|v| v / n);
+
+        let widths = limb_widths(self.params.bits(), self.params.limb_width);
+        let remainder = Self::alloc_limbs(
+            cs.ns(|| "remainder"),
+            &self.params,
+            remainder.as_ref(),
+            &widths,
+            false,
+        )?;
+        self.enforce_quotient(cs.ns(|| "quotient"), quotient.as_ref(), &remainder)?;
+
+        Ok(remainder)
+    }
+
+    /// Enforces `self = q * n + remainder` as integers, of the witnessed
+    /// `quotient` decomposed into the bits of the largest one the limbs
+    /// allow.
+    fn enforce_quotient<CS>(
+        &self,
+        mut cs: CS,
+        quotient: Option<&BigUint>,
+        remainder: &Self,
+    ) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let n = self.params.modulus();
+        let widths = limb_widths(
+            (self.max_value() / n).bits() as usize,
+            self.params.limb_width,
+        );
+        let q = Self::alloc_limbs(cs.ns(|| "q"), &self.params, quotient, &widths, false)?;
+        let n = Self::constant_limbs(&self.params, n, self.params.num_limbs());
+        let qn = Self::product(cs.ns(|| "q * n"), &q, &n)?;
+
+        self.add_signed(true, &qn)
+            .add_signed(true, remainder)
+            .enforce_zero(cs.ns(|| "carry"))
+    }
+
+    /// Enforces the integer of the limbs to be zero: the limbs are summed in
+    /// groups of `g` limbs, as many as the constraint field holds without
+    /// wrapping around, and every group plus the carry of the one below is
+    /// enforced to be a multiple of `2^(limb_width * g)`, of a carry
+    /// decomposed into bits, the top group to carry into zero.
+    fn enforce_zero<CS>(&self, mut cs: CS) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let limb_width = self.params.limb_width;
+        let group_bound = |g: usize| {
+            (0..g).fold(BigUint::zero(), |acc, s| {
+                acc + (&self.max_word << (limb_width * s))
+            })
+        };
+        // the sums, the carries and their multiples stay below 13 times the
+        // group bound, below the modulus.
+        let fits = |g: usize| {
+            group_bound(g).bits() as usize + CARRY_HEADROOM <= F::Params::CAPACITY as usize
+        };
+        assert!(fits(1), "the limbs are too large to carry");
+        let mut g = 1;
+        while g < self.limbs.len() && fits(g + 1) {
+            g += 1;
+        }
+
+        let radix = BigUint::one() << (limb_width * g);
+        let carry_bound = group_bound(g) / (&radix - 1u32);
+        let carry_bits = (&carry_bound * 2u32).bits() as usize;
+        let radix_field: F = biguint_to_field(&radix);
+        let carry_bound_field: F = biguint_to_field(&carry_bound);
+        let (radix, carry_bound) = (BigInt::from(radix), BigInt::from(carry_bound));
+
+        let one = CS::one();
+        let num_groups = (self.limbs.len() + g - 1) / g;
+        let mut carry = (LinearCombination::zero(), Some(BigInt::zero()));
+        for t in 0..num_groups {
+            let mut cs = cs.ns(|| format!("group {}", t));
+
+            let mut lc = carry.0.clone();
+            let mut value = carry.1.clone();
+            let mut coeff = F::one();
+            for s in 0..g {
+                let i = t * g + s;
+                if i >= self.limbs.len() {
+                    break;
+                }
+                lc = lc + (coeff, &self.limbs[i].lc::<CS>());
+                coeff *= &biguint_to_field::<F>(&(BigUint::one() << limb_width));
+                value = match (value, &self.values) {
+                    (Some(v), Some(values)) => Some(v + (&values[i] << (limb_width * s))),
+                    _ => None,
+                };
+            }
+
+            if t + 1 == num_groups {
+                cs.enforce(|| "zero", |_| lc, |lc| lc + one, |lc| lc);
+                break;
+            }
+
+            // the carry plus its bound, in carry_bits bits
+            let carry_value = value.map(|v| v / &radix);
+            let shifted = carry_value
+                .as_ref()
+                .map(|c| (c + &carry_bound).to_biguint().unwrap_or_default());
+            let mut carry_lc = LinearCombination::zero() - (carry_bound_field, one);
+            let mut coeff = F::one();
+            for j in 0..carry_bits {
+                let bit = AllocatedBit::alloc(
+                    cs.ns(|| format!("carry bit {}", j)),
+                    shifted.as_ref().map(|c| c.bit(j as u64)),
+                )?;
+                carry_lc = carry_lc + (coeff, bit.get_variable());
+                coeff.double_in_place();
+            }
+            cs.enforce(
+                || "carry",
+                |_| lc,
+                |lc| lc + one,
+                |_| carry_lc.clone() * radix_field,
+            );
+
+            carry = (carry_lc, carry_value);
+        }
+
+        Ok(())
+    }
+
+    /// Enforces `self = other` modulo `n`: `self - other` is a multiple of
+    /// `n`, of a witnessed quotient.
+    pub fn enforce_equal<CS>(&self, cs: CS, other: &Self) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let diff = self.sub(other);
+        let quotient = diff
+            .value_int()
+            .map(|v| v.to_biguint().expect("the integer is not negative") / self.params.modulus());
+
+        diff.enforce_quotient(cs, quotient.as_ref(), &Self::zero(&self.params))
+    }
+
+    /// Enforces the integer of the limbs to be below `n`, the canonical
+    /// representative: `n - 1 - self` is witnessed as a reduced element.
+    pub fn enforce_canonical<CS>(&self, mut cs: CS) -> Result<(), SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let n_minus_one = self.params.modulus() - 1u32;
+        let gap = self.value_int().map(|v| {
+            (BigInt::from(n_minus_one.clone()) - v)
+                .to_biguint()
+                .unwrap_or_default()
+        });
+        let widths = limb_widths(self.params.bits(), self.params.limb_width);
+        let gap = Self::alloc_limbs(cs.ns(|| "gap"), &self.params, gap.as_ref(), &widths, false)?;
+
+        self.add(&gap)
+            .add_signed(
+                true,
+                &Self::constant_limbs(&self.params, &n_minus_one, self.params.num_limbs()),
+            )
+            .enforce_zero(cs.ns(|| "sum"))
+    }
+
+    /// `self^-1`, enforced by `self * self^-1 = 1`, unsatisfiable if `self`
+    /// has no inverse modulo `n`.
+    pub fn inverse<CS>(&self, mut cs: CS) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = match self.get_value() {
+            Some(v) => {
+                Some(mod_inverse(&v, self.params.modulus()).ok_or(SynthesisError::DivisionByZero)?)
+            }
+            None => None,
+        };
+        let inverse = Self::alloc(cs.ns(|| "inverse"), &self.params, value.as_ref())?;
+
+        self.mul_without_reduce(cs.ns(|| "product"), &inverse)?
+            .enforce_equal(
+                cs.ns(|| "product is one"),
+                &Self::constant(&self.params, &BigUint::one()),
+            )?;
+
+        Ok(inverse)
+    }
+
+    /// The canonical little-endian bits, `bits(n)` of them, enforced to
+    /// pack into an element below `n` equal to `self`.
+    pub fn to_bits_le<CS>(&self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        let value = self.get_value();
+        let bits = (0..self.params.bits())
+            .map(|i| {
+                AllocatedBit::alloc(
+                    cs.ns(|| format!("bit {}", i)),
+                    value.as_ref().map(|v| v.bit(i as u64)),
+                )
+                .map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let packed = Self::from_bits_le::<CS>(&self.params, &bits);
+        packed.enforce_canonical(cs.ns(|| "canonical"))?;
+        packed.enforce_equal(cs.ns(|| "packing"), self)?;
+
+        Ok(bits)
+    }
+
+    /// Returns `a` if `cond` is true, and `b` otherwise, in a constraint a
+    /// limb, none for the limbs constant in both.
+    pub fn conditionally_select<CS>(
+        mut cs: CS,
+        cond: &Boolean,
+        a: &Self,
+        b: &Self,
+    ) -> Result<Self, SynthesisError>
+    where
+        CS: ConstraintSystem<F>,
+    {
+        a.check_params(b);
+        if let Boolean::Constant(c) = *cond {
+            return Ok(if c { a.clone() } else { b.clone() });
+        }
+
+        let len = cmp::max(a.limbs.len(), b.limbs.len());
+        let values = match (cond.get_value(), &a.values, &b.values) {
+            (Some(c), Some(x), Some(y)) => {
+                let chosen = if c { x } else { y };
+                Some(
+                    (0..len)
+                        .map(|i| chosen.get(i).cloned().unwrap_or_default())
+                        .collect::<Vec<_>>(),
+                )
+            }
+            _ => None,
+        };
+
+        let one = CS::one();
+        let zero = Limb::constant(F::zero());
+        let mut limbs = Vec::with_capacity(len);
+        for i in 0..len {
+            let x = a.limbs.get(i).unwrap_or(&zero);
+            let y = b.limbs.get(i).unwrap_or(&zero);
+            if x.is_constant() && y.is_constant() {
+                // y + cond * (x - y)
+                limbs.push(Limb {
+                    lc: cond.lc(one, x.constant - &y.constant),
+                    constant: y.constant,
+                });
+                continue;
+            }
+
+            let variable = cs.alloc(
+                || format!("limb {}", i),
+                || {
+                    values
+                        .as_ref()
+                        .map(|v| bigint_to_field(&v[i]))
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?;
+            cs.enforce(
+                || format!("select {}", i),
+                |_| x.lc::<CS>() - &y.lc::<CS>(),
+                |_| cond.lc(one, F::one()),
+                |lc| lc + variable - &y.lc::<CS>(),
+            );
+            limbs.push(Limb::variable(variable));
+        }
+
+        Ok(NonNativeFieldVar {
+            params: a.params.clone(),
+            limbs,
+            values,
+            max_word: cmp::max(a.max_word.clone(), b.max_word.clone()),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod test {
+    use curve::bn_256::Fr;
+    use curve::secp256k1::Fq;
+    use math::test_rng;
+    use rand::prelude::*;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    fn random(rng: &mut impl Rng, n: &BigUint) -> BigUint {
+        let mut bytes = vec![0u8; n.bits() as usize / 8 + 8];
+        rng.fill_bytes(&mut bytes);
+        BigUint::from_bytes_le(&bytes) % n
+    }
+
+    fn alloc(
+        cs: &mut TestConstraintSystem<Fr>,
+        name: &str,
+        params: &Rc<NonNativeFieldParams>,
+        value: &BigUint,
+    ) -> NonNativeFieldVar<Fr> {
+        NonNativeFieldVar::alloc(cs.ns(|| name), params, Some(value)).unwrap()
+    }
+
+    /// Runs the same operations on the gadget and on integers.
+    fn check_operations(params: NonNativeFieldParams) {
+        let rng = &mut test_rng();
+        let params = Rc::new(params);
+        let n = params.modulus().clone();
+
+        for _ in 0..5 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let (a, b, c) = (random(rng, &n), random(rng, &n), random(rng, &n));
+            let a_var = alloc(&mut cs, "a", &params, &a);
+            let b_var = alloc(&mut cs, "b", &params, &b);
+            let c_var = NonNativeFieldVar::constant(&params, &c);
+
+            let product = a_var.mul(cs.ns(|| "a * b"), &b_var).unwrap();
+            assert_eq!(product.get_value().unwrap(), &a * &b % &n);
+
+            // (a + c) * (b - a) - 3c, of unreduced operands
+            let left = a_var.add(&c_var);
+            let right = b_var.sub(&a_var);
+            let result = left
+                .mul_without_reduce(cs.ns(|| "(a + c) * (b - a)"), &right)
+                .unwrap()
+                .sub(&c_var.scale(3));
+            let expected = ((&a + &c) * (&b + &n - &a) + &n * 3u32 - &c * 3u32) % &n;
+            assert_eq!(result.get_value().unwrap(), expected);
+
+            // growing limbs are reduced before the products
+            let mut x = a_var.clone();
+            let mut expected_x = a.clone();
+            for i in 0..6 {
+                let y = x.add(&b_var).scale(3).neg();
+                x = y
+                    .mul_without_reduce(cs.ns(|| format!("step {}", i)), &y)
+                    .unwrap();
+                expected_x = ((&expected_x + &b) * 3u32).pow(2) % &n;
+            }
+            assert_eq!(x.get_value().unwrap(), expected_x);
+
+            let expected_var = NonNativeFieldVar::constant(&params, &expected_x);
+            x.enforce_equal(cs.ns(|| "x = expected"), &expected_var)
+                .unwrap();
+            result
+                .enforce_equal(
+                    cs.ns(|| "result = expected"),
+                    &NonNativeFieldVar::constant(&params, &expected),
+                )
+                .unwrap();
+
+            let a_inverse = a_var.inverse(cs.ns(|| "a^-1")).unwrap();
+            assert_eq!((a_inverse.get_value().unwrap() * &a) % &n, BigUint::one());
+
+            assert!(cs.is_satisfied());
+        }
+    }
+
+    #[test]
+    fn operations_secp256k1() {
+        for limb_width in &[16, 32, 64] {
+            check_operations(NonNativeFieldParams::of_field::<Fq>(*limb_width));
+        }
+    }
+
+    #[test]
+    fn operations_2048_bits() {
+        let rng = &mut test_rng();
+        let mut bytes = [0u8; 256];
+        rng.fill_bytes(&mut bytes);
+        bytes[255] |= 0x80;
+        bytes[0] |= 1;
+        let n = BigUint::from_bytes_le(&bytes);
+
+        check_operations(NonNativeFieldParams::new(n, 64));
+    }
+
+    #[test]
+    fn mul_constraints() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let one = BigUint::one();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a = alloc(&mut cs, "a", &params, &one);
+        let b = alloc(&mut cs, "b", &params, &one);
+        let before = cs.num_constraints();
+        a.mul(cs.ns(|| "a * b"), &b).unwrap();
+        assert!(cs.is_satisfied());
+
+        let constraints = cs.num_constraints() - before;
+        assert_eq!(constraints, 624);
+    }
+
+    #[test]
+    fn not_equal() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let n = params.modulus().clone();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a = alloc(&mut cs, "a", &params, &BigUint::from(3u32));
+        let b = alloc(&mut cs, "b", &params, &(&n - 3u32));
+        a.add(&b)
+            .enforce_equal(
+                cs.ns(|| "a + b = 0"),
+                &NonNativeFieldVar::constant(&params, &BigUint::zero()),
+            )
+            .unwrap();
+        assert!(cs.is_satisfied());
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a = alloc(&mut cs, "a", &params, &BigUint::from(3u32));
+        let b = alloc(&mut cs, "b", &params, &(&n - 2u32));
+        a.add(&b)
+            .enforce_equal(
+                cs.ns(|| "a + b = 0"),
+                &NonNativeFieldVar::constant(&params, &BigUint::zero()),
+            )
+            .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn tampered_remainder() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let a = alloc(&mut cs, "a", &params, &BigUint::from(5u32));
+        let b = alloc(&mut cs, "b", &params, &BigUint::from(7u32));
+        a.mul(cs.ns(|| "a * b"), &b).unwrap();
+        assert!(cs.is_satisfied());
+
+        // a remainder of 36, of consistent bits, does not carry into zero
+        cs.set("a * b/reduce/remainder/limb 0/limb", Fr::from(36u64));
+        cs.set("a * b/reduce/remainder/limb 0/bit 0/boolean", Fr::zero());
+        cs.set("a * b/reduce/remainder/limb 0/bit 1/boolean", Fr::zero());
+        cs.set("a * b/reduce/remainder/limb 0/bit 2/boolean", Fr::one());
+        assert_eq!(
+            cs.which_is_unsatisfied(),
+            Some("a * b/reduce/quotient/carry/group 0/carry".into())
+        );
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn inverse_of_zero() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let zero = alloc(&mut cs, "zero", &params, params.modulus());
+        assert!(matches!(
+            zero.inverse(cs.ns(|| "inverse")),
+            Err(SynthesisError::DivisionByZero)
+        ));
+    }
+
+    #[test]
+    fn canonical_bits() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let n = params.modulus().clone();
+        let rng = &mut test_rng();
+
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let value = random(rng, &n);
+        let a = alloc(&mut cs, "a", &params, &value);
+        let bits = a.to_bits_le(cs.ns(|| "bits")).unwrap();
+        assert_eq!(bits.len(), 256);
+        for (i, bit) in bits.iter().enumerate() {
+            assert_eq!(bit.get_value(), Some(value.bit(i as u64)));
+        }
+        assert!(cs.is_satisfied());
+
+        // n itself is not canonical
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let bits = (0..256)
+            .map(|i| Boolean::constant(n.bit(i)))
+            .collect::<Vec<_>>();
+        NonNativeFieldVar::from_bits_le::<TestConstraintSystem<Fr>>(&params, &bits)
+            .enforce_canonical(cs.ns(|| "canonical"))
+            .unwrap();
+        assert!(!cs.is_satisfied());
+    }
+
+    #[test]
+    fn select() {
+        let params = Rc::new(NonNativeFieldParams::of_field::<Fq>(32));
+        let rng = &mut test_rng();
+        let (x, y) = (random(rng, params.modulus()), random(rng, params.modulus()));
+
+        for c in &[false, true] {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let cond = Boolean::from(AllocatedBit::alloc(cs.ns(|| "cond"), Some(*c)).unwrap());
+            let a = alloc(&mut cs, "a", &params, &x);
+            let b = NonNativeFieldVar::constant(&params, &y);
+            let selected =
+                NonNativeFieldVar::conditionally_select(cs.ns(|| "select"), &cond, &a, &b).unwrap();
+            assert_eq!(
+                selected.get_value().unwrap(),
+                if *c { x.clone() } else { y.clone() }
+            );
+
+            let constant = NonNativeFieldVar::conditionally_select(
+                cs.ns(|| "select constants"),
+                &cond,
+                &NonNativeFieldVar::constant(&params, &x),
+                &b,
+            )
+            .unwrap();
+            constant
+                .enforce_equal(cs.ns(|| "equal"), &selected)
+                .unwrap();
+            assert!(cs.is_satisfied());
+        }
+    }
+}
@@ -0,0 +1,711 @@
+//! Circuits for the Pedersen hash over a twisted Edwards curve embedded in
+//! the circuit field, i.e. whose base field is the field of the constraint
+//! system (baby_jubjub for bn_256, jubjub for bls12_381).
+//!
+//! The input bits are split into segments, and each segment into chunks of
+//! 3 bits. A chunk `m_j` of the segment `s` adds `m_j * 8^j * G_s` to the
+//! result, where the generators `G_s` are independently derived by hashing
+//! to the curve, so nobody knows a discrete log relation between them. The
+//! segments are short enough that the scalars of a segment never wrap the
+//! order of the prime subgroup. The hash is the x-coordinate of the result.
+//!
+//! The input is padded with zeros to a multiple of 3 bits, so the inputs of
+//! a given use should have a fixed length.
+//!
+//! The gadget may look up wider windows of `w` bits, a chunk then adds
+//! `m_j * 2^(w * j) * G_s`. The scalar of a segment stays the number of its
+//! bits, so the hash does not depend on the window, only its cost does.
+
+use blake2::{Blake2s, Digest};
+use math::{
+    curves::{models::TEModelParameters, twisted_edwards_extended::GroupAffine, AffineCurve},
+    Field, FpParameters, Group, One, PrimeField, Zero,
+};
+use scheme::r1cs::{ConstraintSystem, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::abstract_hash::{fr_native_bytes, fr_to_bytes, AbstractHash, AbstractHashOutput};
+use super::boolean::Boolean;
+use super::fr::AllocatedFr;
+use super::lookup::lookup_table_xy;
+use super::uint8::UInt8;
+
+/// The coordinates of an allocated point.
+pub type AllocatedPoint<F> = (AllocatedFr<F>, AllocatedFr<F>);
+
+/// The personalization of the hash to the curve deriving the generators.
+pub const PERSONALIZATION: &[u8] = b"zkp-toolkit_PH";
+
+/// The personalization of the hash to the curve deriving the generators of
+/// the commitments.
+pub const COMMITMENT_PERSONALIZATION: &[u8] = b"zkp-toolkit_PC";
+
+/// The window of the hash, and of the commitments.
+pub const WINDOW: usize = 3;
+
+/// The number of 3-bit chunks of a segment, so that the scalar of a segment
+/// is below `2^(r_bits - 1)`, which is less than the order `r` of the prime
+/// subgroup.
+pub fn chunks_per_segment<P: TEModelParameters>() -> usize {
+    (<P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize - 1) / 3
+}
+
+/// The number of bits of a segment.
+fn segment_bits<P: TEModelParameters>() -> usize {
+    3 * chunks_per_segment::<P>()
+}
+
+/// The first point of the prime subgroup found by hashing `personalization`,
+/// `index` and a counter.
+pub(super) fn hash_to_curve<P: TEModelParameters>(
+    personalization: &[u8],
+    index: u32,
+) -> GroupAffine<P> {
+    (0u32..)
+        .find_map(|counter| {
+            let digest = Blake2s::new()
+                .chain(personalization)
+                .chain(index.to_le_bytes())
+                .chain(counter.to_le_bytes())
+                .result();
+            GroupAffine::<P>::from_random_bytes(&digest)
+                .map(|p| p.mul_by_cofactor())
+                .filter(|p| !p.is_zero())
+        })
+        .expect("the counter never runs out")
+}
+
+/// The generator of the segment `segment`.
+pub fn generator<P: TEModelParameters>(segment: u32) -> GroupAffine<P> {
+    hash_to_curve(PERSONALIZATION, segment)
+}
+
+/// The generators `G` and `H` of the commitments `v * G + r * H`.
+pub fn commitment_generators<P: TEModelParameters>() -> (GroupAffine<P>, GroupAffine<P>) {
+    (
+        hash_to_curve(COMMITMENT_PERSONALIZATION, 0),
+        hash_to_curve(COMMITMENT_PERSONALIZATION, 1),
+    )
+}
+
+/// The window tables of the first `chunks` chunks of `window` bits of a
+/// scalar multiple of `base`, the table of the chunk `j` is
+/// `[k * 2^(window * j) * base for k in 0..2^window]`.
+pub(super) fn fixed_base_tables<P: TEModelParameters>(
+    mut base: GroupAffine<P>,
+    chunks: usize,
+    window: usize,
+) -> Vec<Vec<GroupAffine<P>>> {
+    let mut tables = Vec::with_capacity(chunks);
+    for _ in 0..chunks {
+        let mut table = Vec::with_capacity(1 << window);
+        let mut point = GroupAffine::<P>::zero();
+        for _ in 0..1 << window {
+            table.push(point);
+            point += &base;
+        }
+        tables.push(table);
+
+        for _ in 0..window {
+            base.double_in_place();
+        }
+    }
+
+    tables
+}
+
+/// The number of chunks of `window` bits of `len` bits, the last one padded.
+pub(super) fn num_chunks(len: usize, window: usize) -> usize {
+    (len + window - 1) / window
+}
+
+/// The sum of the entries of `tables` selected by the chunks of `window`
+/// bits of `bits`.
+pub(super) fn windowed_sum<P: TEModelParameters>(
+    bits: &[bool],
+    tables: &[Vec<GroupAffine<P>>],
+    window: usize,
+) -> GroupAffine<P> {
+    let mut result = GroupAffine::<P>::zero();
+    for (chunk, table) in bits.chunks(window).zip(tables.iter()) {
+        let k = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |k, (i, b)| k | ((*b as usize) << i));
+        result += &table[k];
+    }
+
+    result
+}
+
+/// The windowed sum gadget, every chunk of `window` bits costs a
+/// `2^(window - 1) - window + 2` constraints lookup and a 6 constraints
+/// addition, the first chunk doesn't need the addition. The lookup of a
+/// chunk padded with zeros costs as much as the one of its actual bits.
+pub(super) fn windowed_sum_enforce<P, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    tables: &[Vec<GroupAffine<P>>],
+    window: usize,
+) -> Result<AllocatedPoint<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    assert!(
+        !bits.is_empty(),
+        "the scalar of a windowed sum is not empty"
+    );
+
+    let mut result: Option<AllocatedPoint<P::BaseField>> = None;
+    for (i, (chunk, table)) in bits.chunks(window).zip(tables.iter()).enumerate() {
+        let mut chunk = chunk.to_vec();
+        chunk.resize(window, Boolean::constant(false));
+        let coords = table.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+
+        let point = lookup_table_xy(cs.ns(|| format!("lookup chunk {}", i)), &chunk, &coords)?;
+        result = Some(match result {
+            Some(acc) => add::<P, _>(cs.ns(|| format!("add chunk {}", i)), &acc, &point)?,
+            None => point,
+        });
+    }
+
+    Ok(result.unwrap())
+}
+
+/// Pedersen hash of `bits`, returns the x-coordinate of the result.
+pub fn hash<P: TEModelParameters>(bits: &[bool]) -> P::BaseField
+where
+    P::BaseField: PrimeField,
+{
+    assert!(!bits.is_empty(), "the input of pedersen is not empty");
+
+    let mut result = GroupAffine::<P>::zero();
+    for (s, segment) in bits.chunks(segment_bits::<P>()).enumerate() {
+        let chunks = num_chunks(segment.len(), WINDOW);
+        let tables = fixed_base_tables(generator::<P>(s as u32), chunks, WINDOW);
+        result += &windowed_sum(segment, &tables, WINDOW);
+    }
+
+    result.x
+}
+
+/// Pedersen hash gadget of `bits`, returns the x-coordinate of the result.
+///
+/// It costs 3 constraints per bit.
+pub fn pedersen<P, CS>(
+    cs: CS,
+    bits: &[Boolean],
+) -> Result<AllocatedFr<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    pedersen_with_window::<P, _>(cs, bits, WINDOW)
+}
+
+/// Pedersen hash gadget of `bits` looking up windows of `window` bits, the
+/// result is the one of `pedersen`.
+///
+/// A chunk costs `2^(window - 1) - window + 8` constraints, so 3 constraints
+/// per bit for windows of 3 and 4 bits, and 3.8 for windows of 5 bits.
+pub fn pedersen_with_window<P, CS>(
+    mut cs: CS,
+    bits: &[Boolean],
+    window: usize,
+) -> Result<AllocatedFr<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    assert!(!bits.is_empty(), "the input of pedersen is not empty");
+    assert!(window > 0, "the window of pedersen is not empty");
+
+    let mut result: Option<AllocatedPoint<P::BaseField>> = None;
+    for (s, segment) in bits.chunks(segment_bits::<P>()).enumerate() {
+        let chunks = num_chunks(segment.len(), window);
+        let tables = fixed_base_tables(generator::<P>(s as u32), chunks, window);
+
+        let point =
+            windowed_sum_enforce(cs.ns(|| format!("segment {}", s)), segment, &tables, window)?;
+        result = Some(match result {
+            Some(acc) => add::<P, _>(cs.ns(|| format!("add segment {}", s)), &acc, &point)?,
+            None => point,
+        });
+    }
+
+    Ok(result.unwrap().0)
+}
+
+/// The maximum number of bits of the value of a commitment, the capacity of
+/// the scalar field, so that two values never open the same commitment.
+pub fn max_value_bits<P: TEModelParameters>() -> usize {
+    <P::ScalarField as PrimeField>::Params::CAPACITY as usize
+}
+
+/// Pedersen commitment `v * G + r * H` of the little-endian bits of the
+/// value `v` and of the randomness `r`.
+///
+/// The value has at most `max_value_bits` bits, so it is below the order of
+/// the prime subgroup. The randomness is reduced modulo this order, so it
+/// should have as many bits as the order to hide the value.
+pub fn commitment<P: TEModelParameters>(value: &[bool], randomness: &[bool]) -> GroupAffine<P> {
+    assert!(!value.is_empty() && !randomness.is_empty());
+    assert!(
+        value.len() <= max_value_bits::<P>(),
+        "the value of a commitment is below the order of the subgroup"
+    );
+
+    let (g, h) = commitment_generators::<P>();
+    let value_tables = fixed_base_tables(g, num_chunks(value.len(), WINDOW), WINDOW);
+    let randomness_tables = fixed_base_tables(h, num_chunks(randomness.len(), WINDOW), WINDOW);
+
+    let mut c = windowed_sum(value, &value_tables, WINDOW);
+    c += &windowed_sum(randomness, &randomness_tables, WINDOW);
+    c
+}
+
+/// Pedersen commitment gadget of the little-endian bits of the value and of
+/// the randomness, the coordinates of the commitment are inputized and
+/// returned. A value of more than `max_value_bits` bits is `Unsatisfiable`,
+/// as its commitment would also open to the value reduced modulo the order
+/// of the subgroup.
+///
+/// It costs 3 constraints per bit, and the 2 public inputs.
+pub fn commit<P, CS>(
+    mut cs: CS,
+    value: &[Boolean],
+    randomness: &[Boolean],
+) -> Result<AllocatedPoint<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    assert!(!value.is_empty() && !randomness.is_empty());
+    if value.len() > max_value_bits::<P>() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let (g, h) = commitment_generators::<P>();
+    let value_tables = fixed_base_tables(g, num_chunks(value.len(), WINDOW), WINDOW);
+    let randomness_tables = fixed_base_tables(h, num_chunks(randomness.len(), WINDOW), WINDOW);
+
+    let v = windowed_sum_enforce(cs.ns(|| "value"), value, &value_tables, WINDOW)?;
+    let r = windowed_sum_enforce(
+        cs.ns(|| "randomness"),
+        randomness,
+        &randomness_tables,
+        WINDOW,
+    )?;
+    let (x, y) = add::<P, _>(cs.ns(|| "commitment"), &v, &r)?;
+
+    x.inputize(cs.ns(|| "commitment x"))?;
+    y.inputize(cs.ns(|| "commitment y"))?;
+
+    Ok((x, y))
+}
+
+/// Complete addition of two points of the twisted Edwards curve
+/// `a * x^2 + y^2 = 1 + d * x^2 * y^2`, with 6 constraints:
+///
+/// U = (x1 + y1) * (y2 - a * x2)
+/// A = x1 * y2
+/// B = x2 * y1
+/// C = d * A * B
+/// x3 = (A + B) / (1 + C)
+/// y3 = (U - A + a * B) / (1 - C)
+pub(super) fn add<P, CS>(
+    mut cs: CS,
+    (x1, y1): &AllocatedPoint<P::BaseField>,
+    (x2, y2): &AllocatedPoint<P::BaseField>,
+) -> Result<AllocatedPoint<P::BaseField>, SynthesisError>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+    CS: ConstraintSystem<P::BaseField>,
+{
+    let one = P::BaseField::one();
+    let a = P::COEFF_A;
+    let d = P::COEFF_D;
+    let value =
+        |v: &AllocatedFr<P::BaseField>| v.get_value().ok_or(SynthesisError::AssignmentMissing);
+
+    let u = AllocatedFr::alloc(cs.ns(|| "U"), || {
+        Ok((value(x1)? + value(y1)?) * (value(y2)? - P::mul_by_a(&value(x2)?)))
+    })?;
+    cs.enforce(
+        || "U = (x1 + y1) * (y2 - a * x2)",
+        |lc| lc + x1.get_variable() + y1.get_variable(),
+        |lc| lc + y2.get_variable() + (-a, x2.get_variable()),
+        |lc| lc + u.get_variable(),
+    );
+
+    let aa = AllocatedFr::alloc(cs.ns(|| "A"), || Ok(value(x1)? * value(y2)?))?;
+    cs.enforce(
+        || "A = x1 * y2",
+        |lc| lc + x1.get_variable(),
+        |lc| lc + y2.get_variable(),
+        |lc| lc + aa.get_variable(),
+    );
+
+    let bb = AllocatedFr::alloc(cs.ns(|| "B"), || Ok(value(x2)? * value(y1)?))?;
+    cs.enforce(
+        || "B = x2 * y1",
+        |lc| lc + x2.get_variable(),
+        |lc| lc + y1.get_variable(),
+        |lc| lc + bb.get_variable(),
+    );
+
+    let cc = AllocatedFr::alloc(cs.ns(|| "C"), || Ok(d * value(&aa)? * value(&bb)?))?;
+    cs.enforce(
+        || "C = d * A * B",
+        |lc| lc + (d, aa.get_variable()),
+        |lc| lc + bb.get_variable(),
+        |lc| lc + cc.get_variable(),
+    );
+
+    let x3 = AllocatedFr::alloc(cs.ns(|| "x3"), || {
+        let t = one + value(&cc)?;
+        let inv = t.inverse().ok_or(SynthesisError::DivisionByZero)?;
+        Ok((value(&aa)? + value(&bb)?) * inv)
+    })?;
+    cs.enforce(
+        || "x3 * (1 + C) = A + B",
+        |lc| lc + x3.get_variable(),
+        |lc| lc + CS::one() + cc.get_variable(),
+        |lc| lc + aa.get_variable() + bb.get_variable(),
+    );
+
+    let y3 = AllocatedFr::alloc(cs.ns(|| "y3"), || {
+        let t = one - value(&cc)?;
+        let inv = t.inverse().ok_or(SynthesisError::DivisionByZero)?;
+        Ok((value(&u)? - value(&aa)? + P::mul_by_a(&value(&bb)?)) * inv)
+    })?;
+    cs.enforce(
+        || "y3 * (1 - C) = U - A + a * B",
+        |lc| lc + y3.get_variable(),
+        |lc| lc + CS::one() + (-one, cc.get_variable()),
+        |lc| lc + u.get_variable() + (-one, aa.get_variable()) + (a, bb.get_variable()),
+    );
+
+    Ok((x3, y3))
+}
+
+#[derive(Clone)]
+pub struct AbstractHashPedersenOutput<F: PrimeField> {
+    hash: AllocatedFr<F>,
+}
+
+impl<F: PrimeField> AbstractHashPedersenOutput<F> {
+    pub fn alloc<CS: ConstraintSystem<F>>(cs: CS, f: Option<F>) -> Result<Self, SynthesisError> {
+        let hash = AllocatedFr::alloc(cs, || f.ok_or(SynthesisError::AssignmentMissing))?;
+
+        Ok(Self { hash })
+    }
+
+    pub fn alloc_input<CS: ConstraintSystem<F>>(
+        cs: CS,
+        f: Option<F>,
+    ) -> Result<Self, SynthesisError> {
+        let hash = AllocatedFr::alloc_input(cs, || f.ok_or(SynthesisError::AssignmentMissing))?;
+
+        Ok(Self { hash })
+    }
+
+    pub fn get_value(&self) -> Option<F> {
+        self.hash.get_value()
+    }
+}
+
+impl<F: PrimeField> AbstractHashOutput<F> for AbstractHashPedersenOutput<F> {
+    type Value = F;
+
+    fn get_variables(&self) -> Vec<Variable> {
+        vec![self.hash.get_variable()]
+    }
+
+    fn get_variable_values(&self) -> Vec<Option<F>> {
+        vec![self.hash.get_value()]
+    }
+
+    fn with_variables(&self, variables: &[Variable]) -> Self {
+        let hash = AllocatedFr::from_variable(variables[0], self.hash.get_value());
+
+        Self { hash }
+    }
+
+    fn to_field_elements<CS: ConstraintSystem<F>>(
+        &self,
+        _: CS,
+    ) -> Result<Vec<AllocatedFr<F>>, SynthesisError> {
+        Ok(vec![self.hash.clone()])
+    }
+
+    fn to_bytes<CS: ConstraintSystem<F>>(&self, cs: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        fr_to_bytes(cs, &self.hash)
+    }
+
+    fn native_field_elements(value: &F) -> Vec<F> {
+        vec![*value]
+    }
+
+    fn native_bytes(value: &F) -> Vec<u8> {
+        fr_native_bytes(value)
+    }
+}
+
+// implement AbstractHash, the inputs are hashed as the concatenation of
+// their little-endian bits.
+pub struct AbstractHashPedersen<P>(core::marker::PhantomData<P>);
+
+impl<P> AbstractHash<P::BaseField> for AbstractHashPedersen<P>
+where
+    P: TEModelParameters,
+    P::BaseField: PrimeField,
+{
+    type Output = AbstractHashPedersenOutput<P::BaseField>;
+
+    fn hash_enforce<CS: ConstraintSystem<P::BaseField>>(
+        mut cs: CS,
+        params: &[&Self::Output],
+    ) -> Result<Self::Output, SynthesisError> {
+        let mut bits = vec![];
+        for (i, o) in params.iter().enumerate() {
+            bits.extend(o.hash.to_bits_le_strict(cs.ns(|| format!("input {}", i)))?);
+        }
+
+        let hash = pedersen::<P, _>(cs.ns(|| "pedersen"), &bits)?;
+
+        Ok(AbstractHashPedersenOutput { hash })
+    }
+}
+
+#[cfg(all(test, feature = "baby_jubjub"))]
+mod test {
+    use curve::baby_jubjub::{EdwardsParameters, Fq};
+    use math::{test_rng, BigInteger};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::boolean::AllocatedBit;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_generators() {
+        let chunks = chunks_per_segment::<EdwardsParameters>();
+        assert_eq!(chunks, 83);
+
+        let g0 = generator::<EdwardsParameters>(0);
+        let g1 = generator::<EdwardsParameters>(1);
+        assert!(g0.is_in_prime_subgroup());
+        assert!(g1.is_in_prime_subgroup());
+        assert!(g0 != g1 && g0 != -g1);
+
+        // a single set bit of the second segment is its generator.
+        let mut bits = vec![false; 3 * chunks + 1];
+        bits[3 * chunks] = true;
+        assert_eq!(hash::<EdwardsParameters>(&bits), g1.x);
+    }
+
+    #[test]
+    fn test_pedersen_hash() {
+        let mut rng = test_rng();
+
+        // 2 segments and padded chunks.
+        for &len in [1, 3, 254, 508, 510].iter() {
+            let input: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Fq>::new();
+            let bits = input
+                .iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    AllocatedBit::alloc(cs.ns(|| format!("input bit {}", i)), Some(*b))
+                        .map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let num_constraints = cs.num_constraints();
+
+            let res = pedersen::<EdwardsParameters, _>(cs.ns(|| "pedersen"), &bits).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(res.get_value(), Some(hash::<EdwardsParameters>(&input)));
+
+            // a 3 constraints lookup and a 6 constraints addition per chunk.
+            let chunks = (len + 2) / 3;
+            let padded = if len % 3 == 0 { 0 } else { 1 };
+            assert_eq!(
+                cs.num_constraints() - num_constraints,
+                3 * chunks + 6 * (chunks - 1) - padded
+            );
+            if len == 510 {
+                assert_eq!(cs.num_constraints() - num_constraints, 3 * 510 - 6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pedersen_windows() {
+        let mut rng = test_rng();
+        let segment_bits = segment_bits::<EdwardsParameters>();
+
+        // the cost of the lookup of a chunk of `r` actual bits.
+        let lookup = |r: usize| (1 << (r - 1)) - r + 2;
+
+        for &len in [1, 7, 254, 510].iter() {
+            let input: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+            let expected = hash::<EdwardsParameters>(&input);
+
+            for window in 1..=5 {
+                let mut cs = TestConstraintSystem::<Fq>::new();
+                let bits = input
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        AllocatedBit::alloc(cs.ns(|| format!("input bit {}", i)), Some(*b))
+                            .map(Boolean::from)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                let num_constraints = cs.num_constraints();
+
+                let res = pedersen_with_window::<EdwardsParameters, _>(
+                    cs.ns(|| "pedersen"),
+                    &bits,
+                    window,
+                )
+                .unwrap();
+                assert!(cs.is_satisfied());
+                assert_eq!(res.get_value(), Some(expected));
+
+                // the lookups of the chunks of every segment, and the
+                // additions of all of them.
+                let mut chunks = 0;
+                let mut cost = 0;
+                for segment in input.chunks(segment_bits) {
+                    for chunk in segment.chunks(window) {
+                        chunks += 1;
+                        cost += lookup(chunk.len());
+                    }
+                }
+                cost += 6 * (chunks - 1);
+                assert_eq!(cs.num_constraints() - num_constraints, cost);
+            }
+        }
+    }
+
+    #[test]
+    fn test_abstract_hash_pedersen() {
+        let mut rng = test_rng();
+        let (l, r): (Fq, Fq) = (rng.gen(), rng.gen());
+
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let vl = AbstractHashPedersenOutput::alloc(cs.ns(|| "left"), Some(l)).unwrap();
+        let vr = AbstractHashPedersenOutput::alloc(cs.ns(|| "right"), Some(r)).unwrap();
+        let h =
+            AbstractHashPedersen::<EdwardsParameters>::hash_enforce(cs.ns(|| "hash"), &[&vl, &vr])
+                .unwrap();
+        assert!(cs.is_satisfied());
+
+        let mut input = vec![];
+        for f in [l, r].iter() {
+            let repr = f.into_repr();
+            input.extend((0..Fq::size_in_bits()).map(|i| repr.get_bit(i)));
+        }
+        assert_eq!(h.get_value(), Some(hash::<EdwardsParameters>(&input)));
+    }
+
+    #[test]
+    fn test_pedersen_commitment() {
+        let mut rng = test_rng();
+
+        let (g, h) = commitment_generators::<EdwardsParameters>();
+        assert!(g.is_in_prime_subgroup() && h.is_in_prime_subgroup());
+        assert!(g != h && g != -h);
+
+        let value: Vec<bool> = (0..64).map(|_| rng.gen()).collect();
+        let randomness: Vec<bool> = (0..251).map(|_| rng.gen()).collect();
+
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let mut alloc = |name: &str, bits: &[bool]| {
+            bits.iter()
+                .enumerate()
+                .map(|(i, b)| {
+                    AllocatedBit::alloc(cs.ns(|| format!("{} bit {}", name, i)), Some(*b))
+                        .map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+        let value_bits = alloc("value", &value);
+        let randomness_bits = alloc("randomness", &randomness);
+        let num_constraints = cs.num_constraints();
+
+        let (x, y) =
+            commit::<EdwardsParameters, _>(cs.ns(|| "commit"), &value_bits, &randomness_bits)
+                .unwrap();
+        assert!(cs.is_satisfied());
+
+        let c = commitment::<EdwardsParameters>(&value, &randomness);
+        assert_eq!((x.get_value(), y.get_value()), (Some(c.x), Some(c.y)));
+        assert!(cs.verify(&[c.x, c.y]));
+
+        // the sum of the windowed sums of 22 and 84 chunks, the last of them
+        // padded, and the 2 inputs.
+        assert_eq!(
+            cs.num_constraints() - num_constraints,
+            (3 * 22 + 6 * 21 - 1) + (3 * 84 + 6 * 83 - 1) + 6 + 2
+        );
+
+        // the commitment hides the value with the randomness.
+        let other: Vec<bool> = (0..251).map(|_| rng.gen()).collect();
+        assert_ne!(c, commitment::<EdwardsParameters>(&value, &other));
+    }
+
+    #[test]
+    fn test_pedersen_commitment_value_bound() {
+        let mut rng = test_rng();
+        let bits = max_value_bits::<EdwardsParameters>();
+        assert_eq!(bits, 250);
+
+        let randomness: Vec<bool> = (0..251).map(|_| rng.gen()).collect();
+        let mut cs = TestConstraintSystem::<Fq>::new();
+        let mut alloc = |name: &str, len: usize| {
+            (0..len)
+                .map(|i| {
+                    AllocatedBit::alloc(cs.ns(|| format!("{} bit {}", name, i)), Some(true))
+                        .map(Boolean::from)
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+        let value_bits = alloc("value", bits + 1);
+        let randomness_bits = randomness
+            .iter()
+            .map(|b| Boolean::constant(*b))
+            .collect::<Vec<_>>();
+
+        // the largest value of `max_value_bits` bits commits.
+        let (x, y) = commit::<EdwardsParameters, _>(
+            cs.ns(|| "commit"),
+            &value_bits[..bits],
+            &randomness_bits,
+        )
+        .unwrap();
+        assert!(cs.is_satisfied());
+        let c = commitment::<EdwardsParameters>(&vec![true; bits], &randomness);
+        assert_eq!((x.get_value(), y.get_value()), (Some(c.x), Some(c.y)));
+
+        // one more bit does not.
+        assert!(matches!(
+            commit::<EdwardsParameters, _>(cs.ns(|| "too long"), &value_bits, &randomness_bits),
+            Err(SynthesisError::Unsatisfiable)
+        ));
+    }
+}
@@ -0,0 +1,595 @@
+//! Circuits for the [Poseidon] hash function.
+// The following code refers to Marvellous [https://github.com/KULeuven-COSIC/Marvellous] and Distaff [https://github.com/GuildOfWeavers/distaff]
+// and thanks for their work
+// @Author: JiadongLu (lujd1234@gmail.com)
+// @Author: YunLi (liyunscss@gmail.com)
+
+use core::marker::PhantomData;
+
+use math::{BitIterator, FromBytes, PrimeField};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::abstract_hash::{AbstractHash, AbstractHashFrOutput};
+use super::fr::AllocatedFr;
+use super::merkletree::cbmt::Merge;
+
+pub mod params;
+
+pub use params::PoseidonParameters;
+
+/// This is an implementation of Poseidon
+/// See https://eprint.iacr.org/2019/458 for more
+/// information about this construction.
+///
+/// The permutation of `state`, its length is the width of `params`. The
+/// partial rounds are the ones of `params.optimized`.
+pub fn permute<F: PrimeField>(state: &mut [F], params: &PoseidonParameters<F>) {
+    assert_eq!(state.len(), params.t);
+    let optimized = &params.optimized;
+
+    for i in 0..params.rounds() {
+        if params.is_full_round(i) {
+            for (s, c) in state.iter_mut().zip(optimized.ark[i].iter()) {
+                s.add_assign(c);
+                *s = s.pow([params.alpha]);
+            }
+        } else {
+            state[0].add_assign(&optimized.ark[i][0]);
+            state[0] = state[0].pow([params.alpha]);
+        }
+
+        // MixLayer
+        match mix_layer(params, i) {
+            MixLayer::Dense(mds) => {
+                let mixed = mds
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .zip(state.iter())
+                            .fold(F::zero(), |acc, (m, s)| acc + &(*m * s))
+                    })
+                    .collect::<Vec<_>>();
+                state.copy_from_slice(&mixed);
+            }
+            MixLayer::Sparse(row, column) => {
+                let first = state[0];
+                state[0] = row
+                    .iter()
+                    .zip(state.iter())
+                    .fold(F::zero(), |acc, (m, s)| acc + &(*m * s));
+                for (s, m) in state[1..].iter_mut().zip(column.iter()) {
+                    *s += &(*m * &first);
+                }
+            }
+        }
+    }
+}
+
+/// The matrix of a round of `params.optimized`.
+enum MixLayer<'a, F> {
+    Dense(&'a [Vec<F>]),
+    /// the first row, and the rest of the first column.
+    Sparse(&'a [F], &'a [F]),
+}
+
+fn mix_layer<F: PrimeField>(params: &PoseidonParameters<F>, i: usize) -> MixLayer<'_, F> {
+    let first_partial = params.full_rounds / 2;
+    if i + 1 == first_partial {
+        MixLayer::Dense(&params.optimized.pre_sparse_mds)
+    } else if params.is_full_round(i) {
+        MixLayer::Dense(&params.mds)
+    } else {
+        let (row, column) = &params.optimized.sparse_mds[i - first_partial];
+        MixLayer::Sparse(row, column)
+    }
+}
+
+/// Compress two elements, the state is `[xl, xr, 0, ...]` and the first
+/// element of the permuted state is the result.
+pub fn poseidon_block<F: PrimeField>(xl: F, xr: F, params: &PoseidonParameters<F>) -> F {
+    let mut state = vec![F::zero(); params.t];
+    state[0] = xl;
+    state[1] = xr;
+    permute(&mut state, params);
+    state[0]
+}
+
+/// The hash of `t - 1` elements, the first element of the permutation of
+/// `[0, elements..]`, as the `poseidon` of circomlib with `t - 1` inputs.
+pub fn compress<F: PrimeField>(elements: &[F], params: &PoseidonParameters<F>) -> F {
+    assert_eq!(elements.len() + 1, params.t);
+
+    let mut state = vec![F::zero()];
+    state.extend_from_slice(elements);
+    permute(&mut state, params);
+    state[0]
+}
+
+/// poseidon hash function.
+fn poseidon_hash<F: PrimeField>(b: &[u8], params: &PoseidonParameters<F>) -> (F, F, F) {
+    let mut v: Vec<F> = Vec::new();
+    let n = <F::BigInt as math::BigInteger>::NUM_LIMBS * 8;
+    for i in 0..(b.len() / n) {
+        let repr = F::BigInt::read(&b[i * n..(i + 1) * n]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    if b.len() % n != 0 {
+        let repr = F::BigInt::read(&b[(b.len() / n) * n..]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    let mut h: F = F::zero();
+    let xr = v[v.len() - 1].clone();
+    let mut xl = F::zero();
+
+    for i in 0..v.len() {
+        if i == v.len() - 1 {
+            xl = h.clone();
+        }
+
+        h = poseidon_block(h, v[i], params);
+    }
+
+    (xl, xr, h)
+}
+
+pub fn hash<F: PrimeField>(b: &[u8], params: &PoseidonParameters<F>) -> F {
+    poseidon_hash(b, params).2
+}
+
+/// The chaining of `poseidon_block` over the elements from zero, the hash
+/// of their bytes.
+pub fn hash_elements<F: PrimeField>(elements: &[F], params: &PoseidonParameters<F>) -> F {
+    elements
+        .iter()
+        .fold(F::zero(), |h, e| poseidon_block(h, *e, params))
+}
+
+/// The constraints of the last block of the hash of `b`, the image is the
+/// public output.
+pub fn poseidon<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    b: &[u8],
+    params: &PoseidonParameters<F>,
+) -> Result<F, SynthesisError> {
+    let (xl, xr, image) = poseidon_hash(b, params);
+
+    let var_xl = cs.alloc(|| "preimage xl", || Ok(xl))?;
+    let var_xr = cs.alloc(|| "preimage xr", || Ok(xr))?;
+
+    let mut state_value = vec![Some(F::zero()); params.t];
+    state_value[0] = Some(xl);
+    state_value[1] = Some(xr);
+    let mut state = vec![LinearCombination::zero(); params.t];
+    state[0] = state[0].clone() + var_xl;
+    state[1] = state[1].clone() + var_xr;
+
+    let (state_value, state) = permute_enforce(&mut cs, state_value, state, params)?;
+
+    let output = cs.alloc_input(
+        || "output",
+        || state_value[0].ok_or(SynthesisError::AssignmentMissing),
+    )?;
+
+    cs.enforce(
+        || "output = state[0]",
+        |lc| lc + (F::one(), CS::one()),
+        |lc| lc + &state[0],
+        |lc| lc + output,
+    );
+    Ok(image)
+}
+
+/// The gadget of `hash_elements`, the state goes from a block to the next
+/// as linear combinations, only the image is allocated.
+pub fn hash_elements_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    elements: &[AllocatedFr<F>],
+    params: &PoseidonParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let mut h_value = Some(F::zero());
+    let mut h = LinearCombination::zero();
+
+    for (i, e) in elements.iter().enumerate() {
+        let mut state_value = vec![Some(F::zero()); params.t];
+        state_value[0] = h_value;
+        state_value[1] = e.get_value();
+        let mut state = vec![LinearCombination::zero(); params.t];
+        state[0] = h;
+        state[1] = state[1].clone() + e.get_variable();
+
+        let (state_value, state) =
+            permute_enforce(cs.ns(|| format!("block {}", i)), state_value, state, params)?;
+        h_value = state_value[0];
+        h = state[0].clone();
+    }
+
+    let output = AllocatedFr::alloc(cs.ns(|| "output"), || {
+        h_value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    cs.enforce(
+        || "output = state[0]",
+        |lc| lc + (F::one(), CS::one()),
+        |lc| lc + &h,
+        |lc| lc + output.get_variable(),
+    );
+
+    Ok(output)
+}
+
+/// The gadget of `compress`, the permutation of the `t - 1` elements of a
+/// node of a tree of arity `t - 1`, or of a block of a sponge.
+pub fn compress_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    elements: &[AllocatedFr<F>],
+    params: &PoseidonParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    assert_eq!(elements.len() + 1, params.t);
+
+    let mut state_value = vec![Some(F::zero())];
+    state_value.extend(elements.iter().map(|e| e.get_value()));
+    let mut state = vec![LinearCombination::zero()];
+    state.extend(
+        elements
+            .iter()
+            .map(|e| LinearCombination::zero() + e.get_variable()),
+    );
+
+    let (state_value, state) = permute_enforce(&mut cs, state_value, state, params)?;
+
+    let output = AllocatedFr::alloc(cs.ns(|| "output"), || {
+        state_value[0].ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    cs.enforce(
+        || "output = state[0]",
+        |lc| lc + (F::one(), CS::one()),
+        |lc| lc + &state[0],
+        |lc| lc + output.get_variable(),
+    );
+
+    Ok(output)
+}
+
+/// The values of a state in the circuit, and its linear combinations.
+type State<F> = (Vec<Option<F>>, Vec<LinearCombination<F>>);
+
+/// The constraints of `permute` on the linear combinations of the state,
+/// with their values.
+fn permute_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    mut state_value: Vec<Option<F>>,
+    mut state: Vec<LinearCombination<F>>,
+    params: &PoseidonParameters<F>,
+) -> Result<State<F>, SynthesisError> {
+    let optimized = &params.optimized;
+
+    for i in 0..params.rounds() {
+        let cs = &mut cs.ns(|| format!("round_{}", i));
+
+        let sboxes = if params.is_full_round(i) { params.t } else { 1 };
+        for j in 0..sboxes {
+            let c = optimized.ark[i][j];
+            state_value[j] = state_value[j].map(|v| v + &c);
+            state[j] = state[j].clone() + (c, CS::one());
+
+            let (value, var) = pow_with_constraint(
+                cs.ns(|| format!("sbox_{}", j)),
+                state_value[j],
+                &state[j],
+                [params.alpha],
+            )?;
+            state_value[j] = value;
+            state[j] = LinearCombination::zero() + var;
+        }
+
+        // Mix Layer
+        match mix_layer(params, i) {
+            MixLayer::Dense(mds) => {
+                let mut mixed_value = Vec::with_capacity(params.t);
+                let mut mixed = Vec::with_capacity(params.t);
+                for row in mds.iter() {
+                    let (value, lc) = dot(row, &state_value, &state);
+                    mixed_value.push(value);
+                    mixed.push(lc);
+                }
+                state_value = mixed_value;
+                state = mixed;
+            }
+            MixLayer::Sparse(row, column) => {
+                let (value, lc) = dot(row, &state_value, &state);
+                for (k, m) in column.iter().enumerate() {
+                    state_value[k + 1] =
+                        state_value[k + 1].and_then(|v| state_value[0].map(|s| v + &(*m * &s)));
+                    state[k + 1] = state[k + 1].clone() + (*m, &state[0]);
+                }
+                state_value[0] = value;
+                state[0] = lc;
+            }
+        }
+    }
+
+    Ok((state_value, state))
+}
+
+/// The product of a row of a matrix and the state.
+fn dot<F: PrimeField>(
+    row: &[F],
+    state_value: &[Option<F>],
+    state: &[LinearCombination<F>],
+) -> (Option<F>, LinearCombination<F>) {
+    let mut value = Some(F::zero());
+    let mut lc = LinearCombination::zero();
+    for (k, m) in row.iter().enumerate() {
+        value = value.and_then(|v| state_value[k].map(|s| v + &(*m * &s)));
+        lc = lc + (*m, &state[k]);
+    }
+    (value, lc)
+}
+
+/// `x^exp` by square and multiply, one constraint per step.
+fn pow_with_constraint<F: PrimeField, CS: ConstraintSystem<F>, S: AsRef<[u64]>>(
+    mut cs: CS,
+    value: Option<F>,
+    x: &LinearCombination<F>,
+    exp: S,
+) -> Result<(Option<F>, Variable), SynthesisError> {
+    // the leading one of exp, res = x.
+    let mut res_value = value;
+    let mut res = x.clone();
+    let mut res_var = None;
+
+    for (cnt, bit) in BitIterator::new(exp).skip_while(|b| !b).skip(1).enumerate() {
+        let square_value = res_value.map(|v| v.square());
+        let square = cs.alloc(
+            || format!("square_{}", cnt),
+            || square_value.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+        cs.enforce(
+            || format!("square_{} = res * res", cnt),
+            |lc| lc + &res,
+            |lc| lc + &res,
+            |lc| lc + square,
+        );
+        res_value = square_value;
+        res = LinearCombination::zero() + square;
+        res_var = Some(square);
+
+        if bit {
+            let mul_value = res_value.and_then(|r| value.map(|v| r * &v));
+            let mul = cs.alloc(
+                || format!("mul_{}", cnt),
+                || mul_value.ok_or(SynthesisError::AssignmentMissing),
+            )?;
+            cs.enforce(
+                || format!("mul_{} = res * x", cnt),
+                |lc| lc + &res,
+                |lc| lc + x,
+                |lc| lc + mul,
+            );
+            res_value = mul_value;
+            res = LinearCombination::zero() + mul;
+            res_var = Some(mul);
+        }
+    }
+
+    Ok((res_value, res_var.ok_or(SynthesisError::Unsatisfiable)?))
+}
+
+/// Merges the nodes of a merkle tree into their `hash_elements` with the
+/// default parameters, as `AbstractHashPoseidon` does in the circuit.
+pub struct MergePoseidon<F>(PhantomData<F>);
+
+impl<F: PrimeField> Merge for MergePoseidon<F> {
+    type Item = F;
+
+    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
+        Self::merge_many(&[*left, *right])
+    }
+
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        hash_elements(items, &PoseidonParameters::shared())
+    }
+}
+
+pub type AbstractHashPoseidonOutput<F> = AbstractHashFrOutput<F>;
+
+/// The `hash_elements` of the nodes with the default parameters.
+pub struct AbstractHashPoseidon<F>(PhantomData<F>);
+
+impl<F: PrimeField> AbstractHash<F> for AbstractHashPoseidon<F> {
+    type Output = AbstractHashPoseidonOutput<F>;
+
+    fn hash_enforce<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        params: &[&Self::Output],
+    ) -> Result<Self::Output, SynthesisError> {
+        let elements = params
+            .iter()
+            .map(|o| o.to_allocated_fr())
+            .collect::<Vec<_>>();
+
+        hash_elements_enforce(
+            cs.ns(|| "poseidon_hash"),
+            &elements,
+            &PoseidonParameters::shared(),
+        )
+        .map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::{test_rng, Field, One, ToBytes, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_poseidon_hash() {
+        let rng = &mut test_rng();
+        let params = PoseidonParameters::<Fr>::default();
+
+        for _ in 0..10 {
+            let bytes: Vec<u8> = (0..100).map(|_| rng.next_u32() as u8).collect();
+            let hash1 = hash::<Fr>(&bytes, &params);
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let hash2 = poseidon(cs.ns(|| "poseidon hash"), &bytes, &params).unwrap();
+            assert_eq!(hash1, hash2);
+            assert!(cs.is_satisfied());
+            // 3 constraints an S-box, and the output.
+            assert_eq!(3 * (8 * 3 + 57) + 1, cs.num_constraints());
+        }
+    }
+
+    #[test]
+    fn test_poseidon_permutation() {
+        // the test vector of the reference implementation.
+        let params = PoseidonParameters::<Fr>::default();
+        let mut state = [Fr::zero(), Fr::one(), Fr::from(2u32)];
+        permute(&mut state, &params);
+        assert_eq!(
+            state[0],
+            "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+                .parse::<Fr>()
+                .unwrap()
+        );
+    }
+
+    /// The permutation of the rounds of the paper, without the sparse
+    /// matrices.
+    fn permute_unoptimized(state: &mut [Fr], params: &PoseidonParameters<Fr>) {
+        for i in 0..params.rounds() {
+            for (s, c) in state.iter_mut().zip(params.ark[i].iter()) {
+                *s += c;
+            }
+            let sboxes = if params.is_full_round(i) { params.t } else { 1 };
+            for s in state[..sboxes].iter_mut() {
+                *s = s.pow([params.alpha]);
+            }
+            let mixed = params
+                .mds
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .zip(state.iter())
+                        .fold(Fr::zero(), |acc, (m, s)| acc + &(*m * s))
+                })
+                .collect::<Vec<_>>();
+            state.copy_from_slice(&mixed);
+        }
+    }
+
+    #[test]
+    fn test_optimized_rounds() {
+        let rng = &mut test_rng();
+
+        for t in 2..10 {
+            let params = PoseidonParameters::<Fr>::with_rounds(t, 5, 8, 57);
+            assert_eq!(params.optimized.sparse_mds.len(), 57);
+
+            let mut state: Vec<Fr> = (0..t).map(|_| rng.gen()).collect();
+            let mut expected = state.clone();
+            permute(&mut state, &params);
+            permute_unoptimized(&mut expected, &params);
+            assert_eq!(state, expected);
+        }
+    }
+
+    #[test]
+    fn test_poseidon_widths() {
+        // the circomlib numbers of partial rounds, the `poseidon` of 1 to
+        // `t - 1`, and the constraints: 3 an S-box, and the output.
+        let widths: [(usize, usize, &str, usize); 4] = [
+            (
+                2,
+                56,
+                "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+                217,
+            ),
+            (
+                3,
+                57,
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+                244,
+            ),
+            (
+                5,
+                60,
+                "18821383157269793795438455681495246036402687001665670618754263018637548127333",
+                301,
+            ),
+            (
+                7,
+                63,
+                "20400040500897583745843009878988256314335038853985262692600694741116813247201",
+                358,
+            ),
+        ];
+
+        for (t, partial_rounds, image, num_constraints) in widths.iter() {
+            let params = PoseidonParameters::<Fr>::with_rounds(*t, 5, 8, *partial_rounds);
+            let elements: Vec<Fr> = (1..*t).map(|i| Fr::from(i as u32)).collect();
+            let image = image.parse::<Fr>().unwrap();
+            assert_eq!(compress(&elements, &params), image);
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let vars = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| AllocatedFr::alloc(cs.ns(|| format!("e {}", i)), || Ok(*e)).unwrap())
+                .collect::<Vec<_>>();
+            let r = compress_enforce(cs.ns(|| "compress"), &vars, &params).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(r.get_value(), Some(image));
+            assert_eq!(cs.num_constraints(), *num_constraints);
+            assert_eq!(*num_constraints, 3 * (8 * t + partial_rounds) + 1);
+        }
+    }
+
+    #[test]
+    fn test_poseidon_width_5() {
+        let rng = &mut test_rng();
+        let params = PoseidonParameters::<Fr>::with_rounds(5, 5, 8, 60);
+
+        let bytes: Vec<u8> = (0..64).map(|_| rng.next_u32() as u8).collect();
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let image = poseidon(cs.ns(|| "poseidon hash"), &bytes, &params).unwrap();
+        assert_eq!(image, hash::<Fr>(&bytes, &params));
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.get_input(1, "poseidon hash/output"), image);
+    }
+
+    #[test]
+    fn test_hash_elements() {
+        let rng = &mut test_rng();
+        let params = PoseidonParameters::<Fr>::default();
+
+        for n in 1..4 {
+            let elements: Vec<Fr> = (0..n).map(|_| rng.gen()).collect();
+            let mut bytes = vec![];
+            for e in elements.iter() {
+                e.write(&mut bytes).unwrap();
+            }
+            let image = hash_elements(&elements, &params);
+            assert_eq!(image, hash::<Fr>(&bytes, &params));
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let vars = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| AllocatedFr::alloc(cs.ns(|| format!("e {}", i)), || Ok(*e)).unwrap())
+                .collect::<Vec<_>>();
+            let r = hash_elements_enforce(cs.ns(|| "hash"), &vars, &params).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(r.get_value(), Some(image));
+            // the blocks of `poseidon`, and the output.
+            assert_eq!(cs.num_constraints(), 3 * (8 * 3 + 57) * n + 1);
+        }
+    }
+}
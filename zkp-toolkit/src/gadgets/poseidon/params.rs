@@ -0,0 +1,492 @@
+//! Parameter generation for Poseidon.
+//!
+//! The round constants and the MDS matrix are sampled from the Grain LFSR
+//! as in the reference implementation
+//! [https://extgit.iaik.tugraz.at/krypto/hadeshash], so the parameters of a
+//! given field, width and round numbers are the same as the ones of the
+//! reference Sage scripts.
+use math::{BigInteger, FpParameters, PrimeField};
+
+use crate::{Arc, Vec};
+
+use super::super::cache::cached;
+
+/// The parameters of a Poseidon permutation over `F`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoseidonParameters<F: PrimeField> {
+    /// the width of the state.
+    pub t: usize,
+    /// the exponent of the S-box, `x^alpha`.
+    pub alpha: u64,
+    /// the number of full rounds, half of them at the start and half at the end.
+    pub full_rounds: usize,
+    /// the number of partial rounds, in the middle.
+    pub partial_rounds: usize,
+    /// the round constants, `t` of them for every round.
+    pub ark: Vec<Vec<F>>,
+    /// the `t x t` MDS matrix.
+    pub mds: Vec<Vec<F>>,
+    /// the rounds with the sparse matrices of the partial rounds.
+    pub optimized: OptimizedRounds<F>,
+}
+
+/// The rounds of a permutation rewritten as in the appendix B of the
+/// Poseidon paper, for a partial round to cost `O(t)` instead of `O(t^2)`:
+/// the round constants of the partial rounds are moved to their first
+/// element, and the MDS matrix of a partial round is factored into a sparse
+/// matrix and a dense one, which moves to the round before. The last full
+/// round before the partial rounds takes the dense matrix of the first one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptimizedRounds<F: PrimeField> {
+    /// the round constants, only the first one of a partial round is not zero.
+    pub ark: Vec<Vec<F>>,
+    /// the matrix of the last full round before the partial rounds.
+    pub pre_sparse_mds: Vec<Vec<F>>,
+    /// the sparse matrices of the partial rounds, as their first row and the
+    /// rest of their first column, the rest of a matrix is the identity.
+    pub sparse_mds: Vec<(Vec<F>, Vec<F>)>,
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    /// Generates the parameters of a permutation of width `t` with the S-box
+    /// `x^alpha`, and the round numbers needed for `security_level` bits of
+    /// security, computed from the formulas of the Poseidon paper with its
+    /// security margin (two more full rounds, 7.5% more partial rounds).
+    #[cfg(feature = "std")]
+    pub fn new(t: usize, alpha: u64, security_level: usize) -> Self {
+        let (full_rounds, partial_rounds) = round_numbers::<F>(t, alpha, security_level);
+        Self::with_rounds(t, alpha, full_rounds, partial_rounds)
+    }
+
+    /// Generates the parameters of a permutation of width `t` with the S-box
+    /// `x^alpha` and the given round numbers.
+    pub fn with_rounds(t: usize, alpha: u64, full_rounds: usize, partial_rounds: usize) -> Self {
+        assert!(t >= 2, "the width of poseidon is at least 2");
+        assert!(
+            full_rounds >= 2 && full_rounds % 2 == 0,
+            "the full rounds must be even and at least 2"
+        );
+        assert!(
+            alpha >= 3 && gcd(alpha, modulus_minus_one_rem::<F>(alpha)) == 1,
+            "x^alpha must be a permutation of the field"
+        );
+
+        let mut grain = Grain::new::<F>(t, full_rounds, partial_rounds);
+        let ark: Vec<Vec<F>> = (0..full_rounds + partial_rounds)
+            .map(|_| (0..t).map(|_| grain.next_field_element::<F>()).collect())
+            .collect();
+        let mds = grain.next_mds::<F>(t);
+        let optimized = OptimizedRounds::new(full_rounds, partial_rounds, &ark, &mds);
+
+        Self {
+            t,
+            alpha,
+            full_rounds,
+            partial_rounds,
+            ark,
+            mds,
+            optimized,
+        }
+    }
+
+    /// The number of rounds.
+    pub fn rounds(&self) -> usize {
+        self.full_rounds + self.partial_rounds
+    }
+
+    /// Whether the S-box of round `i` is applied to the full state.
+    pub fn is_full_round(&self, i: usize) -> bool {
+        i < self.full_rounds / 2 || i >= self.full_rounds / 2 + self.partial_rounds
+    }
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    /// The default parameters, generated once per field and then shared.
+    pub fn shared() -> Arc<Self> {
+        cached("poseidon", || Self::with_rounds(3, 5, 8, 57))
+    }
+}
+
+impl<F: PrimeField> Default for PoseidonParameters<F> {
+    /// The width 3, `x^5` instance with 8 full and 57 partial rounds, which is
+    /// the one of circomlib over bn_256.
+    fn default() -> Self {
+        Self::shared().as_ref().clone()
+    }
+}
+
+impl<F: PrimeField> OptimizedRounds<F> {
+    fn new(full_rounds: usize, partial_rounds: usize, ark: &[Vec<F>], mds: &[Vec<F>]) -> Self {
+        let t = mds.len();
+        let first = full_rounds / 2;
+        let end = first + partial_rounds;
+
+        // the constants of the partial rounds but the first ones go through
+        // the S-box unchanged, and are added after the MDS matrix, to the
+        // constants of the next round.
+        let mut ark = ark.to_vec();
+        let mut carry = vec![F::zero(); t];
+        for constants in ark[first..end].iter_mut() {
+            for (c, m) in constants.iter_mut().zip(carry.iter()) {
+                *c += m;
+            }
+            let mut rest = constants.clone();
+            rest[0] = F::zero();
+            carry = mat_vec(mds, &rest);
+            constants[1..].iter_mut().for_each(|c| *c = F::zero());
+        }
+        for (c, m) in ark[end].iter_mut().zip(carry.iter()) {
+            *c += m;
+        }
+
+        // from the last partial round, the matrix `[[m_00, v], [w, M']]` is
+        // `[[m_00, v * M'^-1], [w, I]] * diag(1, M')`, and `diag(1, M')`
+        // commutes with the S-box and the constant of the first element, so
+        // it moves to the matrix of the round before.
+        let mut sparse_mds = Vec::with_capacity(partial_rounds);
+        let mut matrix = mds.to_vec();
+        for _ in 0..partial_rounds {
+            let minor = matrix[1..]
+                .iter()
+                .map(|row| row[1..].to_vec())
+                .collect::<Vec<_>>();
+            let minor_inverse =
+                inverse(&minor).expect("the minors of an MDS matrix are invertible");
+            let column = matrix[1..].iter().map(|row| row[0]).collect::<Vec<_>>();
+            let mut row = vec![matrix[0][0]];
+            row.extend((0..t - 1).map(|j| {
+                matrix[0][1..]
+                    .iter()
+                    .zip(minor_inverse.iter())
+                    .fold(F::zero(), |acc, (v, inv_row)| acc + &(*v * &inv_row[j]))
+            }));
+            sparse_mds.push((row, column));
+
+            let dense = (0..t)
+                .map(|i| {
+                    (0..t)
+                        .map(|j| match (i, j) {
+                            (0, 0) => F::one(),
+                            (0, _) | (_, 0) => F::zero(),
+                            _ => minor[i - 1][j - 1],
+                        })
+                        .collect()
+                })
+                .collect::<Vec<_>>();
+            matrix = mat_mul(&dense, mds);
+        }
+        sparse_mds.reverse();
+
+        Self {
+            ark,
+            pre_sparse_mds: matrix,
+            sparse_mds,
+        }
+    }
+}
+
+fn mat_vec<F: PrimeField>(m: &[Vec<F>], v: &[F]) -> Vec<F> {
+    m.iter()
+        .map(|row| {
+            row.iter()
+                .zip(v)
+                .fold(F::zero(), |acc, (a, b)| acc + &(*a * b))
+        })
+        .collect()
+}
+
+fn mat_mul<F: PrimeField>(a: &[Vec<F>], b: &[Vec<F>]) -> Vec<Vec<F>> {
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|j| {
+                    row.iter()
+                        .zip(b)
+                        .fold(F::zero(), |acc, (x, b_row)| acc + &(*x * &b_row[j]))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The inverse of a square matrix by Gauss-Jordan elimination.
+fn inverse<F: PrimeField>(m: &[Vec<F>]) -> Option<Vec<Vec<F>>> {
+    let n = m.len();
+    let mut a = m.to_vec();
+    let mut inv = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| if i == j { F::one() } else { F::zero() })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| !a[r][col].is_zero())?;
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let factor = a[col][col].inverse()?;
+        a[col].iter_mut().for_each(|x| *x *= &factor);
+        inv[col].iter_mut().for_each(|x| *x *= &factor);
+
+        for r in 0..n {
+            if r != col && !a[r][col].is_zero() {
+                let f = a[r][col];
+                for k in 0..n {
+                    let (x, y) = (a[col][k], inv[col][k]);
+                    a[r][k] -= &(f * &x);
+                    inv[r][k] -= &(f * &y);
+                }
+            }
+        }
+    }
+
+    Some(inv)
+}
+
+/// The Grain LFSR in self-shrinking mode, seeded with the description of the
+/// instance.
+struct Grain {
+    bits: [bool; 80],
+    head: usize,
+}
+
+impl Grain {
+    fn new<F: PrimeField>(t: usize, full_rounds: usize, partial_rounds: usize) -> Self {
+        let mut bits = [true; 80];
+        let mut i = 0;
+        // field: 1 is a prime field, sbox: 0 is x^alpha, the last 30 bits stay 1.
+        for (value, width) in [
+            (1, 2),
+            (0, 4),
+            (F::Params::MODULUS_BITS as usize, 12),
+            (t, 12),
+            (full_rounds, 10),
+            (partial_rounds, 10),
+        ]
+        .iter()
+        {
+            for j in (0..*width).rev() {
+                bits[i] = (value >> j) & 1 == 1;
+                i += 1;
+            }
+        }
+
+        let mut grain = Self { bits, head: 0 };
+        for _ in 0..160 {
+            grain.update();
+        }
+        grain
+    }
+
+    fn update(&mut self) -> bool {
+        let bit = |k: usize| self.bits[(self.head + k) % 80];
+        let new = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.bits[self.head] = new;
+        self.head = (self.head + 1) % 80;
+        new
+    }
+
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.update();
+            let bit = self.update();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    /// The next `MODULUS_BITS` bits, most significant first.
+    fn next_bigint<F: PrimeField>(&mut self) -> F::BigInt {
+        let bits = (0..F::Params::MODULUS_BITS)
+            .map(|_| self.next_bit())
+            .collect::<Vec<_>>();
+        F::BigInt::from_bits(&bits)
+    }
+
+    /// A field element, the integers not below the modulus are skipped.
+    fn next_field_element<F: PrimeField>(&mut self) -> F {
+        loop {
+            let r = self.next_bigint::<F>();
+            if r < F::Params::MODULUS {
+                return F::from_repr(r);
+            }
+        }
+    }
+
+    /// A Cauchy matrix `1 / (x_i + y_j)`, the `x_i` and `y_j` are reduced
+    /// integers and all distinct.
+    ///
+    /// The reference scripts also check that no invariant subspace trail
+    /// exists and sample again otherwise, those checks are not done here.
+    fn next_mds<F: PrimeField>(&mut self, t: usize) -> Vec<Vec<F>> {
+        loop {
+            let xy = (0..2 * t)
+                .map(|_| {
+                    let mut r = self.next_bigint::<F>();
+                    if r >= F::Params::MODULUS {
+                        r.sub_noborrow(&F::Params::MODULUS);
+                    }
+                    F::from_repr(r)
+                })
+                .collect::<Vec<F>>();
+            let distinct = (0..2 * t).all(|i| (0..i).all(|j| xy[i] != xy[j]));
+            if !distinct {
+                continue;
+            }
+
+            let (xs, ys) = xy.split_at(t);
+            let mds = xs
+                .iter()
+                .map(|x| ys.iter().map(|y| (*x + y).inverse()).collect())
+                .collect::<Option<Vec<Vec<F>>>>();
+            if let Some(mds) = mds {
+                return mds;
+            }
+        }
+    }
+}
+
+pub(crate) fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// `(p - 1) mod m`.
+pub(crate) fn modulus_minus_one_rem<F: PrimeField>(m: u64) -> u64 {
+    let rem = F::Params::MODULUS
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0u128, |acc, limb| ((acc << 64) + *limb as u128) % m as u128);
+    ((rem + m as u128 - 1) % m as u128) as u64
+}
+
+/// The numbers of full and partial rounds with the least S-boxes which
+/// resist the known attacks on `security_level` bits, with the security
+/// margin added.
+///
+/// The partial rounds are then rounded up to a multiple of `t`, as for the
+/// published instances of circomlib, e.g. 57, 60 and 63 partial rounds for
+/// `t` of 3, 5 and 9 over bn_256, where the reference script gives 56, 56
+/// and 57.
+#[cfg(feature = "std")]
+pub fn round_numbers<F: PrimeField>(t: usize, alpha: u64, security_level: usize) -> (usize, usize) {
+    let log_p = F::Params::MODULUS
+        .as_ref()
+        .iter()
+        .rev()
+        .fold(0f64, |acc, limb| acc * 2f64.powi(64) + *limb as f64)
+        .log2();
+    let n = F::Params::MODULUS_BITS as f64;
+
+    let mut best = (0, 0);
+    let mut min_cost = usize::max_value();
+    for partial_rounds in 1..500 {
+        // as in the reference script, the margin of a secure instance is kept
+        // while trying the next numbers of full rounds.
+        let mut partial_rounds = partial_rounds;
+        for full_rounds in (4..100).step_by(2) {
+            if is_secure(
+                log_p,
+                n,
+                t as f64,
+                full_rounds as f64,
+                partial_rounds as f64,
+                alpha as f64,
+                security_level as f64,
+            ) {
+                let full_rounds = full_rounds + 2;
+                partial_rounds = (partial_rounds as f64 * 1.075).ceil() as usize;
+                let cost = t * full_rounds + partial_rounds;
+                if cost < min_cost || (cost == min_cost && full_rounds < best.0) {
+                    best = (full_rounds, partial_rounds);
+                    min_cost = cost;
+                }
+            }
+        }
+    }
+    let (full_rounds, partial_rounds) = best;
+    (full_rounds, (partial_rounds + t - 1) / t * t)
+}
+
+/// The statistical, interpolation and Gröbner basis bounds.
+#[cfg(feature = "std")]
+fn is_secure(log_p: f64, n: f64, t: f64, rf: f64, rp: f64, alpha: f64, m: f64) -> bool {
+    let log_alpha_2 = 1f64 / alpha.log2();
+    let rf_1: f64 = if m <= (log_p - (alpha - 1f64) / 2f64).floor() * (t + 1f64) {
+        6f64
+    } else {
+        10f64
+    };
+    let rf_2 = 1f64 + (log_alpha_2 * m.min(n)).ceil() + (t.log2() / alpha.log2()).ceil() - rp;
+    let rf_3 = log_alpha_2 * m.min(log_p) - rp;
+    let rf_4 = t - 1f64 + log_alpha_2 * (m / (t + 1f64)).min(log_p / 2f64) - rp;
+    let rf_5 = (t - 2f64 + m / (2f64 * alpha.log2()) - rp) / (t - 1f64);
+
+    [rf_1, rf_2, rf_3, rf_4, rf_5]
+        .iter()
+        .all(|bound| rf >= bound.ceil())
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+
+    use super::*;
+
+    #[test]
+    fn test_bn_256_reference_parameters() {
+        let params = PoseidonParameters::<Fr>::with_rounds(3, 5, 8, 57);
+        assert_eq!(params, PoseidonParameters::default());
+        assert_eq!(params.ark.len(), 65);
+        // generated once, then shared.
+        assert!(Arc::ptr_eq(
+            &PoseidonParameters::<Fr>::shared(),
+            &PoseidonParameters::<Fr>::shared()
+        ));
+
+        // the output of `generate_parameters_grain.sage 1 0 254 3 8 57 <p>`.
+        let ark: [&str; 3] = [
+            "6745197990210204598374042828761989596302876299545964402857411729872131034734",
+            "426281677759936592021316809065178817848084678679510574715894138690250139748",
+            "4014188762916583598888942667424965430287497824629657219807941460227372577781",
+        ];
+        for (c, s) in params.ark[0].iter().zip(ark.iter()) {
+            assert_eq!(*c, s.parse::<Fr>().unwrap());
+        }
+        assert_eq!(
+            params.mds[0][0],
+            "7511745149465107256748700652201246547602992235352608707588321460060273774987"
+                .parse::<Fr>()
+                .unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_round_numbers() {
+        assert_eq!(round_numbers::<Fr>(2, 5, 128), (8, 56));
+        assert_eq!(round_numbers::<Fr>(3, 5, 128), (8, 57));
+        assert_eq!(round_numbers::<Fr>(5, 5, 128), (8, 60));
+        assert_eq!(round_numbers::<Fr>(9, 5, 128), (8, 63));
+        assert_eq!(
+            PoseidonParameters::<Fr>::new(3, 5, 128),
+            PoseidonParameters::default()
+        );
+
+        let params = PoseidonParameters::<Fr>::new(5, 5, 128);
+        assert_eq!(params.rounds(), 68);
+        assert_eq!(params.mds.len(), 5);
+        assert!(params.is_full_round(3));
+        assert!(!params.is_full_round(4));
+        assert!(!params.is_full_round(63));
+        assert!(params.is_full_round(64));
+    }
+}
@@ -1,54 +1,22 @@
-//! Circuits for the [Rescue] hash function.
-// The following code refers to Marvellous [https://github.com/KULeuven-COSIC/Marvellous] and Distaff [https://github.com/GuildOfWeavers/distaff]
-// and thanks for their work
-// @Author: JiadongLu (lujd1234@gmail.com)
-// @Author: YunLi (liyunscss@gmail.com)
+//! The parameters of Rescue over the scalar field of bn_256, from the
+//! Marvellous reference implementation of the Rescue paper.
+use curve::bn_256::Fr;
 
-use math::{BitIterator, FromBytes, PrimeField};
-use scheme::r1cs::{ConstraintSystem, SynthesisError, Variable};
+use super::params::{str_to_field, RescueParameters};
 
-use crate::Vec;
+const ROUNDS: usize = 22;
 
-use super::abstract_hash::{AbstractHash, AbstractHashOutput};
-
-// Hash Rescue utilizes Sponge Construction
-// r, bitrate; c, capacity; M, state value, equal to r + c;
-const _R: usize = 2;
-const _C: usize = 1;
-const M: usize = _R + _C;
-
-const RESCUE_ROUNDS: usize = 22; // round number
-
-// ALPH * INVALPH == 1 (mod p-1)
+// ALPHA * ALPHA_INV == 1 (mod p-1)
 // p == 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001
-const ALPH: [u64; 1] = [5];
-const INVALPH: [u64; 4] = [
+const ALPHA: u64 = 5;
+const ALPHA_INV: [u64; 4] = [
     0xcfe7f7a98ccccccd,
     0x535cb9d394945a0d,
     0x93736af8679aad17,
     0x26b6a528b427b354,
 ]; // 0x26b6a528b427b35493736af8679aad17535cb9d394945a0dcfe7f7a98ccccccd
 
-/// This is an implementation of Resuce
-/// See https://eprint.iacr.org/2019/426 for more
-/// information about this construction.
-///
-/// ```ignore
-/// $Input:\ Plaintext\ P,\ round\ keys\ K_s\ for \ 0 ≤ s ≤ 2N$
-/// $Output:\ Rescue\ (K, P)$
-/// ​	$S_0 = P + K_0 $
-/// ​	$for \ r = 1\ to\ RESCUE_ROUNDS\ do$ // RESCUE_ROUNDS round
-/// ​		$for\ i = 1\ to\ m\ do$  // for every element
-/// ​			$Interr[i] = K_{2r−1}[i] + ∑{m \atop j=1} M[i, j](S_{r−1}[j])^{1/α}$
-/// ​		$end$
-/// ​	$for\ i = 1\ to\ m\ do $
-/// ​		$S_r[i] = K_{2r}[i] + ∑{m \atop j=1} M[i, j](Inter_r[j])^α $
-/// ​	$end $
-/// ​	$end$
-/// ​$return\ S_N$
-/// ```
-
-const _CONSTANTS_MATRIX: [[&str; 3]; 3] = [
+const MDS: [[&str; 3]; 3] = [
     [
         "1727009077431585087915540656539954534780777332184950406202646746275199217608",
         "13540180854142779318387506093563803091263161877004989521740898372515032806705",
@@ -66,8 +34,8 @@ const _CONSTANTS_MATRIX: [[&str; 3]; 3] = [
     ],
 ];
 
-// (2 * RESCUE_ROUNDS + 1) * M
-const _CONSTANTS_CONSTANT: [[&str; 3]; 45] = [
+// 2 * ROUNDS + 1, the first one is added to the input.
+const CONSTANTS: [[&str; 3]; 45] = [
     [
         "12769180023052250270311692729191801253904905578230519201957672068471212580693",
         "1521613489211025647495719579262333768015696501738145911352210890120622630165",
@@ -295,338 +263,25 @@ const _CONSTANTS_CONSTANT: [[&str; 3]; 45] = [
     ],
 ];
 
-pub struct RescueConstant<F: PrimeField> {
-    pub constants: [[F; M]; 2 * RESCUE_ROUNDS + 1],
-    pub mds: [[F; M]; M], // MDS matrix
-}
-
-fn str2p<F: PrimeField>(s: &str) -> F {
-    let t = F::from_str(&String::from(s));
-    let t = match t {
-        Ok(t) => t,
-        Err(_) => panic!("123"),
-    };
-    t
-}
-
-impl<F: PrimeField> RescueConstant<F> {
-    pub fn new_fp255() -> RescueConstant<F> {
-        let mut constants = [[F::zero(); M]; 2 * RESCUE_ROUNDS + 1];
-        let mut matrix = [[F::zero(); M]; M];
-        for i in 0..2 * RESCUE_ROUNDS + 1 {
-            for j in 0..M {
-                constants[i][j] = str2p(_CONSTANTS_CONSTANT[i][j]);
-            }
-        }
-        for i in 0..M {
-            for j in 0..M {
-                matrix[i][j] = str2p(_CONSTANTS_MATRIX[i][j]);
-            }
-        }
-        RescueConstant {
-            constants: constants,
-            mds: matrix,
-        }
-    }
-}
-
-pub fn constants<F: PrimeField>() -> RescueConstant<F> {
-    RescueConstant::<F>::new_fp255()
-}
-
-pub fn rescue_block<F: PrimeField>(xl: F, xr: F, rc: &RescueConstant<F>) -> F {
-    let mut state = [xl, xr, F::zero()];
-    for i in 0..M {
-        state[i].add_assign(rc.constants[0][i]);
-    }
-
-    let mut af: &[u64];
-    for i in 0..2 * RESCUE_ROUNDS {
-        af = &ALPH;
-        if i % 2 == 1 {
-            af = &INVALPH;
-        }
-        for j in 0..M {
-            state[j] = state[j].pow(af);
-        }
-        // matrix multiplication
-        let mut tmp2 = [F::zero(); M];
-        for j in 0..M {
-            for k in 0..M {
-                let mut t2 = rc.mds[j][k];
-                t2.mul_assign(state[k]);
-                tmp2[j] += t2;
-            }
-        }
-        for j in 0..M {
-            state[j] = tmp2[j] + rc.constants[i + 1][j];
-        }
-    }
-    // c == 1
-    state[0]
-}
-
-/// rescue hash function.
-fn rescue_hash<F: PrimeField>(b: &[u8], constants: &RescueConstant<F>) -> (F, F, F) {
-    let mut v: Vec<F> = Vec::new();
-    let n = <F::BigInt as math::BigInteger>::NUM_LIMBS * 8;
-    for i in 0..(b.len() / n) {
-        let repr = F::BigInt::read(&b[i * n..(i + 1) * n]).unwrap_or(Default::default());
-        v.push(F::from_repr(repr));
-    }
-
-    if b.len() % n != 0 {
-        let repr = F::BigInt::read(&b[(b.len() / n) * n..]).unwrap_or(Default::default());
-        v.push(F::from_repr(repr));
-    }
-
-    let mut h: F = F::zero();
-    let xr = v[v.len() - 1].clone();
-    let mut xl = F::zero();
-
-    for i in 0..v.len() {
-        if i == v.len() - 1 {
-            xl = h.clone();
-        }
-
-        h = rescue_block(h, v[i], constants);
-    }
-
-    (xl, xr, h)
-}
-
-pub fn hash<F: PrimeField>(b: &[u8]) -> F {
-    rescue_hash(b, &constants()).2
-}
-
-pub fn rescue<F: PrimeField, CS: ConstraintSystem<F>>(
-    mut cs: CS,
-    b: &[u8],
-) -> Result<F, SynthesisError> {
-    let constants = constants::<F>();
-    let (xl, xr, image) = rescue_hash(b, &constants);
-
-    let var_xl = cs.alloc(|| "preimage xl", || Ok(xl))?;
-    let var_xr = cs.alloc(|| "preimage xr", || Ok(xr))?;
-
-    let three = F::zero();
-    let var_three = cs.alloc(|| "preimage tmp", || Ok(three))?;
-    let mut state_value = [xl, xr, three];
-    let mut state = [var_xl, var_xr, var_three];
-    let cs = &mut cs.ns(|| "Preassign");
-    for i in 0..M {
-        state_value[i] += constants.constants[0][i];
-        let tmp_value = state_value[i];
-        let tmp = cs.alloc(|| format!("tmp_{}", i), || Ok(tmp_value))?;
-        cs.enforce(
-            || format!("tmp_{} = (state[{}] + Ci) * 1", i, i),
-            |lc| lc + state[i] + (constants.constants[0][i], CS::one()),
-            |lc| lc + (F::one(), CS::one()),
-            |lc| lc + tmp,
-        );
-        state_value[i] = tmp_value;
-        state[i] = tmp;
-    }
-    let mut af: &[u64];
-    for i in 0..2 * RESCUE_ROUNDS {
-        let cs = &mut cs.ns(|| format!("round_{}", i));
-        af = &ALPH;
-        if i % 2 == 1 {
-            af = &INVALPH;
-        }
-        for j in 0..M {
-            let tuple = pow_with_constraint(&state_value[j], &state[j], af, cs, j)?;
-            state_value[j] = tuple.0;
-            state[j] = tuple.1;
-        }
-        let mut tmp2_value = [F::zero(); M];
-        let mut tmp2 = Vec::with_capacity(3);
-        for j in 0..M {
-            tmp2.push(cs.alloc(|| format!("tmp2_{}", j), || Ok(tmp2_value[j]))?);
-        }
-        for j in 0..M {
-            for k in 0..M {
-                let mut tmp3_value = constants.mds[j][k];
-                let tmp3 = cs.alloc(|| format!("tmp3_{}{}", j, k), || Ok(tmp3_value))?;
-                tmp3_value.mul_assign(&state_value[k]);
-                tmp3_value.add_assign(&tmp2_value[j]);
-                let tmp4_value = tmp3_value;
-                let tmp4 = cs.alloc(|| format!("tmp4_{}{}", j, k), || Ok(tmp4_value))?;
-                cs.enforce(
-                    || format!("tmp4 - tmp2[{}] = tmp3_{}{} * state_value[{}]", j, j, k, k),
-                    |lc| lc + tmp3,
-                    |lc| lc + state[k],
-                    |lc| lc + tmp4 - tmp2[j],
-                );
-                tmp2_value[j] = tmp4_value;
-                tmp2[j] = tmp4;
-            }
-        }
-        for j in 0..M {
-            tmp2_value[j].add_assign(constants.constants[i + 1][j]);
-            let tmp5_value = tmp2_value[j];
-            let tmp5 = cs.alloc(|| format!("tmp5_{}", j), || Ok(tmp5_value))?;
-            cs.enforce(
-                || format!("tmp5_{} = tmp2[{}] + constants[{}][{}]", j, j, i + 1, j),
-                |lc| lc + tmp2[j] + (constants.constants[i + 1][j], CS::one()),
-                |lc| lc + (F::one(), CS::one()),
-                |lc| lc + tmp5,
-            );
-            state[j] = tmp5;
-            state_value[j] = tmp5_value;
-        }
-    }
-    let output = cs.alloc_input(|| "output", || Ok(state_value[0]))?;
-    cs.enforce(
-        || "output = tmp2[j] + constants[i+1][j]",
-        |lc| lc + (F::one(), CS::one()),
-        |lc| lc + state[0],
-        |lc| lc + output,
-    );
-    Ok(image)
-}
-
-fn pow_with_constraint<F: PrimeField, CS: ConstraintSystem<F>, S: AsRef<[u64]>>(
-    state_value: &F,
-    state: &scheme::r1cs::Variable,
-    exp: S,
-    cs: &mut CS,
-    j: usize,
-) -> Result<(F, scheme::r1cs::Variable), SynthesisError> {
-    let mut res_value = F::zero();
-    let mut res = cs.alloc(|| format!("res_{}", j), || Ok(res_value))?;
-
-    let mut found_one = false;
-    let mut cnt = 0;
-    for i in BitIterator::new(exp) {
-        if !found_one {
-            if i {
-                found_one = true;
-            } else {
-                continue;
-            }
-        }
-
-        res_value.square_in_place();
-        let tmp_value = res_value;
-
-        let tmp = cs.alloc(|| format!("tmp_{}{}", j, cnt), || Ok(tmp_value))?;
-
-        cs.enforce(
-            || format!("tmp_{}{} = res * res", j, cnt),
-            |lc| lc + res,
-            |lc| lc + res,
-            |lc| lc + tmp,
-        );
-
-        res_value = tmp_value;
-        res = tmp;
-        if i {
-            res_value.mul_assign(&(*state_value));
-            let tmp2_value = res_value;
-            let tmp2 = cs.alloc(|| format!("tmp2_{}{}", j, cnt), || Ok(tmp2_value))?;
-
-            cs.enforce(
-                || format!("tmp2_{}{} = res * state", j, cnt),
-                |lc| lc + res,
-                |lc| lc + *state,
-                |lc| lc + tmp2,
-            );
-            res_value = tmp_value;
-            res = tmp2;
-        }
-        cnt += 1;
-    }
-
-    Ok((res_value, res))
-}
-
-/// implement AbstractHashOutput.
-#[derive(Clone)]
-pub struct AbstractHashRescueOutput<F: PrimeField> {
-    value: Option<F>,
-    variable: Variable,
-}
-
-impl<F: PrimeField> AbstractHashRescueOutput<F> {
-    pub fn alloc<CS: ConstraintSystem<F>>(
-        mut cs: CS,
-        f: Option<F>, // rescue params
-    ) -> Result<Self, SynthesisError> {
-        let var = cs.alloc(
-            || "rescue_hash",
-            || f.ok_or(SynthesisError::AssignmentMissing),
-        )?;
-
-        Ok(Self {
-            value: f,
-            variable: var,
-        })
-    }
-
-    pub fn get_value(&self) -> Option<F> {
-        self.value
-    }
-}
-
-impl<F: PrimeField> AbstractHashOutput<F> for AbstractHashRescueOutput<F> {
-    fn get_variables(&self) -> Vec<Variable> {
-        vec![self.variable]
-    }
-
-    fn get_variable_values(&self) -> Vec<Option<F>> {
-        vec![self.value]
-    }
-}
-
-// implement AbstractHash.
-pub struct AbstractHashRescue<F>(core::marker::PhantomData<F>);
-
-impl<F: PrimeField> AbstractHash<F> for AbstractHashRescue<F> {
-    type Output = AbstractHashRescueOutput<F>;
-
-    fn hash_enforce<CS: ConstraintSystem<F>>(
-        mut cs: CS,
-        params: &[&Self::Output],
-    ) -> Result<Self::Output, SynthesisError> {
-        let mut bytes = vec![];
-        for o in params.iter() {
-            if let Some(l) = o.get_value() {
-                l.write(&mut bytes)
-                    .map_err(|e| SynthesisError::IoError(e))?;
-            } else {
-                return Err(SynthesisError::AssignmentMissing);
-            }
-        }
-
-        let r = rescue(cs.ns(|| format!("rescue_hash")), &bytes)?;
-
-        AbstractHashRescueOutput::alloc(cs.ns(|| "rescue_hash"), Some(r))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use curve::bn_256::Fr;
-    use math::test_rng;
-    use rand::prelude::*;
-    use scheme::r1cs::ConstraintSystem;
-
-    use super::super::test_constraint_system::TestConstraintSystem;
-    use super::*;
-
-    #[test]
-    fn test_rescue_hash() {
-        let rng = &mut test_rng();
-
-        for _ in 0..10 {
-            let bytes: Vec<u8> = (0..100).map(|_| rng.next_u32() as u8).collect();
-            let hash1 = hash::<Fr>(&bytes);
-            let mut cs = TestConstraintSystem::<Fr>::new();
-            let hash2 = rescue(cs.ns(|| "rescue hash"), &bytes).unwrap();
-            assert_eq!(hash1, hash2);
-            assert!(cs.is_satisfied());
-            // assert_eq!(644, cs.num_constraints());
+impl RescueParameters<Fr> {
+    /// The width 3 instance with 22 rounds which the gadget used before the
+    /// parameters could be generated, the constants are the ones of the
+    /// original Rescue, not of Rescue-Prime.
+    pub fn bn_256() -> Self {
+        RescueParameters {
+            m: 3,
+            capacity: 1,
+            alpha: ALPHA,
+            alpha_inv: ALPHA_INV.to_vec(),
+            rounds: ROUNDS,
+            constants: CONSTANTS
+                .iter()
+                .map(|c| c.iter().map(|s| str_to_field(s)).collect())
+                .collect(),
+            mds: MDS
+                .iter()
+                .map(|row| row.iter().map(|s| str_to_field(s)).collect())
+                .collect(),
         }
     }
 }
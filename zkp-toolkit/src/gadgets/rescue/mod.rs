@@ -0,0 +1,504 @@
+//! Circuits for the [Rescue] hash function.
+// The following code refers to Marvellous [https://github.com/KULeuven-COSIC/Marvellous] and Distaff [https://github.com/GuildOfWeavers/distaff]
+// and thanks for their work
+// @Author: JiadongLu (lujd1234@gmail.com)
+// @Author: YunLi (liyunscss@gmail.com)
+
+use core::marker::PhantomData;
+
+use math::{BitIterator, FromBytes, PrimeField};
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::abstract_hash::{AbstractHash, AbstractHashFrOutput};
+use super::fr::AllocatedFr;
+use super::merkletree::cbmt::Merge;
+
+pub mod params;
+
+#[cfg(feature = "bls12_381")]
+mod bls12_381;
+#[cfg(feature = "bn_256")]
+mod bn_256;
+
+pub use params::RescueParameters;
+
+/// This is an implementation of Resuce
+/// See https://eprint.iacr.org/2019/426 for more
+/// information about this construction.
+///
+/// ```ignore
+/// $Input:\ Plaintext\ P,\ round\ keys\ K_s\ for \ 0 ≤ s ≤ 2N$
+/// $Output:\ Rescue\ (K, P)$
+/// ​	$S_0 = P + K_0 $
+/// ​	$for \ r = 1\ to\ N\ do$ // N rounds
+/// ​		$for\ i = 1\ to\ m\ do$  // for every element
+/// ​			$Interr[i] = K_{2r−1}[i] + ∑{m \atop j=1} M[i, j](S_{r−1}[j])^{α}$
+/// ​		$end$
+/// ​	$for\ i = 1\ to\ m\ do $
+/// ​		$S_r[i] = K_{2r}[i] + ∑{m \atop j=1} M[i, j](Inter_r[j])^{1/α} $
+/// ​	$end $
+/// ​	$end$
+/// ​$return\ S_N$
+/// ```
+///
+/// The permutation of `state`, its length is the width of `params`.
+pub fn permute<F: PrimeField>(state: &mut [F], params: &RescueParameters<F>) {
+    assert_eq!(state.len(), params.m);
+
+    for (s, c) in state.iter_mut().zip(params.constants[0].iter()) {
+        s.add_assign(c);
+    }
+
+    for i in 0..2 * params.rounds {
+        for s in state.iter_mut() {
+            *s = if i % 2 == 0 {
+                s.pow([params.alpha])
+            } else {
+                s.pow(&params.alpha_inv)
+            };
+        }
+
+        // matrix multiplication
+        let mixed = params
+            .mds
+            .iter()
+            .zip(params.constants[i + 1].iter())
+            .map(|(row, c)| {
+                row.iter()
+                    .zip(state.iter())
+                    .fold(*c, |acc, (m, s)| acc + &(*m * s))
+            })
+            .collect::<Vec<_>>();
+        state.copy_from_slice(&mixed);
+    }
+}
+
+/// Compress two elements, the state is `[xl, xr, 0, ...]` and the first
+/// element of the permuted state is the result.
+pub fn rescue_block<F: PrimeField>(xl: F, xr: F, params: &RescueParameters<F>) -> F {
+    let mut state = vec![F::zero(); params.m];
+    state[0] = xl;
+    state[1] = xr;
+    permute(&mut state, params);
+    state[0]
+}
+
+/// rescue hash function.
+fn rescue_hash<F: PrimeField>(b: &[u8], params: &RescueParameters<F>) -> (F, F, F) {
+    let mut v: Vec<F> = Vec::new();
+    let n = <F::BigInt as math::BigInteger>::NUM_LIMBS * 8;
+    for i in 0..(b.len() / n) {
+        let repr = F::BigInt::read(&b[i * n..(i + 1) * n]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    if b.len() % n != 0 {
+        let repr = F::BigInt::read(&b[(b.len() / n) * n..]).unwrap_or(Default::default());
+        v.push(F::from_repr(repr));
+    }
+
+    let mut h: F = F::zero();
+    let xr = v[v.len() - 1].clone();
+    let mut xl = F::zero();
+
+    for i in 0..v.len() {
+        if i == v.len() - 1 {
+            xl = h.clone();
+        }
+
+        h = rescue_block(h, v[i], params);
+    }
+
+    (xl, xr, h)
+}
+
+pub fn hash<F: PrimeField>(b: &[u8], params: &RescueParameters<F>) -> F {
+    rescue_hash(b, params).2
+}
+
+/// The chaining of `rescue_block` over the elements from zero, the hash of
+/// their bytes.
+pub fn hash_elements<F: PrimeField>(elements: &[F], params: &RescueParameters<F>) -> F {
+    elements
+        .iter()
+        .fold(F::zero(), |h, e| rescue_block(h, *e, params))
+}
+
+/// The constraints of the last block of the hash of `b`, the image is the
+/// public output.
+pub fn rescue<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    b: &[u8],
+    params: &RescueParameters<F>,
+) -> Result<F, SynthesisError> {
+    let (xl, xr, image) = rescue_hash(b, params);
+
+    let var_xl = cs.alloc(|| "preimage xl", || Ok(xl))?;
+    let var_xr = cs.alloc(|| "preimage xr", || Ok(xr))?;
+
+    let mut state_value = vec![Some(F::zero()); params.m];
+    state_value[0] = Some(xl);
+    state_value[1] = Some(xr);
+    let mut state = vec![LinearCombination::zero(); params.m];
+    state[0] = state[0].clone() + var_xl;
+    state[1] = state[1].clone() + var_xr;
+
+    let (state_value, state) = permute_enforce(&mut cs, state_value, state, params)?;
+
+    let output = cs.alloc_input(
+        || "output",
+        || state_value[0].ok_or(SynthesisError::AssignmentMissing),
+    )?;
+    cs.enforce(
+        || "output = state[0]",
+        |lc| lc + (F::one(), CS::one()),
+        |lc| lc + &state[0],
+        |lc| lc + output,
+    );
+    Ok(image)
+}
+
+/// The gadget of `hash_elements`, the state goes from a block to the next
+/// as linear combinations, only the image is allocated.
+pub fn hash_elements_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    elements: &[AllocatedFr<F>],
+    params: &RescueParameters<F>,
+) -> Result<AllocatedFr<F>, SynthesisError> {
+    let mut h_value = Some(F::zero());
+    let mut h = LinearCombination::zero();
+
+    for (i, e) in elements.iter().enumerate() {
+        let mut state_value = vec![Some(F::zero()); params.m];
+        state_value[0] = h_value;
+        state_value[1] = e.get_value();
+        let mut state = vec![LinearCombination::zero(); params.m];
+        state[0] = h;
+        state[1] = state[1].clone() + e.get_variable();
+
+        let (state_value, state) =
+            permute_enforce(cs.ns(|| format!("block {}", i)), state_value, state, params)?;
+        h_value = state_value[0];
+        h = state[0].clone();
+    }
+
+    let output = AllocatedFr::alloc(cs.ns(|| "output"), || {
+        h_value.ok_or(SynthesisError::AssignmentMissing)
+    })?;
+    cs.enforce(
+        || "output = state[0]",
+        |lc| lc + (F::one(), CS::one()),
+        |lc| lc + &h,
+        |lc| lc + output.get_variable(),
+    );
+
+    Ok(output)
+}
+
+/// The values of a state in the circuit, and its linear combinations.
+type State<F> = (Vec<Option<F>>, Vec<LinearCombination<F>>);
+
+/// The constraints of `permute` on the linear combinations of the state,
+/// with their values.
+fn permute_enforce<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    mut state_value: Vec<Option<F>>,
+    mut state: Vec<LinearCombination<F>>,
+    params: &RescueParameters<F>,
+) -> Result<State<F>, SynthesisError> {
+    for j in 0..params.m {
+        state_value[j] = state_value[j].map(|v| v + &params.constants[0][j]);
+        state[j] = state[j].clone() + (params.constants[0][j], CS::one());
+    }
+
+    for i in 0..2 * params.rounds {
+        let cs = &mut cs.ns(|| format!("round_{}", i));
+
+        for j in 0..params.m {
+            let (value, var) = if i % 2 == 0 {
+                pow_with_constraint(
+                    cs.ns(|| format!("sbox_{}", j)),
+                    state_value[j],
+                    &state[j],
+                    [params.alpha],
+                )?
+            } else {
+                inverse_sbox_with_constraint(
+                    cs.ns(|| format!("inverse_sbox_{}", j)),
+                    state_value[j],
+                    &state[j],
+                    params,
+                )?
+            };
+            state_value[j] = value;
+            state[j] = LinearCombination::zero() + var;
+        }
+
+        let mut mixed_value = Vec::with_capacity(params.m);
+        let mut mixed = Vec::with_capacity(params.m);
+        for (row, c) in params.mds.iter().zip(params.constants[i + 1].iter()) {
+            let mut value = Some(*c);
+            let mut lc = LinearCombination::zero() + (*c, CS::one());
+            for (k, m) in row.iter().enumerate() {
+                value = value.and_then(|v| state_value[k].map(|s| v + &(*m * &s)));
+                lc = lc + (*m, &state[k]);
+            }
+            mixed_value.push(value);
+            mixed.push(lc);
+        }
+        state_value = mixed_value;
+        state = mixed;
+    }
+
+    Ok((state_value, state))
+}
+
+/// `x^exp` by square and multiply, one constraint per step.
+fn pow_with_constraint<F: PrimeField, CS: ConstraintSystem<F>, S: AsRef<[u64]>>(
+    mut cs: CS,
+    value: Option<F>,
+    x: &LinearCombination<F>,
+    exp: S,
+) -> Result<(Option<F>, Variable), SynthesisError> {
+    // the leading one of exp, res = x.
+    let mut res_value = value;
+    let mut res = x.clone();
+    let mut res_var = None;
+
+    for (cnt, bit) in BitIterator::new(exp).skip_while(|b| !b).skip(1).enumerate() {
+        let square_value = res_value.map(|v| v.square());
+        let square = cs.alloc(
+            || format!("square_{}", cnt),
+            || square_value.ok_or(SynthesisError::AssignmentMissing),
+        )?;
+        cs.enforce(
+            || format!("square_{} = res * res", cnt),
+            |lc| lc + &res,
+            |lc| lc + &res,
+            |lc| lc + square,
+        );
+        res_value = square_value;
+        res = LinearCombination::zero() + square;
+        res_var = Some(square);
+
+        if bit {
+            let mul_value = res_value.and_then(|r| value.map(|v| r * &v));
+            let mul = cs.alloc(
+                || format!("mul_{}", cnt),
+                || mul_value.ok_or(SynthesisError::AssignmentMissing),
+            )?;
+            cs.enforce(
+                || format!("mul_{} = res * x", cnt),
+                |lc| lc + &res,
+                |lc| lc + x,
+                |lc| lc + mul,
+            );
+            res_value = mul_value;
+            res = LinearCombination::zero() + mul;
+            res_var = Some(mul);
+        }
+    }
+
+    Ok((res_value, res_var.ok_or(SynthesisError::Unsatisfiable)?))
+}
+
+/// `y = x^(1/alpha)`, which is constrained by `y^alpha = x` rather than by
+/// the long exponentiation.
+fn inverse_sbox_with_constraint<F: PrimeField, CS: ConstraintSystem<F>>(
+    mut cs: CS,
+    value: Option<F>,
+    x: &LinearCombination<F>,
+    params: &RescueParameters<F>,
+) -> Result<(Option<F>, Variable), SynthesisError> {
+    let y_value = value.map(|v| v.pow(&params.alpha_inv));
+    let y = cs.alloc(|| "y", || y_value.ok_or(SynthesisError::AssignmentMissing))?;
+
+    let (_, y_alpha) = pow_with_constraint(
+        cs.ns(|| "y^alpha"),
+        y_value,
+        &(LinearCombination::zero() + y),
+        [params.alpha],
+    )?;
+    cs.enforce(
+        || "y^alpha = x",
+        |lc| lc + y_alpha,
+        |lc| lc + CS::one(),
+        |lc| lc + x,
+    );
+
+    Ok((y_value, y))
+}
+
+/// Merges the nodes of a merkle tree into their `hash_elements` with the
+/// default parameters, as `AbstractHashRescue` does in the circuit.
+pub struct MergeRescue<F>(PhantomData<F>);
+
+impl<F: PrimeField> Merge for MergeRescue<F> {
+    type Item = F;
+
+    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
+        Self::merge_many(&[*left, *right])
+    }
+
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        hash_elements(items, &RescueParameters::shared())
+    }
+}
+
+pub type AbstractHashRescueOutput<F> = AbstractHashFrOutput<F>;
+
+/// The `hash_elements` of the nodes with the default parameters.
+pub struct AbstractHashRescue<F>(PhantomData<F>);
+
+impl<F: PrimeField> AbstractHash<F> for AbstractHashRescue<F> {
+    type Output = AbstractHashRescueOutput<F>;
+
+    fn hash_enforce<CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        params: &[&Self::Output],
+    ) -> Result<Self::Output, SynthesisError> {
+        let elements = params
+            .iter()
+            .map(|o| o.to_allocated_fr())
+            .collect::<Vec<_>>();
+
+        hash_elements_enforce(
+            cs.ns(|| "rescue_hash"),
+            &elements,
+            &RescueParameters::shared(),
+        )
+        .map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+    use crate::Arc;
+
+    fn native_and_gadget<F: PrimeField>(params: &RescueParameters<F>) {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let bytes: Vec<u8> = (0..100).map(|_| rng.next_u32() as u8).collect();
+            let hash1 = hash::<F>(&bytes, params);
+            let mut cs = TestConstraintSystem::<F>::new();
+            let hash2 = rescue(cs.ns(|| "rescue hash"), &bytes, params).unwrap();
+            assert_eq!(hash1, hash2);
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.get_input(1, "rescue hash/output"), hash1);
+        }
+    }
+
+    #[cfg(feature = "bn_256")]
+    #[test]
+    fn test_rescue_hash_bn_256() {
+        use curve::bn_256::Fr;
+
+        native_and_gadget(&RescueParameters::<Fr>::bn_256());
+
+        let params = RescueParameters::<Fr>::default();
+        native_and_gadget(&params);
+        // 3 constraints an S-box, 4 an inverse S-box, and the output.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        rescue(cs.ns(|| "rescue hash"), &[1u8; 32], &params).unwrap();
+        assert_eq!(14 * 3 * (3 + 4) + 1, cs.num_constraints());
+    }
+
+    #[cfg(feature = "bn_256")]
+    #[test]
+    fn test_rescue_hash_changed_from_bn_256() {
+        use curve::bn_256::Fr;
+
+        // the hash of the gadget before the parameters were generated, by a
+        // Python port of its 22 rounds of the original Rescue constants.
+        let bytes = [1u8; 32];
+        let before = "9642913727446410828532605448856429252160679202430452120971680200284301318226"
+            .parse::<Fr>()
+            .unwrap();
+        assert_eq!(hash(&bytes, &RescueParameters::<Fr>::bn_256()), before);
+
+        // the default Rescue-Prime parameters hash to another value, the
+        // commitments and merkle roots of `AbstractHashRescue` changed.
+        let after = hash(&bytes, &RescueParameters::<Fr>::default());
+        assert_ne!(after, before);
+        assert_eq!(
+            after,
+            "9744656878486030791256793549485101583340077404747971151149699194336408801485"
+                .parse::<Fr>()
+                .unwrap()
+        );
+        assert!(Arc::ptr_eq(
+            &RescueParameters::<Fr>::shared(),
+            &RescueParameters::<Fr>::shared()
+        ));
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_rescue_hash_bls12_381() {
+        use curve::bls12_381::Fr;
+
+        native_and_gadget(&RescueParameters::<Fr>::bls12_381());
+    }
+
+    #[cfg(feature = "bn_256")]
+    #[test]
+    fn test_rescue_prime_permutation() {
+        use curve::bn_256::Fr;
+        use math::{One, Zero};
+
+        // computed with the algorithms of the Rescue-Prime reference
+        // implementation, no published test vector is over this field.
+        let params = RescueParameters::<Fr>::default();
+        let mut state = [Fr::zero(), Fr::one(), Fr::from(2u32)];
+        permute(&mut state, &params);
+        let expected = [
+            "6224690566795026170272976986384432621080028281436539532889157379570648910802",
+            "11125085147280074555337181371265636082619440214910773293161304065299707718600",
+            "12118779605307541175395572293313884052054477690855880723785138715937774904848",
+        ];
+        for (s, e) in state.iter().zip(expected.iter()) {
+            assert_eq!(*s, e.parse::<Fr>().unwrap());
+        }
+    }
+
+    #[cfg(feature = "bn_256")]
+    #[test]
+    fn test_hash_elements() {
+        use curve::bn_256::Fr;
+        use math::ToBytes;
+
+        let rng = &mut test_rng();
+        let params = RescueParameters::<Fr>::default();
+
+        for n in 1..4 {
+            let elements: Vec<Fr> = (0..n).map(|_| rng.gen()).collect();
+            let mut bytes = vec![];
+            for e in elements.iter() {
+                e.write(&mut bytes).unwrap();
+            }
+            let image = hash_elements(&elements, &params);
+            assert_eq!(image, hash::<Fr>(&bytes, &params));
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let vars = elements
+                .iter()
+                .enumerate()
+                .map(|(i, e)| AllocatedFr::alloc(cs.ns(|| format!("e {}", i)), || Ok(*e)).unwrap())
+                .collect::<Vec<_>>();
+            let r = hash_elements_enforce(cs.ns(|| "hash"), &vars, &params).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(r.get_value(), Some(image));
+            // the blocks of `rescue`, and the output.
+            assert_eq!(cs.num_constraints(), 14 * 3 * (3 + 4) * n + 1);
+        }
+    }
+}
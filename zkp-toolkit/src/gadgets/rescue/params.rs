@@ -0,0 +1,398 @@
+//! Parameter generation for Rescue-Prime.
+//!
+//! The number of rounds, the round constants and the MDS matrix follow the
+//! reference implementation of the Rescue-Prime specification
+//! [https://eprint.iacr.org/2020/1143].
+use core::cmp;
+use math::{FpParameters, PrimeField};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+use crate::{Arc, String, Vec};
+
+use super::super::cache::cached;
+use super::super::poseidon::params::{gcd, modulus_minus_one_rem};
+
+/// The parameters of a Rescue permutation over `F`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RescueParameters<F: PrimeField> {
+    /// the width of the state.
+    pub m: usize,
+    /// the capacity of the sponge, the rate is `m - capacity`.
+    pub capacity: usize,
+    /// the exponent of the S-box, `x^alpha`.
+    pub alpha: u64,
+    /// the exponent of the inverse S-box, `alpha * alpha_inv == 1 (mod p-1)`.
+    pub alpha_inv: Vec<u64>,
+    /// the number of rounds, each of them is two half rounds.
+    pub rounds: usize,
+    /// `2 * rounds + 1` constants, the first one is added to the input and
+    /// the others after every half round.
+    pub constants: Vec<Vec<F>>,
+    /// the `m x m` MDS matrix.
+    pub mds: Vec<Vec<F>>,
+}
+
+impl<F: PrimeField> RescueParameters<F> {
+    /// Generates the Rescue-Prime parameters of width `m` for `security_level`
+    /// bits of security, the S-box is the smallest `alpha` which is a
+    /// permutation of the field.
+    pub fn new(m: usize, capacity: usize, security_level: usize) -> Self {
+        let rounds = round_number(m, capacity, smallest_alpha::<F>(), security_level);
+        Self::with_rounds(m, capacity, security_level, rounds)
+    }
+
+    /// Generates the Rescue-Prime parameters of width `m` with the given
+    /// number of rounds, the round constants are seeded with
+    /// `security_level`.
+    pub fn with_rounds(m: usize, capacity: usize, security_level: usize, rounds: usize) -> Self {
+        assert!(
+            capacity >= 1 && capacity < m,
+            "the capacity must be at least 1 and less than the width"
+        );
+        let alpha = smallest_alpha::<F>();
+
+        Self {
+            m,
+            capacity,
+            alpha,
+            alpha_inv: inverse_exponent::<F>(alpha),
+            rounds,
+            constants: round_constants(m, capacity, security_level, rounds),
+            mds: mds_matrix(m),
+        }
+    }
+}
+
+impl<F: PrimeField> RescueParameters<F> {
+    /// The default parameters, generated once per field and then shared.
+    pub fn shared() -> Arc<Self> {
+        cached("rescue", || Self::new(3, 1, 128))
+    }
+}
+
+impl<F: PrimeField> Default for RescueParameters<F> {
+    /// The width 3, capacity 1 instance for 128 bits of security.
+    fn default() -> Self {
+        Self::shared().as_ref().clone()
+    }
+}
+
+/// The numbers of rounds which resist the Gröbner basis attacks, at least 5
+/// and with 50% more as the security margin.
+pub fn round_number(m: usize, capacity: usize, alpha: u64, security_level: usize) -> usize {
+    let rate = m - capacity;
+    let alpha = alpha as usize;
+
+    let l1 = (1..25)
+        .find(|&n| {
+            let v = m * (n - 1) + rate;
+            let dcon = (alpha - 1) * m * (n - 1) / 2 + 2;
+            let b = binomial(v + dcon, v);
+            greater_than_power_of_two(&mul(&b, &b), security_level)
+        })
+        .unwrap_or(24);
+
+    (3 * cmp::max(5, l1) + 1) / 2
+}
+
+pub(super) fn str_to_field<F: PrimeField>(s: &str) -> F {
+    s.parse()
+        .unwrap_or_else(|_| panic!("invalid field element {}", s))
+}
+
+fn smallest_alpha<F: PrimeField>() -> u64 {
+    (3..)
+        .find(|&alpha| gcd(alpha, modulus_minus_one_rem::<F>(alpha)) == 1)
+        .unwrap()
+}
+
+/// `(k * (p - 1) + 1) / alpha`, with `k < alpha` such that it is an integer.
+fn inverse_exponent<F: PrimeField>(alpha: u64) -> Vec<u64> {
+    let mut e = F::Params::MODULUS.as_ref().to_vec();
+    let limbs = e.len();
+    // p is odd.
+    e[0] -= 1;
+
+    let r = modulus_minus_one_rem::<F>(alpha) as u128;
+    let k = (1..alpha)
+        .find(|&k| (k as u128 * r + 1) % alpha as u128 == 0)
+        .unwrap();
+    mul_small(&mut e, k);
+    add_small(&mut e, 1);
+    div_small(&mut e, alpha);
+    e.resize(limbs, 0);
+    e
+}
+
+/// The round constants from SHAKE256 of `Rescue-XLIX(p,m,capacity,security_level)`,
+/// with a zero row in front of them.
+fn round_constants<F: PrimeField>(
+    m: usize,
+    capacity: usize,
+    security_level: usize,
+    rounds: usize,
+) -> Vec<Vec<F>> {
+    let bytes_per_int = (F::Params::MODULUS_BITS as usize + 7) / 8 + 1;
+    let seed = format!(
+        "Rescue-XLIX({},{},{},{})",
+        to_decimal(F::Params::MODULUS.as_ref()),
+        m,
+        capacity,
+        security_level
+    );
+
+    let mut shake = Shake256::default();
+    shake.update(seed.as_bytes());
+    let mut reader = shake.finalize_xof();
+
+    let base = F::from(256u32);
+    let mut chunk = vec![0u8; bytes_per_int];
+    let mut constants = vec![vec![F::zero(); m]];
+    for _ in 0..2 * rounds {
+        constants.push(
+            (0..m)
+                .map(|_| {
+                    reader.read(&mut chunk);
+                    // a little endian integer, reduced in the field.
+                    chunk
+                        .iter()
+                        .rev()
+                        .fold(F::zero(), |acc, b| acc * &base + &F::from(*b))
+                })
+                .collect(),
+        );
+    }
+    constants
+}
+
+/// The smallest primitive element, as in the reference implementation.
+///
+/// The small candidates are only tested against the prime factors of `p - 1`
+/// below 2^16, the multiplicative generator of `F` is taken if none of the
+/// candidates below it passes.
+fn primitive_element<F: PrimeField>() -> F {
+    let generator = F::multiplicative_generator();
+    let mut p_minus_one = F::Params::MODULUS.as_ref().to_vec();
+    p_minus_one[0] -= 1;
+
+    let mut rest = p_minus_one.clone();
+    let mut factors = vec![];
+    for q in 2..(1u64 << 16) {
+        let mut divides = false;
+        loop {
+            let mut t = rest.clone();
+            if div_small(&mut t, q) != 0 {
+                break;
+            }
+            rest = t;
+            divides = true;
+        }
+        if divides {
+            factors.push(q);
+        }
+    }
+
+    (2u64..256)
+        .map(F::from)
+        .take_while(|g| *g != generator)
+        .find(|g| {
+            factors.iter().all(|q| {
+                let mut e = p_minus_one.clone();
+                div_small(&mut e, *q);
+                !g.pow(&e).is_one()
+            })
+        })
+        .unwrap_or(generator)
+}
+
+/// The transpose of the right half of the reduced echelon form of the
+/// `m x 2m` Vandermonde matrix `g^(i * j)`, `g` is a primitive element.
+fn mds_matrix<F: PrimeField>(m: usize) -> Vec<Vec<F>> {
+    let g = primitive_element::<F>();
+    let mut v = (0..m)
+        .map(|i| {
+            let gi = g.pow([i as u64]);
+            (0..2 * m).map(|j| gi.pow([j as u64])).collect()
+        })
+        .collect::<Vec<Vec<F>>>();
+
+    for c in 0..m {
+        let pivot = (c..m)
+            .find(|&r| !v[r][c].is_zero())
+            .expect("the left half is an invertible vandermonde matrix");
+        v.swap(c, pivot);
+
+        let inv = v[c][c].inverse().unwrap();
+        for x in v[c].iter_mut() {
+            *x *= &inv;
+        }
+
+        let row = v[c].clone();
+        for (r, other) in v.iter_mut().enumerate() {
+            let f = other[c];
+            if r != c && !f.is_zero() {
+                for (x, y) in other.iter_mut().zip(row.iter()) {
+                    *x -= &(f * y);
+                }
+            }
+        }
+    }
+
+    (0..m)
+        .map(|i| (0..m).map(|j| v[j][m + i]).collect())
+        .collect()
+}
+
+// little endian unsigned integers of any length.
+
+fn mul_small(a: &mut Vec<u64>, b: u64) {
+    let mut carry = 0u128;
+    for x in a.iter_mut() {
+        let t = *x as u128 * b as u128 + carry;
+        *x = t as u64;
+        carry = t >> 64;
+    }
+    if carry != 0 {
+        a.push(carry as u64);
+    }
+}
+
+fn add_small(a: &mut Vec<u64>, b: u64) {
+    let mut carry = b;
+    for x in a.iter_mut() {
+        let (t, overflow) = x.overflowing_add(carry);
+        *x = t;
+        carry = overflow as u64;
+        if carry == 0 {
+            return;
+        }
+    }
+    if carry != 0 {
+        a.push(carry);
+    }
+}
+
+/// Divides `a` by `b` in place and returns the remainder.
+fn div_small(a: &mut Vec<u64>, b: u64) -> u64 {
+    let mut rem = 0u128;
+    for x in a.iter_mut().rev() {
+        let t = (rem << 64) + *x as u128;
+        *x = (t / b as u128) as u64;
+        rem = t % b as u128;
+    }
+    while a.len() > 1 && a[a.len() - 1] == 0 {
+        a.pop();
+    }
+    rem as u64
+}
+
+fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut res = vec![0u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, y) in b.iter().enumerate() {
+            let t = *x as u128 * *y as u128 + res[i + j] as u128 + carry;
+            res[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        res[i + b.len()] = carry as u64;
+    }
+    res
+}
+
+fn binomial(n: usize, k: usize) -> Vec<u64> {
+    let mut c = vec![1u64];
+    for i in 0..k {
+        // C(n, i) * (n - i) == C(n, i + 1) * (i + 1)
+        mul_small(&mut c, (n - i) as u64);
+        div_small(&mut c, (i + 1) as u64);
+    }
+    c
+}
+
+/// `a > 2^k`.
+fn greater_than_power_of_two(a: &[u64], k: usize) -> bool {
+    let bits = match a.iter().rposition(|x| *x != 0) {
+        Some(i) => 64 * i + 64 - a[i].leading_zeros() as usize,
+        None => return false,
+    };
+    let ones = a.iter().map(|x| x.count_ones()).sum::<u32>();
+    bits > k + 1 || (bits == k + 1 && ones > 1)
+}
+
+fn to_decimal(a: &[u64]) -> String {
+    const TEN_19: u64 = 10_000_000_000_000_000_000;
+
+    let mut a = a.to_vec();
+    let mut chunks = vec![];
+    loop {
+        chunks.push(div_small(&mut a, TEN_19));
+        if a.iter().all(|x| *x == 0) {
+            break;
+        }
+    }
+
+    let mut s = format!("{}", chunks.pop().unwrap());
+    for c in chunks.iter().rev() {
+        s.push_str(&format!("{:019}", c));
+    }
+    s
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_number() {
+        assert_eq!(round_number(3, 1, 5, 128), 14);
+        // the instance over 2^64 - 2^32 + 1 in the specification.
+        assert_eq!(round_number(12, 4, 7, 128), 8);
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(to_decimal(&[0]), "0");
+        assert_eq!(to_decimal(&[0, 1]), "18446744073709551616");
+        assert_eq!(
+            to_decimal(&binomial(100, 50)),
+            "100891344545564193334812497256"
+        );
+        assert!(greater_than_power_of_two(&[0, 2], 64));
+        assert!(!greater_than_power_of_two(&[0, 1], 64));
+        assert!(greater_than_power_of_two(&[1, 1], 64));
+    }
+
+    #[cfg(feature = "bn_256")]
+    #[test]
+    fn test_bn_256_parameters() {
+        use curve::bn_256::Fr;
+
+        let params = RescueParameters::<Fr>::new(3, 1, 128);
+        assert_eq!(params.rounds, 14);
+        assert_eq!(params.constants.len(), 29);
+        // the same inverse exponent as the bn_256 parameters of the original Rescue.
+        assert_eq!(params.alpha_inv, RescueParameters::bn_256().alpha_inv);
+        assert_eq!(params.mds[0][0], Fr::from(125u32));
+        assert_eq!(
+            params.constants[1][0],
+            str_to_field(
+                "16315208746038078395621556119853320273013100435293928429550050637277758017174"
+            )
+        );
+    }
+
+    #[cfg(feature = "bls12_381")]
+    #[test]
+    fn test_bls12_381_parameters() {
+        use curve::bls12_381::Fr;
+
+        assert_eq!(
+            RescueParameters::<Fr>::new(3, 1, 128),
+            RescueParameters::bls12_381()
+        );
+    }
+}
@@ -5,13 +5,18 @@
 
 use math::PrimeField;
 use scheme::r1cs::{ConstraintSystem, SynthesisError, Variable};
+use sha2::{Digest, Sha256};
 
 use crate::Vec;
 
 use super::abstract_hash::{AbstractHash, AbstractHashOutput};
 use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+use super::merkletree::cbmt::Merge;
 use super::multieq::MultiEq;
+use super::multipack::{bytes_to_bits_be, compute_multipacking, pack_bits};
 use super::uint32::UInt32;
+use super::uint8::{bits_to_bytes_be, UInt8};
 
 const ROUND_CONSTANTS: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
@@ -46,33 +51,143 @@ where
     )
 }
 
+/// SHA-256 gadget of the big-endian bits of a message of any length, the
+/// padding included, so the `sha256` of the bits of bytes is the digest of
+/// the bytes. The message should have a fixed length in a circuit.
 pub fn sha256<F, CS>(mut cs: CS, input: &[Boolean]) -> Result<Vec<Boolean>, SynthesisError>
 where
     F: PrimeField,
     CS: ConstraintSystem<F>,
 {
-    assert!(input.len() % 8 == 0);
+    // We can save some constraints by combining some of
+    // the constraints in different u32 additions, of all the blocks
+    let mut cs = MultiEq::new(&mut cs);
+    let mut gadget = Sha256Gadget::new();
+    gadget.update_batched(&mut cs, input)?;
+    gadget.finalize_batched(&mut cs)
+}
+
+/// The SHA-256 gadget absorbing a long message in parts, the blocks are
+/// compressed as soon as they are full. The digest of the parts is the
+/// `sha256` of their concatenation. `update` and `finalize` batch the
+/// additions of the blocks of a call, `update_batched` and
+/// `finalize_batched` the ones of all the calls sharing a `MultiEq`, as
+/// `sha256` does, up to the drop of the `MultiEq`.
+#[derive(Clone)]
+pub struct Sha256Gadget {
+    state: Vec<UInt32>,
+    buffer: Vec<Boolean>,
+    length: u64,
+    blocks: usize,
+}
+
+impl Default for Sha256Gadget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha256Gadget {
+    pub fn new() -> Self {
+        Sha256Gadget {
+            state: get_sha256_iv(),
+            buffer: Vec::with_capacity(512),
+            length: 0,
+            blocks: 0,
+        }
+    }
 
-    let mut padded = input.to_vec();
-    let plen = padded.len() as u64;
-    // append a single '1' bit
-    padded.push(Boolean::constant(true));
-    // append K '0' bits, where K is the minimum number >= 0 such that L + 1 + K + 64 is a multiple of 512
-    while (padded.len() + 64) % 512 != 0 {
-        padded.push(Boolean::constant(false));
+    /// Absorbs the big-endian bits of the next part of the message, of any
+    /// length. The blocks are namespaced `block {i}` in the order of the
+    /// message.
+    pub fn update<F, CS>(&mut self, mut cs: CS, input: &[Boolean]) -> Result<(), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        self.update_batched(MultiEq::new(&mut cs), input)
     }
-    // append L as a 64-bit big-endian integer, making the total post-processed length a multiple of 512 bits
-    for b in (0..64).rev().map(|i| (plen >> i) & 1 == 1) {
-        padded.push(Boolean::constant(b));
+
+    /// `update` with the additions batched in the `MultiEq` of the caller.
+    pub fn update_batched<F, CS, M>(
+        &mut self,
+        mut cs: M,
+        input: &[Boolean],
+    ) -> Result<(), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        self.length += input.len() as u64;
+
+        for bit in input {
+            self.buffer.push(*bit);
+            if self.buffer.len() == 512 {
+                self.compress(&mut cs)?;
+            }
+        }
+
+        Ok(())
     }
-    assert!(padded.len() % 512 == 0);
 
-    let mut cur = get_sha256_iv();
-    for (i, block) in padded.chunks(512).enumerate() {
-        cur = sha256_compression_function(cs.ns(|| format!("block {}", i)), block, &cur)?;
+    /// Pads the message with a '1' bit, the '0' bits up to 64 bits before the
+    /// end of a block and the 64 bits length, and returns the big-endian
+    /// bits of the digest.
+    pub fn finalize<F, CS>(self, mut cs: CS) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        self.finalize_batched(MultiEq::new(&mut cs))
     }
 
-    Ok(cur.into_iter().flat_map(|e| e.into_bits_be()).collect())
+    /// `finalize` with the additions batched in the `MultiEq` of the caller.
+    pub fn finalize_batched<F, CS, M>(mut self, mut cs: M) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        let length = self.length;
+
+        self.buffer.push(Boolean::constant(true));
+        if self.buffer.len() > 448 {
+            self.buffer.resize(512, Boolean::constant(false));
+            self.compress(&mut cs)?;
+        }
+        self.buffer.resize(448, Boolean::constant(false));
+        self.buffer.extend(
+            (0..64)
+                .rev()
+                .map(|i| Boolean::constant((length >> i) & 1 == 1)),
+        );
+        self.compress(&mut cs)?;
+
+        Ok(self
+            .state
+            .into_iter()
+            .flat_map(|e| e.into_bits_be())
+            .collect())
+    }
+
+    fn compress<F, CS, M>(&mut self, mut cs: M) -> Result<(), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        let block = self.blocks;
+        self.state = sha256_compression_function_batched(
+            cs.ns(|| format!("block {}", block)),
+            &self.buffer,
+            &self.state,
+        )?;
+        self.buffer.clear();
+        self.blocks += 1;
+
+        Ok(())
+    }
 }
 
 fn get_sha256_iv() -> Vec<UInt32> {
@@ -80,13 +195,30 @@ fn get_sha256_iv() -> Vec<UInt32> {
 }
 
 fn sha256_compression_function<F, CS>(
-    cs: CS,
+    mut cs: CS,
     input: &[Boolean],
     current_hash_value: &[UInt32],
 ) -> Result<Vec<UInt32>, SynthesisError>
 where
     F: PrimeField,
     CS: ConstraintSystem<F>,
+{
+    // We can save some constraints by combining some of
+    // the constraints in different u32 additions
+    sha256_compression_function_batched(MultiEq::new(&mut cs), input, current_hash_value)
+}
+
+/// The compression of a block, its additions batched in the `MultiEq` of
+/// the caller, along the ones of the other blocks.
+fn sha256_compression_function_batched<F, CS, M>(
+    mut cs: M,
+    input: &[Boolean],
+    current_hash_value: &[UInt32],
+) -> Result<Vec<UInt32>, SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+    M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
 {
     assert_eq!(input.len(), 512);
     assert_eq!(current_hash_value.len(), 8);
@@ -96,10 +228,6 @@ where
         .map(|e| UInt32::from_bits_be(e))
         .collect::<Vec<_>>();
 
-    // We can save some constraints by combining some of
-    // the constraints in different u32 additions
-    let mut cs = MultiEq::new(cs);
-
     for i in 16..64 {
         let cs = &mut cs.ns(|| format!("w extension {}", i));
 
@@ -252,82 +380,151 @@ where
     Ok(vec![h0, h1, h2, h3, h4, h5, h6, h7])
 }
 
-/// implement AbstractHashSha256Output.
+/// The digest of `bytes`, the one `sha256` computes on their big-endian bits.
+pub fn hash(bytes: &[u8]) -> Vec<u8> {
+    Sha256::digest(bytes).to_vec()
+}
+
+/// Merges the nodes of a merkle tree into the digest of their concatenation,
+/// as `AbstractHashSha256` does in the circuit.
+pub struct MergeSha256;
+
+impl Merge for MergeSha256 {
+    type Item = Vec<u8>;
+
+    fn merge(left: &Self::Item, right: &Self::Item) -> Self::Item {
+        Self::merge_many(&[left.clone(), right.clone()])
+    }
+
+    fn merge_many(items: &[Self::Item]) -> Self::Item {
+        hash(&items.concat())
+    }
+}
+
+/// The big-endian bits of every byte of `bytes`, or the 256 bits of a digest
+/// without a value.
+fn digest_bits(bytes: Option<Vec<u8>>) -> Vec<Option<bool>> {
+    match bytes {
+        Some(bytes) => bytes
+            .iter()
+            .flat_map(|&byte| (0..8).rev().map(move |i| Some((byte >> i) & 1u8 == 1u8)))
+            .collect(),
+        None => vec![None; 256],
+    }
+}
+
+/// A digest as the bits of a node of `MerkleProofGadget`, big-endian in
+/// every byte.
 #[derive(Clone)]
 pub struct AbstractHashSha256Output {
-    value: Option<Vec<Boolean>>,
+    bits: Vec<Boolean>,
     variables: Vec<Variable>,
 }
 
 impl AbstractHashSha256Output {
+    /// Allocates the bits of `bytes`, a digest or a leaf. Without a value,
+    /// the ones of a digest are allocated, e.g. for the parameter generation.
     pub fn alloc<F: PrimeField, CS: ConstraintSystem<F>>(
         mut cs: CS,
-        bytes: Vec<u8>, // standard sha256 hash result.
+        bytes: Option<Vec<u8>>,
     ) -> Result<Self, SynthesisError> {
-        let s = bytes
-            .iter()
-            .flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1u8 == 1u8));
-
-        let mut values = vec![];
+        let mut bits = vec![];
         let mut variables = vec![];
-        for (i, v) in s.enumerate() {
-            let alloc = AllocatedBit::alloc(cs.ns(|| format!("output_bit_{}", i)), Some(v))?;
+        for (i, v) in digest_bits(bytes).into_iter().enumerate() {
+            let alloc = AllocatedBit::alloc(cs.ns(|| format!("output_bit_{}", i)), v)?;
             variables.push(alloc.get_variable());
-            values.push(alloc.into());
+            bits.push(alloc.into());
         }
-        Ok(Self {
-            value: Some(values),
-            variables: variables,
-        })
+
+        Ok(Self { bits, variables })
     }
 
+    /// Allocates the bits of `bytes` as public inputs, as `alloc` does.
     pub fn alloc_input<F: PrimeField, CS: ConstraintSystem<F>>(
         mut cs: CS,
-        bytes: Vec<u8>, // standard sha256 hash result.
+        bytes: Option<Vec<u8>>,
     ) -> Result<Self, SynthesisError> {
-        let s = bytes
-            .iter()
-            .flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1u8 == 1u8));
-
-        let mut values = vec![];
+        let mut bits = vec![];
         let mut variables = vec![];
-        for (i, v) in s.enumerate() {
-            let alloc = AllocatedBit::alloc_input(cs.ns(|| format!("output_bit_{}", i)), Some(v))?;
+        for (i, v) in digest_bits(bytes).into_iter().enumerate() {
+            let alloc = AllocatedBit::alloc_input(cs.ns(|| format!("output_bit_{}", i)), v)?;
             variables.push(alloc.get_variable());
-            values.push(alloc.into());
+            bits.push(alloc.into());
         }
 
-        Ok(Self {
-            value: Some(values),
-            variables: variables,
-        })
+        Ok(Self { bits, variables })
     }
 
-    pub fn get_value(&self) -> Option<Vec<Boolean>> {
-        self.value.clone()
+    /// The bytes of the digest, if the bits have a value.
+    pub fn get_value(&self) -> Option<Vec<u8>> {
+        self.bits
+            .chunks(8)
+            .map(|byte| {
+                byte.iter().try_fold(0u8, |acc, bit| {
+                    bit.get_value().map(|bit| (acc << 1) | bit as u8)
+                })
+            })
+            .collect()
+    }
+
+    pub fn get_bits(&self) -> &[Boolean] {
+        &self.bits
     }
 }
 
 impl<F: PrimeField> AbstractHashOutput<F> for AbstractHashSha256Output {
+    type Value = Vec<u8>;
+
     fn get_variables(&self) -> Vec<Variable> {
         self.variables.clone()
     }
 
     fn get_variable_values(&self) -> Vec<Option<F>> {
-        let value_vec = self.value.clone().unwrap();
-        let mut ret_vec = vec![Some(F::zero()); value_vec.len() as usize];
-        for i in 0..value_vec.len() as usize {
-            ret_vec[i] = if value_vec[i].get_value().unwrap() {
-                Some(F::one())
-            } else {
-                Some(F::zero())
-            }
+        self.bits
+            .iter()
+            .map(|bit| {
+                bit.get_value()
+                    .map(|b| if b { F::one() } else { F::zero() })
+            })
+            .collect()
+    }
+
+    fn with_variables(&self, variables: &[Variable]) -> Self {
+        let mut output = self.clone();
+        for (k, v) in variables.iter().enumerate() {
+            let bit = AllocatedBit::from_variable(*v, self.bits[k].get_value());
+            output.bits[k] = bit.into();
+            output.variables[k] = *v;
         }
-        ret_vec
+
+        output
+    }
+
+    /// The bits packed by `multipack::pack_bits`, in the order of the bits
+    /// of the digest.
+    fn to_field_elements<CS: ConstraintSystem<F>>(
+        &self,
+        cs: CS,
+    ) -> Result<Vec<AllocatedFr<F>>, SynthesisError> {
+        pack_bits(cs, &self.bits)
+    }
+
+    /// The bits are the ones of the digest, no constraint is needed.
+    fn to_bytes<CS: ConstraintSystem<F>>(&self, _: CS) -> Result<Vec<UInt8>, SynthesisError> {
+        Ok(bits_to_bytes_be(&self.bits))
+    }
+
+    fn native_field_elements(value: &Vec<u8>) -> Vec<F> {
+        compute_multipacking(&bytes_to_bits_be(value))
+    }
+
+    fn native_bytes(value: &Vec<u8>) -> Vec<u8> {
+        value.clone()
     }
 }
 
-// implement AbstractHash.
+/// The `sha256` of the concatenated bits of the nodes, the `MergeSha256` of
+/// their bytes.
 pub struct AbstractHashSha256<F>(core::marker::PhantomData<F>);
 
 impl<F: PrimeField> AbstractHash<F> for AbstractHashSha256<F> {
@@ -337,30 +534,28 @@ impl<F: PrimeField> AbstractHash<F> for AbstractHashSha256<F> {
         mut cs: CS,
         params: &[&Self::Output],
     ) -> Result<Self::Output, SynthesisError> {
-        let mut inputs = Vec::new();
-        for o in params.iter() {
-            if let Some(l) = o.get_value() {
-                inputs.extend(&mut l.iter().map(|i| i.clone()));
-            } else {
-                return Err(SynthesisError::AssignmentMissing);
+        let inputs = params
+            .iter()
+            .flat_map(|o| o.bits.iter().cloned())
+            .collect::<Vec<_>>();
+
+        let bits = sha256(cs.ns(|| "sha256"), &inputs)?;
+
+        // the output bits are the variables of the digest, the ones which
+        // are not allocated are enforced equal to a new bit.
+        let mut variables = Vec::with_capacity(bits.len());
+        for (i, bit) in bits.iter().enumerate() {
+            match bit {
+                Boolean::Is(b) => variables.push(b.get_variable()),
+                _ => {
+                    let a = AllocatedBit::alloc(cs.ns(|| format!("bit_{}", i)), bit.get_value())?;
+                    Boolean::enforce_equal(cs.ns(|| format!("bit_{} equal", i)), &a.into(), bit)?;
+                    variables.push(a.get_variable());
+                }
             }
         }
 
-        let booleans = sha256(cs.ns(|| "sha256"), &inputs)?;
-
-        let var: Vec<Variable> = booleans
-            .iter()
-            .enumerate()
-            .map(|(i, b)| {
-                let a = AllocatedBit::alloc(cs.ns(|| format!("bit_{}", i)), b.get_value()).unwrap(); // TODO
-                a.get_variable()
-            })
-            .collect();
-
-        Ok(AbstractHashSha256Output {
-            value: Some(booleans),
-            variables: var,
-        })
+        Ok(AbstractHashSha256Output { bits, variables })
     }
 }
 
@@ -372,8 +567,8 @@ mod test {
     use scheme::r1cs::ConstraintSystem;
 
     use super::super::boolean::AllocatedBit;
-    use super::super::test_constraint_system::TestConstraintSystem;
     use super::*;
+    use crate::test_cs::TestConstraintSystem;
 
     #[test]
     fn test_blank_hash() {
@@ -476,4 +671,236 @@ mod test {
             }
         }
     }
+
+    /// The values of the digest bits of the gadget, as bytes.
+    fn digest_value(bits: &[Boolean]) -> Vec<u8> {
+        bits.chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .fold(0u8, |acc, b| (acc << 1) | b.get_value().unwrap() as u8)
+            })
+            .collect()
+    }
+
+    fn alloc_bits<F: PrimeField, CS: ConstraintSystem<F>>(
+        mut cs: CS,
+        bits: &[bool],
+    ) -> Vec<Boolean> {
+        bits.iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.ns(|| format!("input bit {}", i)), Some(*b))
+                    .unwrap()
+                    .into()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nist_vectors() {
+        let abc56 = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let vectors: Vec<(Vec<u8>, Vec<u8>)> = vec![
+            (
+                vec![],
+                hex_literal::hex!(
+                    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+                )
+                .to_vec(),
+            ),
+            (
+                b"abc".to_vec(),
+                hex_literal::hex!(
+                    "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+                )
+                .to_vec(),
+            ),
+            (
+                abc56.to_vec(),
+                hex_literal::hex!(
+                    "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+                )
+                .to_vec(),
+            ),
+            (abc56[..55].to_vec(), hash(&abc56[..55])),
+            (
+                [&abc56[..], b"abcdefgh"].concat(),
+                hash(&[&abc56[..], b"abcdefgh"].concat()),
+            ),
+            (vec![b'a'; 1000], hash(&[b'a'; 1000])),
+        ];
+
+        for (message, digest) in vectors.iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = alloc_bits(cs.ns(|| "input"), &bytes_to_bits_be(message));
+            let bits = sha256(cs.ns(|| "sha256"), &input).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(&digest_value(&bits), digest);
+        }
+    }
+
+    #[test]
+    fn test_streaming() {
+        let rng = &mut test_rng();
+
+        // the lengths of the parts, not aligned to the bytes or the blocks.
+        for parts in [vec![0], vec![1, 7, 100], vec![447, 1], vec![511, 2, 600]].iter() {
+            let len = parts.iter().sum::<usize>();
+            let message: Vec<bool> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = alloc_bits(cs.ns(|| "input"), &message);
+            let expected = sha256(cs.ns(|| "sha256"), &input).unwrap();
+            let num_constraints = cs.num_constraints() - len;
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = alloc_bits(cs.ns(|| "input"), &message);
+            let bits = {
+                let mut cs = MultiEq::new(&mut cs);
+                let mut gadget = Sha256Gadget::new();
+                let mut start = 0;
+                for (i, part) in parts.iter().enumerate() {
+                    gadget
+                        .update_batched(
+                            cs.ns(|| format!("part {}", i)),
+                            &input[start..start + part],
+                        )
+                        .unwrap();
+                    start += part;
+                }
+                gadget.finalize_batched(cs.ns(|| "finalize")).unwrap()
+            };
+
+            assert!(cs.is_satisfied());
+            assert_eq!(digest_value(&bits), digest_value(&expected));
+            assert_eq!(cs.num_constraints() - len, num_constraints);
+
+            // the same digest when every call batches its own additions.
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = alloc_bits(cs.ns(|| "input"), &message);
+            let mut gadget = Sha256Gadget::new();
+            let mut start = 0;
+            for (i, part) in parts.iter().enumerate() {
+                gadget
+                    .update(cs.ns(|| format!("part {}", i)), &input[start..start + part])
+                    .unwrap();
+                start += part;
+            }
+            let bits = gadget.finalize(cs.ns(|| "finalize")).unwrap();
+
+            assert!(cs.is_satisfied());
+            assert_eq!(digest_value(&bits), digest_value(&expected));
+            assert!(cs.num_constraints() - len >= num_constraints);
+        }
+    }
+
+    /// The number of constraints of the compressions of the blocks of
+    /// `message` in one `MultiEq` or in one each, and the digest.
+    #[cfg(feature = "bw6_761")]
+    fn compress_blocks<F: PrimeField>(message: &[bool], batched: bool) -> (usize, Vec<u8>) {
+        let mut cs = TestConstraintSystem::<F>::new();
+        let input = alloc_bits(cs.ns(|| "input"), message);
+        let mut state = get_sha256_iv();
+        {
+            let mut shared = MultiEq::new(&mut cs);
+            for (i, block) in input.chunks(512).enumerate() {
+                let cs = shared.ns(|| format!("block {}", i));
+                state = if batched {
+                    sha256_compression_function_batched(cs, block, &state).unwrap()
+                } else {
+                    sha256_compression_function(cs, block, &state).unwrap()
+                };
+            }
+        }
+        assert!(cs.is_satisfied());
+
+        let bits = state
+            .into_iter()
+            .flat_map(|e| e.into_bits_be())
+            .collect::<Vec<_>>();
+        (cs.num_constraints(), digest_value(&bits))
+    }
+
+    // the additions are of 33 to 35 bits, so a constraint of bn_256 always
+    // packs 7 of them and a block ends with a full one, the batching across
+    // blocks gains nothing there. The 376 bits of the capacity of bw6_761
+    // pack 10 or 11 of them, so the last constraint of a block may take the
+    // first additions of the next one.
+    #[cfg(feature = "bw6_761")]
+    #[test]
+    fn test_batching_across_blocks() {
+        use curve::bw6_761::Fr;
+
+        let rng = &mut test_rng();
+        let message: Vec<bool> = (0..512 * 4).map(|_| rng.gen()).collect();
+
+        let (batched, digest) = compress_blocks::<Fr>(&message, true);
+        let (unbatched, expected) = compress_blocks::<Fr>(&message, false);
+        assert_eq!(digest, expected);
+        assert!(batched < unbatched);
+        assert_eq!(unbatched - batched, 1);
+    }
+
+    #[test]
+    fn test_abstract_hash_sha256() {
+        let rng = &mut test_rng();
+
+        for &arity in [2, 3].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let nodes = (0..arity)
+                .map(|_| rng.gen::<[u8; 32]>().to_vec())
+                .collect::<Vec<_>>();
+            let outputs = nodes
+                .iter()
+                .enumerate()
+                .map(|(i, n)| {
+                    AbstractHashSha256Output::alloc(
+                        cs.ns(|| format!("node {}", i)),
+                        Some(n.clone()),
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(outputs[0].get_value(), Some(nodes[0].clone()));
+
+            let digest = AbstractHashSha256::<Fr>::hash_enforce(
+                cs.ns(|| "hash"),
+                &outputs.iter().collect::<Vec<_>>(),
+            )
+            .unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(digest.get_value(), Some(MergeSha256::merge_many(&nodes)));
+            assert_eq!(AbstractHashOutput::<Fr>::get_variables(&digest).len(), 256);
+
+            // the digest as bytes and as the two elements of its packing.
+            let value = digest.get_value().unwrap();
+            let bytes = AbstractHashOutput::<Fr>::to_bytes(&digest, cs.ns(|| "bytes")).unwrap();
+            let elements = digest.to_field_elements(cs.ns(|| "elements")).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(
+                bytes
+                    .iter()
+                    .map(|b| b.get_value())
+                    .collect::<Option<Vec<_>>>(),
+                Some(<AbstractHashSha256Output as AbstractHashOutput<Fr>>::native_bytes(&value))
+            );
+            assert_eq!(
+                elements
+                    .iter()
+                    .map(|e| e.get_value())
+                    .collect::<Option<Vec<_>>>(),
+                Some(AbstractHashSha256Output::native_field_elements(&value))
+            );
+            assert_eq!(elements.len(), 2);
+        }
+
+        // two nodes merge as the concatenation of their bytes.
+        let (left, right) = (vec![1u8; 32], vec![2u8; 32]);
+        assert_eq!(
+            MergeSha256::merge(&left, &right),
+            hash(&[left.clone(), right].concat())
+        );
+        assert_eq!(hash(b"abc")[..4], [0xba, 0x78, 0x16, 0xbf]);
+    }
 }
@@ -0,0 +1,352 @@
+//! Conditional swaps of pairs of values, the comparators of sorting
+//! networks and the ordering of the children of merkle trees.
+//!
+//! A pair `(a, b)` swapped on `cond` is enforced by
+//! `cond * (b - a) = a' - a` and `a + b = a' + b'`: the first makes `a'`
+//! one of the two, the second makes `b'` the other one.
+
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::abstract_hash::AbstractHashOutput;
+use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+
+/// Allocates `(b, a)` if `cond` is true, and `(a, b)` otherwise, of the
+/// linear combinations `a` and `b`, in 2 constraints.
+fn swap_lc<F, CS>(
+    mut cs: CS,
+    cond: &Boolean,
+    a: (LinearCombination<F>, Option<F>),
+    b: (LinearCombination<F>, Option<F>),
+) -> Result<(Variable, Variable), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    let (a, a_value) = a;
+    let (b, b_value) = b;
+    let values = match (cond.get_value(), a_value, b_value) {
+        (Some(false), Some(a), Some(b)) => Some((a, b)),
+        (Some(true), Some(a), Some(b)) => Some((b, a)),
+        _ => None,
+    };
+
+    let first = cs.alloc(
+        || "first",
+        || values.map(|v| v.0).ok_or(SynthesisError::AssignmentMissing),
+    )?;
+    let second = cs.alloc(
+        || "second",
+        || values.map(|v| v.1).ok_or(SynthesisError::AssignmentMissing),
+    )?;
+
+    cs.enforce(
+        || "cond * (b - a) = first - a",
+        |_| cond.lc(CS::one(), F::one()),
+        |_| b.clone() - &a,
+        |lc| lc + first - &a,
+    );
+    cs.enforce(
+        || "a + b = first + second",
+        |_| a + &b,
+        |lc| lc + CS::one(),
+        |lc| lc + first + second,
+    );
+
+    Ok((first, second))
+}
+
+/// Returns `(b, a)` if `cond` is true, and `(a, b)` otherwise, in 2
+/// constraints, none if `cond` is constant.
+pub fn conditionally_swap<F, CS>(
+    cs: CS,
+    cond: &Boolean,
+    a: &AllocatedFr<F>,
+    b: &AllocatedFr<F>,
+) -> Result<(AllocatedFr<F>, AllocatedFr<F>), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    match *cond {
+        Boolean::Constant(false) => return Ok((a.clone(), b.clone())),
+        Boolean::Constant(true) => return Ok((b.clone(), a.clone())),
+        _ => {}
+    }
+
+    let (first, second) = swap_lc(
+        cs,
+        cond,
+        (LinearCombination::zero() + a.get_variable(), a.get_value()),
+        (LinearCombination::zero() + b.get_variable(), b.get_value()),
+    )?;
+
+    let (first_value, second_value) = match cond.get_value() {
+        Some(true) => (b.get_value(), a.get_value()),
+        Some(false) => (a.get_value(), b.get_value()),
+        None => (None, None),
+    };
+
+    Ok((
+        AllocatedFr::from_variable(first, first_value),
+        AllocatedFr::from_variable(second, second_value),
+    ))
+}
+
+/// Returns `(b, a)` if `cond` is true, and `(a, b)` otherwise, of bit
+/// strings of the same length, in 2 constraints a bit. The swapped bits are
+/// ones of the inputs, so they need no boolean constraints.
+pub fn conditionally_swap_bits<F, CS>(
+    mut cs: CS,
+    cond: &Boolean,
+    a: &[Boolean],
+    b: &[Boolean],
+) -> Result<(Vec<Boolean>, Vec<Boolean>), SynthesisError>
+where
+    F: PrimeField,
+    CS: ConstraintSystem<F>,
+{
+    assert_eq!(a.len(), b.len());
+
+    match *cond {
+        Boolean::Constant(false) => return Ok((a.to_vec(), b.to_vec())),
+        Boolean::Constant(true) => return Ok((b.to_vec(), a.to_vec())),
+        _ => {}
+    }
+
+    let mut first = Vec::with_capacity(a.len());
+    let mut second = Vec::with_capacity(b.len());
+    for (i, (a, b)) in a.iter().zip(b.iter()).enumerate() {
+        let value = |bit: &Boolean| {
+            bit.get_value()
+                .map(|v| if v { F::one() } else { F::zero() })
+        };
+        let (f, s) = swap_lc(
+            cs.ns(|| format!("bit {}", i)),
+            cond,
+            (a.lc(CS::one(), F::one()), value(a)),
+            (b.lc(CS::one(), F::one()), value(b)),
+        )?;
+
+        let (f_value, s_value) = match cond.get_value() {
+            Some(true) => (b.get_value(), a.get_value()),
+            Some(false) => (a.get_value(), b.get_value()),
+            None => (None, None),
+        };
+        first.push(Boolean::Is(AllocatedBit::from_variable(f, f_value)));
+        second.push(Boolean::Is(AllocatedBit::from_variable(s, s_value)));
+    }
+
+    Ok((first, second))
+}
+
+/// Returns `(b, a)` if `cond` is true, and `(a, b)` otherwise, of hash
+/// outputs of the same width, e.g. the children of a level of a merkle
+/// tree, in 2 constraints a variable.
+pub fn conditionally_swap_outputs<F, O, CS>(
+    mut cs: CS,
+    cond: &Boolean,
+    a: &O,
+    b: &O,
+) -> Result<(O, O), SynthesisError>
+where
+    F: PrimeField,
+    O: AbstractHashOutput<F>,
+    CS: ConstraintSystem<F>,
+{
+    match *cond {
+        Boolean::Constant(false) => return Ok((a.clone(), b.clone())),
+        Boolean::Constant(true) => return Ok((b.clone(), a.clone())),
+        _ => {}
+    }
+
+    let (a_variables, a_values) = (a.get_variables(), a.get_variable_values());
+    let (b_variables, b_values) = (b.get_variables(), b.get_variable_values());
+    assert_eq!(a_variables.len(), b_variables.len());
+
+    let mut first = Vec::with_capacity(a_variables.len());
+    let mut second = Vec::with_capacity(b_variables.len());
+    for (i, (a, b)) in a_variables.iter().zip(b_variables.iter()).enumerate() {
+        let (f, s) = swap_lc(
+            cs.ns(|| format!("variable {}", i)),
+            cond,
+            (LinearCombination::zero() + *a, a_values[i]),
+            (LinearCombination::zero() + *b, b_values[i]),
+        )?;
+        first.push(f);
+        second.push(s);
+    }
+
+    let (first_output, second_output) = match cond.get_value() {
+        Some(true) => (b, a),
+        _ => (a, b),
+    };
+
+    Ok((
+        first_output.with_variables(&first),
+        second_output.with_variables(&second),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::cmp::is_less_than;
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_conditionally_swap() {
+        let rng = &mut test_rng();
+
+        for &cond in [false, true].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let (a, b) = (rng.gen::<Fr>(), rng.gen::<Fr>());
+            let a_var = AllocatedFr::alloc(cs.ns(|| "a"), || Ok(a)).unwrap();
+            let b_var = AllocatedFr::alloc(cs.ns(|| "b"), || Ok(b)).unwrap();
+            let c = Boolean::from(AllocatedBit::alloc(cs.ns(|| "cond"), Some(cond)).unwrap());
+
+            let (x, y) = conditionally_swap(cs.ns(|| "swap"), &c, &a_var, &b_var).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 1 + 2);
+            let expected = if cond { (b, a) } else { (a, b) };
+            assert_eq!(
+                (x.get_value(), y.get_value()),
+                (Some(expected.0), Some(expected.1))
+            );
+            assert_eq!(cs.get("swap/first"), expected.0);
+
+            // the outputs are bound to the inputs and the condition.
+            cs.set("swap/first", expected.1);
+            cs.set("swap/second", expected.0);
+            assert!(!cs.is_satisfied());
+
+            // a constant condition costs nothing.
+            let (x, y) = conditionally_swap(
+                cs.ns(|| "constant"),
+                &Boolean::constant(cond),
+                &a_var,
+                &b_var,
+            )
+            .unwrap();
+            assert_eq!(cs.num_constraints(), 1 + 2);
+            assert_eq!(
+                (x.get_value(), y.get_value()),
+                (Some(expected.0), Some(expected.1))
+            );
+        }
+    }
+
+    #[test]
+    fn test_conditionally_swap_bits() {
+        let rng = &mut test_rng();
+
+        for &cond in [false, true].iter() {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let a_bits: Vec<bool> = (0..8).map(|_| rng.gen()).collect();
+            let b_bits: Vec<bool> = (0..8).map(|_| rng.gen()).collect();
+            let alloc = |cs: &mut TestConstraintSystem<Fr>, name: &str, bits: &[bool]| {
+                bits.iter()
+                    .enumerate()
+                    .map(|(i, b)| {
+                        // a constant and a negated bit among them.
+                        match i {
+                            0 => Boolean::constant(*b),
+                            1 => Boolean::from(
+                                AllocatedBit::alloc(cs.ns(|| format!("{} {}", name, i)), Some(!b))
+                                    .unwrap(),
+                            )
+                            .not(),
+                            _ => Boolean::from(
+                                AllocatedBit::alloc(cs.ns(|| format!("{} {}", name, i)), Some(*b))
+                                    .unwrap(),
+                            ),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let a = alloc(&mut cs, "a", &a_bits);
+            let b = alloc(&mut cs, "b", &b_bits);
+            let c = Boolean::from(AllocatedBit::alloc(cs.ns(|| "cond"), Some(cond)).unwrap());
+            let before = cs.num_constraints();
+
+            let (x, y) = conditionally_swap_bits(cs.ns(|| "swap"), &c, &a, &b).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints() - before, 2 * 8);
+
+            let expected = if cond {
+                (&b_bits, &a_bits)
+            } else {
+                (&a_bits, &b_bits)
+            };
+            let values = |bits: &[Boolean]| bits.iter().map(|b| b.get_value().unwrap()).collect();
+            assert_eq!(&values(&x) as &Vec<bool>, expected.0);
+            assert_eq!(&values(&y) as &Vec<bool>, expected.1);
+        }
+    }
+
+    /// The optimal sorting network of 4 inputs: 5 comparators, each a
+    /// comparison and a swap of the pair into order.
+    fn sort4<CS: ConstraintSystem<Fr>>(
+        mut cs: CS,
+        inputs: &[AllocatedFr<Fr>],
+    ) -> Vec<AllocatedFr<Fr>> {
+        let mut wires = inputs.to_vec();
+        for (k, &(i, j)) in [(0, 1), (2, 3), (0, 2), (1, 3), (1, 2)].iter().enumerate() {
+            let mut cs = cs.ns(|| format!("comparator {}", k));
+            let out_of_order =
+                is_less_than(cs.ns(|| "less than"), &wires[j], &wires[i], 32).unwrap();
+            let (low, high) =
+                conditionally_swap(cs.ns(|| "swap"), &out_of_order, &wires[i], &wires[j]).unwrap();
+            wires[i] = low;
+            wires[j] = high;
+        }
+        wires
+    }
+
+    #[test]
+    fn test_sorting_network() {
+        let rng = &mut test_rng();
+
+        for _ in 0..10 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let values: Vec<u32> = (0..4).map(|_| rng.gen::<u32>() % 8).collect();
+            let inputs = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    AllocatedFr::alloc(cs.ns(|| format!("input {}", i)), || Ok(Fr::from(*v)))
+                        .unwrap()
+                })
+                .collect::<Vec<_>>();
+
+            let outputs = sort4(cs.ns(|| "sort"), &inputs);
+            assert!(cs.is_satisfied());
+
+            // the outputs are a permutation of the inputs, in order.
+            let mut sorted = values.clone();
+            sorted.sort();
+            let sorted = sorted.into_iter().map(Fr::from).collect::<Vec<_>>();
+            let outputs = outputs
+                .iter()
+                .map(|o| o.get_value().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(outputs, sorted);
+
+            // the last comparator cannot leave its pair out of order.
+            let (low, high) = (outputs[1], outputs[2]);
+            if low != high {
+                cs.set("sort/comparator 4/swap/first", high);
+                cs.set("sort/comparator 4/swap/second", low);
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+}
@@ -0,0 +1,509 @@
+use math::PrimeField;
+use scheme::r1cs::{ConstraintSystem, LinearCombination, SynthesisError, Variable};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+use super::fr::AllocatedFr;
+use super::uint64::UInt64;
+
+/// Represents an interpretation of 128 `Boolean` objects as an
+/// unsigned integer.
+///
+/// The arithmetic packs the operands into one field element, so the field
+/// must be larger than 129 bits.
+#[derive(Clone)]
+pub struct UInt128 {
+    // Least significant bit first
+    bits: Vec<Boolean>,
+    value: Option<u128>,
+}
+
+impl UInt128 {
+    /// Construct a constant `UInt128` from a `u128`
+    pub fn constant(value: u128) -> Self {
+        let bits = (0..128)
+            .map(|i| Boolean::constant((value >> i) & 1 == 1))
+            .collect();
+
+        UInt128 {
+            bits,
+            value: Some(value),
+        }
+    }
+
+    /// Allocate a `UInt128` in the constraint system
+    pub fn alloc<F, CS>(mut cs: CS, value: Option<u128>) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let bits = (0..128)
+            .map(|i| {
+                Ok(Boolean::from(AllocatedBit::alloc(
+                    cs.ns(|| format!("allocated bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )?))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        Ok(UInt128 { bits, value })
+    }
+
+    pub fn get_value(&self) -> Option<u128> {
+        self.value
+    }
+
+    fn is_constant(&self) -> bool {
+        self.bits.iter().all(|b| b.is_constant())
+    }
+
+    /// Turns this `UInt128` into its little-endian bits.
+    pub fn into_bits(self) -> Vec<Boolean> {
+        self.bits
+    }
+
+    /// Converts 128 little-endian bits into a `UInt128`.
+    pub fn from_bits(bits: &[Boolean]) -> Self {
+        assert_eq!(bits.len(), 128);
+
+        let value = bits.iter().rev().try_fold(0u128, |v, b| {
+            b.get_value().map(|b| if b { (v << 1) | 1 } else { v << 1 })
+        });
+
+        UInt128 {
+            bits: bits.to_vec(),
+            value,
+        }
+    }
+
+    /// Joins the low and the high 64 bits, this adds no constraints.
+    pub fn from_uint64s(low: &UInt64, high: &UInt64) -> Self {
+        let bits = low
+            .clone()
+            .into_bits()
+            .into_iter()
+            .chain(high.clone().into_bits())
+            .collect::<Vec<_>>();
+
+        Self::from_bits(&bits)
+    }
+
+    /// Splits this `UInt128` into its low and its high 64 bits.
+    pub fn into_uint64s(self) -> (UInt64, UInt64) {
+        let (low, high) = self.bits.split_at(64);
+
+        (UInt64::from_bits(low), UInt64::from_bits(high))
+    }
+
+    /// The linear combination packing the bits.
+    fn lc<F: PrimeField>(&self, one: Variable) -> LinearCombination<F> {
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in &self.bits {
+            lc = lc + &bit.lc(one, coeff);
+            coeff.double_in_place();
+        }
+
+        lc
+    }
+
+    /// Allocates the 129 bits of `value` and `top`, enforced to pack into
+    /// `lc`, and returns the low 128 bits along with the top one.
+    fn alloc_packing<F, CS>(
+        mut cs: CS,
+        lc: LinearCombination<F>,
+        value: Option<u128>,
+        top: Option<bool>,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 129);
+
+        let result = Self::alloc(cs.ns(|| "result"), value)?;
+        let top = Boolean::from(AllocatedBit::alloc(cs.ns(|| "top bit"), top)?);
+
+        let mut two_128 = F::one();
+        for _ in 0..128 {
+            two_128.double_in_place();
+        }
+        let result_lc = result.lc(CS::one()) + &top.lc(CS::one(), two_128);
+
+        cs.enforce(|| "packing", |_| lc, |lc| lc + CS::one(), |_| result_lc);
+
+        Ok((result, top))
+    }
+
+    /// Adds `other`, returning the sum modulo 2^128 and whether the
+    /// addition overflowed.
+    pub fn overflowing_add<F, CS>(
+        &self,
+        cs: CS,
+        other: &Self,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a.overflowing_add(b)),
+            _ => None,
+        };
+
+        if self.is_constant() && other.is_constant() {
+            let (sum, carry) = value.unwrap();
+            return Ok((Self::constant(sum), Boolean::constant(carry)));
+        }
+
+        let lc = self.lc(CS::one()) + &other.lc(CS::one());
+
+        Self::alloc_packing(cs, lc, value.map(|v| v.0), value.map(|v| v.1))
+    }
+
+    /// Adds `other` modulo 2^128.
+    pub fn wrapping_add<F, CS>(&self, cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_add(cs, other)?.0)
+    }
+
+    /// Subtracts `other`, returning the difference modulo 2^128 and whether
+    /// the subtraction underflowed, i.e. whether `self < other`.
+    pub fn overflowing_sub<F, CS>(
+        &self,
+        cs: CS,
+        other: &Self,
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        let value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a.overflowing_sub(b)),
+            _ => None,
+        };
+
+        if self.is_constant() && other.is_constant() {
+            let (difference, borrow) = value.unwrap();
+            return Ok((Self::constant(difference), Boolean::constant(borrow)));
+        }
+
+        // self + 2^128 - other takes 129 bits, whose top one is set
+        // exactly when there is no borrow.
+        let mut two_128 = F::one();
+        for _ in 0..128 {
+            two_128.double_in_place();
+        }
+        let lc = self.lc(CS::one()) - &other.lc(CS::one()) + (two_128, CS::one());
+
+        let (difference, no_borrow) =
+            Self::alloc_packing(cs, lc, value.map(|v| v.0), value.map(|v| !v.1))?;
+
+        Ok((difference, no_borrow.not()))
+    }
+
+    /// Subtracts `other` modulo 2^128.
+    pub fn wrapping_sub<F, CS>(&self, cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_sub(cs, other)?.0)
+    }
+
+    /// Whether `self < c`.
+    pub fn is_lt_constant<F, CS>(&self, cs: CS, c: u128) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.overflowing_sub(cs, &Self::constant(c))?.1)
+    }
+
+    /// Whether `self >= c`.
+    pub fn is_ge_constant<F, CS>(&self, cs: CS, c: u128) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.is_lt_constant(cs, c)?.not())
+    }
+
+    /// Whether `self > c`.
+    pub fn is_gt_constant<F, CS>(&self, cs: CS, c: u128) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(Self::constant(c).overflowing_sub(cs, self)?.1)
+    }
+
+    /// Whether `self <= c`.
+    pub fn is_le_constant<F, CS>(&self, cs: CS, c: u128) -> Result<Boolean, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        Ok(self.is_gt_constant(cs, c)?.not())
+    }
+
+    /// Allocates the field element packing the bits. As 2^128 is below the
+    /// modulus, the packing is canonical.
+    pub fn to_field_element<F, CS>(&self, mut cs: CS) -> Result<AllocatedFr<F>, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 128);
+
+        let num = AllocatedFr::alloc(cs.ns(|| "num"), || {
+            self.value
+                .map(F::from)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let lc = self.lc(CS::one());
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + num.get_variable(),
+        );
+
+        Ok(num)
+    }
+
+    /// Deconstructs `num` into 128 bits enforced to pack into it, which
+    /// proves `num < 2^128`. As 2^128 is below the modulus, the bits are the
+    /// only ones packing into `num`.
+    pub fn from_field_element<F, CS>(
+        mut cs: CS,
+        num: &AllocatedFr<F>,
+    ) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        assert!(F::size_in_bits() > 128);
+
+        let value = num.get_value().map(|v| {
+            let repr = v.into_repr();
+            let limbs = repr.as_ref();
+            u128::from(limbs[0]) | (u128::from(limbs[1]) << 64)
+        });
+
+        let result = Self::alloc(cs.ns(|| "bits"), value)?;
+
+        let lc = result.lc(CS::one());
+        cs.enforce(
+            || "packing",
+            |_| lc,
+            |lc| lc + CS::one(),
+            |lc| lc + num.get_variable(),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use num_traits::{One, Zero};
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::super::fr::AllocatedFr;
+    use super::super::uint64::UInt64;
+    use super::UInt128;
+    use crate::test_cs::TestConstraintSystem;
+
+    /// Random values along with the ones around the carry boundaries.
+    fn test_values<R: Rng>(rng: &mut R) -> Vec<u128> {
+        let mut values = vec![
+            0,
+            1,
+            u64::max_value() as u128,
+            u64::max_value() as u128 + 1,
+            u128::max_value() >> 1,
+            (u128::max_value() >> 1) + 1,
+            u128::max_value() - 1,
+            u128::max_value(),
+        ];
+        values.extend((0..8).map(|_| rng.gen::<u128>()));
+        values
+    }
+
+    #[test]
+    fn test_uint128_add() {
+        let rng = &mut test_rng();
+        let values = test_values(rng);
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_int = UInt128::alloc(cs.ns(|| "a"), Some(*a)).unwrap();
+                let b_int = UInt128::alloc(cs.ns(|| "b"), Some(*b)).unwrap();
+                let c_int = UInt128::constant(*b);
+
+                let (r, carry) = a_int.overflowing_add(cs.ns(|| "add"), &b_int).unwrap();
+                let w = a_int
+                    .wrapping_add(cs.ns(|| "add constant"), &c_int)
+                    .unwrap();
+                assert!(cs.is_satisfied());
+
+                let (expected, overflow) = a.overflowing_add(*b);
+                assert_eq!(r.get_value(), Some(expected));
+                assert_eq!(
+                    UInt128::from_bits(&r.into_bits()).get_value(),
+                    Some(expected)
+                );
+                assert_eq!(carry.get_value(), Some(overflow));
+                assert_eq!(w.get_value(), Some(expected));
+                assert_eq!(
+                    UInt128::from_bits(&w.into_bits()).get_value(),
+                    Some(expected)
+                );
+
+                // Flip a bit and see if the addition constraint still works
+                if cs.get("add/result/allocated bit 0/boolean").is_zero() {
+                    cs.set("add/result/allocated bit 0/boolean", Fr::one());
+                } else {
+                    cs.set("add/result/allocated bit 0/boolean", Fr::zero());
+                }
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint128_sub() {
+        let rng = &mut test_rng();
+        let values = test_values(rng);
+
+        for a in values.iter() {
+            for b in values.iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_int = UInt128::alloc(cs.ns(|| "a"), Some(*a)).unwrap();
+                let b_int = UInt128::alloc(cs.ns(|| "b"), Some(*b)).unwrap();
+
+                let (r, borrow) = a_int.overflowing_sub(cs.ns(|| "sub"), &b_int).unwrap();
+                let w = UInt128::constant(*a)
+                    .wrapping_sub(cs.ns(|| "sub from constant"), &b_int)
+                    .unwrap();
+                assert!(cs.is_satisfied());
+
+                let (expected, underflow) = a.overflowing_sub(*b);
+                assert_eq!(r.get_value(), Some(expected));
+                assert_eq!(
+                    UInt128::from_bits(&r.into_bits()).get_value(),
+                    Some(expected)
+                );
+                assert_eq!(borrow.get_value(), Some(underflow));
+                assert_eq!(w.get_value(), Some(expected));
+
+                // Flip the borrow and see if the subtraction constraint still works
+                if cs.get("sub/top bit/boolean").is_zero() {
+                    cs.set("sub/top bit/boolean", Fr::one());
+                } else {
+                    cs.set("sub/top bit/boolean", Fr::zero());
+                }
+                assert!(!cs.is_satisfied());
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint128_constants() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let a = UInt128::constant(u128::max_value());
+        let b = UInt128::constant(2);
+        let (sum, carry) = a.overflowing_add(cs.ns(|| "add"), &b).unwrap();
+        let (difference, borrow) = b.overflowing_sub(cs.ns(|| "sub"), &a).unwrap();
+
+        assert_eq!(cs.num_constraints(), 0);
+        assert_eq!(sum.get_value(), Some(1));
+        assert_eq!(carry.get_value(), Some(true));
+        assert_eq!(difference.get_value(), Some(3));
+        assert_eq!(borrow.get_value(), Some(true));
+    }
+
+    #[test]
+    fn test_uint128_compare_constant() {
+        let rng = &mut test_rng();
+        let values = test_values(rng);
+
+        for a in values.iter() {
+            for c in values.iter() {
+                let mut cs = TestConstraintSystem::<Fr>::new();
+
+                let a_int = UInt128::alloc(cs.ns(|| "a"), Some(*a)).unwrap();
+
+                let lt = a_int.is_lt_constant(cs.ns(|| "lt"), *c).unwrap();
+                let le = a_int.is_le_constant(cs.ns(|| "le"), *c).unwrap();
+                let gt = a_int.is_gt_constant(cs.ns(|| "gt"), *c).unwrap();
+                let ge = a_int.is_ge_constant(cs.ns(|| "ge"), *c).unwrap();
+                assert!(cs.is_satisfied());
+
+                assert_eq!(lt.get_value(), Some(a < c));
+                assert_eq!(le.get_value(), Some(a <= c));
+                assert_eq!(gt.get_value(), Some(a > c));
+                assert_eq!(ge.get_value(), Some(a >= c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint128_uint64s() {
+        let rng = &mut test_rng();
+
+        for v in test_values(rng) {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let low = UInt64::alloc(cs.ns(|| "low"), Some(v as u64)).unwrap();
+            let high = UInt64::alloc(cs.ns(|| "high"), Some((v >> 64) as u64)).unwrap();
+
+            let r = UInt128::from_uint64s(&low, &high);
+            assert_eq!(r.get_value(), Some(v));
+
+            let (low, high) = r.into_uint64s();
+            assert_eq!(low.get_value(), Some(v as u64));
+            assert_eq!(high.get_value(), Some((v >> 64) as u64));
+            assert_eq!(cs.num_constraints(), 128);
+        }
+    }
+
+    #[test]
+    fn test_uint128_field_element() {
+        let rng = &mut test_rng();
+
+        for v in test_values(rng) {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let a = UInt128::alloc(cs.ns(|| "a"), Some(v)).unwrap();
+            let num = a.to_field_element(cs.ns(|| "to field")).unwrap();
+            assert_eq!(num.get_value(), Some(Fr::from(v)));
+
+            let b = UInt128::from_field_element(cs.ns(|| "from field"), &num).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(b.get_value(), Some(v));
+        }
+
+        // 2^128 does not fit.
+        let mut cs = TestConstraintSystem::<Fr>::new();
+        let num = AllocatedFr::alloc(cs.ns(|| "num"), || {
+            Ok(Fr::from(u128::max_value()) + &Fr::one())
+        })
+        .unwrap();
+        UInt128::from_field_element(cs.ns(|| "from field"), &num).unwrap();
+        assert!(!cs.is_satisfied());
+    }
+}
@@ -154,6 +154,10 @@ impl UInt32 {
         }
     }
 
+    pub fn rotl(&self, by: usize) -> Self {
+        self.rotr((32 - by % 32) % 32)
+    }
+
     pub fn shr(&self, by: usize) -> Self {
         let by = by % 32;
 
@@ -174,6 +178,35 @@ impl UInt32 {
         }
     }
 
+    /// Arithmetic shift right, the sign bit fills the vacated bits.
+    pub fn sar(&self, by: usize) -> Self {
+        let by = by % 32;
+
+        let fill = self.bits[31];
+
+        let new_bits = self
+            .bits
+            .iter()
+            .skip(by)
+            .chain(Some(&fill).into_iter().cycle())
+            .take(32)
+            .cloned()
+            .collect();
+
+        UInt32 {
+            bits: new_bits,
+            value: self.value.map(|v| ((v as i32) >> by) as u32),
+        }
+    }
+
+    /// Bitwise NOT of this `UInt32`, free of constraints.
+    pub fn not(&self) -> Self {
+        UInt32 {
+            bits: self.bits.iter().map(|b| b.not()).collect(),
+            value: self.value.map(|v| !v),
+        }
+    }
+
     fn triop<F, CS, FN, U>(
         mut cs: CS,
         a: &Self,
@@ -267,8 +300,206 @@ impl UInt32 {
         })
     }
 
+    /// OR of two `UInt32` objects, one constraint per bit which is not
+    /// constant.
+    pub fn or<F, CS>(mut cs: CS, a: &Self, b: &Self) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let new_value = match (a.value, b.value) {
+            (Some(a), Some(b)) => Some(a | b),
+            _ => None,
+        };
+
+        let bits = a
+            .bits
+            .iter()
+            .zip(b.bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| Boolean::kary_or(cs.ns(|| format!("or of bit {}", i)), &[*a, *b]))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UInt32 {
+            bits,
+            value: new_value,
+        })
+    }
+
     /// Perform modular addition of several `UInt32` objects.
-    pub fn addmany<F, CS, M>(mut cs: M, operands: &[Self]) -> Result<Self, SynthesisError>
+    pub fn addmany<F, CS, M>(cs: M, operands: &[Self]) -> Result<Self, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        Self::addmany_with_high_bits(cs, operands).map(|(result, _)| result)
+    }
+
+    /// Perform addition of several `UInt32` objects, returning the sum
+    /// modulo 2^32 and whether it overflowed. With two operands, this is
+    /// the carry out of the full adder.
+    pub fn add_with_carry<F, CS, M>(
+        mut cs: M,
+        operands: &[Self],
+    ) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        let (result, high_bits) = Self::addmany_with_high_bits(cs.ns(|| "sum"), operands)?;
+        let carry = Boolean::kary_or(cs.ns(|| "carry"), &high_bits)?;
+
+        Ok((result, carry))
+    }
+
+    /// Perform the subtraction `a - b` modulo 2^32, returning the
+    /// difference and whether it borrowed, i.e. `a < b`.
+    pub fn sub<F, CS, M>(mut cs: M, a: &Self, b: &Self) -> Result<(Self, Boolean), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+        M: ConstraintSystem<F, Root = MultiEq<F, CS>>,
+    {
+        assert!(F::size_in_bits() >= 64);
+
+        // a + 2^32 - b is in [1, 2^33), its bit 32 is set unless
+        // a < b.
+        let result_value = match (a.value, b.value) {
+            (Some(a), Some(b)) => Some(u64::from(a) + (1 << 32) - u64::from(b)),
+            _ => None,
+        };
+
+        let mut lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for (a, b) in a.bits.iter().zip(b.bits.iter()) {
+            lc = lc + &a.lc(CS::one(), coeff) - &b.lc(CS::one(), coeff);
+            coeff.double_in_place();
+        }
+        lc += (coeff, CS::one());
+
+        if a.is_constant() && b.is_constant() {
+            let v = result_value.unwrap();
+            return Ok((UInt32::constant(v as u32), Boolean::constant(v >> 32 == 0)));
+        }
+
+        let mut result_bits = Self::alloc_result_bits(&mut cs, result_value, 33)?;
+
+        let mut result_lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in &result_bits {
+            result_lc = result_lc + &bit.lc(CS::one(), coeff);
+            coeff.double_in_place();
+        }
+
+        cs.get_root().enforce_equal(33, &lc, &result_lc);
+
+        let borrow = result_bits.pop().unwrap().not();
+
+        Ok((
+            UInt32 {
+                bits: result_bits,
+                value: result_value.map(|v| v as u32),
+            },
+            borrow,
+        ))
+    }
+
+    /// Perform the full multiplication of two `UInt32` objects, returning
+    /// the low and the high 32 bits of the 64 bits product.
+    pub fn mul_lo_hi<F, CS>(mut cs: CS, a: &Self, b: &Self) -> Result<(Self, Self), SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        // The product is less than 2^64, it must not wrap around in the
+        // scalar field.
+        assert!(F::size_in_bits() > 64);
+
+        let result_value = match (a.value, b.value) {
+            (Some(a), Some(b)) => Some(u64::from(a) * u64::from(b)),
+            _ => None,
+        };
+
+        if a.is_constant() && b.is_constant() {
+            let v = result_value.unwrap();
+            return Ok((
+                UInt32::constant(v as u32),
+                UInt32::constant((v >> 32) as u32),
+            ));
+        }
+
+        let mut result_bits = Self::alloc_result_bits(&mut cs, result_value, 64)?;
+
+        let mut coeff = F::one();
+        let mut a_lc = LinearCombination::zero();
+        let mut b_lc = LinearCombination::zero();
+        for (a, b) in a.bits.iter().zip(b.bits.iter()) {
+            a_lc = a_lc + &a.lc(CS::one(), coeff);
+            b_lc = b_lc + &b.lc(CS::one(), coeff);
+            coeff.double_in_place();
+        }
+
+        let mut result_lc = LinearCombination::zero();
+        let mut coeff = F::one();
+        for bit in &result_bits {
+            result_lc = result_lc + &bit.lc(CS::one(), coeff);
+            coeff.double_in_place();
+        }
+
+        cs.enforce(
+            || "product",
+            |lc| lc + &a_lc,
+            |lc| lc + &b_lc,
+            |lc| lc + &result_lc,
+        );
+
+        let hi_bits = result_bits.split_off(32);
+
+        Ok((
+            UInt32 {
+                bits: result_bits,
+                value: result_value.map(|v| v as u32),
+            },
+            UInt32 {
+                bits: hi_bits,
+                value: result_value.map(|v| (v >> 32) as u32),
+            },
+        ))
+    }
+
+    fn is_constant(&self) -> bool {
+        self.bits.iter().all(|b| b.is_constant())
+    }
+
+    /// Allocate the `num_bits` bits of the result `value`.
+    fn alloc_result_bits<F, CS>(
+        mut cs: CS,
+        value: Option<u64>,
+        num_bits: usize,
+    ) -> Result<Vec<Boolean>, SynthesisError>
+    where
+        F: PrimeField,
+        CS: ConstraintSystem<F>,
+    {
+        (0..num_bits)
+            .map(|i| {
+                AllocatedBit::alloc(
+                    cs.ns(|| format!("result bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )
+                .map(Boolean::from)
+            })
+            .collect()
+    }
+
+    /// Perform addition of several `UInt32` objects, returning the sum
+    /// modulo 2^32 and the carry bits above it.
+    fn addmany_with_high_bits<F, CS, M>(
+        mut cs: M,
+        operands: &[Self],
+    ) -> Result<(Self, Vec<Boolean>), SynthesisError>
     where
         F: PrimeField,
         CS: ConstraintSystem<F>,
@@ -282,7 +513,8 @@ impl UInt32 {
 
         // Compute the maximum value of the sum so we allocate enough bits for
         // the result
-        let mut max_value = (operands.len() as u64) * (u64::from(u32::max_value()));
+        let max_value = (operands.len() as u64) * (u64::from(u32::max_value()));
+        let num_bits = 64 - max_value.leading_zeros() as usize;
 
         // Keep track of the resulting value
         let mut result_value = Some(0u64);
@@ -322,50 +554,41 @@ impl UInt32 {
         // The value of the actual result is modulo 2^32
         let modular_value = result_value.map(|v| v as u32);
 
-        if all_constants && modular_value.is_some() {
+        if let (true, Some(v)) = (all_constants, result_value) {
             // We can just return a constant, rather than
             // unpacking the result into allocated bits.
+            let high_bits = (32..num_bits)
+                .map(|i| Boolean::constant((v >> i) & 1 == 1))
+                .collect();
 
-            return Ok(UInt32::constant(modular_value.unwrap()));
+            return Ok((UInt32::constant(v as u32), high_bits));
         }
 
-        // Storage area for the resulting bits
-        let mut result_bits = vec![];
+        // Allocate each bit of the result
+        let mut result_bits = Self::alloc_result_bits(&mut cs, result_value, num_bits)?;
 
         // Linear combination representing the output,
         // for comparison with the sum of the operands
         let mut result_lc = LinearCombination::zero();
-
-        // Allocate each bit of the result
         let mut coeff = F::one();
-        let mut i = 0;
-        while max_value != 0 {
-            // Allocate the bit
-            let b = AllocatedBit::alloc(
-                cs.ns(|| format!("result bit {}", i)),
-                result_value.map(|v| (v >> i) & 1 == 1),
-            )?;
-
-            // Add this bit to the result combination
-            result_lc = result_lc + (coeff, b.get_variable());
-
-            result_bits.push(b.into());
-
-            max_value >>= 1;
-            i += 1;
+        for bit in &result_bits {
+            result_lc = result_lc + &bit.lc(CS::one(), coeff);
             coeff.double_in_place();
         }
 
         // Enforce equality between the sum and result
-        cs.get_root().enforce_equal(i, &lc, &result_lc);
-
-        // Discard carry bits that we don't care about
-        result_bits.truncate(32);
-
-        Ok(UInt32 {
-            bits: result_bits,
-            value: modular_value,
-        })
+        cs.get_root().enforce_equal(num_bits, &lc, &result_lc);
+
+        // Split off the carry bits above the modular result
+        let high_bits = result_bits.split_off(32);
+
+        Ok((
+            UInt32 {
+                bits: result_bits,
+                value: modular_value,
+            },
+            high_bits,
+        ))
     }
 }
 
@@ -379,8 +602,8 @@ mod test {
 
     use super::super::boolean::Boolean;
     use super::super::multieq::MultiEq;
-    use super::super::test_constraint_system::TestConstraintSystem;
     use super::UInt32;
+    use crate::test_cs::TestConstraintSystem;
 
     #[test]
     fn test_uint32_from_bits_be() {
@@ -712,4 +935,267 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_uint32_rotl() {
+        let rng = &mut test_rng();
+
+        for _ in 0..50 {
+            let num = rng.next_u32();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let a = UInt32::alloc(cs.ns(|| "a"), Some(num)).unwrap();
+            let num_constraints = cs.num_constraints();
+
+            for i in 0..64 {
+                let b = a.rotl(i);
+                assert_eq!(b.bits.len(), 32);
+                assert_eq!(b.value, Some(num.rotate_left(i as u32)));
+                for (j, bit) in b.bits.iter().enumerate() {
+                    assert_eq!(bit.get_value(), Some((b.value.unwrap() >> j) & 1 == 1));
+                }
+            }
+
+            assert_eq!(cs.num_constraints(), num_constraints);
+        }
+    }
+
+    #[test]
+    fn test_uint32_sar() {
+        let rng = &mut test_rng();
+
+        for _ in 0..50 {
+            for i in 0..60 {
+                let num = rng.next_u32();
+                let expected = (num as i32).wrapping_shr(i as u32) as u32;
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let a = UInt32::alloc(cs.ns(|| "a"), Some(num)).unwrap();
+                let num_constraints = cs.num_constraints();
+
+                let b = a.sar(i);
+                assert_eq!(cs.num_constraints(), num_constraints);
+                assert_eq!(b.value, Some(expected));
+                assert_eq!(b.bits.len(), 32);
+                for (j, bit) in b.bits.iter().enumerate() {
+                    assert_eq!(bit.get_value(), Some((expected >> j) & 1 == 1));
+                }
+
+                assert_eq!(UInt32::constant(num).sar(i).value, Some(expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint32_not() {
+        let rng = &mut test_rng();
+
+        for _ in 0..50 {
+            let num = rng.next_u32();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let a = UInt32::alloc(cs.ns(|| "a"), Some(num)).unwrap();
+            let num_constraints = cs.num_constraints();
+
+            let b = a.not();
+            assert_eq!(cs.num_constraints(), num_constraints);
+            assert_eq!(b.value, Some(!num));
+            for (j, bit) in b.bits.iter().enumerate() {
+                assert_eq!(bit.get_value(), Some((!num >> j) & 1 == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint32_or() {
+        let rng = &mut test_rng();
+
+        for _ in 0..1000 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let a = rng.next_u32();
+            let b = rng.next_u32();
+            let c = rng.next_u32();
+
+            let expected = (!a | b) | c;
+
+            let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+            let b_bit = UInt32::alloc(cs.ns(|| "b_bit"), Some(b)).unwrap();
+            let c_bit = UInt32::constant(c);
+            let num_constraints = cs.num_constraints();
+
+            let r = UInt32::or(cs.ns(|| "first or"), &a_bit.not(), &b_bit).unwrap();
+            assert_eq!(cs.num_constraints(), num_constraints + 32);
+
+            // or with a constant costs nothing
+            let r = UInt32::or(cs.ns(|| "second or"), &r, &c_bit).unwrap();
+            assert_eq!(cs.num_constraints(), num_constraints + 32);
+
+            assert!(cs.is_satisfied());
+            assert_eq!(r.value, Some(expected));
+            for (j, bit) in r.bits.iter().enumerate() {
+                assert_eq!(bit.get_value(), Some((expected >> j) & 1 == 1));
+            }
+        }
+    }
+
+    const BOUNDARY_VALUES: [u32; 7] = [
+        0,
+        1,
+        2,
+        1 << 31,
+        (1 << 31) - 1,
+        u32::max_value() - 1,
+        u32::max_value(),
+    ];
+
+    fn test_values() -> Vec<u32> {
+        let rng = &mut test_rng();
+
+        let mut values = BOUNDARY_VALUES.to_vec();
+        values.extend((0..8).map(|_| rng.next_u32()));
+        values
+    }
+
+    #[test]
+    fn test_uint32_add_with_carry() {
+        let values = test_values();
+
+        for &a in &values {
+            for &b in &values {
+                let (expected, overflow) = a.overflowing_add(b);
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+                let b_bit = UInt32::alloc(cs.ns(|| "b_bit"), Some(b)).unwrap();
+                let (r, carry) = {
+                    let mut cs = MultiEq::new(&mut cs);
+                    UInt32::add_with_carry(cs.ns(|| "addition"), &[a_bit, b_bit]).unwrap()
+                };
+
+                assert!(cs.is_satisfied());
+                assert_eq!(r.value, Some(expected));
+                assert_eq!(r.bits.len(), 32);
+                assert_eq!(carry.get_value(), Some(overflow));
+
+                // The carry is the bit 32 of the sum
+                let path = "addition/sum/result bit 32/boolean";
+                let flipped = if overflow { Fr::zero() } else { Fr::one() };
+                cs.set(path, flipped);
+                assert!(!cs.is_satisfied());
+
+                let (r, carry) = {
+                    let mut cs = MultiEq::new(&mut cs);
+                    UInt32::add_with_carry(
+                        cs.ns(|| "constant addition"),
+                        &[UInt32::constant(a), UInt32::constant(b)],
+                    )
+                    .unwrap()
+                };
+                assert_eq!(r.value, Some(expected));
+                assert!(carry.is_constant());
+                assert_eq!(carry.get_value(), Some(overflow));
+            }
+        }
+
+        // With more operands, the carry is whether the sum overflowed.
+        for &(a, b, c) in &[
+            (1, 2, 3),
+            (u32::max_value(), 0, 1),
+            (1 << 31, 1 << 30, 1 << 30),
+        ] {
+            let expected = u64::from(a) + u64::from(b) + u64::from(c);
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let operands = [a, b, c]
+                .iter()
+                .enumerate()
+                .map(|(i, v)| UInt32::alloc(cs.ns(|| format!("operand {}", i)), Some(*v)).unwrap())
+                .collect::<Vec<_>>();
+            let (r, carry) = {
+                let mut cs = MultiEq::new(&mut cs);
+                UInt32::add_with_carry(cs.ns(|| "addition"), &operands).unwrap()
+            };
+
+            assert!(cs.is_satisfied());
+            assert_eq!(r.value, Some(expected as u32));
+            assert_eq!(carry.get_value(), Some(expected >> 32 != 0));
+        }
+    }
+
+    #[test]
+    fn test_uint32_sub() {
+        let values = test_values();
+
+        for &a in &values {
+            for &b in &values {
+                let (expected, borrow) = a.overflowing_sub(b);
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+                let b_bit = UInt32::alloc(cs.ns(|| "b_bit"), Some(b)).unwrap();
+                let (r, r_borrow) = {
+                    let mut cs = MultiEq::new(&mut cs);
+                    UInt32::sub(cs.ns(|| "subtraction"), &a_bit, &b_bit).unwrap()
+                };
+
+                assert!(cs.is_satisfied());
+                assert_eq!(r.value, Some(expected));
+                assert_eq!(r.bits.len(), 32);
+                assert_eq!(r_borrow.get_value(), Some(borrow));
+                for (i, bit) in r.bits.iter().enumerate() {
+                    assert_eq!(bit.get_value(), Some((expected >> i) & 1 == 1));
+                }
+
+                let path = "subtraction/result bit 32/boolean";
+                let flipped = if borrow { Fr::one() } else { Fr::zero() };
+                cs.set(path, flipped);
+                assert!(!cs.is_satisfied());
+
+                // a constant subtrahend
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+                let (r, r_borrow) = {
+                    let mut cs = MultiEq::new(&mut cs);
+                    UInt32::sub(cs.ns(|| "subtraction"), &a_bit, &UInt32::constant(b)).unwrap()
+                };
+                assert!(cs.is_satisfied());
+                assert_eq!(r.value, Some(expected));
+                assert_eq!(r_borrow.get_value(), Some(borrow));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uint32_mul_lo_hi() {
+        let values = test_values();
+
+        for &a in &values {
+            for &b in &values {
+                let expected = u64::from(a) * u64::from(b);
+
+                let mut cs = TestConstraintSystem::<Fr>::new();
+                let a_bit = UInt32::alloc(cs.ns(|| "a_bit"), Some(a)).unwrap();
+                let b_bit = UInt32::alloc(cs.ns(|| "b_bit"), Some(b)).unwrap();
+                let (lo, hi) = UInt32::mul_lo_hi(cs.ns(|| "product"), &a_bit, &b_bit).unwrap();
+
+                assert!(cs.is_satisfied());
+                assert_eq!(cs.num_constraints(), 32 + 32 + 64 + 1);
+                assert_eq!(lo.value, Some(expected as u32));
+                assert_eq!(hi.value, Some((expected >> 32) as u32));
+                for (i, bit) in lo.bits.iter().chain(hi.bits.iter()).enumerate() {
+                    assert_eq!(bit.get_value(), Some((expected >> i) & 1 == 1));
+                }
+
+                let (lo, hi) = UInt32::mul_lo_hi(
+                    cs.ns(|| "constant product"),
+                    &UInt32::constant(a),
+                    &UInt32::constant(b),
+                )
+                .unwrap();
+                assert_eq!(lo.value, Some(expected as u32));
+                assert_eq!(hi.value, Some((expected >> 32) as u32));
+            }
+        }
+    }
 }
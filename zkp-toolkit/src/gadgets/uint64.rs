@@ -0,0 +1,104 @@
+use math::Field;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+
+/// Represents an interpretation of 64 `Boolean` objects as an
+/// unsigned integer.
+#[derive(Clone)]
+pub struct UInt64 {
+    // Least significant bit first
+    bits: Vec<Boolean>,
+    value: Option<u64>,
+}
+
+impl UInt64 {
+    /// Construct a constant `UInt64` from a `u64`
+    pub fn constant(value: u64) -> Self {
+        let bits = (0..64)
+            .map(|i| Boolean::constant((value >> i) & 1 == 1))
+            .collect();
+
+        UInt64 {
+            bits,
+            value: Some(value),
+        }
+    }
+
+    /// Allocate a `UInt64` in the constraint system
+    pub fn alloc<F, CS>(mut cs: CS, value: Option<u64>) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let bits = (0..64)
+            .map(|i| {
+                Ok(Boolean::from(AllocatedBit::alloc(
+                    cs.ns(|| format!("allocated bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )?))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        Ok(UInt64 { bits, value })
+    }
+
+    pub fn get_value(&self) -> Option<u64> {
+        self.value
+    }
+
+    /// Turns this `UInt64` into its little-endian bits.
+    pub fn into_bits(self) -> Vec<Boolean> {
+        self.bits
+    }
+
+    /// Converts 64 little-endian bits into a `UInt64`.
+    pub fn from_bits(bits: &[Boolean]) -> Self {
+        assert_eq!(bits.len(), 64);
+
+        let value = bits.iter().rev().try_fold(0u64, |v, b| {
+            b.get_value().map(|b| if b { (v << 1) | 1 } else { v << 1 })
+        });
+
+        UInt64 {
+            bits: bits.to_vec(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::UInt64;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_uint64_alloc_from_bits() {
+        let rng = &mut test_rng();
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let v: u64 = rng.gen();
+            let a = UInt64::alloc(cs.ns(|| "a"), Some(v)).unwrap();
+            assert!(cs.is_satisfied());
+            assert_eq!(cs.num_constraints(), 64);
+
+            for (i, bit) in a.clone().into_bits().iter().enumerate() {
+                assert_eq!(bit.get_value(), Some((v >> i) & 1 == 1));
+            }
+            assert_eq!(UInt64::from_bits(&a.into_bits()).get_value(), Some(v));
+            assert_eq!(
+                UInt64::from_bits(&UInt64::constant(v).into_bits()).get_value(),
+                Some(v)
+            );
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use math::Field;
+use scheme::r1cs::{ConstraintSystem, SynthesisError};
+
+use crate::Vec;
+
+use super::boolean::{AllocatedBit, Boolean};
+
+/// Represents an interpretation of 8 `Boolean` objects as an
+/// unsigned integer.
+#[derive(Clone)]
+pub struct UInt8 {
+    // Least significant bit first
+    bits: Vec<Boolean>,
+    value: Option<u8>,
+}
+
+impl UInt8 {
+    /// Construct a constant `UInt8` from a `u8`
+    pub fn constant(value: u8) -> Self {
+        let bits = (0..8)
+            .map(|i| Boolean::constant((value >> i) & 1 == 1))
+            .collect();
+
+        UInt8 {
+            bits,
+            value: Some(value),
+        }
+    }
+
+    /// Construct a constant vector of `UInt8` from a byte slice
+    pub fn constant_vec(values: &[u8]) -> Vec<Self> {
+        values.iter().map(|v| Self::constant(*v)).collect()
+    }
+
+    /// Allocate a `UInt8` in the constraint system
+    pub fn alloc<F, CS>(mut cs: CS, value: Option<u8>) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let bits = (0..8)
+            .map(|i| {
+                Ok(Boolean::from(AllocatedBit::alloc(
+                    cs.ns(|| format!("allocated bit {}", i)),
+                    value.map(|v| (v >> i) & 1 == 1),
+                )?))
+            })
+            .collect::<Result<Vec<_>, SynthesisError>>()?;
+
+        Ok(UInt8 { bits, value })
+    }
+
+    /// Allocate a vector of `UInt8` in the constraint system, from the bytes
+    /// of the witness, or from `None`s when they are unknown.
+    pub fn alloc_vec<F, CS, T>(mut cs: CS, values: &[T]) -> Result<Vec<Self>, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+        T: Into<Option<u8>> + Copy,
+    {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| Self::alloc(cs.ns(|| format!("byte {}", i)), (*v).into()))
+            .collect()
+    }
+
+    pub fn get_value(&self) -> Option<u8> {
+        self.value
+    }
+
+    /// Turns this `UInt8` into its little-endian bits.
+    pub fn into_bits_le(self) -> Vec<Boolean> {
+        self.bits
+    }
+
+    /// Converts 8 little-endian bits into a `UInt8`.
+    pub fn from_bits_le(bits: &[Boolean]) -> Self {
+        assert_eq!(bits.len(), 8);
+
+        let value = bits.iter().rev().try_fold(0u8, |v, b| {
+            b.get_value().map(|b| if b { (v << 1) | 1 } else { v << 1 })
+        });
+
+        UInt8 {
+            bits: bits.to_vec(),
+            value,
+        }
+    }
+
+    /// XOR this `UInt8` with another `UInt8`
+    pub fn xor<F, CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let new_value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a ^ b),
+            _ => None,
+        };
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| Boolean::xor(cs.ns(|| format!("xor of bit {}", i)), a, b))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UInt8 {
+            bits,
+            value: new_value,
+        })
+    }
+
+    /// AND this `UInt8` with another `UInt8`
+    pub fn and<F, CS>(&self, mut cs: CS, other: &Self) -> Result<Self, SynthesisError>
+    where
+        F: Field,
+        CS: ConstraintSystem<F>,
+    {
+        let new_value = match (self.value, other.value) {
+            (Some(a), Some(b)) => Some(a & b),
+            _ => None,
+        };
+
+        let bits = self
+            .bits
+            .iter()
+            .zip(other.bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| Boolean::and(cs.ns(|| format!("and of bit {}", i)), a, b))
+            .collect::<Result<_, _>>()?;
+
+        Ok(UInt8 {
+            bits,
+            value: new_value,
+        })
+    }
+}
+
+/// The bits of the bytes in byte order, each byte least significant bit
+/// first, as the blake2s gadget takes them.
+pub fn bytes_to_bits_le(bytes: &[UInt8]) -> Vec<Boolean> {
+    bytes.iter().flat_map(|b| b.bits.iter().cloned()).collect()
+}
+
+/// The bits of the bytes in byte order, each byte most significant bit
+/// first, as the sha256 gadget takes them.
+pub fn bytes_to_bits_be(bytes: &[UInt8]) -> Vec<Boolean> {
+    bytes
+        .iter()
+        .flat_map(|b| b.bits.iter().rev().cloned())
+        .collect()
+}
+
+/// The inverse of `bytes_to_bits_le`, e.g. for the output of blake2s.
+pub fn bits_to_bytes_le(bits: &[Boolean]) -> Vec<UInt8> {
+    assert_eq!(bits.len() % 8, 0);
+
+    bits.chunks(8).map(UInt8::from_bits_le).collect()
+}
+
+/// The inverse of `bytes_to_bits_be`, e.g. for the output of sha256.
+pub fn bits_to_bytes_be(bits: &[Boolean]) -> Vec<UInt8> {
+    assert_eq!(bits.len() % 8, 0);
+
+    bits.chunks(8)
+        .map(|chunk| {
+            let le = chunk.iter().rev().cloned().collect::<Vec<_>>();
+            UInt8::from_bits_le(&le)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use curve::bn_256::Fr;
+    use math::test_rng;
+    use rand::prelude::*;
+    use scheme::r1cs::ConstraintSystem;
+
+    use super::*;
+    use crate::test_cs::TestConstraintSystem;
+
+    #[test]
+    fn test_uint8_alloc() {
+        let mut cs = TestConstraintSystem::<Fr>::new();
+
+        let bytes = UInt8::alloc_vec(cs.ns(|| "bytes"), &[0u8, 1, 0x80, 0xa5, 0xff]).unwrap();
+        assert!(cs.is_satisfied());
+        assert_eq!(cs.num_constraints(), 5 * 8);
+        for (b, v) in bytes.iter().zip([0u8, 1, 0x80, 0xa5, 0xff].iter()) {
+            assert_eq!(b.get_value(), Some(*v));
+            assert_eq!(
+                UInt8::from_bits_le(&b.clone().into_bits_le()).get_value(),
+                Some(*v)
+            );
+        }
+    }
+
+    #[test]
+    fn test_uint8_xor_and() {
+        let rng = &mut test_rng();
+
+        for _ in 0..100 {
+            let mut cs = TestConstraintSystem::<Fr>::new();
+
+            let (a, b, c): (u8, u8, u8) = (rng.gen(), rng.gen(), rng.gen());
+
+            let a_byte = UInt8::alloc(cs.ns(|| "a"), Some(a)).unwrap();
+            let b_byte = UInt8::constant(b);
+            let c_byte = UInt8::alloc(cs.ns(|| "c"), Some(c)).unwrap();
+
+            let r = a_byte.xor(cs.ns(|| "xor"), &b_byte).unwrap();
+            let r = r.and(cs.ns(|| "and"), &c_byte).unwrap();
+            assert!(cs.is_satisfied());
+
+            let expected = (a ^ b) & c;
+            assert_eq!(r.get_value(), Some(expected));
+            for (i, bit) in r.into_bits_le().iter().enumerate() {
+                assert_eq!(bit.get_value(), Some((expected >> i) & 1 == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bytes_bits_conversions() {
+        let bytes = UInt8::constant_vec(&[0x01, 0x80]);
+
+        let le = bytes_to_bits_le(&bytes);
+        assert_eq!(le[0].get_value(), Some(true));
+        assert_eq!(le[15].get_value(), Some(true));
+        assert_eq!(le.iter().filter(|b| b.get_value().unwrap()).count(), 2);
+
+        let be = bytes_to_bits_be(&bytes);
+        assert_eq!(be[7].get_value(), Some(true));
+        assert_eq!(be[8].get_value(), Some(true));
+        assert_eq!(be.iter().filter(|b| b.get_value().unwrap()).count(), 2);
+
+        let values = |bytes: Vec<UInt8>| bytes.iter().map(|b| b.get_value()).collect::<Vec<_>>();
+        assert_eq!(values(bits_to_bytes_le(&le)), [Some(0x01), Some(0x80)]);
+        assert_eq!(values(bits_to_bytes_be(&be)), [Some(0x01), Some(0x80)]);
+    }
+
+    #[test]
+    fn test_sha256_of_bytes() {
+        use super::super::sha256::sha256;
+        use sha2::{Digest, Sha256};
+
+        let rng = &mut test_rng();
+
+        for &len in [0usize, 3, 55, 56, 64, 100].iter() {
+            let preimage: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = UInt8::alloc_vec(cs.ns(|| "preimage"), &preimage).unwrap();
+            let hash = sha256(cs.ns(|| "sha256"), &bytes_to_bits_be(&input)).unwrap();
+            assert!(cs.is_satisfied());
+
+            let hash = bits_to_bytes_be(&hash)
+                .iter()
+                .map(|b| b.get_value().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(hash, Sha256::digest(&preimage).to_vec());
+        }
+    }
+
+    #[test]
+    fn test_blake2s_of_bytes() {
+        use super::super::blake2s::blake2s;
+        use blake2s_simd::Params as Blake2sParams;
+
+        let rng = &mut test_rng();
+
+        for &len in [0usize, 3, 64, 100].iter() {
+            let preimage: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut cs = TestConstraintSystem::<Fr>::new();
+            let input = UInt8::alloc_vec(cs.ns(|| "preimage"), &preimage).unwrap();
+            let hash =
+                blake2s(cs.ns(|| "blake2s"), &bytes_to_bits_le(&input), b"12345678").unwrap();
+            assert!(cs.is_satisfied());
+
+            let hash = bits_to_bytes_le(&hash)
+                .iter()
+                .map(|b| b.get_value().unwrap())
+                .collect::<Vec<_>>();
+            let expected = Blake2sParams::new()
+                .hash_length(32)
+                .personal(b"12345678")
+                .hash(&preimage);
+            assert_eq!(&hash[..], expected.as_bytes());
+        }
+    }
+}
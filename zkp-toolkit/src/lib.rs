@@ -5,25 +5,46 @@
 extern crate alloc;
 
 #[cfg(all(not(feature = "std"), feature = "gadgets"))]
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, sync::Arc, vec::Vec};
 
 #[cfg(all(feature = "std", feature = "gadgets"))]
-use std::{string::String, vec::Vec};
+use std::{string::String, sync::Arc, vec::Vec};
+
+#[cfg(all(not(feature = "std"), feature = "ecdsa"))]
+use alloc::rc::Rc;
+
+#[cfg(all(feature = "std", feature = "ecdsa"))]
+use std::rc::Rc;
 
 /// gadgets can used in circuits.
 #[cfg(feature = "gadgets")]
 pub mod gadgets;
 
+/// circuits built with the gadgets.
+#[cfg(feature = "gadgets")]
+pub mod circuits;
+
+/// a constraint system to debug circuits with.
+#[cfg(feature = "std")]
+pub mod test_cs;
+
 /// re-export math.
 pub use math;
 
 /// re-export scheme standard r1cs.
 pub use scheme::r1cs;
 
+/// re-export the fingerprints of verifying keys.
+pub use scheme::key_fingerprint;
+
 /// re-export groth16.
 #[cfg(feature = "groth16")]
 pub use scheme::groth16;
 
+/// re-export the inner product argument.
+#[cfg(feature = "ipa")]
+pub use scheme::ipa;
+
 /// re-export bulletproofs.
 #[cfg(feature = "bulletproofs")]
 pub use scheme::bulletproofs;
@@ -56,10 +77,26 @@ pub use curve::bn_256;
 #[cfg(feature = "bls12_381")]
 pub use curve::bls12_381;
 
+/// re-export bls12_377.
+#[cfg(feature = "bls12_377")]
+pub use curve::bls12_377;
+
+/// re-export bw6_761.
+#[cfg(feature = "bw6_761")]
+pub use curve::bw6_761;
+
 /// re-export jubjub.
 #[cfg(feature = "jubjub")]
 pub use curve::jubjub;
 
+/// re-export edwards_bls12.
+#[cfg(feature = "edwards_bls12")]
+pub use curve::edwards_bls12;
+
 /// re-export baby_jubjub.
 #[cfg(feature = "baby_jubjub")]
 pub use curve::baby_jubjub;
+
+/// re-export secp256k1.
+#[cfg(feature = "secp256k1")]
+pub use curve::secp256k1;
@@ -0,0 +1,919 @@
+//! A constraint system which keeps the assignments and the names of
+//! everything a circuit allocates and enforces, to test and debug circuits.
+//!
+//! Every variable, constraint and namespace is named by its path, the
+//! names of the namespaces down to it joined by `/`. When a proof of a
+//! circuit does not verify, synthesize the circuit with the same witness
+//! into a `TestConstraintSystem`: `which_is_unsatisfied` is the path of the
+//! first constraint which does not hold, `get` and `set` read and change
+//! the assignment of a variable by its path, and `verify` is whether a
+//! proof with these public inputs would verify.
+//!
+//! To find which gadgets a circuit spends its constraints on,
+//! `namespace_report` counts the constraints and the auxiliary variables
+//! under the namespaces down to a given depth.
+//!
+//! To audit a small circuit, `dump` lists its constraints as
+//! `path: (2*a + b) * (c) = (out)`, of the paths of the variables or of
+//! their values.
+use blake2::{Blake2s, Digest};
+use byteorder::{BigEndian, ByteOrder};
+use core::cmp::{self, Ordering};
+use math::PrimeField;
+use math::ToBytes;
+use scheme::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, SynthesisError, Variable,
+};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::io;
+
+#[derive(Debug)]
+enum NamedObject {
+    Constraint(usize),
+    Var(Variable),
+    Namespace,
+}
+
+/// Constraint system for testing purposes.
+pub struct TestConstraintSystem<F: PrimeField> {
+    named_objects: HashMap<String, NamedObject>,
+    current_namespace: Vec<String>,
+    constraints: Vec<(
+        LinearCombination<F>,
+        LinearCombination<F>,
+        LinearCombination<F>,
+        String,
+    )>,
+    inputs: Vec<(F, String)>,
+    aux: Vec<(F, String)>,
+}
+
+#[derive(Clone, Copy)]
+struct OrderedVariable(Variable);
+
+impl Eq for OrderedVariable {}
+impl PartialEq for OrderedVariable {
+    fn eq(&self, other: &OrderedVariable) -> bool {
+        match (self.0.get_unchecked(), other.0.get_unchecked()) {
+            (Index::Input(ref a), Index::Input(ref b)) => a == b,
+            (Index::Aux(ref a), Index::Aux(ref b)) => a == b,
+            _ => false,
+        }
+    }
+}
+impl PartialOrd for OrderedVariable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for OrderedVariable {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0.get_unchecked(), other.0.get_unchecked()) {
+            (Index::Input(ref a), Index::Input(ref b)) => a.cmp(b),
+            (Index::Aux(ref a), Index::Aux(ref b)) => a.cmp(b),
+            (Index::Input(_), Index::Aux(_)) => Ordering::Less,
+            (Index::Aux(_), Index::Input(_)) => Ordering::Greater,
+        }
+    }
+}
+
+fn proc_lc<F: PrimeField>(terms: &[(Variable, F)]) -> BTreeMap<OrderedVariable, F> {
+    let mut map = BTreeMap::new();
+    for &(var, coeff) in terms {
+        map.entry(OrderedVariable(var))
+            .or_insert_with(F::zero)
+            .add_assign(&coeff);
+    }
+
+    // Remove terms that have a zero coefficient to normalize
+    let mut to_remove = vec![];
+    for (var, coeff) in map.iter() {
+        if coeff.is_zero() {
+            to_remove.push(var.clone())
+        }
+    }
+
+    for var in to_remove {
+        map.remove(&var);
+    }
+
+    map
+}
+
+fn hash_lc<F: PrimeField>(terms: &[(Variable, F)], h: &mut Blake2s) {
+    let map = proc_lc::<F>(terms);
+
+    let mut buf = [0u8; 9 + 32];
+    BigEndian::write_u64(&mut buf[0..8], map.len() as u64);
+    h.input(&buf[0..8]);
+
+    for (var, coeff) in map {
+        match var.0.get_unchecked() {
+            Index::Input(i) => {
+                buf[0] = b'I';
+                BigEndian::write_u64(&mut buf[1..9], i as u64);
+            }
+            Index::Aux(i) => {
+                buf[0] = b'A';
+                BigEndian::write_u64(&mut buf[1..9], i as u64);
+            }
+        }
+
+        coeff.into_repr().write(&mut buf[9..]).unwrap();
+
+        h.input(&buf);
+    }
+}
+
+fn eval_lc<F: PrimeField>(
+    terms: &[(Variable, F)],
+    inputs: &[(F, String)],
+    aux: &[(F, String)],
+) -> F {
+    let mut acc = F::zero();
+
+    for &(var, ref coeff) in terms {
+        let mut tmp = match var.get_unchecked() {
+            Index::Input(index) => inputs[index].0,
+            Index::Aux(index) => aux[index].0,
+        };
+
+        tmp.mul_assign(coeff);
+        acc.add_assign(&tmp);
+    }
+
+    acc
+}
+
+/// What `dump` lists of the constraints.
+#[derive(Clone, Debug, Default)]
+pub struct DumpOptions {
+    /// Only the constraints under the namespace of this path, all of them
+    /// if empty.
+    pub namespace: String,
+    /// Whether to list the values of the variables for their paths.
+    pub values: bool,
+}
+
+/// An element of the field as a small signed number if it is one, as
+/// coefficients and values mostly are.
+fn signed<F: PrimeField>(x: &F) -> (bool, String) {
+    let small = |x: &F| {
+        let repr = x.into_repr();
+        if repr.as_ref()[1..].iter().all(|limb| *limb == 0) {
+            Some(repr.as_ref()[0].to_string())
+        } else {
+            None
+        }
+    };
+
+    match (small(x), small(&-*x)) {
+        (Some(x), _) => (false, x),
+        (None, Some(neg)) => (true, neg),
+        (None, None) => (false, x.to_string()),
+    }
+}
+
+/// The terms of a linear combination, of repeated variables merged, in the
+/// order the variables are allocated, the constant last.
+fn dump_lc<F: PrimeField>(lc: &LinearCombination<F>, var: &dyn Fn(Index) -> String) -> String {
+    let (constant, terms): (Vec<_>, Vec<_>) = proc_lc::<F>(lc.as_ref())
+        .into_iter()
+        .partition(|(v, _)| v.0.get_unchecked() == Index::Input(0));
+
+    let mut s = String::from("(");
+    for (i, (v, coeff)) in terms.iter().chain(&constant).enumerate() {
+        let (neg, coeff) = signed(coeff);
+        let term = if v.0.get_unchecked() == Index::Input(0) {
+            coeff
+        } else if coeff == "1" {
+            var(v.0.get_unchecked())
+        } else {
+            format!("{}*{}", coeff, var(v.0.get_unchecked()))
+        };
+
+        match (i, neg) {
+            (0, false) => s += &term,
+            (0, true) => write!(&mut s, "-{}", term).unwrap(),
+            (_, false) => write!(&mut s, " + {}", term).unwrap(),
+            (_, true) => write!(&mut s, " - {}", term).unwrap(),
+        }
+    }
+    if terms.is_empty() && constant.is_empty() {
+        s.push('0');
+    }
+    s.push(')');
+
+    s
+}
+
+fn write_dump<'a, F, W, I>(
+    w: &mut W,
+    constraints: I,
+    var: &dyn Fn(Index) -> String,
+    options: &DumpOptions,
+) -> io::Result<()>
+where
+    F: PrimeField,
+    W: io::Write,
+    I: Iterator<
+        Item = (
+            &'a LinearCombination<F>,
+            &'a LinearCombination<F>,
+            &'a LinearCombination<F>,
+            &'a str,
+        ),
+    >,
+{
+    let namespace = format!("{}/", options.namespace);
+    for (a, b, c, path) in constraints {
+        if !options.namespace.is_empty() && !path.starts_with(&namespace) {
+            continue;
+        }
+
+        writeln!(
+            w,
+            "{}: {} * {} = {}",
+            path,
+            dump_lc(a, var),
+            dump_lc(b, var),
+            dump_lc(c, var)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A constraint system that keeps the constraints and the paths of the
+/// variables, of their values if it can compute them.
+struct DumpConstraintSystem<F: PrimeField> {
+    current_namespace: Vec<String>,
+    constraints: Vec<(
+        LinearCombination<F>,
+        LinearCombination<F>,
+        LinearCombination<F>,
+        String,
+    )>,
+    inputs: Vec<(Option<F>, String)>,
+    aux: Vec<(Option<F>, String)>,
+}
+
+impl<F: PrimeField> ConstraintSystem<F> for DumpConstraintSystem<F> {
+    type Root = Self;
+
+    fn alloc<T, A, AR>(&mut self, annotation: A, f: T) -> Result<Variable, SynthesisError>
+    where
+        T: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let path = compute_path(&self.current_namespace, annotation().into());
+        self.aux.push((f().ok(), path));
+
+        Ok(Variable::new_unchecked(Index::Aux(self.aux.len() - 1)))
+    }
+
+    fn alloc_input<T, A, AR>(&mut self, annotation: A, f: T) -> Result<Variable, SynthesisError>
+    where
+        T: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let path = compute_path(&self.current_namespace, annotation().into());
+        self.inputs.push((f().ok(), path));
+
+        Ok(Variable::new_unchecked(Index::Input(self.inputs.len() - 1)))
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let path = compute_path(&self.current_namespace, annotation().into());
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+
+        self.constraints.push((a, b, c, path));
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.current_namespace.push(name_fn().into());
+    }
+
+    fn pop_namespace(&mut self) {
+        assert!(self.current_namespace.pop().is_some());
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux.len()
+    }
+}
+
+/// Synthesizes `circuit` to list its constraints as
+/// `TestConstraintSystem::dump` does, of a witness or of none, and of
+/// repeated names. The values a circuit cannot compute are listed as `?`.
+pub fn dump<F, C, W>(circuit: C, w: &mut W, options: &DumpOptions) -> Result<(), SynthesisError>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+    W: io::Write,
+{
+    let mut cs = DumpConstraintSystem {
+        current_namespace: vec![],
+        constraints: vec![],
+        inputs: vec![(Some(F::one()), "ONE".into())],
+        aux: vec![],
+    };
+    circuit.generate_constraints(&mut cs)?;
+
+    let var = |index: Index| {
+        let (value, path) = match index {
+            Index::Input(i) => &cs.inputs[i],
+            Index::Aux(i) => &cs.aux[i],
+        };
+        match value {
+            Some(value) if options.values => match signed(value) {
+                (false, value) => value,
+                (true, neg) => format!("-{}", neg),
+            },
+            None if options.values => "?".to_owned(),
+            _ => path.clone(),
+        }
+    };
+    let constraints = cs
+        .constraints
+        .iter()
+        .map(|(a, b, c, path)| (a, b, c, path.as_str()));
+    write_dump(w, constraints, &var, options)?;
+
+    Ok(())
+}
+
+impl<F: PrimeField> Default for TestConstraintSystem<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> TestConstraintSystem<F> {
+    pub fn new() -> TestConstraintSystem<F> {
+        let mut map = HashMap::new();
+        map.insert(
+            "ONE".into(),
+            NamedObject::Var(TestConstraintSystem::<F>::one()),
+        );
+
+        TestConstraintSystem {
+            named_objects: map,
+            current_namespace: vec![],
+            constraints: vec![],
+            inputs: vec![(F::one(), "ONE".into())],
+            aux: vec![],
+        }
+    }
+
+    pub fn pretty_print(&self) -> String {
+        let mut s = String::new();
+
+        let negone = -F::one();
+
+        let powers_of_two = (0..F::size_in_bits())
+            .map(|i| F::from(2u32).pow(&[i as u64]))
+            .collect::<Vec<_>>();
+
+        let pp = |s: &mut String, lc: &LinearCombination<F>| {
+            write!(s, "(").unwrap();
+            let mut is_first = true;
+            for (var, coeff) in proc_lc::<F>(lc.as_ref()) {
+                if coeff == negone {
+                    write!(s, " - ").unwrap();
+                } else if !is_first {
+                    write!(s, " + ").unwrap();
+                }
+                is_first = false;
+
+                if coeff != F::one() && coeff != negone {
+                    for (i, x) in powers_of_two.iter().enumerate() {
+                        if x == &coeff {
+                            write!(s, "2^{} . ", i).unwrap();
+                            break;
+                        }
+                    }
+
+                    write!(s, "{} . ", coeff).unwrap();
+                }
+
+                match var.0.get_unchecked() {
+                    Index::Input(i) => {
+                        write!(s, "`{}`", &self.inputs[i].1).unwrap();
+                    }
+                    Index::Aux(i) => {
+                        write!(s, "`{}`", &self.aux[i].1).unwrap();
+                    }
+                }
+            }
+            if is_first {
+                // Nothing was visited, print 0.
+                write!(s, "0").unwrap();
+            }
+            write!(s, ")").unwrap();
+        };
+
+        for &(ref a, ref b, ref c, ref name) in &self.constraints {
+            write!(&mut s, "\n").unwrap();
+
+            write!(&mut s, "{}: ", name).unwrap();
+            pp(&mut s, a);
+            write!(&mut s, " * ").unwrap();
+            pp(&mut s, b);
+            write!(&mut s, " = ").unwrap();
+            pp(&mut s, c);
+        }
+
+        write!(&mut s, "\n").unwrap();
+
+        s
+    }
+
+    /// Lists the constraints, one a line, as `path: (2*a + b) * (c) = (out)`
+    /// of the paths of the variables, or of their values with
+    /// `options.values`. The terms are in the order the variables are
+    /// allocated, the constant last, so the listing of a circuit is stable.
+    pub fn dump<W: io::Write>(&self, w: &mut W, options: &DumpOptions) -> io::Result<()> {
+        let var = |index: Index| {
+            let (value, path) = match index {
+                Index::Input(i) => &self.inputs[i],
+                Index::Aux(i) => &self.aux[i],
+            };
+            if options.values {
+                match signed(value) {
+                    (false, value) => value,
+                    (true, neg) => format!("-{}", neg),
+                }
+            } else {
+                path.clone()
+            }
+        };
+        let constraints = self
+            .constraints
+            .iter()
+            .map(|(a, b, c, path)| (a, b, c, path.as_str()));
+
+        write_dump(w, constraints, &var, options)
+    }
+
+    pub fn hash(&self) -> String {
+        let mut h = Blake2s::new();
+        {
+            let mut buf = [0u8; 24];
+
+            BigEndian::write_u64(&mut buf[0..8], self.inputs.len() as u64);
+            BigEndian::write_u64(&mut buf[8..16], self.aux.len() as u64);
+            BigEndian::write_u64(&mut buf[16..24], self.constraints.len() as u64);
+            h.input(&buf);
+        }
+
+        for constraint in &self.constraints {
+            hash_lc::<F>(constraint.0.as_ref(), &mut h);
+            hash_lc::<F>(constraint.1.as_ref(), &mut h);
+            hash_lc::<F>(constraint.2.as_ref(), &mut h);
+        }
+
+        let mut s = String::new();
+        for b in h.result().as_ref() {
+            s += &format!("{:02x}", b);
+        }
+
+        s
+    }
+
+    /// The path of the first constraint which does not hold.
+    pub fn which_is_unsatisfied(&self) -> Option<String> {
+        for &(ref a, ref b, ref c, ref path) in &self.constraints {
+            let mut a = eval_lc::<F>(a.as_ref(), &self.inputs, &self.aux);
+            let b = eval_lc::<F>(b.as_ref(), &self.inputs, &self.aux);
+            let c = eval_lc::<F>(c.as_ref(), &self.inputs, &self.aux);
+
+            a.mul_assign(&b);
+
+            if a != c {
+                return Some(path.clone());
+            }
+        }
+
+        None
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.which_is_unsatisfied().is_none()
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// The numbers of constraints and of auxiliary variables under every
+    /// namespace of at most `depth` names, the most constraints first. The
+    /// ones out of any namespace are under `""`.
+    pub fn namespace_report(&self, depth: usize) -> Vec<(String, usize, usize)> {
+        let mut counts = BTreeMap::<String, (usize, usize)>::new();
+        for (.., path) in self.constraints.iter() {
+            counts.entry(namespace_prefix(path, depth)).or_default().0 += 1;
+        }
+        for (_, path) in self.aux.iter() {
+            counts.entry(namespace_prefix(path, depth)).or_default().1 += 1;
+        }
+
+        let mut report = counts
+            .into_iter()
+            .map(|(namespace, (constraints, aux))| (namespace, constraints, aux))
+            .collect::<Vec<_>>();
+        // stable, the namespaces of as many constraints stay sorted by name.
+        report.sort_by_key(|r| cmp::Reverse(r.1));
+        report
+    }
+
+    /// The `namespace_report` as a table.
+    pub fn pretty_report(&self, depth: usize) -> String {
+        let mut s = String::new();
+
+        writeln!(&mut s, "{:>12} {:>12}  namespace", "constraints", "aux").unwrap();
+        for (namespace, constraints, aux) in self.namespace_report(depth) {
+            writeln!(&mut s, "{:>12} {:>12}  {}", constraints, aux, namespace).unwrap();
+        }
+
+        s
+    }
+
+    pub fn set(&mut self, path: &str, to: F) {
+        match self.named_objects.get(path) {
+            Some(&NamedObject::Var(ref v)) => match v.get_unchecked() {
+                Index::Input(index) => self.inputs[index].0 = to,
+                Index::Aux(index) => self.aux[index].0 = to,
+            },
+            Some(e) => panic!(
+                "tried to set path `{}` to value, but `{:?}` already exists there.",
+                path, e
+            ),
+            _ => panic!("no variable exists at path: {}", path),
+        }
+    }
+
+    /// Whether the constraints hold and the public inputs, but the first
+    /// one, are `expected`, as a proof of the circuit would verify.
+    pub fn verify(&self, expected: &[F]) -> bool {
+        if expected.len() + 1 != self.inputs.len() {
+            return false;
+        }
+
+        for (a, b) in self.inputs.iter().skip(1).zip(expected.iter()) {
+            if &a.0 != b {
+                return false;
+            }
+        }
+
+        self.is_satisfied()
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    pub fn get_input(&self, index: usize, path: &str) -> F {
+        let (assignment, name) = self.inputs[index].clone();
+
+        assert_eq!(path, name);
+
+        assignment
+    }
+
+    pub fn get(&self, path: &str) -> F {
+        match self.named_objects.get(path) {
+            Some(&NamedObject::Var(ref v)) => match v.get_unchecked() {
+                Index::Input(index) => self.inputs[index].0,
+                Index::Aux(index) => self.aux[index].0,
+            },
+            Some(e) => panic!(
+                "tried to get value of path `{}`, but `{:?}` exists there (not a variable)",
+                path, e
+            ),
+            _ => panic!("no variable exists at path: {}", path),
+        }
+    }
+
+    fn set_named_obj(&mut self, path: String, to: NamedObject) {
+        if self.named_objects.contains_key(&path) {
+            panic!("tried to create object at existing path: {}", path);
+        }
+
+        self.named_objects.insert(path, to);
+    }
+}
+
+/// The first `depth` namespaces of the path of an object.
+fn namespace_prefix(path: &str, depth: usize) -> String {
+    let names = path.split('/').collect::<Vec<_>>();
+    let namespaces = &names[..names.len() - 1];
+
+    namespaces[..cmp::min(depth, namespaces.len())].join("/")
+}
+
+fn compute_path(ns: &[String], this: String) -> String {
+    if this.chars().any(|a| a == '/') {
+        panic!("'/' is not allowed in names");
+    }
+
+    let mut name = String::new();
+
+    let mut needs_separation = false;
+    for ns in ns.iter().chain(Some(&this).into_iter()) {
+        if needs_separation {
+            name += "/";
+        }
+
+        name += ns;
+        needs_separation = true;
+    }
+
+    name
+}
+
+impl<F: PrimeField> ConstraintSystem<F> for TestConstraintSystem<F> {
+    type Root = Self;
+
+    fn alloc<T, A, AR>(&mut self, annotation: A, f: T) -> Result<Variable, SynthesisError>
+    where
+        T: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.aux.len();
+        let path = compute_path(&self.current_namespace, annotation().into());
+        let value = f().map_err(|e| e.in_context(path.clone()))?;
+        self.aux.push((value, path.clone()));
+        let var = Variable::new_unchecked(Index::Aux(index));
+        self.set_named_obj(path, NamedObject::Var(var));
+
+        Ok(var)
+    }
+
+    fn alloc_input<T, A, AR>(&mut self, annotation: A, f: T) -> Result<Variable, SynthesisError>
+    where
+        T: FnOnce() -> Result<F, SynthesisError>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let index = self.inputs.len();
+        let path = compute_path(&self.current_namespace, annotation().into());
+        let value = f().map_err(|e| e.in_context(path.clone()))?;
+        self.inputs.push((value, path.clone()));
+        let var = Variable::new_unchecked(Index::Input(index));
+        self.set_named_obj(path, NamedObject::Var(var));
+
+        Ok(var)
+    }
+
+    fn enforce<A, AR, LA, LB, LC>(&mut self, annotation: A, a: LA, b: LB, c: LC)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+        LA: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LB: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+        LC: FnOnce(LinearCombination<F>) -> LinearCombination<F>,
+    {
+        let path = compute_path(&self.current_namespace, annotation().into());
+        let index = self.constraints.len();
+        self.set_named_obj(path.clone(), NamedObject::Constraint(index));
+
+        let a = a(LinearCombination::zero());
+        let b = b(LinearCombination::zero());
+        let c = c(LinearCombination::zero());
+
+        self.constraints.push((a, b, c, path));
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        let name = name_fn().into();
+        let path = compute_path(&self.current_namespace, name.clone());
+        self.set_named_obj(path.clone(), NamedObject::Namespace);
+        self.current_namespace.push(name);
+    }
+
+    fn pop_namespace(&mut self) {
+        assert!(self.current_namespace.pop().is_some());
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    /// Output the number of constraints in the system.
+    fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    fn num_public_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    fn num_private_variables(&self) -> usize {
+        self.aux.len()
+    }
+}
+
+#[test]
+fn test_cs() {
+    use curve::bn_256::Fr;
+    use num_traits::One;
+
+    let mut cs = TestConstraintSystem::<Fr>::new();
+    assert!(cs.is_satisfied());
+    assert_eq!(cs.num_constraints(), 0);
+    let a = cs
+        .ns(|| "a")
+        .alloc(|| "var", || Ok(Fr::from(10u32)))
+        .unwrap();
+    let b = cs
+        .ns(|| "b")
+        .alloc(|| "var", || Ok(Fr::from(4u32)))
+        .unwrap();
+    let c = cs.alloc(|| "product", || Ok(Fr::from(40u32))).unwrap();
+
+    cs.enforce(|| "mult", |lc| lc + a, |lc| lc + b, |lc| lc + c);
+    assert!(cs.is_satisfied());
+    assert_eq!(cs.num_constraints(), 1);
+    assert_eq!(cs.num_public_inputs(), 1);
+    assert_eq!(cs.num_private_variables(), 3);
+
+    cs.set("a/var", Fr::from(4u32));
+
+    let one = TestConstraintSystem::<Fr>::one();
+    cs.enforce(|| "eq", |lc| lc + a, |lc| lc + one, |lc| lc + b);
+
+    assert!(!cs.is_satisfied());
+    assert_eq!(cs.which_is_unsatisfied().as_deref(), Some("mult"));
+
+    assert!(cs.get("product") == Fr::from(40u32));
+
+    cs.set("product", Fr::from(16u32));
+    assert!(cs.is_satisfied());
+
+    {
+        let mut cs = cs.ns(|| "test1");
+        let mut cs = cs.ns(|| "test2");
+        cs.alloc(|| "hehe", || Ok(Fr::one())).unwrap();
+        cs.alloc_input(|| "input", || Ok(Fr::one())).unwrap();
+        assert_eq!(cs.num_constraints(), 2);
+        assert_eq!(cs.num_public_inputs(), 2);
+        assert_eq!(cs.num_private_variables(), 4);
+    }
+
+    assert!(cs.get("test1/test2/hehe") == Fr::one());
+}
+
+#[test]
+fn test_namespace_report() {
+    use curve::bn_256::Fr;
+
+    let mut cs = TestConstraintSystem::<Fr>::new();
+    let one = TestConstraintSystem::<Fr>::one();
+    let a = cs.alloc(|| "a", || Ok(Fr::from(1u32))).unwrap();
+    cs.enforce(|| "a is one", |lc| lc + a, |lc| lc + one, |lc| lc + one);
+    {
+        let mut cs = cs.ns(|| "outer");
+        let b = cs.alloc(|| "b", || Ok(Fr::from(1u32))).unwrap();
+        cs.enforce(|| "b is one", |lc| lc + b, |lc| lc + one, |lc| lc + one);
+        for i in 0..2 {
+            let mut cs = cs.ns(|| format!("inner {}", i));
+            let c = cs.alloc(|| "c", || Ok(Fr::from(1u32))).unwrap();
+            cs.enforce(|| "c is b", |lc| lc + c, |lc| lc + one, |lc| lc + b);
+            cs.enforce(|| "c is one", |lc| lc + c, |lc| lc + one, |lc| lc + one);
+        }
+    }
+    let hash = cs.hash();
+
+    assert_eq!(cs.namespace_report(0), vec![("".to_owned(), 6, 4)]);
+    assert_eq!(
+        cs.namespace_report(1),
+        vec![("outer".to_owned(), 5, 3), ("".to_owned(), 1, 1)]
+    );
+    assert_eq!(
+        cs.namespace_report(2),
+        vec![
+            ("outer/inner 0".to_owned(), 2, 1),
+            ("outer/inner 1".to_owned(), 2, 1),
+            ("".to_owned(), 1, 1),
+            ("outer".to_owned(), 1, 1),
+        ]
+    );
+    assert_eq!(cs.namespace_report(2), cs.namespace_report(3));
+    assert!(cs
+        .pretty_report(1)
+        .ends_with("           5            3  outer\n           1            1  \n"));
+
+    // the report does not touch the system.
+    assert_eq!(cs.hash(), hash);
+    assert!(cs.is_satisfied());
+}
+
+#[test]
+fn test_missing_assignment_path() {
+    use curve::bn_256::Fr;
+
+    let mut cs = TestConstraintSystem::<Fr>::new();
+    let err = cs
+        .ns(|| "outer")
+        .ns(|| "inner")
+        .alloc(|| "x", || Err(SynthesisError::AssignmentMissing))
+        .unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        "outer/inner/x: an assignment for a variable could not be computed"
+    );
+    assert!(matches!(
+        err.without_context(),
+        SynthesisError::AssignmentMissing
+    ));
+}
+
+#[test]
+fn test_dump() {
+    use curve::bn_256::Fr;
+
+    let mut cs = TestConstraintSystem::<Fr>::new();
+    let one = TestConstraintSystem::<Fr>::one();
+    let a = cs.alloc(|| "a", || Ok(Fr::from(3u32))).unwrap();
+    {
+        let mut cs = cs.ns(|| "inner");
+        let b = cs.alloc(|| "b", || Ok(Fr::from(5u32))).unwrap();
+        let out = cs.alloc_input(|| "out", || Ok(Fr::from(9u32))).unwrap();
+        // the repeated b merges into 2*b.
+        cs.enforce(
+            || "sum",
+            |lc| lc + b + (Fr::from(2u32), a) + b + (-Fr::from(1u32), one),
+            |lc| lc + one,
+            |lc| lc + out + (Fr::from(10u32), one),
+        );
+        cs.enforce(|| "zero", |lc| lc - a, |lc| lc, |lc| lc);
+    }
+    cs.enforce(
+        || "a is three",
+        |lc| lc + a,
+        |lc| lc + one,
+        |lc| lc + (Fr::from(3u32), one),
+    );
+
+    let dump = |options: &DumpOptions| {
+        let mut out = vec![];
+        cs.dump(&mut out, options).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+    assert_eq!(
+        dump(&DumpOptions::default()),
+        "inner/sum: (2*a + 2*inner/b - 1) * (1) = (inner/out + 10)\n\
+         inner/zero: (-a) * (0) = (0)\n\
+         a is three: (a) * (1) = (3)\n"
+    );
+    assert_eq!(
+        dump(&DumpOptions {
+            namespace: "inner".to_owned(),
+            values: true,
+        }),
+        "inner/sum: (2*3 + 2*5 - 1) * (1) = (9 + 10)\n\
+         inner/zero: (-3) * (0) = (0)\n"
+    );
+    assert_eq!(
+        dump(&DumpOptions {
+            namespace: "in".to_owned(),
+            values: false,
+        }),
+        ""
+    );
+}
@@ -0,0 +1,99 @@
+// For randomness (during paramgen and proof generation)
+use rand::Rng;
+
+use curve::bn_256::{Bn_256, Fr};
+use math::{test_rng, PrimeField};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    blake2s::blake2s,
+    boolean::{AllocatedBit, Boolean},
+};
+
+const PERSONALIZATION: &[u8; 8] = b"12345678";
+
+/// The little-endian bits of each byte, in byte order, as the blake2s gadget
+/// takes and returns them.
+fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|&byte| (0..8).map(move |i| (byte >> i) & 1u8 == 1u8))
+        .collect()
+}
+
+/// This is our demo circuit for proving knowledge of the preimage of a
+/// BLAKE2s hash. The preimage is private, the bits of the hash are the
+/// public inputs.
+struct Blake2sDemo {
+    preimage: Option<[u8; 32]>,
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for Blake2sDemo {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let preimage_bits = match self.preimage {
+            Some(preimage) => bytes_to_bits(&preimage).into_iter().map(Some).collect(),
+            None => vec![None; 256],
+        };
+
+        let input = preimage_bits
+            .into_iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.ns(|| format!("preimage bit {}", i)), b).map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hash = blake2s(cs.ns(|| "blake2s"), &input, PERSONALIZATION)?;
+
+        for (i, bit) in hash.iter().enumerate() {
+            let image =
+                AllocatedBit::alloc_input(cs.ns(|| format!("image bit {}", i)), bit.get_value())?;
+            Boolean::enforce_equal(
+                cs.ns(|| format!("image bit {} is the hash bit", i)),
+                &Boolean::from(image),
+                bit,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_blake2s_groth16() {
+    use blake2s_simd::Params as Blake2sParams;
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let params =
+        generate_random_parameters::<Bn_256, _, _>(Blake2sDemo { preimage: None }, rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let preimage: [u8; 32] = rng.gen();
+    let image = Blake2sParams::new()
+        .hash_length(32)
+        .personal(PERSONALIZATION)
+        .hash(&preimage);
+    let image = bytes_to_bits(image.as_bytes())
+        .into_iter()
+        .map(|b| if b { Fr::from(1u32) } else { Fr::from(0u32) })
+        .collect::<Vec<_>>();
+
+    let c = Blake2sDemo {
+        preimage: Some(preimage),
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    assert!(verify_proof(&pvk, &proof, &image).unwrap());
+
+    // the proof does not verify for another image.
+    let mut other_image = image.clone();
+    other_image[0] = Fr::from(1u32) - &other_image[0];
+    assert!(!verify_proof(&pvk, &proof, &other_image).unwrap());
+}
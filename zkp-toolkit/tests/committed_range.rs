@@ -0,0 +1,83 @@
+use rand::Rng;
+
+use curve::{
+    baby_jubjub::EdwardsParameters,
+    bn_256::{Bn_256, Fr},
+};
+use math::test_rng;
+use scheme::r1cs::debug_satisfied;
+use zkp_toolkit::circuits::committed_range::{
+    commitment, public_inputs, randomness_bits, RangeCircuit,
+};
+
+/// The range of the values, the one of a u64.
+const N_BITS: usize = 64;
+
+fn randomness() -> Vec<bool> {
+    let rng = &mut test_rng();
+    (0..randomness_bits::<EdwardsParameters>())
+        .map(|_| rng.gen())
+        .collect()
+}
+
+#[test]
+fn test_range_groth16() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        RangeCircuit::<EdwardsParameters>::blank(N_BITS),
+        rng,
+    )
+    .unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let r = randomness();
+    let c = RangeCircuit::<EdwardsParameters>::new(42, r.clone(), N_BITS);
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    let inputs = public_inputs(&commitment::<EdwardsParameters>(42, N_BITS, &r));
+    assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    // the proof does not verify for the commitment of another value.
+    let inputs = public_inputs(&commitment::<EdwardsParameters>(43, N_BITS, &r));
+    assert!(!verify_proof(&pvk, &proof, &inputs).unwrap());
+}
+
+#[test]
+fn test_range_bulletproofs() {
+    use scheme::bulletproofs::{prove_with_shape, setup, synthesize_shape, verify};
+
+    let rng = &mut test_rng();
+
+    let shape =
+        synthesize_shape::<Bn_256, _>(RangeCircuit::<EdwardsParameters>::blank(N_BITS)).unwrap();
+    let gens = setup::<Bn_256, _>(shape.generators_size(), rng);
+
+    let r = randomness();
+    let c = RangeCircuit::<EdwardsParameters>::new(42, r.clone(), N_BITS);
+    let proof = prove_with_shape(&gens, &shape, c, rng).unwrap();
+
+    let inputs = public_inputs(&commitment::<EdwardsParameters>(42, N_BITS, &r));
+    assert!(verify(&gens, &shape, &proof, &inputs).unwrap());
+
+    let inputs = public_inputs(&commitment::<EdwardsParameters>(43, N_BITS, &r));
+    assert!(!verify(&gens, &shape, &proof, &inputs).unwrap());
+}
+
+#[test]
+fn test_range_out_of_range() {
+    let r = randomness();
+    let c = RangeCircuit::<EdwardsParameters>::new(255, r.clone(), 8);
+    assert_eq!(debug_satisfied::<Fr, _>(c).unwrap(), None);
+
+    // 256 has no decomposition into 8 bits, the packing of its low bits is
+    // not the value.
+    let c = RangeCircuit::<EdwardsParameters>::new(256, r, 8);
+    let unsatisfied = debug_satisfied::<Fr, _>(c).unwrap().unwrap();
+    assert_eq!(unsatisfied.path, "packing");
+}
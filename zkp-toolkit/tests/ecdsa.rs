@@ -0,0 +1,106 @@
+//! A proof of a secp256k1 ECDSA signature over bn_256: the public key and
+//! the SHA-256 hash of the message are public, the signature is not.
+
+use std::rc::Rc;
+
+use curve::bn_256::{Bn_256, Fr};
+use curve::secp256k1::{Fq, Fr as Scalar, Secp256k1Affine, Secp256k1Parameters};
+use math::test_rng;
+use num_bigint::BigUint;
+use scheme::groth16::{
+    create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof,
+};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    ecdsa::{enforce_verify, AffinePointVar},
+    nonnative::{biguint_to_field, NonNativeFieldParams, NonNativeFieldVar},
+};
+
+const LIMB_WIDTH: usize = 64;
+
+struct EcdsaDemo {
+    public_key: Option<Secp256k1Affine>,
+    hash: Option<BigUint>,
+    r: Option<BigUint>,
+    s: Option<BigUint>,
+}
+
+impl ConstraintSynthesizer<Fr> for EcdsaDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let params_p = Rc::new(NonNativeFieldParams::of_field::<Fq>(LIMB_WIDTH));
+        let params_n = Rc::new(NonNativeFieldParams::of_field::<Scalar>(LIMB_WIDTH));
+
+        let public_key = AffinePointVar::<Fr, Secp256k1Parameters>::alloc_input(
+            cs.ns(|| "public key"),
+            &params_p,
+            self.public_key.as_ref(),
+        )?;
+        let hash = NonNativeFieldVar::alloc_input(cs.ns(|| "hash"), &params_n, self.hash.as_ref())?;
+        let r = NonNativeFieldVar::alloc(cs.ns(|| "r"), &params_n, self.r.as_ref())?;
+        let s = NonNativeFieldVar::alloc(cs.ns(|| "s"), &params_n, self.s.as_ref())?;
+
+        enforce_verify(cs.ns(|| "verify"), &public_key, &hash, &r, &s)
+    }
+}
+
+fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+/// The public inputs of an integer below the modulus, its 64-bit limbs.
+fn limbs(x: &BigUint) -> Vec<Fr> {
+    let mut digits = x.to_u64_digits();
+    digits.resize(4, 0);
+    digits.into_iter().map(Fr::from).collect()
+}
+
+/// Proves a signature of "ckb-zkp ecdsa gadget" by OpenSSL; about a million
+/// constraints, it takes minutes.
+#[test]
+#[ignore]
+fn test_ecdsa() {
+    let rng = &mut test_rng();
+
+    let x = hex("a84c77ed8534b8001a5421ab9417071cb39a203978a395084ffddcb78525e111");
+    let y = hex("f183564b56400b3d17014e70e00ed31a052e827d921a4b70f945991449e362c9");
+    let public_key = Secp256k1Affine::new(biguint_to_field(&x), biguint_to_field(&y), false);
+    let hash = hex("f8fb9c6c612e2f8d318da6b3e45ee42dfbe1aad5a19498847086ff85117d2ae4");
+    let r = hex("8c2b8e985c4954b45901e6e5d78be5409fd2cfbfd9c9400806ae91fe9224ad1a");
+    let s = hex("0a0bbfdf02405da5596a4a8777ad7f80ff14835a1080a329b38a9d5332349db4");
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        EcdsaDemo {
+            public_key: None,
+            hash: None,
+            r: None,
+            s: None,
+        },
+        rng,
+    )
+    .unwrap();
+    let proof = create_random_proof(
+        &params,
+        EcdsaDemo {
+            public_key: Some(public_key),
+            hash: Some(hash.clone()),
+            r: Some(r),
+            s: Some(s),
+        },
+        rng,
+    )
+    .unwrap();
+
+    let pvk = prepare_verifying_key(&params.vk);
+    let mut inputs = limbs(&x);
+    inputs.extend(limbs(&y));
+    inputs.extend(limbs(&hash));
+    assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    let mut inputs = limbs(&x);
+    inputs.extend(limbs(&y));
+    inputs.extend(limbs(&(hash + 1u32)));
+    assert!(!verify_proof(&pvk, &proof, &inputs).unwrap());
+}
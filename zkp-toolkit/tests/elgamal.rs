@@ -0,0 +1,131 @@
+// For randomness (during paramgen and proof generation)
+use rand::Rng;
+
+use curve::{
+    baby_jubjub::EdwardsParameters,
+    bn_256::{Bn_256, Fr},
+};
+use math::{curves::twisted_edwards_extended::GroupAffine, test_rng};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    boolean::{AllocatedBit, Boolean},
+    elgamal::{alloc_public_key, decrypt, encrypt, encryption, public_key},
+    pedersen::{commit, commitment},
+};
+
+/// The number of bits of the value.
+const VALUE_BITS: usize = 32;
+
+/// The number of bits of the randomness and of the keys, the ones of the
+/// order of the baby_jubjub subgroup.
+const SCALAR_BITS: usize = 251;
+
+/// The little-endian bits of `v`.
+fn u64_to_bits(v: u64) -> Vec<bool> {
+    (0..VALUE_BITS).map(|i| (v >> i) & 1 == 1).collect()
+}
+
+fn alloc_bits<CS: ConstraintSystem<Fr>>(
+    mut cs: CS,
+    bits: Option<Vec<bool>>,
+    len: usize,
+) -> Result<Vec<Boolean>, SynthesisError> {
+    let bits = match bits {
+        Some(bits) => bits.into_iter().map(Some).collect(),
+        None => vec![None; len],
+    };
+
+    bits.into_iter()
+        .enumerate()
+        .map(|(i, b)| AllocatedBit::alloc(cs.ns(|| format!("bit {}", i)), b).map(Boolean::from))
+        .collect()
+}
+
+/// This is our demo circuit for proving that a ciphertext under the public
+/// key of an auditor encrypts the value of a Pedersen commitment. The
+/// value and the randomness are private, the key, the commitment and the
+/// ciphertext are the public inputs.
+struct AuditedCommitmentDemo {
+    pk: Option<GroupAffine<EdwardsParameters>>,
+    value: Option<u64>,
+    commitment_randomness: Option<Vec<bool>>,
+    encryption_randomness: Option<Vec<bool>>,
+}
+
+impl ConstraintSynthesizer<Fr> for AuditedCommitmentDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let pk = alloc_public_key(cs.ns(|| "pk"), self.pk)?;
+
+        let value = alloc_bits(cs.ns(|| "value"), self.value.map(u64_to_bits), VALUE_BITS)?;
+        let commitment_randomness = alloc_bits(
+            cs.ns(|| "commitment randomness"),
+            self.commitment_randomness,
+            SCALAR_BITS,
+        )?;
+        let encryption_randomness = alloc_bits(
+            cs.ns(|| "encryption randomness"),
+            self.encryption_randomness,
+            SCALAR_BITS,
+        )?;
+
+        commit::<EdwardsParameters, _>(cs.ns(|| "commit"), &value, &commitment_randomness)?;
+        encrypt::<EdwardsParameters, _>(cs.ns(|| "encrypt"), &pk, &value, &encryption_randomness)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_elgamal_groth16() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        AuditedCommitmentDemo {
+            pk: None,
+            value: None,
+            commitment_randomness: None,
+            encryption_randomness: None,
+        },
+        rng,
+    )
+    .unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    // the auditor publishes its key.
+    let secret: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+    let pk = public_key::<EdwardsParameters>(&secret);
+
+    // the committer publishes the commitment and the encryption of a value.
+    let value = 12345u64;
+    let commitment_randomness: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+    let encryption_randomness: Vec<bool> = (0..SCALAR_BITS).map(|_| rng.gen()).collect();
+    let c = commitment::<EdwardsParameters>(&u64_to_bits(value), &commitment_randomness);
+    let (c1, c2) = encryption(&pk, &u64_to_bits(value), &encryption_randomness);
+
+    let circuit = AuditedCommitmentDemo {
+        pk: Some(pk),
+        value: Some(value),
+        commitment_randomness: Some(commitment_randomness),
+        encryption_randomness: Some(encryption_randomness.clone()),
+    };
+    let proof = create_random_proof(&params, circuit, rng).unwrap();
+
+    let inputs = [pk.x, pk.y, c.x, c.y, c1.x, c1.y, c2.x, c2.y];
+    assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    // the proof does not verify for the encryption of another value.
+    let (_, other) = encryption(&pk, &u64_to_bits(value + 1), &encryption_randomness);
+    let inputs = [pk.x, pk.y, c.x, c.y, c1.x, c1.y, other.x, other.y];
+    assert!(!verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    // the auditor recovers the value.
+    assert_eq!(decrypt(&secret, &(c1, c2), 1 << 14), Some(value));
+}
@@ -0,0 +1,124 @@
+//! A proof of a proof: a Groth16 proof over BLS12-377 of the cubic demo
+//! circuit, verified in a circuit over BW6-761, whose base field is the
+//! scalar field of the outer Groth16 proof.
+
+use curve::bls12_377::{Bls12_377, Fr, Parameters};
+use curve::bw6_761::{Fr as OuterFr, BW6_761};
+use math::{test_rng, Field};
+use scheme::groth16::{
+    create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof, Proof,
+    VerifyKey,
+};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    boolean::Boolean,
+    fr::AllocatedFr,
+    groth16_verifier::{input_to_bits, verify, ProofVar, VerifyKeyVar},
+};
+
+/// The inner circuit, `x^3 + x + 5 = out` of the public `out`.
+struct CubicDemo {
+    x: Option<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for CubicDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let x_value = self.x;
+        let x = cs.alloc(|| "x", || x_value.ok_or(SynthesisError::AssignmentMissing))?;
+        let x2 = cs.alloc(
+            || "x^2",
+            || {
+                x_value
+                    .map(|x| x.square())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let out = cs.alloc_input(
+            || "out",
+            || {
+                x_value
+                    .map(|x| x.square() * &x + &x + &Fr::from(5u32))
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        cs.enforce(|| "x * x = x^2", |lc| lc + x, |lc| lc + x, |lc| lc + x2);
+        cs.enforce(
+            || "x^2 * x = out - x - 5",
+            |lc| lc + x2,
+            |lc| lc + x,
+            |lc| lc + out - x - (Fr::from(5u32), CS::one()),
+        );
+
+        Ok(())
+    }
+}
+
+/// The outer circuit: the inner proof is private, its public input `out` is
+/// the public input of the outer proof, and its verifying key is fixed.
+struct VerifierDemo<'a> {
+    vk: &'a VerifyKey<Bls12_377>,
+    proof: Option<Proof<Bls12_377>>,
+    out: Option<OuterFr>,
+}
+
+impl<'a> ConstraintSynthesizer<OuterFr> for VerifierDemo<'a> {
+    fn generate_constraints<CS: ConstraintSystem<OuterFr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let vk = VerifyKeyVar::<Parameters>::constant(self.vk);
+        let proof = ProofVar::alloc(cs.ns(|| "proof"), self.proof.as_ref())?;
+        let out = AllocatedFr::alloc_input(cs.ns(|| "out"), || {
+            self.out.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let out_bits = input_to_bits::<Parameters, _>(cs.ns(|| "out bits"), &out)?;
+
+        let valid = verify(cs.ns(|| "verify"), &vk, &proof, &[out_bits])?;
+        Boolean::enforce_equal(cs.ns(|| "valid"), &valid, &Boolean::constant(true))
+    }
+}
+
+#[test]
+fn test_groth16_verifier() {
+    let rng = &mut test_rng();
+
+    let inner_params =
+        generate_random_parameters::<Bls12_377, _, _>(CubicDemo { x: None }, rng).unwrap();
+    let inner_proof = create_random_proof(
+        &inner_params,
+        CubicDemo {
+            x: Some(Fr::from(3u32)),
+        },
+        rng,
+    )
+    .unwrap();
+    let inner_pvk = prepare_verifying_key(&inner_params.vk);
+    assert!(verify_proof(&inner_pvk, &inner_proof, &[Fr::from(35u32)]).unwrap());
+
+    let outer_params = generate_random_parameters::<BW6_761, _, _>(
+        VerifierDemo {
+            vk: &inner_params.vk,
+            proof: None,
+            out: None,
+        },
+        rng,
+    )
+    .unwrap();
+    let outer_proof = create_random_proof(
+        &outer_params,
+        VerifierDemo {
+            vk: &inner_params.vk,
+            proof: Some(inner_proof),
+            out: Some(OuterFr::from(35u32)),
+        },
+        rng,
+    )
+    .unwrap();
+
+    let outer_pvk = prepare_verifying_key(&outer_params.vk);
+    assert!(verify_proof(&outer_pvk, &outer_proof, &[OuterFr::from(35u32)]).unwrap());
+    assert!(!verify_proof(&outer_pvk, &outer_proof, &[OuterFr::from(36u32)]).unwrap());
+}
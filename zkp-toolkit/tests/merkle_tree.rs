@@ -0,0 +1,78 @@
+use curve::bn_256::{Bn_256, Fr};
+use math::test_rng;
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    merkletree::{cbmt::CBMT, cbmt_constraints::MerkleProofGadget},
+    mimc::{AbstractHashMimc, AbstractHashMimcOutput, MergeMimc},
+};
+
+/// This is our demo circuit for proving the membership of a leaf in the
+/// tree of a public root. The leaf, its position and its siblings are
+/// private.
+struct MembershipDemo {
+    root: Fr,
+    leaf: Fr,
+    index: u32,
+    lemmas: Vec<Fr>,
+}
+
+impl MembershipDemo {
+    fn new(leaves: &[Fr], index: u32) -> Self {
+        let tree = CBMT::<Fr, MergeMimc<Fr>>::build_merkle_tree(leaves.to_vec());
+        let proof = tree.build_proof(&index).unwrap();
+
+        MembershipDemo {
+            root: tree.root(),
+            leaf: leaves[index as usize],
+            index: *proof.index(),
+            lemmas: proof.lemmas().to_vec(),
+        }
+    }
+}
+
+impl ConstraintSynthesizer<Fr> for MembershipDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let root = AbstractHashMimcOutput::alloc_input(cs.ns(|| "root"), Some(self.root))?;
+        let leaf = AbstractHashMimcOutput::alloc(cs.ns(|| "leaf"), Some(self.leaf))?;
+        let lemmas = self
+            .lemmas
+            .iter()
+            .enumerate()
+            .map(|(i, l)| AbstractHashMimcOutput::alloc(cs.ns(|| format!("lemma {}", i)), Some(*l)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let g = MerkleProofGadget::<u32, Fr, AbstractHashMimc<Fr>>::new(self.index, lemmas);
+        g.set_membership(cs.ns(|| "membership"), root, leaf)
+    }
+}
+
+#[test]
+fn test_merkle_membership_groth16() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let leaves = (1..=8u32).map(Fr::from).collect::<Vec<_>>();
+    let root = CBMT::<Fr, MergeMimc<Fr>>::build_merkle_root(&leaves);
+
+    // the parameters of the first leaf serve all of them.
+    let params =
+        generate_random_parameters::<Bn_256, _, _>(MembershipDemo::new(&leaves, 0), rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    for &index in [3u32, 4].iter() {
+        let c = MembershipDemo::new(&leaves, index);
+        let proof = create_random_proof(&params, c, rng).unwrap();
+
+        assert!(verify_proof(&pvk, &proof, &[root]).unwrap());
+
+        // the proof does not verify for another root.
+        assert!(!verify_proof(&pvk, &proof, &[leaves[index as usize]]).unwrap());
+    }
+}
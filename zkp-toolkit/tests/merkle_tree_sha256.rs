@@ -0,0 +1,99 @@
+// For randomness (during paramgen and proof generation)
+use rand::Rng;
+
+use curve::bn_256::{Bn_256, Fr};
+use math::{test_rng, PrimeField};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    merkletree::{cbmt::CBMT, cbmt_constraints::MerkleProofGadget},
+    multipack::bytes_to_bits_be,
+    sha256::{AbstractHashSha256, AbstractHashSha256Output, MergeSha256},
+};
+
+/// The depth of a tree of 4 leaves.
+const DEPTH: usize = 2;
+
+/// This is our demo circuit for proving the membership of a leaf in a
+/// SHA-256 merkle tree. The leaf and the siblings are private, the bits of
+/// the root are the public inputs.
+struct MerkleTreeDemo {
+    index: u32,
+    root: Option<Vec<u8>>,
+    leaf: Option<Vec<u8>>,
+    lemmas: Option<Vec<Vec<u8>>>,
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for MerkleTreeDemo {
+    fn generate_constraints<CS: ConstraintSystem<F>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let root = AbstractHashSha256Output::alloc_input(cs.ns(|| "root"), self.root)?;
+        let leaf = AbstractHashSha256Output::alloc(cs.ns(|| "leaf"), self.leaf)?;
+
+        let lemmas = match self.lemmas {
+            Some(lemmas) => lemmas.into_iter().map(Some).collect(),
+            None => vec![None; DEPTH],
+        };
+        let lemmas = lemmas
+            .into_iter()
+            .enumerate()
+            .map(|(i, l)| AbstractHashSha256Output::alloc(cs.ns(|| format!("lemma {}", i)), l))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let g = MerkleProofGadget::<u32, F, AbstractHashSha256<F>>::new(self.index, lemmas);
+        g.set_membership(cs.ns(|| "set membership"), root, leaf)
+    }
+}
+
+#[test]
+fn test_merkle_tree_sha256_groth16() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let leaves = (0..4)
+        .map(|_| rng.gen::<[u8; 32]>().to_vec())
+        .collect::<Vec<_>>();
+    let tree = CBMT::<Vec<u8>, MergeSha256>::build_merkle_tree(leaves.clone());
+    let root = tree.root();
+    let root_bits = bytes_to_bits_be(&root)
+        .into_iter()
+        .map(|b| if b { Fr::from(1u32) } else { Fr::from(0u32) })
+        .collect::<Vec<_>>();
+
+    let leaf = leaves[1].clone();
+    let proof = tree.build_proof(&1).unwrap();
+    assert_eq!(proof.lemmas().len(), DEPTH);
+    assert!(proof.verify(&root, &leaf));
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        MerkleTreeDemo {
+            index: *proof.index(),
+            root: None,
+            leaf: None,
+            lemmas: None,
+        },
+        rng,
+    )
+    .unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let c = MerkleTreeDemo {
+        index: *proof.index(),
+        root: Some(root.clone()),
+        leaf: Some(leaf),
+        lemmas: Some(proof.lemmas().to_vec()),
+    };
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    assert!(verify_proof(&pvk, &proof, &root_bits).unwrap());
+
+    // the proof does not verify for another root.
+    let mut other_root = root_bits.clone();
+    other_root[0] = Fr::from(1u32) - &other_root[0];
+    assert!(!verify_proof(&pvk, &proof, &other_root).unwrap());
+}
@@ -0,0 +1,118 @@
+// For randomness (during paramgen and proof generation)
+use rand::Rng;
+
+use curve::{
+    baby_jubjub::EdwardsParameters,
+    bn_256::{Bn_256, Fr},
+};
+use math::{test_rng, BigInteger, PrimeField, ToBytes};
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::{
+    boolean::{AllocatedBit, Boolean},
+    fr::AllocatedFr,
+    mimc::{hash, mimc_block_enforce, MimcParameters},
+    pedersen::{commit, commitment, max_value_bits},
+};
+
+/// The number of bits of the randomness, the ones of the order of the
+/// baby_jubjub subgroup.
+const RANDOMNESS_BITS: usize = 251;
+
+/// The little-endian bits of `f`.
+fn field_to_bits(f: &Fr) -> Vec<bool> {
+    let repr = f.into_repr();
+    (0..Fr::size_in_bits()).map(|i| repr.get_bit(i)).collect()
+}
+
+/// This is our demo circuit for proving that the value of a Pedersen
+/// commitment is the MiMC hash of a preimage, of the 250 low bits of the
+/// hash a commitment binds. The preimage, the value and the randomness are
+/// private, the commitment is the public input.
+struct CommittedMimcDemo {
+    preimage: Option<Fr>,
+    randomness: Option<Vec<bool>>,
+}
+
+impl ConstraintSynthesizer<Fr> for CommittedMimcDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        // the MiMC hash of one element is the block of 0 and the element.
+        let xl = AllocatedFr::alloc(cs.ns(|| "preimage xl"), || Ok(Fr::from(0u32)))?;
+        cs.enforce(
+            || "preimage xl is zero",
+            |lc| lc + xl.get_variable(),
+            |lc| lc + CS::one(),
+            |lc| lc,
+        );
+        let xr = AllocatedFr::alloc(cs.ns(|| "preimage xr"), || {
+            self.preimage.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let image = mimc_block_enforce(cs.ns(|| "mimc"), &xl, &xr, &MimcParameters::default())?;
+
+        // the hash truncated to the bits a commitment binds.
+        let mut value = image.to_bits_le_strict(cs.ns(|| "value"))?;
+        value.truncate(max_value_bits::<EdwardsParameters>());
+
+        let randomness = match self.randomness {
+            Some(randomness) => randomness.into_iter().map(Some).collect(),
+            None => vec![None; RANDOMNESS_BITS],
+        };
+        let randomness = randomness
+            .into_iter()
+            .enumerate()
+            .map(|(i, b)| {
+                AllocatedBit::alloc(cs.ns(|| format!("randomness bit {}", i)), b).map(Boolean::from)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        commit::<EdwardsParameters, _>(cs.ns(|| "commit"), &value, &randomness)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pedersen_commitment_groth16() {
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        CommittedMimcDemo {
+            preimage: None,
+            randomness: None,
+        },
+        rng,
+    )
+    .unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let preimage: Fr = rng.gen();
+    let randomness: Vec<bool> = (0..RANDOMNESS_BITS).map(|_| rng.gen()).collect();
+
+    // the committer publishes the commitment of the hash.
+    let mut bytes = vec![];
+    preimage.write(&mut bytes).unwrap();
+    let mut value = field_to_bits(&hash::<Fr>(&bytes, &MimcParameters::default()));
+    value.truncate(max_value_bits::<EdwardsParameters>());
+    let c = commitment::<EdwardsParameters>(&value, &randomness);
+
+    let circuit = CommittedMimcDemo {
+        preimage: Some(preimage),
+        randomness: Some(randomness.clone()),
+    };
+    let proof = create_random_proof(&params, circuit, rng).unwrap();
+
+    assert!(verify_proof(&pvk, &proof, &[c.x, c.y]).unwrap());
+
+    // the proof does not verify for the commitment of another value.
+    let mut other_value = value.clone();
+    other_value[0] = !other_value[0];
+    let other = commitment::<EdwardsParameters>(&other_value, &randomness);
+    assert!(!verify_proof(&pvk, &proof, &[other.x, other.y]).unwrap());
+}
@@ -0,0 +1,98 @@
+use curve::bn_256::{Bn_256, Fr};
+use math::{test_rng, One, UniformRand};
+use zkp_toolkit::circuits::spend::{build_tree, commitment, nullifier, public_inputs, Spend};
+use zkp_toolkit::gadgets::{
+    abstract_hash::{AbstractHash, AbstractHashFrOutput},
+    merkletree::cbmt::{Merge, MerkleTree},
+    mimc::{AbstractHashMimc, MergeMimc},
+    poseidon::{AbstractHashPoseidon, MergePoseidon},
+};
+
+/// The depth of a tree of 8 notes.
+const DEPTH: usize = 3;
+
+/// The notes of `(secret, value)` and the tree of their commitments.
+fn notes<M: Merge<Item = Fr>>() -> (Vec<(Fr, Fr)>, MerkleTree<Fr, M>) {
+    let rng = &mut test_rng();
+
+    let notes = (0..5)
+        .map(|_| (Fr::rand(rng), Fr::rand(rng)))
+        .collect::<Vec<_>>();
+    let commitments = notes
+        .iter()
+        .map(|(secret, value)| commitment::<_, M>(secret, value))
+        .collect::<Vec<_>>();
+
+    (notes, build_tree(&commitments, DEPTH))
+}
+
+fn spend_groth16<H, M>()
+where
+    H: AbstractHash<Fr, Output = AbstractHashFrOutput<Fr>>,
+    M: Merge<Item = Fr>,
+{
+    use scheme::groth16::{
+        create_random_proof, generate_random_parameters, verifier::prepare_verifying_key,
+        verify_proof,
+    };
+
+    let rng = &mut test_rng();
+
+    let params =
+        generate_random_parameters::<Bn_256, _, _>(Spend::<Fr, H>::blank(DEPTH), rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let (notes, tree) = notes::<M>();
+    let (secret, value) = notes[3];
+    let proof = tree.build_proof(&3).unwrap();
+    let c = Spend::<Fr, H>::new(secret, value, &proof);
+    let proof = create_random_proof(&params, c, rng).unwrap();
+
+    let inputs = public_inputs(&tree.root(), &nullifier::<_, M>(&secret, 3));
+    assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    // the proof does not verify for the nullifier of another leaf.
+    let inputs = public_inputs(&tree.root(), &nullifier::<_, M>(&secret, 2));
+    assert!(!verify_proof(&pvk, &proof, &inputs).unwrap());
+}
+
+#[test]
+fn test_spend_mimc_groth16() {
+    spend_groth16::<AbstractHashMimc<Fr>, MergeMimc<Fr>>();
+}
+
+#[test]
+fn test_spend_poseidon_groth16() {
+    spend_groth16::<AbstractHashPoseidon<Fr>, MergePoseidon<Fr>>();
+}
+
+#[cfg(feature = "spartan")]
+#[test]
+fn test_spend_poseidon_spartan() {
+    use scheme::spartan::snark::{create_random_proof, generate_random_parameters, verify_proof};
+
+    let rng = &mut test_rng();
+
+    let params = generate_random_parameters::<Bn_256, _, _>(
+        Spend::<Fr, AbstractHashPoseidon<Fr>>::blank(DEPTH),
+        rng,
+    )
+    .unwrap();
+    let (pk, vk) = params.keypair();
+
+    let (notes, tree) = notes::<MergePoseidon<Fr>>();
+    let (secret, value) = notes[1];
+    let proof = tree.build_proof(&1).unwrap();
+    let c = Spend::<Fr, AbstractHashPoseidon<Fr>>::new(secret, value, &proof);
+    let proof = create_random_proof(&pk, c, rng).unwrap();
+
+    let inputs = public_inputs(&tree.root(), &nullifier::<_, MergePoseidon<Fr>>(&secret, 1));
+    assert!(verify_proof::<Bn_256>(&vk, &proof, &inputs).unwrap());
+
+    // the proof does not verify for another root.
+    let inputs = public_inputs(
+        &(tree.root() + &Fr::one()),
+        &nullifier::<_, MergePoseidon<Fr>>(&secret, 1),
+    );
+    assert!(!verify_proof::<Bn_256>(&vk, &proof, &inputs).unwrap());
+}
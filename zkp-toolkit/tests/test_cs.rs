@@ -0,0 +1,133 @@
+use curve::bn_256::Fr;
+use math::Field;
+use scheme::r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
+use zkp_toolkit::gadgets::fr::AllocatedFr;
+use zkp_toolkit::test_cs::TestConstraintSystem;
+
+/// This is our demo circuit for proving knowledge of `x` such that
+/// `x^3 + x + 5` is the public `out`. With `broken`, the witness of the
+/// cube is `x^2` instead, as a bug in a gadget would compute it.
+struct CubicDemo {
+    x: Option<Fr>,
+    broken: bool,
+}
+
+impl ConstraintSynthesizer<Fr> for CubicDemo {
+    fn generate_constraints<CS: ConstraintSystem<Fr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let x = AllocatedFr::alloc(cs.ns(|| "x"), || {
+            self.x.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        let mut cs = cs.ns(|| "cubic");
+        let square = AllocatedFr::alloc(cs.ns(|| "x^2"), || {
+            self.x
+                .map(|x| x.square())
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "x^2 = x * x",
+            |lc| lc + x.get_variable(),
+            |lc| lc + x.get_variable(),
+            |lc| lc + square.get_variable(),
+        );
+
+        let broken = self.broken;
+        let cube = AllocatedFr::alloc(cs.ns(|| "x^3"), || {
+            self.x
+                .map(|x| if broken { x.square() } else { x.square() * x })
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        cs.enforce(
+            || "x^3 = x^2 * x",
+            |lc| lc + square.get_variable(),
+            |lc| lc + x.get_variable(),
+            |lc| lc + cube.get_variable(),
+        );
+
+        let out = AllocatedFr::alloc_input(cs.ns(|| "out"), || {
+            match (cube.get_value(), x.get_value()) {
+                (Some(cube), Some(x)) => Ok(cube + x + Fr::from(5u32)),
+                _ => Err(SynthesisError::AssignmentMissing),
+            }
+        })?;
+        cs.enforce(
+            || "out = x^3 + x + 5",
+            |lc| lc + cube.get_variable() + x.get_variable() + (Fr::from(5u32), CS::one()),
+            |lc| lc + CS::one(),
+            |lc| lc + out.get_variable(),
+        );
+
+        Ok(())
+    }
+}
+
+fn synthesize(x: u32, broken: bool) -> TestConstraintSystem<Fr> {
+    let mut cs = TestConstraintSystem::<Fr>::new();
+    CubicDemo {
+        x: Some(Fr::from(x)),
+        broken,
+    }
+    .generate_constraints(&mut cs)
+    .unwrap();
+
+    cs
+}
+
+#[test]
+fn test_cubic() {
+    let cs = synthesize(3, false);
+
+    assert!(cs.is_satisfied());
+    assert_eq!(cs.which_is_unsatisfied(), None);
+    assert_eq!(cs.num_constraints(), 3);
+    assert_eq!(cs.num_inputs(), 2);
+    assert_eq!(cs.get("cubic/x^3/fr"), Fr::from(27u32));
+    assert_eq!(cs.get_input(1, "cubic/out/fr"), Fr::from(35u32));
+
+    assert!(cs.verify(&[Fr::from(35u32)]));
+    assert!(!cs.verify(&[Fr::from(36u32)]));
+    assert!(!cs.verify(&[]));
+}
+
+#[test]
+fn test_broken_cubic() {
+    let mut cs = synthesize(3, true);
+
+    // the public input is consistent with the broken cube, only the cube
+    // itself is wrong.
+    assert!(!cs.is_satisfied());
+    assert_eq!(
+        cs.which_is_unsatisfied().as_deref(),
+        Some("cubic/x^3 = x^2 * x")
+    );
+    assert!(!cs.verify(&[Fr::from(17u32)]));
+
+    // fixing the witness by its path finds the next broken constraint.
+    cs.set("cubic/x^3/fr", Fr::from(27u32));
+    assert_eq!(
+        cs.which_is_unsatisfied().as_deref(),
+        Some("cubic/out = x^3 + x + 5")
+    );
+
+    cs.set("cubic/out/fr", Fr::from(35u32));
+    assert!(cs.verify(&[Fr::from(35u32)]));
+}
+
+#[test]
+fn test_shape_of_cubic() {
+    use scheme::r1cs::shape_of;
+
+    let cs = synthesize(3, false);
+    let shape = shape_of(CubicDemo {
+        x: None,
+        broken: false,
+    })
+    .unwrap();
+
+    assert_eq!(shape.num_constraints, cs.num_constraints());
+    assert_eq!(shape.num_inputs, cs.num_inputs());
+    assert_eq!(shape.num_aux, cs.num_private_variables());
+}